@@ -0,0 +1,302 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Attribute, Data, DataStruct, DeriveInput, Expr, ExprLit, Field};
+use syn::{Ident, Index, Lit, Token, Type, TypePath};
+
+/// A single `name = value` argument of the `object` or `property` attribute.
+struct Argument {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: Expr = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
+
+/// Parse the arguments of an attribute.
+fn parse_arguments(attribute: &Attribute) -> Punctuated<Argument, Token![,]> {
+    attribute
+        .parse_args_with(Punctuated::<Argument, Token![,]>::parse_terminated)
+        .unwrap_or_else(|_| {
+            panic!(
+                "The `{}` attribute has to be a list of `name = value` pairs",
+                attribute.path.get_ident().unwrap()
+            )
+        })
+}
+
+/// Create the null-terminated byte string literal of a URI.
+fn make_uri(value: &Expr) -> Literal {
+    const PARSING_ERROR: &str = "A URI has to be a string literal";
+
+    let uri = match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(uri), ..
+        }) => uri.value(),
+        _ => panic!("{}", PARSING_ERROR),
+    };
+    if uri.contains('\0') {
+        panic!("Unexpected Null terminator");
+    }
+
+    let mut uri_vec: Vec<u8> = Vec::with_capacity(uri.len() + 1);
+    uri_vec.extend(uri.as_bytes());
+    uri_vec.push(0);
+    Literal::byte_string(uri_vec.as_ref())
+}
+
+/// Check whether a type is an `Option`.
+fn is_option(value_type: &Type) -> bool {
+    match value_type {
+        Type::Path(TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// A field in the struct we implement `AtomObject` for.
+struct PropertyField<'a> {
+    identifier: &'a Ident,
+    uri: Literal,
+    atom: Expr,
+    optional: bool,
+}
+
+impl<'a> PropertyField<'a> {
+    /// Create a `Self` instance from a field object.
+    ///
+    /// The field has to have a `property` attribute with the `uri` and the `atom` argument.
+    fn from_input_field(input: &'a Field) -> Self {
+        let identifier = input.ident.as_ref().unwrap();
+        let attribute = input
+            .attrs
+            .iter()
+            .find(|attribute| attribute.path.is_ident("property"))
+            .unwrap_or_else(|| panic!("Field `{}` has no `property` attribute", identifier));
+
+        let mut uri = None;
+        let mut atom = None;
+        for argument in parse_arguments(attribute) {
+            match argument.name.to_string().as_str() {
+                "uri" => uri = Some(make_uri(&argument.value)),
+                "atom" => atom = Some(argument.value),
+                name => panic!("Unknown property argument `{}`", name),
+            }
+        }
+
+        Self {
+            identifier,
+            uri: uri.unwrap_or_else(|| panic!("Property `{}` has no URI", identifier)),
+            atom: atom.unwrap_or_else(|| panic!("Property `{}` has no atom type", identifier)),
+            optional: is_option(&input.ty),
+        }
+    }
+
+    /// Create the mapping expression of the property key.
+    fn make_key_mapping(&self) -> impl quote::ToTokens {
+        let uri = &self.uri;
+        quote! {
+            map.map_uri(Uri::from_bytes_with_nul(#uri).ok()?)?,
+        }
+    }
+
+    /// Create the mapping expression of the atom type.
+    fn make_atom_mapping(&self) -> impl quote::ToTokens {
+        let atom = &self.atom;
+        quote! {
+            map.map_type::<#atom>()?,
+        }
+    }
+
+    /// Create the type of the atom URID.
+    fn make_atom_urid_type(&self) -> impl quote::ToTokens {
+        let atom = &self.atom;
+        quote! {
+            URID<#atom>,
+        }
+    }
+
+    /// Create the identifier of the variable that holds the value while reading.
+    ///
+    /// It's prefixed so that it doesn't shadow the other variables of the reading method.
+    fn make_variable(&self) -> Ident {
+        Ident::new(&format!("value_{}", self.identifier), Span::call_site())
+    }
+
+    /// Create the declaration of the variable that holds the value while reading.
+    fn make_declaration(&self) -> impl quote::ToTokens {
+        let variable = self.make_variable();
+        quote! {
+            let mut #variable = None;
+        }
+    }
+
+    /// Create the matching arm to read the value of the property.
+    fn make_reader(&self, index: usize) -> impl quote::ToTokens {
+        let variable = self.make_variable();
+        let atom_index = Index::from(index);
+        quote! {
+            if header.key == (urids.1)[#index] {
+                #variable = Some(atom.read((urids.2).#atom_index, ())?);
+                continue;
+            }
+        }
+    }
+
+    /// Create the field initialization with the read value.
+    ///
+    /// Required properties have to be present, optional properties are `None` if they are missing.
+    fn make_initialization(&self) -> impl quote::ToTokens {
+        let identifier = self.identifier;
+        let variable = self.make_variable();
+        if self.optional {
+            quote! {
+                #identifier: #variable,
+            }
+        } else {
+            quote! {
+                #identifier: #variable?,
+            }
+        }
+    }
+
+    /// Create the statement to write the property.
+    ///
+    /// Optional properties are only written if they have a value.
+    fn make_writer(&self, index: usize) -> impl quote::ToTokens {
+        let identifier = self.identifier;
+        let atom_index = Index::from(index);
+        if self.optional {
+            quote! {
+                if let Some(value) = self.#identifier {
+                    writer.init((urids.1)[#index], None, (urids.2).#atom_index, value)?;
+                }
+            }
+        } else {
+            quote! {
+                writer.init((urids.1)[#index], None, (urids.2).#atom_index, self.#identifier)?;
+            }
+        }
+    }
+}
+
+/// Representation of a struct we implement `AtomObject` for.
+struct AtomObjectStruct<'a> {
+    struct_name: &'a Ident,
+    uri: Literal,
+    fields: Vec<PropertyField<'a>>,
+}
+
+impl<'a> AtomObjectStruct<'a> {
+    /// Construct a `Self` instance from a `DeriveInput`.
+    ///
+    /// The struct has to have an `object` attribute with the `uri` argument.
+    fn from_derive_input(input: &'a DeriveInput) -> Self {
+        let struct_name = &input.ident;
+        let fields = match &input.data {
+            Data::Struct(DataStruct { fields, .. }) => fields,
+            _ => panic!("Only structs can implement `AtomObject`"),
+        };
+
+        let attribute = input
+            .attrs
+            .iter()
+            .find(|attribute| attribute.path.is_ident("object"))
+            .unwrap_or_else(|| panic!("Struct `{}` has no `object` attribute", struct_name));
+        let mut uri = None;
+        for argument in parse_arguments(attribute) {
+            match argument.name.to_string().as_str() {
+                "uri" => uri = Some(make_uri(&argument.value)),
+                name => panic!("Unknown object argument `{}`", name),
+            }
+        }
+
+        Self {
+            struct_name,
+            uri: uri.unwrap_or_else(|| panic!("Object `{}` has no URI", struct_name)),
+            fields: fields.iter().map(PropertyField::from_input_field).collect(),
+        }
+    }
+
+    /// Implement `AtomObject` for the struct.
+    fn make_implementation(&self) -> TokenStream {
+        let struct_name = self.struct_name;
+        let uri = &self.uri;
+        let count = self.fields.len();
+        let atom_urid_types = self.fields.iter().map(PropertyField::make_atom_urid_type);
+        let key_mappings = self.fields.iter().map(PropertyField::make_key_mapping);
+        let atom_mappings = self.fields.iter().map(PropertyField::make_atom_mapping);
+        let declarations = self.fields.iter().map(PropertyField::make_declaration);
+        let readers = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field.make_reader(index));
+        let initializations = self.fields.iter().map(PropertyField::make_initialization);
+        let writers = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field.make_writer(index));
+
+        (quote! {
+            impl AtomObject for #struct_name {
+                type URIDs = (URID, [URID; #count], (#(#atom_urid_types)*));
+
+                fn map_urids<M: Map + ?Sized>(map: &M) -> Option<Self::URIDs> {
+                    Some((
+                        map.map_uri(Uri::from_bytes_with_nul(#uri).ok()?)?,
+                        [#(#key_mappings)*],
+                        (#(#atom_mappings)*),
+                    ))
+                }
+
+                fn header(urids: &Self::URIDs) -> ObjectHeader {
+                    ObjectHeader {
+                        id: None,
+                        otype: urids.0,
+                    }
+                }
+
+                #[allow(unused_variables)]
+                fn read_properties(reader: ObjectReader, urids: &Self::URIDs) -> Option<Self> {
+                    #(#declarations)*
+                    for (header, atom) in reader {
+                        #(#readers)*
+                    }
+                    Some(Self {
+                        #(#initializations)*
+                    })
+                }
+
+                #[allow(unused_variables)]
+                fn write_properties(
+                    &self,
+                    writer: &mut ObjectWriter,
+                    urids: &Self::URIDs,
+                ) -> Option<()> {
+                    #(#writers)*
+                    Some(())
+                }
+            }
+        })
+        .into()
+    }
+}
+
+/// Implement `AtomObject` for a struct.
+pub fn atom_object_derive_impl(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    AtomObjectStruct::from_derive_input(&input).make_implementation()
+}
@@ -0,0 +1,162 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span};
+use quote::quote;
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Fields, Ident, Index, Lit, Meta};
+use syn::{MetaNameValue, NestedMeta, Type};
+
+/// Read the URI from the `event` attribute of the struct, if it has one.
+fn parse_uri(input: &DeriveInput) -> Option<Literal> {
+    let attribute = input
+        .attrs
+        .iter()
+        .find(|attribute| attribute.path.is_ident("event"))?;
+
+    let list = match attribute.parse_meta() {
+        Ok(Meta::List(list)) => list,
+        _ => panic!("The `event` attribute has to be a list of `name = value` pairs"),
+    };
+    let mut uri = None;
+    for argument in list.nested {
+        match argument {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(value),
+                ..
+            })) if path.is_ident("uri") => {
+                let value = value.value();
+                if value.contains('\0') {
+                    panic!("Unexpected Null terminator");
+                }
+                uri = Some(Literal::byte_string(format!("{}\0", value).as_bytes()));
+            }
+            _ => panic!("The `event` attribute only accepts a `uri` string"),
+        }
+    }
+    Some(uri.unwrap_or_else(|| panic!("Event `{}` has no URI", input.ident)))
+}
+
+/// A field of the event body.
+struct BodyField<'a> {
+    member: proc_macro2::TokenStream,
+    variable: Ident,
+    field_type: &'a Type,
+}
+
+impl<'a> BodyField<'a> {
+    /// Create the expression of the field's size.
+    fn make_size(&self) -> impl quote::ToTokens {
+        let field_type = self.field_type;
+        quote! {
+            <#field_type as EventField>::SIZE
+        }
+    }
+
+    /// Create the statement that reads the field and advances the data.
+    fn make_reader(&self) -> impl quote::ToTokens {
+        let variable = &self.variable;
+        let field_type = self.field_type;
+        let size = self.make_size();
+        quote! {
+            let (#variable, data) = data.split_at(#size);
+            let #variable = <#field_type as EventField>::read_field(#variable)?;
+        }
+    }
+
+    /// Create the field initialization with the read value.
+    fn make_initialization(&self) -> impl quote::ToTokens {
+        let member = &self.member;
+        let variable = &self.variable;
+        quote! {
+            #member: #variable,
+        }
+    }
+
+    /// Create the statement that writes the field and advances the data.
+    fn make_writer(&self) -> impl quote::ToTokens {
+        let member = &self.member;
+        let variable = &self.variable;
+        let size = self.make_size();
+        quote! {
+            let (#variable, data) = data.split_at_mut(#size);
+            EventField::write_field(&self.#member, #variable);
+        }
+    }
+}
+
+/// Implement `EventBody` and `Atom` for a struct.
+pub fn event_body_derive_impl(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    let struct_name = &input.ident;
+    if !input.generics.params.is_empty() {
+        panic!("Event `{}` may not be generic", struct_name);
+    }
+    let fields = match &input.data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        _ => panic!("Only structs can implement `EventBody`"),
+    };
+
+    let fields: Vec<BodyField> = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| {
+                let identifier = field.ident.as_ref().unwrap();
+                BodyField {
+                    member: quote! {#identifier},
+                    variable: Ident::new(&format!("field_{}", identifier), Span::call_site()),
+                    field_type: &field.ty,
+                }
+            })
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let index = Index::from(index);
+                BodyField {
+                    member: quote! {#index},
+                    variable: Ident::new(&format!("field_{}", index.index), Span::call_site()),
+                    field_type: &field.ty,
+                }
+            })
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let uri_bound = parse_uri(&input).map(|uri| {
+        quote! {
+            unsafe impl UriBound for #struct_name {
+                const URI: &'static [u8] = #uri;
+            }
+        }
+    });
+    let sizes = fields.iter().map(BodyField::make_size);
+    let readers = fields.iter().map(BodyField::make_reader);
+    let initializations = fields.iter().map(BodyField::make_initialization);
+    let writers = fields.iter().map(BodyField::make_writer);
+
+    (quote! {
+        #uri_bound
+
+        impl EventBody for #struct_name {
+            const SIZE: usize = 0 #(+ #sizes)*;
+
+            #[allow(unused_variables)]
+            fn read_body(data: &[u8]) -> Option<Self> {
+                #(#readers)*
+                Some(Self {
+                    #(#initializations)*
+                })
+            }
+
+            #[allow(unused_variables)]
+            fn write_body(&self, data: &mut [u8]) {
+                #(#writers)*
+            }
+        }
+
+        event_atom!(#struct_name);
+    })
+    .into()
+}
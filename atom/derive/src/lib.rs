@@ -0,0 +1,19 @@
+//! Procedural macros for `lv2-atom`.
+#![recursion_limit = "128"]
+
+mod atom_object_derive;
+mod event_body_derive;
+
+use proc_macro::TokenStream;
+
+/// Implement the `AtomObject` trait for a struct whose fields are the properties of an object.
+#[proc_macro_derive(AtomObject, attributes(object, property))]
+pub fn atom_object_derive(input: TokenStream) -> TokenStream {
+    atom_object_derive::atom_object_derive_impl(input)
+}
+
+/// Implement the `EventBody` and `Atom` traits for a struct whose fields are the body of a custom event.
+#[proc_macro_derive(EventBody, attributes(event))]
+pub fn event_body_derive(input: TokenStream) -> TokenStream {
+    event_body_derive::event_body_derive_impl(input)
+}
@@ -0,0 +1,10 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use lv2_atom::prelude::Float;
+use lv2_test::AtomFuzzer;
+
+fuzz_target!(|body: &[u8]| {
+    let fuzzer = AtomFuzzer::new();
+    let urids = fuzzer.urids().clone();
+    fuzzer.check_body(urids.matrix::<Float>(), body);
+});
@@ -0,0 +1,470 @@
+//! Streaming of large binary blobs across several cycles.
+//!
+//! The atom ports of a plugin have a fixed capacity, which is set by the host and is usually only a few kilobytes. Large data, like a sound font, a waveform overview or an impulse response the UI should display, can't be sent in a single event. Instead, a [`BlobSender`](struct.BlobSender.html) splits the blob into [fragments](struct.Fragment.html) and writes as many of them as fit into the output sequence of every cycle, until the blob is complete. On the other side, a [`BlobReceiver`](struct.BlobReceiver.html) reassembles the fragments.
+//!
+//! Every fragment is an atom with a [`FragmentHeader`](struct.FragmentHeader.html), which contains the id of the stream, the sequence number of the fragment, its offset in the blob and the total size of the blob, followed by the data of the fragment. Since hosts deliver events in order, the receiver rejects fragments that don't continue the current stream, for example because an event was dropped, and waits for the first fragment of the next stream.
+//!
+//! Fragments are written with the type URID of [`Fragment`](struct.Fragment.html), which has to be mapped by both sides, like any other atom type. The sender doesn't allocate memory and can be used in the `run()` context. The receiver, which needs the `alloc` feature, allocates the blob when the first fragment arrives and is supposed to be used outside of it, like in a UI.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::blob::*;
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::space::*;
+//! use lv2_units::prelude::*;
+//! use urid::*;
+//!
+//! let map = HashURIDMapper::new();
+//! let urids: AtomURIDCollection = map.populate_collection().unwrap();
+//! let frame: URID<Frame> = map.map_type().unwrap();
+//! let fragment: URID<Fragment> = map.map_type().unwrap();
+//!
+//! let blob: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+//! let mut sender = BlobSender::new(&blob[..], 1).unwrap();
+//! let mut receiver = BlobReceiver::new(1 << 20);
+//! let mut received = None;
+//!
+//! // Every cycle, the plugin writes as many fragments as fit into its small output sequence...
+//! while !sender.is_finished() {
+//!     let mut buffer = vec![0u64; 32];
+//!     let bytes = unsafe {
+//!         std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8)
+//!     };
+//!     {
+//!         let mut space = RootMutSpace::new(bytes);
+//!         let mut sequence = (&mut space as &mut dyn MutSpace)
+//!             .init(urids.sequence, TimeStampURID::Frames(frame))
+//!             .unwrap();
+//!         assert!(sender.send(&mut sequence, FrameTime(0), fragment) > 0);
+//!     }
+//!
+//!     // ...and the UI reassembles them.
+//!     let sequence = UnidentifiedAtom::new(Space::from_slice(bytes))
+//!         .read(urids.sequence, map.map_type().unwrap())
+//!         .unwrap();
+//!     for (_, atom) in sequence {
+//!         let (header, data) = atom.read(fragment, ()).unwrap();
+//!         if let Some(blob) = receiver.receive(header, data).unwrap() {
+//!             received = Some(blob);
+//!         }
+//!     }
+//! }
+//! assert_eq!(received.unwrap(), blob);
+//! ```
+use crate::sequence::{SequenceWriter, TimeStamp};
+use crate::space::*;
+use crate::Atom;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::mem::size_of;
+use urid::{UriBound, URID};
+
+/// An atom with a fragment of a blob.
+///
+/// The body of the atom is a [`FragmentHeader`](struct.FragmentHeader.html), followed by the data of the fragment. Reading a fragment returns both, and the header is checked for consistency with the data.
+pub struct Fragment;
+
+unsafe impl UriBound for Fragment {
+    const URI: &'static [u8] = b"urn:lv2_atom:blob:Fragment\0";
+}
+
+/// The header of a blob fragment.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FragmentHeader {
+    /// The id of the stream, which identifies the blob.
+    pub stream: u32,
+    /// The sequence number of the fragment, which starts at zero for every stream.
+    pub index: u32,
+    /// The position of the fragment's data in the blob.
+    pub offset: u32,
+    /// The size of the complete blob.
+    pub total_size: u32,
+}
+
+impl<'a, 'b> Atom<'a, 'b> for Fragment
+where
+    'a: 'b,
+{
+    type ReadParameter = ();
+    type ReadHandle = (FragmentHeader, &'a [u8]);
+    type WriteParameter = FragmentHeader;
+    type WriteHandle = FramedMutSpace<'a, 'b>;
+
+    fn read(body: Space<'a>, _: ()) -> Option<(FragmentHeader, &'a [u8])> {
+        let (header, body) = body.read_type::<FragmentHeader>()?;
+        let data = body.data().unwrap_or(&[]);
+        let end = (header.offset as usize).checked_add(data.len())?;
+        if end > header.total_size as usize {
+            return None;
+        }
+        Some((header, data))
+    }
+
+    fn init(
+        mut frame: FramedMutSpace<'a, 'b>,
+        header: FragmentHeader,
+    ) -> Option<FramedMutSpace<'a, 'b>> {
+        (&mut frame as &mut dyn MutSpace).write(&header, true)?;
+        Some(frame)
+    }
+}
+
+/// The number of bytes a fragment occupies in a sequence besides its data: The padding of the previous event, the time stamp, the atom header and the fragment header.
+const FRAGMENT_OVERHEAD: usize = 7 + 8 + size_of::<sys::LV2_Atom>() + size_of::<FragmentHeader>();
+
+/// Sends a blob in fragments, across as many cycles as needed.
+///
+/// The sender owns or borrows the blob, for example as a `Box<[u8]>` or an `Arc<[u8]>` that was created by the worker, and keeps track of the part that has been sent already. Every call to [`send`](#method.send) writes the next fragments to an output sequence, until it's full or the blob is complete.
+pub struct BlobSender<B> {
+    blob: B,
+    stream: u32,
+    index: u32,
+    offset: usize,
+    fragment_size: usize,
+}
+
+impl<B: AsRef<[u8]>> BlobSender<B> {
+    /// Create a sender for the blob, which is identified by the given stream id.
+    ///
+    /// Fragments have at most 4096 bytes of data by default. Since the size of the blob is stored in 32 bits, `None` is returned if the blob is bigger than 4 GiB.
+    pub fn new(blob: B, stream: u32) -> Option<Self> {
+        if blob.as_ref().len() > u32::MAX as usize {
+            return None;
+        }
+        Some(Self {
+            blob,
+            stream,
+            index: 0,
+            offset: 0,
+            fragment_size: 4096,
+        })
+    }
+
+    /// Set the maximal number of data bytes per fragment.
+    ///
+    /// Smaller fragments leave room for other events in the sequence, and bigger ones have less overhead.
+    pub fn fragment_size(mut self, size: usize) -> Self {
+        self.fragment_size = size.max(1);
+        self
+    }
+
+    /// The id of the stream.
+    pub fn stream(&self) -> u32 {
+        self.stream
+    }
+
+    /// The number of bytes that have been sent already, and the size of the blob.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.offset, self.blob.as_ref().len())
+    }
+
+    /// Check whether the complete blob has been sent.
+    pub fn is_finished(&self) -> bool {
+        self.index > 0 && self.offset == self.blob.as_ref().len()
+    }
+
+    /// Return the blob.
+    pub fn into_inner(self) -> B {
+        self.blob
+    }
+
+    /// Write the next fragments of the blob to the sequence, and return how many were written.
+    ///
+    /// All fragments are written with the given time stamp. Fragments are written until the blob is complete or the sequence has no room for another fragment with at least one byte of data, which means that the remaining ones are sent in the next cycle. If the time stamp is rejected by the sequence, no fragment is written.
+    pub fn send(
+        &mut self,
+        sequence: &mut SequenceWriter,
+        stamp: impl Into<TimeStamp>,
+        urid: URID<Fragment>,
+    ) -> usize {
+        let stamp = stamp.into();
+        let blob = self.blob.as_ref();
+        let mut written = 0;
+        while !self.is_finished() {
+            let mut size = self.fragment_size.min(blob.len() - self.offset);
+            if let Some(remaining) = sequence.remaining() {
+                let available = remaining.saturating_sub(FRAGMENT_OVERHEAD);
+                // An empty fragment is only written for an empty blob.
+                if available == 0 && size > 0 || remaining < FRAGMENT_OVERHEAD {
+                    break;
+                }
+                size = size.min(available);
+            }
+            let header = FragmentHeader {
+                stream: self.stream,
+                index: self.index,
+                offset: self.offset as u32,
+                total_size: blob.len() as u32,
+            };
+            let data = &blob[self.offset..self.offset + size];
+            let complete = sequence
+                .init(stamp, urid, header)
+                .and_then(|mut frame| (&mut frame as &mut dyn MutSpace).write_raw(data, false))
+                .is_some();
+            if !complete {
+                break;
+            }
+            self.index += 1;
+            self.offset += size;
+            written += 1;
+        }
+        written
+    }
+}
+
+/// Errors of a [`BlobReceiver`](struct.BlobReceiver.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlobError {
+    /// The fragment doesn't continue the current stream, for example because a fragment was lost.
+    ///
+    /// The incomplete blob is discarded and the receiver waits for the first fragment of a stream.
+    OutOfOrder,
+    /// The blob is bigger than the maximal size of the receiver.
+    TooLarge,
+}
+
+/// Reassembles the fragments of blobs.
+///
+/// The receiver handles one stream at a time: The first fragment of a stream starts a new blob, even if the previous one is incomplete, and the following fragments have to continue it. Once the blob is complete, it's returned by [`receive`](#method.receive).
+#[cfg(feature = "alloc")]
+pub struct BlobReceiver {
+    max_size: usize,
+    blob: Vec<u8>,
+    current: Option<FragmentHeader>,
+}
+
+#[cfg(feature = "alloc")]
+impl BlobReceiver {
+    /// Create a receiver for blobs with up to `max_size` bytes.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            blob: Vec::new(),
+            current: None,
+        }
+    }
+
+    /// The id of the stream that is currently received, if any.
+    pub fn stream(&self) -> Option<u32> {
+        self.current.map(|header| header.stream)
+    }
+
+    /// The number of bytes that have been received of the current blob, and its size.
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        self.current
+            .map(|header| (self.blob.len(), header.total_size as usize))
+    }
+
+    /// Add a fragment, as it's read from a [`Fragment`](struct.Fragment.html) atom.
+    ///
+    /// If the fragment completes a blob, the blob is returned.
+    pub fn receive(
+        &mut self,
+        header: FragmentHeader,
+        data: &[u8],
+    ) -> Result<Option<Vec<u8>>, BlobError> {
+        if header.index == 0 {
+            if header.total_size as usize > self.max_size {
+                self.current = None;
+                return Err(BlobError::TooLarge);
+            }
+            self.blob = Vec::with_capacity(header.total_size as usize);
+            self.current = Some(FragmentHeader {
+                index: 0,
+                offset: 0,
+                ..header
+            });
+        }
+
+        let current = match self.current {
+            Some(current) => current,
+            None => return Err(BlobError::OutOfOrder),
+        };
+        if header.stream != current.stream
+            || header.index != current.index
+            || header.offset as usize != self.blob.len()
+            || header.total_size != current.total_size
+            || self.blob.len() + data.len() > current.total_size as usize
+        {
+            self.current = None;
+            self.blob = Vec::new();
+            return Err(BlobError::OutOfOrder);
+        }
+
+        self.blob.extend_from_slice(data);
+        if self.blob.len() == current.total_size as usize {
+            self.current = None;
+            Ok(Some(core::mem::take(&mut self.blob)))
+        } else {
+            self.current = Some(FragmentHeader {
+                index: current.index + 1,
+                ..current
+            });
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blob::*;
+    use crate::prelude::*;
+    use units::prelude::*;
+    use urid::*;
+
+    /// Run the sender for one cycle with a sequence of the given capacity and return the written fragments.
+    fn cycle<B: AsRef<[u8]>>(
+        sender: &mut BlobSender<B>,
+        capacity: usize,
+        map: &HashURIDMapper,
+    ) -> Vec<(FragmentHeader, Vec<u8>)> {
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let frame: URID<Frame> = map.map_type().unwrap();
+        let fragment: URID<Fragment> = map.map_type().unwrap();
+
+        let mut buffer = vec![0u64; capacity / 8];
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8)
+        };
+        {
+            let mut space = RootMutSpace::new(bytes);
+            let mut sequence = (&mut space as &mut dyn MutSpace)
+                .init(urids.sequence, TimeStampURID::Frames(frame))
+                .unwrap();
+            sender.send(&mut sequence, FrameTime(4), fragment);
+        }
+        UnidentifiedAtom::new(Space::from_slice(bytes))
+            .read(urids.sequence, map.map_type().unwrap())
+            .unwrap()
+            .map(|(stamp, atom)| {
+                assert_eq!(stamp.as_frames(), Some(4));
+                let (header, data) = atom.read(fragment, ()).unwrap();
+                (header, data.to_vec())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_blob_transfer() {
+        let map = HashURIDMapper::new();
+        let blob: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let mut sender = BlobSender::new(blob.as_slice(), 7)
+            .unwrap()
+            .fragment_size(100);
+        let mut receiver = BlobReceiver::new(1000);
+
+        let mut cycles = 0;
+        let mut received = None;
+        while !sender.is_finished() {
+            let fragments = cycle(&mut sender, 256, &map);
+            assert!(!fragments.is_empty());
+            for (header, data) in fragments {
+                assert_eq!(header.stream, 7);
+                assert!(data.len() <= 100);
+                if let Some(blob) = receiver.receive(header, &data).unwrap() {
+                    received = Some(blob);
+                }
+            }
+            cycles += 1;
+        }
+        assert!(cycles > 1);
+        assert_eq!(sender.progress(), (1000, 1000));
+        assert_eq!(received.unwrap(), blob);
+        assert_eq!(receiver.stream(), None);
+
+        // Nothing is written once the blob is complete.
+        assert!(cycle(&mut sender, 256, &map).is_empty());
+    }
+
+    #[test]
+    fn test_empty_blob() {
+        let map = HashURIDMapper::new();
+        let mut sender = BlobSender::new([0u8; 0], 1).unwrap();
+        let fragments = cycle(&mut sender, 256, &map);
+        assert_eq!(fragments.len(), 1);
+        assert!(sender.is_finished());
+
+        let mut receiver = BlobReceiver::new(0);
+        let (header, data) = &fragments[0];
+        assert_eq!(receiver.receive(*header, data), Ok(Some(Vec::new())));
+    }
+
+    #[test]
+    fn test_receiver_errors() {
+        let header = FragmentHeader {
+            stream: 1,
+            index: 0,
+            offset: 0,
+            total_size: 8,
+        };
+        let mut receiver = BlobReceiver::new(8);
+
+        // A lost fragment discards the blob.
+        assert_eq!(receiver.receive(header, &[0; 4]), Ok(None));
+        assert_eq!(receiver.progress(), Some((4, 8)));
+        let lost = FragmentHeader {
+            index: 2,
+            offset: 6,
+            ..header
+        };
+        assert_eq!(receiver.receive(lost, &[0; 2]), Err(BlobError::OutOfOrder));
+        let next = FragmentHeader {
+            index: 1,
+            offset: 4,
+            ..header
+        };
+        assert_eq!(receiver.receive(next, &[0; 4]), Err(BlobError::OutOfOrder));
+
+        // A new stream starts over, even if the previous one is incomplete.
+        assert_eq!(receiver.receive(header, &[1; 4]), Ok(None));
+        let other = FragmentHeader {
+            stream: 2,
+            ..header
+        };
+        assert_eq!(receiver.receive(other, &[2; 4]), Ok(None));
+        let next = FragmentHeader { stream: 2, ..next };
+        assert_eq!(
+            receiver.receive(next, &[3; 4]),
+            Ok(Some(vec![2, 2, 2, 2, 3, 3, 3, 3]))
+        );
+
+        let large = FragmentHeader {
+            total_size: 9,
+            ..header
+        };
+        assert_eq!(receiver.receive(large, &[0; 4]), Err(BlobError::TooLarge));
+    }
+
+    #[test]
+    fn test_malformed_fragment() {
+        let map = HashURIDMapper::new();
+        let fragment: URID<Fragment> = map.map_type().unwrap();
+        let mut buffer = vec![0u64; 8];
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8)
+        };
+        {
+            let mut space = RootMutSpace::new(bytes);
+            let mut frame = (&mut space as &mut dyn MutSpace)
+                .init(
+                    fragment,
+                    FragmentHeader {
+                        stream: 0,
+                        index: 0,
+                        offset: 2,
+                        total_size: 4,
+                    },
+                )
+                .unwrap();
+            (&mut frame as &mut dyn MutSpace)
+                .write_raw(&[1, 2, 3], false)
+                .unwrap();
+        }
+        // The data exceeds the size of the blob.
+        assert!(UnidentifiedAtom::new(Space::from_slice(bytes))
+            .read(fragment, ())
+            .is_none());
+    }
+}
@@ -59,7 +59,7 @@ where
 mod tests {
     use crate::chunk::*;
     use crate::*;
-    use std::mem::size_of;
+    use core::mem::size_of;
     use urid::*;
 
     #[test]
@@ -0,0 +1,292 @@
+//! Custom event types with fixed-size bodies.
+//!
+//! Many plugins exchange events that don't fit any of the standard atom types, like the note events of a vendor-specific protocol between a plugin and its UI. Such an event is a Rust struct, which is bound to a URI and whose body is the concatenation of its fields. With the [`EventBody`](trait.EventBody.html) trait and the [`event_atom`](../macro.event_atom.html) macro, the struct becomes an atom type: It's read from and written to sequences like a MIDI event or any other atom, and its type URID is mapped like the URID of every other atom type.
+//!
+//! The `EventBody` trait is usually implemented with the `EventBody` derive macro, which reads and writes the fields in the order of their declaration and also implements the `Atom` trait. Every field has to implement [`EventField`](trait.EventField.html), which is the case for the integer and floating-point types, `bool`, URIDs and byte arrays. The URI of the event is either set with the `event` attribute or with a manual implementation of `UriBound`. The generated code uses the items of the prelude and of `urid`, which therefore have to be imported.
+//!
+//! Fields are stored with the native byte order and without padding, since events never leave the machine they were written on. Bodies that are longer than expected are accepted, which allows new versions of a protocol to append fields to an event.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::space::*;
+//! use lv2_units::prelude::*;
+//! use urid::*;
+//!
+//! #[derive(EventBody, Clone, Copy, Debug, PartialEq)]
+//! #[event(uri = "urn:event-example:Grain")]
+//! struct Grain {
+//!     voice: u8,
+//!     position: f32,
+//!     length: u32,
+//! }
+//!
+//! let map = HashURIDMapper::new();
+//! let urids: AtomURIDCollection = map.populate_collection().unwrap();
+//! let frame: URID<Frame> = map.map_type().unwrap();
+//! let grain: URID<Grain> = map.map_type().unwrap();
+//!
+//! let event = Grain { voice: 3, position: 0.25, length: 4800 };
+//!
+//! // Write the event to a sequence...
+//! let mut buffer = vec![0u64; 32];
+//! let bytes = unsafe {
+//!     std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8)
+//! };
+//! {
+//!     let mut space = RootMutSpace::new(bytes);
+//!     let mut sequence = (&mut space as &mut dyn MutSpace)
+//!         .init(urids.sequence, TimeStampURID::Frames(frame))
+//!         .unwrap();
+//!     sequence.init(FrameTime(16), grain, event).unwrap();
+//! }
+//!
+//! // ...and read it back.
+//! let mut sequence = UnidentifiedAtom::new(Space::from_slice(bytes))
+//!     .read(urids.sequence, map.map_type().unwrap())
+//!     .unwrap();
+//! let (stamp, atom) = sequence.next().unwrap();
+//! assert_eq!(stamp.as_frames(), Some(16));
+//! assert_eq!(atom.read(grain, ()), Some(event));
+//! ```
+use crate::space::*;
+use core::num::NonZeroU32;
+use urid::{UriBound, URID};
+
+pub use lv2_atom_derive::EventBody;
+
+/// A field of an [`EventBody`](trait.EventBody.html), with a fixed size.
+pub trait EventField: Sized {
+    /// The size of the field in bytes.
+    const SIZE: usize;
+
+    /// Read the field.
+    ///
+    /// The slice has exactly `SIZE` bytes. If they don't contain a valid value, `None` is returned.
+    fn read_field(data: &[u8]) -> Option<Self>;
+
+    /// Write the field.
+    ///
+    /// The slice has exactly `SIZE` bytes.
+    fn write_field(&self, data: &mut [u8]);
+}
+
+macro_rules! impl_event_field {
+    ($($type:ty),*) => {
+        $(
+            impl EventField for $type {
+                const SIZE: usize = core::mem::size_of::<$type>();
+
+                fn read_field(data: &[u8]) -> Option<Self> {
+                    let mut bytes = [0; core::mem::size_of::<$type>()];
+                    bytes.copy_from_slice(data);
+                    Some(<$type>::from_ne_bytes(bytes))
+                }
+
+                fn write_field(&self, data: &mut [u8]) {
+                    data.copy_from_slice(&self.to_ne_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_event_field!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl EventField for bool {
+    const SIZE: usize = 1;
+
+    fn read_field(data: &[u8]) -> Option<Self> {
+        match data[0] {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn write_field(&self, data: &mut [u8]) {
+        data[0] = *self as u8;
+    }
+}
+
+impl<T: ?Sized> EventField for URID<T> {
+    const SIZE: usize = 4;
+
+    fn read_field(data: &[u8]) -> Option<Self> {
+        let raw = NonZeroU32::new(u32::read_field(data)?)?;
+        Some(unsafe { URID::new_unchecked(raw.get()) })
+    }
+
+    fn write_field(&self, data: &mut [u8]) {
+        self.get().write_field(data);
+    }
+}
+
+impl<const N: usize> EventField for [u8; N] {
+    const SIZE: usize = N;
+
+    fn read_field(data: &[u8]) -> Option<Self> {
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(data);
+        Some(bytes)
+    }
+
+    fn write_field(&self, data: &mut [u8]) {
+        data.copy_from_slice(self);
+    }
+}
+
+/// The body of a custom event type.
+///
+/// Types that implement this trait and `UriBound` are turned into atoms with the [`event_atom`](../macro.event_atom.html) macro. Usually, all of this is done by the `EventBody` derive macro, as described in the [module documentation](index.html).
+pub trait EventBody: UriBound + Sized {
+    /// The size of the body in bytes.
+    const SIZE: usize;
+
+    /// Read the body.
+    ///
+    /// The slice has exactly `SIZE` bytes. If the event is malformed, `None` is returned.
+    fn read_body(data: &[u8]) -> Option<Self>;
+
+    /// Write the body.
+    ///
+    /// The slice has exactly `SIZE` bytes.
+    fn write_body(&self, data: &mut [u8]);
+}
+
+/// Read an event from the body of its atom.
+///
+/// This is the `read` method of the atoms created by [`event_atom`](../macro.event_atom.html). Trailing bytes after the event are ignored.
+pub fn read_event<T: EventBody>(body: Space) -> Option<T> {
+    let data = body.data()?;
+    if data.len() < T::SIZE {
+        return None;
+    }
+    T::read_body(&data[..T::SIZE])
+}
+
+/// Write an event to the frame of its atom.
+///
+/// This is the `init` method of the atoms created by [`event_atom`](../macro.event_atom.html).
+pub fn write_event<T: EventBody>(mut frame: FramedMutSpace, event: &T) -> Option<()> {
+    let (_, data) = frame.allocate(T::SIZE, false)?;
+    event.write_body(data);
+    Some(())
+}
+
+/// Implement the `Atom` trait for a custom event type.
+///
+/// The type has to implement [`EventBody`](event/trait.EventBody.html). Its events are read by value, without a parameter, and they are written by passing the event as the parameter. The `EventBody` derive macro already invokes this macro.
+#[macro_export]
+macro_rules! event_atom {
+    ($event:ty) => {
+        impl<'a, 'b> $crate::Atom<'a, 'b> for $event
+        where
+            'a: 'b,
+        {
+            type ReadParameter = ();
+            type ReadHandle = $event;
+            type WriteParameter = $event;
+            type WriteHandle = ();
+
+            fn read(body: $crate::space::Space<'a>, _: ()) -> Option<$event> {
+                $crate::event::read_event(body)
+            }
+
+            fn init(frame: $crate::space::FramedMutSpace<'a, 'b>, event: $event) -> Option<()> {
+                $crate::event::write_event(frame, &event)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::space::*;
+    use units::prelude::*;
+    use urid::*;
+
+    #[derive(EventBody, Clone, Copy, Debug, PartialEq)]
+    #[event(uri = "urn:lv2_atom:test:Note")]
+    struct Note {
+        on: bool,
+        key: u8,
+        velocity: f32,
+        voice: i64,
+        tag: [u8; 3],
+    }
+
+    #[derive(EventBody, Clone, Copy, Debug, PartialEq)]
+    struct Control(URID, f64);
+
+    unsafe impl UriBound for Control {
+        const URI: &'static [u8] = b"urn:lv2_atom:test:Control\0";
+    }
+
+    #[test]
+    fn test_event_size() {
+        assert_eq!(<Note as EventBody>::SIZE, 1 + 1 + 4 + 8 + 3);
+        assert_eq!(<Control as EventBody>::SIZE, 4 + 8);
+    }
+
+    #[test]
+    fn test_events_in_sequence() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let frame: URID<Frame> = map.map_type().unwrap();
+        let note_urid: URID<Note> = map.map_type().unwrap();
+        let control_urid: URID<Control> = map.map_type().unwrap();
+
+        let note = Note {
+            on: true,
+            key: 60,
+            velocity: 0.75,
+            voice: -3,
+            tag: *b"abc",
+        };
+        let control = Control(urids.float.into_general(), 42.5);
+
+        let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+        {
+            let mut space = RootMutSpace::new(raw_space.as_mut());
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.sequence, TimeStampURID::Frames(frame))
+                .unwrap();
+            writer.init(FrameTime(0), note_urid, note).unwrap();
+            writer.init(FrameTime(8), control_urid, control).unwrap();
+        }
+
+        let mut reader = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()))
+            .read(urids.sequence, map.map_type().unwrap())
+            .unwrap();
+        let (stamp, atom) = reader.next().unwrap();
+        assert_eq!(stamp.as_frames(), Some(0));
+        assert_eq!(atom.read(note_urid, ()), Some(note));
+        assert_eq!(atom.read(control_urid, ()), None);
+        let (stamp, atom) = reader.next().unwrap();
+        assert_eq!(stamp.as_frames(), Some(8));
+        assert_eq!(atom.read(control_urid, ()), Some(control));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_malformed_events() {
+        let mut data = [0u8; 17];
+        data[0] = 1;
+        assert!(Note::read_body(&data).is_some());
+        data[0] = 2;
+        assert!(Note::read_body(&data).is_none());
+
+        // A short body is rejected, while trailing bytes are ignored.
+        let space = Space::from_slice(&data[..11]);
+        assert!(crate::event::read_event::<Control>(space).is_none());
+        let data = [1u8; 16];
+        let control: Control = crate::event::read_event(Space::from_slice(&data)).unwrap();
+        assert_eq!(control.0.get(), 0x0101_0101);
+
+        // A URID may not be zero.
+        assert!(crate::event::read_event::<Control>(Space::from_slice(&[0u8; 12])).is_none());
+    }
+}
@@ -61,11 +61,35 @@
 //! # Internals
 //!
 //! Internally, all atoms are powered by the structs in the [`space`](space/index.html) module. They safely abstract the reading and writing process and assure that no memory is improperly accessed or leaked and that alignments are upheld. If you simply want to use the atoms in this crate, you don't need to deal with. They are only interesting if you want to create your own atom types.
+//!
+//! # Features
+//!
+//! The crate is `no_std` if the default `std` feature is disabled. Reading and writing atoms works in preallocated memory and therefore doesn't need an allocator, but the dynamically allocated [`SpaceHead`](space/struct.SpaceHead.html) and [`AtomSpace`](space/struct.AtomSpace.html) as well as the [`ring`](ring/index.html) buffer need the `alloc` feature. Without the default `lv2-core` feature, the [`port`](port/index.html) module isn't available.
+//!
+//! Atoms are supposed to be 64-bit-aligned, but some legacy hosts only align their buffers to 32 bits. By default, 64-bit values at such addresses, like `Long` and `Double` atoms or the time stamps of sequences, aren't read, since references to them would be misaligned. With the `unaligned` feature, these values are copied out of the buffer instead. Vectors of 64-bit values are never read from misaligned buffers, since they are returned as slices.
+//!
+//! Vendor-specific events with a fixed layout are defined as plain structs with the [`event`](event/index.html) module, which turns them into atom types.
+//!
+//! Large binary data, which doesn't fit into an atom port at once, is sent across several cycles with the [`blob`](blob/index.html) module.
+//!
+//! Matrices, like the spectra of several channels, are written as tuples with a shape and a vector of elements by the [`matrix`](matrix/index.html) module. With the optional `ndarray` and `nalgebra` features, matrices and vectors can be read as views and written from the arrays and matrices of these crates.
+//!
+//! The optional `osc` feature enables the [`osc`](osc/index.html) module, which converts between OSC packets and atoms.
+#![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
 extern crate lv2_sys as sys;
 extern crate lv2_units as units;
 
+pub mod blob;
 pub mod chunk;
+pub mod event;
+pub mod matrix;
 pub mod object;
+#[cfg(feature = "osc")]
+pub mod osc;
+#[cfg(feature = "alloc")]
+pub mod ring;
 pub mod scalar;
 pub mod sequence;
 pub mod space;
@@ -80,14 +104,22 @@ pub mod port;
 pub mod prelude {
     use crate::*;
 
+    pub use crate::event_atom;
     pub use crate::{Atom, AtomURIDCollection, UnidentifiedAtom};
     pub use chunk::Chunk;
-    pub use object::{Object, ObjectHeader, PropertyHeader};
+    pub use event::{EventBody, EventField};
+    pub use matrix::Matrix;
+    pub use object::{
+        AtomObject, Object, ObjectHeader, ObjectReader, ObjectWriter, PropertyHeader,
+    };
+    #[cfg(feature = "lv2-core")]
     pub use port::AtomPort;
     pub use scalar::{AtomURID, Bool, Double, Float, Int, Long};
-    pub use sequence::{Sequence, TimeStamp, TimeStampURID};
+    pub use sequence::{BeatTime, FrameTime, OverflowPolicy, Sequence, TimeStamp, TimeStampURID};
+    #[cfg(feature = "alloc")]
+    pub use space::AtomSpace;
     pub use space::{FramedMutSpace, MutSpace, Space};
-    pub use string::{Literal, LiteralInfo, String};
+    pub use string::{Literal, LiteralInfo, Path, String};
     pub use tuple::Tuple;
     pub use vector::Vector;
 }
@@ -109,6 +141,7 @@ pub struct AtomURIDCollection {
     pub chunk: URID<chunk::Chunk>,
     pub literal: URID<string::Literal>,
     pub object: URID<object::Object>,
+    pub path: URID<string::Path>,
     pub property: URID<object::Property>,
     pub string: URID<string::String>,
     pub tuple: URID<tuple::Tuple>,
@@ -119,6 +152,10 @@ impl AtomURIDCollection {
     pub fn vector<S: scalar::ScalarAtom>(&self) -> URID<vector::Vector<S>> {
         unsafe { URID::new_unchecked(self.vector.get()) }
     }
+
+    pub fn matrix<S: scalar::ScalarAtom>(&self) -> URID<matrix::Matrix<S>> {
+        unsafe { URID::new_unchecked(self.tuple.get()) }
+    }
 }
 
 /// Atom type.
@@ -209,4 +246,20 @@ impl<'a> UnidentifiedAtom<'a> {
             .split_type::<sys::LV2_Atom>()
             .and_then(|(header, _)| URID::new(header.type_))
     }
+
+    /// Retrieve the space occupied by the atom, including its header.
+    ///
+    /// The space of an unidentified atom may continue behind the atom, for example if it was created from a whole buffer. The returned space only contains the atom itself, which makes it possible to copy the atom.
+    pub fn atom_space(self) -> Option<Space<'a>> {
+        self.space.split_atom().map(|(atom, _)| atom)
+    }
+
+    /// Retrieve the raw header of the atom.
+    ///
+    /// The header is followed by the body of the atom, which is `size` bytes long, and both are checked to be in the space of the atom. This makes it possible to pass the atom to C libraries, like `sratom`, which read the body behind the header. They must not write to the atom or read beyond its body.
+    pub fn as_raw(self) -> Option<&'a sys::LV2_Atom> {
+        self.atom_space()?
+            .split_type::<sys::LV2_Atom>()
+            .map(|(header, _)| header)
+    }
 }
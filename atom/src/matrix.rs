@@ -0,0 +1,523 @@
+//! An atom containing a matrix of scalar atom bodies.
+//!
+//! Analysis plugins often produce more than a single series of values, like a spectrogram with a spectrum per channel or a correlation matrix, and want to hand them to their UI. There is no matrix type in the atom specification, which is why this module uses a convention that is built from standard atoms: A matrix is a [`Tuple`](../tuple/struct.Tuple.html) of an `Int` with the number of rows, an `Int` with the number of columns and a [`Vector`](../vector/struct.Vector.html) with the elements in column-major order. Hosts and other tools that don't know the convention can still read, store and forward the tuple.
+//!
+//! Reading a matrix returns a [`MatrixView`](struct.MatrixView.html), which borrows the elements of the atom. Writing a matrix is done with a [`MatrixWriter`](struct.MatrixWriter.html), which is created with the shape of the matrix and appends the elements column by column.
+//!
+//! With the optional `ndarray` feature, views and vectors can be used as arrays of the [`ndarray`](https://crates.io/crates/ndarray) crate, and with the optional `nalgebra` feature as matrices of the [`nalgebra`](https://crates.io/crates/nalgebra) crate, without copying the elements. Writers accept the arrays and matrices of both crates, regardless of their memory layout.
+//!
+//! # Example
+//! ```
+//! use lv2_atom::matrix::*;
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::space::*;
+//! use urid::*;
+//!
+//! let map = HashURIDMapper::new();
+//! let urids: AtomURIDCollection = map.populate_collection().unwrap();
+//! let parameter = MatrixURIDs::new(&urids, urids.float);
+//!
+//! // A spectrum with three bins for each of two channels.
+//! let mut buffer: Box<[u8]> = Box::new([0; 256]);
+//! {
+//!     let mut space = RootMutSpace::new(buffer.as_mut());
+//!     let mut writer = (&mut space as &mut dyn MutSpace)
+//!         .init(urids.matrix(), (parameter, 3, 2))
+//!         .unwrap();
+//!     writer.append(&[0.5, 0.25, 0.125]).unwrap();
+//!     writer.append(&[1.0, 0.5, 0.25]).unwrap();
+//! }
+//!
+//! let spectra = UnidentifiedAtom::new(Space::from_slice(buffer.as_ref()))
+//!     .read(urids.matrix(), parameter)
+//!     .unwrap();
+//! assert_eq!((spectra.rows(), spectra.columns()), (3, 2));
+//! assert_eq!(spectra.column(1), Some(&[1.0, 0.5, 0.25][..]));
+//! assert_eq!(spectra.get(2, 0), Some(&0.125));
+//! ```
+use crate::scalar::{Int, ScalarAtom};
+use crate::space::*;
+use crate::tuple::Tuple;
+use crate::vector::Vector;
+use crate::*;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use urid::*;
+
+/// An atom containing a matrix of scalar atom bodies.
+///
+/// The atom has the type URID of a tuple, since it's a tuple with a certain layout. Like vectors, two matrices with different item types are different types in Rust, which is why the URID is retrieved with the [`matrix`](../struct.AtomURIDCollection.html#method.matrix) method of the atom URID collection.
+///
+/// [See also the module documentation.](index.html)
+pub struct Matrix<C: ScalarAtom> {
+    child: PhantomData<C>,
+}
+
+unsafe impl<C: ScalarAtom> UriBound for Matrix<C> {
+    const URI: &'static [u8] = sys::LV2_ATOM__Tuple;
+}
+
+/// The URIDs of the atoms a matrix consists of, which are needed to read and write it.
+pub struct MatrixURIDs<C: ScalarAtom> {
+    pub int: URID<Int>,
+    pub vector: URID<Vector<C>>,
+    pub child: URID<C>,
+}
+
+impl<C: ScalarAtom> MatrixURIDs<C> {
+    /// Collect the URIDs of a matrix with the given item type.
+    pub fn new(urids: &AtomURIDCollection, child: URID<C>) -> Self {
+        Self {
+            int: urids.int,
+            vector: urids.vector(),
+            child,
+        }
+    }
+}
+
+impl<C: ScalarAtom> Clone for MatrixURIDs<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: ScalarAtom> Copy for MatrixURIDs<C> {}
+
+impl<'a, 'b, C: ScalarAtom> Atom<'a, 'b> for Matrix<C>
+where
+    'a: 'b,
+    C: 'b,
+{
+    type ReadParameter = MatrixURIDs<C>;
+    type ReadHandle = MatrixView<'a, C::InternalType>;
+    type WriteParameter = (MatrixURIDs<C>, usize, usize);
+    type WriteHandle = MatrixWriter<'a, 'b, C>;
+
+    fn read(body: Space<'a>, urids: MatrixURIDs<C>) -> Option<MatrixView<'a, C::InternalType>> {
+        let mut atoms = Tuple::read(body, ())?;
+        let rows = atoms.next()?.read(urids.int, ())?;
+        let columns = atoms.next()?.read(urids.int, ())?;
+        let elements = atoms.next()?.read(urids.vector, urids.child)?;
+        if atoms.next().is_some() || rows < 0 || columns < 0 {
+            return None;
+        }
+        MatrixView::new(rows as usize, columns as usize, elements)
+    }
+
+    fn init(
+        mut frame: FramedMutSpace<'a, 'b>,
+        (urids, rows, columns): (MatrixURIDs<C>, usize, usize),
+    ) -> Option<MatrixWriter<'a, 'b, C>> {
+        let len = rows.checked_mul(columns)?;
+        if rows > i32::MAX as usize || columns > i32::MAX as usize {
+            return None;
+        }
+        {
+            let space = &mut frame as &mut dyn MutSpace;
+            space.init(urids.int, rows as i32)?;
+            space.init(urids.int, columns as i32)?;
+        }
+        // The vector is written by hand, since its writer would borrow the frame of the tuple.
+        let vector = sys::LV2_Atom_Vector {
+            atom: sys::LV2_Atom {
+                size: size_of::<sys::LV2_Atom_Vector_Body>() as u32,
+                type_: urids.vector.get(),
+            },
+            body: sys::LV2_Atom_Vector_Body {
+                child_size: size_of::<C::InternalType>() as u32,
+                child_type: urids.child.get(),
+            },
+        };
+        let vector = (&mut frame as &mut dyn MutSpace).write(&vector, true)?;
+
+        Some(MatrixWriter {
+            frame,
+            vector: &mut vector.atom,
+            remaining: len,
+            rows,
+            columns,
+            type_: PhantomData,
+        })
+    }
+}
+
+/// A view of the elements of a matrix atom.
+///
+/// The elements are stored in column-major order, which means that the elements of every column are contiguous.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MatrixView<'a, T> {
+    rows: usize,
+    columns: usize,
+    elements: &'a [T],
+}
+
+impl<'a, T> MatrixView<'a, T> {
+    /// Create a view of elements in column-major order.
+    ///
+    /// If the number of elements doesn't match the shape, `None` is returned.
+    pub fn new(rows: usize, columns: usize, elements: &'a [T]) -> Option<Self> {
+        if rows.checked_mul(columns)? != elements.len() {
+            return None;
+        }
+        Some(Self {
+            rows,
+            columns,
+            elements,
+        })
+    }
+
+    /// The number of rows of the matrix.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns of the matrix.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// All elements of the matrix, in column-major order.
+    pub fn elements(&self) -> &'a [T] {
+        self.elements
+    }
+
+    /// The elements of a column.
+    pub fn column(&self, column: usize) -> Option<&'a [T]> {
+        if column >= self.columns {
+            return None;
+        }
+        self.elements
+            .get(column * self.rows..(column + 1) * self.rows)
+    }
+
+    /// The element in the given row and column.
+    pub fn get(&self, row: usize, column: usize) -> Option<&'a T> {
+        if row >= self.rows {
+            return None;
+        }
+        self.column(column).map(|column| &column[row])
+    }
+
+    /// View the matrix as a two-dimensional `ndarray` array.
+    ///
+    /// This method is only available with the `ndarray` feature.
+    #[cfg(feature = "ndarray")]
+    pub fn as_array(&self) -> ndarray::ArrayView2<'a, T> {
+        use ndarray::ShapeBuilder;
+        // The shape was checked when the view was created.
+        ndarray::ArrayView2::from_shape((self.rows, self.columns).f(), self.elements).unwrap()
+    }
+
+    /// View the matrix as a `nalgebra` matrix.
+    ///
+    /// This method is only available with the `nalgebra` feature.
+    #[cfg(feature = "nalgebra")]
+    pub fn as_matrix(&self) -> nalgebra::DMatrixView<'a, T>
+    where
+        T: nalgebra::Scalar,
+    {
+        nalgebra::DMatrixView::from_slice(self.elements, self.rows, self.columns)
+    }
+}
+
+/// Handle to append the elements of a matrix.
+///
+/// The elements are appended in column-major order. The writer doesn't accept more elements than the matrix has, and a matrix with fewer elements than its shape requires can't be read.
+pub struct MatrixWriter<'a, 'b, C: ScalarAtom> {
+    frame: FramedMutSpace<'a, 'b>,
+    vector: &'a mut sys::LV2_Atom,
+    remaining: usize,
+    rows: usize,
+    columns: usize,
+    type_: PhantomData<C>,
+}
+
+impl<'a, 'b, C: ScalarAtom> MatrixWriter<'a, 'b, C> {
+    /// The number of rows of the matrix.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns of the matrix.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// The number of elements that are still missing.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Append a single element to the matrix.
+    pub fn push(&mut self, element: C::InternalType) -> Option<&mut C::InternalType> {
+        self.allocate(1).map(|elements| {
+            elements[0] = element;
+            &mut elements[0]
+        })
+    }
+
+    /// Append multiple elements to the matrix.
+    pub fn append(&mut self, elements: &[C::InternalType]) -> Option<&mut [C::InternalType]> {
+        let data = self.allocate(elements.len())?;
+        data.copy_from_slice(elements);
+        Some(data)
+    }
+
+    /// Append all elements of an `ndarray` array, which needs to have the shape of the matrix.
+    ///
+    /// The array may have any memory layout. This method is only available with the `ndarray` feature.
+    #[cfg(feature = "ndarray")]
+    pub fn append_array<S>(&mut self, array: &ndarray::ArrayBase<S, ndarray::Ix2>) -> Option<()>
+    where
+        S: ndarray::Data<Elem = C::InternalType>,
+    {
+        if array.dim() != (self.rows, self.columns) {
+            return None;
+        }
+        let data = self.allocate(array.len())?;
+        // Iterating over the transposed array yields the elements in column-major order.
+        for (target, source) in data.iter_mut().zip(array.t().iter()) {
+            *target = *source;
+        }
+        Some(())
+    }
+
+    /// Append all elements of a `nalgebra` matrix, which needs to have the shape of the matrix.
+    ///
+    /// The matrix may be a view with any strides. This method is only available with the `nalgebra` feature.
+    #[cfg(feature = "nalgebra")]
+    pub fn append_matrix<R, K, S>(
+        &mut self,
+        matrix: &nalgebra::Matrix<C::InternalType, R, K, S>,
+    ) -> Option<()>
+    where
+        C::InternalType: nalgebra::Scalar,
+        R: nalgebra::Dim,
+        K: nalgebra::Dim,
+        S: nalgebra::RawStorage<C::InternalType, R, K>,
+    {
+        if matrix.shape() != (self.rows, self.columns) {
+            return None;
+        }
+        let data = self.allocate(matrix.len())?;
+        for (target, source) in data.iter_mut().zip(matrix.iter()) {
+            *target = *source;
+        }
+        Some(())
+    }
+
+    fn allocate(&mut self, count: usize) -> Option<&'a mut [C::InternalType]> {
+        if count > self.remaining {
+            return None;
+        }
+        let size = size_of::<C::InternalType>() * count;
+        let (_, data) = self.frame.allocate(size, false)?;
+        self.vector.size += size as u32;
+        self.remaining -= count;
+        Some(unsafe {
+            core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut C::InternalType, count)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::*;
+    use core::mem::size_of;
+
+    #[test]
+    fn test_matrix() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let parameter = MatrixURIDs::new(&urids, urids.double);
+
+        let mut raw_space: Box<[u64]> = Box::new([0; 32]);
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(raw_space.as_mut_ptr() as *mut u8, raw_space.len() * 8)
+        };
+
+        // writing
+        {
+            let mut space = RootMutSpace::new(bytes);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.matrix(), (parameter, 2, 3))
+                .unwrap();
+            assert_eq!(writer.remaining(), 6);
+            writer.push(1.0).unwrap();
+            writer.append(&[2.0, 3.0, 4.0]).unwrap();
+            assert!(writer.append(&[5.0, 6.0, 7.0]).is_none());
+            writer.append(&[5.0, 6.0]).unwrap();
+            assert!(writer.push(7.0).is_none());
+        }
+
+        // verifying
+        {
+            let atom = unsafe { &*(bytes.as_ptr() as *const sys::LV2_Atom) };
+            assert_eq!(atom.type_, urids.tuple);
+            assert_eq!(
+                atom.size as usize,
+                2 * (size_of::<sys::LV2_Atom_Int>() + 4)
+                    + size_of::<sys::LV2_Atom_Vector>()
+                    + 6 * size_of::<f64>()
+            );
+            let tuple: Vec<UnidentifiedAtom> = UnidentifiedAtom::new(Space::from_slice(bytes))
+                .read(urids.tuple, ())
+                .unwrap()
+                .collect();
+            assert_eq!(tuple.len(), 3);
+            assert_eq!(tuple[0].read(urids.int, ()), Some(2));
+            assert_eq!(tuple[1].read(urids.int, ()), Some(3));
+            assert_eq!(
+                tuple[2].read(urids.vector(), urids.double),
+                Some(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0][..])
+            );
+        }
+
+        // reading
+        {
+            let atom = UnidentifiedAtom::new(Space::from_slice(bytes));
+            let matrix = atom.read(urids.matrix(), parameter).unwrap();
+            assert_eq!((matrix.rows(), matrix.columns()), (2, 3));
+            assert_eq!(matrix.elements(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+            assert_eq!(matrix.column(1), Some(&[3.0, 4.0][..]));
+            assert_eq!(matrix.column(3), None);
+            assert_eq!(matrix.get(1, 2), Some(&6.0));
+            assert_eq!(matrix.get(2, 0), None);
+
+            // The item type has to match.
+            assert!(atom
+                .read(urids.matrix(), MatrixURIDs::new(&urids, urids.float))
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn test_malformed_matrix() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let parameter = MatrixURIDs::new(&urids, urids.float);
+
+        let mut raw_space: Box<[u64]> = Box::new([0; 32]);
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(raw_space.as_mut_ptr() as *mut u8, raw_space.len() * 8)
+        };
+
+        // A matrix with a missing element.
+        {
+            let mut space = RootMutSpace::new(bytes);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.matrix(), (parameter, 2, 2))
+                .unwrap();
+            writer.append(&[1.0, 2.0, 3.0]).unwrap();
+        }
+        let atom = UnidentifiedAtom::new(Space::from_slice(bytes));
+        assert!(atom.read(urids.matrix(), parameter).is_none());
+
+        // A tuple with a negative number of rows.
+        {
+            let mut space = RootMutSpace::new(bytes);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.tuple, ())
+                .unwrap();
+            writer.init(urids.int, -1).unwrap();
+            writer.init(urids.int, 0).unwrap();
+            writer.init(urids.vector(), urids.float).unwrap();
+        }
+        let atom = UnidentifiedAtom::new(Space::from_slice(bytes));
+        assert!(atom.read(urids.matrix(), parameter).is_none());
+    }
+
+    #[test]
+    fn test_writer_shape() {
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let parameter = MatrixURIDs::new(&urids, urids.int);
+
+        let mut raw_space: Box<[u64]> = Box::new([0; 32]);
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(raw_space.as_mut_ptr() as *mut u8, raw_space.len() * 8)
+        };
+
+        {
+            let mut space = RootMutSpace::new(bytes);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.matrix(), (parameter, 4, 1))
+                .unwrap();
+            assert_eq!((writer.rows(), writer.columns()), (4, 1));
+            // The shape doesn't change while the elements are appended.
+            for element in 0..4 {
+                writer.push(element).unwrap();
+                assert_eq!((writer.rows(), writer.columns()), (4, 1));
+            }
+            assert_eq!(writer.remaining(), 0);
+        }
+
+        let matrix = UnidentifiedAtom::new(Space::from_slice(bytes))
+            .read(urids.matrix(), parameter)
+            .unwrap();
+        assert_eq!((matrix.rows(), matrix.columns()), (4, 1));
+        assert_eq!(matrix.elements(), &[0, 1, 2, 3]);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_ndarray() {
+        use ndarray::{arr1, arr2};
+
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let parameter = MatrixURIDs::new(&urids, urids.float);
+        let array = arr2(&[[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+
+        let mut raw_space: Box<[u64]> = Box::new([0; 32]);
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(raw_space.as_mut_ptr() as *mut u8, raw_space.len() * 8)
+        };
+        {
+            let mut space = RootMutSpace::new(bytes);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.matrix(), (parameter, 2, 3))
+                .unwrap();
+            assert!(writer.append_array(&array.t()).is_none());
+            writer.append_array(&array).unwrap();
+        }
+
+        let matrix = UnidentifiedAtom::new(Space::from_slice(bytes))
+            .read(urids.matrix(), parameter)
+            .unwrap();
+        assert_eq!(matrix.elements(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        assert_eq!(matrix.as_array(), array);
+        assert_eq!(matrix.as_array().row(1), arr1(&[4.0, 5.0, 6.0]));
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra() {
+        use nalgebra::DMatrix;
+
+        let map = HashURIDMapper::new();
+        let urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let parameter = MatrixURIDs::new(&urids, urids.double);
+        let source = DMatrix::from_row_slice(3, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        let block = source.view((1, 0), (2, 3));
+
+        let mut raw_space: Box<[u64]> = Box::new([0; 32]);
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(raw_space.as_mut_ptr() as *mut u8, raw_space.len() * 8)
+        };
+        {
+            let mut space = RootMutSpace::new(bytes);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.matrix(), (parameter, 2, 3))
+                .unwrap();
+            assert!(writer.append_matrix(&source).is_none());
+            writer.append_matrix(&block).unwrap();
+        }
+
+        let matrix = UnidentifiedAtom::new(Space::from_slice(bytes))
+            .read(urids.matrix(), parameter)
+            .unwrap();
+        assert_eq!(matrix.elements(), &[4.0, 7.0, 5.0, 8.0, 6.0, 9.0]);
+        assert_eq!(matrix.as_matrix(), block);
+    }
+}
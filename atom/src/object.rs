@@ -69,11 +69,14 @@
 //! [http://lv2plug.in/ns/ext/atom/atom.html#Object](http://lv2plug.in/ns/ext/atom/atom.html#Object).
 use crate::space::*;
 use crate::*;
-use std::convert::TryFrom;
-use std::iter::Iterator;
+use core::convert::TryFrom;
+use core::iter::Iterator;
+use urid::Map;
 use urid::UriBound;
 use urid::URID;
 
+pub use lv2_atom_derive::AtomObject;
+
 /// An atom containing multiple key-value pairs.
 ///
 /// [See also the module documentation.](index.html)
@@ -172,6 +175,21 @@ pub struct ObjectReader<'a> {
     space: Space<'a>,
 }
 
+impl<'a> ObjectReader<'a> {
+    /// Read the properties of an object or blank, regardless of its type.
+    ///
+    /// [`Object::read`](struct.Object.html) requires the object to have a type, but objects that are only used as anonymous descriptions, like the bodies of patch messages, often have none. This method returns a reader for the properties of these objects too.
+    pub fn from_atom(atom: UnidentifiedAtom<'a>, urids: &AtomURIDCollection) -> Option<Self> {
+        let body = atom
+            .space
+            .split_atom_body(urids.object)
+            .or_else(|| atom.space.split_atom_body(urids.blank))
+            .map(|(body, _)| body)?;
+        let (_, space) = body.split_type::<sys::LV2_Atom_Object_Body>()?;
+        Some(Self { space })
+    }
+}
+
 impl<'a> Iterator for ObjectReader<'a> {
     type Item = (PropertyHeader, UnidentifiedAtom<'a>);
 
@@ -205,6 +223,91 @@ impl<'a, 'b> ObjectWriter<'a, 'b> {
     }
 }
 
+/// A struct that is represented by an object atom.
+///
+/// Every field of the struct is a property of the object. Reading and writing these objects by hand is repetitive and error-prone, since the keys and atom types have to match on both sides, for example in the DSP and the UI of a plugin. Therefore, this trait is usually implemented with the `AtomObject` derive macro: The struct is annotated with the type URI of the object and every field is annotated with the URI of its property and the type of its atom. Fields with an `Option` type are optional properties: They don't have to be present when the object is read and they are only written if they have a value. The field types have to be the read handles and write parameters of their atoms, which is the case for the [scalar atoms](../scalar/index.html). The generated code uses the items of the prelude and of `urid`, which therefore have to be imported.
+///
+/// # Example
+///
+/// ```
+/// use lv2_atom::prelude::*;
+/// use lv2_atom::space::*;
+/// use lv2_urid::*;
+/// use urid::*;
+///
+/// #[derive(AtomObject, Debug, PartialEq)]
+/// #[object(uri = "urn:my-plugin#Note")]
+/// struct Note {
+///     #[property(uri = "urn:my-plugin#pitch", atom = Int)]
+///     pitch: i32,
+///     #[property(uri = "urn:my-plugin#velocity", atom = Float)]
+///     velocity: Option<f32>,
+/// }
+///
+/// let map = HashURIDMapper::new();
+/// let atom_urids = AtomURIDCollection::from_map(&map).unwrap();
+/// let note_urids = Note::map_urids(&map).unwrap();
+///
+/// let note = Note {
+///     pitch: 60,
+///     velocity: None,
+/// };
+///
+/// let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+/// note.write(
+///     &mut RootMutSpace::new(raw_space.as_mut()),
+///     atom_urids.object,
+///     &note_urids,
+/// )
+/// .unwrap();
+///
+/// let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+/// assert_eq!(
+///     Note::read(atom, atom_urids.object, &note_urids),
+///     Some(note)
+/// );
+/// ```
+pub trait AtomObject: Sized {
+    /// The URIDs of the object type, the property keys and the atom types.
+    type URIDs;
+
+    /// Map all URIs of the object.
+    fn map_urids<M: Map + ?Sized>(map: &M) -> Option<Self::URIDs>;
+
+    /// Create the header of the object.
+    fn header(urids: &Self::URIDs) -> ObjectHeader;
+
+    /// Read the struct from the properties of an object.
+    ///
+    /// Unknown properties are ignored. If a required property is missing or if a property has the wrong atom type, `None` is returned.
+    fn read_properties(reader: ObjectReader, urids: &Self::URIDs) -> Option<Self>;
+
+    /// Write the fields of the struct as properties of an object.
+    fn write_properties(&self, writer: &mut ObjectWriter, urids: &Self::URIDs) -> Option<()>;
+
+    /// Read the struct from an object atom.
+    ///
+    /// `None` is returned if the atom isn't an object of the right type or if the properties can't be read.
+    fn read(atom: UnidentifiedAtom, object: URID<Object>, urids: &Self::URIDs) -> Option<Self> {
+        let (header, reader) = atom.read(object, ())?;
+        if header.otype != Self::header(urids).otype {
+            return None;
+        }
+        Self::read_properties(reader, urids)
+    }
+
+    /// Write the struct as an object atom.
+    fn write(
+        &self,
+        space: &mut dyn MutSpace,
+        object: URID<Object>,
+        urids: &Self::URIDs,
+    ) -> Option<()> {
+        let mut writer = space.init(object, Self::header(urids))?;
+        self.write_properties(&mut writer, urids)
+    }
+}
+
 /// An atom containing a key-value pair.
 ///
 /// A property represents a single URID -> atom mapping. Additionally and optionally, you may also define a context in which the property is valid. For more information, visit the [specification](http://lv2plug.in/ns/ext/atom/atom.html#Property).
@@ -263,9 +366,10 @@ impl Property {
 
 #[cfg(test)]
 mod tests {
+    use crate::object::ObjectReader;
     use crate::prelude::*;
     use crate::space::*;
-    use std::mem::size_of;
+    use core::mem::size_of;
     use urid::*;
 
     #[test]
@@ -377,5 +481,110 @@ mod tests {
             assert_eq!(header.key, second_key);
             assert_eq!(atom.read::<Float>(urids.float, ()).unwrap(), second_value);
         }
+
+        // reading without a type
+        {
+            let otype_offset = size_of::<sys::LV2_Atom>() + size_of::<u32>();
+            raw_space[otype_offset..otype_offset + size_of::<u32>()].copy_from_slice(&[0; 4]);
+
+            let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+            assert!(atom.read(urids.object, ()).is_none());
+
+            let properties: Vec<(PropertyHeader, UnidentifiedAtom)> =
+                ObjectReader::from_atom(atom, &urids).unwrap().collect();
+            assert_eq!(properties.len(), 2);
+            assert_eq!(properties[0].0.key, first_key);
+            assert_eq!(properties[1].0.key, second_key);
+        }
+    }
+
+    #[derive(AtomObject, Debug, PartialEq)]
+    #[object(uri = "urn:my-type")]
+    struct TestObject {
+        #[property(uri = "urn:value-a", atom = Int)]
+        a: i32,
+        #[property(uri = "urn:value-b", atom = Float)]
+        b: Option<f32>,
+    }
+
+    #[test]
+    fn test_atom_object() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+        let object_urids = TestObject::map_urids(&map).unwrap();
+        let other_key = map
+            .map_uri(Uri::from_bytes_with_nul(b"urn:value-c\0").unwrap())
+            .unwrap();
+        let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+
+        // round trip, with and without the optional property
+        for object in [
+            TestObject { a: 17, b: None },
+            TestObject {
+                a: 1,
+                b: Some(42.0),
+            },
+        ] {
+            object
+                .write(
+                    &mut RootMutSpace::new(raw_space.as_mut()),
+                    urids.object,
+                    &object_urids,
+                )
+                .unwrap();
+            let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+            assert_eq!(
+                TestObject::read(atom, urids.object, &object_urids),
+                Some(object)
+            );
+        }
+
+        // unknown properties are ignored, a missing required property fails
+        let write_properties = |raw_space: &mut [u8], properties: &[(URID, i32)]| {
+            let mut space = RootMutSpace::new(raw_space);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.object, TestObject::header(&object_urids))
+                .unwrap();
+            for (key, value) in properties {
+                writer.init(*key, None, urids.int, *value).unwrap();
+            }
+        };
+        write_properties(
+            raw_space.as_mut(),
+            &[(other_key, 3), (object_urids.1[0], 5)],
+        );
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        assert_eq!(
+            TestObject::read(atom, urids.object, &object_urids),
+            Some(TestObject { a: 5, b: None })
+        );
+        write_properties(raw_space.as_mut(), &[(object_urids.1[1], 5)]);
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        assert_eq!(TestObject::read(atom, urids.object, &object_urids), None);
+
+        // a property with the wrong atom type fails
+        write_properties(
+            raw_space.as_mut(),
+            &[(object_urids.1[0], 5), (object_urids.1[1], 5)],
+        );
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        assert_eq!(TestObject::read(atom, urids.object, &object_urids), None);
+
+        // an object of another type fails
+        {
+            let mut space = RootMutSpace::new(raw_space.as_mut());
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(
+                    urids.object,
+                    ObjectHeader {
+                        id: None,
+                        otype: other_key,
+                    },
+                )
+                .unwrap();
+            writer.init(object_urids.1[0], None, urids.int, 5).unwrap();
+        }
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        assert_eq!(TestObject::read(atom, urids.object, &object_urids), None);
     }
 }
@@ -0,0 +1,1334 @@
+//! Conversion between OSC packets and atoms.
+//!
+//! [Open Sound Control](http://opensoundcontrol.org/spec-1_0) is a common protocol to control audio software over the network. OSC packets are either messages, which consist of an address and a list of typed arguments, or bundles, which contain other packets and a time tag that tells when they should be applied. This module converts these packets to and from atom objects, which makes it possible to use the atom infrastructure for OSC: Plugins can receive OSC messages on their atom ports and UIs can forward the messages of an OSC control surface to the DSP, for example while developing a plugin.
+//!
+//! The atom representation follows the [OSC extension by Open Music Kontrollers](http://open-music-kontrollers.ch/lv2/osc), which is understood by several other plugins and hosts:
+//!
+//! * A message is an [`Object`](../object/struct.Object.html) of the type [`Message`](struct.Message.html), with the address as a [`String`](../string/struct.String.html) in the [`MessagePath`](struct.MessagePath.html) property and the arguments as a [`Tuple`](../tuple/struct.Tuple.html) in the [`MessageArguments`](struct.MessageArguments.html) property.
+//! * A bundle is an object of the type [`Bundle`](struct.Bundle.html), with its time tag in the [`BundleTimetag`](struct.BundleTimetag.html) property and a tuple of its messages and bundles in the [`BundleItems`](struct.BundleItems.html) property.
+//! * Time tags are objects of the type [`Timetag`](struct.Timetag.html), with the seconds and the fraction as `Long`s.
+//! * Integers, floats, strings, longs, doubles and booleans are written as their scalar and string atoms and blobs are written as [`Chunk`](../chunk/struct.Chunk.html)s. The special values `Nil` and `Impulse`, as well as characters and colors, are [`Literal`](../string/struct.Literal.html)s with the respective datatype.
+//!
+//! Packets are parsed from their binary form with [`OscPacket::parse`](enum.OscPacket.html#method.parse) and written as an atom with [`write_atom`](enum.OscPacket.html#method.write_atom). The other direction is done with [`write_osc`](fn.write_osc.html), which writes an atom object as an OSC packet. Neither of them allocates memory, and the packets are read in place.
+//!
+//! This module is only available with the `osc` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::osc::*;
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::space::*;
+//! use urid::*;
+//!
+//! let map = HashURIDMapper::new();
+//! let atom_urids = AtomURIDCollection::from_map(&map).unwrap();
+//! let osc_urids = OscURIDCollection::from_map(&map).unwrap();
+//!
+//! // A message that was received from the network.
+//! let mut packet = [0u8; 64];
+//! let length = OscPacket::Message(OscMessage::new("/gain", ",f").unwrap())
+//!     .write_osc(&mut packet, &[OscArgument::Float(0.5)])
+//!     .unwrap();
+//!
+//! // Write it as an atom, for example to the input sequence of the plugin...
+//! let mut atom_space: Box<[u8]> = Box::new([0; 256]);
+//! OscPacket::parse(&packet[..length])
+//!     .unwrap()
+//!     .write_atom(
+//!         &mut RootMutSpace::new(atom_space.as_mut()),
+//!         &atom_urids,
+//!         &osc_urids,
+//!     )
+//!     .unwrap();
+//!
+//! // ...and read the arguments in the plugin.
+//! let atom = UnidentifiedAtom::new(Space::from_slice(atom_space.as_ref()));
+//! let (address, mut arguments) = read_message(atom, &atom_urids, &osc_urids).unwrap();
+//! assert_eq!(address, "/gain");
+//! assert_eq!(arguments.next(), Some(Ok(OscArgument::Float(0.5))));
+//! assert_eq!(arguments.next(), None);
+//!
+//! // The atom can also be sent back over the network.
+//! let mut output = [0u8; 64];
+//! let output_length = write_osc(atom, &mut output, &atom_urids, &osc_urids).unwrap();
+//! assert_eq!(&output[..output_length], &packet[..length]);
+//! ```
+//!
+//! # Specification
+//!
+//! [http://open-music-kontrollers.ch/lv2/osc](http://open-music-kontrollers.ch/lv2/osc)
+use crate::object::{ObjectHeader, ObjectWriter};
+use crate::space::*;
+use crate::string::LiteralInfo;
+use crate::tuple::{TupleIterator, TupleWriter};
+use crate::*;
+use core::convert::TryFrom;
+use core::fmt::Write;
+
+/// The prefix of all URIs of the OSC extension.
+macro_rules! osc_uri {
+    ($name:literal) => {
+        concat!("http://open-music-kontrollers.ch/lv2/osc#", $name, "\0").as_bytes()
+    };
+}
+
+/// Macro to define the URI bounds of the OSC extension.
+macro_rules! make_osc_uri {
+    ($(#[$attr:meta])* $name:ident, $uri:literal) => {
+        $(#[$attr])*
+        pub struct $name;
+
+        unsafe impl UriBound for $name {
+            const URI: &'static [u8] = osc_uri!($uri);
+        }
+    };
+}
+
+make_osc_uri!(
+    /// The object type of OSC bundles.
+    Bundle,
+    "Bundle"
+);
+make_osc_uri!(
+    /// The object type of OSC messages.
+    Message,
+    "Message"
+);
+make_osc_uri!(
+    /// The object type of OSC time tags.
+    Timetag,
+    "Timetag"
+);
+make_osc_uri!(
+    /// The property of a bundle that contains its time tag.
+    BundleTimetag,
+    "bundleTimetag"
+);
+make_osc_uri!(
+    /// The property of a bundle that contains the tuple of its messages and bundles.
+    BundleItems,
+    "bundleItems"
+);
+make_osc_uri!(
+    /// The property of a message that contains its address.
+    MessagePath,
+    "messagePath"
+);
+make_osc_uri!(
+    /// The property of a message that contains the tuple of its arguments.
+    MessageArguments,
+    "messageArguments"
+);
+make_osc_uri!(
+    /// The property of a time tag that contains the seconds.
+    TimetagIntegral,
+    "timetagIntegral"
+);
+make_osc_uri!(
+    /// The property of a time tag that contains the fraction of a second.
+    TimetagFraction,
+    "timetagFraction"
+);
+make_osc_uri!(
+    /// The datatype of the literal that represents the `Nil` argument.
+    Nil,
+    "Nil"
+);
+make_osc_uri!(
+    /// The datatype of the literal that represents the `Impulse` argument.
+    Impulse,
+    "Impulse"
+);
+make_osc_uri!(
+    /// The datatype of the literal that represents a character argument.
+    Char,
+    "Char"
+);
+make_osc_uri!(
+    /// The datatype of the literal that represents a color argument.
+    ///
+    /// The text of the literal is the color in hexadecimal notation, like `ff8000ff`.
+    Rgba,
+    "RGBA"
+);
+
+/// Collection with the URIDs of the OSC extension.
+#[derive(Clone, URIDCollection)]
+pub struct OscURIDCollection {
+    pub bundle: URID<Bundle>,
+    pub message: URID<Message>,
+    pub timetag: URID<Timetag>,
+    pub bundle_timetag: URID<BundleTimetag>,
+    pub bundle_items: URID<BundleItems>,
+    pub message_path: URID<MessagePath>,
+    pub message_arguments: URID<MessageArguments>,
+    pub timetag_integral: URID<TimetagIntegral>,
+    pub timetag_fraction: URID<TimetagFraction>,
+    pub nil: URID<Nil>,
+    pub impulse: URID<Impulse>,
+    pub char: URID<Char>,
+    pub rgba: URID<Rgba>,
+}
+
+/// Kinds of errors that may occur while converting between OSC packets and atoms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OscError {
+    /// The OSC packet is truncated or otherwise malformed.
+    Malformed,
+    /// An argument of a message has a type tag that isn't supported.
+    UnsupportedType(char),
+    /// The atom isn't an OSC message or bundle.
+    NotAPacket,
+    /// An argument atom has a type that can't be represented in OSC.
+    UnsupportedAtom,
+    /// The buffer or space isn't big enough for the converted packet.
+    InsufficientSpace,
+}
+
+/// An OSC time tag, which tells when a bundle should be applied.
+///
+/// Like an NTP timestamp, it contains the seconds since the first of January 1900 and the fraction of a second in units of 2<sup>-32</sup> seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeTag {
+    pub seconds: u32,
+    pub fraction: u32,
+}
+
+impl TimeTag {
+    /// The special time tag that tells to apply a bundle immediately.
+    pub const IMMEDIATELY: Self = Self {
+        seconds: 0,
+        fraction: 1,
+    };
+
+    /// Create a time tag from its binary form.
+    pub fn from_bits(bits: u64) -> Self {
+        Self {
+            seconds: (bits >> 32) as u32,
+            fraction: bits as u32,
+        }
+    }
+
+    /// The binary form of the time tag.
+    pub fn to_bits(self) -> u64 {
+        (u64::from(self.seconds) << 32) | u64::from(self.fraction)
+    }
+
+    /// Check if the time tag tells to apply a bundle immediately.
+    pub fn is_immediately(self) -> bool {
+        self == Self::IMMEDIATELY
+    }
+
+    /// Read a time tag from its atom object.
+    fn read_atom(
+        atom: UnidentifiedAtom,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Option<Self> {
+        let (header, reader) = atom.read(atom_urids.object, ())?;
+        if header.otype != osc_urids.timetag {
+            return None;
+        }
+        let mut seconds = None;
+        let mut fraction = None;
+        for (property, value) in reader {
+            if property.key == osc_urids.timetag_integral {
+                seconds = Some(value.read(atom_urids.long, ())? as u32);
+            } else if property.key == osc_urids.timetag_fraction {
+                fraction = Some(value.read(atom_urids.long, ())? as u32);
+            }
+        }
+        Some(Self {
+            seconds: seconds?,
+            fraction: fraction?,
+        })
+    }
+
+    /// Write the properties of the time tag's atom object.
+    fn write_properties(
+        self,
+        writer: &mut ObjectWriter,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Option<()> {
+        writer.init(
+            osc_urids.timetag_integral,
+            None,
+            atom_urids.long,
+            i64::from(self.seconds),
+        )?;
+        writer.init(
+            osc_urids.timetag_fraction,
+            None,
+            atom_urids.long,
+            i64::from(self.fraction),
+        )?;
+        Some(())
+    }
+}
+
+/// A single argument of an OSC message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OscArgument<'a> {
+    /// A 32-bit integer, with the type tag `i`.
+    Int(i32),
+    /// A 32-bit float, with the type tag `f`.
+    Float(f32),
+    /// A string, with the type tag `s`.
+    ///
+    /// Symbols, with the type tag `S`, are read as strings too.
+    String(&'a str),
+    /// A blob of binary data, with the type tag `b`.
+    Blob(&'a [u8]),
+    /// A 64-bit integer, with the type tag `h`.
+    Long(i64),
+    /// A 64-bit float, with the type tag `d`.
+    Double(f64),
+    /// A time tag, with the type tag `t`.
+    Timetag(TimeTag),
+    /// A boolean, with the type tags `T` and `F`.
+    Bool(bool),
+    /// The absence of a value, with the type tag `N`.
+    Nil,
+    /// An event without a value, like a trigger, with the type tag `I`.
+    Impulse,
+    /// An ASCII character, with the type tag `c`.
+    Char(char),
+    /// A color with 8 bits per red, green, blue and alpha channel, with the type tag `r`.
+    Rgba(u32),
+}
+
+impl<'a> OscArgument<'a> {
+    /// The type tag of the argument.
+    pub fn type_tag(&self) -> char {
+        match self {
+            OscArgument::Int(_) => 'i',
+            OscArgument::Float(_) => 'f',
+            OscArgument::String(_) => 's',
+            OscArgument::Blob(_) => 'b',
+            OscArgument::Long(_) => 'h',
+            OscArgument::Double(_) => 'd',
+            OscArgument::Timetag(_) => 't',
+            OscArgument::Bool(true) => 'T',
+            OscArgument::Bool(false) => 'F',
+            OscArgument::Nil => 'N',
+            OscArgument::Impulse => 'I',
+            OscArgument::Char(_) => 'c',
+            OscArgument::Rgba(_) => 'r',
+        }
+    }
+
+    /// Read an argument from its atom.
+    ///
+    /// Returns an [`UnsupportedAtom`](enum.OscError.html#variant.UnsupportedAtom) error if the atom can't be represented in OSC.
+    pub fn read_atom(
+        atom: UnidentifiedAtom<'a>,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Result<Self, OscError> {
+        let atom_type = atom.type_urid().ok_or(OscError::UnsupportedAtom)?;
+        let argument = if atom_type == atom_urids.int {
+            atom.read(atom_urids.int, ()).map(OscArgument::Int)
+        } else if atom_type == atom_urids.float {
+            atom.read(atom_urids.float, ()).map(OscArgument::Float)
+        } else if atom_type == atom_urids.string {
+            atom.read(atom_urids.string, ()).map(OscArgument::String)
+        } else if atom_type == atom_urids.chunk {
+            atom.read(atom_urids.chunk, ()).map(OscArgument::Blob)
+        } else if atom_type == atom_urids.long {
+            atom.read(atom_urids.long, ()).map(OscArgument::Long)
+        } else if atom_type == atom_urids.double {
+            atom.read(atom_urids.double, ()).map(OscArgument::Double)
+        } else if atom_type == atom_urids.bool {
+            atom.read(atom_urids.bool, ())
+                .map(|value| OscArgument::Bool(value != 0))
+        } else if atom_type == atom_urids.object {
+            TimeTag::read_atom(atom, atom_urids, osc_urids).map(OscArgument::Timetag)
+        } else if atom_type == atom_urids.literal {
+            Self::read_literal(atom, atom_urids, osc_urids)
+        } else {
+            None
+        };
+        argument.ok_or(OscError::UnsupportedAtom)
+    }
+
+    /// Read one of the special arguments that are represented by literals.
+    fn read_literal(
+        atom: UnidentifiedAtom<'a>,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Option<Self> {
+        let datatype = match atom.read(atom_urids.literal, ())? {
+            (LiteralInfo::Datatype(datatype), text) => (datatype, text),
+            _ => return None,
+        };
+        match datatype {
+            (datatype, _) if datatype == osc_urids.nil => Some(OscArgument::Nil),
+            (datatype, _) if datatype == osc_urids.impulse => Some(OscArgument::Impulse),
+            (datatype, text) if datatype == osc_urids.char => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(character), None) if character.is_ascii() => {
+                        Some(OscArgument::Char(character))
+                    }
+                    _ => None,
+                }
+            }
+            (datatype, text) if datatype == osc_urids.rgba => {
+                if text.len() != 8 {
+                    return None;
+                }
+                u32::from_str_radix(text, 16).ok().map(OscArgument::Rgba)
+            }
+            _ => None,
+        }
+    }
+
+    /// Write the argument as an element of a tuple.
+    fn write_atom(
+        &self,
+        writer: &mut TupleWriter,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Option<()> {
+        match *self {
+            OscArgument::Int(value) => writer.init(atom_urids.int, value).map(|_| ()),
+            OscArgument::Float(value) => writer.init(atom_urids.float, value).map(|_| ()),
+            OscArgument::String(value) => writer
+                .init(atom_urids.string, ())?
+                .append(value)
+                .map(|_| ()),
+            OscArgument::Blob(value) => writer
+                .init(atom_urids.chunk, ())?
+                .write_raw(value, false)
+                .map(|_| ()),
+            OscArgument::Long(value) => writer.init(atom_urids.long, value).map(|_| ()),
+            OscArgument::Double(value) => writer.init(atom_urids.double, value).map(|_| ()),
+            OscArgument::Timetag(value) => {
+                let mut object = writer.init(
+                    atom_urids.object,
+                    ObjectHeader {
+                        id: None,
+                        otype: osc_urids.timetag.into_general(),
+                    },
+                )?;
+                value.write_properties(&mut object, atom_urids, osc_urids)
+            }
+            OscArgument::Bool(value) => writer.init(atom_urids.bool, value as i32).map(|_| ()),
+            OscArgument::Nil => writer
+                .init(
+                    atom_urids.literal,
+                    LiteralInfo::Datatype(osc_urids.nil.into_general()),
+                )
+                .map(|_| ()),
+            OscArgument::Impulse => writer
+                .init(
+                    atom_urids.literal,
+                    LiteralInfo::Datatype(osc_urids.impulse.into_general()),
+                )
+                .map(|_| ()),
+            OscArgument::Char(value) => {
+                let mut buffer = [0; 4];
+                writer
+                    .init(
+                        atom_urids.literal,
+                        LiteralInfo::Datatype(osc_urids.char.into_general()),
+                    )?
+                    .append(value.encode_utf8(&mut buffer))
+                    .map(|_| ())
+            }
+            OscArgument::Rgba(value) => {
+                let mut literal = writer.init(
+                    atom_urids.literal,
+                    LiteralInfo::Datatype(osc_urids.rgba.into_general()),
+                )?;
+                let mut color = HexColor::default();
+                write!(color, "{:08x}", value).ok()?;
+                literal.append(color.as_str()).map(|_| ())
+            }
+        }
+    }
+
+    /// Write the argument in its binary OSC form.
+    fn write_osc(&self, cursor: &mut Cursor) -> Option<()> {
+        match *self {
+            OscArgument::Int(value) => cursor.write(&value.to_be_bytes()),
+            OscArgument::Float(value) => cursor.write(&value.to_be_bytes()),
+            OscArgument::String(value) => cursor.write_string(value),
+            OscArgument::Blob(value) => {
+                cursor.write(&u32::try_from(value.len()).ok()?.to_be_bytes())?;
+                cursor.write(value)?;
+                cursor.pad()
+            }
+            OscArgument::Long(value) => cursor.write(&value.to_be_bytes()),
+            OscArgument::Double(value) => cursor.write(&value.to_be_bytes()),
+            OscArgument::Timetag(value) => cursor.write(&value.to_bits().to_be_bytes()),
+            OscArgument::Bool(_) | OscArgument::Nil | OscArgument::Impulse => Some(()),
+            OscArgument::Char(value) => cursor.write(&(value as u32).to_be_bytes()),
+            OscArgument::Rgba(value) => cursor.write(&value.to_be_bytes()),
+        }
+    }
+}
+
+/// A buffer for the hexadecimal notation of a color.
+#[derive(Default)]
+struct HexColor {
+    buffer: [u8; 8],
+    length: usize,
+}
+
+impl HexColor {
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buffer[..self.length]).unwrap_or("")
+    }
+}
+
+impl Write for HexColor {
+    fn write_str(&mut self, string: &str) -> core::fmt::Result {
+        let end = self.length + string.len();
+        self.buffer
+            .get_mut(self.length..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(string.as_bytes());
+        self.length = end;
+        Ok(())
+    }
+}
+
+/// Reads the parts of an OSC packet, which are aligned to four bytes.
+#[derive(Clone, Copy)]
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], OscError> {
+        if length > self.data.len() {
+            return Err(OscError::Malformed);
+        }
+        let (bytes, data) = self.data.split_at(length);
+        self.data = data;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], OscError> {
+        let bytes = self.read_bytes(N)?;
+        <[u8; N]>::try_from(bytes).map_err(|_| OscError::Malformed)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, OscError> {
+        self.read_array().map(u32::from_be_bytes)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, OscError> {
+        self.read_array().map(u64::from_be_bytes)
+    }
+
+    /// Skip the padding behind a part of the given length.
+    fn skip_padding(&mut self, length: usize) -> Result<(), OscError> {
+        let padding = (4 - length % 4) % 4;
+        self.read_bytes(padding).map(|_| ())
+    }
+
+    /// Read a null-terminated string with its padding.
+    fn read_string(&mut self) -> Result<&'a str, OscError> {
+        let length = self
+            .data
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or(OscError::Malformed)?;
+        let bytes = self.read_bytes(length + 1)?;
+        self.skip_padding(length + 1)?;
+        core::str::from_utf8(&bytes[..length]).map_err(|_| OscError::Malformed)
+    }
+
+    /// Read a blob, which is prefixed with its length.
+    fn read_blob(&mut self) -> Result<&'a [u8], OscError> {
+        let length = self.read_u32()? as usize;
+        let bytes = self.read_bytes(length)?;
+        self.skip_padding(length)?;
+        Ok(bytes)
+    }
+}
+
+/// Writes the parts of an OSC packet to a buffer.
+struct Cursor<'a> {
+    buffer: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn write(&mut self, data: &[u8]) -> Option<()> {
+        let end = self.position + data.len();
+        self.buffer
+            .get_mut(self.position..end)?
+            .copy_from_slice(data);
+        self.position = end;
+        Some(())
+    }
+
+    /// Write zeros until the position is aligned to four bytes.
+    fn pad(&mut self) -> Option<()> {
+        let padding = (4 - self.position % 4) % 4;
+        self.write(&[0; 3][..padding])
+    }
+
+    /// Write a string with a null terminator and padding.
+    fn write_string(&mut self, string: &str) -> Option<()> {
+        self.write(string.as_bytes())?;
+        self.write(&[0])?;
+        self.pad()
+    }
+}
+
+/// A message of an OSC packet.
+///
+/// The message only borrows the address and the type tags. The arguments of a parsed message are read on demand with [`arguments`](#method.arguments).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OscMessage<'a> {
+    address: &'a str,
+    type_tags: &'a str,
+    arguments: &'a [u8],
+}
+
+impl<'a> OscMessage<'a> {
+    /// Create a message with an address and type tags, but without arguments.
+    ///
+    /// The type tags start with a comma, like `,if` for a message with an integer and a float. This is used to write new messages with [`OscPacket::write_osc`](enum.OscPacket.html#method.write_osc). Returns `None` if the address doesn't start with a slash or if the type tags don't start with a comma.
+    pub fn new(address: &'a str, type_tags: &'a str) -> Option<Self> {
+        if !address.starts_with('/') || !type_tags.starts_with(',') {
+            return None;
+        }
+        Some(Self {
+            address,
+            type_tags,
+            arguments: &[],
+        })
+    }
+
+    fn parse(data: &'a [u8]) -> Result<Self, OscError> {
+        let mut reader = Reader { data };
+        let address = reader.read_string()?;
+        if !address.starts_with('/') {
+            return Err(OscError::Malformed);
+        }
+        // Very old implementations omit the type tags of messages without arguments.
+        let type_tags = if reader.is_empty() {
+            ","
+        } else {
+            reader.read_string()?
+        };
+        if !type_tags.starts_with(',') {
+            return Err(OscError::Malformed);
+        }
+        Ok(Self {
+            address,
+            type_tags,
+            arguments: reader.data,
+        })
+    }
+
+    /// The address pattern of the message, like `/mixer/gain`.
+    pub fn address(&self) -> &'a str {
+        self.address
+    }
+
+    /// The type tags of the arguments, including the leading comma.
+    pub fn type_tags(&self) -> &'a str {
+        self.type_tags
+    }
+
+    /// Iterate over the arguments of the message.
+    ///
+    /// The iteration stops after the first error.
+    pub fn arguments(&self) -> OscArguments<'a> {
+        OscArguments {
+            type_tags: self.type_tags[1..].chars(),
+            reader: Reader {
+                data: self.arguments,
+            },
+            failed: false,
+        }
+    }
+
+    fn write_atom(
+        &self,
+        writer: &mut ObjectWriter,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Result<(), OscError> {
+        writer
+            .init(osc_urids.message_path, None, atom_urids.string, ())
+            .and_then(|mut path| path.append(self.address).map(|_| ()))
+            .ok_or(OscError::InsufficientSpace)?;
+        let mut tuple = writer
+            .init(osc_urids.message_arguments, None, atom_urids.tuple, ())
+            .ok_or(OscError::InsufficientSpace)?;
+        for argument in self.arguments() {
+            argument?
+                .write_atom(&mut tuple, atom_urids, osc_urids)
+                .ok_or(OscError::InsufficientSpace)?;
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the arguments of an [`OscMessage`](struct.OscMessage.html).
+#[derive(Clone)]
+pub struct OscArguments<'a> {
+    type_tags: core::str::Chars<'a>,
+    reader: Reader<'a>,
+    failed: bool,
+}
+
+impl<'a> Iterator for OscArguments<'a> {
+    type Item = Result<OscArgument<'a>, OscError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let reader = &mut self.reader;
+        let argument = match self.type_tags.next()? {
+            'i' => reader
+                .read_u32()
+                .map(|value| OscArgument::Int(value as i32)),
+            'f' => reader
+                .read_u32()
+                .map(|value| OscArgument::Float(f32::from_bits(value))),
+            's' | 'S' => reader.read_string().map(OscArgument::String),
+            'b' => reader.read_blob().map(OscArgument::Blob),
+            'h' => reader
+                .read_u64()
+                .map(|value| OscArgument::Long(value as i64)),
+            'd' => reader
+                .read_u64()
+                .map(|value| OscArgument::Double(f64::from_bits(value))),
+            't' => reader
+                .read_u64()
+                .map(|value| OscArgument::Timetag(TimeTag::from_bits(value))),
+            'T' => Ok(OscArgument::Bool(true)),
+            'F' => Ok(OscArgument::Bool(false)),
+            'N' => Ok(OscArgument::Nil),
+            'I' => Ok(OscArgument::Impulse),
+            'c' => reader.read_u32().and_then(|value| {
+                char::from_u32(value)
+                    .filter(char::is_ascii)
+                    .map(OscArgument::Char)
+                    .ok_or(OscError::Malformed)
+            }),
+            'r' => reader.read_u32().map(OscArgument::Rgba),
+            tag => Err(OscError::UnsupportedType(tag)),
+        };
+        self.failed = argument.is_err();
+        Some(argument)
+    }
+}
+
+/// A bundle of an OSC packet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OscBundle<'a> {
+    timetag: TimeTag,
+    elements: &'a [u8],
+}
+
+impl<'a> OscBundle<'a> {
+    /// The identifier at the beginning of every bundle.
+    const IDENTIFIER: &'static [u8] = b"#bundle\0";
+
+    /// Create a bundle with a time tag, but without elements.
+    ///
+    /// This is used to write new bundles with [`OscPacket::write_osc`](enum.OscPacket.html#method.write_osc).
+    pub fn new(timetag: TimeTag) -> Self {
+        Self {
+            timetag,
+            elements: &[],
+        }
+    }
+
+    fn parse(data: &'a [u8]) -> Result<Self, OscError> {
+        let mut reader = Reader { data };
+        if reader.read_bytes(Self::IDENTIFIER.len())? != Self::IDENTIFIER {
+            return Err(OscError::Malformed);
+        }
+        let timetag = TimeTag::from_bits(reader.read_u64()?);
+        Ok(Self {
+            timetag,
+            elements: reader.data,
+        })
+    }
+
+    /// The time tag of the bundle.
+    pub fn timetag(&self) -> TimeTag {
+        self.timetag
+    }
+
+    /// Iterate over the messages and bundles in the bundle.
+    ///
+    /// The iteration stops after the first error.
+    pub fn elements(&self) -> OscElements<'a> {
+        OscElements {
+            reader: Reader {
+                data: self.elements,
+            },
+            failed: false,
+        }
+    }
+
+    fn write_atom(
+        &self,
+        writer: &mut ObjectWriter,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Result<(), OscError> {
+        {
+            let mut timetag = writer
+                .init(
+                    osc_urids.bundle_timetag,
+                    None,
+                    atom_urids.object,
+                    ObjectHeader {
+                        id: None,
+                        otype: osc_urids.timetag.into_general(),
+                    },
+                )
+                .ok_or(OscError::InsufficientSpace)?;
+            self.timetag
+                .write_properties(&mut timetag, atom_urids, osc_urids)
+                .ok_or(OscError::InsufficientSpace)?;
+        }
+        let mut tuple = writer
+            .init(osc_urids.bundle_items, None, atom_urids.tuple, ())
+            .ok_or(OscError::InsufficientSpace)?;
+        for element in self.elements() {
+            let element = element?;
+            let mut object = tuple
+                .init(atom_urids.object, element.header(osc_urids))
+                .ok_or(OscError::InsufficientSpace)?;
+            element.write_properties(&mut object, atom_urids, osc_urids)?;
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the elements of an [`OscBundle`](struct.OscBundle.html).
+#[derive(Clone)]
+pub struct OscElements<'a> {
+    reader: Reader<'a>,
+    failed: bool,
+}
+
+impl<'a> Iterator for OscElements<'a> {
+    type Item = Result<OscPacket<'a>, OscError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.reader.is_empty() {
+            return None;
+        }
+        let element = self
+            .reader
+            .read_u32()
+            .and_then(|length| self.reader.read_bytes(length as usize))
+            .and_then(OscPacket::parse);
+        self.failed = element.is_err();
+        Some(element)
+    }
+}
+
+/// An OSC packet, which is either a message or a bundle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OscPacket<'a> {
+    Message(OscMessage<'a>),
+    Bundle(OscBundle<'a>),
+}
+
+impl<'a> OscPacket<'a> {
+    /// Parse a packet from its binary form, for example the content of a UDP datagram.
+    ///
+    /// Only the address and type tags of a message and the time tag of a bundle are checked. The arguments and elements are parsed on demand and therefore, errors in them are only detected when they are read or when the packet is converted.
+    pub fn parse(data: &'a [u8]) -> Result<Self, OscError> {
+        if !data.len().is_multiple_of(4) {
+            return Err(OscError::Malformed);
+        }
+        if data.starts_with(OscBundle::IDENTIFIER) {
+            OscBundle::parse(data).map(OscPacket::Bundle)
+        } else {
+            OscMessage::parse(data).map(OscPacket::Message)
+        }
+    }
+
+    fn header(&self, osc_urids: &OscURIDCollection) -> ObjectHeader {
+        let otype = match self {
+            OscPacket::Message(_) => osc_urids.message.into_general(),
+            OscPacket::Bundle(_) => osc_urids.bundle.into_general(),
+        };
+        ObjectHeader { id: None, otype }
+    }
+
+    fn write_properties(
+        &self,
+        writer: &mut ObjectWriter,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Result<(), OscError> {
+        match self {
+            OscPacket::Message(message) => message.write_atom(writer, atom_urids, osc_urids),
+            OscPacket::Bundle(bundle) => bundle.write_atom(writer, atom_urids, osc_urids),
+        }
+    }
+
+    /// Write the packet as an atom object.
+    ///
+    /// If an error occurs, the space contains an incomplete atom, which should be discarded.
+    pub fn write_atom(
+        &self,
+        space: &mut dyn MutSpace,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Result<(), OscError> {
+        let mut writer = space
+            .init(atom_urids.object, self.header(osc_urids))
+            .ok_or(OscError::InsufficientSpace)?;
+        self.write_properties(&mut writer, atom_urids, osc_urids)
+    }
+
+    /// Write a new packet in its binary form and return its length.
+    ///
+    /// The packet is written with the address and type tags of the message or the time tag of the bundle, followed by the given arguments. The elements of a bundle are written with [`write_osc`](fn.write_osc.html) from atoms, which is why a bundle must not have arguments. If the arguments don't match the type tags, `None` is returned, as well as if the buffer is too small.
+    pub fn write_osc(&self, buffer: &mut [u8], arguments: &[OscArgument]) -> Option<usize> {
+        let mut cursor = Cursor {
+            buffer,
+            position: 0,
+        };
+        match self {
+            OscPacket::Message(message) => {
+                if !message.type_tags[1..]
+                    .chars()
+                    .eq(arguments.iter().map(OscArgument::type_tag))
+                {
+                    return None;
+                }
+                cursor.write_string(message.address)?;
+                cursor.write_string(message.type_tags)?;
+                for argument in arguments {
+                    argument.write_osc(&mut cursor)?;
+                }
+            }
+            OscPacket::Bundle(bundle) => {
+                if !arguments.is_empty() {
+                    return None;
+                }
+                cursor.write(OscBundle::IDENTIFIER)?;
+                cursor.write(&bundle.timetag.to_bits().to_be_bytes())?;
+            }
+        }
+        Some(cursor.position)
+    }
+}
+
+/// An iterator over the arguments of a message atom.
+///
+/// The iteration stops after the first error.
+#[derive(Clone)]
+pub struct AtomArguments<'a, 'b> {
+    tuple: TupleIterator<'a>,
+    atom_urids: &'b AtomURIDCollection,
+    osc_urids: &'b OscURIDCollection,
+    failed: bool,
+}
+
+impl<'a, 'b> Iterator for AtomArguments<'a, 'b> {
+    type Item = Result<OscArgument<'a>, OscError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let argument = OscArgument::read_atom(self.tuple.next()?, self.atom_urids, self.osc_urids);
+        self.failed = argument.is_err();
+        Some(argument)
+    }
+}
+
+/// The properties of a packet atom.
+enum PacketAtom<'a> {
+    Message {
+        address: &'a str,
+        arguments: TupleIterator<'a>,
+    },
+    Bundle {
+        timetag: TimeTag,
+        items: TupleIterator<'a>,
+    },
+}
+
+impl<'a> PacketAtom<'a> {
+    fn read(
+        atom: UnidentifiedAtom<'a>,
+        atom_urids: &AtomURIDCollection,
+        osc_urids: &OscURIDCollection,
+    ) -> Result<Self, OscError> {
+        let (header, reader) = atom
+            .read(atom_urids.object, ())
+            .ok_or(OscError::NotAPacket)?;
+        if header.otype == osc_urids.message {
+            let mut address = None;
+            let mut arguments = None;
+            for (property, value) in reader {
+                if property.key == osc_urids.message_path {
+                    address = value.read(atom_urids.string, ());
+                } else if property.key == osc_urids.message_arguments {
+                    arguments = value.read(atom_urids.tuple, ());
+                }
+            }
+            match (address, arguments) {
+                (Some(address), Some(arguments)) => Ok(PacketAtom::Message { address, arguments }),
+                _ => Err(OscError::NotAPacket),
+            }
+        } else if header.otype == osc_urids.bundle {
+            let mut timetag = None;
+            let mut items = None;
+            for (property, value) in reader {
+                if property.key == osc_urids.bundle_timetag {
+                    timetag = TimeTag::read_atom(value, atom_urids, osc_urids);
+                } else if property.key == osc_urids.bundle_items {
+                    items = value.read(atom_urids.tuple, ());
+                }
+            }
+            match (timetag, items) {
+                (Some(timetag), Some(items)) => Ok(PacketAtom::Bundle { timetag, items }),
+                _ => Err(OscError::NotAPacket),
+            }
+        } else {
+            Err(OscError::NotAPacket)
+        }
+    }
+}
+
+/// Read the address and the arguments of a message atom.
+///
+/// Returns a [`NotAPacket`](enum.OscError.html#variant.NotAPacket) error if the atom isn't a message object with an address and arguments.
+pub fn read_message<'a, 'b>(
+    atom: UnidentifiedAtom<'a>,
+    atom_urids: &'b AtomURIDCollection,
+    osc_urids: &'b OscURIDCollection,
+) -> Result<(&'a str, AtomArguments<'a, 'b>), OscError> {
+    match PacketAtom::read(atom, atom_urids, osc_urids)? {
+        PacketAtom::Message { address, arguments } => Ok((
+            address,
+            AtomArguments {
+                tuple: arguments,
+                atom_urids,
+                osc_urids,
+                failed: false,
+            },
+        )),
+        PacketAtom::Bundle { .. } => Err(OscError::NotAPacket),
+    }
+}
+
+/// Write a message or bundle atom as a binary OSC packet and return its length.
+///
+/// This is the reverse of [`OscPacket::write_atom`](enum.OscPacket.html#method.write_atom): The packet can be sent over the network or parsed again.
+pub fn write_osc(
+    atom: UnidentifiedAtom,
+    buffer: &mut [u8],
+    atom_urids: &AtomURIDCollection,
+    osc_urids: &OscURIDCollection,
+) -> Result<usize, OscError> {
+    let mut cursor = Cursor {
+        buffer,
+        position: 0,
+    };
+    write_packet(atom, &mut cursor, atom_urids, osc_urids)?;
+    Ok(cursor.position)
+}
+
+fn write_packet(
+    atom: UnidentifiedAtom,
+    cursor: &mut Cursor,
+    atom_urids: &AtomURIDCollection,
+    osc_urids: &OscURIDCollection,
+) -> Result<(), OscError> {
+    match PacketAtom::read(atom, atom_urids, osc_urids)? {
+        PacketAtom::Message { address, arguments } => {
+            let arguments = AtomArguments {
+                tuple: arguments,
+                atom_urids,
+                osc_urids,
+                failed: false,
+            };
+            cursor
+                .write_string(address)
+                .ok_or(OscError::InsufficientSpace)?;
+
+            // The type tags are written first, which is why the arguments are read twice.
+            let mut tag_buffer = [0; 4];
+            cursor.write(b",").ok_or(OscError::InsufficientSpace)?;
+            for argument in arguments.clone() {
+                let tag = argument?.type_tag().encode_utf8(&mut tag_buffer);
+                cursor
+                    .write(tag.as_bytes())
+                    .ok_or(OscError::InsufficientSpace)?;
+            }
+            cursor
+                .write(&[0])
+                .and_then(|_| cursor.pad())
+                .ok_or(OscError::InsufficientSpace)?;
+
+            for argument in arguments {
+                argument?
+                    .write_osc(cursor)
+                    .ok_or(OscError::InsufficientSpace)?;
+            }
+        }
+        PacketAtom::Bundle { timetag, items } => {
+            cursor
+                .write(OscBundle::IDENTIFIER)
+                .and_then(|_| cursor.write(&timetag.to_bits().to_be_bytes()))
+                .ok_or(OscError::InsufficientSpace)?;
+            for item in items {
+                // The length of the element is only known after it has been written.
+                let length_position = cursor.position;
+                cursor.write(&[0; 4]).ok_or(OscError::InsufficientSpace)?;
+                write_packet(item, cursor, atom_urids, osc_urids)?;
+                let length = (cursor.position - length_position - 4) as u32;
+                cursor.buffer[length_position..length_position + 4]
+                    .copy_from_slice(&length.to_be_bytes());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osc::*;
+    use urid::*;
+
+    const ARGUMENTS: &[OscArgument] = &[
+        OscArgument::Int(-42),
+        OscArgument::Float(0.5),
+        OscArgument::String("hello"),
+        OscArgument::Blob(&[1, 2, 3, 4, 5]),
+        OscArgument::Long(1 << 40),
+        OscArgument::Double(-0.25),
+        OscArgument::Timetag(TimeTag {
+            seconds: 3_800_000_000,
+            fraction: 1 << 31,
+        }),
+        OscArgument::Bool(true),
+        OscArgument::Bool(false),
+        OscArgument::Nil,
+        OscArgument::Impulse,
+        OscArgument::Char('x'),
+        OscArgument::Rgba(0xff80_00ff),
+    ];
+
+    #[test]
+    fn test_message() {
+        let map = HashURIDMapper::new();
+        let atom_urids = AtomURIDCollection::from_map(&map).unwrap();
+        let osc_urids = OscURIDCollection::from_map(&map).unwrap();
+
+        let mut packet = [0u8; 256];
+        let message = OscMessage::new("/synth/voice", ",ifsbhdtTFNIcr").unwrap();
+        let length = OscPacket::Message(message)
+            .write_osc(&mut packet, ARGUMENTS)
+            .unwrap();
+        let packet = &packet[..length];
+        assert_eq!(&packet[..16], b"/synth/voice\0\0\0\0");
+        assert_eq!(length % 4, 0);
+
+        // parsing
+        let parsed = match OscPacket::parse(packet).unwrap() {
+            OscPacket::Message(message) => message,
+            OscPacket::Bundle(_) => panic!("parsed a message as a bundle"),
+        };
+        assert_eq!(parsed.address(), "/synth/voice");
+        assert_eq!(parsed.type_tags(), ",ifsbhdtTFNIcr");
+        let arguments: Vec<_> = parsed.arguments().map(Result::unwrap).collect();
+        assert_eq!(arguments, ARGUMENTS);
+
+        // converting to an atom
+        let mut raw_space: Box<[u8]> = Box::new([0; 1024]);
+        OscPacket::Message(parsed)
+            .write_atom(
+                &mut RootMutSpace::new(raw_space.as_mut()),
+                &atom_urids,
+                &osc_urids,
+            )
+            .unwrap();
+
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        let (header, _) = atom.read(atom_urids.object, ()).unwrap();
+        assert_eq!(header.otype, osc_urids.message);
+        let (address, arguments) = read_message(atom, &atom_urids, &osc_urids).unwrap();
+        assert_eq!(address, "/synth/voice");
+        let arguments: Vec<_> = arguments.map(Result::unwrap).collect();
+        assert_eq!(arguments, ARGUMENTS);
+
+        // converting back
+        let mut output = [0u8; 256];
+        let output_length = write_osc(atom, &mut output, &atom_urids, &osc_urids).unwrap();
+        assert_eq!(&output[..output_length], packet);
+        assert_eq!(
+            write_osc(atom, &mut output[..length - 1], &atom_urids, &osc_urids),
+            Err(OscError::InsufficientSpace)
+        );
+    }
+
+    #[test]
+    fn test_bundle() {
+        let map = HashURIDMapper::new();
+        let atom_urids = AtomURIDCollection::from_map(&map).unwrap();
+        let osc_urids = OscURIDCollection::from_map(&map).unwrap();
+
+        let mut first = [0u8; 32];
+        let first_length = OscPacket::Message(OscMessage::new("/a", ",i").unwrap())
+            .write_osc(&mut first, &[OscArgument::Int(1)])
+            .unwrap();
+        let mut second = [0u8; 32];
+        let second_length = OscPacket::Message(OscMessage::new("/b", ",").unwrap())
+            .write_osc(&mut second, &[])
+            .unwrap();
+
+        // A bundle that contains the first message and a nested bundle with the second message.
+        let timetag = TimeTag::from_bits(0x1234_5678_9abc_def0);
+        let mut inner = [0u8; 64];
+        let mut inner_length = OscPacket::Bundle(OscBundle::new(TimeTag::IMMEDIATELY))
+            .write_osc(&mut inner, &[])
+            .unwrap();
+        inner[inner_length..inner_length + 4]
+            .copy_from_slice(&(second_length as u32).to_be_bytes());
+        inner[inner_length + 4..inner_length + 4 + second_length]
+            .copy_from_slice(&second[..second_length]);
+        inner_length += 4 + second_length;
+
+        let mut packet = [0u8; 128];
+        let mut length = OscPacket::Bundle(OscBundle::new(timetag))
+            .write_osc(&mut packet, &[])
+            .unwrap();
+        for element in [&first[..first_length], &inner[..inner_length]].iter() {
+            packet[length..length + 4].copy_from_slice(&(element.len() as u32).to_be_bytes());
+            packet[length + 4..length + 4 + element.len()].copy_from_slice(element);
+            length += 4 + element.len();
+        }
+        let packet = &packet[..length];
+
+        let bundle = match OscPacket::parse(packet).unwrap() {
+            OscPacket::Bundle(bundle) => bundle,
+            OscPacket::Message(_) => panic!("parsed a bundle as a message"),
+        };
+        assert_eq!(bundle.timetag(), timetag);
+        assert!(!bundle.timetag().is_immediately());
+        let elements: Vec<_> = bundle.elements().map(Result::unwrap).collect();
+        assert_eq!(elements.len(), 2);
+        match elements[1] {
+            OscPacket::Bundle(inner) => assert!(inner.timetag().is_immediately()),
+            OscPacket::Message(_) => panic!("parsed a bundle as a message"),
+        }
+
+        let mut raw_space: Box<[u8]> = Box::new([0; 1024]);
+        OscPacket::Bundle(bundle)
+            .write_atom(
+                &mut RootMutSpace::new(raw_space.as_mut()),
+                &atom_urids,
+                &osc_urids,
+            )
+            .unwrap();
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        assert_eq!(
+            read_message(atom, &atom_urids, &osc_urids).err(),
+            Some(OscError::NotAPacket)
+        );
+
+        let mut output = [0u8; 128];
+        let output_length = write_osc(atom, &mut output, &atom_urids, &osc_urids).unwrap();
+        assert_eq!(&output[..output_length], packet);
+    }
+
+    #[test]
+    fn test_invalid_packets() {
+        let map = HashURIDMapper::new();
+        let atom_urids = AtomURIDCollection::from_map(&map).unwrap();
+        let osc_urids = OscURIDCollection::from_map(&map).unwrap();
+
+        assert_eq!(OscPacket::parse(b"/a\0"), Err(OscError::Malformed));
+        assert_eq!(
+            OscPacket::parse(b"a\0\0\0,\0\0\0"),
+            Err(OscError::Malformed)
+        );
+        assert_eq!(OscPacket::parse(b"/a\0\0i\0\0\0"), Err(OscError::Malformed));
+        assert_eq!(OscPacket::parse(b"#bundle\0"), Err(OscError::Malformed));
+
+        // Arguments are only checked when they are read.
+        let message = match OscPacket::parse(b"/a\0\0,im\0\0\0\0\x01").unwrap() {
+            OscPacket::Message(message) => message,
+            OscPacket::Bundle(_) => panic!("parsed a message as a bundle"),
+        };
+        let arguments: Vec<_> = message.arguments().collect();
+        assert_eq!(
+            arguments,
+            [Ok(OscArgument::Int(1)), Err(OscError::UnsupportedType('m'))]
+        );
+        let truncated = OscPacket::parse(b"/a\0\0,ii\0\0\0\0\x01").unwrap();
+        let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+        assert_eq!(
+            truncated.write_atom(
+                &mut RootMutSpace::new(raw_space.as_mut()),
+                &atom_urids,
+                &osc_urids,
+            ),
+            Err(OscError::Malformed)
+        );
+
+        // Messages with arguments that don't match their type tags can't be written.
+        let mut packet = [0u8; 32];
+        assert_eq!(
+            OscPacket::Message(OscMessage::new("/a", ",f").unwrap())
+                .write_osc(&mut packet, &[OscArgument::Int(1)]),
+            None
+        );
+        assert_eq!(OscMessage::new("a", ","), None);
+
+        // Atoms that can't be represented in OSC.
+        {
+            let mut space = RootMutSpace::new(raw_space.as_mut());
+            let mut object = (&mut space as &mut dyn MutSpace)
+                .init(
+                    atom_urids.object,
+                    ObjectHeader {
+                        id: None,
+                        otype: osc_urids.message.into_general(),
+                    },
+                )
+                .unwrap();
+            object
+                .init(osc_urids.message_path, None, atom_urids.string, ())
+                .unwrap()
+                .append("/a")
+                .unwrap();
+            object
+                .init(osc_urids.message_arguments, None, atom_urids.tuple, ())
+                .unwrap()
+                .init(atom_urids.urid, atom_urids.int.into_general())
+                .unwrap();
+        }
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        assert_eq!(
+            write_osc(atom, &mut packet, &atom_urids, &osc_urids),
+            Err(OscError::UnsupportedAtom)
+        );
+        let atom = UnidentifiedAtom::new(Space::from_slice(&[0; 16]));
+        assert_eq!(
+            write_osc(atom, &mut packet, &atom_urids, &osc_urids),
+            Err(OscError::NotAPacket)
+        );
+    }
+}
@@ -24,10 +24,10 @@
 //! }
 //! ```
 use crate::space::*;
+use core::ffi::c_void;
+use core::ptr::NonNull;
 use lv2_core::port::PortType;
-use std::ffi::c_void;
-use std::ptr::NonNull;
-use urid::URID;
+use urid::{Uri, UriBound, URID};
 
 /// A handle to read atoms from a port.
 ///
@@ -54,6 +54,13 @@ impl<'a> PortReader<'a> {
     ) -> Option<A::ReadHandle> {
         A::read(self.space.split_atom_body(urid)?.0, parameter)
     }
+
+    /// Retrieve the raw header of the atom in the port.
+    ///
+    /// See [`UnidentifiedAtom::as_raw`](../struct.UnidentifiedAtom.html#method.as_raw) for how it can be used.
+    pub fn as_raw(&self) -> Option<&'a sys::LV2_Atom> {
+        crate::UnidentifiedAtom::new(self.space).as_raw()
+    }
 }
 
 /// A handle to write atoms into a port.
@@ -92,6 +99,22 @@ impl<'a> PortWriter<'a> {
             None
         }
     }
+
+    /// Retrieve the raw buffer of the port, to write an atom into it with a C library like the atom forge.
+    ///
+    /// The buffer starts with the header of the atom and is as long as the space the host provides. Like [`init`](#method.init), this method can be called once only and only if no atom has been written yet. Otherwise, `None` is returned.
+    ///
+    /// # Safety
+    ///
+    /// When the `run` method of the plugin returns, the buffer has to contain a valid atom, whose header is at the start of the buffer and whose body fits into the buffer. Otherwise, the host reads an invalid atom or even memory behind the buffer.
+    pub unsafe fn as_raw_buffer(&mut self) -> Option<&'a mut [u8]> {
+        if self.has_been_written {
+            return None;
+        }
+        self.has_been_written = true;
+        let size = self.space.remaining()?;
+        self.space.allocate(size, false).map(|(_, buffer)| buffer)
+    }
 }
 
 /// The port type for Atom IO.
@@ -101,6 +124,10 @@ impl<'a> PortWriter<'a> {
 /// [See also the module documentation.](index.html)
 pub struct AtomPort;
 
+unsafe impl UriBound for AtomPort {
+    const URI: &'static [u8] = sys::LV2_ATOM__AtomPort;
+}
+
 impl PortType for AtomPort {
     type InputPortType = PortReader<'static>;
     type OutputPortType = PortWriter<'static>;
@@ -114,15 +141,19 @@ impl PortType for AtomPort {
         let space = RootMutSpace::from_atom(pointer.cast().as_mut());
         PortWriter::new(space)
     }
+
+    fn class() -> Option<&'static Uri> {
+        Some(Self::uri())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
     use crate::space::*;
+    use core::mem::size_of;
+    use core::ptr::NonNull;
     use lv2_core::prelude::*;
-    use std::mem::size_of;
-    use std::ptr::NonNull;
     use urid::*;
 
     #[test]
@@ -157,4 +188,44 @@ mod tests {
             assert_eq!(reader.read::<Int>(urids.int, ()).unwrap(), 42);
         }
     }
+
+    #[test]
+    fn test_raw_atom_port() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let mut raw_space: Box<[u64]> = Box::new([0; 32]);
+        let raw_space =
+            unsafe { core::slice::from_raw_parts_mut(raw_space.as_mut_ptr() as *mut u8, 256) };
+        unsafe { (*(raw_space.as_mut_ptr() as *mut sys::LV2_Atom)).size = 248 };
+
+        // Writing an atom like a C library would.
+        {
+            let mut writer =
+                unsafe { AtomPort::output_from_raw(NonNull::from(&mut *raw_space).cast(), 0) };
+            let buffer = unsafe { writer.as_raw_buffer() }.unwrap();
+            assert_eq!(buffer.len(), 256);
+            let atom = sys::LV2_Atom_Int {
+                atom: sys::LV2_Atom {
+                    size: size_of::<i32>() as u32,
+                    type_: urids.int.get(),
+                },
+                body: 17,
+            };
+            unsafe { (buffer.as_mut_ptr() as *mut sys::LV2_Atom_Int).write(atom) };
+
+            assert!(unsafe { writer.as_raw_buffer() }.is_none());
+            assert!(writer.init::<Int>(urids.int, 42).is_none());
+        }
+
+        // Reading the raw atom.
+        {
+            let reader =
+                unsafe { AtomPort::input_from_raw(NonNull::from(&mut *raw_space).cast(), 0) };
+            let atom = reader.as_raw().unwrap();
+            assert_eq!(atom.size, size_of::<i32>() as u32);
+            assert_eq!(atom.type_, urids.int.get());
+            assert_eq!(reader.read::<Int>(urids.int, ()).unwrap(), 17);
+        }
+    }
 }
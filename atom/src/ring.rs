@@ -0,0 +1,357 @@
+//! A ring buffer to send atoms from one thread to another.
+//!
+//! Plugins often have to send messages from the `run` method to another thread, like the thread of an in-process UI or of a worker, or back. The `run` method must not block or allocate memory, which is why these messages are usually sent with a lock-free ring buffer. This module provides such a ring buffer for atoms: A [`channel`](fn.channel.html) consists of a [`RingWriter`](struct.RingWriter.html) and a [`RingReader`](struct.RingReader.html) and since there is only one of each, they can be used without locks. Only the creation of the channel allocates memory.
+//!
+//! Every message is a single atom, which is written to the ring buffer with the atom writing methods, or copied from an existing atom. The reader receives it as an [`UnidentifiedAtom`](../struct.UnidentifiedAtom.html).
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::ring::*;
+//! use urid::*;
+//!
+//! let map = HashURIDMapper::new();
+//! let urids = AtomURIDCollection::from_map(&map).unwrap();
+//!
+//! let (mut writer, mut reader) = channel(1024);
+//!
+//! // In the `run` method of the plugin...
+//! writer
+//!     .write(|space| space.init(urids.int, 42).map(|_| ()))
+//!     .unwrap();
+//!
+//! // ...and in the other thread.
+//! let value = reader.read(|atom| atom.read(urids.int, ())).unwrap();
+//! assert_eq!(value, Some(42));
+//! assert!(reader.is_empty());
+//! ```
+use crate::space::*;
+use crate::UnidentifiedAtom;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::UnsafeCell;
+use core::mem::size_of;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The size of an atom header and the alignment of messages.
+const HEADER_SIZE: usize = size_of::<sys::LV2_Atom>();
+
+/// The number of bytes a message with the given body size occupies.
+fn message_size(body_size: usize) -> usize {
+    HEADER_SIZE + body_size.div_ceil(8) * 8
+}
+
+/// The state that is shared between the writer and the reader.
+///
+/// Both positions are counters of the bytes that have been written and read, respectively. They are only reduced to an offset in the buffer when the buffer is accessed, which is why the capacity has to be a power of two.
+struct Shared {
+    buffer: Box<[UnsafeCell<u64>]>,
+    write: AtomicUsize,
+    read: AtomicUsize,
+}
+
+// The writer only accesses free bytes and the reader only accesses written bytes, which are synchronized by the positions.
+unsafe impl Sync for Shared {}
+
+impl Shared {
+    fn capacity(&self) -> usize {
+        self.buffer.len() * size_of::<u64>()
+    }
+
+    fn ptr(&self, offset: usize) -> *mut u8 {
+        unsafe { (self.buffer.as_ptr() as *mut u8).add(offset) }
+    }
+}
+
+/// Create a new ring buffer, which can hold at least `capacity` bytes of messages.
+///
+/// The capacity is rounded up to a power of two. Every message occupies the size of its atom, padded to 64 bits. Messages don't wrap around the end of the buffer, which is why the space at the end may be skipped if a message doesn't fit in it anymore.
+pub fn channel(capacity: usize) -> (RingWriter, RingReader) {
+    let capacity = capacity.max(2 * HEADER_SIZE).next_power_of_two();
+    let buffer: Vec<UnsafeCell<u64>> = (0..capacity / size_of::<u64>())
+        .map(|_| UnsafeCell::new(0))
+        .collect();
+    let shared = Arc::new(Shared {
+        buffer: buffer.into_boxed_slice(),
+        write: AtomicUsize::new(0),
+        read: AtomicUsize::new(0),
+    });
+    (
+        RingWriter {
+            shared: shared.clone(),
+        },
+        RingReader { shared },
+    )
+}
+
+/// The writing end of a ring buffer.
+///
+/// None of the methods block or allocate memory, which is why the writer may be used in the `run` method of a plugin.
+pub struct RingWriter {
+    shared: Arc<Shared>,
+}
+
+impl RingWriter {
+    /// Return the number of bytes the ring buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Return the offset of the next message and the number of free bytes, as well as the number of bytes at the end of the buffer.
+    fn free(&self) -> (usize, usize, usize) {
+        let write = self.shared.write.load(Ordering::Relaxed);
+        let read = self.shared.read.load(Ordering::Acquire);
+        let capacity = self.shared.capacity();
+        let offset = write & (capacity - 1);
+        (
+            offset,
+            capacity - write.wrapping_sub(read),
+            capacity - offset,
+        )
+    }
+
+    /// Publish a message that was written at the given offset.
+    ///
+    /// If the message was written to the start of the buffer, the rest of the buffer is skipped with an atom that has no type.
+    fn commit(&mut self, offset: usize, wrapped: bool, size: usize) {
+        let write = self.shared.write.load(Ordering::Relaxed);
+        let mut skipped = 0;
+        if wrapped {
+            let end = self.shared.capacity() - offset;
+            let skip = sys::LV2_Atom {
+                size: (end - HEADER_SIZE) as u32,
+                type_: 0,
+            };
+            unsafe { (self.shared.ptr(offset) as *mut sys::LV2_Atom).write(skip) };
+            skipped = end;
+        }
+        self.shared
+            .write
+            .store(write.wrapping_add(skipped + size), Ordering::Release);
+    }
+
+    /// Write a message with the atom writing methods.
+    ///
+    /// The closure receives the space of the message, in which it has to initialize exactly one atom. If the closure fails or doesn't initialize an atom, the message is discarded. Since the size of the message isn't known in advance, the space is the largest block of contiguous, free memory.
+    ///
+    /// Returns `None` if the message has been discarded.
+    pub fn write<F>(&mut self, f: F) -> Option<()>
+    where
+        F: for<'r> FnOnce(&mut (dyn MutSpace<'r> + 'r)) -> Option<()>,
+    {
+        let (offset, free, end) = self.free();
+        let (start, len, wrapped) = if free <= end {
+            (offset, free, false)
+        } else if end >= free - end {
+            (offset, end, false)
+        } else {
+            (0, free - end, true)
+        };
+        if len < HEADER_SIZE {
+            return None;
+        }
+
+        let region = unsafe { core::slice::from_raw_parts_mut(self.shared.ptr(start), len) };
+        // The header of a previous message may still be there.
+        region[..HEADER_SIZE].copy_from_slice(&[0; HEADER_SIZE]);
+        f(&mut RootMutSpace::new(&mut *region))?;
+
+        let header = unsafe { &*(region.as_ptr() as *const sys::LV2_Atom) };
+        if header.type_ == 0 {
+            return None;
+        }
+        self.commit(offset, wrapped, message_size(header.size as usize));
+        Some(())
+    }
+
+    /// Copy an atom to the ring buffer.
+    ///
+    /// Returns `None` if there isn't enough space for the atom.
+    pub fn forward(&mut self, atom: UnidentifiedAtom) -> Option<()> {
+        atom.type_urid()?;
+        let data = atom.space.data()?;
+        let size = message_size(data.len().checked_sub(HEADER_SIZE)?);
+
+        let (offset, free, end) = self.free();
+        let (start, wrapped) = if size <= free.min(end) {
+            (offset, false)
+        } else if free >= end + size {
+            (0, true)
+        } else {
+            return None;
+        };
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(data.as_ptr(), self.shared.ptr(start), data.len());
+        }
+        self.commit(offset, wrapped, size);
+        Some(())
+    }
+}
+
+/// The reading end of a ring buffer.
+///
+/// None of the methods block or allocate memory.
+pub struct RingReader {
+    shared: Arc<Shared>,
+}
+
+impl RingReader {
+    /// Return the number of bytes the ring buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Return `true` if there are no messages to read.
+    pub fn is_empty(&self) -> bool {
+        self.shared.read.load(Ordering::Relaxed) == self.shared.write.load(Ordering::Acquire)
+    }
+
+    /// Read the next message.
+    ///
+    /// The message is passed to the closure and removed from the ring buffer afterwards. Returns `None` if there are no messages to read.
+    pub fn read<R, F>(&mut self, f: F) -> Option<R>
+    where
+        F: for<'r> FnOnce(UnidentifiedAtom<'r>) -> R,
+    {
+        let capacity = self.shared.capacity();
+        loop {
+            let read = self.shared.read.load(Ordering::Relaxed);
+            if read == self.shared.write.load(Ordering::Acquire) {
+                return None;
+            }
+            let offset = read & (capacity - 1);
+            let header = unsafe { &*(self.shared.ptr(offset) as *const sys::LV2_Atom) };
+            let size = message_size(header.size as usize);
+
+            // Skip the end of the buffer.
+            if header.type_ == 0 {
+                self.shared
+                    .read
+                    .store(read.wrapping_add(size), Ordering::Release);
+                continue;
+            }
+
+            let data = unsafe {
+                core::slice::from_raw_parts(
+                    self.shared.ptr(offset),
+                    HEADER_SIZE + header.size as usize,
+                )
+            };
+            let result = f(UnidentifiedAtom::new(Space::from_slice(data)));
+            self.shared
+                .read
+                .store(read.wrapping_add(size), Ordering::Release);
+            return Some(result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::ring::*;
+    use crate::space::RootMutSpace;
+    use urid::*;
+
+    #[test]
+    fn test_ring() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+
+        let (mut writer, mut reader) = channel(60);
+        assert_eq!(writer.capacity(), 64);
+        assert_eq!(reader.capacity(), 64);
+        assert!(reader.read(|_| ()).is_none());
+
+        // Messages are read in the order they are written.
+        writer
+            .write(|space| space.init(urids.int, 1).map(|_| ()))
+            .unwrap();
+        writer
+            .write(|space| space.init(urids.long, 2).map(|_| ()))
+            .unwrap();
+        assert_eq!(reader.read(|atom| atom.read(urids.int, ())), Some(Some(1)));
+        assert_eq!(reader.read(|atom| atom.read(urids.long, ())), Some(Some(2)));
+        writer
+            .write(|space| space.init(urids.int, 3).map(|_| ()))
+            .unwrap();
+        assert_eq!(reader.read(|atom| atom.read(urids.int, ())), Some(Some(3)));
+        assert!(reader.is_empty());
+
+        // Messages that weren't initialized or don't fit are discarded.
+        assert!(writer.write(|_| Some(())).is_none());
+        assert!(writer.write(|_| None).is_none());
+        assert!(writer
+            .write(|space| space
+                .init(urids.vector::<Int>(), urids.int)
+                .and_then(|mut writer| {
+                    writer.append(&[0; 16])?;
+                    Some(())
+                }))
+            .is_none());
+        assert!(reader.is_empty());
+
+        // Copy an atom. This one doesn't fit at the end of the buffer anymore.
+        let mut raw_space: Box<[u8]> = Box::new([0; 32]);
+        {
+            let mut space = RootMutSpace::new(raw_space.as_mut());
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.vector::<Int>(), urids.int)
+                .unwrap();
+            writer.append(&[1, 2, 3]).unwrap();
+        }
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        writer.forward(atom).unwrap();
+        assert_eq!(
+            reader.read(|atom| atom
+                .read(urids.vector::<Int>(), urids.int)
+                .map(<[i32]>::to_vec)),
+            Some(Some(vec![1, 2, 3]))
+        );
+        assert!(reader.is_empty());
+
+        // The buffer is full.
+        writer.forward(atom).unwrap();
+        writer.forward(atom).unwrap();
+        assert!(writer.forward(atom).is_none());
+        assert!(writer
+            .write(|space| space.init(urids.int, 3).map(|_| ()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_ring_threads() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+        let int = urids.int;
+
+        let (mut writer, mut reader) = channel(64);
+        let thread = std::thread::spawn(move || {
+            let mut value = 0;
+            while value < 1000 {
+                if writer
+                    .write(|space| space.init(int, value).map(|_| ()))
+                    .is_some()
+                {
+                    value += 1;
+                } else {
+                    std::thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < 1000 {
+            if let Some(value) = reader.read(|atom| atom.read(urids.int, ())) {
+                assert_eq!(value, Some(expected));
+                expected += 1;
+            } else {
+                std::thread::yield_now();
+            }
+        }
+        thread.join().unwrap();
+    }
+}
@@ -32,7 +32,7 @@
 //! [http://lv2plug.in/ns/ext/atom/atom.html#Number](http://lv2plug.in/ns/ext/atom/atom.html#Number)
 use crate::space::*;
 use crate::*;
-use std::marker::Unpin;
+use core::marker::Unpin;
 use urid::UriBound;
 use urid::URID;
 
@@ -49,8 +49,8 @@ pub trait ScalarAtom: UriBound {
     ///
     /// If the space does not contain the atom or is not big enough, return `None`. The second return value is the space behind the atom.
     fn read_scalar(body: Space) -> Option<Self::InternalType> {
-        body.split_type::<Self::InternalType>()
-            .map(|(value, _)| *value)
+        body.read_type::<Self::InternalType>()
+            .map(|(value, _)| value)
     }
 
     /// Try to write the atom into a space.
@@ -165,14 +165,14 @@ mod tests {
     use crate::prelude::*;
     use crate::scalar::ScalarAtom;
     use crate::space::*;
-    use std::convert::TryFrom;
-    use std::mem::size_of;
+    use core::convert::TryFrom;
+    use core::mem::size_of;
     use urid::*;
 
     fn test_scalar<A: ScalarAtom>(value: A::InternalType)
     where
         A::InternalType: PartialEq<A::InternalType>,
-        A::InternalType: std::fmt::Debug,
+        A::InternalType: core::fmt::Debug,
     {
         let map = HashURIDMapper::new();
         let urid: URID<A> = map.map_type().unwrap();
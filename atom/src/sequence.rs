@@ -62,11 +62,46 @@
 //! }
 //! ```
 //!
+//! # Event kinds
+//!
+//! Instead of checking the type of every atom, the iterator can also pick out the events of one [kind](trait.EventKind.html) with [`of_type`](struct.SequenceIterator.html#method.of_type), or tell two kinds apart with [`split`](struct.SequenceIterator.html#method.split). Atom URIDs are event kinds, as well as pairs of an atom URID and its reading parameter:
+//!
+//! ```
+//! # use lv2_atom::prelude::*;
+//! # use lv2_atom::sequence::*;
+//! fn handle_events(input_sequence: SequenceIterator, urids: &AtomURIDCollection) {
+//!     for (timestamp, event) in input_sequence.split(urids.int, urids.float) {
+//!         match event {
+//!             SplitEvent::First(integer) => (),
+//!             SplitEvent::Second(float) => (),
+//!             SplitEvent::Other(atom) => (),
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! # Capacity
+//!
+//! The space of an output sequence is set by the host. The writer reports its [`capacity`](struct.SequenceWriter.html#method.capacity) and how many bytes are [`remaining`](struct.SequenceWriter.html#method.remaining), and its [`OverflowPolicy`](enum.OverflowPolicy.html) decides what happens to events that don't fit anymore. By default, writing them fails, but they can also be dropped, or older events can be dropped to make room for them:
+//!
+//! ```
+//! # use lv2_atom::sequence::*;
+//! # use urid::*;
+//! fn write_events(writer: &mut SequenceWriter, resync: URID) {
+//!     writer.set_overflow_policy(OverflowPolicy::DropOldest(resync));
+//!     // Writing the events...
+//!     if writer.statistics().dropped > 0 {
+//!         // Report the overflow.
+//!     }
+//! }
+//! ```
+//!
 //! # Specification
 //!
 //! [http://lv2plug.in/ns/ext/atom/atom.html#Sequence](http://lv2plug.in/ns/ext/atom/atom.html#Sequence)
 use crate::space::*;
 use crate::*;
+use core::mem::size_of;
 use sys::LV2_Atom_Event__bindgen_ty_1 as RawTimeStamp;
 use units::prelude::*;
 use urid::*;
@@ -118,6 +153,12 @@ where
             frame,
             unit: unit.into(),
             last_stamp: None,
+            policy: OverflowPolicy::Error,
+            statistics: SequenceStatistics::default(),
+            keep: size_of::<sys::LV2_Atom_Sequence_Body>(),
+            last_initialized: false,
+            resync: None,
+            recoverable: true,
         })
     }
 }
@@ -166,6 +207,131 @@ impl TimeStamp {
             _ => None,
         }
     }
+
+    /// Return the time stamp as a frame time, if it's measured in frames.
+    pub fn frame_time(self) -> Option<FrameTime> {
+        self.as_frames().map(FrameTime)
+    }
+
+    /// Return the time stamp as a beat time, if it's measured in beats.
+    pub fn beat_time(self) -> Option<BeatTime> {
+        self.as_bpm().map(BeatTime)
+    }
+}
+
+impl From<FrameTime> for TimeStamp {
+    fn from(time: FrameTime) -> Self {
+        TimeStamp::Frames(time.0)
+    }
+}
+
+impl From<BeatTime> for TimeStamp {
+    fn from(time: BeatTime) -> Self {
+        TimeStamp::BeatsPerMinute(time.0)
+    }
+}
+
+/// A point in time, measured in audio frames.
+///
+/// Frame times of events are relative to the start of the current block, which means that an event in a block of `n` frames is at a frame time from `0` to `n - 1`. The arithmetic methods of this type are checked, and conversions to other units require the sample rate or the tempo, which makes confusing frames with other units explicit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FrameTime(pub i64);
+
+impl FrameTime {
+    /// Move the time forward by the given number of frames, or return `None` if an overflow occurred.
+    pub fn checked_add(self, frames: i64) -> Option<Self> {
+        self.0.checked_add(frames).map(Self)
+    }
+
+    /// Move the time backward by the given number of frames, or return `None` if an overflow occurred.
+    pub fn checked_sub(self, frames: i64) -> Option<Self> {
+        self.0.checked_sub(frames).map(Self)
+    }
+
+    /// Return the number of frames from an earlier time to this time.
+    ///
+    /// `None` is returned if the other time is later than this time or if an overflow occurred.
+    pub fn checked_since(self, earlier: Self) -> Option<u64> {
+        self.0
+            .checked_sub(earlier.0)
+            .filter(|frames| *frames >= 0)
+            .map(|frames| frames as u64)
+    }
+
+    /// Return the offset of this time within a block, if the time is within the block.
+    ///
+    /// The block starts at the given time and contains the given number of frames, which means that its last frame is `start + sample_count - 1`.
+    pub fn block_offset(self, start: Self, sample_count: u32) -> Option<u32> {
+        self.checked_since(start)
+            .filter(|offset| *offset < u64::from(sample_count))
+            .map(|offset| offset as u32)
+    }
+
+    /// Create a frame time from a time in seconds.
+    ///
+    /// The time is rounded to the nearest frame.
+    pub fn from_seconds(seconds: f64, sample_rate: f64) -> Self {
+        Self((seconds * sample_rate).round() as i64)
+    }
+
+    /// Convert the time to seconds.
+    pub fn to_seconds(self, sample_rate: f64) -> f64 {
+        self.0 as f64 / sample_rate
+    }
+
+    /// Convert the time to beats, with the given number of frames per beat.
+    pub fn to_beats(self, frames_per_beat: f64) -> BeatTime {
+        BeatTime(self.0 as f64 / frames_per_beat)
+    }
+}
+
+impl From<i64> for FrameTime {
+    fn from(frames: i64) -> Self {
+        Self(frames)
+    }
+}
+
+/// A point in time, measured in beats.
+///
+/// The arithmetic methods of this type are checked: They fail if the result isn't a finite number.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct BeatTime(pub f64);
+
+impl BeatTime {
+    /// Move the time forward by the given number of beats, or return `None` if the result isn't finite.
+    pub fn checked_add(self, beats: f64) -> Option<Self> {
+        Some(self.0 + beats)
+            .filter(|beats| beats.is_finite())
+            .map(Self)
+    }
+
+    /// Move the time backward by the given number of beats, or return `None` if the result isn't finite.
+    pub fn checked_sub(self, beats: f64) -> Option<Self> {
+        self.checked_add(-beats)
+    }
+
+    /// Convert the time to seconds, at the given tempo in beats per minute.
+    pub fn to_seconds(self, beats_per_minute: f64) -> f64 {
+        self.0 * 60.0 / beats_per_minute
+    }
+
+    /// Convert the time to frames, with the given number of frames per beat.
+    ///
+    /// The time is rounded down to the frame it falls into. `None` is returned if the result isn't finite or doesn't fit into a frame time.
+    pub fn to_frames(self, frames_per_beat: f64) -> Option<FrameTime> {
+        let frames = (self.0 * frames_per_beat).floor();
+        if frames.is_finite() && frames >= i64::MIN as f64 && frames < i64::MAX as f64 {
+            Some(FrameTime(frames as i64))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<f64> for BeatTime {
+    fn from(beats: f64) -> Self {
+        Self(beats)
+    }
 }
 
 /// An iterator over all events in a sequence.
@@ -178,13 +344,35 @@ impl<'a> SequenceIterator<'a> {
     pub fn unit(&self) -> TimeStampUnit {
         self.unit
     }
+
+    /// Iterate over the events of the given kind, skipping all other events.
+    ///
+    /// The kind is usually the URID of an atom type, like the MIDI event, and the events are read with it.
+    pub fn of_type<K: EventKind<'a>>(self, kind: K) -> OfType<'a, K> {
+        OfType { events: self, kind }
+    }
+
+    /// Iterate over all events and tell the events of two kinds apart.
+    ///
+    /// Unlike two calls to [`of_type`](#method.of_type), this keeps the order of the events. If an event is of both kinds, it's read as the first kind. Events of neither kind are returned as they are.
+    pub fn split<K1, K2>(self, first: K1, second: K2) -> Split<'a, K1, K2>
+    where
+        K1: EventKind<'a>,
+        K2: EventKind<'a>,
+    {
+        Split {
+            events: self,
+            first,
+            second,
+        }
+    }
 }
 
 impl<'a> Iterator for SequenceIterator<'a> {
     type Item = (TimeStamp, UnidentifiedAtom<'a>);
 
     fn next(&mut self) -> Option<(TimeStamp, UnidentifiedAtom<'a>)> {
-        let (raw_stamp, space) = self.space.split_type::<RawTimeStamp>()?;
+        let (raw_stamp, space) = self.space.read_type::<RawTimeStamp>()?;
         let stamp = match self.unit {
             TimeStampUnit::Frames => unsafe { TimeStamp::Frames(raw_stamp.frames) },
             TimeStampUnit::BeatsPerMinute => unsafe { TimeStamp::BeatsPerMinute(raw_stamp.beats) },
@@ -195,22 +383,192 @@ impl<'a> Iterator for SequenceIterator<'a> {
     }
 }
 
+/// A kind of event that can be read from the atoms of a sequence.
+///
+/// This trait is implemented for atom URIDs, if the atom is read without a parameter, and for pairs of an atom URID and its reading parameter. Other crates implement it for events that need more than a URID to be identified.
+pub trait EventKind<'a> {
+    /// The type of the events.
+    type Item;
+
+    /// Read the atom as an event of this kind, or return `None` if it isn't one.
+    fn read_event(&self, atom: UnidentifiedAtom<'a>) -> Option<Self::Item>;
+}
+
+impl<'a, A> EventKind<'a> for URID<A>
+where
+    A: Atom<'a, 'a, ReadParameter = ()>,
+{
+    type Item = A::ReadHandle;
+
+    fn read_event(&self, atom: UnidentifiedAtom<'a>) -> Option<A::ReadHandle> {
+        atom.read(*self, ())
+    }
+}
+
+impl<'a, A, P> EventKind<'a> for (URID<A>, P)
+where
+    A: Atom<'a, 'a, ReadParameter = P>,
+    P: Clone,
+{
+    type Item = A::ReadHandle;
+
+    fn read_event(&self, atom: UnidentifiedAtom<'a>) -> Option<A::ReadHandle> {
+        atom.read(self.0, self.1.clone())
+    }
+}
+
+/// An iterator over the events of one kind in a sequence.
+///
+/// It's created by [`SequenceIterator::of_type`](struct.SequenceIterator.html#method.of_type).
+pub struct OfType<'a, K> {
+    events: SequenceIterator<'a>,
+    kind: K,
+}
+
+impl<'a, K: EventKind<'a>> Iterator for OfType<'a, K> {
+    type Item = (TimeStamp, K::Item);
+
+    fn next(&mut self) -> Option<(TimeStamp, K::Item)> {
+        let kind = &self.kind;
+        self.events
+            .find_map(|(stamp, atom)| kind.read_event(atom).map(|event| (stamp, event)))
+    }
+}
+
+/// An event of a sequence that was split into two kinds.
+pub enum SplitEvent<'a, A, B> {
+    /// An event of the first kind.
+    First(A),
+    /// An event of the second kind.
+    Second(B),
+    /// An event of neither kind.
+    Other(UnidentifiedAtom<'a>),
+}
+
+/// An iterator over the events of a sequence, which tells two kinds of events apart.
+///
+/// It's created by [`SequenceIterator::split`](struct.SequenceIterator.html#method.split).
+pub struct Split<'a, K1, K2> {
+    events: SequenceIterator<'a>,
+    first: K1,
+    second: K2,
+}
+
+impl<'a, K1, K2> Iterator for Split<'a, K1, K2>
+where
+    K1: EventKind<'a>,
+    K2: EventKind<'a>,
+{
+    type Item = (TimeStamp, SplitEvent<'a, K1::Item, K2::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (stamp, atom) = self.events.next()?;
+        let event = if let Some(event) = self.first.read_event(atom) {
+            SplitEvent::First(event)
+        } else if let Some(event) = self.second.read_event(atom) {
+            SplitEvent::Second(event)
+        } else {
+            SplitEvent::Other(atom)
+        };
+        Some((stamp, event))
+    }
+}
+
+/// What a [`SequenceWriter`](struct.SequenceWriter.html) does if an event doesn't fit into the sequence anymore.
+///
+/// The capacity of an output sequence is set by the host and can't be changed by the plugin. Once it's exhausted, the writer either rejects or drops events. Dropped events are counted in the [statistics](struct.SequenceStatistics.html) of the writer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OverflowPolicy {
+    /// The writing method returns `None`.
+    ///
+    /// This is the default policy.
+    #[default]
+    Error,
+    /// The new event is dropped, but the writing method returns `Some` for forwarded events.
+    DropNewest,
+    /// The oldest events are dropped to make room for the new event.
+    ///
+    /// The dropped events are replaced by a single resync event, an empty atom with the given type URID, which carries the time stamp of the last dropped event. The receiver can use it to tell that events were lost and, for example, release all notes.
+    ///
+    /// Events that were written with [`init`](struct.SequenceWriter.html#method.init), as well as all events before them, are never dropped since the plugin may still hold references to their contents. Also, older events are only dropped for forwarded events, because the size of an initialized atom isn't known in advance. In all other cases, the new event is dropped.
+    DropOldest(URID),
+}
+
+/// Statistics of a [`SequenceWriter`](struct.SequenceWriter.html), which a plugin can report to detect overflows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SequenceStatistics {
+    /// The number of events that were written to the sequence, including those that were dropped later.
+    pub written: u32,
+    /// The number of events that were dropped because the sequence was full.
+    pub dropped: u32,
+    /// The number of events that were rejected, either because of their time stamp or because the sequence was full and the policy is [`Error`](enum.OverflowPolicy.html#variant.Error).
+    pub rejected: u32,
+}
+
+/// The size of the time stamp and the atom header of an event, which is also the size of a resync event.
+const EVENT_HEADER_SIZE: usize = size_of::<RawTimeStamp>() + size_of::<sys::LV2_Atom>();
+
+/// Return the number of padding bytes needed to align the given offset to 64 bits.
+fn padding(offset: usize) -> usize {
+    (8 - offset % 8) % 8
+}
+
 /// The writing handle for sequences.
+///
+/// The sequence can only hold as many events as the space it's written to, which is set by the host for output ports. The writer reports how much of it is used and applies an [`OverflowPolicy`](enum.OverflowPolicy.html) once it's full.
 pub struct SequenceWriter<'a, 'b> {
     frame: FramedMutSpace<'a, 'b>,
     unit: TimeStampUnit,
     last_stamp: Option<TimeStamp>,
+    policy: OverflowPolicy,
+    statistics: SequenceStatistics,
+    /// The offset of the first event that may be dropped.
+    keep: usize,
+    /// Whether the last event was written with `init`. If so, it mustn't be dropped either.
+    last_initialized: bool,
+    /// The offset of the last resync event.
+    resync: Option<usize>,
+    /// Whether the space wasn't poisoned before the current event, which means that it can be recovered once the event is removed.
+    recoverable: bool,
 }
 
 impl<'a, 'b> SequenceWriter<'a, 'b> {
-    /// Write out the time stamp and update `last_stamp`.
+    /// Return the number of bytes the sequence can hold for events, or `None` if the underlying space isn't bounded.
+    ///
+    /// An event occupies 16 bytes for the time stamp and the atom header, plus the size of the atom's body. Every event except the last one is padded to 64 bits.
+    pub fn capacity(&self) -> Option<usize> {
+        self.remaining().map(|remaining| {
+            remaining + self.frame.size() - size_of::<sys::LV2_Atom_Sequence_Body>()
+        })
+    }
+
+    /// Return the number of bytes that are still free for events, or `None` if the underlying space isn't bounded.
+    pub fn remaining(&self) -> Option<usize> {
+        self.frame.remaining()
+    }
+
+    /// Return the policy that's applied when an event doesn't fit into the sequence.
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Set the policy that's applied when an event doesn't fit into the sequence.
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.policy = policy;
+    }
+
+    /// Return the statistics of the writer.
+    pub fn statistics(&self) -> SequenceStatistics {
+        self.statistics
+    }
+
+    /// Check the time stamp and convert it to a raw time stamp.
     ///
     /// This method returns `Ǹone` if:
     /// * The time stamp is not measured in our unit.
     /// * The last time stamp is younger than the time stamp.
-    /// * Space is insufficient.
-    fn write_time_stamp(&mut self, stamp: TimeStamp) -> Option<()> {
-        let raw_stamp = match self.unit {
+    fn check_time_stamp(&self, stamp: TimeStamp) -> Option<RawTimeStamp> {
+        match self.unit {
             TimeStampUnit::Frames => {
                 let frames = stamp.as_frames()?;
                 if let Some(last_stamp) = self.last_stamp {
@@ -218,7 +576,7 @@ impl<'a, 'b> SequenceWriter<'a, 'b> {
                         return None;
                     }
                 }
-                RawTimeStamp { frames }
+                Some(RawTimeStamp { frames })
             }
             TimeStampUnit::BeatsPerMinute => {
                 let beats = stamp.as_bpm()?;
@@ -227,37 +585,213 @@ impl<'a, 'b> SequenceWriter<'a, 'b> {
                         return None;
                     }
                 }
-                RawTimeStamp { beats }
+                Some(RawTimeStamp { beats })
             }
+        }
+    }
+
+    /// Prepare writing a new event and return its offset.
+    ///
+    /// If the last event was initialized, it must be kept.
+    fn begin_event(&mut self) -> usize {
+        let offset = self.frame.size();
+        if self.last_initialized {
+            self.keep = offset + padding(offset);
+            self.last_initialized = false;
+        }
+        self.recoverable = !self.frame.is_poisoned();
+        offset
+    }
+
+    /// Remove everything that has been written after the given offset.
+    ///
+    /// If the policy drops events, the space is recovered afterwards, so that smaller events may still be written. A space that was already poisoned before the current event, for example by the handle of an initialized event, stays poisoned since the sequence may contain a malformed atom.
+    fn truncate(&mut self, offset: usize) {
+        // Nothing references the released bytes since the event couldn't be written.
+        let released = unsafe { self.frame.release(self.frame.size() - offset) };
+        if released && self.recoverable && self.policy != OverflowPolicy::Error {
+            unsafe { self.frame.recover() };
+        }
+    }
+
+    /// Check whether an event with a body of the given size fits into the sequence.
+    fn fits(&self, body_size: usize) -> bool {
+        let size = self.frame.size();
+        match self.remaining() {
+            Some(remaining) => padding(size) + EVENT_HEADER_SIZE + body_size <= remaining,
+            None => true,
+        }
+    }
+
+    /// Write a forwarded event, or remove what has been written if it doesn't fit.
+    fn write_forwarded(&mut self, raw_stamp: &RawTimeStamp, data: &[u8]) -> Option<()> {
+        let offset = self.frame.size();
+        let written = (&mut self.frame as &mut dyn MutSpace)
+            .write(raw_stamp, true)
+            .and_then(|_| self.frame.write_raw(data, true));
+        if written.is_none() {
+            self.truncate(offset);
+        }
+        written.map(|_| ())
+    }
+
+    /// Drop the oldest events until an event with a body of the given size fits into the sequence.
+    ///
+    /// The dropped events are replaced by a resync event with the given type. This method returns `false` and doesn't change the sequence if the event wouldn't fit even if all droppable events were dropped.
+    fn drop_oldest(&mut self, body_size: usize, resync: URID) -> bool {
+        if self.frame.is_poisoned() {
+            return false;
+        }
+        let size = self.frame.size();
+        let remaining = match self.remaining() {
+            Some(remaining) => remaining,
+            None => return false,
         };
-        self.last_stamp = Some(stamp);
-        (&mut self.frame as &mut dyn MutSpace)
-            .write(&raw_stamp, true)
-            .map(|_| ())
+        let reuse_resync =
+            matches!(self.resync, Some(offset) if offset + EVENT_HEADER_SIZE == self.keep);
+        let target = if reuse_resync {
+            self.keep
+        } else {
+            self.keep + EVENT_HEADER_SIZE
+        };
+        let body = self.frame.body_ptr();
+
+        // Find the first event that can be kept.
+        let mut offset = self.keep;
+        let mut dropped: u32 = 0;
+        let mut last_dropped_stamp: Option<RawTimeStamp> = None;
+        loop {
+            if dropped > 0 {
+                let new_size = target + (size - offset);
+                if new_size + padding(new_size) + EVENT_HEADER_SIZE + body_size <= remaining + size
+                {
+                    break;
+                }
+            }
+            if offset >= size {
+                return false;
+            }
+            let (stamp, header) = unsafe {
+                let stamp = core::ptr::read(body.add(offset) as *const RawTimeStamp);
+                let header = core::ptr::read(body.add(offset + 8) as *const sys::LV2_Atom);
+                (stamp, header)
+            };
+            let event_size = EVENT_HEADER_SIZE + header.size as usize;
+            offset = (offset + event_size + padding(event_size)).min(size);
+            dropped += 1;
+            last_dropped_stamp = Some(stamp);
+        }
+
+        let new_size = target + (size - offset);
+        // The events behind the dropped ones are moved, but nothing references them since they were forwarded.
+        if !unsafe { self.frame.release(size - new_size) } {
+            return false;
+        }
+        unsafe {
+            core::ptr::copy(body.add(offset), body.add(target), size - offset);
+            let resync_offset = target - EVENT_HEADER_SIZE;
+            core::ptr::write(
+                body.add(resync_offset) as *mut RawTimeStamp,
+                last_dropped_stamp.unwrap(),
+            );
+            core::ptr::write(
+                body.add(resync_offset + 8) as *mut sys::LV2_Atom,
+                sys::LV2_Atom {
+                    size: 0,
+                    type_: resync.get(),
+                },
+            );
+        }
+        if !reuse_resync {
+            self.resync = Some(self.keep);
+            self.keep += EVENT_HEADER_SIZE;
+        }
+        self.statistics.dropped += dropped;
+        true
     }
 
     /// Initialize an event.
     ///
-    /// The time stamp has to be measured in the unit of the sequence. If the time stamp is measured in the wrong unit, is younger than the last written time stamp or space is insufficient, this method returns `None`.
+    /// The time stamp has to be measured in the unit of the sequence, which is checked at runtime. It can be a [`TimeStamp`](enum.TimeStamp.html) or a [`FrameTime`](struct.FrameTime.html) or [`BeatTime`](struct.BeatTime.html). If the time stamp is measured in the wrong unit, is younger than the last written time stamp or space is insufficient, this method returns `None`.
+    ///
+    /// Since the handle of the atom may reference the sequence, initialized events are never dropped by the [`DropOldest`](enum.OverflowPolicy.html#variant.DropOldest) policy.
     pub fn init<'c, A: Atom<'a, 'c>>(
         &'c mut self,
-        stamp: TimeStamp,
+        stamp: impl Into<TimeStamp>,
         urid: URID<A>,
         parameter: A::WriteParameter,
     ) -> Option<A::WriteHandle> {
-        self.write_time_stamp(stamp)?;
-        (&mut self.frame as &mut dyn MutSpace).init(urid, parameter)
+        let stamp = stamp.into();
+        let raw_stamp = match self.check_time_stamp(stamp) {
+            Some(raw_stamp) => raw_stamp,
+            None => {
+                self.statistics.rejected += 1;
+                return None;
+            }
+        };
+        let offset = self.begin_event();
+
+        // The borrow checker can't tell that the frame isn't borrowed anymore if the initialization failed, which is why it's accessed via a pointer.
+        let frame: *mut FramedMutSpace<'a, 'b> = &mut self.frame;
+        let handle = (unsafe { &mut *frame } as &mut dyn MutSpace)
+            .write(&raw_stamp, true)
+            .and_then(|_| (unsafe { &mut *frame } as &mut dyn MutSpace).init(urid, parameter));
+        if handle.is_some() {
+            self.last_stamp = Some(stamp);
+            self.last_initialized = true;
+            self.statistics.written += 1;
+        } else {
+            self.truncate(offset);
+            match self.policy {
+                OverflowPolicy::Error => self.statistics.rejected += 1,
+                _ => self.statistics.dropped += 1,
+            }
+        }
+        handle
     }
 
     /// Forward an unidentified atom to the sequence.
     ///
     /// If your cannot identify the type of the atom but have to write it, you can simply forward it.
     ///
-    /// The time stamp has to be measured in the unit of the sequence. If the time stamp is measured in the wrong unit, is younger than the last written time stamp or space is insufficient, this method returns `None`.
-    pub fn forward(&mut self, stamp: TimeStamp, atom: UnidentifiedAtom) -> Option<()> {
+    /// The time stamp has to be measured in the unit of the sequence, just like for [`init`](#method.init). If the time stamp is measured in the wrong unit or is younger than the last written time stamp, this method returns `None`. If space is insufficient, the [overflow policy](enum.OverflowPolicy.html) of the writer is applied.
+    pub fn forward(&mut self, stamp: impl Into<TimeStamp>, atom: UnidentifiedAtom) -> Option<()> {
+        let stamp = stamp.into();
         let data = atom.space.data()?;
-        self.write_time_stamp(stamp)?;
-        self.frame.write_raw(data, true).map(|_| ())
+        let raw_stamp = match self.check_time_stamp(stamp) {
+            Some(raw_stamp) => raw_stamp,
+            None => {
+                self.statistics.rejected += 1;
+                return None;
+            }
+        };
+        self.begin_event();
+
+        let body_size = data.len().saturating_sub(size_of::<sys::LV2_Atom>());
+        let written = if self.fits(body_size) {
+            self.write_forwarded(&raw_stamp, data)
+        } else {
+            None
+        };
+        let policy = self.policy;
+        let written = written.or_else(|| match policy {
+            OverflowPolicy::DropOldest(resync) if self.drop_oldest(body_size, resync) => {
+                self.write_forwarded(&raw_stamp, data)
+            }
+            _ => None,
+        });
+
+        if written.is_some() {
+            self.last_stamp = Some(stamp);
+            self.statistics.written += 1;
+            Some(())
+        } else if policy == OverflowPolicy::Error {
+            self.statistics.rejected += 1;
+            None
+        } else {
+            self.statistics.dropped += 1;
+            Some(())
+        }
     }
 }
 
@@ -265,7 +799,7 @@ impl<'a, 'b> SequenceWriter<'a, 'b> {
 mod tests {
     use crate::prelude::*;
     use crate::sequence::*;
-    use std::mem::size_of;
+    use core::mem::size_of;
     use sys::LV2_Atom_Event__bindgen_ty_1 as RawTimeStamp;
 
     #[derive(URIDCollection)]
@@ -294,7 +828,7 @@ mod tests {
                 .init::<Int>(TimeStamp::Frames(0), urids.atom.int, 42)
                 .unwrap();
             writer
-                .init::<Long>(TimeStamp::Frames(1), urids.atom.long, 17)
+                .init::<Long>(FrameTime(1), urids.atom.long, 17)
                 .unwrap();
         }
 
@@ -360,5 +894,305 @@ mod tests {
 
             assert!(reader.next().is_none());
         }
+
+        // reading by event kind
+        {
+            let space = Space::from_slice(raw_space.as_ref());
+            let (body, _) = space.split_atom_body(urids.atom.sequence).unwrap();
+            let reader = Sequence::read(body, urids.units.beat).unwrap();
+
+            let ints: Vec<(i64, i32)> = reader
+                .of_type(urids.atom.int)
+                .map(|(stamp, int)| (stamp.as_frames().unwrap(), int))
+                .collect();
+            assert_eq!(ints, vec![(0, 42)]);
+            let longs: Vec<i64> = Sequence::read(body, urids.units.beat)
+                .unwrap()
+                .of_type((urids.atom.long, ()))
+                .map(|(_, long)| long)
+                .collect();
+            assert_eq!(longs, vec![17]);
+
+            let mut reader = Sequence::read(body, urids.units.beat)
+                .unwrap()
+                .split(urids.atom.long, urids.atom.float);
+            match reader.next().unwrap() {
+                (_, SplitEvent::Other(atom)) => {
+                    assert_eq!(atom.type_urid(), Some(urids.atom.int.into_general()))
+                }
+                _ => panic!("Invalid event!"),
+            }
+            match reader.next().unwrap() {
+                (_, SplitEvent::First(long)) => assert_eq!(long, 17),
+                _ => panic!("Invalid event!"),
+            }
+            assert!(reader.next().is_none());
+        }
+    }
+
+    /// Write an integer atom to a 64-bit-aligned buffer.
+    fn int_atom(urids: &TestURIDCollection, value: i32) -> Box<[u64]> {
+        let mut data: Box<[u64]> = Box::new([0; 2]);
+        unsafe {
+            *(data.as_mut_ptr() as *mut sys::LV2_Atom_Int) = sys::LV2_Atom_Int {
+                atom: sys::LV2_Atom {
+                    size: size_of::<i32>() as u32,
+                    type_: urids.atom.int.get(),
+                },
+                body: value,
+            };
+        }
+        data
+    }
+
+    /// Read the frames and values of the events in a sequence. Resync events have the value `None`.
+    fn read_events(urids: &TestURIDCollection, raw_space: &[u8]) -> Vec<(i64, Option<i32>)> {
+        let (body, _) = Space::from_slice(raw_space)
+            .split_atom_body(urids.atom.sequence)
+            .unwrap();
+        Sequence::read(body, urids.units.beat)
+            .unwrap()
+            .map(|(stamp, atom)| (stamp.as_frames().unwrap(), atom.read(urids.atom.int, ())))
+            .collect()
+    }
+
+    #[test]
+    fn test_overflow() {
+        let map = HashURIDMapper::new();
+        let urids = TestURIDCollection::from_map(&map).unwrap();
+        let resync = map.map_str("urn:lv2_atom:test:resync").unwrap();
+
+        // Space for the headers and three integer events.
+        const SIZE: usize = 88;
+        let atoms: Vec<Box<[u64]>> = (0..5).map(|i| int_atom(&urids, i)).collect();
+        let atom = |i: usize| {
+            UnidentifiedAtom::new(
+                Space::from_reference(atoms[i].as_ref())
+                    .split_atom()
+                    .unwrap()
+                    .0,
+            )
+        };
+
+        for policy in [OverflowPolicy::Error, OverflowPolicy::DropNewest].iter() {
+            let mut raw_space: Box<[u64]> = Box::new([0; SIZE / 8]);
+            let raw_space: &mut [u8] =
+                unsafe { core::slice::from_raw_parts_mut(raw_space.as_mut_ptr() as *mut u8, SIZE) };
+            {
+                let mut space = RootMutSpace::new(raw_space);
+                let mut writer = (&mut space as &mut dyn MutSpace)
+                    .init(
+                        urids.atom.sequence,
+                        TimeStampURID::Frames(urids.units.frame),
+                    )
+                    .unwrap();
+                writer.set_overflow_policy(*policy);
+                assert_eq!(writer.capacity(), Some(SIZE - 16));
+
+                for i in 0..3 {
+                    writer
+                        .forward(TimeStamp::Frames(i as i64), atom(i))
+                        .unwrap();
+                }
+                assert_eq!(writer.remaining(), Some(4));
+
+                let result = writer.forward(TimeStamp::Frames(3), atom(3));
+                let statistics = writer.statistics();
+                assert_eq!(statistics.written, 3);
+                if *policy == OverflowPolicy::Error {
+                    assert!(result.is_none());
+                    assert_eq!(statistics.rejected, 1);
+                } else {
+                    assert!(result.is_some());
+                    assert_eq!(statistics.dropped, 1);
+                }
+                assert_eq!(writer.remaining(), Some(4));
+
+                // Time stamps are still checked.
+                assert!(writer.forward(TimeStamp::Frames(1), atom(4)).is_none());
+            }
+            assert_eq!(
+                read_events(&urids, raw_space),
+                vec![(0, Some(0)), (1, Some(1)), (2, Some(2))]
+            );
+        }
+
+        let mut raw_space: Box<[u64]> = Box::new([0; SIZE / 8]);
+        let raw_space: &mut [u8] =
+            unsafe { core::slice::from_raw_parts_mut(raw_space.as_mut_ptr() as *mut u8, SIZE) };
+        {
+            let mut space = RootMutSpace::new(raw_space);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(
+                    urids.atom.sequence,
+                    TimeStampURID::Frames(urids.units.frame),
+                )
+                .unwrap();
+            writer.set_overflow_policy(OverflowPolicy::DropOldest(resync));
+
+            // Initialized events are never dropped.
+            writer
+                .init(TimeStamp::Frames(0), urids.atom.int, 0)
+                .unwrap();
+            writer.forward(TimeStamp::Frames(1), atom(1)).unwrap();
+            writer.forward(TimeStamp::Frames(2), atom(2)).unwrap();
+
+            writer.forward(TimeStamp::Frames(3), atom(3)).unwrap();
+            assert_eq!(writer.statistics().dropped, 2);
+
+            // The resync event is reused.
+            writer.forward(TimeStamp::Frames(4), atom(4)).unwrap();
+            assert_eq!(
+                writer.statistics(),
+                SequenceStatistics {
+                    written: 5,
+                    dropped: 3,
+                    rejected: 0,
+                }
+            );
+
+            // Initialized events are dropped if they don't fit.
+            assert!(writer
+                .init(TimeStamp::Frames(5), urids.atom.long, 5)
+                .is_none());
+            assert_eq!(writer.statistics().dropped, 4);
+
+            // Events that don't fit even if all other events are dropped are dropped themselves.
+            let mut big_atom: Box<[u64]> = Box::new([0; 5]);
+            unsafe {
+                *(big_atom.as_mut_ptr() as *mut sys::LV2_Atom) = sys::LV2_Atom {
+                    size: 32,
+                    type_: urids.atom.chunk.get(),
+                };
+            }
+            let big_atom = UnidentifiedAtom::new(Space::from_reference(big_atom.as_ref()));
+            assert!(writer.forward(TimeStamp::Frames(6), big_atom).is_some());
+            assert_eq!(writer.statistics().dropped, 5);
+            assert_eq!(writer.remaining(), Some(12));
+        }
+
+        let events = read_events(&urids, raw_space);
+        assert_eq!(events, vec![(0, Some(0)), (3, None), (4, Some(4))]);
+        let (body, _) = Space::from_slice(raw_space)
+            .split_atom_body(urids.atom.sequence)
+            .unwrap();
+        let mut reader = Sequence::read(body, urids.units.beat).unwrap();
+        let (_, resync_atom) = reader.nth(1).unwrap();
+        assert_eq!(resync_atom.type_urid(), Some(resync));
+    }
+
+    #[test]
+    fn test_poisoning() {
+        let map = HashURIDMapper::new();
+        let urids = TestURIDCollection::from_map(&map).unwrap();
+
+        // Space for the headers and one integer event, but not for a long event.
+        const SIZE: usize = 36;
+        let int = int_atom(&urids, 1);
+        let int =
+            || UnidentifiedAtom::new(Space::from_reference(int.as_ref()).split_atom().unwrap().0);
+
+        for policy in [OverflowPolicy::Error, OverflowPolicy::DropNewest].iter() {
+            let mut memory: Box<[u64]> = Box::new([0; 5]);
+            let raw_space: &mut [u8] =
+                unsafe { core::slice::from_raw_parts_mut(memory.as_mut_ptr() as *mut u8, SIZE) };
+            {
+                let mut space = RootMutSpace::new(raw_space);
+                let mut writer = (&mut space as &mut dyn MutSpace)
+                    .init(
+                        urids.atom.sequence,
+                        TimeStampURID::Frames(urids.units.frame),
+                    )
+                    .unwrap();
+                writer.set_overflow_policy(*policy);
+                assert!(writer
+                    .init(TimeStamp::Frames(0), urids.atom.long, 0)
+                    .is_none());
+
+                // The failed event poisons the sequence, unless the policy drops events.
+                let result = writer.forward(TimeStamp::Frames(1), int());
+                if *policy == OverflowPolicy::Error {
+                    assert!(result.is_none());
+                    assert_eq!(writer.remaining(), Some(0));
+                    assert_eq!(writer.statistics().rejected, 2);
+                } else {
+                    assert!(result.is_some());
+                    assert_eq!(writer.statistics().written, 1);
+                }
+            }
+            let expected = if *policy == OverflowPolicy::Error {
+                vec![]
+            } else {
+                vec![(1, Some(1))]
+            };
+            assert_eq!(read_events(&urids, raw_space), expected);
+        }
+
+        // A handle that fails to write its atom poisons the sequence, even if the policy drops events.
+        let mut memory: Box<[u64]> = Box::new([0; 5]);
+        let raw_space: &mut [u8] =
+            unsafe { core::slice::from_raw_parts_mut(memory.as_mut_ptr() as *mut u8, SIZE) };
+        {
+            let mut space = RootMutSpace::new(raw_space);
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(
+                    urids.atom.sequence,
+                    TimeStampURID::Frames(urids.units.frame),
+                )
+                .unwrap();
+            writer.set_overflow_policy(OverflowPolicy::DropNewest);
+            {
+                let mut chunk = writer
+                    .init(TimeStamp::Frames(0), urids.atom.chunk, ())
+                    .unwrap();
+                chunk.write_raw(&[1, 2], false).unwrap();
+                assert!(chunk.write_raw(&[0; 8], false).is_none());
+                assert!(chunk.write_raw(&[3], false).is_none());
+            }
+            assert!(writer.forward(TimeStamp::Frames(1), int()).is_some());
+            assert_eq!(writer.statistics().dropped, 1);
+            assert_eq!(writer.remaining(), Some(0));
+        }
+        let (body, _) = Space::from_slice(raw_space)
+            .split_atom_body(urids.atom.sequence)
+            .unwrap();
+        let events: Vec<(TimeStamp, UnidentifiedAtom)> =
+            Sequence::read(body, urids.units.beat).unwrap().collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].1.read(urids.atom.chunk, ()), Some(&[1, 2][..]));
+    }
+
+    #[test]
+    fn test_time_arithmetic() {
+        assert_eq!(FrameTime(4).checked_add(4), Some(FrameTime(8)));
+        assert_eq!(FrameTime(i64::MAX).checked_add(1), None);
+        assert_eq!(FrameTime(4).checked_sub(5), Some(FrameTime(-1)));
+        assert_eq!(FrameTime(8).checked_since(FrameTime(3)), Some(5));
+        assert_eq!(FrameTime(3).checked_since(FrameTime(8)), None);
+
+        // The last frame of a block is `start + sample_count - 1`.
+        assert_eq!(FrameTime(100).block_offset(FrameTime(100), 64), Some(0));
+        assert_eq!(FrameTime(163).block_offset(FrameTime(100), 64), Some(63));
+        assert_eq!(FrameTime(164).block_offset(FrameTime(100), 64), None);
+        assert_eq!(FrameTime(99).block_offset(FrameTime(100), 64), None);
+
+        assert_eq!(FrameTime::from_seconds(0.5, 48000.0), FrameTime(24000));
+        assert_eq!(FrameTime(24000).to_seconds(48000.0), 0.5);
+        assert_eq!(FrameTime(36000).to_beats(24000.0), BeatTime(1.5));
+
+        assert_eq!(BeatTime(1.5).checked_add(0.5), Some(BeatTime(2.0)));
+        assert_eq!(BeatTime(1.5).checked_sub(f64::INFINITY), None);
+        assert_eq!(BeatTime(1.5).to_seconds(120.0), 0.75);
+        assert_eq!(BeatTime(1.5).to_frames(24000.0), Some(FrameTime(36000)));
+        assert_eq!(BeatTime(-0.1).to_frames(10.0), Some(FrameTime(-1)));
+        assert_eq!(BeatTime(f64::MAX).to_frames(2.0), None);
+
+        assert_eq!(TimeStamp::from(FrameTime(3)).as_frames(), Some(3));
+        assert_eq!(TimeStamp::Frames(3).frame_time(), Some(FrameTime(3)));
+        assert_eq!(TimeStamp::Frames(3).beat_time(), None);
+        assert_eq!(
+            TimeStamp::BeatsPerMinute(1.5).beat_time(),
+            Some(BeatTime(1.5))
+        );
     }
 }
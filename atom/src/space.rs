@@ -8,9 +8,11 @@
 //!
 //! The second case is sound since a) the data is contained in a slice and therefore is accessible, b) generic type parameter bounds assure that the type is plain-old-data and c) 64-bit padding is assured.
 use crate::Atom;
-use std::cell::Cell;
-use std::marker::Unpin;
-use std::mem::{size_of, size_of_val};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::cell::Cell;
+use core::marker::Unpin;
+use core::mem::{align_of, size_of, size_of_val};
 use urid::URID;
 
 /// Specialized smart pointer to retrieve struct instances from a slice of memory.
@@ -32,7 +34,7 @@ impl<'a> Space<'a> {
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub unsafe fn from_atom(atom: &sys::LV2_Atom) -> Self {
         let size = atom.size as usize;
-        let data = std::slice::from_raw_parts(
+        let data = core::slice::from_raw_parts(
             atom as *const sys::LV2_Atom as *const u8,
             size + size_of::<sys::LV2_Atom>(),
         );
@@ -41,7 +43,7 @@ impl<'a> Space<'a> {
 
     /// Create a new space from a slice.
     ///
-    /// Since everything regarding atoms is 64-bit-aligned, this method panics if the data slice is not 64-bit-aligned.
+    /// Atoms are supposed to be 64-bit-aligned, but some hosts only align their buffers to 32 bits. Such a slice is accepted, but values that need a bigger alignment than their address provides can't be referenced: [`split_type`](#method.split_type) returns `None` for them and [`read_type`](#method.read_type) only reads them with the `unaligned` feature.
     pub fn from_slice(data: &'a [u8]) -> Self {
         Space { data: Some(data) }
     }
@@ -81,12 +83,36 @@ impl<'a> Space<'a> {
     /// Try to retrieve a reference to a sized type.
     ///
     /// This method retrieves a slice of memory using the [`split_raw`](#method.split_raw) method and interprets it as an instance of `T`. Since there is no way to check that the memory is actually a valid instance of `T`, this method is unsafe. The second return value is the space after the instance of `T`.
+    ///
+    /// If the memory isn't aligned for `T`, `None` is returned, since creating the reference would be undefined behaviour.
     pub fn split_type<T>(self) -> Option<(&'a T, Self)>
     where
         T: Unpin + Copy + Send + Sync + Sized + 'static,
     {
-        self.split_raw(size_of::<T>())
-            .map(|(data, rhs)| (unsafe { &*(data.as_ptr() as *const T) }, rhs))
+        let (data, rhs) = self.split_raw(size_of::<T>())?;
+        if !is_aligned::<T>(data) {
+            return None;
+        }
+        Some((unsafe { &*(data.as_ptr() as *const T) }, rhs))
+    }
+
+    /// Try to read a copy of a sized type.
+    ///
+    /// This method works like [`split_type`](#method.split_type), but returns the instance of `T` by value. Usually, the memory has to be aligned for `T` too, but with the `unaligned` feature, misaligned instances are read byte by byte. This way, 64-bit values can be read from hosts that only align their buffers to 32 bits.
+    pub fn read_type<T>(self) -> Option<(T, Self)>
+    where
+        T: Unpin + Copy + Send + Sync + Sized + 'static,
+    {
+        let (data, rhs) = self.split_raw(size_of::<T>())?;
+        let pointer = data.as_ptr() as *const T;
+        let value = if is_aligned::<T>(data) {
+            unsafe { pointer.read() }
+        } else if cfg!(feature = "unaligned") {
+            unsafe { pointer.read_unaligned() }
+        } else {
+            return None;
+        };
+        Some((value, rhs))
     }
 
     /// Try to retrieve the space occupied by an atom.
@@ -115,7 +141,7 @@ impl<'a> Space<'a> {
     /// Create a space from a reference.
     pub fn from_reference<T: ?Sized>(instance: &'a T) -> Self {
         let data = unsafe {
-            std::slice::from_raw_parts(instance as *const T as *const u8, size_of_val(instance))
+            core::slice::from_raw_parts(instance as *const T as *const u8, size_of_val(instance))
         };
         assert_eq!(data.as_ptr() as usize % 8, 0);
         Space { data: Some(data) }
@@ -135,7 +161,7 @@ impl<'a> Space<'a> {
         };
         if unsafe { lhs_data.as_ptr().add(lhs_data.len()) } == rhs_data.as_ptr() {
             Some(Self::from_slice(unsafe {
-                std::slice::from_raw_parts(lhs_data.as_ptr(), lhs_data.len() + rhs_data.len())
+                core::slice::from_raw_parts(lhs_data.as_ptr(), lhs_data.len() + rhs_data.len())
             }))
         } else {
             None
@@ -173,12 +199,51 @@ pub trait MutSpace<'a> {
             space
         })
     }
+
+    /// Return the number of bytes that can still be allocated, or `None` if the space isn't bounded.
+    ///
+    /// The padding that may be needed by the next allocation is not subtracted.
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
+
+    /// Try to release the last `size` allocated bytes, so that they can be allocated again.
+    ///
+    /// This method returns `false` and does nothing if the space doesn't support releasing memory. Only spaces that allocate contiguous memory may support it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must assure that there are no references to the released bytes anymore.
+    unsafe fn release(&mut self, _size: usize) -> bool {
+        false
+    }
+
+    /// Return whether a failed allocation poisoned the space.
+    ///
+    /// Once an allocation failed, a space may refuse all further allocations, since the atom that was being written is incomplete and writing more data after it would produce a malformed atom.
+    fn is_poisoned(&self) -> bool {
+        false
+    }
+
+    /// Try to make a poisoned space usable again.
+    ///
+    /// This method returns `false` and does nothing if the space doesn't support it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must assure that the incomplete atom has been [released](#method.release) completely, so that no malformed data remains in the space.
+    unsafe fn recover(&mut self) -> bool {
+        false
+    }
 }
 
 /// A `MutSpace` that directly manages it's own internal data slice.
+///
+/// Once an allocation failed, the space is poisoned and all further allocations fail too.
 pub struct RootMutSpace<'a> {
     space: Cell<Option<&'a mut [u8]>>,
     allocated_bytes: usize,
+    poisoned: bool,
 }
 
 impl<'a> RootMutSpace<'a> {
@@ -190,7 +255,7 @@ impl<'a> RootMutSpace<'a> {
     ///
     /// Since the body is not included in the atom reference, this method has to assume that it is valid memory and therefore is unsafe.
     pub unsafe fn from_atom(atom: &mut sys::LV2_Atom) -> Self {
-        let space = std::slice::from_raw_parts_mut(
+        let space = core::slice::from_raw_parts_mut(
             atom as *mut _ as *mut u8,
             atom.size as usize + size_of::<sys::LV2_Atom>(),
         );
@@ -204,54 +269,93 @@ impl<'a> RootMutSpace<'a> {
         RootMutSpace {
             space: Cell::new(Some(space)),
             allocated_bytes: 0,
+            poisoned: false,
         }
     }
 }
 
 impl<'a> MutSpace<'a> for RootMutSpace<'a> {
     fn allocate(&mut self, size: usize, apply_padding: bool) -> Option<(usize, &'a mut [u8])> {
-        if self.space.get_mut().is_none() {
+        if self.poisoned {
             return None;
         }
-        let mut space = self.space.replace(None).unwrap();
+        let space = self.space.get_mut().take()?;
 
         let padding = if apply_padding {
             let alignment = self.allocated_bytes % 8;
-            let padding = if alignment == 0 { 0 } else { 8 - alignment };
-            if padding > space.len() {
-                return None;
+            if alignment == 0 {
+                0
+            } else {
+                8 - alignment
             }
-            space = space.split_at_mut(padding).1;
-            self.allocated_bytes += padding;
-            padding
         } else {
             0
         };
 
-        if size > space.len() {
+        if padding + size > space.len() {
+            // The space is kept so that it can be released and recovered.
+            *self.space.get_mut() = Some(space);
+            self.poisoned = true;
             return None;
         }
-        let (lower_slice, upper_slice) = space.split_at_mut(size);
-        self.allocated_bytes += size;
+        let (lower_slice, upper_slice) = space.split_at_mut(padding).1.split_at_mut(size);
+        self.allocated_bytes += padding + size;
 
-        self.space.set(Some(upper_slice));
+        *self.space.get_mut() = Some(upper_slice);
         Some((padding, lower_slice))
     }
+
+    fn remaining(&self) -> Option<usize> {
+        if self.poisoned {
+            return Some(0);
+        }
+        let space = self.space.take();
+        let remaining = space.as_ref().map_or(0, |space| space.len());
+        self.space.set(space);
+        Some(remaining)
+    }
+
+    unsafe fn release(&mut self, size: usize) -> bool {
+        if size > self.allocated_bytes {
+            return false;
+        }
+        let space = match self.space.get_mut().take() {
+            Some(space) => space,
+            None => return false,
+        };
+        let space =
+            core::slice::from_raw_parts_mut(space.as_mut_ptr().sub(size), space.len() + size);
+        self.allocated_bytes -= size;
+        *self.space.get_mut() = Some(space);
+        true
+    }
+
+    fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    unsafe fn recover(&mut self) -> bool {
+        self.poisoned = false;
+        true
+    }
 }
 
+#[cfg(feature = "alloc")]
 /// Linked list element for dynamic atom writing.
 ///
-/// This struct works in conjunction with [`SpaceHead`](struct.SpaceHead.html) to provide a way to write atoms to dynamically allocated memory.
+/// This struct works in conjunction with [`SpaceHead`](struct.SpaceHead.html) to provide a way to write atoms to dynamically allocated memory. It's only available with the `alloc` feature.
 pub struct SpaceElement {
     next: Option<(Box<Self>, Box<[u8]>)>,
 }
 
+#[cfg(feature = "alloc")]
 impl Default for SpaceElement {
     fn default() -> Self {
         Self { next: None }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl SpaceElement {
     /// Append an element to the list.
     ///
@@ -282,11 +386,12 @@ impl SpaceElement {
 
     /// Return an iterator over the chunks of all elements following this one.
     pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
-        std::iter::successors(self.next.as_ref(), |element| element.0.next.as_ref())
+        core::iter::successors(self.next.as_ref(), |element| element.0.next.as_ref())
             .map(|(_, data)| data.as_ref())
     }
 }
 
+#[cfg(feature = "alloc")]
 /// A mutable space that dynamically allocates memory.
 ///
 /// This space uses a linked list of [`SpaceElement`s](struct.SpaceElement.html) to allocate memory. Every time `allocate` is called, a new element is appended to the list and a new byte slice is created.
@@ -322,6 +427,7 @@ pub struct SpaceHead<'a> {
     allocated_space: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> SpaceHead<'a> {
     /// Create a new head that references the given element.
     pub fn new(element: &'a mut SpaceElement) -> Self {
@@ -340,6 +446,7 @@ impl<'a> SpaceHead<'a> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> MutSpace<'a> for SpaceHead<'a> {
     fn allocate(&mut self, size: usize, apply_padding: bool) -> Option<(usize, &'a mut [u8])> {
         let padding: usize = if apply_padding {
@@ -357,6 +464,144 @@ impl<'a> MutSpace<'a> for SpaceHead<'a> {
     }
 }
 
+#[cfg(feature = "alloc")]
+/// Contiguous, heap-allocated memory for atoms.
+///
+/// Host-provided buffers, like the ones of atom ports, are only available in the `run` method. An `AtomSpace` owns its memory instead, which makes it possible to write atoms in the worker thread or in tests and then copy or send them. It has a fixed capacity, is 64-bit-aligned and is written with an [`AtomSpaceWriter`](struct.AtomSpaceWriter.html), which is a `MutSpace` just like a host-provided buffer. In contrast to a [`SpaceHead`](struct.SpaceHead.html), the written atoms are contiguous and can be read right away.
+///
+/// # Usage example
+///
+/// ```
+/// # use lv2_core::prelude::*;
+/// # use lv2_atom::prelude::*;
+/// # use lv2_atom::space::*;
+/// # use urid::*;
+/// # let map = HashURIDMapper::new();
+/// // URID cache creation is omitted.
+/// let urids: AtomURIDCollection = map.populate_collection().unwrap();
+///
+/// let mut space = AtomSpace::new(256);
+/// (&mut space.writer() as &mut dyn MutSpace)
+///     .init(urids.int, 42)
+///     .unwrap();
+/// assert_eq!(42, space.atom().unwrap().read(urids.int, ()).unwrap());
+///
+/// // The atom can be copied to any other space, like the buffer of an atom port.
+/// let mut buffer = vec![0u64; 32];
+/// let mut target = RootMutSpace::new(unsafe {
+///     std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, 256)
+/// });
+/// (&mut target as &mut dyn MutSpace).write_raw(space.as_bytes(), true).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct AtomSpace {
+    data: Box<[u64]>,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl AtomSpace {
+    /// Allocate an empty space with a capacity of at least the given number of bytes.
+    ///
+    /// The capacity is rounded up to a multiple of 8 bytes.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            data: vec![0; capacity.div_ceil(8)].into_boxed_slice(),
+            len: 0,
+        }
+    }
+
+    /// Return the number of bytes the space can hold.
+    pub fn capacity(&self) -> usize {
+        self.data.len() * 8
+    }
+
+    /// Return the number of written bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether nothing has been written to the space.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all written data.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Create a writer that appends data to the space.
+    ///
+    /// The first allocation of the writer is 64-bit-aligned if it applies padding, even if the previously written data doesn't end at a 64-bit boundary.
+    pub fn writer(&mut self) -> AtomSpaceWriter<'_> {
+        let start = self.len.div_ceil(8) * 8;
+        let capacity = self.capacity();
+        let data =
+            unsafe { core::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut u8, capacity) };
+        let start = start.min(capacity);
+        AtomSpaceWriter {
+            root: RootMutSpace::new(&mut data[start..]),
+            len: &mut self.len,
+            start,
+            written: 0,
+        }
+    }
+
+    /// Return the written bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        let data = unsafe {
+            core::slice::from_raw_parts(self.data.as_ptr() as *const u8, self.capacity())
+        };
+        &data[..self.len]
+    }
+
+    /// Return the written data as a space, to read the atoms in it.
+    pub fn space(&self) -> Space<'_> {
+        Space::from_slice(self.as_bytes())
+    }
+
+    /// Return the first atom in the space, if there is one.
+    pub fn atom(&self) -> Option<crate::UnidentifiedAtom<'_>> {
+        let (atom, _) = self.space().split_atom()?;
+        Some(crate::UnidentifiedAtom::new(atom))
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// A `MutSpace` that appends data to an [`AtomSpace`](struct.AtomSpace.html).
+///
+/// The written data is visible in the atom space once the writer is dropped.
+pub struct AtomSpaceWriter<'a> {
+    root: RootMutSpace<'a>,
+    len: &'a mut usize,
+    start: usize,
+    written: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> MutSpace<'a> for AtomSpaceWriter<'a> {
+    fn allocate(&mut self, size: usize, apply_padding: bool) -> Option<(usize, &'a mut [u8])> {
+        let (padding, data) = self.root.allocate(size, apply_padding)?;
+        self.written += padding + size;
+        *self.len = self.start + self.written;
+        Some((padding, data))
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.root.remaining()
+    }
+
+    unsafe fn release(&mut self, size: usize) -> bool {
+        if !self.root.release(size) {
+            return false;
+        }
+        self.written -= size;
+        *self.len = self.start + self.written;
+        true
+    }
+}
+
 /// A `MutSpace` that notes the amount of allocated space in an atom header.
 pub struct FramedMutSpace<'a, 'b> {
     atom: &'a mut sys::LV2_Atom,
@@ -373,6 +618,18 @@ impl<'a, 'b> FramedMutSpace<'a, 'b> {
         let atom: &'a mut sys::LV2_Atom = parent.write(&atom, true)?;
         Some(Self { atom, parent })
     }
+
+    /// Return the number of bytes that have been written to the frame, not including the atom header.
+    pub fn size(&self) -> usize {
+        self.atom.size as usize
+    }
+
+    /// Return a pointer to the body of the atom.
+    ///
+    /// The body is only contiguous if the parent space is contiguous, which is the case if the frame supports releasing memory.
+    pub(crate) fn body_ptr(&mut self) -> *mut u8 {
+        unsafe { (self.atom as *mut sys::LV2_Atom).add(1) as *mut u8 }
+    }
 }
 
 impl<'a, 'b> MutSpace<'a> for FramedMutSpace<'a, 'b> {
@@ -384,6 +641,26 @@ impl<'a, 'b> MutSpace<'a> for FramedMutSpace<'a, 'b> {
                 (padding, data)
             })
     }
+
+    fn remaining(&self) -> Option<usize> {
+        self.parent.remaining()
+    }
+
+    unsafe fn release(&mut self, size: usize) -> bool {
+        if size > self.size() || !self.parent.release(size) {
+            return false;
+        }
+        self.atom.size -= size as u32;
+        true
+    }
+
+    fn is_poisoned(&self) -> bool {
+        self.parent.is_poisoned()
+    }
+
+    unsafe fn recover(&mut self) -> bool {
+        self.parent.recover()
+    }
 }
 
 impl<'a, 'b> dyn MutSpace<'a> + 'b {
@@ -394,9 +671,9 @@ impl<'a, 'b> dyn MutSpace<'a> + 'b {
     where
         T: Unpin + Copy + Send + Sync + Sized + 'static,
     {
-        let size = std::mem::size_of::<T>();
+        let size = core::mem::size_of::<T>();
         let input_data =
-            unsafe { std::slice::from_raw_parts(instance as *const T as *const u8, size) };
+            unsafe { core::slice::from_raw_parts(instance as *const T as *const u8, size) };
 
         let output_data = self.write_raw(input_data, apply_padding)?;
 
@@ -415,10 +692,15 @@ impl<'a, 'b> dyn MutSpace<'a> + 'b {
     }
 }
 
+/// Check whether the slice starts at an address that is aligned for `T`.
+fn is_aligned<T>(data: &[u8]) -> bool {
+    data.as_ptr().align_offset(align_of::<T>()) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use crate::space::*;
-    use std::mem::{size_of, size_of_val};
+    use core::mem::{size_of, size_of_val};
     use urid::*;
 
     #[test]
@@ -549,7 +831,7 @@ mod tests {
         const MEMORY_SIZE: usize = 256;
         let mut memory: [u64; MEMORY_SIZE] = [0; MEMORY_SIZE];
         let frame: RootMutSpace = RootMutSpace::new(unsafe {
-            std::slice::from_raw_parts_mut(
+            core::slice::from_raw_parts_mut(
                 (&mut memory).as_mut_ptr() as *mut u8,
                 MEMORY_SIZE * size_of::<u64>(),
             )
@@ -565,12 +847,49 @@ mod tests {
         test_mut_space(head);
     }
 
+    #[test]
+    fn test_atom_space() {
+        let mut space = AtomSpace::new(509);
+        assert_eq!(space.capacity(), 512);
+        test_mut_space(space.writer());
+        assert_eq!(space.len(), 24 + 8 + 8 + 24 + 8);
+
+        let map = HashURIDMapper::new();
+        let urids = crate::AtomURIDCollection::from_map(&map).unwrap();
+        let mut space = AtomSpace::new(32);
+        (&mut space.writer() as &mut dyn MutSpace)
+            .init(urids.int, 42)
+            .unwrap();
+        assert_eq!(space.len(), 12);
+
+        // The next atom starts at a 64-bit boundary and the space is full afterwards.
+        (&mut space.writer() as &mut dyn MutSpace)
+            .init(urids.long, 17)
+            .unwrap();
+        assert_eq!(space.len(), 32);
+        assert!((&mut space.writer() as &mut dyn MutSpace)
+            .init(urids.int, 1)
+            .is_none());
+
+        let (first, rest) = space.space().split_atom().unwrap();
+        let first = crate::UnidentifiedAtom::new(first);
+        assert_eq!(first.read(urids.int, ()), Some(42));
+        let second = crate::UnidentifiedAtom::new(rest.split_atom().unwrap().0);
+        assert_eq!(second.read(urids.long, ()), Some(17));
+
+        let copy = space.clone();
+        space.clear();
+        assert!(space.is_empty());
+        assert!(space.atom().is_none());
+        assert_eq!(copy.atom().unwrap().read(urids.int, ()), Some(42));
+    }
+
     #[test]
     fn test_padding_inside_frame() {
         const MEMORY_SIZE: usize = 256;
         let mut memory: [u64; MEMORY_SIZE] = [0; MEMORY_SIZE];
         let raw_space: &mut [u8] = unsafe {
-            std::slice::from_raw_parts_mut(
+            core::slice::from_raw_parts_mut(
                 (&mut memory).as_mut_ptr() as *mut u8,
                 MEMORY_SIZE * size_of::<u64>(),
             )
@@ -607,6 +926,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_root_remaining_and_release() {
+        let mut memory: Box<[u64]> = Box::new([0; 4]);
+        let raw_space: &mut [u8] =
+            unsafe { core::slice::from_raw_parts_mut(memory.as_mut_ptr() as *mut u8, 32) };
+        let mut root = RootMutSpace::new(raw_space);
+        assert_eq!(root.remaining(), Some(32));
+
+        root.allocate(12, true).unwrap();
+        assert_eq!(root.remaining(), Some(20));
+
+        // A failed allocation poisons the space until it's recovered.
+        assert!(root.allocate(17, true).is_none());
+        assert!(root.is_poisoned());
+        assert_eq!(root.remaining(), Some(0));
+        assert!(root.allocate(1, false).is_none());
+        assert!(unsafe { root.recover() });
+        assert!(!root.is_poisoned());
+        assert_eq!(root.remaining(), Some(20));
+        assert_eq!(root.allocate(8, true).unwrap().0, 4);
+        assert_eq!(root.remaining(), Some(8));
+
+        {
+            let mut frame =
+                FramedMutSpace::new(&mut root as &mut dyn MutSpace, URID::<()>::new(1).unwrap())
+                    .unwrap();
+            assert_eq!(frame.remaining(), Some(0));
+            assert!(!unsafe { frame.release(1) });
+        }
+
+        assert!(unsafe { root.release(16) });
+        assert_eq!(root.remaining(), Some(16));
+        assert!(!unsafe { root.release(17) });
+        assert_eq!(root.allocate(16, true).unwrap().0, 0);
+        assert_eq!(root.remaining(), Some(0));
+    }
+
+    #[test]
+    fn test_unaligned_read() {
+        // The first value is placed at an address that is only 32-bit-aligned.
+        let mut memory = [0u64; 3];
+        let bytes = unsafe { std::slice::from_raw_parts_mut(memory.as_mut_ptr() as *mut u8, 24) };
+        bytes[4..12].copy_from_slice(&42i64.to_ne_bytes());
+        bytes[16..24].copy_from_slice(&17i64.to_ne_bytes());
+
+        let space = Space::from_slice(&bytes[4..]);
+        assert!(space.split_type::<i64>().is_none());
+        assert!(space.split_type::<sys::LV2_Atom>().is_some());
+        #[cfg(feature = "unaligned")]
+        assert_eq!(space.read_type::<i64>().unwrap().0, 42);
+        #[cfg(not(feature = "unaligned"))]
+        assert!(space.read_type::<i64>().is_none());
+
+        // Aligned values are read in both modes.
+        let space = Space::from_slice(&bytes[16..]);
+        assert_eq!(space.read_type::<i64>().unwrap().0, 17);
+        assert_eq!(space.split_type::<i64>().unwrap().0, &17);
+    }
+
     #[test]
     fn unaligned_root_write() {
         let mut raw_space = Box::new([0u8; 8]);
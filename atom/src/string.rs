@@ -1,6 +1,6 @@
 //! String handling atoms.
 //!
-//! This module contains two different atoms: The [`String`](struct.String.html) and the [`Literal`](struct.Literal.html). The former is for simple, non-localized UTF-8 strings, like URIs or paths, and the later is either for localized text, e.g. descriptions in the user interface, or RDF literals.
+//! This module contains three different atoms: The [`String`](struct.String.html), the [`Path`](struct.Path.html) and the [`Literal`](struct.Literal.html). The first is for simple, non-localized UTF-8 strings, like URIs, the second one is a string that contains the path of a local file, and the last is either for localized text, e.g. descriptions in the user interface, or RDF literals.
 //!
 //! Reading and writing these atoms is pretty simple: They don't require a parameter and return a either a `&str` or the literal info and a `&str`. Writing is done with a writing handle which can append strings to the string/literal. When dropped, the handle will append the null character, you therefore don't have to handle it on your own.
 //!
@@ -26,6 +26,7 @@
 //! # Specifications
 //!
 //! [http://lv2plug.in/ns/ext/atom/atom.html#String](http://lv2plug.in/ns/ext/atom/atom.html#String)
+//! [http://lv2plug.in/ns/ext/atom/atom.html#Path](http://lv2plug.in/ns/ext/atom/atom.html#Path)
 //! [http://lv2plug.in/ns/ext/atom/atom.html#Literal](http://lv2plug.in/ns/ext/atom/atom.html#Literal)
 use crate::prelude::*;
 use crate::space::*;
@@ -66,8 +67,9 @@ where
             return None;
         };
         let data = body.data()?;
-        std::str::from_utf8(&data[0..data.len() - 1])
-            .or_else(|error| std::str::from_utf8(&data[0..error.valid_up_to()]))
+        let data = &data[..data.len().checked_sub(1)?];
+        core::str::from_utf8(data)
+            .or_else(|error| core::str::from_utf8(&data[0..error.valid_up_to()]))
             .ok()
             .map(|string| (info, string))
     }
@@ -109,9 +111,9 @@ where
     type WriteHandle = StringWriter<'a, 'b>;
 
     fn read(body: Space<'a>, _: ()) -> Option<&'a str> {
-        body.data()
-            .and_then(|data| std::str::from_utf8(data).ok())
-            .map(|string| &string[..string.len() - 1]) // removing the null-terminator
+        let data = body.data()?;
+        // removing the null-terminator
+        core::str::from_utf8(&data[..data.len().checked_sub(1)?]).ok()
     }
 
     fn init(frame: FramedMutSpace<'a, 'b>, _: ()) -> Option<StringWriter<'a, 'b>> {
@@ -119,6 +121,35 @@ where
     }
 }
 
+/// An atom containing the path of a local file.
+///
+/// Paths are read and written like [strings](struct.String.html), but they are special in the state of a plugin, where they are mapped to abstract paths that are independent of the file system of the host.
+///
+/// [See also the module documentation.](index.html)
+pub struct Path;
+
+unsafe impl UriBound for Path {
+    const URI: &'static [u8] = sys::LV2_ATOM__Path;
+}
+
+impl<'a, 'b> Atom<'a, 'b> for Path
+where
+    'a: 'b,
+{
+    type ReadParameter = ();
+    type ReadHandle = &'a str;
+    type WriteParameter = ();
+    type WriteHandle = StringWriter<'a, 'b>;
+
+    fn read(body: Space<'a>, _: ()) -> Option<&'a str> {
+        String::read(body, ())
+    }
+
+    fn init(frame: FramedMutSpace<'a, 'b>, _: ()) -> Option<StringWriter<'a, 'b>> {
+        String::init(frame, ())
+    }
+}
+
 /// Handle to append strings to a string or literal.
 pub struct StringWriter<'a, 'b> {
     frame: FramedMutSpace<'a, 'b>,
@@ -133,7 +164,7 @@ impl<'a, 'b> StringWriter<'a, 'b> {
     pub fn append(&mut self, string: &str) -> Option<&mut str> {
         let data = string.as_bytes();
         let space = self.frame.write_raw(data, false)?;
-        unsafe { Some(std::str::from_utf8_unchecked_mut(space)) }
+        unsafe { Some(core::str::from_utf8_unchecked_mut(space)) }
     }
 }
 
@@ -148,8 +179,8 @@ impl<'a, 'b> Drop for StringWriter<'a, 'b> {
 mod tests {
     use crate::prelude::*;
     use crate::space::*;
-    use std::ffi::CStr;
-    use std::mem::{size_of, size_of_val};
+    use core::ffi::CStr;
+    use core::mem::{size_of, size_of_val};
     use urid::*;
 
     struct German;
@@ -246,7 +277,7 @@ mod tests {
             assert_eq!(string.atom.type_, urids.string);
             assert_eq!(string.atom.size as usize, SAMPLE0.len() + SAMPLE1.len() + 1);
 
-            let string = std::str::from_utf8(space.split_at(string.atom.size as usize).0).unwrap();
+            let string = core::str::from_utf8(space.split_at(string.atom.size as usize).0).unwrap();
             assert_eq!(string[..string.len() - 1], SAMPLE0.to_owned() + SAMPLE1);
         }
 
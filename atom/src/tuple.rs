@@ -64,6 +64,7 @@ where
 /// An iterator over all atoms in a tuple.
 ///
 /// The item of this iterator is simply the space a single atom occupies.
+#[derive(Clone)]
 pub struct TupleIterator<'a> {
     space: Space<'a>,
 }
@@ -98,7 +99,7 @@ impl<'a, 'b> TupleWriter<'a, 'b> {
 mod tests {
     use crate::prelude::*;
     use crate::space::*;
-    use std::mem::size_of;
+    use core::mem::size_of;
     use urid::*;
 
     #[test]
@@ -147,7 +148,7 @@ mod tests {
 
             let (vector_items, space) = space.split_at(size_of::<i32>() * 9);
             let vector_items =
-                unsafe { std::slice::from_raw_parts(vector_items.as_ptr() as *const i32, 9) };
+                unsafe { core::slice::from_raw_parts(vector_items.as_ptr() as *const i32, 9) };
             assert_eq!(vector_items, &[17; 9]);
             let (_, space) = space.split_at(4);
 
@@ -25,6 +25,8 @@
 //! }
 //! ```
 //!
+//! With the optional `ndarray` and `nalgebra` features, the read slices can be viewed as arrays of these crates with [`as_array`](fn.as_array.html) and [`as_dvector`](fn.as_dvector.html), for example to analyse a spectrum with the tools of the ecosystem. Matrices are sent with the [`matrix`](../matrix/index.html) module.
+//!
 //! You may note that, unlike other atoms, the vector's URID is retrieved by calling the `vector` method. This is because two vectors with a different item type are considered two different types, and therefore would have the different URIDs. In reality, however, all vectors have the same URID and the `vector` method returns it with the fitting type.
 //!
 //! # Specification
@@ -33,8 +35,8 @@
 use crate::scalar::ScalarAtom;
 use crate::space::*;
 use crate::*;
-use std::marker::PhantomData;
-use std::mem::size_of;
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of};
 use urid::*;
 
 /// An atom containg an array of scalar atom bodies.
@@ -68,12 +70,19 @@ where
         }
 
         let data = body.data()?;
+        // The children can't be referenced if the buffer is less aligned than they are.
+        if data.as_ptr().align_offset(align_of::<C::InternalType>()) != 0 {
+            return None;
+        }
 
-        assert_eq!(data.len() % size_of::<C::InternalType>(), 0);
+        // A truncated last child means that the vector is malformed.
+        if !data.len().is_multiple_of(size_of::<C::InternalType>()) {
+            return None;
+        }
         let children_count = data.len() / size_of::<C::InternalType>();
 
         let children = unsafe {
-            std::slice::from_raw_parts(data.as_ptr() as *const C::InternalType, children_count)
+            core::slice::from_raw_parts(data.as_ptr() as *const C::InternalType, children_count)
         };
         Some(children)
     }
@@ -116,20 +125,20 @@ impl<'a, 'b, A: ScalarAtom> VectorWriter<'a, 'b, A> {
         self.frame
             .allocate(size_of::<A::InternalType>() * size, false)
             .map(|(_, data)| unsafe {
-                std::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut A::InternalType, size)
+                core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut A::InternalType, size)
             })
     }
 
     /// Append multiple elements to the vector.
     pub fn append(&mut self, data: &[A::InternalType]) -> Option<&mut [A::InternalType]> {
         let raw_data = unsafe {
-            std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+            core::slice::from_raw_parts(data.as_ptr() as *const u8, core::mem::size_of_val(data))
         };
         self.frame
             .allocate(raw_data.len(), false)
             .map(|(_, space)| unsafe {
                 space.copy_from_slice(raw_data);
-                std::slice::from_raw_parts_mut(
+                core::slice::from_raw_parts_mut(
                     space.as_mut_ptr() as *mut A::InternalType,
                     data.len(),
                 )
@@ -137,11 +146,27 @@ impl<'a, 'b, A: ScalarAtom> VectorWriter<'a, 'b, A> {
     }
 }
 
+/// View the elements of a vector as a one-dimensional `ndarray` array.
+///
+/// This function is only available with the `ndarray` feature.
+#[cfg(feature = "ndarray")]
+pub fn as_array<T>(elements: &[T]) -> ndarray::ArrayView1<'_, T> {
+    ndarray::ArrayView1::from(elements)
+}
+
+/// View the elements of a vector as a `nalgebra` column vector.
+///
+/// This function is only available with the `nalgebra` feature.
+#[cfg(feature = "nalgebra")]
+pub fn as_dvector<T: nalgebra::Scalar>(elements: &[T]) -> nalgebra::DVectorView<'_, T> {
+    nalgebra::DVectorView::from_slice(elements, elements.len())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
     use crate::space::*;
-    use std::mem::size_of;
+    use core::mem::size_of;
     use urid::*;
 
     #[test]
@@ -176,8 +201,9 @@ mod tests {
             assert_eq!(vector.body.child_size as usize, size_of::<i32>());
             assert_eq!(vector.body.child_type, urids.int.get());
 
-            let children =
-                unsafe { std::slice::from_raw_parts(children.as_ptr() as *const i32, CHILD_COUNT) };
+            let children = unsafe {
+                core::slice::from_raw_parts(children.as_ptr() as *const i32, CHILD_COUNT)
+            };
             for value in &children[0..children.len() - 1] {
                 assert_eq!(*value, 42);
             }
@@ -0,0 +1,248 @@
+//! Extension for LV2 plugins to negotiate the size of the buffers they process.
+//!
+//! By default, a plugin has to handle any block length the host passes to it, from a single frame to an arbitrary large block. Some plugins, for example FFT-based ones, can not work efficiently under these conditions and need to know the bounds of the block length in advance.
+//!
+//! There are two parts of this specification: The features [`BoundedBlockLength`](struct.BoundedBlockLength.html), [`FixedBlockLength`](struct.FixedBlockLength.html) and [`PowerOf2BlockLength`](struct.PowerOf2BlockLength.html) are guarantees from the host about the block lengths it will use. If you require one of them, simply add it to your plugin's feature collection and the plugin won't be instantiated if the host doesn't provide it. The actual buffer sizes are passed as options, which can be read into a [`BufferSizes`](struct.BufferSizes.html) struct.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_buf_size::*;
+//! use lv2_core::prelude::*;
+//! use lv2_options::*;
+//! use lv2_urid::*;
+//! use urid::*;
+//!
+//! #[derive(FeatureCollection)]
+//! pub struct Features<'a> {
+//!     map: LV2Map<'a>,
+//!     options: OptionsList<'a>,
+//!     // The plugin can only be instantiated if the host guarantees block length bounds.
+//!     _bounded: BoundedBlockLength,
+//! }
+//!
+//! #[uri("urn:lv2_buf_size:fft")]
+//! struct Fft {
+//!     buffer: Vec<f32>,
+//! }
+//!
+//! impl Plugin for Fft {
+//!     type Ports = ();
+//!     type InitFeatures = Features<'static>;
+//!     type AudioFeatures = ();
+//!
+//!     fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+//!         let urids: BufSizeURIDCollection = features.map.populate_collection()?;
+//!         let int = features.map.map_type::<Int>()?;
+//!         let sizes = BufferSizes::from_options(&features.options, &urids, int);
+//!
+//!         // Size the internal buffer according to the maximal block length.
+//!         let max_block_length = sizes.max_block_length?;
+//!         Some(Self {
+//!             buffer: vec![0.0; max_block_length as usize],
+//!         })
+//!     }
+//!
+//!     fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+//! }
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_options as options;
+extern crate lv2_sys as sys;
+
+use atom::prelude::*;
+use core::feature::*;
+use options::OptionsList;
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use urid::*;
+
+/// Marker feature to signal that the host will run the plugin with a block length between the minimal and maximal block length.
+///
+/// The bounds themselves are passed as options, which can be retrieved with [`BufferSizes`](struct.BufferSizes.html).
+pub struct BoundedBlockLength;
+
+unsafe impl UriBound for BoundedBlockLength {
+    const URI: &'static [u8] = sys::LV2_BUF_SIZE__boundedBlockLength;
+}
+
+unsafe impl Feature for BoundedBlockLength {
+    unsafe fn from_feature_ptr(
+        _feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        Ok(Self)
+    }
+}
+
+/// Marker feature to signal that the host will always run the plugin with the same block length.
+///
+/// The block length is the maximal block length, which is passed as an option.
+pub struct FixedBlockLength;
+
+unsafe impl UriBound for FixedBlockLength {
+    const URI: &'static [u8] = sys::LV2_BUF_SIZE__fixedBlockLength;
+}
+
+unsafe impl Feature for FixedBlockLength {
+    unsafe fn from_feature_ptr(
+        _feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        Ok(Self)
+    }
+}
+
+/// Marker feature to signal that the host will always run the plugin with a block length that is a power of two.
+pub struct PowerOf2BlockLength;
+
+unsafe impl UriBound for PowerOf2BlockLength {
+    const URI: &'static [u8] = sys::LV2_BUF_SIZE__powerOf2BlockLength;
+}
+
+unsafe impl Feature for PowerOf2BlockLength {
+    unsafe fn from_feature_ptr(
+        _feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        Ok(Self)
+    }
+}
+
+/// The minimal block length, as an option key.
+pub struct MinBlockLength;
+
+unsafe impl UriBound for MinBlockLength {
+    const URI: &'static [u8] = sys::LV2_BUF_SIZE__minBlockLength;
+}
+
+/// The maximal block length, as an option key.
+pub struct MaxBlockLength;
+
+unsafe impl UriBound for MaxBlockLength {
+    const URI: &'static [u8] = sys::LV2_BUF_SIZE__maxBlockLength;
+}
+
+/// The nominal block length, as an option key.
+///
+/// This is the block length the host will usually use, but it's not guaranteed to be used for every call to `run`.
+pub struct NominalBlockLength;
+
+unsafe impl UriBound for NominalBlockLength {
+    const URI: &'static [u8] = sys::LV2_BUF_SIZE__nominalBlockLength;
+}
+
+/// The size of sequence buffers in bytes, as an option key.
+pub struct SequenceSize;
+
+unsafe impl UriBound for SequenceSize {
+    const URI: &'static [u8] = sys::LV2_BUF_SIZE__sequenceSize;
+}
+
+/// A URID cache containing the keys of all buffer size options.
+#[derive(URIDCollection)]
+pub struct BufSizeURIDCollection {
+    pub min_block_length: URID<MinBlockLength>,
+    pub max_block_length: URID<MaxBlockLength>,
+    pub nominal_block_length: URID<NominalBlockLength>,
+    pub sequence_size: URID<SequenceSize>,
+}
+
+/// The buffer sizes passed by the host.
+///
+/// Every buffer size is optional since hosts are not required to provide any of them. However, if the host provides the [`BoundedBlockLength`](struct.BoundedBlockLength.html) or the [`FixedBlockLength`](struct.FixedBlockLength.html) feature, it also has to provide the minimal and maximal block length.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BufferSizes {
+    /// The minimal number of frames the plugin is run with.
+    pub min_block_length: Option<u32>,
+    /// The maximal number of frames the plugin is run with.
+    pub max_block_length: Option<u32>,
+    /// The number of frames the plugin is usually run with.
+    pub nominal_block_length: Option<u32>,
+    /// The size of sequence buffers in bytes.
+    pub sequence_size: Option<u32>,
+}
+
+impl BufferSizes {
+    /// Read the buffer sizes from the options passed by the host.
+    ///
+    /// Buffer sizes are integer atoms. Options that are missing, have a different type or are negative are ignored.
+    pub fn from_options(
+        options: &OptionsList,
+        urids: &BufSizeURIDCollection,
+        int: URID<Int>,
+    ) -> Self {
+        let read = |key: URID| {
+            options
+                .read(key, int, ())
+                .and_then(|value| u32::try_from(value).ok())
+        };
+
+        Self {
+            min_block_length: read(urids.min_block_length.into_general()),
+            max_block_length: read(urids.max_block_length.into_general()),
+            nominal_block_length: read(urids.nominal_block_length.into_general()),
+            sequence_size: read(urids.sequence_size.into_general()),
+        }
+    }
+}
+
+/// Prelude of `lv2_buf_size` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        BoundedBlockLength, BufSizeURIDCollection, BufferSizes, FixedBlockLength,
+        PowerOf2BlockLength,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::mem::size_of;
+
+    fn option(key: u32, type_: u32, value: &i32) -> sys::LV2_Options_Option {
+        sys::LV2_Options_Option {
+            context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+            subject: 0,
+            key,
+            size: size_of::<i32>() as u32,
+            type_,
+            value: value as *const i32 as *const c_void,
+        }
+    }
+
+    #[test]
+    fn test_buffer_sizes() {
+        let map = HashURIDMapper::new();
+        let urids: BufSizeURIDCollection = map.populate_collection().unwrap();
+        let int = map.map_type::<Int>().unwrap();
+        let float = map.map_type::<Float>().unwrap();
+
+        let min: i32 = 16;
+        let max: i32 = 4096;
+        let nominal: i32 = -1;
+        let raw_options = [
+            option(urids.min_block_length.get(), int.get(), &min),
+            option(urids.max_block_length.get(), int.get(), &max),
+            option(urids.nominal_block_length.get(), int.get(), &nominal),
+            option(urids.sequence_size.get(), float.get(), &max),
+        ];
+        let options = OptionsList::from_raw(&raw_options);
+
+        assert_eq!(
+            BufferSizes {
+                min_block_length: Some(16),
+                max_block_length: Some(4096),
+                nominal_block_length: None,
+                sequence_size: None,
+            },
+            BufferSizes::from_options(&options, &urids, int)
+        );
+        assert_eq!(
+            BufferSizes::default(),
+            BufferSizes::from_options(&OptionsList::from_raw(&[]), &urids, int)
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use cargo_lv2::{build_bundle, BundleOptions};
+use std::path::Path;
+use std::process::exit;
+
+const USAGE: &str =
+    "Usage: cargo lv2bundle [--release] [--target TRIPLE] [--manifest-path PATH] [--out DIRECTORY]";
+
+fn main() {
+    // Cargo passes the name of the subcommand as the first argument.
+    let mut args = std::env::args()
+        .skip(1)
+        .skip_while(|arg| arg == "lv2bundle");
+    let mut manifest_path = String::from("Cargo.toml");
+    let mut release = false;
+    let mut target: Option<String> = None;
+    let mut output: Option<String> = None;
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "--release" => {
+                release = true;
+                continue;
+            }
+            "--target" => &mut target,
+            "--out" => &mut output,
+            "--manifest-path" => {
+                manifest_path = args.next().unwrap_or_else(|| usage());
+                continue;
+            }
+            _ => usage(),
+        };
+        *value = Some(args.next().unwrap_or_else(|| usage()));
+    }
+
+    let mut options = BundleOptions::new(Path::new(&manifest_path));
+    if release {
+        options = options.release();
+    }
+    if let Some(target) = target.as_ref() {
+        options = options.target(target);
+    }
+    if let Some(output) = output.as_ref() {
+        options = options.output(Path::new(output));
+    }
+
+    let bundle_path = match build_bundle(&options) {
+        Ok(bundle_path) => bundle_path,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            exit(1);
+        }
+    };
+    println!("Assembled the bundle {}", bundle_path.display());
+
+    // Libraries for other targets can't be loaded to check them.
+    if target.is_some() {
+        return;
+    }
+    let issues = unsafe { lv2_lint::lint_bundle(&bundle_path) };
+    for issue in issues.iter() {
+        let severity = if issue.is_error() { "error" } else { "warning" };
+        match issue.plugin() {
+            Some(plugin) => eprintln!("{}: <{}>: {}", severity, plugin, issue),
+            None => eprintln!("{}: {}", severity, issue),
+        }
+    }
+    if issues.iter().any(|issue| issue.is_error()) {
+        exit(1);
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    exit(2);
+}
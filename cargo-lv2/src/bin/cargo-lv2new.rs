@@ -0,0 +1,24 @@
+use cargo_lv2::scaffold;
+use std::path::Path;
+use std::process::exit;
+
+fn main() {
+    // Cargo passes the name of the subcommand as the first argument.
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .skip_while(|arg| arg == "lv2new")
+        .collect();
+    let name = match args.as_slice() {
+        [name] => name,
+        _ => {
+            eprintln!("Usage: cargo lv2new NAME");
+            exit(2);
+        }
+    };
+
+    if let Err(error) = scaffold(Path::new(name), name) {
+        eprintln!("error: {}", error);
+        exit(1);
+    }
+    println!("Created the plugin crate `{}`", name);
+}
@@ -0,0 +1,208 @@
+use crate::{Manifest, ToolError};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use ttl_gen::binary_name;
+
+/// The options to build and bundle a plugin crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleOptions {
+    manifest_path: PathBuf,
+    release: bool,
+    target: Option<String>,
+    output: Option<PathBuf>,
+}
+
+impl BundleOptions {
+    /// Create the options to bundle the crate with the given `Cargo.toml`.
+    ///
+    /// By default, the crate is built in debug mode for the host platform and the bundle is assembled in `lv2/<package>.lv2` in the target directory.
+    pub fn new(manifest_path: &Path) -> Self {
+        Self {
+            manifest_path: manifest_path.to_owned(),
+            release: false,
+            target: None,
+            output: None,
+        }
+    }
+
+    /// Build the crate in release mode.
+    pub fn release(mut self) -> Self {
+        self.release = true;
+        self
+    }
+
+    /// Build the crate for the given target triple.
+    pub fn target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_owned());
+        self
+    }
+
+    /// Assemble the bundle in the given directory.
+    pub fn output(mut self, path: &Path) -> Self {
+        self.output = Some(path.to_owned());
+        self
+    }
+
+    /// Run a Cargo command for the crate, with the common build options.
+    fn cargo(&self, command: &str, extra_args: &[&str]) -> Result<(), ToolError> {
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+        let mut cargo = Command::new(cargo);
+        cargo
+            .arg(command)
+            .arg("--manifest-path")
+            .arg(&self.manifest_path);
+        if self.release {
+            cargo.arg("--release");
+        }
+        if let Some(target) = self.target.as_ref() {
+            cargo.arg("--target").arg(target);
+        }
+        cargo.args(extra_args);
+
+        let status = cargo.status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(ToolError::Cargo(command.to_owned()))
+        }
+    }
+
+    /// Find the target directory of the crate.
+    fn target_directory(&self) -> Result<PathBuf, ToolError> {
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+        let output = Command::new(cargo)
+            .args(["metadata", "--format-version", "1", "--no-deps"])
+            .arg("--manifest-path")
+            .arg(&self.manifest_path)
+            .output()?;
+        if !output.status.success() {
+            return Err(ToolError::Cargo("metadata".to_owned()));
+        }
+        let metadata = String::from_utf8_lossy(&output.stdout);
+        json_string(&metadata, "target_directory")
+            .map(PathBuf::from)
+            .ok_or_else(|| ToolError::Cargo("metadata".to_owned()))
+    }
+}
+
+/// Build a plugin crate and assemble its bundle.
+///
+/// The library of the crate is built and copied into the bundle. Then, the Turtle files of the bundle are created: If the crate contains a directory whose name ends with `.lv2`, like `eg-amp-rs.lv2`, its Turtle files are copied into the bundle. Otherwise, the crate has to have a `turtle` example, which is run with the bundle directory as its argument and writes the Turtle files with [`lv2-ttl-gen`](../lv2_ttl_gen/index.html).
+///
+/// The path of the assembled bundle is returned.
+pub fn build_bundle(options: &BundleOptions) -> Result<PathBuf, ToolError> {
+    let manifest = Manifest::read(&options.manifest_path)?;
+    if !manifest.is_cdylib() {
+        return Err(ToolError::NotCdylib);
+    }
+    let crate_directory = options
+        .manifest_path
+        .parent()
+        .filter(|directory| !directory.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    options.cargo("build", &["--lib"])?;
+
+    let target_directory = options.target_directory()?;
+    let mut library_directory = target_directory.clone();
+    if let Some(target) = options.target.as_ref() {
+        library_directory.push(target);
+    }
+    library_directory.push(if options.release { "release" } else { "debug" });
+
+    let bundle_path = match options.output.as_ref() {
+        Some(output) => output.clone(),
+        None => target_directory
+            .join("lv2")
+            .join(format!("{}.lv2", manifest.package())),
+    };
+    if bundle_path.exists() {
+        fs::remove_dir_all(&bundle_path)?;
+    }
+
+    match find_turtle_directory(crate_directory)? {
+        Some(turtle_directory) => assemble_bundle(
+            &manifest,
+            &library_directory,
+            Some(&turtle_directory),
+            &bundle_path,
+        )?,
+        None => {
+            assemble_bundle(&manifest, &library_directory, None, &bundle_path)?;
+            let bundle = bundle_path.to_str().ok_or(ToolError::BadPath)?;
+            // The example runs on the host, even if the plugin is built for another target.
+            let host_options = BundleOptions {
+                target: None,
+                ..options.clone()
+            };
+            host_options
+                .cargo("run", &["--example", "turtle", "--", bundle])
+                .map_err(|_| ToolError::MissingTurtle)?;
+        }
+    }
+
+    Ok(bundle_path)
+}
+
+/// Assemble a bundle from a built library.
+///
+/// The library of the crate is copied from the library directory into the bundle, along with all Turtle files of the Turtle directory, if there is one. The bundle directory is created if it doesn't exist.
+pub fn assemble_bundle(
+    manifest: &Manifest,
+    library_directory: &Path,
+    turtle_directory: Option<&Path>,
+    bundle_path: &Path,
+) -> Result<(), ToolError> {
+    let binary = binary_name(&manifest.lib_name());
+    let library_path = library_directory.join(&binary);
+    if !library_path.is_file() {
+        return Err(ToolError::MissingLibrary(library_path));
+    }
+
+    fs::create_dir_all(bundle_path)?;
+    fs::copy(&library_path, bundle_path.join(&binary))?;
+
+    if let Some(turtle_directory) = turtle_directory {
+        for entry in fs::read_dir(turtle_directory)? {
+            let path = entry?.path();
+            if path.extension() == Some("ttl".as_ref()) {
+                fs::copy(&path, bundle_path.join(path.file_name().unwrap()))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Find a directory with a hand-written manifest in the crate, like `eg-amp-rs.lv2/manifest.ttl`.
+fn find_turtle_directory(crate_directory: &Path) -> Result<Option<PathBuf>, ToolError> {
+    for entry in fs::read_dir(crate_directory)? {
+        let path = entry?.path();
+        if path.extension() == Some("lv2".as_ref()) && path.join("manifest.ttl").is_file() {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+/// Find the value of a string field in a JSON document.
+///
+/// The metadata of Cargo only contains one `target_directory` field, which is why it's enough to search for the key.
+fn json_string(json: &str, key: &str) -> Option<String> {
+    let start = json.find(&format!("\"{}\":\"", key))? + key.len() + 4;
+    let mut value = String::new();
+    let mut chars = json[start..].chars();
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                escaped => value.push(escaped),
+            },
+            c => value.push(c),
+        }
+    }
+}
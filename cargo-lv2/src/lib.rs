@@ -0,0 +1,79 @@
+//! Cargo subcommands to create and package LV2 plugins.
+//!
+//! Plugins are distributed as bundles: Directories whose names end with `.lv2` and which contain the plugin library and its Turtle description. Assembling them by hand is error-prone, since the library has to be built as a `cdylib`, its platform-specific file name has to match the description and the description has to match the code. This crate provides two Cargo subcommands, which are installed with `cargo install cargo-lv2`:
+//!
+//! * `cargo lv2new <name>` creates a new plugin crate with [`scaffold`](fn.scaffold.html). It contains a simple amplifier plugin, which is built as a `cdylib`, and a `turtle` example, which describes the plugin with [`lv2-ttl-gen`](../lv2_ttl_gen/index.html).
+//! * `cargo lv2bundle [--release] [--target <triple>] [--out <directory>]` builds the plugin crate in the current directory and assembles its bundle with [`build_bundle`](fn.build_bundle.html). The Turtle files are either copied from a hand-written bundle directory in the crate, like `eg-amp-rs.lv2`, or written by the `turtle` example. Afterwards, the bundle is checked with [`lv2-lint`](../lv2_lint/index.html).
+//!
+//! ## Example usage
+//!
+//! ```sh
+//! cargo lv2new my-amp
+//! cd my-amp
+//! cargo lv2bundle --release
+//! cp -r target/lv2/my-amp.lv2 ~/.lv2/
+//! ```
+extern crate lv2_ttl_gen as ttl_gen;
+
+mod bundle;
+pub use bundle::*;
+
+mod manifest;
+pub use manifest::*;
+
+mod scaffold;
+pub use scaffold::*;
+
+use std::path::PathBuf;
+
+/// Errors that may occur when creating or bundling plugin crates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ToolError {
+    /// Reading or writing a file failed.
+    Io(std::io::ErrorKind),
+    /// The name isn't a valid crate name.
+    BadName(String),
+    /// A path can't be passed to Cargo.
+    BadPath,
+    /// A field of `Cargo.toml` is missing or can't be read.
+    Manifest(String),
+    /// The library isn't built as a `cdylib`, which is required to load it in hosts.
+    NotCdylib,
+    /// A Cargo command failed.
+    Cargo(String),
+    /// The built library wasn't found at the given path.
+    MissingLibrary(PathBuf),
+    /// The crate neither contains a bundle directory with Turtle files nor a `turtle` example that writes them.
+    MissingTurtle,
+}
+
+impl From<std::io::Error> for ToolError {
+    fn from(error: std::io::Error) -> Self {
+        ToolError::Io(error.kind())
+    }
+}
+
+impl std::fmt::Display for ToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ToolError::Io(kind) => write!(f, "I/O error: {:?}", kind),
+            ToolError::BadName(name) => write!(f, "\"{}\" isn't a valid crate name", name),
+            ToolError::BadPath => write!(f, "The bundle path isn't valid UTF-8"),
+            ToolError::Manifest(field) => {
+                write!(f, "Unable to read \"{}\" from Cargo.toml", field)
+            }
+            ToolError::NotCdylib => write!(
+                f,
+                "The library has to be built as a cdylib; Add `crate-type = [\"cdylib\"]` to the [lib] section of Cargo.toml"
+            ),
+            ToolError::Cargo(command) => write!(f, "`cargo {}` failed", command),
+            ToolError::MissingLibrary(path) => {
+                write!(f, "The library {} wasn't built", path.display())
+            }
+            ToolError::MissingTurtle => write!(
+                f,
+                "The Turtle description is missing; Add a bundle directory with a manifest.ttl or a `turtle` example"
+            ),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::ToolError;
+use std::path::Path;
+
+/// The parts of a plugin crate's `Cargo.toml` that are needed to build and bundle it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Manifest {
+    package: String,
+    lib_name: Option<String>,
+    crate_types: Vec<String>,
+}
+
+impl Manifest {
+    /// Read the manifest from a `Cargo.toml` file.
+    pub fn read(path: &Path) -> Result<Self, ToolError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse the manifest from the contents of a `Cargo.toml` file.
+    ///
+    /// This isn't a complete TOML parser: Only the `name` of the `[package]` section and the `name` and `crate-type` of the `[lib]` section are read, and they have to be written as plain strings and arrays.
+    pub fn parse(toml: &str) -> Result<Self, ToolError> {
+        let mut package: Option<String> = None;
+        let mut lib_name: Option<String> = None;
+        let mut crate_types: Vec<String> = Vec::new();
+
+        let mut section = String::new();
+        let mut lines = toml.lines();
+        while let Some(line) = lines.next() {
+            let line = strip_comment(line).trim();
+            if line.starts_with('[') {
+                section = line
+                    .trim_matches(|c| c == '[' || c == ']')
+                    .trim()
+                    .to_owned();
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            match (section.as_str(), key) {
+                ("package", "name") => package = Some(parse_string(value)?),
+                ("lib", "name") => lib_name = Some(parse_string(value)?),
+                ("lib", "crate-type") => {
+                    // Arrays may span multiple lines.
+                    let mut array = value.to_owned();
+                    while !array.contains(']') {
+                        match lines.next() {
+                            Some(line) => array.push_str(strip_comment(line)),
+                            None => return Err(ToolError::Manifest("crate-type".to_owned())),
+                        }
+                    }
+                    crate_types = parse_array(&array)?;
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            package: package.ok_or_else(|| ToolError::Manifest("package.name".to_owned()))?,
+            lib_name,
+            crate_types,
+        })
+    }
+
+    /// The name of the package.
+    pub fn package(&self) -> &str {
+        self.package.as_ref()
+    }
+
+    /// The name of the library, which defaults to the package name with the dashes replaced by underscores.
+    pub fn lib_name(&self) -> String {
+        match self.lib_name.as_ref() {
+            Some(name) => name.clone(),
+            None => self.package.replace('-', "_"),
+        }
+    }
+
+    /// The crate types of the library.
+    pub fn crate_types(&self) -> &[String] {
+        self.crate_types.as_ref()
+    }
+
+    /// Check whether the library is built as a dynamic library that can be loaded by hosts.
+    pub fn is_cdylib(&self) -> bool {
+        self.crate_types.iter().any(|type_| type_ == "cdylib")
+    }
+}
+
+/// Remove a comment from a line.
+///
+/// Strings in the manifest don't contain hashes, which is why the quotes aren't taken into account.
+fn strip_comment(line: &str) -> &str {
+    match line.find('#') {
+        Some(position) => &line[..position],
+        None => line,
+    }
+}
+
+/// Parse a basic string, like `"amp"`.
+fn parse_string(value: &str) -> Result<String, ToolError> {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .filter(|value| !value.contains('"'))
+        .map(str::to_owned)
+        .ok_or_else(|| ToolError::Manifest(value.to_owned()))
+}
+
+/// Parse an array of basic strings, like `["cdylib", "rlib"]`.
+fn parse_array(value: &str) -> Result<Vec<String>, ToolError> {
+    let items = value
+        .trim()
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .ok_or_else(|| ToolError::Manifest(value.to_owned()))?;
+    items
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse_string)
+        .collect()
+}
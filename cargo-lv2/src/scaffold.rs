@@ -0,0 +1,131 @@
+use crate::ToolError;
+use std::fs;
+use std::path::Path;
+
+const CARGO_TOML: &str = r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2018"
+
+# Hosts load plugins as dynamic libraries. The library is also built as a
+# Rust library, so that the `turtle` example can describe the plugin.
+[lib]
+crate-type = ["cdylib", "rlib"]
+
+[dependencies]
+lv2 = "0.5.0"
+
+[dev-dependencies]
+lv2-ttl-gen = "0.1.0"
+"#;
+
+const LIB_RS: &str = r#"use lv2::prelude::*;
+
+#[derive(PortCollection)]
+pub struct Ports {
+    gain: InputPort<Control>,
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+// Every plugin needs a globally unique URI. Replace it with a URI you own,
+// like the URL of the plugin's repository.
+#[uri("urn:{name}")]
+pub struct {type_name};
+
+impl Plugin for {type_name} {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_plugin_info: &PluginInfo, _features: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        let coef = if *ports.gain > -90.0 {
+            10.0_f32.powf(*ports.gain * 0.05)
+        } else {
+            0.0
+        };
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = input * coef;
+        }
+    }
+}
+
+lv2_descriptors!({type_name});
+"#;
+
+const TURTLE_RS: &str = r#"//! Write the Turtle description of the plugin to the bundle directory passed
+//! as the first argument. This example is run by `cargo lv2bundle`.
+use lv2_ttl_gen::*;
+use {lib_name}::{type_name};
+
+fn main() {
+    let bundle_path = std::env::args_os()
+        .nth(1)
+        .expect("The bundle directory is missing");
+
+    let plugin = PluginDescription::new::<{type_name}>()
+        .name("{type_name}")
+        .class(plugin_class::AMPLIFIER)
+        .port_range("gain", -90.0, 0.0, 24.0);
+
+    BundleDescription::new(&binary_name("{name}"))
+        .plugin(plugin, "{lib_name}.ttl")
+        .write(bundle_path.as_ref())
+        .unwrap();
+}
+"#;
+
+const GITIGNORE: &str = "/target\n";
+
+/// Create a new plugin crate with the given name in the given directory.
+///
+/// The crate contains a simple amplifier plugin, which is built as a dynamic library, and a `turtle` example, which writes the Turtle description of the plugin with [`lv2-ttl-gen`](../lv2_ttl_gen/index.html). The directory must not exist yet.
+pub fn scaffold(directory: &Path, name: &str) -> Result<(), ToolError> {
+    if name.is_empty()
+        || name.starts_with(|c: char| c.is_ascii_digit())
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ToolError::BadName(name.to_owned()));
+    }
+    if directory.exists() {
+        return Err(ToolError::Io(std::io::ErrorKind::AlreadyExists));
+    }
+
+    let lib_name = name.replace('-', "_");
+    let type_name = type_name(name);
+    let fill = |template: &str| {
+        template
+            .replace("{name}", name)
+            .replace("{lib_name}", &lib_name)
+            .replace("{type_name}", &type_name)
+    };
+
+    fs::create_dir_all(directory.join("src"))?;
+    fs::create_dir_all(directory.join("examples"))?;
+    fs::write(directory.join("Cargo.toml"), fill(CARGO_TOML))?;
+    fs::write(directory.join("src").join("lib.rs"), fill(LIB_RS))?;
+    fs::write(
+        directory.join("examples").join("turtle.rs"),
+        fill(TURTLE_RS),
+    )?;
+    fs::write(directory.join(".gitignore"), GITIGNORE)?;
+    Ok(())
+}
+
+/// Convert a crate name to the name of the plugin type, like `MyAmp` for `my-amp`.
+fn type_name(name: &str) -> String {
+    name.split(['-', '_'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect()
+}
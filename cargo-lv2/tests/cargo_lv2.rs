@@ -0,0 +1,124 @@
+use cargo_lv2::*;
+use lv2_ttl_gen::binary_name;
+use std::fs;
+use std::path::PathBuf;
+
+/// Return the path of a fresh directory for a test.
+fn test_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+#[test]
+fn test_manifest() {
+    let manifest = Manifest::parse(
+        r#"
+[package]
+name = "my-amp" # The name of the package.
+version = "0.1.0"
+
+[lib]
+crate-type = [
+    "cdylib",
+    "rlib",
+]
+
+[dependencies]
+name = "not-the-package"
+"#,
+    )
+    .unwrap();
+    assert_eq!(manifest.package(), "my-amp");
+    assert_eq!(manifest.lib_name(), "my_amp");
+    assert_eq!(manifest.crate_types(), &["cdylib", "rlib"]);
+    assert!(manifest.is_cdylib());
+
+    let manifest = Manifest::parse("[package]\nname = \"amp\"\n[lib]\nname = \"eg_amp\"").unwrap();
+    assert_eq!(manifest.lib_name(), "eg_amp");
+    assert!(!manifest.is_cdylib());
+
+    assert_eq!(
+        Manifest::parse("[lib]\ncrate-type = [\"cdylib\"]"),
+        Err(ToolError::Manifest("package.name".to_owned()))
+    );
+    assert!(Manifest::parse("[package]\nname = amp").is_err());
+}
+
+#[test]
+fn test_scaffold() {
+    let directory = test_directory("cargo_lv2_scaffold");
+    let crate_directory = directory.join("my-amp");
+    scaffold(&crate_directory, "my-amp").unwrap();
+
+    let manifest = Manifest::read(&crate_directory.join("Cargo.toml")).unwrap();
+    assert_eq!(manifest.package(), "my-amp");
+    assert!(manifest.is_cdylib());
+    let lib = fs::read_to_string(crate_directory.join("src").join("lib.rs")).unwrap();
+    assert!(lib.contains("pub struct MyAmp;"));
+    assert!(lib.contains("lv2_descriptors!(MyAmp);"));
+    let turtle = fs::read_to_string(crate_directory.join("examples").join("turtle.rs")).unwrap();
+    assert!(turtle.contains("use my_amp::MyAmp;"));
+    assert!(turtle.contains("binary_name(\"my-amp\")"));
+
+    // Existing crates aren't overwritten.
+    assert_eq!(
+        scaffold(&crate_directory, "my-amp"),
+        Err(ToolError::Io(std::io::ErrorKind::AlreadyExists))
+    );
+    assert_eq!(
+        scaffold(&directory.join("amp"), "my amp"),
+        Err(ToolError::BadName("my amp".to_owned()))
+    );
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_assemble_bundle() {
+    let directory = test_directory("cargo_lv2_assemble");
+    let library_directory = directory.join("release");
+    let turtle_directory = directory.join("eg-amp-rs.lv2");
+    let bundle_path = directory.join("lv2").join("amp.lv2");
+    fs::create_dir_all(&library_directory).unwrap();
+    fs::create_dir_all(&turtle_directory).unwrap();
+    fs::write(turtle_directory.join("manifest.ttl"), "manifest").unwrap();
+    fs::write(turtle_directory.join("amp.ttl"), "amp").unwrap();
+    fs::write(turtle_directory.join("README.md"), "readme").unwrap();
+
+    let manifest = Manifest::parse("[package]\nname = \"amp\"").unwrap();
+    assert_eq!(
+        assemble_bundle(
+            &manifest,
+            &library_directory,
+            Some(&turtle_directory),
+            &bundle_path
+        ),
+        Err(ToolError::MissingLibrary(
+            library_directory.join(binary_name("amp"))
+        ))
+    );
+
+    fs::write(library_directory.join(binary_name("amp")), "library").unwrap();
+    assemble_bundle(
+        &manifest,
+        &library_directory,
+        Some(&turtle_directory),
+        &bundle_path,
+    )
+    .unwrap();
+    let mut files: Vec<String> = fs::read_dir(&bundle_path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    let mut expected = vec![
+        "amp.ttl".to_owned(),
+        "manifest.ttl".to_owned(),
+        binary_name("amp"),
+    ];
+    expected.sort();
+    assert_eq!(files, expected);
+
+    fs::remove_dir_all(&directory).unwrap();
+}
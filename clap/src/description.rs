@@ -0,0 +1,260 @@
+use crate::sys;
+use core::port::PortDirection;
+use std::ffi::{c_char, CString};
+use ttl_gen::{plugin_class, PluginDescription};
+use urid::uri_str;
+
+/// The description of a plugin for CLAP hosts.
+///
+/// It wraps the [`PluginDescription`](../lv2_ttl_gen/struct.PluginDescription.html) that is also used to write the Turtle files of the plugin, which provides the names and ranges of the ports, and adds the information CLAP hosts display in their plugin browsers. The id of the plugin is its URI, its version is the [`VERSION`](../lv2_core/plugin/trait.Plugin.html#associatedconstant.VERSION) of the plugin and its features are derived from its classes: Instruments have the `instrument` feature, analysers the `analyzer` feature and all other plugins the `audio-effect` feature.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClapDescription {
+    plugin: PluginDescription,
+    id: String,
+    name: String,
+    vendor: String,
+    url: String,
+    version: String,
+    description: String,
+    features: Vec<String>,
+}
+
+impl ClapDescription {
+    /// Create the description from the description of the plugin and a human-readable name.
+    pub fn new(plugin: PluginDescription, name: &str) -> Self {
+        let classes = plugin.classes();
+        let feature = if classes
+            .iter()
+            .any(|class| class == plugin_class::INSTRUMENT)
+        {
+            "instrument"
+        } else if classes.iter().any(|class| class == plugin_class::ANALYSER) {
+            "analyzer"
+        } else {
+            "audio-effect"
+        };
+        Self {
+            id: plugin.uri().to_owned(),
+            name: name.to_owned(),
+            vendor: String::new(),
+            url: String::new(),
+            version: plugin
+                .version()
+                .map(|version| version.to_string())
+                .unwrap_or_default(),
+            description: String::new(),
+            features: vec![feature.to_owned()],
+            plugin,
+        }
+    }
+
+    /// Set the id of the plugin, which defaults to its URI.
+    ///
+    /// CLAP hosts identify plugins in their sessions by this id, which therefore must not change after the plugin was released.
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = id.to_owned();
+        self
+    }
+
+    /// Set the vendor of the plugin.
+    pub fn vendor(mut self, vendor: &str) -> Self {
+        self.vendor = vendor.to_owned();
+        self
+    }
+
+    /// Set the URL of the plugin's website.
+    pub fn url(mut self, url: &str) -> Self {
+        self.url = url.to_owned();
+        self
+    }
+
+    /// Set the version string of the plugin, which defaults to its LV2 version.
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = version.to_owned();
+        self
+    }
+
+    /// Set a short description of the plugin.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_owned();
+        self
+    }
+
+    /// Add a CLAP feature, like `stereo` or `reverb`.
+    pub fn feature(mut self, feature: &str) -> Self {
+        if !self.features.iter().any(|known| known == feature) {
+            self.features.push(feature.to_owned());
+        }
+        self
+    }
+
+    /// The description of the LV2 plugin.
+    pub fn plugin(&self) -> &PluginDescription {
+        &self.plugin
+    }
+
+    /// The CLAP features of the plugin.
+    pub fn features(&self) -> &[String] {
+        self.features.as_ref()
+    }
+}
+
+/// The raw CLAP descriptor of a plugin, together with the strings it points to.
+pub(crate) struct RawDescriptor {
+    _strings: Vec<CString>,
+    _features: Vec<*const c_char>,
+    pub raw: sys::clap_plugin_descriptor,
+}
+
+impl RawDescriptor {
+    pub fn new(description: &ClapDescription) -> Self {
+        // Strings with null bytes can't be passed to the host and are cut off at the first null byte.
+        let string = |value: &str| {
+            CString::new(value.split('\0').next().unwrap_or_default()).unwrap_or_default()
+        };
+        let strings: Vec<CString> = [
+            description.id.as_str(),
+            description.name.as_str(),
+            description.vendor.as_str(),
+            description.url.as_str(),
+            description.version.as_str(),
+            description.description.as_str(),
+        ]
+        .iter()
+        .map(|value| string(value))
+        .chain(description.features.iter().map(|feature| string(feature)))
+        .collect();
+        let mut features: Vec<*const c_char> = strings[6..]
+            .iter()
+            .map(|feature| feature.as_ptr())
+            .collect();
+        features.push(std::ptr::null());
+
+        let raw = sys::clap_plugin_descriptor {
+            clap_version: sys::CLAP_VERSION,
+            id: strings[0].as_ptr(),
+            name: strings[1].as_ptr(),
+            vendor: strings[2].as_ptr(),
+            url: strings[3].as_ptr(),
+            manual_url: strings[3].as_ptr(),
+            support_url: strings[3].as_ptr(),
+            version: strings[4].as_ptr(),
+            description: strings[5].as_ptr(),
+            features: features.as_ptr(),
+        };
+        Self {
+            _strings: strings,
+            _features: features,
+            raw,
+        }
+    }
+}
+
+/// A CLAP audio port, which combines the LV2 audio ports of a group or is a single LV2 port.
+pub(crate) struct AudioPortLayout {
+    pub name: String,
+    pub group: Option<String>,
+    pub channels: Vec<u32>,
+    pub main: bool,
+}
+
+/// A CLAP parameter, which is an LV2 control port.
+pub(crate) struct ParamLayout {
+    pub index: u32,
+    pub name: String,
+    pub minimum: f64,
+    pub default: f64,
+    pub maximum: f64,
+    pub output: bool,
+}
+
+/// The mapping of the LV2 ports of a plugin to CLAP ports and parameters.
+pub(crate) struct Layout {
+    pub audio_inputs: Vec<AudioPortLayout>,
+    pub audio_outputs: Vec<AudioPortLayout>,
+    pub params: Vec<ParamLayout>,
+    pub atom_inputs: Vec<u32>,
+    pub atom_outputs: Vec<u32>,
+    /// CV and double-precision audio ports, which are connected to silent buffers.
+    pub scratch: Vec<u32>,
+}
+
+impl Layout {
+    pub fn new(plugin: &PluginDescription) -> Self {
+        let mut layout = Self {
+            audio_inputs: Vec::new(),
+            audio_outputs: Vec::new(),
+            params: Vec::new(),
+            atom_inputs: Vec::new(),
+            atom_outputs: Vec::new(),
+            scratch: Vec::new(),
+        };
+        for port in plugin.ports() {
+            let class = port.class().unwrap_or("");
+            let input = port.direction() == Some(PortDirection::Input);
+            if class == uri_str(lv2_sys::LV2_CORE__AudioPort) {
+                let audio_ports = if input {
+                    &mut layout.audio_inputs
+                } else {
+                    &mut layout.audio_outputs
+                };
+                let group = port.group().and_then(|symbol| {
+                    plugin
+                        .groups()
+                        .iter()
+                        .find(|group| group.symbol() == symbol)
+                });
+                let position = group.and_then(|group| {
+                    audio_ports
+                        .iter()
+                        .position(|audio_port| audio_port.group.as_deref() == Some(group.symbol()))
+                });
+                let position = match position {
+                    Some(position) => {
+                        audio_ports[position].channels.push(port.index());
+                        position
+                    }
+                    None => {
+                        audio_ports.push(AudioPortLayout {
+                            name: group.map_or(port.name(), |group| group.name()).to_owned(),
+                            group: group.map(|group| group.symbol().to_owned()),
+                            channels: vec![port.index()],
+                            main: false,
+                        });
+                        audio_ports.len() - 1
+                    }
+                };
+                if !port.is_side_chain() && !audio_ports.iter().any(|audio_port| audio_port.main) {
+                    audio_ports[position].main = true;
+                }
+            } else if class == uri_str(lv2_sys::LV2_CORE__ControlPort) {
+                let (minimum, default, maximum) = port.range().unwrap_or((0.0, 0.0, 1.0));
+                layout.params.push(ParamLayout {
+                    index: port.index(),
+                    name: port.name().to_owned(),
+                    minimum: minimum as f64,
+                    default: default as f64,
+                    maximum: maximum as f64,
+                    output: !input,
+                });
+            } else if class == uri_str(lv2_sys::LV2_ATOM__AtomPort) {
+                if input {
+                    layout.atom_inputs.push(port.index());
+                } else {
+                    layout.atom_outputs.push(port.index());
+                }
+            } else if !class.is_empty() {
+                layout.scratch.push(port.index());
+            }
+        }
+        layout
+    }
+
+    /// Find the parameter with the given id, which is the index of its port.
+    pub fn param(&self, id: sys::clap_id) -> Option<(usize, &ParamLayout)> {
+        self.params
+            .iter()
+            .enumerate()
+            .find(|(_, param)| param.index == id)
+    }
+}
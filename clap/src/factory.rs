@@ -0,0 +1,141 @@
+use crate::description::{Layout, RawDescriptor};
+use crate::instance::ClapInstance;
+use crate::{sys, ClapDescription};
+use core::plugin::{Plugin, PluginInstance};
+use std::ffi::{c_char, c_void, CStr};
+use std::ptr::null;
+
+/// A plugin that is exported as a CLAP plugin.
+///
+/// The plugin is adapted with its LV2 interfaces: The adapter instantiates it with the URID map and worker schedule features, runs it in CLAP's process function and saves its state with the state interface, if it provides one. Therefore, the plugin keeps working as an LV2 plugin and this trait only provides the information CLAP hosts need in addition.
+pub trait ClapExport: Plugin {
+    /// Describe the plugin for CLAP hosts.
+    ///
+    /// The description is created once, when the host asks for the plugins of the library.
+    fn clap_description() -> ClapDescription;
+}
+
+/// A plugin of a factory, with its descriptors.
+pub(crate) struct ExportedPlugin {
+    pub description: ClapDescription,
+    pub layout: Layout,
+    pub raw: RawDescriptor,
+    pub lv2: lv2_sys::LV2_Descriptor,
+}
+
+/// The plugin factory of a CLAP library.
+///
+/// CLAP hosts find the plugins of a library through its factory, which is returned by the `clap_entry` symbol of the library. The entry and the factory are usually created with the [`clap_entry`](macro.clap_entry.html) macro, which keeps the factory in a static.
+#[repr(C)]
+pub struct ClapFactory {
+    raw: sys::clap_plugin_factory,
+    plugins: Vec<ExportedPlugin>,
+}
+
+// The factory isn't changed after it was created and the raw pointers of its descriptors point into its own data.
+unsafe impl Send for ClapFactory {}
+unsafe impl Sync for ClapFactory {}
+
+impl Default for ClapFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClapFactory {
+    /// Create a factory without plugins.
+    pub fn new() -> Self {
+        Self {
+            raw: sys::clap_plugin_factory {
+                get_plugin_count: Some(get_plugin_count),
+                get_plugin_descriptor: Some(get_plugin_descriptor),
+                create_plugin: Some(create_plugin),
+            },
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Add a plugin to the factory.
+    pub fn with_plugin<T: ClapExport>(mut self) -> Self {
+        let description = T::clap_description();
+        self.plugins.push(ExportedPlugin {
+            layout: Layout::new(description.plugin()),
+            raw: RawDescriptor::new(&description),
+            description,
+            lv2: lv2_sys::LV2_Descriptor {
+                URI: T::URI.as_ptr() as *const c_char,
+                instantiate: Some(PluginInstance::<T>::instantiate),
+                connect_port: Some(PluginInstance::<T>::connect_port),
+                activate: Some(PluginInstance::<T>::activate),
+                run: Some(PluginInstance::<T>::run),
+                deactivate: Some(PluginInstance::<T>::deactivate),
+                cleanup: Some(PluginInstance::<T>::cleanup),
+                extension_data: Some(PluginInstance::<T>::extension_data),
+            },
+        });
+        self
+    }
+
+    /// The descriptions of the plugins of the factory.
+    pub fn descriptions(&self) -> impl Iterator<Item = &ClapDescription> {
+        self.plugins.iter().map(|plugin| &plugin.description)
+    }
+
+    /// The raw factory, which is passed to the host.
+    ///
+    /// The factory has to live as long as the library is loaded, since the host may use it and its plugins until then.
+    pub fn as_raw(&'static self) -> *const sys::clap_plugin_factory {
+        &self.raw
+    }
+
+    /// Return the factory with the given id, as required by the `get_factory` function of the entry.
+    ///
+    /// This returns the raw plugin factory for the id `clap.plugin-factory` and a null pointer for all other ids.
+    ///
+    /// # Safety
+    ///
+    /// The id has to be a valid, null-terminated string or a null pointer.
+    pub unsafe fn get_factory(&'static self, factory_id: *const c_char) -> *const c_void {
+        if !factory_id.is_null()
+            && CStr::from_ptr(factory_id).to_bytes_with_nul() == sys::CLAP_PLUGIN_FACTORY_ID
+        {
+            self.as_raw() as *const c_void
+        } else {
+            null()
+        }
+    }
+
+    unsafe fn from_raw(factory: *const sys::clap_plugin_factory) -> Option<&'static Self> {
+        (factory as *const Self).as_ref()
+    }
+}
+
+unsafe extern "C" fn get_plugin_count(factory: *const sys::clap_plugin_factory) -> u32 {
+    ClapFactory::from_raw(factory).map_or(0, |factory| factory.plugins.len() as u32)
+}
+
+unsafe extern "C" fn get_plugin_descriptor(
+    factory: *const sys::clap_plugin_factory,
+    index: u32,
+) -> *const sys::clap_plugin_descriptor {
+    ClapFactory::from_raw(factory)
+        .and_then(|factory| factory.plugins.get(index as usize))
+        .map_or(null(), |plugin| &plugin.raw.raw)
+}
+
+unsafe extern "C" fn create_plugin(
+    factory: *const sys::clap_plugin_factory,
+    host: *const sys::clap_host,
+    plugin_id: *const c_char,
+) -> *const sys::clap_plugin {
+    let factory = match ClapFactory::from_raw(factory) {
+        Some(factory) if !plugin_id.is_null() => factory,
+        _ => return null(),
+    };
+    let plugin_id = CStr::from_ptr(plugin_id);
+    factory
+        .plugins
+        .iter()
+        .find(|plugin| CStr::from_ptr(plugin.raw.raw.id) == plugin_id)
+        .map_or(null(), |plugin| ClapInstance::create(plugin, host))
+}
@@ -0,0 +1,934 @@
+use crate::description::Layout;
+use crate::factory::ExportedPlugin;
+use crate::saved_state::{read_stream, write_stream, Properties, SavedState};
+use crate::sys;
+use atom::prelude::*;
+use atom::space::RootMutSpace;
+use host::{HostError, HostFeatures, Instance};
+use midi::prelude::*;
+use std::ffi::{c_char, c_void, CStr};
+use std::io::Write;
+use std::ptr::{null, null_mut};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use units::prelude::*;
+use urid::*;
+
+/// The sample rate of instances that weren't activated yet.
+const DEFAULT_SAMPLE_RATE: f64 = 48000.0;
+/// The capacity of the buffers of the worker in bytes.
+const WORKER_CAPACITY: usize = 65536;
+/// The size of the buffers of atom ports in 64-bit words.
+const ATOM_BUFFER_SIZE: usize = 1024;
+/// The maximal number of MIDI events per block.
+const MIDI_EVENT_CAPACITY: usize = 512;
+
+/// The URIDs of the atoms the adapter writes.
+struct AdapterURIDs {
+    sequence: URID<Sequence>,
+    chunk: URID<Chunk>,
+    frame: URID<Frame>,
+    midi: URID<MidiEvent>,
+}
+
+/// An event of the host that is passed to the plugin.
+enum InputEvent {
+    Param(sys::clap_id, f64),
+    Midi([u8; 3], usize),
+    Other,
+}
+
+impl InputEvent {
+    unsafe fn read(header: &sys::clap_event_header) -> Self {
+        if header.space_id != sys::CLAP_CORE_EVENT_SPACE_ID {
+            return InputEvent::Other;
+        }
+        match header.type_ {
+            sys::CLAP_EVENT_PARAM_VALUE => {
+                let event = &*(header as *const _ as *const sys::clap_event_param_value);
+                InputEvent::Param(event.param_id, event.value)
+            }
+            sys::CLAP_EVENT_NOTE_ON | sys::CLAP_EVENT_NOTE_OFF => {
+                let event = &*(header as *const _ as *const sys::clap_event_note);
+                // Wildcard notes of CLAP have no MIDI equivalent.
+                if !(0..16).contains(&event.channel) || !(0..128).contains(&event.key) {
+                    return InputEvent::Other;
+                }
+                let velocity = (event.velocity.clamp(0.0, 1.0) * 127.0).round() as u8;
+                let message = if header.type_ == sys::CLAP_EVENT_NOTE_ON {
+                    [0x90 | event.channel as u8, event.key as u8, velocity.max(1)]
+                } else {
+                    [0x80 | event.channel as u8, event.key as u8, velocity]
+                };
+                InputEvent::Midi(message, 3)
+            }
+            sys::CLAP_EVENT_MIDI => {
+                let event = &*(header as *const _ as *const sys::clap_event_midi);
+                InputEvent::Midi(event.data, midi_length(event.data[0]))
+            }
+            _ => InputEvent::Other,
+        }
+    }
+}
+
+/// The length of a MIDI message with the given status byte.
+fn midi_length(status: u8) -> usize {
+    match status {
+        0xC0..=0xDF | 0xF1 | 0xF3 => 2,
+        0xF6..=0xFF => 1,
+        _ => 3,
+    }
+}
+
+/// The events of a process call or a flush.
+struct InputEvents<'a>(&'a sys::clap_input_events);
+
+impl<'a> InputEvents<'a> {
+    unsafe fn new(events: *const sys::clap_input_events) -> Option<Self> {
+        events.as_ref().map(InputEvents)
+    }
+
+    unsafe fn len(&self) -> u32 {
+        self.0.size.map_or(0, |size| size(self.0))
+    }
+
+    unsafe fn get(&self, index: u32) -> Option<&'a sys::clap_event_header> {
+        self.0.get.and_then(|get| get(self.0, index).as_ref())
+    }
+}
+
+/// The LV2 instance of an adapted plugin, together with the buffers of its ports.
+struct Engine {
+    // The instance borrows the features and has to be dropped before them.
+    instance: Option<Instance<'static>>,
+    features: Box<HostFeatures>,
+    plugin: &'static ExportedPlugin,
+    urids: AdapterURIDs,
+    sample_rate: f64,
+    active: bool,
+    controls: Box<[f32]>,
+    silence: Vec<f32>,
+    discard: Vec<f32>,
+    scratch: Vec<Vec<f64>>,
+    atom_inputs: Vec<Vec<u64>>,
+    atom_outputs: Vec<Vec<u64>>,
+    /// The MIDI events of the current block, with their lengths and offsets.
+    midi_events: Vec<([u8; 3], usize, u32)>,
+}
+
+impl Engine {
+    fn new(plugin: &'static ExportedPlugin) -> Option<Self> {
+        let features = Box::new(
+            HostFeatures::new()
+                .with_urid_map()
+                .with_worker(WORKER_CAPACITY),
+        );
+        let mapper = features.mapper()?;
+        let urids = AdapterURIDs {
+            sequence: mapper.map_type()?,
+            chunk: mapper.map_type()?,
+            frame: mapper.map_type()?,
+            midi: mapper.map_type()?,
+        };
+        let layout = &plugin.layout;
+        let mut engine = Self {
+            instance: None,
+            features,
+            plugin,
+            urids,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            active: false,
+            controls: layout
+                .params
+                .iter()
+                .map(|param| param.default as f32)
+                .collect(),
+            silence: Vec::new(),
+            discard: Vec::new(),
+            scratch: vec![Vec::new(); layout.scratch.len()],
+            atom_inputs: vec![vec![0; ATOM_BUFFER_SIZE]; layout.atom_inputs.len()],
+            atom_outputs: vec![vec![0; ATOM_BUFFER_SIZE]; layout.atom_outputs.len()],
+            midi_events: Vec::with_capacity(MIDI_EVENT_CAPACITY),
+        };
+        if engine.instantiate(DEFAULT_SAMPLE_RATE) {
+            Some(engine)
+        } else {
+            None
+        }
+    }
+
+    /// Create a new LV2 instance with the given sample rate, which takes over the state of the previous instance.
+    fn instantiate(&mut self, sample_rate: f64) -> bool {
+        let mut properties = Properties::default();
+        let transfer = match self.instance.as_mut() {
+            Some(instance) => instance.save_state(&mut properties, &self.features).is_ok(),
+            None => false,
+        };
+        // The previous instance has to be dropped first, since the worker can only be used by one instance.
+        self.instance = None;
+
+        // The features are boxed and are only dropped after the instance.
+        let features: &'static HostFeatures = unsafe { &*(self.features.as_ref() as *const _) };
+        let instance = unsafe {
+            Instance::from_descriptor(
+                &self.plugin.lv2,
+                crate::bundle_path(),
+                sample_rate,
+                features,
+            )
+        };
+        let mut instance = match instance {
+            Ok(instance) => instance,
+            Err(_) => return false,
+        };
+        if transfer {
+            let _ = instance.restore_state(&properties, &self.features);
+        }
+        self.instance = Some(instance);
+        self.sample_rate = sample_rate;
+        self.connect();
+        true
+    }
+
+    /// Connect all ports to the buffers of the engine.
+    fn connect(&mut self) {
+        let layout = &self.plugin.layout;
+        let instance = match self.instance.as_mut() {
+            Some(instance) => instance,
+            None => return,
+        };
+        unsafe {
+            for (param, value) in layout.params.iter().zip(self.controls.iter_mut()) {
+                instance.connect_port(param.index, value as *mut f32 as *mut c_void);
+            }
+            for (index, buffer) in layout.scratch.iter().zip(self.scratch.iter_mut()) {
+                instance.connect_port(*index, buffer.as_mut_ptr() as *mut c_void);
+            }
+            for (index, buffer) in layout.atom_inputs.iter().zip(self.atom_inputs.iter_mut()) {
+                instance.connect_port(*index, buffer.as_mut_ptr() as *mut c_void);
+            }
+            for (index, buffer) in layout.atom_outputs.iter().zip(self.atom_outputs.iter_mut()) {
+                instance.connect_port(*index, buffer.as_mut_ptr() as *mut c_void);
+            }
+            for port in layout.audio_inputs.iter() {
+                for index in port.channels.iter() {
+                    instance.connect_port(*index, self.silence.as_mut_ptr() as *mut c_void);
+                }
+            }
+            for port in layout.audio_outputs.iter() {
+                for index in port.channels.iter() {
+                    instance.connect_port(*index, self.discard.as_mut_ptr() as *mut c_void);
+                }
+            }
+        }
+    }
+
+    fn activate(&mut self, sample_rate: f64, max_frames: u32) -> bool {
+        if (self.instance.is_none() || sample_rate != self.sample_rate)
+            && !self.instantiate(sample_rate)
+        {
+            return false;
+        }
+        let max_frames = max_frames as usize;
+        self.silence = vec![0.0; max_frames];
+        self.discard = vec![0.0; max_frames];
+        for buffer in self.scratch.iter_mut() {
+            *buffer = vec![0.0; max_frames];
+        }
+        self.connect();
+        if let Some(instance) = self.instance.as_mut() {
+            instance.activate();
+        }
+        self.active = true;
+        true
+    }
+
+    fn deactivate(&mut self) {
+        if let Some(instance) = self.instance.as_mut() {
+            instance.deactivate();
+        }
+        self.active = false;
+    }
+
+    /// Set the value of an input parameter.
+    fn set_param(&mut self, id: sys::clap_id, value: f64) {
+        if let Some((position, param)) = self.plugin.layout.param(id) {
+            if !param.output {
+                let (minimum, maximum) = if param.minimum <= param.maximum {
+                    (param.minimum, param.maximum)
+                } else {
+                    (param.maximum, param.minimum)
+                };
+                self.controls[position] = value.clamp(minimum, maximum) as f32;
+            }
+        }
+    }
+
+    /// Write the MIDI events of a block to the first atom input and prepare the other atom ports.
+    fn begin_block(&mut self) {
+        let urids = &self.urids;
+        let mut events = Some(self.midi_events.iter());
+        for buffer in self.atom_inputs.iter_mut() {
+            let bytes = unsafe {
+                std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8)
+            };
+            let mut space = RootMutSpace::new(bytes);
+            let mut writer = match (&mut space as &mut dyn MutSpace)
+                .init(urids.sequence, TimeStampURID::Frames(urids.frame))
+            {
+                Some(writer) => writer,
+                None => continue,
+            };
+            for (message, length, offset) in events.take().into_iter().flatten() {
+                // Events that don't fit into the buffer are dropped.
+                if let Some(mut frame) =
+                    writer.init(TimeStamp::Frames(*offset as i64), urids.midi, ())
+                {
+                    (&mut frame as &mut dyn MutSpace).write_raw(&message[..*length], false);
+                }
+            }
+        }
+        for buffer in self.atom_outputs.iter_mut() {
+            // Hosts announce the capacity of output atom ports in the header of an empty chunk.
+            let atom = buffer.as_mut_ptr() as *mut lv2_sys::LV2_Atom;
+            unsafe {
+                (*atom).size = (buffer.len() * 8 - std::mem::size_of::<lv2_sys::LV2_Atom>()) as u32;
+                (*atom).type_ = urids.chunk.get();
+            }
+        }
+    }
+
+    /// Connect the audio ports to the buffers of the host, starting at the given frame.
+    ///
+    /// Channels that the host doesn't provide are connected to silent buffers.
+    unsafe fn connect_audio(&mut self, process: &sys::clap_process, start: usize) {
+        let layout = &self.plugin.layout;
+        let instance = match self.instance.as_mut() {
+            Some(instance) => instance,
+            None => return,
+        };
+        for (position, port) in layout.audio_inputs.iter().enumerate() {
+            let buffer = if position < process.audio_inputs_count as usize {
+                process.audio_inputs.add(position).as_ref()
+            } else {
+                None
+            };
+            for (channel, index) in port.channels.iter().enumerate() {
+                let data = channel_data(buffer, channel)
+                    .map_or(self.silence.as_mut_ptr(), |data| data.add(start));
+                instance.connect_port(*index, data as *mut c_void);
+            }
+        }
+        for (position, port) in layout.audio_outputs.iter().enumerate() {
+            let buffer = if position < process.audio_outputs_count as usize {
+                process.audio_outputs.add(position).as_ref()
+            } else {
+                None
+            };
+            for (channel, index) in port.channels.iter().enumerate() {
+                let data = channel_data(buffer, channel)
+                    .map_or(self.discard.as_mut_ptr(), |data| data.add(start));
+                instance.connect_port(*index, data as *mut c_void);
+            }
+        }
+    }
+
+    /// Run the instance for a process call of the host.
+    ///
+    /// The call is split into blocks at parameter changes, so that they are applied at the right frame. MIDI and note events are passed to the first atom input.
+    unsafe fn process(&mut self, process: &sys::clap_process) {
+        let frames = process.frames_count;
+        let events = InputEvents::new(process.in_events);
+        let count = events.as_ref().map_or(0, |events| events.len());
+        let event = |index: u32| events.as_ref().and_then(|events| events.get(index));
+
+        let mut index = 0;
+        let mut start = 0;
+        loop {
+            let end = (index..count)
+                .filter_map(event)
+                .find(|header| {
+                    header.time > start
+                        && matches!(InputEvent::read(header), InputEvent::Param(_, _))
+                })
+                .map_or(frames, |header| header.time.min(frames));
+
+            self.midi_events.clear();
+            while index < count {
+                let header = match event(index) {
+                    Some(header) => header,
+                    None => {
+                        index += 1;
+                        continue;
+                    }
+                };
+                // The last block takes all remaining events.
+                if header.time >= end && end < frames {
+                    break;
+                }
+                match InputEvent::read(header) {
+                    InputEvent::Param(id, value) => self.set_param(id, value),
+                    InputEvent::Midi(message, length) => {
+                        let offset = header
+                            .time
+                            .min(frames.saturating_sub(1))
+                            .saturating_sub(start);
+                        // The buffer is never reallocated in the audio thread.
+                        if self.midi_events.len() < self.midi_events.capacity() {
+                            self.midi_events.push((message, length, offset));
+                        }
+                    }
+                    InputEvent::Other => (),
+                }
+                index += 1;
+            }
+
+            self.begin_block();
+            self.connect_audio(process, start as usize);
+            if let Some(instance) = self.instance.as_mut() {
+                instance.run(end - start);
+            }
+            if end >= frames {
+                break;
+            }
+            start = end;
+        }
+    }
+
+    /// Apply the parameter changes of a flush, without running the instance.
+    unsafe fn flush(&mut self, events: *const sys::clap_input_events) {
+        if let Some(events) = InputEvents::new(events) {
+            for index in 0..events.len() {
+                if let Some(InputEvent::Param(id, value)) =
+                    events.get(index).map(|header| InputEvent::read(header))
+                {
+                    self.set_param(id, value);
+                }
+            }
+        }
+    }
+
+    /// Save the values of the input parameters and the LV2 state of the instance.
+    fn save(&mut self) -> Option<Vec<u8>> {
+        let mut state = SavedState::default();
+        for (param, value) in self.plugin.layout.params.iter().zip(self.controls.iter()) {
+            if !param.output {
+                state.controls.push((param.index, *value));
+            }
+        }
+        if let Some(instance) = self.instance.as_mut() {
+            match instance.save_state(&mut state.properties, &self.features) {
+                Ok(()) | Err(HostError::MissingExtension(_)) => (),
+                Err(_) => return None,
+            }
+        }
+        state.encode(self.features.mapper()?)
+    }
+
+    /// Restore a state that was created by [`save`](#method.save).
+    fn load(&mut self, data: &[u8]) -> bool {
+        let state = match self
+            .features
+            .mapper()
+            .and_then(|mapper| SavedState::decode(data, mapper))
+        {
+            Some(state) => state,
+            None => return false,
+        };
+        for (index, value) in state.controls.iter() {
+            self.set_param(*index, *value as f64);
+        }
+        match self.instance.as_mut() {
+            Some(instance) => matches!(
+                instance.restore_state(&state.properties, &self.features),
+                Ok(()) | Err(HostError::MissingExtension(_))
+            ),
+            None => true,
+        }
+    }
+}
+
+/// The buffer of a channel of a CLAP audio port, if the host provides it.
+unsafe fn channel_data(
+    buffer: Option<&sys::clap_audio_buffer>,
+    channel: usize,
+) -> Option<*mut f32> {
+    let buffer = buffer?;
+    if buffer.data32.is_null() || channel >= buffer.channel_count as usize {
+        return None;
+    }
+    let data = *buffer.data32.add(channel);
+    if data.is_null() {
+        None
+    } else {
+        Some(data)
+    }
+}
+
+/// Write silence to all outputs of a process call.
+unsafe fn silence_outputs(process: &sys::clap_process) {
+    for position in 0..process.audio_outputs_count as usize {
+        let buffer = process.audio_outputs.add(position).as_ref();
+        for channel in 0..buffer.map_or(0, |buffer| buffer.channel_count as usize) {
+            if let Some(data) = channel_data(buffer, channel) {
+                std::slice::from_raw_parts_mut(data, process.frames_count as usize).fill(0.0);
+            }
+        }
+    }
+}
+
+/// A plugin instance for a CLAP host.
+///
+/// All functions of the host receive the raw plugin struct, whose data points to this struct. Since the host calls them from the main thread and the audio thread, the LV2 instance is guarded by a mutex, which the audio thread never waits for: If the main thread saves or restores the state while the host processes audio, the outputs are silent for this cycle. The values of the parameters are mirrored in atomics, which the host reads from the main thread.
+#[repr(C)]
+pub(crate) struct ClapInstance {
+    raw: sys::clap_plugin,
+    host: *const sys::clap_host,
+    plugin: &'static ExportedPlugin,
+    values: Box<[AtomicU32]>,
+    engine: Mutex<Option<Engine>>,
+}
+
+impl ClapInstance {
+    /// Create an instance of the plugin.
+    ///
+    /// The instance is destroyed by the host with the `destroy` function of the returned struct.
+    pub fn create(
+        plugin: &'static ExportedPlugin,
+        host: *const sys::clap_host,
+    ) -> *const sys::clap_plugin {
+        let instance = Box::new(Self {
+            raw: sys::clap_plugin {
+                desc: &plugin.raw.raw,
+                plugin_data: null_mut(),
+                init: Some(init),
+                destroy: Some(destroy),
+                activate: Some(activate),
+                deactivate: Some(deactivate),
+                start_processing: Some(start_processing),
+                stop_processing: Some(stop_processing),
+                reset: Some(reset),
+                process: Some(process),
+                get_extension: Some(get_extension),
+                on_main_thread: Some(on_main_thread),
+            },
+            host,
+            plugin,
+            values: plugin
+                .layout
+                .params
+                .iter()
+                .map(|param| AtomicU32::new((param.default as f32).to_bits()))
+                .collect(),
+            engine: Mutex::new(None),
+        });
+        let instance = Box::into_raw(instance);
+        unsafe {
+            (*instance).raw.plugin_data = instance as *mut c_void;
+            &(*instance).raw
+        }
+    }
+
+    unsafe fn from_raw<'a>(plugin: *const sys::clap_plugin) -> Option<&'a Self> {
+        (plugin.as_ref()?.plugin_data as *const Self).as_ref()
+    }
+
+    fn layout(&self) -> &'static Layout {
+        &self.plugin.layout
+    }
+
+    /// Run a function with the engine, waiting for the lock.
+    fn with_engine<R>(&self, f: impl FnOnce(&mut Engine) -> R) -> Option<R> {
+        let mut engine = self.engine.lock().ok()?;
+        let result = engine.as_mut().map(f);
+        if let Some(engine) = engine.as_ref() {
+            self.publish(engine);
+        }
+        result
+    }
+
+    /// Mirror the values of the parameters of the engine.
+    fn publish(&self, engine: &Engine) {
+        for (value, control) in self.values.iter().zip(engine.controls.iter()) {
+            value.store(control.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Tell the host that the values of the parameters changed.
+    unsafe fn rescan_params(&self) {
+        let host = match self.host.as_ref() {
+            Some(host) => host,
+            None => return,
+        };
+        let params = host
+            .get_extension
+            .map(|get_extension| {
+                get_extension(host, sys::CLAP_EXT_PARAMS.as_ptr() as *const c_char)
+            })
+            .and_then(|params| (params as *const sys::clap_host_params).as_ref());
+        if let Some(rescan) = params.and_then(|params| params.rescan) {
+            rescan(host, sys::CLAP_PARAM_RESCAN_VALUES);
+        }
+    }
+}
+
+unsafe extern "C" fn init(plugin: *const sys::clap_plugin) -> bool {
+    let instance = match ClapInstance::from_raw(plugin) {
+        Some(instance) => instance,
+        None => return false,
+    };
+    let engine = match Engine::new(instance.plugin) {
+        Some(engine) => engine,
+        None => return false,
+    };
+    match instance.engine.lock() {
+        Ok(mut slot) => {
+            *slot = Some(engine);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+unsafe extern "C" fn destroy(plugin: *const sys::clap_plugin) {
+    if let Some(instance) = ClapInstance::from_raw(plugin) {
+        drop(Box::from_raw(
+            instance as *const ClapInstance as *mut ClapInstance,
+        ));
+    }
+}
+
+unsafe extern "C" fn activate(
+    plugin: *const sys::clap_plugin,
+    sample_rate: f64,
+    _min_frames_count: u32,
+    max_frames_count: u32,
+) -> bool {
+    ClapInstance::from_raw(plugin)
+        .and_then(|instance| {
+            instance.with_engine(|engine| engine.activate(sample_rate, max_frames_count))
+        })
+        .unwrap_or(false)
+}
+
+unsafe extern "C" fn deactivate(plugin: *const sys::clap_plugin) {
+    if let Some(instance) = ClapInstance::from_raw(plugin) {
+        instance.with_engine(Engine::deactivate);
+    }
+}
+
+unsafe extern "C" fn start_processing(_plugin: *const sys::clap_plugin) -> bool {
+    true
+}
+
+unsafe extern "C" fn stop_processing(_plugin: *const sys::clap_plugin) {}
+
+unsafe extern "C" fn reset(plugin: *const sys::clap_plugin) {
+    // LV2 plugins reset their internal state when they're activated.
+    if let Some(instance) = ClapInstance::from_raw(plugin) {
+        instance.with_engine(|engine| {
+            if let Some(instance) = engine.instance.as_mut() {
+                if engine.active {
+                    instance.deactivate();
+                    instance.activate();
+                }
+            }
+        });
+    }
+}
+
+unsafe extern "C" fn process(
+    plugin: *const sys::clap_plugin,
+    process: *const sys::clap_process,
+) -> sys::clap_process_status {
+    let (instance, process) = match (ClapInstance::from_raw(plugin), process.as_ref()) {
+        (Some(instance), Some(process)) => (instance, process),
+        _ => return sys::CLAP_PROCESS_ERROR,
+    };
+    match instance.engine.try_lock() {
+        Ok(mut engine) => match engine.as_mut() {
+            Some(engine) if engine.active => {
+                engine.process(process);
+                instance.publish(engine);
+                sys::CLAP_PROCESS_CONTINUE
+            }
+            _ => sys::CLAP_PROCESS_ERROR,
+        },
+        Err(_) => {
+            silence_outputs(process);
+            sys::CLAP_PROCESS_CONTINUE
+        }
+    }
+}
+
+unsafe extern "C" fn get_extension(
+    _plugin: *const sys::clap_plugin,
+    id: *const c_char,
+) -> *const c_void {
+    if id.is_null() {
+        return null();
+    }
+    match CStr::from_ptr(id).to_bytes_with_nul() {
+        id if id == sys::CLAP_EXT_AUDIO_PORTS => {
+            &AUDIO_PORTS as *const sys::clap_plugin_audio_ports as *const c_void
+        }
+        id if id == sys::CLAP_EXT_NOTE_PORTS => {
+            &NOTE_PORTS as *const sys::clap_plugin_note_ports as *const c_void
+        }
+        id if id == sys::CLAP_EXT_PARAMS => {
+            &PARAMS as *const sys::clap_plugin_params as *const c_void
+        }
+        id if id == sys::CLAP_EXT_STATE => &STATE as *const sys::clap_plugin_state as *const c_void,
+        _ => null(),
+    }
+}
+
+unsafe extern "C" fn on_main_thread(_plugin: *const sys::clap_plugin) {}
+
+/// Copy a name into a fixed-size buffer of the host, truncating it if necessary.
+fn write_name(buffer: &mut [c_char], name: &str) {
+    let length = name.len().min(buffer.len() - 1);
+    for (target, source) in buffer.iter_mut().zip(name.bytes().take(length)) {
+        *target = source as c_char;
+    }
+    buffer[length] = 0;
+}
+
+static AUDIO_PORTS: sys::clap_plugin_audio_ports = sys::clap_plugin_audio_ports {
+    count: Some(audio_ports_count),
+    get: Some(audio_ports_get),
+};
+
+unsafe extern "C" fn audio_ports_count(plugin: *const sys::clap_plugin, is_input: bool) -> u32 {
+    ClapInstance::from_raw(plugin).map_or(0, |instance| {
+        let layout = instance.layout();
+        if is_input {
+            layout.audio_inputs.len() as u32
+        } else {
+            layout.audio_outputs.len() as u32
+        }
+    })
+}
+
+unsafe extern "C" fn audio_ports_get(
+    plugin: *const sys::clap_plugin,
+    index: u32,
+    is_input: bool,
+    info: *mut sys::clap_audio_port_info,
+) -> bool {
+    let (instance, info) = match (ClapInstance::from_raw(plugin), info.as_mut()) {
+        (Some(instance), Some(info)) => (instance, info),
+        _ => return false,
+    };
+    let layout = instance.layout();
+    let ports = if is_input {
+        &layout.audio_inputs
+    } else {
+        &layout.audio_outputs
+    };
+    let port = match ports.get(index as usize) {
+        Some(port) => port,
+        None => return false,
+    };
+    info.id = index;
+    write_name(&mut info.name, &port.name);
+    info.flags = if port.main {
+        sys::CLAP_AUDIO_PORT_IS_MAIN
+    } else {
+        0
+    };
+    info.channel_count = port.channels.len() as u32;
+    info.port_type = match port.channels.len() {
+        1 => sys::CLAP_PORT_MONO.as_ptr() as *const c_char,
+        2 => sys::CLAP_PORT_STEREO.as_ptr() as *const c_char,
+        _ => null(),
+    };
+    info.in_place_pair = sys::CLAP_INVALID_ID;
+    true
+}
+
+static NOTE_PORTS: sys::clap_plugin_note_ports = sys::clap_plugin_note_ports {
+    count: Some(note_ports_count),
+    get: Some(note_ports_get),
+};
+
+unsafe extern "C" fn note_ports_count(plugin: *const sys::clap_plugin, is_input: bool) -> u32 {
+    ClapInstance::from_raw(plugin).map_or(0, |instance| {
+        (is_input && !instance.layout().atom_inputs.is_empty()) as u32
+    })
+}
+
+unsafe extern "C" fn note_ports_get(
+    plugin: *const sys::clap_plugin,
+    index: u32,
+    is_input: bool,
+    info: *mut sys::clap_note_port_info,
+) -> bool {
+    if index >= note_ports_count(plugin, is_input) {
+        return false;
+    }
+    let info = match info.as_mut() {
+        Some(info) => info,
+        None => return false,
+    };
+    info.id = 0;
+    info.supported_dialects = sys::CLAP_NOTE_DIALECT_CLAP | sys::CLAP_NOTE_DIALECT_MIDI;
+    info.preferred_dialect = sys::CLAP_NOTE_DIALECT_MIDI;
+    write_name(&mut info.name, "MIDI input");
+    true
+}
+
+static PARAMS: sys::clap_plugin_params = sys::clap_plugin_params {
+    count: Some(params_count),
+    get_info: Some(params_get_info),
+    get_value: Some(params_get_value),
+    value_to_text: Some(params_value_to_text),
+    text_to_value: Some(params_text_to_value),
+    flush: Some(params_flush),
+};
+
+unsafe extern "C" fn params_count(plugin: *const sys::clap_plugin) -> u32 {
+    ClapInstance::from_raw(plugin).map_or(0, |instance| instance.layout().params.len() as u32)
+}
+
+unsafe extern "C" fn params_get_info(
+    plugin: *const sys::clap_plugin,
+    param_index: u32,
+    param_info: *mut sys::clap_param_info,
+) -> bool {
+    let (instance, info) = match (ClapInstance::from_raw(plugin), param_info.as_mut()) {
+        (Some(instance), Some(info)) => (instance, info),
+        _ => return false,
+    };
+    let param = match instance.layout().params.get(param_index as usize) {
+        Some(param) => param,
+        None => return false,
+    };
+    info.id = param.index;
+    info.flags = if param.output {
+        sys::CLAP_PARAM_IS_READONLY
+    } else {
+        sys::CLAP_PARAM_IS_AUTOMATABLE
+    };
+    info.cookie = null_mut();
+    write_name(&mut info.name, &param.name);
+    write_name(&mut info.module, "");
+    info.min_value = param.minimum;
+    info.max_value = param.maximum;
+    info.default_value = param.default;
+    true
+}
+
+unsafe extern "C" fn params_get_value(
+    plugin: *const sys::clap_plugin,
+    param_id: sys::clap_id,
+    out_value: *mut f64,
+) -> bool {
+    let (instance, out_value) = match (ClapInstance::from_raw(plugin), out_value.as_mut()) {
+        (Some(instance), Some(out_value)) => (instance, out_value),
+        _ => return false,
+    };
+    match instance.layout().param(param_id) {
+        Some((position, _)) => {
+            *out_value = f32::from_bits(instance.values[position].load(Ordering::Relaxed)) as f64;
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe extern "C" fn params_value_to_text(
+    plugin: *const sys::clap_plugin,
+    param_id: sys::clap_id,
+    value: f64,
+    out_buffer: *mut c_char,
+    out_buffer_capacity: u32,
+) -> bool {
+    let exists = ClapInstance::from_raw(plugin)
+        .is_some_and(|instance| instance.layout().param(param_id).is_some());
+    if !exists || out_buffer.is_null() || out_buffer_capacity == 0 {
+        return false;
+    }
+    let buffer =
+        std::slice::from_raw_parts_mut(out_buffer as *mut u8, out_buffer_capacity as usize);
+    let capacity = buffer.len() - 1;
+    let mut cursor = std::io::Cursor::new(&mut buffer[..capacity]);
+    let _ = write!(cursor, "{:.3}", value);
+    let length = cursor.position() as usize;
+    buffer[length] = 0;
+    true
+}
+
+unsafe extern "C" fn params_text_to_value(
+    plugin: *const sys::clap_plugin,
+    param_id: sys::clap_id,
+    param_value_text: *const c_char,
+    out_value: *mut f64,
+) -> bool {
+    let exists = ClapInstance::from_raw(plugin)
+        .is_some_and(|instance| instance.layout().param(param_id).is_some());
+    if !exists || param_value_text.is_null() || out_value.is_null() {
+        return false;
+    }
+    match CStr::from_ptr(param_value_text)
+        .to_str()
+        .ok()
+        .and_then(|text| text.trim().parse().ok())
+    {
+        Some(value) => {
+            *out_value = value;
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe extern "C" fn params_flush(
+    plugin: *const sys::clap_plugin,
+    in_: *const sys::clap_input_events,
+    _out: *const sys::clap_output_events,
+) {
+    if let Some(instance) = ClapInstance::from_raw(plugin) {
+        instance.with_engine(|engine| engine.flush(in_));
+    }
+}
+
+static STATE: sys::clap_plugin_state = sys::clap_plugin_state {
+    save: Some(state_save),
+    load: Some(state_load),
+};
+
+unsafe extern "C" fn state_save(
+    plugin: *const sys::clap_plugin,
+    stream: *const sys::clap_ostream,
+) -> bool {
+    let (instance, stream) = match (ClapInstance::from_raw(plugin), stream.as_ref()) {
+        (Some(instance), Some(stream)) => (instance, stream),
+        _ => return false,
+    };
+    match instance.with_engine(Engine::save).flatten() {
+        Some(data) => write_stream(stream, &data),
+        None => false,
+    }
+}
+
+unsafe extern "C" fn state_load(
+    plugin: *const sys::clap_plugin,
+    stream: *const sys::clap_istream,
+) -> bool {
+    let (instance, stream) = match (ClapInstance::from_raw(plugin), stream.as_ref()) {
+        (Some(instance), Some(stream)) => (instance, stream),
+        _ => return false,
+    };
+    let data = match read_stream(stream) {
+        Some(data) => data,
+        None => return false,
+    };
+    let loaded = instance
+        .with_engine(|engine| engine.load(&data))
+        .unwrap_or(false);
+    if loaded {
+        instance.rescan_params();
+    }
+    loaded
+}
@@ -0,0 +1,160 @@
+//! Export of LV2 plugins in the CLAP format.
+//!
+//! [CLAP](https://github.com/free-audio/clap) is a plugin format that is supported by a growing number of hosts which don't load LV2 plugins. This crate lets plugin authors ship their plugins in both formats from a single implementation: A plugin type implements [`ClapExport`](trait.ClapExport.html) and the [`clap_entry`](macro.clap_entry.html) macro exports the entry point CLAP hosts look for, next to the `lv2_descriptor` function of the LV2 library. The same library can then be installed as an LV2 bundle and, renamed to `*.clap`, as a CLAP plugin.
+//!
+//! The adapter drives the plugin through its LV2 interfaces, like any other LV2 host, and maps them to their CLAP equivalents:
+//!
+//! * Audio ports in a port group become one CLAP audio port with a channel per LV2 port, all other audio ports become mono ports. The first audio port of each direction that isn't a side-chain is the main port.
+//! * Control ports become parameters, whose ids are the indices of the ports. Input ports are automatable and output ports are read-only. The names and ranges are taken from the [`PluginDescription`](../lv2_ttl_gen/struct.PluginDescription.html) of the plugin, which is also used to write its Turtle files. Parameter changes are applied at the right frame by splitting the process call into blocks.
+//! * If the plugin has atom inputs, it has a note port, whose MIDI and note events are passed to the first atom input as `midi:MidiEvent`s. The other atom inputs receive empty sequences and atom outputs are ignored. CV and double-precision audio ports are connected to silent buffers.
+//! * The state contains the values of the input parameters and, if the plugin provides the state extension, the properties of its LV2 state. The keys and types of the properties are written as URIs, so that states can be loaded in other sessions.
+//! * The plugin is instantiated with the URID map and the worker schedule features. Work is done in a worker thread of the adapter and the responses are delivered after every block, just like in an LV2 host.
+//!
+//! LV2 plugins know their sample rate when they're instantiated, while CLAP plugins learn it when they're activated. The adapter therefore creates the LV2 instance with a sample rate of 48 kHz, which lets hosts load states before they activate the plugin, and creates a new instance when the plugin is activated with another sample rate. The state of the previous instance is handed over to the new one.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_clap::*;
+//! use lv2_core::prelude::*;
+//! use lv2_ttl_gen::*;
+//! use urid::*;
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     gain: InputPort<Control>,
+//!     input: InputPort<Audio>,
+//!     output: OutputPort<Audio>,
+//! }
+//!
+//! #[uri("urn:lv2_clap:amp")]
+//! struct Amp;
+//!
+//! impl Plugin for Amp {
+//!     type Ports = Ports;
+//!     type InitFeatures = ();
+//!     type AudioFeatures = ();
+//!
+//!     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+//!         Some(Self)
+//!     }
+//!
+//!     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+//!         let coef = 10.0_f32.powf(*ports.gain * 0.05);
+//!         for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+//!             *output = input * coef;
+//!         }
+//!     }
+//! }
+//!
+//! /// The description that is written to the Turtle file of the plugin.
+//! fn describe() -> PluginDescription {
+//!     PluginDescription::new::<Amp>()
+//!         .port_name("gain", "Gain")
+//!         .port_range("gain", -90.0, 0.0, 24.0)
+//! }
+//!
+//! impl ClapExport for Amp {
+//!     fn clap_description() -> ClapDescription {
+//!         ClapDescription::new(describe(), "Amp").vendor("rust-lv2")
+//!     }
+//! }
+//!
+//! // Export the plugin in both formats.
+//! lv2_descriptors!(Amp);
+//! clap_entry!(Amp);
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_host as host;
+extern crate lv2_midi as midi;
+extern crate lv2_state as state;
+extern crate lv2_ttl_gen as ttl_gen;
+extern crate lv2_units as units;
+
+pub mod sys;
+
+mod description;
+pub use description::ClapDescription;
+
+mod factory;
+pub use factory::{ClapExport, ClapFactory};
+
+mod instance;
+mod saved_state;
+
+use std::ffi::{c_char, CStr};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The path of the plugin library, as passed to the entry by the host.
+static PLUGIN_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Initialize the library, as required by the `init` function of the entry.
+///
+/// The host passes the path of the library, whose directory is passed to the plugins as their bundle path. Plugins that are created before the library is initialized use the current directory instead.
+///
+/// # Safety
+///
+/// The path has to be a valid, null-terminated string or a null pointer.
+pub unsafe fn init_entry(plugin_path: *const c_char) -> bool {
+    if !plugin_path.is_null() {
+        if let Ok(path) = CStr::from_ptr(plugin_path).to_str() {
+            let _ = PLUGIN_PATH.set(PathBuf::from(path));
+        }
+    }
+    true
+}
+
+/// The bundle path of the plugins.
+pub(crate) fn bundle_path() -> &'static Path {
+    PLUGIN_PATH
+        .get()
+        .and_then(|path| path.parent())
+        .filter(|path| !path.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+}
+
+/// Export the entry point of a CLAP library with the given plugins.
+///
+/// The macro creates the `clap_entry` symbol, which CLAP hosts load from the library, with a [`ClapFactory`](struct.ClapFactory.html) of the plugins. Every plugin has to implement [`ClapExport`](trait.ClapExport.html). Like `lv2_descriptors!`, the macro may only be used once per library.
+#[macro_export]
+macro_rules! clap_entry {
+    ($($plugin:ty),+ $(,)?) => {
+        /// The entry point of the CLAP plugins of this library.
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        pub static clap_entry: $crate::sys::clap_plugin_entry = {
+            fn factory() -> &'static $crate::ClapFactory {
+                static FACTORY: ::std::sync::OnceLock<$crate::ClapFactory> =
+                    ::std::sync::OnceLock::new();
+                FACTORY.get_or_init(|| $crate::ClapFactory::new()$(.with_plugin::<$plugin>())+)
+            }
+
+            unsafe extern "C" fn init(plugin_path: *const ::std::ffi::c_char) -> bool {
+                $crate::init_entry(plugin_path)
+            }
+
+            unsafe extern "C" fn deinit() {}
+
+            unsafe extern "C" fn get_factory(
+                factory_id: *const ::std::ffi::c_char,
+            ) -> *const ::std::ffi::c_void {
+                factory().get_factory(factory_id)
+            }
+
+            $crate::sys::clap_plugin_entry {
+                clap_version: $crate::sys::CLAP_VERSION,
+                init: Some(init),
+                deinit: Some(deinit),
+                get_factory: Some(get_factory),
+            }
+        };
+    };
+}
+
+/// Prelude of `lv2_clap` for wildcard usage.
+pub mod prelude {
+    pub use crate::clap_entry;
+    pub use crate::{ClapDescription, ClapExport, ClapFactory};
+}
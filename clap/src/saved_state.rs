@@ -0,0 +1,147 @@
+use crate::sys;
+use host::StateStore;
+use state::StateErr;
+use std::convert::TryInto;
+use std::ffi::{c_void, CString};
+use urid::*;
+
+/// The magic bytes at the start of every saved state.
+const MAGIC: &[u8; 8] = b"LV2CLAP\0";
+/// The version of the state format.
+const FORMAT_VERSION: u32 = 1;
+
+/// The properties of an LV2 state.
+#[derive(Default)]
+pub(crate) struct Properties {
+    properties: Vec<(URID, URID, Vec<u8>, u32)>,
+}
+
+impl StateStore for Properties {
+    fn store(&mut self, key: URID, type_: URID, value: &[u8], flags: u32) -> Result<(), StateErr> {
+        self.properties.retain(|(known, _, _, _)| *known != key);
+        self.properties.push((key, type_, value.to_owned(), flags));
+        Ok(())
+    }
+
+    fn retrieve(&self, key: URID) -> Option<(URID, &[u8], u32)> {
+        self.properties
+            .iter()
+            .find(|(known, _, _, _)| *known == key)
+            .map(|(_, type_, value, flags)| (*type_, value.as_ref(), *flags))
+    }
+}
+
+/// The state of an adapted plugin: The values of its control inputs and the properties of its LV2 state.
+///
+/// CLAP states are streams of bytes, which are saved in the host's sessions. Since the URIDs of a property are only valid in one session, they are written as URIs. All numbers are stored in little endian.
+#[derive(Default)]
+pub(crate) struct SavedState {
+    pub controls: Vec<(u32, f32)>,
+    pub properties: Properties,
+}
+
+impl SavedState {
+    /// Write the state, with the URIDs of the properties unmapped by the mapper.
+    pub fn encode<U: Unmap>(&self, unmap: &U) -> Option<Vec<u8>> {
+        let mut data = MAGIC.to_vec();
+        data.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        data.extend_from_slice(&(self.controls.len() as u32).to_le_bytes());
+        for (index, value) in self.controls.iter() {
+            data.extend_from_slice(&index.to_le_bytes());
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&(self.properties.properties.len() as u32).to_le_bytes());
+        for (key, type_, value, flags) in self.properties.properties.iter() {
+            write_bytes(&mut data, unmap.unmap(*key)?.to_bytes());
+            write_bytes(&mut data, unmap.unmap(*type_)?.to_bytes());
+            data.extend_from_slice(&flags.to_le_bytes());
+            write_bytes(&mut data, value);
+        }
+        Some(data)
+    }
+
+    /// Read a state, with the URIs of the properties mapped by the mapper.
+    ///
+    /// If the state is malformed, `None` is returned.
+    pub fn decode<M: Map>(mut data: &[u8], map: &M) -> Option<Self> {
+        if read(&mut data, MAGIC.len())? != MAGIC || read_u32(&mut data)? != FORMAT_VERSION {
+            return None;
+        }
+        let mut state = Self::default();
+        for _ in 0..read_u32(&mut data)? {
+            let index = read_u32(&mut data)?;
+            let value = f32::from_le_bytes(read(&mut data, 4)?.try_into().ok()?);
+            state.controls.push((index, value));
+        }
+        for _ in 0..read_u32(&mut data)? {
+            let key = map.map_uri(&read_uri(&mut data)?)?;
+            let type_ = map.map_uri(&read_uri(&mut data)?)?;
+            let flags = read_u32(&mut data)?;
+            let size = read_u32(&mut data)? as usize;
+            let value = read(&mut data, size)?;
+            state
+                .properties
+                .properties
+                .push((key, type_, value.to_owned(), flags));
+        }
+        Some(state)
+    }
+}
+
+fn write_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+    data.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    data.extend_from_slice(bytes);
+}
+
+fn read<'a>(data: &mut &'a [u8], size: usize) -> Option<&'a [u8]> {
+    if data.len() < size {
+        return None;
+    }
+    let (head, tail) = data.split_at(size);
+    *data = tail;
+    Some(head)
+}
+
+fn read_u32(data: &mut &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(read(data, 4)?.try_into().ok()?))
+}
+
+fn read_uri(data: &mut &[u8]) -> Option<CString> {
+    let size = read_u32(data)? as usize;
+    CString::new(read(data, size)?).ok()
+}
+
+/// Write all bytes to a CLAP output stream.
+pub(crate) unsafe fn write_stream(stream: &sys::clap_ostream, mut data: &[u8]) -> bool {
+    let write = match stream.write {
+        Some(write) => write,
+        None => return false,
+    };
+    while !data.is_empty() {
+        let written = write(stream, data.as_ptr() as *const c_void, data.len() as u64);
+        if written <= 0 {
+            return false;
+        }
+        data = &data[(written as usize).min(data.len())..];
+    }
+    true
+}
+
+/// Read all bytes of a CLAP input stream.
+pub(crate) unsafe fn read_stream(stream: &sys::clap_istream) -> Option<Vec<u8>> {
+    let read = stream.read?;
+    let mut data: Vec<u8> = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        let count = read(
+            stream,
+            buffer.as_mut_ptr() as *mut c_void,
+            buffer.len() as u64,
+        );
+        match count {
+            0 => return Some(data),
+            count if count < 0 => return None,
+            count => data.extend_from_slice(&buffer[..(count as usize).min(buffer.len())]),
+        }
+    }
+}
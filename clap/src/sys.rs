@@ -0,0 +1,362 @@
+//! Raw bindings of the parts of the CLAP API that are used by the adapter.
+//!
+//! The types mirror the C headers of [CLAP](https://github.com/free-audio/clap) 1.2 and have the same names, which makes it easy to compare them with the headers. Only the entry point, the plugin factory, the plugin and host structs, the process data and events as well as the audio ports, note ports, params and state extensions are covered.
+#![allow(non_camel_case_types)]
+
+use std::ffi::{c_char, c_void};
+
+/// The version of CLAP the adapter implements.
+pub const CLAP_VERSION: clap_version = clap_version {
+    major: 1,
+    minor: 2,
+    revision: 0,
+};
+
+/// The size of the name buffers of port and parameter infos.
+pub const CLAP_NAME_SIZE: usize = 256;
+/// The size of the path buffers of parameter infos.
+pub const CLAP_PATH_SIZE: usize = 1024;
+/// An id that refers to nothing.
+pub const CLAP_INVALID_ID: clap_id = u32::MAX;
+
+pub type clap_id = u32;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct clap_version {
+    pub major: u32,
+    pub minor: u32,
+    pub revision: u32,
+}
+
+#[repr(C)]
+pub struct clap_plugin_entry {
+    pub clap_version: clap_version,
+    pub init: Option<unsafe extern "C" fn(plugin_path: *const c_char) -> bool>,
+    pub deinit: Option<unsafe extern "C" fn()>,
+    pub get_factory: Option<unsafe extern "C" fn(factory_id: *const c_char) -> *const c_void>,
+}
+
+pub const CLAP_PLUGIN_FACTORY_ID: &[u8] = b"clap.plugin-factory\0";
+
+#[repr(C)]
+pub struct clap_plugin_factory {
+    pub get_plugin_count: Option<unsafe extern "C" fn(factory: *const clap_plugin_factory) -> u32>,
+    pub get_plugin_descriptor: Option<
+        unsafe extern "C" fn(
+            factory: *const clap_plugin_factory,
+            index: u32,
+        ) -> *const clap_plugin_descriptor,
+    >,
+    pub create_plugin: Option<
+        unsafe extern "C" fn(
+            factory: *const clap_plugin_factory,
+            host: *const clap_host,
+            plugin_id: *const c_char,
+        ) -> *const clap_plugin,
+    >,
+}
+
+#[repr(C)]
+pub struct clap_plugin_descriptor {
+    pub clap_version: clap_version,
+    pub id: *const c_char,
+    pub name: *const c_char,
+    pub vendor: *const c_char,
+    pub url: *const c_char,
+    pub manual_url: *const c_char,
+    pub support_url: *const c_char,
+    pub version: *const c_char,
+    pub description: *const c_char,
+    pub features: *const *const c_char,
+}
+
+#[repr(C)]
+pub struct clap_plugin {
+    pub desc: *const clap_plugin_descriptor,
+    pub plugin_data: *mut c_void,
+    pub init: Option<unsafe extern "C" fn(plugin: *const clap_plugin) -> bool>,
+    pub destroy: Option<unsafe extern "C" fn(plugin: *const clap_plugin)>,
+    pub activate: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            sample_rate: f64,
+            min_frames_count: u32,
+            max_frames_count: u32,
+        ) -> bool,
+    >,
+    pub deactivate: Option<unsafe extern "C" fn(plugin: *const clap_plugin)>,
+    pub start_processing: Option<unsafe extern "C" fn(plugin: *const clap_plugin) -> bool>,
+    pub stop_processing: Option<unsafe extern "C" fn(plugin: *const clap_plugin)>,
+    pub reset: Option<unsafe extern "C" fn(plugin: *const clap_plugin)>,
+    pub process: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            process: *const clap_process,
+        ) -> clap_process_status,
+    >,
+    pub get_extension: Option<
+        unsafe extern "C" fn(plugin: *const clap_plugin, id: *const c_char) -> *const c_void,
+    >,
+    pub on_main_thread: Option<unsafe extern "C" fn(plugin: *const clap_plugin)>,
+}
+
+#[repr(C)]
+pub struct clap_host {
+    pub clap_version: clap_version,
+    pub host_data: *mut c_void,
+    pub name: *const c_char,
+    pub vendor: *const c_char,
+    pub url: *const c_char,
+    pub version: *const c_char,
+    pub get_extension: Option<
+        unsafe extern "C" fn(host: *const clap_host, extension_id: *const c_char) -> *const c_void,
+    >,
+    pub request_restart: Option<unsafe extern "C" fn(host: *const clap_host)>,
+    pub request_process: Option<unsafe extern "C" fn(host: *const clap_host)>,
+    pub request_callback: Option<unsafe extern "C" fn(host: *const clap_host)>,
+}
+
+pub type clap_process_status = i32;
+pub const CLAP_PROCESS_ERROR: clap_process_status = 0;
+pub const CLAP_PROCESS_CONTINUE: clap_process_status = 1;
+pub const CLAP_PROCESS_CONTINUE_IF_NOT_QUIET: clap_process_status = 2;
+pub const CLAP_PROCESS_TAIL: clap_process_status = 3;
+pub const CLAP_PROCESS_SLEEP: clap_process_status = 4;
+
+#[repr(C)]
+pub struct clap_audio_buffer {
+    pub data32: *mut *mut f32,
+    pub data64: *mut *mut f64,
+    pub channel_count: u32,
+    pub latency: u32,
+    pub constant_mask: u64,
+}
+
+#[repr(C)]
+pub struct clap_process {
+    pub steady_time: i64,
+    pub frames_count: u32,
+    pub transport: *const c_void,
+    pub audio_inputs: *const clap_audio_buffer,
+    pub audio_outputs: *mut clap_audio_buffer,
+    pub audio_inputs_count: u32,
+    pub audio_outputs_count: u32,
+    pub in_events: *const clap_input_events,
+    pub out_events: *const clap_output_events,
+}
+
+#[repr(C)]
+pub struct clap_event_header {
+    pub size: u32,
+    pub time: u32,
+    pub space_id: u16,
+    pub type_: u16,
+    pub flags: u32,
+}
+
+pub const CLAP_CORE_EVENT_SPACE_ID: u16 = 0;
+pub const CLAP_EVENT_NOTE_ON: u16 = 0;
+pub const CLAP_EVENT_NOTE_OFF: u16 = 1;
+pub const CLAP_EVENT_PARAM_VALUE: u16 = 5;
+pub const CLAP_EVENT_MIDI: u16 = 10;
+
+#[repr(C)]
+pub struct clap_event_note {
+    pub header: clap_event_header,
+    pub note_id: i32,
+    pub port_index: i16,
+    pub channel: i16,
+    pub key: i16,
+    pub velocity: f64,
+}
+
+#[repr(C)]
+pub struct clap_event_param_value {
+    pub header: clap_event_header,
+    pub param_id: clap_id,
+    pub cookie: *mut c_void,
+    pub note_id: i32,
+    pub port_index: i16,
+    pub channel: i16,
+    pub key: i16,
+    pub value: f64,
+}
+
+#[repr(C)]
+pub struct clap_event_midi {
+    pub header: clap_event_header,
+    pub port_index: u16,
+    pub data: [u8; 3],
+}
+
+#[repr(C)]
+pub struct clap_input_events {
+    pub ctx: *mut c_void,
+    pub size: Option<unsafe extern "C" fn(list: *const clap_input_events) -> u32>,
+    pub get: Option<
+        unsafe extern "C" fn(
+            list: *const clap_input_events,
+            index: u32,
+        ) -> *const clap_event_header,
+    >,
+}
+
+#[repr(C)]
+pub struct clap_output_events {
+    pub ctx: *mut c_void,
+    pub try_push: Option<
+        unsafe extern "C" fn(
+            list: *const clap_output_events,
+            event: *const clap_event_header,
+        ) -> bool,
+    >,
+}
+
+pub const CLAP_EXT_AUDIO_PORTS: &[u8] = b"clap.audio-ports\0";
+pub const CLAP_AUDIO_PORT_IS_MAIN: u32 = 1 << 0;
+pub const CLAP_PORT_MONO: &[u8] = b"mono\0";
+pub const CLAP_PORT_STEREO: &[u8] = b"stereo\0";
+
+#[repr(C)]
+pub struct clap_audio_port_info {
+    pub id: clap_id,
+    pub name: [c_char; CLAP_NAME_SIZE],
+    pub flags: u32,
+    pub channel_count: u32,
+    pub port_type: *const c_char,
+    pub in_place_pair: clap_id,
+}
+
+#[repr(C)]
+pub struct clap_plugin_audio_ports {
+    pub count: Option<unsafe extern "C" fn(plugin: *const clap_plugin, is_input: bool) -> u32>,
+    pub get: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            index: u32,
+            is_input: bool,
+            info: *mut clap_audio_port_info,
+        ) -> bool,
+    >,
+}
+
+pub const CLAP_EXT_NOTE_PORTS: &[u8] = b"clap.note-ports\0";
+pub const CLAP_NOTE_DIALECT_CLAP: u32 = 1 << 0;
+pub const CLAP_NOTE_DIALECT_MIDI: u32 = 1 << 1;
+
+#[repr(C)]
+pub struct clap_note_port_info {
+    pub id: clap_id,
+    pub supported_dialects: u32,
+    pub preferred_dialect: u32,
+    pub name: [c_char; CLAP_NAME_SIZE],
+}
+
+#[repr(C)]
+pub struct clap_plugin_note_ports {
+    pub count: Option<unsafe extern "C" fn(plugin: *const clap_plugin, is_input: bool) -> u32>,
+    pub get: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            index: u32,
+            is_input: bool,
+            info: *mut clap_note_port_info,
+        ) -> bool,
+    >,
+}
+
+pub const CLAP_EXT_PARAMS: &[u8] = b"clap.params\0";
+pub const CLAP_PARAM_IS_READONLY: u32 = 1 << 3;
+pub const CLAP_PARAM_IS_AUTOMATABLE: u32 = 1 << 5;
+pub const CLAP_PARAM_RESCAN_VALUES: u32 = 1 << 0;
+
+#[repr(C)]
+pub struct clap_param_info {
+    pub id: clap_id,
+    pub flags: u32,
+    pub cookie: *mut c_void,
+    pub name: [c_char; CLAP_NAME_SIZE],
+    pub module: [c_char; CLAP_PATH_SIZE],
+    pub min_value: f64,
+    pub max_value: f64,
+    pub default_value: f64,
+}
+
+#[repr(C)]
+pub struct clap_plugin_params {
+    pub count: Option<unsafe extern "C" fn(plugin: *const clap_plugin) -> u32>,
+    pub get_info: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            param_index: u32,
+            param_info: *mut clap_param_info,
+        ) -> bool,
+    >,
+    pub get_value: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            param_id: clap_id,
+            out_value: *mut f64,
+        ) -> bool,
+    >,
+    pub value_to_text: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            param_id: clap_id,
+            value: f64,
+            out_buffer: *mut c_char,
+            out_buffer_capacity: u32,
+        ) -> bool,
+    >,
+    pub text_to_value: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            param_id: clap_id,
+            param_value_text: *const c_char,
+            out_value: *mut f64,
+        ) -> bool,
+    >,
+    pub flush: Option<
+        unsafe extern "C" fn(
+            plugin: *const clap_plugin,
+            in_: *const clap_input_events,
+            out: *const clap_output_events,
+        ),
+    >,
+}
+
+#[repr(C)]
+pub struct clap_host_params {
+    pub rescan: Option<unsafe extern "C" fn(host: *const clap_host, flags: u32)>,
+    pub clear: Option<unsafe extern "C" fn(host: *const clap_host, param_id: clap_id, flags: u32)>,
+    pub request_flush: Option<unsafe extern "C" fn(host: *const clap_host)>,
+}
+
+pub const CLAP_EXT_STATE: &[u8] = b"clap.state\0";
+
+#[repr(C)]
+pub struct clap_ostream {
+    pub ctx: *mut c_void,
+    pub write: Option<
+        unsafe extern "C" fn(stream: *const clap_ostream, buffer: *const c_void, size: u64) -> i64,
+    >,
+}
+
+#[repr(C)]
+pub struct clap_istream {
+    pub ctx: *mut c_void,
+    pub read: Option<
+        unsafe extern "C" fn(stream: *const clap_istream, buffer: *mut c_void, size: u64) -> i64,
+    >,
+}
+
+#[repr(C)]
+pub struct clap_plugin_state {
+    pub save: Option<
+        unsafe extern "C" fn(plugin: *const clap_plugin, stream: *const clap_ostream) -> bool,
+    >,
+    pub load: Option<
+        unsafe extern "C" fn(plugin: *const clap_plugin, stream: *const clap_istream) -> bool,
+    >,
+}
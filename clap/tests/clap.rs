@@ -0,0 +1,639 @@
+use lv2_atom::prelude::*;
+use lv2_clap::sys::*;
+use lv2_clap::*;
+use lv2_core::prelude::*;
+use lv2_midi::prelude::*;
+use lv2_state::*;
+use lv2_ttl_gen::*;
+use lv2_units::prelude::*;
+use lv2_urid::*;
+use lv2_worker::*;
+use std::ffi::{c_char, c_void, CStr};
+use std::ptr::{null, null_mut};
+use std::sync::atomic::{AtomicU32, Ordering};
+use urid::*;
+
+#[derive(PortCollection)]
+struct SynthPorts {
+    gain: InputPort<Control>,
+    #[port_group(group = "main_in", kind = Stereo, channel = Left)]
+    input_left: InputPort<Audio>,
+    #[port_group(group = "main_in", channel = Right)]
+    input_right: InputPort<Audio>,
+    #[port_group(group = "main_out", kind = Stereo, channel = Left)]
+    output_left: OutputPort<Audio>,
+    #[port_group(group = "main_out", channel = Right)]
+    output_right: OutputPort<Audio>,
+    midi: InputPort<AtomPort>,
+    notes: OutputPort<Control>,
+}
+
+#[derive(FeatureCollection)]
+struct Features<'a> {
+    map: LV2Map<'a>,
+}
+
+#[uri("urn:lv2_clap:test:notes")]
+struct NotesKey;
+
+#[derive(URIDCollection)]
+struct URIDs {
+    atom: AtomURIDCollection,
+    midi: MidiURIDCollection,
+    units: UnitURIDCollection,
+}
+
+/// A stereo amp that counts the notes it receives.
+#[uri("urn:lv2_clap:test:synth")]
+struct Synth {
+    urids: URIDs,
+    notes_key: URID<NotesKey>,
+    notes: u32,
+}
+
+#[extensions(StateDescriptor)]
+impl Plugin for Synth {
+    type Ports = SynthPorts;
+    type InitFeatures = Features<'static>;
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+        Some(Self {
+            urids: features.map.populate_collection()?,
+            notes_key: features.map.map_type()?,
+            notes: 0,
+        })
+    }
+
+    fn run(&mut self, ports: &mut SynthPorts, _: &mut RunContext<()>) {
+        let gain = *ports.gain;
+        for (input, output) in ports.input_left.iter().zip(ports.output_left.iter_mut()) {
+            *output = input * gain;
+        }
+        for (input, output) in ports.input_right.iter().zip(ports.output_right.iter_mut()) {
+            *output = input * gain;
+        }
+        let events = ports
+            .midi
+            .read(self.urids.atom.sequence, self.urids.units.beat)
+            .unwrap();
+        for (_, atom) in events {
+            let message = atom.read(self.urids.midi.raw, ()).unwrap();
+            if message[0] & 0xf0 == 0x90 {
+                self.notes += 1;
+            }
+        }
+        **ports.notes = self.notes as f32;
+    }
+}
+
+impl State for Synth {
+    type StateFeatures = ();
+
+    fn save(&self, mut store: StoreHandle, _: ()) -> Result<(), StateErr> {
+        store
+            .draft(self.notes_key.into_general())
+            .init(self.urids.atom.int, self.notes as i32)?;
+        store.commit_all()
+    }
+
+    fn restore(&mut self, store: RetrieveHandle, _: ()) -> Result<(), StateErr> {
+        self.notes = store
+            .retrieve(self.notes_key.into_general())?
+            .read(self.urids.atom.int, ())? as u32;
+        Ok(())
+    }
+}
+
+impl ClapExport for Synth {
+    fn clap_description() -> ClapDescription {
+        let plugin = PluginDescription::new::<Synth>()
+            .class(plugin_class::INSTRUMENT)
+            .port_name("gain", "Gain")
+            .port_range("gain", 0.0, 1.0, 2.0)
+            .group_name("main_in", "Main input")
+            .group_name("main_out", "Main output");
+        ClapDescription::new(plugin, "Synth")
+            .vendor("rust-lv2")
+            .feature("stereo")
+    }
+}
+
+#[derive(PortCollection)]
+struct DoublerPorts {
+    total: OutputPort<Control>,
+}
+
+#[derive(FeatureCollection)]
+struct DoublerFeatures<'a> {
+    schedule: Schedule<'a, Doubler>,
+}
+
+/// A plugin that lets its worker double the number of frames of every cycle and sums the responses up.
+#[uri("urn:lv2_clap:test:doubler")]
+struct Doubler {
+    total: u32,
+}
+
+#[extensions(WorkerDescriptor)]
+impl Plugin for Doubler {
+    type Ports = DoublerPorts;
+    type InitFeatures = ();
+    type AudioFeatures = DoublerFeatures<'static>;
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self { total: 0 })
+    }
+
+    fn run(
+        &mut self,
+        ports: &mut DoublerPorts,
+        context: &mut RunContext<DoublerFeatures<'static>>,
+    ) {
+        let frames = context.sample_count();
+        assert!(context.features().schedule.schedule_work(frames).is_ok());
+        **ports.total = self.total as f32;
+    }
+}
+
+impl Worker for Doubler {
+    type WorkData = u32;
+    type ResponseData = u32;
+
+    fn work(response_handler: &ResponseHandler<Self>, data: u32) -> Result<(), WorkerError> {
+        response_handler
+            .respond(data * 2)
+            .map_err(|_| WorkerError::Unknown)
+    }
+
+    fn work_response(
+        &mut self,
+        data: u32,
+        _: &mut DoublerFeatures<'static>,
+    ) -> Result<(), WorkerError> {
+        self.total += data;
+        Ok(())
+    }
+}
+
+impl ClapExport for Doubler {
+    fn clap_description() -> ClapDescription {
+        ClapDescription::new(PluginDescription::new::<Doubler>(), "Doubler")
+    }
+}
+
+clap_entry!(Synth, Doubler);
+
+/// The number of times the plugins asked the host to rescan their parameters.
+static RESCANS: AtomicU32 = AtomicU32::new(0);
+
+unsafe extern "C" fn rescan(_: *const clap_host, flags: u32) {
+    assert_eq!(flags, CLAP_PARAM_RESCAN_VALUES);
+    RESCANS.fetch_add(1, Ordering::SeqCst);
+}
+
+static HOST_PARAMS: clap_host_params = clap_host_params {
+    rescan: Some(rescan),
+    clear: None,
+    request_flush: None,
+};
+
+unsafe extern "C" fn host_get_extension(_: *const clap_host, id: *const c_char) -> *const c_void {
+    if CStr::from_ptr(id).to_bytes_with_nul() == CLAP_EXT_PARAMS {
+        &HOST_PARAMS as *const clap_host_params as *const c_void
+    } else {
+        null()
+    }
+}
+
+fn host() -> clap_host {
+    clap_host {
+        clap_version: CLAP_VERSION,
+        host_data: null_mut(),
+        name: b"lv2-clap test\0".as_ptr() as *const c_char,
+        vendor: null(),
+        url: null(),
+        version: b"0.1.0\0".as_ptr() as *const c_char,
+        get_extension: Some(host_get_extension),
+        request_restart: None,
+        request_process: None,
+        request_callback: None,
+    }
+}
+
+unsafe fn factory() -> &'static clap_plugin_factory {
+    assert!(clap_entry.init.unwrap()(
+        b"/tmp/lv2-clap/test.clap\0".as_ptr() as *const c_char
+    ));
+    assert!(clap_entry.get_factory.unwrap()(b"clap.gui\0".as_ptr() as *const c_char).is_null());
+    let factory = clap_entry.get_factory.unwrap()(CLAP_PLUGIN_FACTORY_ID.as_ptr() as *const c_char);
+    (factory as *const clap_plugin_factory).as_ref().unwrap()
+}
+
+unsafe fn create(host: &clap_host, id: &[u8]) -> &'static clap_plugin {
+    let factory = factory();
+    let plugin = factory.create_plugin.unwrap()(factory, host, id.as_ptr() as *const c_char);
+    let plugin = plugin.as_ref().unwrap();
+    assert!(plugin.init.unwrap()(plugin));
+    plugin
+}
+
+unsafe fn extension<T>(plugin: &clap_plugin, id: &[u8]) -> &'static T {
+    let extension = plugin.get_extension.unwrap()(plugin, id.as_ptr() as *const c_char);
+    (extension as *const T).as_ref().unwrap()
+}
+
+fn name(name: &[c_char]) -> &str {
+    unsafe { CStr::from_ptr(name.as_ptr()) }.to_str().unwrap()
+}
+
+/// A list of input events for a process call.
+struct Events {
+    headers: Vec<*const clap_event_header>,
+    raw: clap_input_events,
+}
+
+unsafe extern "C" fn events_size(list: *const clap_input_events) -> u32 {
+    let headers = &*((*list).ctx as *const Vec<*const clap_event_header>);
+    headers.len() as u32
+}
+
+unsafe extern "C" fn events_get(
+    list: *const clap_input_events,
+    index: u32,
+) -> *const clap_event_header {
+    let headers = &*((*list).ctx as *const Vec<*const clap_event_header>);
+    headers[index as usize]
+}
+
+impl Events {
+    fn new(headers: Vec<*const clap_event_header>) -> Box<Self> {
+        let mut events = Box::new(Self {
+            headers,
+            raw: clap_input_events {
+                ctx: null_mut(),
+                size: Some(events_size),
+                get: Some(events_get),
+            },
+        });
+        events.raw.ctx = &mut events.headers as *mut Vec<_> as *mut c_void;
+        events
+    }
+}
+
+fn header<T>(time: u32, type_: u16) -> clap_event_header {
+    clap_event_header {
+        size: std::mem::size_of::<T>() as u32,
+        time,
+        space_id: CLAP_CORE_EVENT_SPACE_ID,
+        type_,
+        flags: 0,
+    }
+}
+
+fn param_event(time: u32, param_id: clap_id, value: f64) -> clap_event_param_value {
+    clap_event_param_value {
+        header: header::<clap_event_param_value>(time, CLAP_EVENT_PARAM_VALUE),
+        param_id,
+        cookie: null_mut(),
+        note_id: -1,
+        port_index: -1,
+        channel: -1,
+        key: -1,
+        value,
+    }
+}
+
+unsafe extern "C" fn stream_write(
+    stream: *const clap_ostream,
+    buffer: *const c_void,
+    size: u64,
+) -> i64 {
+    // Only write a few bytes at a time, like a host with a small buffer.
+    let size = size.min(7) as usize;
+    let data = &mut *((*stream).ctx as *mut Vec<u8>);
+    data.extend_from_slice(std::slice::from_raw_parts(buffer as *const u8, size));
+    size as i64
+}
+
+unsafe extern "C" fn stream_read(
+    stream: *const clap_istream,
+    buffer: *mut c_void,
+    size: u64,
+) -> i64 {
+    let data = &mut *((*stream).ctx as *mut &[u8]);
+    let size = (size as usize).min(data.len());
+    std::ptr::copy_nonoverlapping(data.as_ptr(), buffer as *mut u8, size);
+    *data = &data[size..];
+    size as i64
+}
+
+unsafe fn save(plugin: &clap_plugin) -> Vec<u8> {
+    let state = extension::<clap_plugin_state>(plugin, CLAP_EXT_STATE);
+    let mut data: Vec<u8> = Vec::new();
+    let stream = clap_ostream {
+        ctx: &mut data as *mut Vec<u8> as *mut c_void,
+        write: Some(stream_write),
+    };
+    assert!(state.save.unwrap()(plugin, &stream));
+    data
+}
+
+unsafe fn load(plugin: &clap_plugin, mut data: &[u8]) -> bool {
+    let state = extension::<clap_plugin_state>(plugin, CLAP_EXT_STATE);
+    let stream = clap_istream {
+        ctx: &mut data as *mut &[u8] as *mut c_void,
+        read: Some(stream_read),
+    };
+    state.load.unwrap()(plugin, &stream)
+}
+
+unsafe fn param_value(plugin: &clap_plugin, id: clap_id) -> f64 {
+    let params = extension::<clap_plugin_params>(plugin, CLAP_EXT_PARAMS);
+    let mut value = f64::NAN;
+    assert!(params.get_value.unwrap()(plugin, id, &mut value));
+    value
+}
+
+/// Process a block of stereo audio with the given events and return the outputs.
+unsafe fn process(
+    plugin: &clap_plugin,
+    input: &[f32],
+    events: Vec<*const clap_event_header>,
+) -> (Vec<f32>, Vec<f32>) {
+    let mut input_left = input.to_owned();
+    let mut input_right: Vec<f32> = input.iter().map(|sample| -sample).collect();
+    let mut output_left = vec![0.0f32; input.len()];
+    let mut output_right = vec![0.0f32; input.len()];
+    let mut inputs = [input_left.as_mut_ptr(), input_right.as_mut_ptr()];
+    let mut outputs = [output_left.as_mut_ptr(), output_right.as_mut_ptr()];
+    let audio_input = clap_audio_buffer {
+        data32: inputs.as_mut_ptr(),
+        data64: null_mut(),
+        channel_count: 2,
+        latency: 0,
+        constant_mask: 0,
+    };
+    let mut audio_output = clap_audio_buffer {
+        data32: outputs.as_mut_ptr(),
+        data64: null_mut(),
+        channel_count: 2,
+        latency: 0,
+        constant_mask: 0,
+    };
+    let events = Events::new(events);
+    let process = clap_process {
+        steady_time: -1,
+        frames_count: input.len() as u32,
+        transport: null(),
+        audio_inputs: &audio_input,
+        audio_outputs: &mut audio_output,
+        audio_inputs_count: 1,
+        audio_outputs_count: 1,
+        in_events: &events.raw,
+        out_events: null(),
+    };
+    assert_eq!(
+        plugin.process.unwrap()(plugin, &process),
+        CLAP_PROCESS_CONTINUE
+    );
+    (output_left, output_right)
+}
+
+#[test]
+fn test_factory() {
+    unsafe {
+        let factory = factory();
+        assert_eq!(factory.get_plugin_count.unwrap()(factory), 2);
+        assert!(factory.get_plugin_descriptor.unwrap()(factory, 2).is_null());
+
+        let descriptor = factory.get_plugin_descriptor.unwrap()(factory, 0)
+            .as_ref()
+            .unwrap();
+        assert_eq!(descriptor.clap_version, CLAP_VERSION);
+        assert_eq!(
+            CStr::from_ptr(descriptor.id).to_str().unwrap(),
+            "urn:lv2_clap:test:synth"
+        );
+        assert_eq!(CStr::from_ptr(descriptor.name).to_str().unwrap(), "Synth");
+        assert_eq!(
+            CStr::from_ptr(descriptor.vendor).to_str().unwrap(),
+            "rust-lv2"
+        );
+        let features: Vec<&str> = (0..)
+            .map(|index| *descriptor.features.add(index))
+            .take_while(|feature| !feature.is_null())
+            .map(|feature| CStr::from_ptr(feature).to_str().unwrap())
+            .collect();
+        assert_eq!(features, ["instrument", "stereo"]);
+
+        let host = host();
+        let plugin = factory.create_plugin.unwrap()(
+            factory,
+            &host,
+            b"urn:lv2_clap:test:unknown\0".as_ptr() as *const c_char,
+        );
+        assert!(plugin.is_null());
+    }
+}
+
+#[test]
+fn test_ports_and_params() {
+    unsafe {
+        let host = host();
+        let plugin = create(&host, b"urn:lv2_clap:test:synth\0");
+
+        let audio_ports = extension::<clap_plugin_audio_ports>(plugin, CLAP_EXT_AUDIO_PORTS);
+        assert_eq!(audio_ports.count.unwrap()(plugin, true), 1);
+        assert_eq!(audio_ports.count.unwrap()(plugin, false), 1);
+        let mut info: clap_audio_port_info = std::mem::zeroed();
+        assert!(audio_ports.get.unwrap()(plugin, 0, true, &mut info));
+        assert_eq!(name(&info.name), "Main input");
+        assert_eq!(info.flags, CLAP_AUDIO_PORT_IS_MAIN);
+        assert_eq!(info.channel_count, 2);
+        assert_eq!(
+            CStr::from_ptr(info.port_type).to_bytes_with_nul(),
+            CLAP_PORT_STEREO
+        );
+        assert!(!audio_ports.get.unwrap()(plugin, 1, true, &mut info));
+
+        let note_ports = extension::<clap_plugin_note_ports>(plugin, CLAP_EXT_NOTE_PORTS);
+        assert_eq!(note_ports.count.unwrap()(plugin, true), 1);
+        assert_eq!(note_ports.count.unwrap()(plugin, false), 0);
+
+        let params = extension::<clap_plugin_params>(plugin, CLAP_EXT_PARAMS);
+        assert_eq!(params.count.unwrap()(plugin), 2);
+        let mut info: clap_param_info = std::mem::zeroed();
+        assert!(params.get_info.unwrap()(plugin, 0, &mut info));
+        assert_eq!(info.id, 0);
+        assert_eq!(name(&info.name), "Gain");
+        assert_eq!(info.flags, CLAP_PARAM_IS_AUTOMATABLE);
+        assert_eq!(
+            (info.min_value, info.default_value, info.max_value),
+            (0.0, 1.0, 2.0)
+        );
+        assert!(params.get_info.unwrap()(plugin, 1, &mut info));
+        assert_eq!(info.id, 6);
+        assert_eq!(info.flags, CLAP_PARAM_IS_READONLY);
+        assert_eq!(param_value(plugin, 0), 1.0);
+
+        let mut text = [0 as c_char; 16];
+        assert!(params.value_to_text.unwrap()(
+            plugin,
+            0,
+            0.5,
+            text.as_mut_ptr(),
+            text.len() as u32
+        ));
+        assert_eq!(name(&text), "0.500");
+        let mut value = 0.0;
+        assert!(params.text_to_value.unwrap()(
+            plugin,
+            0,
+            b" 1.25\0".as_ptr() as *const c_char,
+            &mut value
+        ));
+        assert_eq!(value, 1.25);
+        assert!(!params.value_to_text.unwrap()(
+            plugin,
+            1000,
+            0.5,
+            text.as_mut_ptr(),
+            text.len() as u32
+        ));
+
+        // Values are clamped to the range of the parameter.
+        let event = param_event(0, 0, 4.0);
+        let events = Events::new(vec![&event.header]);
+        params.flush.unwrap()(plugin, &events.raw, null());
+        assert_eq!(param_value(plugin, 0), 2.0);
+
+        plugin.destroy.unwrap()(plugin);
+    }
+}
+
+#[test]
+fn test_process() {
+    unsafe {
+        let host = host();
+        let plugin = create(&host, b"urn:lv2_clap:test:synth\0");
+        assert!(plugin.activate.unwrap()(plugin, 44100.0, 1, 16));
+        assert!(plugin.start_processing.unwrap()(plugin));
+
+        let gain = param_event(4, 0, 0.5);
+        let note_on = clap_event_note {
+            header: header::<clap_event_note>(2, CLAP_EVENT_NOTE_ON),
+            note_id: -1,
+            port_index: 0,
+            channel: 0,
+            key: 60,
+            velocity: 1.0,
+        };
+        let midi = clap_event_midi {
+            header: header::<clap_event_midi>(6, CLAP_EVENT_MIDI),
+            port_index: 0,
+            data: [0x91, 64, 100],
+        };
+        let note_off = clap_event_note {
+            header: header::<clap_event_note>(7, CLAP_EVENT_NOTE_OFF),
+            note_id: -1,
+            port_index: 0,
+            channel: 0,
+            key: 60,
+            velocity: 0.0,
+        };
+        let (left, right) = process(
+            plugin,
+            &[1.0; 8],
+            vec![
+                &note_on.header,
+                &gain.header,
+                &midi.header,
+                &note_off.header,
+            ],
+        );
+        // The gain is changed at the frame of the event.
+        assert_eq!(left, [1.0, 1.0, 1.0, 1.0, 0.5, 0.5, 0.5, 0.5]);
+        assert_eq!(right, [-1.0, -1.0, -1.0, -1.0, -0.5, -0.5, -0.5, -0.5]);
+        assert_eq!(param_value(plugin, 0), 0.5);
+        assert_eq!(param_value(plugin, 6), 2.0);
+
+        plugin.stop_processing.unwrap()(plugin);
+        plugin.deactivate.unwrap()(plugin);
+        assert_eq!(
+            plugin.process.unwrap()(plugin, &std::mem::zeroed()),
+            CLAP_PROCESS_ERROR
+        );
+        plugin.destroy.unwrap()(plugin);
+    }
+}
+
+#[test]
+fn test_state() {
+    unsafe {
+        let host = host();
+        let plugin = create(&host, b"urn:lv2_clap:test:synth\0");
+        assert!(plugin.activate.unwrap()(plugin, 48000.0, 1, 16));
+        let gain = param_event(0, 0, 1.5);
+        let note_on = clap_event_note {
+            header: header::<clap_event_note>(0, CLAP_EVENT_NOTE_ON),
+            note_id: -1,
+            port_index: 0,
+            channel: 0,
+            key: 60,
+            velocity: 1.0,
+        };
+        process(plugin, &[0.0; 4], vec![&gain.header, &note_on.header]);
+        let state = save(plugin);
+        plugin.deactivate.unwrap()(plugin);
+        plugin.destroy.unwrap()(plugin);
+
+        // The state is loaded before the plugin is activated with another sample rate.
+        let plugin = create(&host, b"urn:lv2_clap:test:synth\0");
+        let rescans = RESCANS.load(Ordering::SeqCst);
+        assert!(load(plugin, &state));
+        assert!(RESCANS.load(Ordering::SeqCst) > rescans);
+        assert_eq!(param_value(plugin, 0), 1.5);
+        assert!(plugin.activate.unwrap()(plugin, 44100.0, 1, 16));
+        let (left, _) = process(plugin, &[1.0; 4], vec![]);
+        assert_eq!(left, [1.5; 4]);
+        assert_eq!(param_value(plugin, 6), 1.0);
+
+        assert!(!load(plugin, b"garbage"));
+        assert!(!load(plugin, &state[..state.len() - 1]));
+        plugin.deactivate.unwrap()(plugin);
+        plugin.destroy.unwrap()(plugin);
+    }
+}
+
+#[test]
+fn test_worker() {
+    unsafe {
+        let host = host();
+        let plugin = create(&host, b"urn:lv2_clap:test:doubler\0");
+        let params = extension::<clap_plugin_params>(plugin, CLAP_EXT_PARAMS);
+        let mut info: clap_param_info = std::mem::zeroed();
+        assert!(params.get_info.unwrap()(plugin, 0, &mut info));
+        assert_eq!(info.flags, CLAP_PARAM_IS_READONLY);
+        assert_eq!(
+            extension::<clap_plugin_audio_ports>(plugin, CLAP_EXT_AUDIO_PORTS)
+                .count
+                .unwrap()(plugin, false),
+            0
+        );
+
+        assert!(plugin.activate.unwrap()(plugin, 48000.0, 1, 16));
+        process(plugin, &[0.0; 8], vec![]);
+        // The worker runs in its own thread, whose responses are delivered after the following cycles.
+        for _ in 0..1000 {
+            if param_value(plugin, 0) != 0.0 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            process(plugin, &[], vec![]);
+        }
+        assert_eq!(param_value(plugin, 0), 16.0);
+        plugin.deactivate.unwrap()(plugin);
+        plugin.destroy.unwrap()(plugin);
+    }
+}
@@ -0,0 +1,122 @@
+use proc_macro::TokenStream;
+use std::cmp::Reverse;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, parse_macro_input, parse_quote, token, Token};
+use syn::{Ident, ImplItem, ItemImpl, LitInt, Path, PathArguments, Result};
+
+/// An extension that is provided by the plugin.
+struct ExtensionEntry {
+    descriptor: Path,
+    priority: i32,
+}
+
+impl Parse for ExtensionEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut descriptor = Path::parse_mod_style(input)?;
+
+        // Descriptors are usually generic over the plugin, which is why the plugin is used as the argument if there is none.
+        let arguments = if input.peek(Token![<]) {
+            PathArguments::AngleBracketed(input.parse()?)
+        } else {
+            PathArguments::AngleBracketed(parse_quote! {<Self>})
+        };
+        descriptor.segments.last_mut().unwrap().arguments = arguments;
+
+        let priority = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            let name: Ident = content.parse()?;
+            if name != "priority" {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Only the `priority` of an extension can be set",
+                ));
+            }
+            content.parse::<Token![=]>()?;
+            content.parse::<LitInt>()?.base10_parse()?
+        } else {
+            0
+        };
+
+        Ok(Self {
+            descriptor,
+            priority,
+        })
+    }
+}
+
+/// The arguments of the `extensions` attribute.
+struct ExtensionList {
+    entries: Punctuated<ExtensionEntry, Token![,]>,
+}
+
+impl Parse for ExtensionList {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            entries: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+impl ExtensionList {
+    /// The descriptors in the order they're looked up: By descending priority and in the order of the attribute.
+    fn sorted_descriptors(&self) -> Vec<&Path> {
+        let mut entries: Vec<&ExtensionEntry> = self.entries.iter().collect();
+        entries.sort_by_key(|entry| Reverse(entry.priority));
+        entries.iter().map(|entry| &entry.descriptor).collect()
+    }
+
+    /// Create the table of extension URIs.
+    fn make_table(&self) -> ImplItem {
+        let descriptors = self.sorted_descriptors();
+        parse_quote! {
+            const EXTENSIONS: &'static [&'static [u8]] = &[
+                #(<#descriptors as __derive::UriBound>::URI,)*
+            ];
+        }
+    }
+
+    /// Create the `extension_data` method, which returns the interface of the first descriptor with a matching URI.
+    fn make_extension_data(&self) -> ImplItem {
+        let descriptors = self.sorted_descriptors();
+        parse_quote! {
+            fn extension_data(uri: &__derive::Uri) -> Option<&'static dyn __derive::Any> {
+                let uri = uri.to_bytes_with_nul();
+                #(
+                    if uri == <#descriptors as __derive::UriBound>::URI {
+                        return Some(<#descriptors as __derive::ExtensionDescriptor>::INTERFACE as &'static dyn __derive::Any);
+                    }
+                )*
+                None
+            }
+        }
+    }
+}
+
+/// Add the extension table and the `extension_data` method to a `Plugin` implementation.
+pub fn extensions_impl(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let list: ExtensionList = parse_macro_input!(attribute);
+    let mut implementation: ItemImpl = parse_macro_input!(item);
+
+    if implementation.trait_.is_none() {
+        panic!("The `extensions` attribute can only be used on a `Plugin` implementation");
+    }
+    for item in implementation.items.iter() {
+        let name = match item {
+            ImplItem::Method(method) => &method.sig.ident,
+            ImplItem::Const(constant) => &constant.ident,
+            _ => continue,
+        };
+        if name == "extension_data" || name == "EXTENSIONS" {
+            panic!(
+                "`{}` is generated by the `extensions` attribute and must not be implemented",
+                name
+            );
+        }
+    }
+
+    implementation.items.push(list.make_table());
+    implementation.items.push(list.make_extension_data());
+    (quote! {#implementation}).into()
+}
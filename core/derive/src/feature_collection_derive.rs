@@ -1,17 +1,21 @@
+use crate::feature_collections::ClassArgument;
 use proc_macro::TokenStream;
 use syn::export::Span;
+use syn::spanned::Spanned;
 use syn::Field;
-use syn::{parse_macro_input, Data, DataStruct, Ident};
+use syn::{parse_macro_input, Attribute, Data, DataStruct, Ident, Type};
 use syn::{DeriveInput, Generics, Lifetime};
 
 struct FeatureCollectionField<'a> {
     identifier: &'a Ident,
+    feature_type: &'a Type,
 }
 
 impl<'a> FeatureCollectionField<'a> {
     fn from_input_field(input: &'a Field) -> Self {
         FeatureCollectionField {
             identifier: input.ident.as_ref().unwrap(),
+            feature_type: &input.ty,
         }
     }
 
@@ -19,12 +23,57 @@ impl<'a> FeatureCollectionField<'a> {
         let identifier = self.identifier;
         quote! {#identifier: cache.retrieve_feature(class)?,}
     }
+
+    /// Create a compile-time assertion that the feature can be used in the threading class.
+    fn make_class_check(&self, class: &Ident) -> impl ::quote::ToTokens {
+        let feature_type = self.feature_type;
+        let message = format!(
+            "The feature `{}` can't be used in the {} threading class",
+            quote! {#feature_type},
+            class
+        );
+        quote_spanned! {feature_type.span()=>
+            const {
+                assert!(
+                    ThreadingClass::#class.is_in(FeatureCache::threading_classes::<_, #feature_type>()),
+                    #message
+                )
+            };
+        }
+    }
+
+    fn make_feature_info(&self) -> impl ::quote::ToTokens {
+        let feature_type = self.feature_type;
+        quote! {FeatureCache::feature_info::<_, #feature_type>(),}
+    }
 }
 
 struct FeatureCollectionStruct<'a> {
     struct_name: &'a Ident,
     generics: &'a Generics,
     fields: Vec<FeatureCollectionField<'a>>,
+    class: Option<Ident>,
+}
+
+/// Read the threading class of a collection from its `feature_collection` attribute.
+fn class_from_attributes(attributes: &[Attribute]) -> Option<Ident> {
+    let attribute = attributes
+        .iter()
+        .find(|attribute| attribute.path.is_ident("feature_collection"))?;
+    let argument: ClassArgument = attribute.parse_args().expect(
+        "The `feature_collection` attribute has to look like `#[feature_collection(class = \"audio\")]`",
+    );
+    let class = match argument.class.value().as_str() {
+        "discovery" => "Discovery",
+        "instantiation" => "Instantiation",
+        "audio" => "Audio",
+        "other" => "Other",
+        class => panic!(
+            "Unknown threading class `{}`; Use \"discovery\", \"instantiation\", \"audio\" or \"other\"",
+            class
+        ),
+    };
+    Some(Ident::new(class, argument.class.span()))
 }
 
 impl<'a> FeatureCollectionStruct<'a> {
@@ -40,6 +89,7 @@ impl<'a> FeatureCollectionStruct<'a> {
             struct_name: &input.ident,
             fields,
             generics: &input.generics,
+            class: class_from_attributes(&input.attrs),
         }
     }
 
@@ -47,6 +97,12 @@ impl<'a> FeatureCollectionStruct<'a> {
         let struct_name = self.struct_name;
         let generics = self.generics;
         let retrievals = self.fields.iter().map(|field| field.make_retrieval());
+        let feature_infos = self.fields.iter().map(|field| field.make_feature_info());
+        let class_checks = self.class.iter().flat_map(|class| {
+            self.fields
+                .iter()
+                .map(move |field| field.make_class_check(class))
+        });
         // retrieve the first lifetime of the struct, or set it to `'static` if there is none.
         let lifetime = self
             .generics
@@ -60,11 +116,16 @@ impl<'a> FeatureCollectionStruct<'a> {
                 fn from_cache(
                     cache: &mut FeatureCache<#lifetime>,
                     class: ThreadingClass,
-                ) -> Result<Self, MissingFeatureError> {
+                ) -> Result<Self, FeatureError> {
+                    #(#class_checks)*
                     Ok(Self {
                         #(#retrievals)*
                     })
                 }
+
+                fn features() -> __derive::Vec<FeatureInfo> {
+                    __derive::Vec::from([#(#feature_infos)*])
+                }
             }
         })
         .into()
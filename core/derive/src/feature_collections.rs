@@ -0,0 +1,218 @@
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Field, Fields, GenericParam, Generics, Ident, ItemStruct};
+use syn::{LitStr, Token, WherePredicate};
+
+/// The arguments of the `feature_collections` attribute, like `audio = AudioFeatures`.
+struct CollectionNames {
+    audio: Ident,
+}
+
+impl Parse for CollectionNames {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if name != "audio" {
+            return Err(syn::Error::new(
+                name.span(),
+                "Only the name of the `audio` collection can be set",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let audio: Ident = input.parse()?;
+        Ok(Self { audio })
+    }
+}
+
+/// The argument of the `feature` attribute of a field, like `class = "audio"`.
+///
+/// The `feature_collection` attribute of the `FeatureCollection` derive takes the same argument.
+pub struct ClassArgument {
+    pub class: LitStr,
+}
+
+impl Parse for ClassArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if name != "class" {
+            return Err(syn::Error::new(
+                name.span(),
+                "Only the `class` of a feature can be set",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(Self {
+            class: input.parse()?,
+        })
+    }
+}
+
+/// The collections a feature is a part of.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FeatureClass {
+    Init,
+    Audio,
+    Both,
+}
+
+impl FeatureClass {
+    /// Read the class of a field from its `feature` attribute.
+    ///
+    /// Fields without the attribute are only a part of the init collection.
+    fn from_field(field: &Field) -> Self {
+        let attribute = match field
+            .attrs
+            .iter()
+            .find(|attribute| attribute.path.is_ident("feature"))
+        {
+            Some(attribute) => attribute,
+            None => return FeatureClass::Init,
+        };
+        let argument: ClassArgument = attribute
+            .parse_args()
+            .expect("The `feature` attribute has to look like `#[feature(class = \"audio\")]`");
+        match argument.class.value().as_str() {
+            "init" => FeatureClass::Init,
+            "audio" => FeatureClass::Audio,
+            "both" => FeatureClass::Both,
+            class => panic!(
+                "Unknown feature class `{}`; Use \"init\", \"audio\" or \"both\"",
+                class
+            ),
+        }
+    }
+
+    fn is_init(self) -> bool {
+        self != FeatureClass::Audio
+    }
+
+    fn is_audio(self) -> bool {
+        self != FeatureClass::Init
+    }
+}
+
+/// Check whether the tokens mention a generic parameter.
+fn mentions(tokens: TokenStream2, parameter: &GenericParam) -> bool {
+    let mut after_apostrophe = false;
+    for tree in tokens {
+        let found = match &tree {
+            TokenTree::Group(group) => mentions(group.stream(), parameter),
+            TokenTree::Ident(ident) => match parameter {
+                GenericParam::Lifetime(lifetime) => {
+                    after_apostrophe && *ident == lifetime.lifetime.ident
+                }
+                GenericParam::Type(type_parameter) => {
+                    !after_apostrophe && *ident == type_parameter.ident
+                }
+                GenericParam::Const(constant) => *ident == constant.ident,
+            },
+            _ => false,
+        };
+        if found {
+            return true;
+        }
+        after_apostrophe = match &tree {
+            TokenTree::Punct(punct) => punct.as_char() == '\'',
+            _ => false,
+        };
+    }
+    false
+}
+
+/// Create the generics of a collection, which only contain the parameters used by its fields.
+///
+/// Unused parameters aren't allowed in structs, and a collection may only use some of the parameters of the annotated struct.
+fn make_generics(generics: &Generics, fields: &[Field]) -> Generics {
+    let field_types: TokenStream2 = fields
+        .iter()
+        .map(|field| field.ty.to_token_stream())
+        .collect();
+    let (used, unused): (Vec<GenericParam>, Vec<GenericParam>) = generics
+        .params
+        .iter()
+        .cloned()
+        .partition(|parameter| mentions(field_types.clone(), parameter));
+
+    let mut collection_generics = generics.clone();
+    collection_generics.params = used.into_iter().collect();
+    if let Some(where_clause) = collection_generics.where_clause.as_mut() {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate: &&WherePredicate| {
+                !unused
+                    .iter()
+                    .any(|parameter| mentions(quote! {#predicate}, parameter))
+            })
+            .cloned()
+            .collect();
+    }
+    collection_generics
+}
+
+/// Create a collection struct from the fields of the given class.
+fn make_collection(
+    generics: &Generics,
+    fields: &[Field],
+    filter: fn(FeatureClass) -> bool,
+) -> (Vec<Field>, Generics) {
+    let fields: Vec<Field> = fields
+        .iter()
+        .filter(|field| filter(FeatureClass::from_field(field)))
+        .cloned()
+        .map(|mut field| {
+            field
+                .attrs
+                .retain(|attribute| !attribute.path.is_ident("feature"));
+            field
+        })
+        .collect();
+    let generics = make_generics(generics, &fields);
+    (fields, generics)
+}
+
+/// Create the init and the audio feature collection from one struct.
+pub fn feature_collections_impl(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let names: CollectionNames = parse_macro_input!(attribute);
+    let item: ItemStruct = parse_macro_input!(item);
+
+    let fields: Vec<Field> = match &item.fields {
+        Fields::Named(fields) => fields.named.iter().cloned().collect(),
+        _ => panic!(
+            "The `feature_collections` attribute can only be used on structs with named fields"
+        ),
+    };
+
+    let attributes = &item.attrs;
+    let visibility = &item.vis;
+    let init_name = &item.ident;
+    let (init_fields, init_generics) =
+        make_collection(&item.generics, &fields, FeatureClass::is_init);
+    let init_where = &init_generics.where_clause;
+    let audio_name = &names.audio;
+    let (audio_fields, audio_generics) =
+        make_collection(&item.generics, &fields, FeatureClass::is_audio);
+    let audio_where = &audio_generics.where_clause;
+    let audio_doc = format!(
+        "The features of [`{}`](struct.{}.html) that are used in the audio threading class.",
+        init_name, init_name
+    );
+
+    (quote! {
+        #(#attributes)*
+        #[derive(FeatureCollection)]
+        #[feature_collection(class = "instantiation")]
+        #visibility struct #init_name #init_generics #init_where {
+            #(#init_fields,)*
+        }
+
+        #[doc = #audio_doc]
+        #[derive(FeatureCollection)]
+        #[feature_collection(class = "audio")]
+        #visibility struct #audio_name #audio_generics #audio_where {
+            #(#audio_fields,)*
+        }
+    })
+    .into()
+}
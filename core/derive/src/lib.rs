@@ -2,12 +2,17 @@
 #![recursion_limit = "128"]
 
 extern crate proc_macro;
+extern crate proc_macro2;
 extern crate syn;
 #[macro_use]
 extern crate quote;
 
+mod extensions;
 mod feature_collection_derive;
+mod feature_collections;
 mod lv2_descriptors;
+mod lv2_library;
+mod plugin_version;
 mod port_collection_derive;
 
 use proc_macro::TokenStream;
@@ -18,13 +23,58 @@ pub fn lv2_descriptors(input: TokenStream) -> TokenStream {
     lv2_descriptors::lv2_descriptors_impl(input)
 }
 
+/// Generate external symbols for LV2 plugins, together with a symbol that identifies the library.
+///
+/// The attribute is used on the `lv2_descriptors!` invocation of a plugin library. Besides the `lv2_descriptor` entry point, it exports a static with the name and version of the library under the symbol `rust_lv2_library_<name>`, which makes the library recognizable in debuggers and symbol tables when several plugin libraries are loaded into the same host. The name defaults to the name of the crate and can be set with `#[lv2_library(name = "my_plugins")]`.
+///
+/// The attribute also checks that the entry point has the signature hosts expect. Both symbols are exported with unmangled names, which rustc exports from `cdylib` crates on every platform, including the `dllexport` on Windows, so the crate only needs `crate-type = ["cdylib"]` and no linker arguments.
+#[proc_macro_attribute]
+pub fn lv2_library(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    lv2_library::lv2_library_impl(attribute, item)
+}
+
 /// Implement the `PortCollection` trait for a port struct.
-#[proc_macro_derive(PortCollection)]
+///
+/// Besides the trait, the macro generates a `PORT_INDEX_<FIELD>` constant for every port and an enum of all port indices, which is named after the struct with an `Index` suffix. The index of a port can be pinned with `#[port(index = N)]`, its designation can be declared with `#[port(designation = Enabled)]` and side-chain inputs are marked with `#[port(side_chain)]`.
+#[proc_macro_derive(PortCollection, attributes(port, port_group))]
 pub fn port_collection_derive(input: TokenStream) -> TokenStream {
     port_collection_derive::port_collection_derive_impl(input)
 }
 
-#[proc_macro_derive(FeatureCollection)]
+/// Implement the `FeatureCollection` trait for a feature struct.
+///
+/// The threading class of the collection can be declared with `#[feature_collection(class = "audio")]`, which checks at compile time that all features of the collection can be used in this class. The classes are `"discovery"`, `"instantiation"`, `"audio"` and `"other"`.
+#[proc_macro_derive(FeatureCollection, attributes(feature_collection))]
 pub fn feature_collection_derive(input: TokenStream) -> TokenStream {
     feature_collection_derive::feature_collection_derive_impl(input)
 }
+
+/// Create the init and the audio feature collection of a plugin from one struct.
+///
+/// The attribute is used on the struct of the init collection and takes the name of the audio collection, like `#[feature_collections(audio = AudioFeatures)]`. Both structs implement `FeatureCollection`, which therefore must not be derived for the annotated struct again, and their features are checked against the instantiation and the audio threading class at compile time.
+///
+/// By default, a field is only a part of the init collection. With `#[feature(class = "audio")]`, it's only a part of the audio collection, and with `#[feature(class = "both")]`, it's a part of both collections. The audio collection only has the generic parameters that its fields use.
+#[proc_macro_attribute]
+pub fn feature_collections(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    feature_collections::feature_collections_impl(attribute, item)
+}
+
+/// Generate the `extension_data` method of a plugin from a list of extension descriptors.
+///
+/// The attribute is used on the `Plugin` implementation of a plugin and takes the descriptors of all extensions the plugin provides, like `#[extensions(StateDescriptor, WorkerDescriptor)]`. The descriptors are instantiated for the plugin, so `StateDescriptor` stands for `StateDescriptor<Self>`. Descriptors with explicit generic arguments, like `MyDescriptor<Self, f32>` or `MyDescriptor<>`, are used as they are.
+///
+/// Besides `extension_data`, the attribute generates the `EXTENSIONS` table with the URIs of the extensions. Both are generated from the same list and therefore can't diverge.
+///
+/// If two descriptors have the same URI, the one that comes first in the list is used. This order can be changed with priorities: A descriptor with a higher priority, like `MyStateDescriptor(priority = 1)`, is looked up before descriptors with lower priorities. The default priority is 0.
+#[proc_macro_attribute]
+pub fn extensions(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    extensions::extensions_impl(attribute, item)
+}
+
+/// Declare the version of a plugin and the plugins it replaces.
+///
+/// The attribute is used on the `Plugin` implementation of a plugin, like `#[plugin_version(minor = 2, micro = 1)]`, and generates the `VERSION` constant. The micro version defaults to 0. The URIs of plugins that are replaced by this one are added with `replaces = "urn:ladspa:1049"`, which may be repeated, and are collected in the `REPLACES` table.
+#[proc_macro_attribute]
+pub fn plugin_version(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    plugin_version::plugin_version_impl(attribute, item)
+}
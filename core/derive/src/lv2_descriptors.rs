@@ -1,7 +1,7 @@
 use proc_macro::TokenStream;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, Result, Token, Type};
+use syn::{Result, Token, Type};
 
 /// An instance descriptor that should be exported.
 ///
@@ -28,7 +28,7 @@ impl Lv2InstanceDescriptor {
         quote! {
             unsafe impl PluginInstanceDescriptor for #plugin_type {
                 const DESCRIPTOR: LV2_Descriptor = LV2_Descriptor {
-                    URI: Self::URI.as_ptr() as *const u8 as *const ::std::os::raw::c_char,
+                    URI: Self::URI.as_ptr() as *const u8 as *const __derive::c_char,
                     instantiate: Some(PluginInstance::<Self>::instantiate),
                     connect_port: Some(PluginInstance::<Self>::connect_port),
                     activate: Some(PluginInstance::<Self>::activate),
@@ -106,23 +106,40 @@ impl Lv2InstanceDescriptorList {
             pub unsafe extern "C" fn lv2_descriptor(index: u32) -> *const LV2_Descriptor {
                 match index {
                     #(#index_matchers)*
-                    _ => ::std::ptr::null()
+                    _ => __derive::null()
                 }
             }
+
+            /// Return a raw pointer to the contract between the library and hosts that are built with rust-lv2.
+            ///
+            /// # Safety
+            ///
+            /// This function is only unsafe because it's directly called by the host. The returned pointer references a static.
+            #[no_mangle]
+            pub unsafe extern "C" fn rust_lv2_abi() -> *const __derive::AbiInfo {
+                &__derive::ABI_INFO
+            }
         }
     }
 }
 
-/// Generate external symbols for LV2 plugins.
-#[inline]
-pub fn lv2_descriptors_impl(input: TokenStream) -> TokenStream {
-    let list: Lv2InstanceDescriptorList = parse_macro_input!(input);
+/// Generate external symbols for LV2 plugins from the arguments of the macro.
+pub fn lv2_descriptors_tokens(input: proc_macro2::TokenStream) -> Result<proc_macro2::TokenStream> {
+    let list: Lv2InstanceDescriptorList = syn::parse2(input)?;
     let descriptors = list.make_instance_descriptor_impls();
     let export_function = list.make_descriptor_function();
 
-    (quote! {
+    Ok(quote! {
         #(#descriptors)*
         #export_function
     })
-    .into()
+}
+
+/// Generate external symbols for LV2 plugins.
+#[inline]
+pub fn lv2_descriptors_impl(input: TokenStream) -> TokenStream {
+    match lv2_descriptors_tokens(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
 }
@@ -0,0 +1,98 @@
+use crate::lv2_descriptors::lv2_descriptors_tokens;
+use proc_macro::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Error, Ident, ItemMacro, LitStr, Result, Token};
+
+/// The arguments of the `lv2_library` attribute.
+struct LibraryArguments {
+    name: Option<LitStr>,
+}
+
+impl Parse for LibraryArguments {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.is_empty() {
+            return Ok(Self { name: None });
+        }
+        let key: Ident = input.parse()?;
+        if key != "name" {
+            return Err(Error::new(
+                key.span(),
+                "Only the `name` of a library can be set",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let name: LitStr = input.parse()?;
+        if name.value().is_empty()
+            || !name
+                .value()
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(Error::new(
+                name.span(),
+                "The name of a library may only contain ASCII letters, digits and underscores",
+            ));
+        }
+        Ok(Self { name: Some(name) })
+    }
+}
+
+/// Return the name of the crate that is currently compiled, as used in symbol names.
+fn crate_name() -> String {
+    std::env::var("CARGO_CRATE_NAME")
+        .or_else(|_| std::env::var("CARGO_PKG_NAME"))
+        .unwrap_or_else(|_| "plugins".to_owned())
+        .replace('-', "_")
+}
+
+/// Export the plugins of a library, together with a symbol that identifies the library.
+#[inline]
+pub fn lv2_library_impl(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let arguments = parse_macro_input!(attribute as LibraryArguments);
+    let invocation = parse_macro_input!(item as ItemMacro);
+    if invocation
+        .mac
+        .path
+        .segments
+        .last()
+        .is_none_or(|segment| segment.ident != "lv2_descriptors")
+    {
+        return Error::new_spanned(
+            &invocation.mac.path,
+            "The `lv2_library` attribute has to be used on the `lv2_descriptors!` invocation",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let descriptors = match lv2_descriptors_tokens(invocation.mac.tokens) {
+        Ok(descriptors) => descriptors,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let name = arguments
+        .name
+        .map(|name| name.value())
+        .unwrap_or_else(crate_name);
+    let symbol = format!("rust_lv2_library_{}", name);
+    let version = std::env::var("CARGO_PKG_VERSION").unwrap_or_default();
+    let identification = format!("{} {}\0", name, version);
+    let identification =
+        syn::LitByteStr::new(identification.as_bytes(), proc_macro2::Span::call_site());
+    let length = identification.value().len();
+
+    (quote! {
+        #descriptors
+
+        /// The name and version of the plugin library.
+        ///
+        /// The symbol of this static is unique for every library, which identifies the library in debuggers and symbol tables, even if several plugin libraries that all export `lv2_descriptor` are loaded into the same host.
+        #[doc(hidden)]
+        #[used]
+        #[export_name = #symbol]
+        pub static __LV2_LIBRARY: [u8; #length] = *#identification;
+
+        // The exported function has to match the entry point that hosts look up.
+        const _: unsafe extern "C" fn(u32) -> *const LV2_Descriptor = lv2_descriptor;
+    })
+    .into()
+}
@@ -0,0 +1,134 @@
+use proc_macro::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, parse_quote, Error, Ident, ImplItem, ItemImpl, Lit, LitByteStr};
+use syn::{Result, Token};
+
+/// A `key = value` argument of the `plugin_version` attribute.
+struct VersionArgument {
+    key: Ident,
+    value: Lit,
+}
+
+impl Parse for VersionArgument {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Self { key, value })
+    }
+}
+
+/// The version and the replaced plugins, as declared by the attribute.
+struct VersionArguments {
+    minor: u32,
+    micro: u32,
+    replaces: Vec<LitByteStr>,
+}
+
+impl Parse for VersionArguments {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let arguments: Punctuated<VersionArgument, Token![,]> =
+            Punctuated::parse_terminated(input)?;
+
+        let mut minor = None;
+        let mut micro = None;
+        let mut replaces = Vec::new();
+        for argument in arguments {
+            match (argument.key.to_string().as_str(), &argument.value) {
+                ("minor", Lit::Int(value)) if minor.is_none() => {
+                    minor = Some(value.base10_parse()?)
+                }
+                ("micro", Lit::Int(value)) if micro.is_none() => {
+                    micro = Some(value.base10_parse()?)
+                }
+                ("replaces", Lit::Str(uri)) => {
+                    let value = uri.value();
+                    if value.is_empty() || value.contains('\0') {
+                        return Err(Error::new(
+                            uri.span(),
+                            "The URI of a replaced plugin may neither be empty nor contain null bytes",
+                        ));
+                    }
+                    replaces.push(LitByteStr::new(
+                        format!("{}\0", value).as_bytes(),
+                        uri.span(),
+                    ));
+                }
+                ("minor", _) | ("micro", _) | ("replaces", _) => {
+                    return Err(Error::new_spanned(
+                        &argument.value,
+                        "`minor` and `micro` have to be set once to an integer, and `replaces` to a string with a URI",
+                    ))
+                }
+                _ => {
+                    return Err(Error::new(
+                        argument.key.span(),
+                        "Only the `minor` and `micro` version and the `replaces` URIs of a plugin can be set",
+                    ))
+                }
+            }
+        }
+
+        let minor =
+            minor.ok_or_else(|| Error::new(input.span(), "The `minor` version has to be set"))?;
+        Ok(Self {
+            minor,
+            micro: micro.unwrap_or(0),
+            replaces,
+        })
+    }
+}
+
+impl VersionArguments {
+    /// Create the `VERSION` constant.
+    fn make_version(&self) -> ImplItem {
+        let minor = self.minor;
+        let micro = self.micro;
+        parse_quote! {
+            const VERSION: Option<__derive::PluginVersion> = Some(__derive::PluginVersion::new(#minor, #micro));
+        }
+    }
+
+    /// Create the `REPLACES` table.
+    fn make_replaces(&self) -> ImplItem {
+        let replaces = &self.replaces;
+        parse_quote! {
+            const REPLACES: &'static [&'static [u8]] = &[#(#replaces,)*];
+        }
+    }
+}
+
+/// Add the version and the table of replaced plugins to a `Plugin` implementation.
+pub fn plugin_version_impl(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let arguments: VersionArguments = parse_macro_input!(attribute);
+    let mut implementation: ItemImpl = parse_macro_input!(item);
+
+    if implementation.trait_.is_none() {
+        return Error::new_spanned(
+            &implementation.self_ty,
+            "The `plugin_version` attribute can only be used on a `Plugin` implementation",
+        )
+        .to_compile_error()
+        .into();
+    }
+    for item in implementation.items.iter() {
+        if let ImplItem::Const(constant) = item {
+            if constant.ident == "VERSION" || constant.ident == "REPLACES" {
+                return Error::new(
+                    constant.ident.span(),
+                    format!(
+                        "`{}` is generated by the `plugin_version` attribute and must not be implemented",
+                        constant.ident
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+    }
+
+    implementation.items.push(arguments.make_version());
+    implementation.items.push(arguments.make_replaces());
+    (quote! {#implementation}).into()
+}
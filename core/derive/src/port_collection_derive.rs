@@ -1,21 +1,252 @@
 use proc_macro::TokenStream;
 use syn::export::Span;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::DeriveInput;
 use syn::Field;
-use syn::{parse_macro_input, Data, DataStruct, Ident, Type};
+use syn::{parse_macro_input, Data, DataStruct, Ident, LitInt, LitStr, Token, Type, Visibility};
+
+/// The value of a `port_group` argument, which is either a string literal or an identifier.
+enum PortGroupValue {
+    Str(LitStr),
+    Ident(Ident),
+}
+
+/// A single `name = value` argument of the `port_group` attribute.
+struct PortGroupArgument {
+    name: Ident,
+    value: PortGroupValue,
+}
+
+impl Parse for PortGroupArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = if input.peek(LitStr) {
+            PortGroupValue::Str(input.parse()?)
+        } else {
+            PortGroupValue::Ident(input.parse()?)
+        };
+        Ok(Self { name, value })
+    }
+}
+
+impl PortGroupArgument {
+    fn into_string(self) -> String {
+        match self.value {
+            PortGroupValue::Str(value) => value.value(),
+            PortGroupValue::Ident(_) => panic!("`{}` has to be a string literal", self.name),
+        }
+    }
+
+    fn into_ident(self) -> Ident {
+        match self.value {
+            PortGroupValue::Ident(value) => value,
+            PortGroupValue::Str(_) => panic!("`{}` has to be an identifier", self.name),
+        }
+    }
+}
+
+/// The `port_group` attribute of a field.
+struct PortGroupAttribute {
+    group: String,
+    kind: Option<Ident>,
+    channel: Ident,
+    side_chain_of: Option<String>,
+}
+
+impl PortGroupAttribute {
+    /// Parse the `port_group` attribute of a field, if it has one.
+    fn from_input_field(input: &Field) -> Option<Self> {
+        let identifier = input.ident.as_ref().unwrap();
+        let attribute = input
+            .attrs
+            .iter()
+            .find(|attribute| attribute.path.is_ident("port_group"))?;
+        let arguments = attribute
+            .parse_args_with(Punctuated::<PortGroupArgument, Token![,]>::parse_terminated)
+            .expect("The `port_group` attribute has to be a list of `name = value` pairs");
+
+        let mut group = None;
+        let mut kind = None;
+        let mut channel = None;
+        let mut side_chain_of = None;
+        for argument in arguments {
+            match argument.name.to_string().as_str() {
+                "group" => group = Some(argument.into_string()),
+                "kind" => kind = Some(argument.into_ident()),
+                "channel" => channel = Some(argument.into_ident()),
+                "side_chain_of" => side_chain_of = Some(argument.into_string()),
+                name => panic!("Unknown port group argument `{}`", name),
+            }
+        }
+
+        Some(Self {
+            group: group.unwrap_or_else(|| panic!("Port `{}` has no group", identifier)),
+            kind,
+            channel: channel
+                .unwrap_or_else(|| panic!("Port `{}` has no channel in its group", identifier)),
+            side_chain_of,
+        })
+    }
+}
+
+/// The value of a `port` argument, which is an integer, an identifier or missing for flags.
+enum PortValue {
+    Int(LitInt),
+    Ident(Ident),
+    None,
+}
+
+/// A single argument of the `port` attribute, which is either a `name = value` pair or a flag.
+struct PortArgument {
+    name: Ident,
+    value: PortValue,
+}
+
+impl Parse for PortArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(LitInt) {
+                PortValue::Int(input.parse()?)
+            } else {
+                PortValue::Ident(input.parse()?)
+            }
+        } else {
+            PortValue::None
+        };
+        Ok(Self { name, value })
+    }
+}
+
+/// The `port` attribute of a field.
+#[derive(Default)]
+struct PortAttribute {
+    index: Option<u32>,
+    designation: Option<Ident>,
+    side_chain: bool,
+}
+
+impl PortAttribute {
+    /// Parse the `port` attribute of a field, or return the default if it has none.
+    fn from_input_field(input: &Field) -> Self {
+        let attribute = match input
+            .attrs
+            .iter()
+            .find(|attribute| attribute.path.is_ident("port"))
+        {
+            Some(attribute) => attribute,
+            None => return Self::default(),
+        };
+        let arguments = attribute
+            .parse_args_with(Punctuated::<PortArgument, Token![,]>::parse_terminated)
+            .expect("The `port` attribute has to be a list of `name = value` pairs and flags");
+
+        let mut port = Self::default();
+        for argument in arguments {
+            match (argument.name.to_string().as_str(), argument.value) {
+                ("index", PortValue::Int(index)) => {
+                    port.index = Some(
+                        index
+                            .base10_parse()
+                            .expect("A port index has to be a `u32`"),
+                    )
+                }
+                ("designation", PortValue::Ident(designation)) => {
+                    port.designation = Some(designation)
+                }
+                ("side_chain", PortValue::None) => port.side_chain = true,
+                ("index", _) => panic!("The `index` of a port has to be an integer"),
+                ("designation", _) => panic!("The `designation` of a port has to be an identifier"),
+                ("side_chain", _) => panic!("`side_chain` is a flag and doesn't take a value"),
+                (name, _) => panic!("Unknown port argument `{}`", name),
+            }
+        }
+        port
+    }
+}
 
 /// A field in the struct we implement `PortCollection` for.
 struct PortCollectionField<'a> {
     identifier: &'a Ident,
     port_type: &'a Type,
+    index: u32,
+    attribute: PortAttribute,
+    group: Option<PortGroupAttribute>,
 }
 
 impl<'a> PortCollectionField<'a> {
     /// Create a `Self` instance from a field object.
-    fn from_input_field(input: &'a Field) -> Self {
+    ///
+    /// The index is the pinned index of the field, if it has one, or the given default index.
+    fn from_input_field(input: &'a Field, default_index: u32) -> Self {
+        let identifier = input.ident.as_ref().unwrap();
+        let attribute = PortAttribute::from_input_field(input);
+        let group = PortGroupAttribute::from_input_field(input);
+        if attribute.designation.is_some() && group.is_some() {
+            panic!(
+                "Port `{}` is a member of a group and therefore can't have a designation",
+                identifier
+            );
+        }
         PortCollectionField {
-            identifier: input.ident.as_ref().unwrap(),
+            identifier,
             port_type: &input.ty,
+            index: attribute.index.unwrap_or(default_index),
+            attribute,
+            group,
+        }
+    }
+
+    /// Create the index constant of the port.
+    fn make_index_constant(&self) -> impl ::quote::ToTokens {
+        let constant = Ident::new(
+            &format!("PORT_INDEX_{}", self.identifier.to_string().to_uppercase()),
+            Span::call_site(),
+        );
+        let index = self.index;
+        let doc = format!("The index of the `{}` port.", self.identifier);
+        quote! {
+            #[doc = #doc]
+            pub const #constant: u32 = #index;
+        }
+    }
+
+    /// Create the name of the port's variant in the index enum, which is the camel-cased identifier.
+    fn make_variant_name(&self) -> Ident {
+        let name: String = self
+            .identifier
+            .to_string()
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                let first = chars.next().unwrap().to_uppercase();
+                first.chain(chars).collect::<String>()
+            })
+            .collect();
+        Ident::new(&name, Span::call_site())
+    }
+
+    /// Create the variant of the port in the index enum.
+    fn make_index_variant(&self) -> impl ::quote::ToTokens {
+        let variant = self.make_variant_name();
+        let index = self.index;
+        let doc = format!("The `{}` port.", self.identifier);
+        quote! {
+            #[doc = #doc]
+            #variant = #index,
+        }
+    }
+
+    /// Create the matching arm to convert an index to its variant.
+    fn make_index_matcher(&self, index_name: &Ident) -> impl ::quote::ToTokens {
+        let variant = self.make_variant_name();
+        let index = self.index;
+        quote! {
+            #index => Ok(#index_name::#variant),
         }
     }
 
@@ -32,7 +263,7 @@ impl<'a> PortCollectionField<'a> {
     fn make_raw_field_declaration(&self) -> impl ::quote::ToTokens {
         let identifier = self.identifier;
         quote! {
-            pub #identifier: *mut ::std::ffi::c_void,
+            pub #identifier: *mut __derive::c_void,
         }
     }
 
@@ -40,13 +271,72 @@ impl<'a> PortCollectionField<'a> {
     fn make_raw_field_initialization(&self) -> impl ::quote::ToTokens {
         let identifier = self.identifier;
         quote! {
-            #identifier: ::std::ptr::null_mut(),
+            #identifier: __derive::null_mut(),
+        }
+    }
+
+    /// Create the descriptor of the port.
+    ///
+    /// The kind of the port's group and the group it is a side-chain of may be declared by any port of the group and are therefore passed separately.
+    fn make_port_descriptor(
+        &self,
+        group_kind: Option<&Ident>,
+        side_chain_of: Option<&String>,
+    ) -> impl ::quote::ToTokens {
+        let identifier = self.identifier;
+        let port_type = self.port_type;
+        let index = self.index;
+        let group = self.group.as_ref().map(|group| {
+            let symbol = &group.group;
+            let kind = group_kind
+                .unwrap_or_else(|| panic!("The kind of port group `{}` is not declared", symbol));
+            let channel = &group.channel;
+            let side_chain_of = match side_chain_of {
+                Some(side_chain_of) => quote! {Some(#side_chain_of)},
+                None => quote! {None},
+            };
+            quote! {
+                .with_group(PortGroupInfo {
+                    symbol: #symbol,
+                    kind: PortGroupKind::#kind,
+                    channel: PortChannel::#channel,
+                    side_chain_of: #side_chain_of,
+                })
+            }
+        });
+        let designation = self.attribute.designation.as_ref().map(|designation| {
+            quote! {.with_designation(PortDesignation::#designation)}
+        });
+        let side_chain = if self.attribute.side_chain {
+            quote! {.with_side_chain()}
+        } else {
+            quote! {}
+        };
+        quote! {
+            PortDescriptor::new::<#port_type>(#index, stringify!(#identifier))#group #designation #side_chain,
+        }
+    }
+
+    /// Create the visitor call of the port, if it's a member of the group.
+    fn make_group_visit(
+        &self,
+        group: &str,
+        reference: impl ::quote::ToTokens,
+    ) -> impl ::quote::ToTokens {
+        let identifier = self.identifier;
+        match self.group.as_ref() {
+            Some(attribute) if attribute.group == group => {
+                let channel = &attribute.channel;
+                quote! {visitor(PortChannel::#channel, #reference self.#identifier);}
+            }
+            _ => quote! {},
         }
     }
 
     /// Create the connection matching arm for the raw pointer struct.
-    fn make_connect_matcher(&self, index: u32) -> impl ::quote::ToTokens {
+    fn make_connect_matcher(&self) -> impl ::quote::ToTokens {
         let identifier = self.identifier;
+        let index = self.index;
         quote! {
             #index => self.#identifier = pointer,
         }
@@ -59,6 +349,7 @@ impl<'a> PortCollectionField<'a> {
 /// the raw pointers for the port. Then, the ports object is created from the raw version.
 struct PortCollectionStruct<'a> {
     struct_name: &'a Ident,
+    visibility: &'a Visibility,
     fields: Vec<PortCollectionField<'a>>,
 }
 
@@ -74,22 +365,163 @@ impl<'a> PortCollectionStruct<'a> {
         )
     }
 
+    /// Return an `Ident` for the index enum.
+    fn index_name(&self) -> Ident {
+        Ident::new(&format!("{}Index", self.struct_name), Span::call_site())
+    }
+
     /// Construct a `Self` instance from a `DeriveInput`.
+    ///
+    /// Fields without a pinned index get the index after the one of the previous field, like the discriminants of an enum. The fields are sorted by their indices, which have to be unique and contiguous.
     fn from_derive_input(input: &'a DeriveInput) -> Self {
         let struct_name = &input.ident;
-        let fields = match &input.data {
+        let mut fields: Vec<PortCollectionField> = match &input.data {
             Data::Enum(_) | Data::Union(_) => panic!("Only structs can implement PortCollection"),
-            Data::Struct(DataStruct { fields, .. }) => fields
-                .iter()
-                .map(PortCollectionField::from_input_field)
-                .collect(),
+            Data::Struct(DataStruct { fields, .. }) => {
+                let mut next_index = 0;
+                fields
+                    .iter()
+                    .map(|field| {
+                        let field = PortCollectionField::from_input_field(field, next_index);
+                        next_index = field.index + 1;
+                        field
+                    })
+                    .collect()
+            }
         };
+
+        fields.sort_by_key(|field| field.index);
+        for (expected, field) in fields.iter().enumerate() {
+            if field.index < expected as u32 {
+                panic!(
+                    "Port `{}` has the same index as another port: {}",
+                    field.identifier, field.index
+                );
+            }
+            if field.index > expected as u32 {
+                panic!(
+                    "Port indices have to be contiguous, but {} is missing",
+                    expected
+                );
+            }
+        }
+
         PortCollectionStruct {
             struct_name,
+            visibility: &input.vis,
             fields,
         }
     }
 
+    /// The symbols of all port groups, in the order of their first port.
+    fn groups(&self) -> Vec<&str> {
+        let mut groups: Vec<&str> = Vec::new();
+        for group in self.fields.iter().filter_map(|field| field.group.as_ref()) {
+            if !groups.contains(&group.group.as_str()) {
+                groups.push(&group.group);
+            }
+        }
+        groups
+    }
+
+    /// Return the kind of a group, as declared by one of its ports.
+    fn group_kind(&self, group: &str) -> Option<&Ident> {
+        let mut kinds = self
+            .fields
+            .iter()
+            .filter_map(|field| field.group.as_ref())
+            .filter(|attribute| attribute.group == group)
+            .filter_map(|attribute| attribute.kind.as_ref());
+        let kind = kinds.next()?;
+        if kinds.any(|other| other != kind) {
+            panic!("The ports of group `{}` declare different kinds", group);
+        }
+        Some(kind)
+    }
+
+    /// Return the group a group is a side-chain of, as declared by one of its ports.
+    fn group_side_chain_of(&self, group: &str) -> Option<&String> {
+        self.fields
+            .iter()
+            .filter_map(|field| field.group.as_ref())
+            .filter(|attribute| attribute.group == group)
+            .find_map(|attribute| attribute.side_chain_of.as_ref())
+    }
+
+    /// Implement the freewheeling check, if the struct has a port with the `FreeWheeling` designation.
+    fn make_freewheeling_check(&self) -> impl ::quote::ToTokens {
+        let mut ports = self.fields.iter().filter(|field| {
+            field
+                .attribute
+                .designation
+                .as_ref()
+                .map(|designation| designation == "FreeWheeling")
+                .unwrap_or(false)
+        });
+        let port = match ports.next() {
+            Some(port) => port,
+            None => return quote! {},
+        };
+        if ports.next().is_some() {
+            panic!("Only one port can have the `FreeWheeling` designation");
+        }
+        let identifier = port.identifier;
+        quote! {
+            fn is_freewheeling(&self) -> bool {
+                ToggledPort::is_on(&self.#identifier)
+            }
+        }
+    }
+
+    /// Implement silencing of all ports.
+    fn make_silence(&self) -> impl ::quote::ToTokens {
+        let identifiers = self.fields.iter().map(|field| field.identifier);
+        quote! {
+            fn silence(&mut self) {
+                #(__derive::PortHandle::silence(&mut self.#identifiers);)*
+            }
+        }
+    }
+
+    /// Implement the group visiting methods, if the struct has any port groups.
+    fn make_group_visitors(&self) -> impl ::quote::ToTokens {
+        let groups = self.groups();
+        if groups.is_empty() {
+            return quote! {};
+        }
+
+        let visits = groups.iter().map(|group| {
+            let visits = self
+                .fields
+                .iter()
+                .map(|field| field.make_group_visit(group, quote! {&}));
+            quote! {#group => {#(#visits)*}}
+        });
+        let mut_visits = groups.iter().map(|group| {
+            let visits = self
+                .fields
+                .iter()
+                .map(|field| field.make_group_visit(group, quote! {&mut}));
+            quote! {#group => {#(#visits)*}}
+        });
+
+        quote! {
+            fn visit_group(&self, group: &str, visitor: &mut dyn FnMut(PortChannel, &dyn __derive::Any)) {
+                match group {
+                    #(#visits)*
+                    _ => (),
+                }
+            }
+
+            fn visit_group_mut(&mut self, group: &str, visitor: &mut dyn FnMut(PortChannel, &mut dyn __derive::Any)) {
+                match group {
+                    #(#mut_visits)*
+                    _ => (),
+                }
+            }
+        }
+    }
+
     /// Implement `PortCollection` for the struct.
     fn make_derived_contents(&self) -> TokenStream {
         let struct_name = self.struct_name;
@@ -110,10 +542,79 @@ impl<'a> PortCollectionStruct<'a> {
         let connect_matchers = self
             .fields
             .iter()
-            .enumerate()
-            .map(|(i, f)| f.make_connect_matcher(i as u32));
+            .map(PortCollectionField::make_connect_matcher);
+        let port_descriptors = self.fields.iter().map(|f| {
+            let group = f.group.as_ref();
+            f.make_port_descriptor(
+                group.and_then(|group| self.group_kind(&group.group)),
+                group.and_then(|group| self.group_side_chain_of(&group.group)),
+            )
+        });
+        let group_visitors = self.make_group_visitors();
+        let freewheeling_check = self.make_freewheeling_check();
+        let silence = self.make_silence();
+        let index_constants = self
+            .fields
+            .iter()
+            .map(PortCollectionField::make_index_constant);
+        let visibility = self.visibility;
+        let index_name = self.index_name();
+        let index_doc = format!(
+            "The indices of the ports in [`{}`](struct.{}.html).",
+            struct_name, struct_name
+        );
+        let index_variants = self
+            .fields
+            .iter()
+            .map(PortCollectionField::make_index_variant);
+        let index_matchers = self
+            .fields
+            .iter()
+            .map(|field| field.make_index_matcher(&index_name));
+
+        // A `repr(u32)` enum needs at least one variant.
+        let index_enum = if self.fields.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                #[doc = #index_doc]
+                #[allow(dead_code)]
+                #[repr(u32)]
+                #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+                #visibility enum #index_name {
+                    #(#index_variants)*
+                }
+
+                impl From<#index_name> for u32 {
+                    #[inline]
+                    fn from(index: #index_name) -> u32 {
+                        index as u32
+                    }
+                }
+
+                impl __derive::TryFrom<u32> for #index_name {
+                    type Error = u32;
+
+                    #[inline]
+                    fn try_from(index: u32) -> Result<Self, u32> {
+                        match index {
+                            #(#index_matchers)*
+                            _ => Err(index),
+                        }
+                    }
+                }
+            }
+        };
 
         (quote! {
+            #[allow(dead_code)]
+            impl #struct_name {
+                #(#index_constants)*
+            }
+
+            #index_enum
+
+
             impl PortCollection for #struct_name {
                 type Cache = #internal_cache_name;
 
@@ -125,6 +626,14 @@ impl<'a> PortCollectionStruct<'a> {
                         }
                     )
                 }
+
+                const PORTS: &'static [PortDescriptor] = &[#(#port_descriptors)*];
+
+                #group_visitors
+
+                #freewheeling_check
+
+                #silence
             }
 
             #[doc(hidden)]
@@ -143,7 +652,7 @@ impl<'a> PortCollectionStruct<'a> {
             }
 
             impl PortPointerCache for #internal_cache_name {
-                fn connect(&mut self, index: u32, pointer: *mut ::std::ffi::c_void) {
+                fn connect(&mut self, index: u32, pointer: *mut __derive::c_void) {
                     match index {
                         #(#connect_matchers)*
                         _ => ()
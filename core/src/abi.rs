@@ -0,0 +1,89 @@
+//! The binary contract between plugin libraries and hosts that are built with rust-lv2.
+//!
+//! Hosts drive plugins through the C interfaces of LV2, which never change. A few parts of rust-lv2 go beyond these interfaces, and a host may load plugin libraries that were built with other rust-lv2 releases than itself, which is why these parts follow a contract with a layout that is stable across releases:
+//!
+//! * Every library that exports its plugins with [`lv2_descriptors!`](../plugin/macro.lv2_descriptors.html) also exports the function `rust_lv2_abi`, which returns a pointer to the library's [`AbiInfo`](struct.AbiInfo.html). A host looks it up next to `lv2_descriptor` and checks it with [`AbiInfo::from_function`](struct.AbiInfo.html#method.from_function). Libraries that were built before the contract existed don't export the function and are driven as plain LV2 plugins.
+//! * The static of the [`lv2_library`](../plugin/attr.lv2_library.html) attribute is a null-terminated string with the name and the version of the library.
+//! * Every request of the worker of `lv2-worker` starts with a header of a 32-bit magic value and an 8-bit priority, which hosts read with `Priority::of_request`.
+//!
+//! The layouts of these items are checked with static assertions, which means that a change that breaks the contract doesn't compile. Fields may be appended to `AbiInfo`, whose size is stored in the struct itself, but everything else requires a new [`ABI_VERSION`](constant.ABI_VERSION.html). A host only relies on the contract if the version of the library matches its own.
+use core::ffi::{c_char, CStr};
+use core::mem::{offset_of, size_of};
+
+/// The version of the contract, which is only incremented by incompatible changes.
+pub const ABI_VERSION: u32 = 1;
+
+/// The name of the function that returns the contract of a library, as a null-terminated string.
+pub const ABI_SYMBOL: &[u8] = b"rust_lv2_abi\0";
+
+/// The type of the `rust_lv2_abi` function, which is exported by every library that uses `lv2_descriptors!`.
+pub type AbiFunction = unsafe extern "C" fn() -> *const AbiInfo;
+
+/// The contract of a plugin library, as it's returned by its `rust_lv2_abi` function.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AbiInfo {
+    /// The size of the struct in the library, which grows if fields are appended.
+    pub size: u32,
+    /// The version of the contract the library follows.
+    pub version: u32,
+    /// The version of `lv2-core` the library was built with, as a null-terminated string.
+    pub core_version: *const c_char,
+}
+
+// The struct is only created as an immutable static, with a pointer to a string literal.
+unsafe impl Sync for AbiInfo {}
+
+/// The size of the first version of `AbiInfo`, which every library provides.
+const MINIMAL_SIZE: usize = offset_of!(AbiInfo, core_version) + size_of::<*const c_char>();
+
+const _: () = {
+    assert!(offset_of!(AbiInfo, size) == 0);
+    assert!(offset_of!(AbiInfo, version) == 4);
+    assert!(offset_of!(AbiInfo, core_version) == 8);
+    assert!(size_of::<AbiInfo>() >= MINIMAL_SIZE);
+    // The descriptor is defined by LV2, but the generated statics rely on it being a URI and seven function pointers.
+    assert!(size_of::<crate::sys::LV2_Descriptor>() == 8 * size_of::<*const c_char>());
+    assert!(
+        offset_of!(crate::sys::LV2_Descriptor, extension_data) == 7 * size_of::<*const c_char>()
+    );
+};
+
+/// The contract of the library this crate is compiled into, which is returned by the generated `rust_lv2_abi` function.
+#[doc(hidden)]
+pub static ABI_INFO: AbiInfo = AbiInfo {
+    size: size_of::<AbiInfo>() as u32,
+    version: ABI_VERSION,
+    core_version: concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char,
+};
+
+impl AbiInfo {
+    /// Retrieve the contract of a library from its `rust_lv2_abi` function.
+    ///
+    /// `None` is returned if the library follows another version of the contract, which means that the host can only use the LV2 interfaces of its plugins.
+    ///
+    /// # Safety
+    ///
+    /// The function has to be the `rust_lv2_abi` function of a library that is loaded for as long as the contract is used.
+    pub unsafe fn from_function(function: AbiFunction) -> Option<&'static Self> {
+        let info = function().as_ref()?;
+        if info.version == ABI_VERSION && info.size as usize >= MINIMAL_SIZE {
+            Some(info)
+        } else {
+            None
+        }
+    }
+
+    /// The contract of the library this crate is compiled into.
+    pub fn current() -> &'static Self {
+        &ABI_INFO
+    }
+
+    /// The version of `lv2-core` the library was built with.
+    pub fn core_version(&self) -> Option<&str> {
+        if self.core_version.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(self.core_version) }.to_str().ok()
+    }
+}
@@ -1,9 +1,13 @@
 //! Means to extend the interface of a plugin.
 //!
-//! This module is relatively thin: It only contains a trait and a macro. Instead, most of the extension handling is a convention:
+//! This module is relatively thin: It only contains a trait, a macro and a few helpers for extension functions. Instead, most of the extension handling is a convention:
 //!
 //! An extension is a trait a plugin can implement and every extension has a descriptor: This is a marker struct that implements the [`ExtensionDescriptor`](trait.ExtensionDescriptor.html) trait for every plugin that implements the extension. This descriptor is then used by the [`match_extensions`](../macro.match_extensions.html) macro to generate the body of a plugin's `extension_data` method.
 //!
+//! Instead of implementing `extension_data` with `match_extensions`, the [`extensions`](../plugin/attr.extensions.html) attribute can be used on the `Plugin` implementation. It takes the list of extension descriptors, like `#[extensions(MyExtensionDescriptor)]`, and generates `extension_data` as well as the table of extension URIs. Since the descriptors are instantiated for the plugin automatically, a missing type parameter can't slip through.
+//!
+//! The functions of an extension interface are called by the host with the handle of the plugin instance. The [`ExtensionCall`](struct.ExtensionCall.html) builder turns this handle back into the plugin and the host features of the function's threading class, and [`resolve_features`](fn.resolve_features.html) populates a feature collection from the features that some extension functions receive. With these building blocks, vendor-specific extensions can be implemented without repeating the unsafe code of the extensions in this framework.
+//!
 //! # Example
 //!
 //! This is a complete example on how to create an extension and implement it for a plugin:
@@ -79,7 +83,7 @@
 //!         Some(Self { internal: 0 })
 //!     }
 //!
-//!     fn run(&mut self, _: &mut (), _: &mut ()) {
+//!     fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {
 //!         self.internal += 1;
 //!     }
 //!
@@ -118,8 +122,10 @@
 //!
 //! assert_eq!(42, plugin.internal);
 //! ```
-use std::any::Any;
-use urid::UriBound;
+use crate::feature::{FeatureCache, FeatureCollection, FeatureError, ThreadingClass};
+use crate::plugin::{Plugin, PluginInstance};
+use core::any::Any;
+use urid::{Uri, UriBound};
 
 /// A descriptor for a plugin extension.
 ///
@@ -132,12 +138,115 @@ pub trait ExtensionDescriptor: UriBound {
     const INTERFACE: &'static Self::ExtensionInterface;
 }
 
+/// The raw interface of an extension, as it's returned by the `extension_data` function of a plugin.
+///
+/// This is the host-side counterpart of [`ExtensionDescriptor`](trait.ExtensionDescriptor.html): Hosts don't know the plugin type a descriptor is generic over, but they know the interface they want to use. This trait associates the interface with the URI of its extension, so that hosts can query typed interfaces, for example with `Instance::extension` of `lv2-host`. It's implemented for the interfaces of the Worker, State and Options extensions, and the interfaces of other extensions can implement it too.
+///
+/// # Safety
+///
+/// `URI` has to be the null-terminated URI of the extension, and plugins that provide the extension have to return a pointer to a `Self` from `extension_data` when they're asked for this URI.
+pub unsafe trait ExtensionInterface: 'static {
+    /// The URI of the extension.
+    const URI: &'static [u8];
+
+    /// The URI of the extension, as a `Uri`.
+    fn uri() -> &'static Uri {
+        unsafe { Uri::from_bytes_with_nul_unchecked(Self::URI) }
+    }
+}
+
+unsafe impl ExtensionInterface for sys::LV2_Worker_Interface {
+    const URI: &'static [u8] = sys::LV2_WORKER__interface;
+}
+
+unsafe impl ExtensionInterface for sys::LV2_State_Interface {
+    const URI: &'static [u8] = sys::LV2_STATE__interface;
+}
+
+unsafe impl ExtensionInterface for sys::LV2_Options_Interface {
+    const URI: &'static [u8] = sys::LV2_OPTIONS__interface;
+}
+
+/// A call of an extension function into a plugin instance.
+///
+/// The functions of an extension interface receive the handle of the plugin instance, which has to be cast back to the instance and split into the plugin and the host features of the threading class the function belongs to. This builder does this in one place, so that an extension only needs a single `unsafe` block per function:
+///
+/// ```
+/// # use lv2_core::extension::ExtensionCall;
+/// # use lv2_core::prelude::*;
+/// # use std::ffi::c_void;
+/// pub trait Counter: Plugin {
+///     fn count(&mut self) -> u32;
+/// }
+///
+/// unsafe extern "C" fn extern_count<P: Counter>(handle: *mut c_void) -> u32 {
+///     ExtensionCall::<P>::from_handle(handle)
+///         .audio_class(|plugin, _features| plugin.count())
+///         .unwrap_or(0)
+/// }
+/// ```
+///
+/// The threading class is chosen with the method that calls the plugin: [`instantiation_class`](#method.instantiation_class) provides the features of the Instantiation class, [`audio_class`](#method.audio_class) provides the features of the Audio class and [`other_class`](#method.other_class) provides no features at all, since the plugin's features must not be used outside of their class. If the handle is null, the plugin isn't called and `None` is returned.
+pub struct ExtensionCall<P: Plugin> {
+    handle: *mut PluginInstance<P>,
+}
+
+impl<P: Plugin> ExtensionCall<P> {
+    /// Prepare a call with the handle that the host passed to an extension function.
+    ///
+    /// # Safety
+    ///
+    /// The handle has to be null or a handle that was created by [`PluginInstance::instantiate`](../plugin/struct.PluginInstance.html#method.instantiate) for the plugin type `P`. The host must not use the instance in another thread while the call is running, which the threading rules of LV2 guarantee as long as the extension function is called in the threading class that is used for the call.
+    pub unsafe fn from_handle(handle: *mut core::ffi::c_void) -> Self {
+        Self {
+            handle: handle as *mut PluginInstance<P>,
+        }
+    }
+
+    /// Call a method of the Instantiation threading class, with the features of that class.
+    pub fn instantiation_class<R>(
+        self,
+        f: impl FnOnce(&mut P, &mut P::InitFeatures) -> R,
+    ) -> Option<R> {
+        let instance = unsafe { self.handle.as_mut() }?;
+        let (plugin, features) = instance.init_class_handle();
+        Some(f(plugin, features))
+    }
+
+    /// Call a method of the Audio threading class, with the features of that class.
+    pub fn audio_class<R>(self, f: impl FnOnce(&mut P, &mut P::AudioFeatures) -> R) -> Option<R> {
+        let instance = unsafe { self.handle.as_mut() }?;
+        let (plugin, features) = instance.audio_class_handle();
+        Some(f(plugin, features))
+    }
+
+    /// Call a method that belongs to neither the Instantiation nor the Audio threading class.
+    pub fn other_class<R>(self, f: impl FnOnce(&mut P) -> R) -> Option<R> {
+        let instance = unsafe { self.handle.as_mut() }?;
+        Some(f(instance.plugin_handle()))
+    }
+}
+
+/// Resolve the features that the host passes to an extension function.
+///
+/// Some extension functions receive their own array of host features, like the `save` and `restore` functions of the state extension. This function populates a feature collection from such an array, just like the features of the plugin are populated.
+///
+/// # Safety
+///
+/// The features have to be null or a null-terminated array of valid features, which live at least as long as the collection.
+pub unsafe fn resolve_features<'a, F: FeatureCollection<'a>>(
+    features: *const *const crate::sys::LV2_Feature,
+    class: ThreadingClass,
+) -> Result<F, FeatureError> {
+    F::from_cache(&mut FeatureCache::from_raw(features), class)
+}
+
 /// Generate the body of a plugin's `extension_data` function.
 ///
 /// This macro takes a URI as it's first argument, followed by a list of extension descriptors. This will
 /// create a match expression that matches the given URI with the URIs of the extension descriptors. If one of the extension URIs matches, the statement returns the interface of the descriptor.
 ///
-/// The generated statement returns a value of `Option<&'static dyn std::any::Any>`.
+/// The generated statement returns a value of `Option<&'static dyn core::any::Any>`.
 ///
 /// See the documentation of the `extension` module for more information on how to use this macro.
 #[macro_export]
@@ -145,7 +254,7 @@ macro_rules! match_extensions {
     ($uri:expr, $($descriptor:ty),*) => {
         match ($uri).to_bytes_with_nul() {
             $(
-                <$descriptor as UriBound>::URI => Some(<$descriptor as ExtensionDescriptor>::INTERFACE as &'static dyn ::std::any::Any),
+                <$descriptor as UriBound>::URI => Some(<$descriptor as ExtensionDescriptor>::INTERFACE as &'static dyn $crate::__derive::Any),
             )*
             _ => None,
         }
@@ -1,28 +1,31 @@
 use crate::feature::*;
-use std::collections::{hash_map, HashMap};
-use std::ffi::{c_void, CStr};
-use std::iter::Map;
+use alloc::collections::{btree_map, BTreeMap};
+use core::ffi::{c_void, CStr};
+use core::iter::Map;
 
 /// Cache for host features, used in the feature discovery stage.
 ///
 /// At initialization time, a raw LV2 plugin receives a null-terminated array containing all requested host features. Obviously, this is not suited for safe Rust code and therefore, it needs an abstraction layer.
 ///
-/// Internally, this struct contains a hash map which is filled the raw LV2 feature descriptors. Using this map, methods are defined to identify and retrieve features.
+/// Internally, this struct contains a map which is filled the raw LV2 feature descriptors. Using this map, methods are defined to identify and retrieve features.
+///
+/// A cache may also be retained, for example by adding it to the `InitFeatures` of a plugin, to [lend](#method.lend) its features to collections of other threading classes later.
 #[derive(Clone)]
 pub struct FeatureCache<'a> {
-    internal: HashMap<&'a CStr, *const c_void>,
+    internal: BTreeMap<&'a CStr, *const c_void>,
+    lending: bool,
 }
 
 impl<'a> FeatureCache<'a> {
     /// Construct a cache from the raw features array.
     ///
-    /// It basically populates a hash map by walking through the array and then creates a `FeatureContainer` with it. However, this method is unsafe since it dereferences a C string to a URI. Also, this method should only be used with the features list supplied by the host since the soundness of the whole module depends on that assumption.
+    /// It basically populates a map by walking through the array and then creates a `FeatureContainer` with it. However, this method is unsafe since it dereferences a C string to a URI. Also, this method should only be used with the features list supplied by the host since the soundness of the whole module depends on that assumption.
     ///
     /// # Safety
     ///
     /// This method is unsafe since it needs to dereference the raw feature pointers.
     pub unsafe fn from_raw(raw: *const *const ::sys::LV2_Feature) -> Self {
-        let mut internal_map = HashMap::new();
+        let mut internal_map = BTreeMap::new();
         let mut feature_ptr = raw;
 
         if !raw.is_null() {
@@ -36,6 +39,7 @@ impl<'a> FeatureCache<'a> {
 
         Self {
             internal: internal_map,
+            lending: false,
         }
     }
 
@@ -46,27 +50,69 @@ impl<'a> FeatureCache<'a> {
 
     /// Try to retrieve a feature.
     ///
-    /// If the feature is not found, this method will return `None`. Since the resulting feature object may have mutable access to the raw data, it will be removed from the cache to avoid aliasing.
+    /// If the feature is not found, this method will return a [`FeatureError::Missing`](enum.FeatureError.html#variant.Missing). Since the resulting feature object may have mutable access to the raw data, it will be removed from the cache to avoid aliasing, unless the cache is [lending](#method.lend) its features.
     ///
     /// You also have to provide the threading class of the feature you want to retrieve.
     pub fn retrieve_feature<F: Feature, T: FromResolvedFeature<F>>(
         &mut self,
         class: ThreadingClass,
-    ) -> Result<T, MissingFeatureError> {
-        T::from_resolved_feature(
-            self.internal
-                .remove(F::uri())
-                .and_then(|ptr| unsafe { F::from_feature_ptr(ptr, class) }),
-        )
+    ) -> Result<T, FeatureError> {
+        let ptr = if self.lending {
+            self.internal.get(F::uri()).copied()
+        } else {
+            self.internal.remove(F::uri())
+        };
+        let feature = match ptr {
+            Some(ptr) => unsafe { F::from_feature_ptr(ptr, class) },
+            None => Err(FeatureError::Missing { uri: F::uri() }),
+        };
+        T::from_resolved_feature(feature)
+    }
+
+    /// Populate a collection for the given threading class without removing its features from the cache.
+    ///
+    /// This is how features are shared between threading classes: A plugin retains the cache of its instantiation and lends collections to other threading classes from it, like a view of features that are usable in the audio threading class.
+    ///
+    /// Every lent feature is created again with the given threading class, which lets the feature check that it's usable in this class. Since creating a feature may allocate memory, collections should be lent before they are needed in the audio threading class, for example when the plugin is activated, and not in the `run` method.
+    ///
+    /// # Safety
+    ///
+    /// All lent features point to the same data as the features that are retrieved from the cache in other ways. Some features assume that they have exclusive access to their data, for example because they store state in it or because their host functions must not be called concurrently. The caller has to assure that no such feature is used through more than one collection or thread at the same time. Features that only read their data, like URID maps, may be lent freely.
+    pub unsafe fn lend<C: FeatureCollection<'a>>(
+        &mut self,
+        class: ThreadingClass,
+    ) -> Result<C, FeatureError> {
+        let lending = core::mem::replace(&mut self.lending, true);
+        let collection = C::from_cache(self, class);
+        self.lending = lending;
+        collection
+    }
+
+    /// Describe a feature that is retrieved as a `T`.
+    ///
+    /// This is the counterpart of [`retrieve_feature`](#method.retrieve_feature) for the metadata of a feature collection.
+    pub fn feature_info<F: Feature, T: FromResolvedFeature<F>>() -> FeatureInfo {
+        FeatureInfo {
+            uri: F::uri(),
+            optional: T::OPTIONAL,
+        }
+    }
+
+    /// The threading classes of a feature that is retrieved as a `T`.
+    ///
+    /// This is used by the `FeatureCollection` derive to check the classes of features at compile time.
+    pub const fn threading_classes<F: Feature, T: FromResolvedFeature<F>>(
+    ) -> &'static [ThreadingClass] {
+        F::THREADING_CLASSES
     }
 }
 
-type HashMapIterator<'a> = hash_map::IntoIter<&'a CStr, *const c_void>;
+type MapIterator<'a> = btree_map::IntoIter<&'a CStr, *const c_void>;
 type DescriptorBuildFn<'a> = fn((&'a CStr, *const c_void)) -> FeatureDescriptor<'a>;
 
-impl<'a> std::iter::IntoIterator for FeatureCache<'a> {
+impl<'a> core::iter::IntoIterator for FeatureCache<'a> {
     type Item = FeatureDescriptor<'a>;
-    type IntoIter = Map<HashMapIterator<'a>, DescriptorBuildFn<'a>>;
+    type IntoIter = Map<MapIterator<'a>, DescriptorBuildFn<'a>>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.internal.into_iter().map(|element| {
@@ -78,12 +124,10 @@ impl<'a> std::iter::IntoIterator for FeatureCache<'a> {
 }
 
 impl<'a> FeatureCollection<'a> for FeatureCache<'a> {
-    fn from_cache(
-        cache: &mut FeatureCache<'a>,
-        _: ThreadingClass,
-    ) -> Result<Self, MissingFeatureError> {
+    fn from_cache(cache: &mut FeatureCache<'a>, _: ThreadingClass) -> Result<Self, FeatureError> {
         Ok(FeatureCache {
             internal: cache.internal.clone(),
+            lending: false,
         })
     }
 }
@@ -94,21 +138,31 @@ impl<'a> FeatureCollection<'a> for FeatureCache<'a> {
 ///
 /// For more information, see `FeatureCollection`.
 ///
-/// For now this only covers `&T` and `Option<&T>` (where T is a `Feature`), but this may be
-/// extended in the future.
+/// This covers the feature `F` itself, which is required, and `Option<F>`, which is optional: A collection with an `Option<F>` field can be created even if the host doesn't provide the feature, and the feature is described as an `lv2:optionalFeature` in the [feature infos](trait.FeatureCollection.html#method.features) of the collection.
 pub trait FromResolvedFeature<F: Feature>: Sized {
-    fn from_resolved_feature(feature: Option<F>) -> Result<Self, MissingFeatureError>;
+    /// Whether the type can be created if the feature is missing.
+    const OPTIONAL: bool = false;
+
+    fn from_resolved_feature(feature: Result<F, FeatureError>) -> Result<Self, FeatureError>;
 }
 
 impl<F: Feature> FromResolvedFeature<F> for F {
-    fn from_resolved_feature(feature: Option<F>) -> Result<Self, MissingFeatureError> {
-        feature.ok_or_else(|| MissingFeatureError { uri: F::uri() })
+    #[inline]
+    fn from_resolved_feature(feature: Result<F, FeatureError>) -> Result<Self, FeatureError> {
+        feature
     }
 }
 
+/// An optional feature is `None` if it's missing or its data is invalid. Requesting it in the wrong threading class is still an error, since it's a mistake of the plugin.
 impl<F: Feature> FromResolvedFeature<F> for Option<F> {
+    const OPTIONAL: bool = true;
+
     #[inline]
-    fn from_resolved_feature(feature: Option<F>) -> Result<Self, MissingFeatureError> {
-        Ok(feature)
+    fn from_resolved_feature(feature: Result<F, FeatureError>) -> Result<Self, FeatureError> {
+        match feature {
+            Ok(feature) => Ok(Some(feature)),
+            Err(FeatureError::WrongThreadingClass { .. }) => feature.map(Some),
+            Err(_) => Ok(None),
+        }
     }
 }
@@ -3,7 +3,7 @@
 //! This module is for internal organization only and is not meant to be exposed.
 
 use crate::feature::*;
-use std::ffi::c_void;
+use core::ffi::c_void;
 use urid::UriBound;
 
 /// Marker feature to signal that the plugin can run in a hard real-time environment.
@@ -14,8 +14,11 @@ unsafe impl UriBound for HardRTCapable {
 }
 
 unsafe impl Feature for HardRTCapable {
-    unsafe fn from_feature_ptr(_feature: *const c_void, _: ThreadingClass) -> Option<Self> {
-        Some(Self)
+    unsafe fn from_feature_ptr(
+        _feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        Ok(Self)
     }
 }
 
@@ -29,8 +32,11 @@ unsafe impl UriBound for InPlaceBroken {
 }
 
 unsafe impl Feature for InPlaceBroken {
-    unsafe fn from_feature_ptr(_feature: *const c_void, _: ThreadingClass) -> Option<Self> {
-        Some(Self)
+    unsafe fn from_feature_ptr(
+        _feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        Ok(Self)
     }
 }
 
@@ -42,7 +48,10 @@ unsafe impl UriBound for IsLive {
 }
 
 unsafe impl Feature for IsLive {
-    unsafe fn from_feature_ptr(_feature: *const c_void, _: ThreadingClass) -> Option<Self> {
-        Some(Self)
+    unsafe fn from_feature_ptr(
+        _feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        Ok(Self)
     }
 }
@@ -1,7 +1,7 @@
 //! This module is for internal organization only and is not meant to be exposed.
 
 use crate::feature::*;
-use std::ffi::{c_void, CStr};
+use core::ffi::{c_void, CStr};
 
 /// Descriptor of a single host feature.
 ///
@@ -34,6 +34,6 @@ impl<'a> FeatureDescriptor<'a> {
     ///
     /// If the feature construction fails, the descriptor will be returned again.
     pub fn into_feature<T: Feature>(self, class: ThreadingClass) -> Result<T, Self> {
-        unsafe { T::from_feature_ptr(self.data, class) }.ok_or(self)
+        unsafe { T::from_feature_ptr(self.data, class) }.map_err(|_| self)
     }
 }
@@ -0,0 +1,89 @@
+//! The name of a plugin instance, as shown to the user by the host.
+
+use crate::feature::*;
+use core::ffi::{c_char, c_void, CStr};
+use urid::UriBound;
+
+/// Fallback feature that tells the plugin the name of its instance.
+///
+/// Hosts show a name for every plugin instance, which is usually the name of the plugin, followed by a number, or a name the user has chosen. A plugin that knows this name can use it to tell its instances apart in log messages, or to disambiguate files it creates for its state.
+///
+/// Hosts pass this name as the `ui:windowTitle` option when they instantiate a plugin, which should be read with `OptionsList::instance_name` of the [`lv2-options`](https://crates.io/crates/lv2-options) crate. This feature only covers hosts that pass the name as a raw, null-terminated string instead, like the [`lv2-host`](https://crates.io/crates/lv2-host) crate does in addition to the option. The name is the one the instance had when it was created; Renaming an instance later isn't announced.
+///
+/// The feature should therefore only be requested as an optional one, and only be used if there is no option:
+///
+///     use lv2_core::plugin::*;
+///     use lv2_core::feature::*;
+///
+///     #[derive(FeatureCollection)]
+///     struct InitFeatures<'a> {
+///         name: Option<InstanceName<'a>>,
+///     }
+pub struct InstanceName<'a> {
+    name: &'a CStr,
+}
+
+unsafe impl<'a> UriBound for InstanceName<'a> {
+    const URI: &'static [u8] = b"urn:lv2_core:instanceName\0";
+}
+
+unsafe impl<'a> Feature for InstanceName<'a> {
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if feature.is_null() {
+            return Err(FeatureError::invalid_data::<Self>());
+        }
+        Ok(Self {
+            name: CStr::from_ptr(feature as *const c_char),
+        })
+    }
+}
+
+impl<'a> InstanceName<'a> {
+    /// The name as a string slice.
+    ///
+    /// If the host passed a name that isn't valid UTF-8, `None` is returned.
+    pub fn as_str(&self) -> Option<&'a str> {
+        self.name.to_str().ok()
+    }
+
+    /// The raw name, as it was passed by the host.
+    pub fn as_c_str(&self) -> &'a CStr {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::feature::*;
+    use core::ffi::c_void;
+    use core::ptr::null;
+
+    #[test]
+    fn test_instance_name() {
+        let name = b"Amp 2\0";
+        let feature = unsafe {
+            InstanceName::from_feature_ptr(name.as_ptr() as *const c_void, ThreadingClass::Audio)
+        }
+        .unwrap();
+        assert_eq!(feature.as_str(), Some("Amp 2"));
+        assert_eq!(feature.as_c_str().to_bytes(), b"Amp 2");
+
+        let invalid = b"Amp \xff\0";
+        let feature = unsafe {
+            InstanceName::from_feature_ptr(
+                invalid.as_ptr() as *const c_void,
+                ThreadingClass::Instantiation,
+            )
+        }
+        .unwrap();
+        assert_eq!(feature.as_str(), None);
+
+        assert!(matches!(
+            unsafe { InstanceName::from_feature_ptr(null(), ThreadingClass::Instantiation) },
+            Err(FeatureError::InvalidData { .. })
+        ));
+    }
+}
@@ -4,12 +4,17 @@ use urid::{Uri, UriBound};
 mod cache;
 mod core_features;
 mod descriptor;
+mod instance_name;
 
+#[doc(hidden)]
+pub use crate::__derive;
 pub use cache::FeatureCache;
 pub use core_features::*;
 pub use descriptor::FeatureDescriptor;
+pub use instance_name::InstanceName;
 
-use std::ffi::c_void;
+use alloc::vec::Vec;
+use core::ffi::c_void;
 
 /// All threading contexts of LV2 interface methods.
 ///
@@ -22,6 +27,30 @@ pub enum ThreadingClass {
     Other,
 }
 
+impl ThreadingClass {
+    /// All threading classes, which is the default set of classes a feature can be used in.
+    pub const ALL: &'static [ThreadingClass] = &[
+        ThreadingClass::Discovery,
+        ThreadingClass::Instantiation,
+        ThreadingClass::Audio,
+        ThreadingClass::Other,
+    ];
+
+    /// Check whether this class is one of the given classes.
+    ///
+    /// This method can be used in constant expressions, which lets feature collections check their features at compile time.
+    pub const fn is_in(self, classes: &[ThreadingClass]) -> bool {
+        let mut index = 0;
+        while index < classes.len() {
+            if classes[index] as u8 == self as u8 {
+                return true;
+            }
+            index += 1;
+        }
+        false
+    }
+}
+
 /// Trait to generalize the feature detection system.
 ///
 /// A host that only implements the core LV2 specification does not have much functionality. Instead, hosts can provide extra functionalities, called "host features" or short "features", which a make plugins more useful.
@@ -30,48 +59,117 @@ pub enum ThreadingClass {
 ///
 /// In Rust, most of this behaviour is done internally and instead of simply casting a pointer, a safe feature descriptor, which implements this trait, is constructed using the [`from_raw_data`](#tymethod.from_raw_data) method.
 ///
-/// Some host features may only be used in certain threading classes. This is guarded by Rust-LV2 by passing the threading class in which the plugin will be used to the feature, which then may take different actions.
+/// Some host features may only be used in certain threading classes. This is guarded by Rust-LV2 by passing the threading class in which the plugin will be used to the feature, which then may take different actions. Features also declare the classes they can be used in, which lets the [`FeatureCollection`](trait.FeatureCollection.html) derive reject them in collections of other classes at compile time.
 pub unsafe trait Feature: UriBound + Sized {
+    /// The threading classes the feature can be used in.
+    ///
+    /// By default, a feature can be used in all threading classes. [`from_feature_ptr`](#tymethod.from_feature_ptr) should return a [`FeatureError::WrongThreadingClass`](enum.FeatureError.html#variant.WrongThreadingClass) for all other classes.
+    const THREADING_CLASSES: &'static [ThreadingClass] = ThreadingClass::ALL;
+
     /// Create an instance of the featurer.
     ///
-    /// The feature pointer is provided by the host and points to the feature-specific data. If the data is invalid, for one reason or another, the method returns `None`.
+    /// The feature pointer is provided by the host and points to the feature-specific data. If the data is invalid, for one reason or another, the method returns a [`FeatureError::InvalidData`](enum.FeatureError.html#variant.InvalidData).
     ///
     /// # Implementing
     ///
     /// If nescessary, you should dereference it and store the reference inside the feature struct in order to use it.
     ///
-    /// You have to document in which threading classes your feature can be used and should panic if the threading class is not supported. When this happens when the plugin programmer has added your feature to the wrong feature collection, which is considered a programming error and therefore justifies the panic. If you don't panic in this case, the error is handled silently, which may make debugging harder.
+    /// You have to declare in which threading classes your feature can be used with [`THREADING_CLASSES`](#associatedconstant.THREADING_CLASSES) and return a [`FeatureError::WrongThreadingClass`](enum.FeatureError.html#variant.WrongThreadingClass) if the threading class is not supported. This happens when the plugin programmer has added your feature to the wrong feature collection, which is a programming error. The error is reported to the host, which then fails to instantiate the plugin, even if the feature is optional.
     ///
     /// You should always allow the [`Other`](enum.ThreadingClass.html#variant.Other) threading class in order to restrict your feature from use cases you might not know.
     ///
     /// # Safety
     ///
     /// This method is unsafe since it has to de-reference a pointer.
-    unsafe fn from_feature_ptr(feature: *const c_void, class: ThreadingClass) -> Option<Self>;
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError>;
 }
 
-/// An error created during feature resolution when a required feature is missing.
-#[derive(Copy, Clone, Debug)]
-pub struct MissingFeatureError {
-    pub(crate) uri: &'static Uri,
+/// An error created during feature resolution.
+///
+/// Every error names the URI of the offending feature.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FeatureError {
+    /// A required feature is missing.
+    Missing { uri: &'static Uri },
+    /// The host provided invalid data for a feature, like a null pointer.
+    InvalidData { uri: &'static Uri },
+    /// A feature was requested in a threading class it can't be used in.
+    ///
+    /// This means that the feature was added to the wrong feature collection, which is a programming error of the plugin.
+    WrongThreadingClass {
+        uri: &'static Uri,
+        class: ThreadingClass,
+    },
+}
+
+impl FeatureError {
+    /// Create an error for data of the feature `F` that is invalid.
+    pub fn invalid_data<F: Feature>() -> Self {
+        FeatureError::InvalidData { uri: F::uri() }
+    }
+
+    /// Create an error for the feature `F` that is requested in a threading class it can't be used in.
+    pub fn wrong_threading_class<F: Feature>(class: ThreadingClass) -> Self {
+        FeatureError::WrongThreadingClass {
+            uri: F::uri(),
+            class,
+        }
+    }
+
+    /// The URI of the offending feature.
+    pub fn uri(&self) -> &'static Uri {
+        match self {
+            FeatureError::Missing { uri }
+            | FeatureError::InvalidData { uri }
+            | FeatureError::WrongThreadingClass { uri, .. } => uri,
+        }
+    }
 }
 
-impl std::fmt::Display for MissingFeatureError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        let uri = self.uri.to_str().unwrap_or("[error while reading URI]");
-        write!(
-            f,
-            "Unable to instantiate plugin: missing required feature: {}",
-            uri
-        )
+impl core::fmt::Display for FeatureError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        let uri = self.uri().to_str().unwrap_or("[error while reading URI]");
+        match self {
+            FeatureError::Missing { .. } => write!(
+                f,
+                "Unable to instantiate plugin: missing required feature: {}",
+                uri
+            ),
+            FeatureError::InvalidData { .. } => write!(
+                f,
+                "Unable to instantiate plugin: invalid data for feature: {}",
+                uri
+            ),
+            FeatureError::WrongThreadingClass { class, .. } => write!(
+                f,
+                "Unable to instantiate plugin: feature {} isn't allowed in the {:?} threading class",
+                uri, class
+            ),
+        }
     }
 }
 
+/// Metadata of a feature in a feature collection.
+///
+/// This information is retrieved with [`FeatureCollection::features`](trait.FeatureCollection.html#method.features) and is used to generate the description of a plugin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeatureInfo {
+    /// The URI of the feature.
+    pub uri: &'static Uri,
+    /// Whether the collection can be created without the feature.
+    pub optional: bool,
+}
+
 /// Convenience trait for feature collections.
 ///
 /// The feature cache is only for temporary use; Once a feature is retrieved, it is removed from the cache. Therefore you need a way to properly store features.
 ///
-/// You can simply create a struct with features as it's fields and derive `FeatureCollection` for it. A procedural macro will then create a method that populates the struct from the cache, or returns `None` if one of the required features is not in the cache.
+/// You can simply create a struct with features as it's fields and derive `FeatureCollection` for it. A procedural macro will then create a method that populates the struct from the cache, or returns an error if one of the required features is not in the cache or can't be used in the threading class. Fields with an `Option` type are optional features: They are `None` if the host doesn't provide them, which lets the plugin decide at runtime how to work without them.
+///
+/// The threading class a collection is used in can be declared with the `feature_collection` attribute, like `#[feature_collection(class = "audio")]`. The classes are named like the variants of [`ThreadingClass`](enum.ThreadingClass.html), in lowercase. With the attribute, a feature that can't be used in the class, like a feature that is only usable in the audio threading class in the `InitFeatures` of a plugin, is a compile error instead of an error during instantiation.
 ///
 /// An example using the few built-in features:
 ///
@@ -83,20 +181,38 @@ impl std::fmt::Display for MissingFeatureError {
 ///         live: IsLive,
 ///         hardrt: Option<HardRTCapable>,
 ///     }
+///
+/// Plugins that need many features in both the instantiation and the audio threading class can declare both collections in one struct with the [`feature_collections`](../plugin/attr.feature_collections.html) attribute:
+///
+///     use lv2_core::plugin::*;
+///     use lv2_core::feature::*;
+///
+///     #[feature_collections(audio = AudioFeatures)]
+///     struct InitFeatures {
+///         hardrt: HardRTCapable,
+///         #[feature(class = "both")]
+///         live: Option<IsLive>,
+///     }
+///
+/// Features that are usable in several threading classes can also be shared by retaining a cache instead, for example by using a [`FeatureCache`](struct.FeatureCache.html) as the `InitFeatures` of a plugin, and [lending](struct.FeatureCache.html#method.lend) collections from it to other threading classes.
 pub trait FeatureCollection<'a>: Sized + 'a {
     /// Populate a collection with features from the cache for the given threading class.
     fn from_cache(
         cache: &mut FeatureCache<'a>,
         class: ThreadingClass,
-    ) -> Result<Self, MissingFeatureError>;
+    ) -> Result<Self, FeatureError>;
+
+    /// Describe all features of the collection.
+    ///
+    /// The derive macro returns an entry for every field of the struct. The default implementation returns an empty list.
+    fn features() -> Vec<FeatureInfo> {
+        Vec::new()
+    }
 }
 
 impl<'a> FeatureCollection<'a> for () {
     #[inline]
-    fn from_cache(
-        _cache: &mut FeatureCache,
-        _: ThreadingClass,
-    ) -> Result<Self, MissingFeatureError> {
+    fn from_cache(_cache: &mut FeatureCache, _: ThreadingClass) -> Result<Self, FeatureError> {
         Ok(())
     }
 }
@@ -106,9 +222,9 @@ impl<'a> FeatureCollection<'a> for () {
 mod tests {
     use crate::feature::FeatureCache;
     use crate::{feature::*, plugin::*};
-    use std::ffi::c_void;
-    use std::os::raw::c_char;
-    use std::pin::Pin;
+    use core::ffi::c_char;
+    use core::ffi::c_void;
+    use core::pin::Pin;
     use urid::UriBound;
 
     struct FeatureA<'a> {
@@ -124,10 +240,14 @@ mod tests {
     }
 
     unsafe impl<'a> Feature for FeatureA<'a> {
-        unsafe fn from_feature_ptr(feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        unsafe fn from_feature_ptr(
+            feature: *const c_void,
+            _: ThreadingClass,
+        ) -> Result<Self, FeatureError> {
             (feature as *const i32)
                 .as_ref()
                 .map(|number| Self { number })
+                .ok_or_else(FeatureError::invalid_data::<Self>)
         }
     }
 
@@ -136,20 +256,43 @@ mod tests {
     }
 
     unsafe impl<'a> Feature for FeatureB<'a> {
-        unsafe fn from_feature_ptr(feature: *const c_void, _: ThreadingClass) -> Option<Self> {
+        const THREADING_CLASSES: &'static [ThreadingClass] = &[
+            ThreadingClass::Instantiation,
+            ThreadingClass::Audio,
+            ThreadingClass::Other,
+        ];
+
+        unsafe fn from_feature_ptr(
+            feature: *const c_void,
+            class: ThreadingClass,
+        ) -> Result<Self, FeatureError> {
+            if !class.is_in(Self::THREADING_CLASSES) {
+                return Err(FeatureError::wrong_threading_class::<Self>(class));
+            }
             (feature as *const f32)
                 .as_ref()
                 .map(|number| Self { number })
+                .ok_or_else(FeatureError::invalid_data::<Self>)
         }
     }
 
     #[derive(FeatureCollection)]
+    #[feature_collection(class = "instantiation")]
     struct Collection<'a> {
         a: FeatureA<'a>,
         b: FeatureB<'a>,
         _c: crate::feature::IsLive,
     }
 
+    #[feature_collections(audio = SharedAudioCollection)]
+    struct SharedInitCollection<'a> {
+        a: FeatureA<'a>,
+        #[feature(class = "audio")]
+        b: FeatureB<'a>,
+        #[feature(class = "both")]
+        c: Option<crate::feature::IsLive>,
+    }
+
     struct FeatureTestSetting<'a> {
         pub data_a: Pin<Box<i32>>,
         pub feature_a_sys: Pin<Box<::sys::LV2_Feature>>,
@@ -175,14 +318,14 @@ mod tests {
 
             let feature_c_sys = Box::pin(::sys::LV2_Feature {
                 URI: crate::feature::IsLive::URI.as_ptr() as *const c_char,
-                data: std::ptr::null_mut(),
+                data: core::ptr::null_mut(),
             });
 
             let features_list: &[*const sys::LV2_Feature] = &[
                 feature_a_sys.as_ref().get_ref(),
                 feature_b_sys.as_ref().get_ref(),
                 feature_c_sys.as_ref().get_ref(),
-                std::ptr::null(),
+                core::ptr::null(),
             ];
 
             // Constructing the cache.
@@ -217,7 +360,7 @@ mod tests {
         let retrieved_feature_b: FeatureB = features_cache
             .retrieve_feature(ThreadingClass::Other)
             .unwrap();
-        assert!(retrieved_feature_b.number - *(setting.data_b) < std::f32::EPSILON);
+        assert!(retrieved_feature_b.number - *(setting.data_b) < f32::EPSILON);
     }
 
     #[test]
@@ -277,4 +420,93 @@ mod tests {
         assert_eq!(*cache.a.number, *setting.data_a);
         assert_eq!(*cache.b.number, *setting.data_b);
     }
+
+    #[test]
+    fn test_feature_lending() {
+        let setting = FeatureTestSetting::new();
+        let mut features_cache = setting.features_cache;
+
+        for _ in 0..2 {
+            // The test features only read their data.
+            let collection: Collection =
+                unsafe { features_cache.lend(ThreadingClass::Audio) }.unwrap();
+            assert_eq!(*collection.a.number, *setting.data_a);
+            assert_eq!(*collection.b.number, *setting.data_b);
+        }
+        assert!(features_cache.contains::<FeatureA>());
+        assert!(features_cache.contains::<FeatureB>());
+
+        // Lending doesn't change how features are retrieved afterwards.
+        let _: FeatureA = features_cache
+            .retrieve_feature(ThreadingClass::Other)
+            .unwrap();
+        assert!(!features_cache.contains::<FeatureA>());
+    }
+
+    #[test]
+    fn test_feature_error() {
+        let setting = FeatureTestSetting::new();
+        let mut features_cache = setting.features_cache.clone();
+        assert_eq!(
+            Collection::from_cache(&mut features_cache, ThreadingClass::Discovery).err(),
+            Some(FeatureError::WrongThreadingClass {
+                uri: FeatureB::uri(),
+                class: ThreadingClass::Discovery,
+            })
+        );
+
+        // Optional features are only `None` if they are missing.
+        let mut features_cache = setting.features_cache.clone();
+        let feature_b: Result<Option<FeatureB>, FeatureError> =
+            features_cache.retrieve_feature(ThreadingClass::Discovery);
+        assert!(feature_b.is_err());
+        let feature_b: Option<FeatureB> = features_cache
+            .retrieve_feature(ThreadingClass::Other)
+            .unwrap();
+        assert!(feature_b.is_none());
+        let feature_b: Result<FeatureB, FeatureError> =
+            features_cache.retrieve_feature(ThreadingClass::Other);
+        assert_eq!(
+            feature_b.err(),
+            Some(FeatureError::Missing {
+                uri: FeatureB::uri()
+            })
+        );
+    }
+
+    #[test]
+    fn test_threading_classes() {
+        assert!(ThreadingClass::Audio.is_in(ThreadingClass::ALL));
+        assert!(!ThreadingClass::Discovery.is_in(FeatureB::THREADING_CLASSES));
+        assert_eq!(
+            FeatureCache::threading_classes::<FeatureB, Option<FeatureB>>(),
+            FeatureB::THREADING_CLASSES
+        );
+        assert_eq!(IsLive::THREADING_CLASSES, ThreadingClass::ALL);
+    }
+
+    #[test]
+    fn test_shared_feature_collections() {
+        let setting = FeatureTestSetting::new();
+        let mut features_cache = setting.features_cache;
+
+        let infos = SharedInitCollection::features();
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[0].uri, FeatureA::uri());
+        assert_eq!(infos[1].uri, IsLive::uri());
+        assert!(infos[1].optional);
+        let infos = SharedAudioCollection::features();
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[0].uri, FeatureB::uri());
+        assert_eq!(infos[1].uri, IsLive::uri());
+
+        let init: SharedInitCollection =
+            unsafe { features_cache.lend(ThreadingClass::Instantiation) }.unwrap();
+        assert_eq!(*init.a.number, *setting.data_a);
+        assert!(init.c.is_some());
+        let audio: SharedAudioCollection =
+            unsafe { features_cache.lend(ThreadingClass::Audio) }.unwrap();
+        assert_eq!(*audio.b.number, *setting.data_b);
+        assert!(audio.c.is_some());
+    }
 }
@@ -43,7 +43,7 @@
 //!
 //!     // Process a chunk of audio. The audio ports are dereferenced to slices, which the plugin
 //!     // iterates over.
-//!     fn run(&mut self, ports: &mut Ports, _features: &mut ()) {
+//!     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
 //!         let coef = if *(ports.gain) > -90.0 {
 //!             10.0_f32.powf(*(ports.gain) * 0.05)
 //!         } else {
@@ -56,10 +56,51 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Features
+//!
+//! The crate is `no_std` if the default `std` feature is disabled, which makes it usable for plugins and minimal hosts on embedded platforms. It still needs the `alloc` crate, since plugin instances and the feature cache live on the heap. Without `std`, errors during instantiation aren't printed and [`PluginInfo::bundle_path`](plugin/struct.PluginInfo.html#method.bundle_path) returns a `&str` instead of a `&Path`.
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
 extern crate lv2_sys as sys;
 
+/// Report an error that prevents the instantiation of a plugin.
+///
+/// Errors are printed to the standard error output, or discarded without `std`.
+#[cfg(feature = "std")]
+macro_rules! report_error {
+    ($($arg:tt)*) => {
+        eprintln!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! report_error {
+    ($($arg:tt)*) => {
+        let _ = format_args!($($arg)*);
+    };
+}
+
+pub mod abi;
 pub mod extension;
 pub mod feature;
 pub mod plugin;
 pub mod port;
 pub mod prelude;
+
+/// Items used by the code that the derive macros generate.
+///
+/// The generated code can't rely on `std` or `core` since the first one may be missing and the second one may be shadowed by an alias of this crate.
+#[doc(hidden)]
+pub mod __derive {
+    pub use crate::abi::{AbiInfo, ABI_INFO};
+    pub use crate::extension::ExtensionDescriptor;
+    pub use crate::plugin::PluginVersion;
+    pub use crate::port::PortHandle;
+    pub use alloc::vec::Vec;
+    pub use core::any::Any;
+    pub use core::convert::TryFrom;
+    pub use core::ffi::{c_char, c_void};
+    pub use core::ptr::{null, null_mut};
+    pub use urid::{Uri, UriBound};
+}
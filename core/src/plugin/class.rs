@@ -0,0 +1,73 @@
+use urid::Uri;
+
+macro_rules! plugin_classes {
+    ($($(#[$meta:meta])* $class:ident => $uri:ident,)*) => {
+        /// The classes of the LV2 core specification, which categorize plugins.
+        ///
+        /// Every plugin is an `lv2:Plugin`, and it may declare more specific classes with the [`CLASSES`](trait.Plugin.html#associatedconstant.CLASSES) constant of its [`Plugin`](trait.Plugin.html) implementation. Hosts use these classes to sort plugins into categories, and tools that describe a plugin, like a Turtle generator, read them from this constant.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum PluginClass {
+            $($(#[$meta])* $class,)*
+        }
+
+        impl PluginClass {
+            /// All plugin classes, in alphabetical order.
+            pub const ALL: &'static [PluginClass] = &[$(PluginClass::$class,)*];
+
+            /// The URI of the class.
+            pub fn uri(self) -> &'static Uri {
+                let uri: &'static [u8] = match self {
+                    $(PluginClass::$class => sys::$uri,)*
+                };
+                unsafe { Uri::from_bytes_with_nul_unchecked(uri) }
+            }
+        }
+    };
+}
+
+plugin_classes! {
+    Allpass => LV2_CORE__AllpassPlugin,
+    Amplifier => LV2_CORE__AmplifierPlugin,
+    Analyser => LV2_CORE__AnalyserPlugin,
+    Bandpass => LV2_CORE__BandpassPlugin,
+    Chorus => LV2_CORE__ChorusPlugin,
+    Comb => LV2_CORE__CombPlugin,
+    Compressor => LV2_CORE__CompressorPlugin,
+    Constant => LV2_CORE__ConstantPlugin,
+    Converter => LV2_CORE__ConverterPlugin,
+    Delay => LV2_CORE__DelayPlugin,
+    Distortion => LV2_CORE__DistortionPlugin,
+    Dynamics => LV2_CORE__DynamicsPlugin,
+    EQ => LV2_CORE__EQPlugin,
+    Envelope => LV2_CORE__EnvelopePlugin,
+    Expander => LV2_CORE__ExpanderPlugin,
+    Filter => LV2_CORE__FilterPlugin,
+    Flanger => LV2_CORE__FlangerPlugin,
+    Function => LV2_CORE__FunctionPlugin,
+    Gate => LV2_CORE__GatePlugin,
+    Generator => LV2_CORE__GeneratorPlugin,
+    Highpass => LV2_CORE__HighpassPlugin,
+    Instrument => LV2_CORE__InstrumentPlugin,
+    Limiter => LV2_CORE__LimiterPlugin,
+    Lowpass => LV2_CORE__LowpassPlugin,
+    Mixer => LV2_CORE__MixerPlugin,
+    Modulator => LV2_CORE__ModulatorPlugin,
+    MultiEQ => LV2_CORE__MultiEQPlugin,
+    Oscillator => LV2_CORE__OscillatorPlugin,
+    ParaEQ => LV2_CORE__ParaEQPlugin,
+    Phaser => LV2_CORE__PhaserPlugin,
+    Pitch => LV2_CORE__PitchPlugin,
+    Reverb => LV2_CORE__ReverbPlugin,
+    Simulator => LV2_CORE__SimulatorPlugin,
+    Spatial => LV2_CORE__SpatialPlugin,
+    Spectral => LV2_CORE__SpectralPlugin,
+    Utility => LV2_CORE__UtilityPlugin,
+    Waveshaper => LV2_CORE__WaveshaperPlugin,
+}
+
+impl PluginClass {
+    /// Find the class with the given URI.
+    pub fn from_uri(uri: &Uri) -> Option<Self> {
+        Self::ALL.iter().copied().find(|class| class.uri() == uri)
+    }
+}
@@ -0,0 +1,142 @@
+use super::TransportState;
+
+/// Information about a processing cycle, which is passed to [`Plugin::run`](trait.Plugin.html#tymethod.run).
+///
+/// The context bundles everything the plugin may need in a cycle besides its ports: the number of frames to process, the features of the "Audio" threading class, whether the host is freewheeling and, if the host provides it, the state of its transport. New per-cycle information is added here, so that the signature of `run` doesn't have to change for it.
+pub struct RunContext<'a, F> {
+    sample_count: u32,
+    freewheeling: bool,
+    transport: Option<&'a TransportState>,
+    failure: Option<&'static str>,
+    features: &'a mut F,
+}
+
+impl<'a, F> RunContext<'a, F> {
+    /// Create the context of a cycle with the given number of frames.
+    ///
+    /// The host isn't freewheeling in this context and there's no transport state. Plugins don't create contexts themselves, but it's useful to call `run` in tests.
+    pub fn new(sample_count: u32, features: &'a mut F) -> Self {
+        Self {
+            sample_count,
+            freewheeling: false,
+            transport: None,
+            failure: None,
+            features,
+        }
+    }
+
+    /// Set whether the host is freewheeling.
+    pub fn with_freewheeling(mut self, freewheeling: bool) -> Self {
+        self.freewheeling = freewheeling;
+        self
+    }
+
+    /// Set the state of the host's transport at the start of the cycle.
+    pub fn with_transport(mut self, transport: Option<&'a TransportState>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Return the number of frames to process in this cycle.
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Check whether the cycle has no frames to process.
+    ///
+    /// Hosts may run plugins with a sample count of zero to deliver events or control changes without processing audio. Audio ports are empty slices in such a cycle, but events still have to be handled and output controls still have to be written.
+    pub fn is_empty(&self) -> bool {
+        self.sample_count == 0
+    }
+
+    /// Check whether the host is freewheeling.
+    ///
+    /// See [`PortCollection::is_freewheeling`](../port/trait.PortCollection.html#method.is_freewheeling) for details.
+    pub fn is_freewheeling(&self) -> bool {
+        self.freewheeling
+    }
+
+    /// Return the state of the host's transport at the start of the cycle, or `None` if the host doesn't provide it.
+    ///
+    /// Only in-process hosts can pass the transport this way, by [setting it](struct.PluginInstance.html#method.set_transport) on the instance. Hosts that load the plugin via the C interface send `time:Position` objects to an atom port instead, which can be read with `lv2-time`.
+    pub fn transport(&self) -> Option<&TransportState> {
+        self.transport
+    }
+
+    /// Mark the cycle as failed, for the given reason.
+    ///
+    /// This is the alternative to panicking for errors that make the output of a cycle unusable, but don't leave the plugin in an undefined state, like a buffer that's too small or a port that's connected to invalid data. Panics unwind through the C interface, which is undefined behaviour, or abort the host if the plugin is compiled with `panic = "abort"`. A failed cycle instead ends normally: After `run` has returned, the framework [silences](../port/trait.PortCollection.html#method.silence) the outputs and calls [`Plugin::cycle_failed`](trait.Plugin.html#method.cycle_failed), which may log the reason or notify the UI. The reason of the last failed cycle is also kept by the [instance](struct.PluginInstance.html#method.last_failure) for in-process hosts.
+    ///
+    /// The plugin is expected to return from `run` right after it failed, which is what the [`fail_cycle`](../macro.fail_cycle.html) macro does. If the cycle fails more than once, the first reason is kept.
+    pub fn fail(&mut self, reason: &'static str) {
+        if self.failure.is_none() {
+            self.failure = Some(reason);
+        }
+    }
+
+    /// Return the reason the cycle failed for, or `None` if it hasn't failed.
+    pub fn failure(&self) -> Option<&'static str> {
+        self.failure
+    }
+
+    /// Return the features of the "Audio" threading class.
+    pub fn features(&self) -> &F {
+        self.features
+    }
+
+    /// Return the features of the "Audio" threading class, with mutable access.
+    pub fn features_mut(&mut self) -> &mut F {
+        self.features
+    }
+}
+
+/// [Fail](plugin/struct.RunContext.html#method.fail) the current cycle and return from `run`.
+///
+/// The first argument is the [`RunContext`](plugin/struct.RunContext.html) of the cycle and the second one is the reason. If the reason is a string literal, it's prefixed with the file and line of the macro call, which doesn't cost anything at run time.
+///
+/// ```
+/// use lv2_core::prelude::*;
+/// use urid::*;
+///
+/// #[derive(PortCollection)]
+/// struct Ports {
+///     input: InputPort<Audio>,
+///     output: OutputPort<Audio>,
+/// }
+///
+/// #[uri("urn:lv2_core:test:delay")]
+/// struct Delay {
+///     buffer: Vec<f32>,
+/// }
+///
+/// impl Plugin for Delay {
+///     type Ports = Ports;
+///     type InitFeatures = ();
+///     type AudioFeatures = ();
+///
+///     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+///         Some(Self { buffer: vec![0.0; 64] })
+///     }
+///
+///     fn run(&mut self, ports: &mut Ports, context: &mut RunContext<()>) {
+///         if context.sample_count() as usize > self.buffer.len() {
+///             // The outputs are silenced by the framework.
+///             lv2_core::fail_cycle!(context, "the cycle is longer than the buffer");
+///         }
+///         let frames = ports.input.len();
+///         ports.output.copy_from_slice(&self.buffer[..frames]);
+///         self.buffer[..frames].copy_from_slice(&ports.input);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! fail_cycle {
+    ($context:expr, $reason:literal) => {{
+        $context.fail(concat!(file!(), ":", line!(), ": ", $reason));
+        return;
+    }};
+    ($context:expr, $reason:expr) => {{
+        $context.fail($reason);
+        return;
+    }};
+}
@@ -0,0 +1,208 @@
+use super::{Plugin, PluginInfo, PluginInstance, TransportState};
+use crate::extension::ExtensionInterface;
+use crate::feature::FeatureError;
+use crate::port::{PortCollection, PortPointerCache};
+use alloc::boxed::Box;
+use core::ffi::c_void;
+use core::fmt;
+use sys::LV2_Handle;
+
+/// Errors that occur when a plugin is instantiated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstantiationError {
+    /// A feature of the plugin is missing, or it can't be used.
+    Feature(FeatureError),
+    /// The plugin failed to create itself.
+    PluginFailed,
+}
+
+impl From<FeatureError> for InstantiationError {
+    fn from(error: FeatureError) -> Self {
+        InstantiationError::Feature(error)
+    }
+}
+
+impl fmt::Display for InstantiationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstantiationError::Feature(error) => error.fmt(f),
+            InstantiationError::PluginFailed => write!(f, "The plugin failed to create itself"),
+        }
+    }
+}
+
+/// A plugin instance that is driven directly from Rust.
+///
+/// Hosts usually load plugins from shared libraries and talk to them through the C interface. Rust projects that link a plugin type directly, like a bridge that wraps LV2 plugins in another plugin format, can skip this detour and use this struct instead: It instantiates the plugin type, connects its ports, activates and runs it and looks up its extensions, without going through descriptors or raw handles.
+///
+/// The instance keeps track of whether it's active: It's deactivated when it's dropped, and it isn't run while it's inactive. The raw [handle](#method.handle) is still available for the functions of extension interfaces, like the `work` function of a worker.
+///
+/// # Example
+///
+/// ```
+/// use lv2_core::prelude::*;
+/// use std::path::Path;
+/// use urid::*;
+///
+/// #[derive(PortCollection)]
+/// struct Ports {
+///     gain: InputPort<Control>,
+///     output: OutputPort<Audio>,
+/// }
+///
+/// #[uri("urn:lv2_core:embedded:constant")]
+/// struct Constant;
+///
+/// impl Plugin for Constant {
+///     type Ports = Ports;
+///     type InitFeatures = ();
+///     type AudioFeatures = ();
+///
+///     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+///         Some(Self)
+///     }
+///
+///     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+///         for frame in ports.output.iter_mut() {
+///             *frame = *ports.gain;
+///         }
+///     }
+/// }
+///
+/// let info = PluginInfo::new(Constant::uri(), Path::new("/"), 48000.0);
+/// let mut plugin = unsafe { EmbeddedPlugin::<Constant>::new(&info, std::ptr::null()) }.unwrap();
+///
+/// let mut gain = 0.5f32;
+/// let mut output = [0.0f32; 64];
+/// unsafe {
+///     plugin.connect_port(0, &mut gain as *mut f32);
+///     plugin.connect_port(1, output.as_mut_ptr());
+/// }
+/// plugin.activate();
+/// assert!(unsafe { plugin.run(64) });
+/// assert_eq!(output, [0.5; 64]);
+/// ```
+pub struct EmbeddedPlugin<T: Plugin> {
+    instance: Box<PluginInstance<T>>,
+    active: bool,
+}
+
+impl<T: Plugin> EmbeddedPlugin<T> {
+    /// Instantiate the plugin.
+    ///
+    /// The features are passed like the features of a C host: as a null-terminated array of pointers, which may also be null if there are no features at all. The plugin is created in the "Instantiation" threading class and is inactive.
+    ///
+    /// # Safety
+    ///
+    /// The features have to be valid, and they have to live as long as the instance, since the plugin keeps them.
+    pub unsafe fn new(
+        plugin_info: &PluginInfo,
+        features: *const *const sys::LV2_Feature,
+    ) -> Result<Self, InstantiationError> {
+        PluginInstance::try_create(plugin_info, features, Default::default(), T::new).map(
+            |instance| Self {
+                instance: Box::new(instance),
+                active: false,
+            },
+        )
+    }
+
+    /// Connect the port with the given index to a buffer.
+    ///
+    /// The type of the buffer depends on the port type: Audio and CV ports take an array of `f32` with at least as many frames as are run, control ports a single `f32`, and atom ports an atom with its buffer. Connections are kept until the port is connected again, and the plugin only reads them when it's run.
+    ///
+    /// # Safety
+    ///
+    /// The buffer has to fit the type of the port, and it has to stay valid until the port is connected to another buffer or the instance is dropped.
+    pub unsafe fn connect_port<D>(&mut self, index: u32, data: *mut D) {
+        self.instance
+            .connections
+            .connect(index, data as *mut c_void)
+    }
+
+    /// Activate the plugin, unless it's already active.
+    pub fn activate(&mut self) {
+        if !self.active {
+            let (plugin, features) = self.instance.init_class_handle();
+            plugin.activate(features);
+            self.active = true;
+        }
+    }
+
+    /// Deactivate the plugin, unless it's already inactive.
+    pub fn deactivate(&mut self) {
+        if self.active {
+            let (plugin, features) = self.instance.init_class_handle();
+            plugin.deactivate(features);
+            self.active = false;
+        }
+    }
+
+    /// Check whether the plugin is active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Run the plugin for the given number of frames.
+    ///
+    /// Like every host, this respects the [`FLUSH_DENORMALS`](trait.Plugin.html#associatedconstant.FLUSH_DENORMALS) and [`SKIP_EMPTY_CYCLES`](trait.Plugin.html#associatedconstant.SKIP_EMPTY_CYCLES) constants of the plugin. Returns `false` without running the plugin if it's inactive or if one of its required ports isn't connected.
+    ///
+    /// # Safety
+    ///
+    /// The buffers of all connected ports have to be valid, and audio and CV buffers have to hold at least `sample_count` frames.
+    pub unsafe fn run(&mut self, sample_count: u32) -> bool {
+        if !self.active
+            || <T::Ports as PortCollection>::from_connections(
+                &self.instance.connections,
+                sample_count,
+            )
+            .is_none()
+        {
+            return false;
+        }
+        PluginInstance::<T>::run(self.handle(), sample_count);
+        true
+    }
+
+    /// Set the transport state that is passed to the plugin in the following cycles.
+    ///
+    /// See [`PluginInstance::set_transport`](struct.PluginInstance.html#method.set_transport) for details.
+    pub fn set_transport(&mut self, transport: Option<TransportState>) {
+        self.instance.set_transport(transport);
+    }
+
+    /// Look up the interface of an extension, like the worker interface.
+    ///
+    /// `None` is returned if the plugin doesn't provide the extension.
+    pub fn extension<I: ExtensionInterface>(&self) -> Option<&'static I> {
+        T::extension_data(I::uri())?.downcast_ref()
+    }
+
+    /// Return the raw handle of the instance, which is passed to the functions of extension interfaces.
+    ///
+    /// The handle stays valid as long as the instance, but the threading classes of the functions it's passed to have to be respected.
+    pub fn handle(&mut self) -> LV2_Handle {
+        self.instance.as_raw()
+    }
+
+    /// Return the plugin.
+    pub fn plugin(&self) -> &T {
+        &self.instance.instance
+    }
+
+    /// Return the plugin, with mutable access.
+    pub fn plugin_mut(&mut self) -> &mut T {
+        self.instance.plugin_handle()
+    }
+
+    /// Return the wrapped plugin instance, which provides the features of the plugin to extension methods.
+    pub fn instance_mut(&mut self) -> &mut PluginInstance<T> {
+        &mut self.instance
+    }
+}
+
+impl<T: Plugin> Drop for EmbeddedPlugin<T> {
+    fn drop(&mut self) {
+        self.deactivate();
+    }
+}
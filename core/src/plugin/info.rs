@@ -1,6 +1,7 @@
-use std::os::raw::c_char;
+use core::ffi::c_char;
+use core::str::Utf8Error;
+#[cfg(feature = "std")]
 use std::path::Path;
-use std::str::Utf8Error;
 use urid::Uri;
 
 #[derive(Debug)]
@@ -8,10 +9,16 @@ pub enum PluginInfoError {
     InvalidBundlePathUtf8(Utf8Error),
 }
 
+/// The path to a bundle directory: a `Path` with `std` and a string without it.
+#[cfg(feature = "std")]
+type BundlePath = Path;
+#[cfg(not(feature = "std"))]
+type BundlePath = str;
+
 /// Holds various data that is passed from the host at plugin instantiation time.
 pub struct PluginInfo<'a> {
     plugin_uri: &'a Uri,
-    bundle_path: &'a Path,
+    bundle_path: &'a BundlePath,
     sample_rate: f64,
 }
 
@@ -27,11 +34,11 @@ impl<'a> PluginInfo<'a> {
         bundle_path: *const c_char,
         sample_rate: f64,
     ) -> Result<Self, PluginInfoError> {
-        let bundle_path = Path::new(
-            Uri::from_ptr(bundle_path)
-                .to_str()
-                .map_err(PluginInfoError::InvalidBundlePathUtf8)?,
-        );
+        let bundle_path = Uri::from_ptr(bundle_path)
+            .to_str()
+            .map_err(PluginInfoError::InvalidBundlePathUtf8)?;
+        #[cfg(feature = "std")]
+        let bundle_path = Path::new(bundle_path);
         Ok(Self::new(
             Uri::from_ptr((*plugin_descriptor).URI),
             bundle_path,
@@ -40,7 +47,7 @@ impl<'a> PluginInfo<'a> {
     }
 
     /// Create a new plugin info instance.
-    pub fn new(plugin_uri: &'a Uri, bundle_path: &'a Path, sample_rate: f64) -> Self {
+    pub fn new(plugin_uri: &'a Uri, bundle_path: &'a BundlePath, sample_rate: f64) -> Self {
         Self {
             sample_rate,
             plugin_uri,
@@ -57,7 +64,9 @@ impl<'a> PluginInfo<'a> {
     ///
     /// This is useful to get if the plugin needs to store extra resources in its bundle directory,
     /// such as presets, or any other kind of data.
-    pub fn bundle_path(&self) -> &Path {
+    ///
+    /// Without the `std` feature, the path is returned as a string.
+    pub fn bundle_path(&self) -> &BundlePath {
         self.bundle_path
     }
 
@@ -1,14 +1,28 @@
 //! Types to create plugins.
+mod class;
+mod context;
+mod denormal;
+mod embedded;
 pub(crate) mod info;
+mod transport;
+mod version;
 
+#[doc(hidden)]
+pub use crate::__derive;
+pub use class::PluginClass;
+pub use context::RunContext;
+pub use denormal::{flush_denormal, DenormalGuard};
+pub use embedded::{EmbeddedPlugin, InstantiationError};
 pub use info::PluginInfo;
 pub use lv2_core_derive::*;
+pub use transport::TransportState;
+pub use version::PluginVersion;
 
 use crate::feature::*;
 use crate::port::*;
-use std::any::Any;
-use std::ffi::c_void;
-use std::os::raw::c_char;
+use alloc::boxed::Box;
+use core::any::Any;
+use core::ffi::{c_char, c_void};
 use sys::LV2_Handle;
 use urid::{Uri, UriBound};
 
@@ -42,10 +56,34 @@ pub trait Plugin: UriBound + Sized + Send + Sync + 'static {
     /// This method only creates an instance of the plugin, it does not reset or set up it's internal state. This is done by the `activate` method.
     fn new(plugin_info: &PluginInfo, features: &mut Self::InitFeatures) -> Option<Self>;
 
+    /// Create a new plugin instance that takes over the state of a previous instance.
+    ///
+    /// The sample rate of a plugin instance can't change: If the sample rate of the host changes, the host has to deactivate the plugin and create a new instance with the new sample rate. Hosts that go through the C API carry the state over by saving it from the previous instance and restoring it in the new one, but in-process hosts can instead hand the previous instance over to this method, which is called in place of [`new`](#tymethod.new). The plugin info contains the new sample rate, which lets the plugin keep user state like loaded samples or parameter values and recompute everything that depends on the sample rate.
+    ///
+    /// The previous instance has already been deactivated. The default implementation drops it and calls `new`.
+    fn migrate(
+        previous: Self,
+        plugin_info: &PluginInfo,
+        features: &mut Self::InitFeatures,
+    ) -> Option<Self> {
+        drop(previous);
+        Self::new(plugin_info, features)
+    }
+
     /// Run a processing step.
     ///
-    /// The host will always call this method after `active` has been called and before `deactivate` has been called.
-    fn run(&mut self, ports: &mut Self::Ports, features: &mut Self::AudioFeatures);
+    /// The host will always call this method after `active` has been called and before `deactivate` has been called. The context contains the number of frames to process and the audio features, among other things.
+    fn run(&mut self, ports: &mut Self::Ports, context: &mut RunContext<Self::AudioFeatures>);
+
+    /// Handle a cycle that was [failed](struct.RunContext.html#method.fail) by `run`.
+    ///
+    /// This method is called right after `run` returned, when the outputs have already been [silenced](../port/trait.PortCollection.html#method.silence). It's in the "Audio" threading class and may therefore log the reason with a real-time safe logger or write a notification to an atom output, but it must not allocate or block. The reason is available from the context. The default implementation does nothing.
+    fn cycle_failed(
+        &mut self,
+        _ports: &mut Self::Ports,
+        _context: &mut RunContext<Self::AudioFeatures>,
+    ) {
+    }
 
     /// Reset and initialize the complete internal state of the plugin.
     ///
@@ -63,10 +101,40 @@ pub trait Plugin: UriBound + Sized + Send + Sync + 'static {
     ///
     /// However, these implemented methods must be passed to the host. This is where this method comes into play: The host will call it with a URI for an extension. Then, it is the plugin's responsibilty to return the extension data to the host.
     ///
-    /// In most cases, you can simply use the [`match_extensions`](../macro.match_extensions.html) macro to generate an appropiate method body.
+    /// In most cases, you can simply use the [`extensions`](attr.extensions.html) attribute to generate this method, or the [`match_extensions`](../macro.match_extensions.html) macro to generate an appropiate method body.
     fn extension_data(_uri: &Uri) -> Option<&'static dyn Any> {
         None
     }
+
+    /// The URIs of the extensions the plugin provides, in the order they're looked up.
+    ///
+    /// This table is generated by the [`extensions`](attr.extensions.html) attribute along with `extension_data`, and it's empty if the attribute isn't used. Tools that describe the plugin, like a Turtle generator, use it to list the provided extensions.
+    const EXTENSIONS: &'static [&'static [u8]] = &[];
+
+    /// The classes of the plugin, like [`PluginClass::Instrument`](enum.PluginClass.html#variant.Instrument).
+    ///
+    /// Hosts use the classes to categorize plugins. The list is empty by default, which means that the plugin is only described as a generic `lv2:Plugin`. Tools that describe the plugin, like a Turtle generator, read the classes from this constant.
+    const CLASSES: &'static [PluginClass] = &[];
+
+    /// The version of the plugin, or `None` if it doesn't declare one.
+    ///
+    /// The version is declared with the [`plugin_version`](attr.plugin_version.html) attribute, which generates this constant along with [`REPLACES`](#associatedconstant.REPLACES). Hosts learn the version from the plugin's Turtle description, which a Turtle generator creates from this constant.
+    const VERSION: Option<PluginVersion> = None;
+
+    /// The null-terminated URIs of the plugins this plugin replaces, like a previous plugin with incompatible ports or a LADSPA plugin.
+    ///
+    /// This table is generated by the [`plugin_version`](attr.plugin_version.html) attribute and is written to the Turtle description as `dc:replaces`, which lets hosts load this plugin for projects that used one of the replaced ones.
+    const REPLACES: &'static [&'static [u8]] = &[];
+
+    /// Whether denormal numbers are flushed to zero while `run` is called.
+    ///
+    /// If this is `true`, the framework creates a [`DenormalGuard`](struct.DenormalGuard.html) before it calls `run` and drops it afterwards, which avoids the load spikes of denormal numbers in recursive filters and reverbs. It's `false` by default, since flushing changes the results of calculations with very small numbers.
+    const FLUSH_DENORMALS: bool = false;
+
+    /// Whether `run` is skipped for cycles without frames.
+    ///
+    /// Hosts may call `run` with a sample count of zero, for example to deliver events or control changes while the transport is stopped, or to let the plugin update its output controls. Plugins that only process audio have nothing to do in such a cycle and may set this to `true`, which lets the framework return before the ports are even assembled. Plugins with event or control ports should keep the default, `false`, and check [`RunContext::is_empty`](struct.RunContext.html#method.is_empty) instead, since the events of a skipped cycle are lost.
+    const SKIP_EMPTY_CYCLES: bool = false;
 }
 
 /// Plugin wrapper which translated between the host and the plugin.
@@ -84,6 +152,10 @@ pub struct PluginInstance<T: Plugin> {
     init_features: T::InitFeatures,
     /// All features that may be used in the audio threading class.
     audio_features: T::AudioFeatures,
+    /// The reason of the last failed cycle.
+    last_failure: Option<&'static str>,
+    /// The transport state that is passed to `run`, if the host provides it.
+    transport: Option<TransportState>,
 }
 
 impl<T: Plugin> PluginInstance<T> {
@@ -108,64 +180,109 @@ impl<T: Plugin> PluginInstance<T> {
         sample_rate: f64,
         bundle_path: *const c_char,
         features: *const *const sys::LV2_Feature,
+    ) -> LV2_Handle {
+        Self::create(
+            descriptor,
+            sample_rate,
+            bundle_path,
+            features,
+            Default::default(),
+            T::new,
+        )
+    }
+
+    /// Instantiate the plugin with a new sample rate, taking over the state of a previous instance.
+    ///
+    /// This works like [`instantiate`](#method.instantiate), but the new instance is created by [`Plugin::migrate`](trait.Plugin.html#method.migrate) and the port connections of the previous instance are kept. It isn't part of the C interface of a plugin, but it can be used by in-process hosts to change the sample rate of a plugin.
+    ///
+    /// The previous instance is consumed, even if the new instance can't be created, and it must not be used again. If the instantiation fails, a null pointer is returned.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it derefences multiple raw pointers. The previous instance has to be a deactivated instance of the same plugin type that was created by `instantiate` or `migrate`.
+    pub unsafe fn migrate(
+        previous: LV2_Handle,
+        descriptor: *const sys::LV2_Descriptor,
+        sample_rate: f64,
+        bundle_path: *const c_char,
+        features: *const *const sys::LV2_Feature,
+    ) -> LV2_Handle {
+        let previous = *Box::from_raw(previous as *mut Self);
+        let instance = previous.instance;
+        Self::create(
+            descriptor,
+            sample_rate,
+            bundle_path,
+            features,
+            previous.connections,
+            move |plugin_info, features| T::migrate(instance, plugin_info, features),
+        )
+    }
+
+    /// Create a new instance with the given constructor, reporting errors.
+    unsafe fn create(
+        descriptor: *const sys::LV2_Descriptor,
+        sample_rate: f64,
+        bundle_path: *const c_char,
+        features: *const *const sys::LV2_Feature,
+        connections: <T::Ports as PortCollection>::Cache,
+        constructor: impl FnOnce(&PluginInfo, &mut T::InitFeatures) -> Option<T>,
     ) -> LV2_Handle {
         // Dereference the descriptor.
-        let descriptor = match descriptor.as_ref() {
-            Some(descriptor) => descriptor,
-            None => {
-                eprintln!("Failed to initialize plugin: Descriptor points to null");
-                return std::ptr::null_mut();
-            }
-        };
+        if descriptor.is_null() {
+            report_error!("Failed to initialize plugin: Descriptor points to null");
+            return core::ptr::null_mut();
+        }
 
         // Dereference the plugin info.
         let plugin_info = match PluginInfo::from_raw(descriptor, bundle_path, sample_rate) {
             Ok(info) => info,
             Err(e) => {
-                eprintln!(
+                report_error!(
                     "Failed to initialize plugin: Illegal info from host: {:?}",
                     e
                 );
-                return std::ptr::null_mut();
+                return core::ptr::null_mut();
             }
         };
 
+        match Self::try_create(&plugin_info, features, connections, constructor) {
+            Ok(instance) => Box::leak(Box::new(instance)) as *mut Self as LV2_Handle,
+            Err(InstantiationError::Feature(e)) => {
+                report_error!("{}", e);
+                core::ptr::null_mut()
+            }
+            Err(InstantiationError::PluginFailed) => core::ptr::null_mut(),
+        }
+    }
+
+    /// Collect the features and create a new instance with the given constructor.
+    unsafe fn try_create(
+        plugin_info: &PluginInfo,
+        features: *const *const sys::LV2_Feature,
+        connections: <T::Ports as PortCollection>::Cache,
+        constructor: impl FnOnce(&PluginInfo, &mut T::InitFeatures) -> Option<T>,
+    ) -> Result<Self, InstantiationError> {
         // Collect the supported features.
         let mut init_features_cache = FeatureCache::from_raw(features);
         let mut audio_features_cache = init_features_cache.clone();
 
-        let mut init_features = match T::InitFeatures::from_cache(
-            &mut init_features_cache,
-            ThreadingClass::Instantiation,
-        ) {
-            Ok(f) => f,
-            Err(e) => {
-                eprintln!("{}", e);
-                return std::ptr::null_mut();
-            }
-        };
+        let mut init_features =
+            T::InitFeatures::from_cache(&mut init_features_cache, ThreadingClass::Instantiation)?;
         let audio_features =
-            match T::AudioFeatures::from_cache(&mut audio_features_cache, ThreadingClass::Audio) {
-                Ok(f) => f,
-                Err(e) => {
-                    eprintln!("{}", e);
-                    return std::ptr::null_mut();
-                }
-            };
+            T::AudioFeatures::from_cache(&mut audio_features_cache, ThreadingClass::Audio)?;
 
         // Instantiate the plugin.
-        match T::new(&plugin_info, &mut init_features) {
-            Some(instance) => {
-                let instance = Box::new(Self {
-                    instance,
-                    connections: <<T::Ports as PortCollection>::Cache as Default>::default(),
-                    init_features,
-                    audio_features,
-                });
-                Box::leak(instance) as *mut Self as LV2_Handle
-            }
-            None => std::ptr::null_mut(),
-        }
+        let instance =
+            constructor(plugin_info, &mut init_features).ok_or(InstantiationError::PluginFailed)?;
+        Ok(Self {
+            instance,
+            connections,
+            init_features,
+            audio_features,
+            last_failure: None,
+            transport: None,
+        })
     }
 
     /// Clean the plugin.
@@ -224,11 +341,25 @@ impl<T: Plugin> PluginInstance<T> {
     ///
     /// This method is unsafe since it derefences multiple raw pointers and is part of the C interface.
     pub unsafe extern "C" fn run(instance: *mut c_void, sample_count: u32) {
+        if sample_count == 0 && T::SKIP_EMPTY_CYCLES {
+            return;
+        }
         let instance = &mut *(instance as *mut Self);
+        let _guard = if T::FLUSH_DENORMALS {
+            Some(DenormalGuard::new())
+        } else {
+            None
+        };
         if let Some(mut ports) = instance.ports(sample_count) {
-            instance
-                .instance
-                .run(&mut ports, &mut instance.audio_features);
+            let mut context = RunContext::new(sample_count, &mut instance.audio_features)
+                .with_freewheeling(ports.is_freewheeling())
+                .with_transport(instance.transport.as_ref());
+            instance.instance.run(&mut ports, &mut context);
+            if let Some(reason) = context.failure() {
+                ports.silence();
+                instance.instance.cycle_failed(&mut ports, &mut context);
+                instance.last_failure = Some(reason);
+            }
         }
     }
 
@@ -244,10 +375,36 @@ impl<T: Plugin> PluginInstance<T> {
         if let Some(data) = T::extension_data(uri) {
             data as *const _ as *const c_void
         } else {
-            std::ptr::null()
+            core::ptr::null()
         }
     }
 
+    /// Return the raw handle of the instance.
+    ///
+    /// This is the handle the host passes to the functions of the plugin and its extensions, which can be turned into the instance again with an [`ExtensionCall`](../extension/struct.ExtensionCall.html). It stays valid until the host cleans the instance up, but it must only be used by one thread at a time, according to the threading classes of the functions it's passed to.
+    pub fn as_raw(&mut self) -> LV2_Handle {
+        self as *mut Self as LV2_Handle
+    }
+
+    /// Return the reason of the last cycle that was [failed](struct.RunContext.html#method.fail) by the plugin, or `None` if no cycle has failed yet.
+    ///
+    /// Hosts that run the plugin in-process can poll this to tell the user about failures.
+    pub fn last_failure(&self) -> Option<&'static str> {
+        self.last_failure
+    }
+
+    /// Set the transport state that is passed to the plugin in the [`RunContext`](struct.RunContext.html#method.transport) of the following cycles.
+    ///
+    /// The C interface of a plugin has no way to pass the transport, so this is only available to hosts that run the plugin in-process. The state isn't advanced by the framework: The host has to set it before every cycle in which the transport has moved, or `None` if it doesn't know it.
+    pub fn set_transport(&mut self, transport: Option<TransportState>) {
+        self.transport = transport;
+    }
+
+    /// Return the transport state that is passed to the plugin, if any.
+    pub fn transport(&self) -> Option<&TransportState> {
+        self.transport.as_ref()
+    }
+
     /// Retrieve the internal plugin.
     pub fn plugin_handle(&mut self) -> &mut T {
         &mut self.instance
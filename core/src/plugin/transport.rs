@@ -0,0 +1,50 @@
+/// The current state of the host's transport.
+///
+/// Hosts that know the transport position can pass it to the plugin with the [`RunContext`](struct.RunContext.html#method.transport) of a cycle. Plugins can also keep an instance of this struct themselves and update it with the `time:Position` objects they receive over atom ports, which is what `lv2-time` provides methods for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransportState {
+    /// The current frame.
+    pub frame: i64,
+    /// The rate of the progress of time as a fraction of normal speed; 0.0 means that the transport is stopped.
+    pub speed: f32,
+    /// The current bar number.
+    pub bar: i64,
+    /// The current beat within the bar.
+    pub bar_beat: f32,
+    /// The global running beat number.
+    pub beat: f64,
+    /// The note value that counts as one beat.
+    pub beat_unit: u32,
+    /// The number of beats per bar.
+    pub beats_per_bar: f32,
+    /// The tempo in beats per minute.
+    pub beats_per_minute: f32,
+}
+
+impl Default for TransportState {
+    /// Create a stopped transport at the beginning of the timeline, with 120 BPM and a 4/4 time signature.
+    fn default() -> Self {
+        Self {
+            frame: 0,
+            speed: 0.0,
+            bar: 0,
+            bar_beat: 0.0,
+            beat: 0.0,
+            beat_unit: 4,
+            beats_per_bar: 4.0,
+            beats_per_minute: 120.0,
+        }
+    }
+}
+
+impl TransportState {
+    /// Check whether the transport is currently rolling.
+    pub fn is_rolling(&self) -> bool {
+        self.speed != 0.0
+    }
+
+    /// Return the number of frames per beat at the current tempo and the given sample rate.
+    pub fn frames_per_beat(&self, sample_rate: f64) -> f64 {
+        60.0 * sample_rate / f64::from(self.beats_per_minute)
+    }
+}
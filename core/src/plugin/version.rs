@@ -0,0 +1,39 @@
+use core::fmt;
+
+/// The version of a plugin, as declared with the `lv2:minorVersion` and `lv2:microVersion` properties.
+///
+/// Unlike the version of a crate, the version of an LV2 plugin has no major number: A plugin that breaks compatibility, for example by changing its ports, has to get a new URI, and it can declare that it replaces the previous plugin. The minor version is incremented when features are added, and the micro version when bugs are fixed.
+///
+/// As by the LV2 specification, a minor version of zero marks an experimental plugin, and odd minor or micro versions mark development releases. Hosts may hide experimental or development plugins, or warn about them, and they use the version to pick the newest installation of a plugin if several bundles contain it.
+///
+/// The version of a plugin is declared with the [`plugin_version`](attr.plugin_version.html) attribute and read from the [`VERSION`](trait.Plugin.html#associatedconstant.VERSION) constant of its `Plugin` implementation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PluginVersion {
+    /// The minor version, which is incremented when features are added.
+    pub minor: u32,
+    /// The micro version, which is incremented when bugs are fixed.
+    pub micro: u32,
+}
+
+impl PluginVersion {
+    /// Create a new version.
+    pub const fn new(minor: u32, micro: u32) -> Self {
+        Self { minor, micro }
+    }
+
+    /// Check whether the version marks a stable release, with an even, non-zero minor version and an even micro version.
+    pub fn is_stable(&self) -> bool {
+        self.minor != 0 && self.minor.is_multiple_of(2) && self.micro.is_multiple_of(2)
+    }
+
+    /// Check whether the version marks an experimental plugin, with a minor version of zero.
+    pub fn is_experimental(&self) -> bool {
+        self.minor == 0
+    }
+}
+
+impl fmt::Display for PluginVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.minor, self.micro)
+    }
+}
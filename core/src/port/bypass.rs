@@ -0,0 +1,122 @@
+//! Click-free bypassing with the `Enabled` port.
+use super::{InputGroup, OutputGroup, SamplePortType};
+
+/// Click-free bypassing of a plugin, controlled by the port with the [`Enabled`](enum.PortDesignation.html#variant.Enabled) designation.
+///
+/// Hosts bypass a plugin by setting its enabled port to zero. Simply switching the output from the processed to the dry signal would click, which is why `Bypass` crossfades between them over a ramp of a configurable number of frames. Once the ramp is finished, [`is_bypassed`](#method.is_bypassed) returns `true` and the plugin may skip its processing, since the output is the dry signal anyway.
+///
+/// # Example
+///
+/// ```
+/// use lv2_core::prelude::*;
+///
+/// #[derive(PortCollection)]
+/// struct Ports {
+///     input: InputPort<Audio>,
+///     output: OutputPort<Audio>,
+///     #[port(designation = Enabled)]
+///     enabled: InputPort<Control>,
+/// }
+///
+/// fn run(bypass: &mut Bypass, ports: &mut Ports) {
+///     let enabled = *ports.enabled;
+///     if !bypass.is_bypassed(enabled) {
+///         for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+///             *output = input.tanh();
+///         }
+///     }
+///     let dry = InputGroup::<Audio, 1>::new([*ports.input]);
+///     let mut output = OutputGroup::<Audio, 1>::new([&mut *ports.output]);
+///     bypass.apply(enabled, &dry, &mut output);
+/// }
+///
+/// // Crossfade over 10 ms at 44.1 kHz.
+/// let mut bypass = Bypass::from_duration(44100.0, 0.01);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bypass {
+    mix: f32,
+    step: f32,
+}
+
+impl Bypass {
+    /// Create an enabled bypass that crossfades over the given number of frames.
+    ///
+    /// A ramp length of zero switches immediately.
+    pub fn new(ramp_length: u32) -> Self {
+        let mut bypass = Self {
+            mix: 1.0,
+            step: 1.0,
+        };
+        bypass.set_ramp_length(ramp_length);
+        bypass
+    }
+
+    /// Create an enabled bypass that crossfades over the given number of seconds.
+    pub fn from_duration(sample_rate: f64, seconds: f64) -> Self {
+        Self::new((sample_rate * seconds + 0.5) as u32)
+    }
+
+    /// Set the number of frames a crossfade takes.
+    ///
+    /// A crossfade that is in progress continues with the new ramp length.
+    pub fn set_ramp_length(&mut self, ramp_length: u32) {
+        self.step = if ramp_length == 0 {
+            1.0
+        } else {
+            1.0 / ramp_length as f32
+        };
+    }
+
+    /// Return the current share of the processed signal in the output, between `0.0` and `1.0`.
+    pub fn mix(&self) -> f32 {
+        self.mix
+    }
+
+    /// Check whether the plugin is completely bypassed with the given value of the enabled port.
+    ///
+    /// If this is the case, the plugin doesn't need to process the current block, since [`apply`](#method.apply) will replace its output with the dry signal.
+    pub fn is_bypassed(&self, enabled: f32) -> bool {
+        enabled <= 0.0 && self.mix <= 0.0
+    }
+
+    /// Crossfade between the processed signal in the output and the dry signal, according to the value of the enabled port.
+    ///
+    /// The output has to contain the processed signal, unless the plugin [is bypassed](#method.is_bypassed). All channels are faded with the same ramp.
+    pub fn apply<T: SamplePortType<Sample = f32>, const N: usize>(
+        &mut self,
+        enabled: f32,
+        dry: &InputGroup<T, N>,
+        output: &mut OutputGroup<T, N>,
+    ) {
+        let target = if enabled > 0.0 { 1.0 } else { 0.0 };
+        let len = dry.len().min(output.len());
+        let dry = dry.channels();
+        let output = output.channels_mut();
+        for index in 0..len {
+            if self.mix < target {
+                self.mix = (self.mix + self.step).min(target);
+            } else if self.mix > target {
+                self.mix = (self.mix - self.step).max(target);
+            }
+
+            if self.mix >= 1.0 {
+                continue;
+            }
+            for (dry, output) in dry.iter().zip(output.iter_mut()) {
+                output[index] = if self.mix <= 0.0 {
+                    dry[index]
+                } else {
+                    dry[index] + self.mix * (output[index] - dry[index])
+                };
+            }
+        }
+    }
+}
+
+impl Default for Bypass {
+    /// Create an enabled bypass that switches immediately.
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
@@ -0,0 +1,38 @@
+//! Designations of ports, which tell the host what a port is used for.
+use urid::Uri;
+
+/// The designation of a port.
+///
+/// A designation tells the host about the meaning of a port, so that it can connect it to the right data or present it in a special way. For example, the host may use the `Enabled` port to bypass the plugin and the `Latency` port to compensate the plugin's latency.
+///
+/// Designations are declared with the `port` attribute of a [derived port collection](trait.PortCollection.html#port-designations).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PortDesignation {
+    /// The plugin is processing if the value of this control input is positive and bypassed otherwise.
+    Enabled,
+    /// The plugin runs faster than real time if the value of this control input is positive.
+    FreeWheeling,
+    /// This control output reports the latency of the plugin in frames.
+    Latency,
+    /// The linear amplitude of the signal.
+    Amplitude,
+    /// The gain of the signal in decibels.
+    Gain,
+    /// This atom input is the main control input of the plugin, to which the host sends messages like parameter changes.
+    Control,
+}
+
+impl PortDesignation {
+    /// The URI of the designation.
+    pub fn uri(self) -> &'static Uri {
+        let uri: &'static [u8] = match self {
+            PortDesignation::Enabled => b"http://lv2plug.in/ns/lv2core#enabled\0",
+            PortDesignation::FreeWheeling => sys::LV2_CORE__freeWheeling,
+            PortDesignation::Latency => sys::LV2_CORE__latency,
+            PortDesignation::Amplitude => sys::LV2_PARAMETERS__amplitude,
+            PortDesignation::Gain => sys::LV2_PARAMETERS__gain,
+            PortDesignation::Control => sys::LV2_CORE__control,
+        };
+        Uri::from_bytes_with_nul(uri).unwrap()
+    }
+}
@@ -0,0 +1,287 @@
+//! Port groups, as defined by the LV2 Port Groups specification.
+use super::{Audio, AudioF64, PortType, CV};
+use urid::Uri;
+
+/// The kind of a port group.
+///
+/// The kind of a group defines the channels its ports may have, like the left and right channels of a stereo group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PortGroupKind {
+    Mono,
+    Stereo,
+    MidSide,
+    ThreePointZero,
+    FourPointZero,
+    FivePointZero,
+    FivePointOne,
+    SixPointOne,
+    SevenPointOne,
+    SevenPointOneWide,
+}
+
+impl PortGroupKind {
+    /// The URI of the group class.
+    pub fn uri(self) -> &'static Uri {
+        let uri: &'static [u8] = match self {
+            PortGroupKind::Mono => sys::LV2_PORT_GROUPS__MonoGroup,
+            PortGroupKind::Stereo => sys::LV2_PORT_GROUPS__StereoGroup,
+            PortGroupKind::MidSide => sys::LV2_PORT_GROUPS__MidSideGroup,
+            PortGroupKind::ThreePointZero => sys::LV2_PORT_GROUPS__ThreePointZeroGroup,
+            PortGroupKind::FourPointZero => sys::LV2_PORT_GROUPS__FourPointZeroGroup,
+            PortGroupKind::FivePointZero => sys::LV2_PORT_GROUPS__FivePointZeroGroup,
+            PortGroupKind::FivePointOne => sys::LV2_PORT_GROUPS__FivePointOneGroup,
+            PortGroupKind::SixPointOne => sys::LV2_PORT_GROUPS__SixPointOneGroup,
+            PortGroupKind::SevenPointOne => sys::LV2_PORT_GROUPS__SevenPointOneGroup,
+            PortGroupKind::SevenPointOneWide => sys::LV2_PORT_GROUPS__SevenPointOneWideGroup,
+        };
+        Uri::from_bytes_with_nul(uri).unwrap()
+    }
+}
+
+/// The channel of a port in its group.
+///
+/// Mid-side groups use the `Center` channel for the mid signal and the `Side` channel for the side signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PortChannel {
+    Center,
+    CenterLeft,
+    CenterRight,
+    Left,
+    LowFrequencyEffects,
+    RearCenter,
+    RearLeft,
+    RearRight,
+    Right,
+    Side,
+    SideLeft,
+    SideRight,
+}
+
+impl PortChannel {
+    /// The URI of the channel's designation.
+    pub fn uri(self) -> &'static Uri {
+        let uri: &'static [u8] = match self {
+            PortChannel::Center => sys::LV2_PORT_GROUPS__center,
+            PortChannel::CenterLeft => sys::LV2_PORT_GROUPS__centerLeft,
+            PortChannel::CenterRight => sys::LV2_PORT_GROUPS__centerRight,
+            PortChannel::Left => sys::LV2_PORT_GROUPS__left,
+            PortChannel::LowFrequencyEffects => sys::LV2_PORT_GROUPS__lowFrequencyEffects,
+            PortChannel::RearCenter => sys::LV2_PORT_GROUPS__rearCenter,
+            PortChannel::RearLeft => sys::LV2_PORT_GROUPS__rearLeft,
+            PortChannel::RearRight => sys::LV2_PORT_GROUPS__rearRight,
+            PortChannel::Right => sys::LV2_PORT_GROUPS__right,
+            PortChannel::Side => sys::LV2_PORT_GROUPS__side,
+            PortChannel::SideLeft => sys::LV2_PORT_GROUPS__sideLeft,
+            PortChannel::SideRight => sys::LV2_PORT_GROUPS__sideRight,
+        };
+        Uri::from_bytes_with_nul(uri).unwrap()
+    }
+}
+
+/// The membership of a port in a port group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortGroupInfo {
+    /// The symbol of the group.
+    pub symbol: &'static str,
+    /// The kind of the group.
+    pub kind: PortGroupKind,
+    /// The channel of the port in the group.
+    pub channel: PortChannel,
+    /// The symbol of the group this group is a side-chain of, if it is one.
+    pub side_chain_of: Option<&'static str>,
+}
+
+/// Port types whose data is a slice of samples, like audio and CV ports.
+///
+/// The ports of a group with such a type can be accessed frame by frame with an [`InputGroup`](struct.InputGroup.html) or an [`OutputGroup`](struct.OutputGroup.html).
+pub trait SamplePortType:
+    PortType<
+        InputPortType = &'static [<Self as SamplePortType>::Sample],
+        OutputPortType = &'static mut [<Self as SamplePortType>::Sample],
+    > + 'static
+{
+    /// The type of the samples.
+    type Sample: Copy + 'static;
+}
+
+impl SamplePortType for Audio {
+    type Sample = f32;
+}
+
+impl SamplePortType for AudioF64 {
+    type Sample = f64;
+}
+
+impl SamplePortType for CV {
+    type Sample = f32;
+}
+
+/// The input ports of a group, which can be read frame by frame.
+///
+/// A frame contains one sample of every channel, in the order of the ports' indices. This frame-major, or interleaved, layout is what most DSP libraries expect. Input groups are usually created with [`PortCollection::input_group`](trait.PortCollection.html#method.input_group) and never allocate memory.
+pub struct InputGroup<'a, T: SamplePortType, const N: usize> {
+    channels: [&'a [T::Sample]; N],
+}
+
+impl<'a, T: SamplePortType, const N: usize> InputGroup<'a, T, N> {
+    /// Create a group from the samples of its channels.
+    pub fn new(channels: [&'a [T::Sample]; N]) -> Self {
+        Self { channels }
+    }
+
+    /// Return the samples of the channels.
+    pub fn channels(&self) -> &[&'a [T::Sample]; N] {
+        &self.channels
+    }
+
+    /// Return the number of frames, which is the length of the shortest channel.
+    pub fn len(&self) -> usize {
+        self.channels
+            .iter()
+            .map(|channel| channel.len())
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Check whether the group has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the frame with the given index, or `None` if it's out of bounds.
+    pub fn frame(&self, index: usize) -> Option<[T::Sample; N]> {
+        if index < self.len() {
+            Some(core::array::from_fn(|channel| {
+                self.channels[channel][index]
+            }))
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator over all frames.
+    pub fn frames(&self) -> Frames<'a, T, N> {
+        Frames {
+            channels: self.channels,
+            index: 0,
+            len: self.len(),
+        }
+    }
+
+    /// Write the frames to an interleaved buffer and return the number of written frames.
+    ///
+    /// As many frames are written as fit into the buffer.
+    pub fn interleave(&self, target: &mut [T::Sample]) -> usize {
+        if N == 0 {
+            return 0;
+        }
+        let mut written = 0;
+        for (frame, target) in self.frames().zip(target.chunks_exact_mut(N)) {
+            target.copy_from_slice(&frame);
+            written += 1;
+        }
+        written
+    }
+}
+
+/// An iterator over the frames of an [`InputGroup`](struct.InputGroup.html).
+pub struct Frames<'a, T: SamplePortType, const N: usize> {
+    channels: [&'a [T::Sample]; N],
+    index: usize,
+    len: usize,
+}
+
+impl<'a, T: SamplePortType, const N: usize> Iterator for Frames<'a, T, N> {
+    type Item = [T::Sample; N];
+
+    fn next(&mut self) -> Option<[T::Sample; N]> {
+        if self.index >= self.len {
+            return None;
+        }
+        let index = self.index;
+        self.index += 1;
+        Some(core::array::from_fn(|channel| {
+            self.channels[channel][index]
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T: SamplePortType, const N: usize> ExactSizeIterator for Frames<'a, T, N> {}
+
+/// The output ports of a group, which can be written frame by frame.
+///
+/// This is the counterpart of an [`InputGroup`](struct.InputGroup.html). Output groups are usually created with [`PortCollection::output_group`](trait.PortCollection.html#method.output_group) and never allocate memory.
+pub struct OutputGroup<'a, T: SamplePortType, const N: usize> {
+    channels: [&'a mut [T::Sample]; N],
+}
+
+impl<'a, T: SamplePortType, const N: usize> OutputGroup<'a, T, N> {
+    /// Create a group from the samples of its channels.
+    pub fn new(channels: [&'a mut [T::Sample]; N]) -> Self {
+        Self { channels }
+    }
+
+    /// Return the samples of the channels.
+    pub fn channels_mut(&mut self) -> &mut [&'a mut [T::Sample]; N] {
+        &mut self.channels
+    }
+
+    /// Return the number of frames, which is the length of the shortest channel.
+    pub fn len(&self) -> usize {
+        self.channels
+            .iter()
+            .map(|channel| channel.len())
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Check whether the group has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Set the frame with the given index.
+    ///
+    /// If the index is out of bounds, nothing is written and `false` is returned.
+    pub fn set_frame(&mut self, index: usize, frame: [T::Sample; N]) -> bool {
+        if index >= self.len() {
+            return false;
+        }
+        for (channel, sample) in self.channels.iter_mut().zip(frame.iter()) {
+            channel[index] = *sample;
+        }
+        true
+    }
+
+    /// Write the frames, starting with the first one, and return the number of written frames.
+    ///
+    /// Frames that don't fit into the ports are ignored.
+    pub fn write_frames<I: IntoIterator<Item = [T::Sample; N]>>(&mut self, frames: I) -> usize {
+        let len = self.len();
+        let mut written = 0;
+        for frame in frames.into_iter().take(len) {
+            self.set_frame(written, frame);
+            written += 1;
+        }
+        written
+    }
+
+    /// Read the frames from an interleaved buffer and return the number of read frames.
+    ///
+    /// As many frames are read as the buffer contains and fit into the ports.
+    pub fn deinterleave(&mut self, source: &[T::Sample]) -> usize {
+        if N == 0 {
+            return 0;
+        }
+        self.write_frames(
+            source
+                .chunks_exact(N)
+                .map(|frame| core::array::from_fn(|channel| frame[channel])),
+        )
+    }
+}
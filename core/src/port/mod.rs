@@ -0,0 +1,813 @@
+//! Types to declare derivable port collections.
+//!
+//! Every plugin has a type of [`PortCollection`](trait.PortCollection.html) which is used to handle input/output ports. In order to make the creation of these port collection types easier, `PortCollection` can simply be derived. However, the macro that implements `PortCollection` requires the fields of the struct to have specific types. These types are provided in this module.
+mod bypass;
+mod designation;
+mod group;
+mod smooth;
+
+pub use bypass::*;
+pub use designation::*;
+pub use group::*;
+pub use smooth::*;
+
+use alloc::vec::Vec;
+use core::any::Any;
+use core::ffi::c_void;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{null_mut, NonNull};
+use urid::{Uri, UriBound};
+
+#[doc(hidden)]
+pub use crate::__derive;
+pub use lv2_core_derive::*;
+
+/// Generalization of port types.
+///
+/// A port can read input or create a pointer to the output, but the exact type of input/output (pointer) depends on the type of port. This trait generalizes these types and behaviour.
+pub trait PortType {
+    /// The type of input read by the port.
+    type InputPortType: Sized;
+    /// The type of output reference created by the port.
+    type OutputPortType: Sized;
+
+    /// Read data from the pointer or create a reference to the input.
+    ///
+    /// If the resulting data is a slice, `sample_count` is the length of the slice.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe because one needs to de-reference a raw pointer to implement this method.
+    unsafe fn input_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::InputPortType;
+
+    /// Create a reference to the data where output should be written to.
+    ///
+    /// If the data is a slice, `sample_count` is the length of the slice.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe because one needs to de-reference a raw pointer to implement this method.
+    unsafe fn output_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::OutputPortType;
+
+    /// The URI of the port class, like `lv2:AudioPort`.
+    ///
+    /// This is used to describe the port in the plugin's metadata. The default implementation returns `None`, which means that the class of the port is unknown.
+    fn class() -> Option<&'static Uri> {
+        None
+    }
+
+    /// The URI of the unit of the port's values.
+    ///
+    /// The default implementation returns `None`, which means that the values don't have a unit.
+    fn unit() -> Option<&'static Uri> {
+        None
+    }
+
+    /// Write silence to an output, like after a [failed cycle](../plugin/struct.RunContext.html#method.fail).
+    ///
+    /// The default implementation leaves the output untouched, since not every type of port has a neutral value.
+    fn silence(_output: &mut Self::OutputPortType) {}
+}
+
+/// Audio port type.
+///
+/// Audio ports are the most common type of input/output ports: Their input is a slice of audio samples, as well as their output.
+pub struct Audio;
+
+unsafe impl UriBound for Audio {
+    const URI: &'static [u8] = ::lv2_sys::LV2_CORE__AudioPort;
+}
+
+impl PortType for Audio {
+    type InputPortType = &'static [f32];
+    type OutputPortType = &'static mut [f32];
+
+    #[inline]
+    unsafe fn input_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::InputPortType {
+        core::slice::from_raw_parts(pointer.as_ptr() as *const f32, sample_count as usize)
+    }
+
+    #[inline]
+    unsafe fn output_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::OutputPortType {
+        core::slice::from_raw_parts_mut(pointer.as_ptr() as *mut f32, sample_count as usize)
+    }
+
+    fn class() -> Option<&'static Uri> {
+        Some(Self::uri())
+    }
+
+    fn silence(output: &mut Self::OutputPortType) {
+        output.fill(0.0);
+    }
+}
+
+/// Double-precision audio port type.
+///
+/// These ports work like [audio ports](struct.Audio.html), but their samples are `f64`s instead of `f32`s. LV2 only specifies single-precision audio ports, which is why this port class is an extension of rust-lv2 that hosts have to support explicitly. Hosts that don't know the class leave the port unconnected if it's optional and refuse to instantiate the plugin otherwise.
+///
+/// Plugins that prefer to process in double precision should therefore declare an optional port for both precisions and use the one the host has connected:
+///
+///     use lv2_core::port::*;
+///
+///     #[derive(PortCollection)]
+///     struct Ports {
+///         input: Option<InputPort<Audio>>,
+///         output: Option<OutputPort<Audio>>,
+///         input_f64: Option<InputPort<AudioF64>>,
+///         output_f64: Option<OutputPort<AudioF64>>,
+///     }
+///
+///     fn process(sample: f64) -> f64 {
+///         sample * 0.5
+///     }
+///
+///     fn run(ports: &mut Ports) {
+///         if let (Some(input), Some(output)) = (ports.input_f64.as_ref(), ports.output_f64.as_mut()) {
+///             for (input, output) in input.iter().zip(output.iter_mut()) {
+///                 *output = process(*input);
+///             }
+///         } else if let (Some(input), Some(output)) = (ports.input.as_ref(), ports.output.as_mut()) {
+///             for (input, output) in input.iter().zip(output.iter_mut()) {
+///                 *output = process(f64::from(*input)) as f32;
+///             }
+///         }
+///     }
+pub struct AudioF64;
+
+unsafe impl UriBound for AudioF64 {
+    const URI: &'static [u8] = b"https://github.com/RustAudio/rust-lv2#AudioF64Port\0";
+}
+
+impl PortType for AudioF64 {
+    type InputPortType = &'static [f64];
+    type OutputPortType = &'static mut [f64];
+
+    #[inline]
+    unsafe fn input_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::InputPortType {
+        core::slice::from_raw_parts(pointer.as_ptr() as *const f64, sample_count as usize)
+    }
+
+    #[inline]
+    unsafe fn output_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::OutputPortType {
+        core::slice::from_raw_parts_mut(pointer.as_ptr() as *mut f64, sample_count as usize)
+    }
+
+    fn class() -> Option<&'static Uri> {
+        Some(Self::uri())
+    }
+
+    fn silence(output: &mut Self::OutputPortType) {
+        output.fill(0.0);
+    }
+}
+
+/// Value type of control ports.
+///
+/// Control ports carry a single floating-point number, but plugins may use a type that also carries the unit of the value, like the unit types of `lv2-units`. The unit is used to describe the port in the plugin's metadata.
+///
+/// # Safety
+///
+/// The port's value is read and written through a pointer to a `f32`. Therefore, implementors have to be `f32` itself or a `#[repr(transparent)]` wrapper of it.
+pub unsafe trait ControlValue: Copy + 'static {
+    /// The URI of the value's unit, if it has one.
+    fn unit() -> Option<&'static Uri> {
+        None
+    }
+}
+
+unsafe impl ControlValue for f32 {}
+
+/// Control value port type.
+///
+/// Control ports in general are used to control the behaviour of the plugin. These control value ports only have one value per `run` call and therefore don't have a fixed sampling rate.
+///
+/// Therefore, their input is a floating-point number and their output is a mutable reference to a floating-point number. Instead of a plain `f32`, the value may also be of another [`ControlValue`](trait.ControlValue.html) type, like `Control<Db>`.
+pub struct Control<T: ControlValue = f32> {
+    value: PhantomData<T>,
+}
+
+unsafe impl<T: ControlValue> UriBound for Control<T> {
+    const URI: &'static [u8] = ::lv2_sys::LV2_CORE__ControlPort;
+}
+
+impl<T: ControlValue> PortType for Control<T> {
+    type InputPortType = T;
+    type OutputPortType = &'static mut T;
+
+    #[inline]
+    unsafe fn input_from_raw(pointer: NonNull<c_void>, _sample_count: u32) -> T {
+        *(pointer.cast().as_ref())
+    }
+
+    unsafe fn output_from_raw(pointer: NonNull<c_void>, _sample_count: u32) -> &'static mut T {
+        (pointer.as_ptr() as *mut T).as_mut().unwrap()
+    }
+
+    fn class() -> Option<&'static Uri> {
+        Some(Self::uri())
+    }
+
+    fn unit() -> Option<&'static Uri> {
+        T::unit()
+    }
+}
+
+/// CV port type.
+///
+/// Control ports in general are used to control the behaviour of the plugin. CV ports are sampled just like [audio data](struct.Audio.html). This means that audio data is often valid CV data, but CV data generally is not audio data, because it may not be within the audio bounds of -1.0 to 1.0.
+pub struct CV;
+
+unsafe impl UriBound for CV {
+    const URI: &'static [u8] = ::lv2_sys::LV2_CORE__CVPort;
+}
+
+impl PortType for CV {
+    type InputPortType = &'static [f32];
+    type OutputPortType = &'static mut [f32];
+
+    #[inline]
+    unsafe fn input_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::InputPortType {
+        core::slice::from_raw_parts(pointer.as_ptr() as *const f32, sample_count as usize)
+    }
+
+    #[inline]
+    unsafe fn output_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> Self::OutputPortType {
+        core::slice::from_raw_parts_mut(pointer.as_ptr() as *mut f32, sample_count as usize)
+    }
+
+    fn class() -> Option<&'static Uri> {
+        Some(Self::uri())
+    }
+
+    fn silence(output: &mut Self::OutputPortType) {
+        output.fill(0.0);
+    }
+}
+
+/// Abstraction of safe port handles.
+pub trait PortHandle: Sized {
+    /// Try to create a port handle from a port connection pointer and the sample count.
+    ///
+    /// If the pointer is null, this method will return `None`.
+    ///
+    /// # Safety
+    ///
+    /// Implementing this method requires a de-referentation of a raw pointer and therefore, it is unsafe.
+    unsafe fn from_raw(pointer: *mut c_void, sample_count: u32) -> Option<Self>;
+
+    /// The direction of the port, if it's known.
+    const DIRECTION: Option<PortDirection> = None;
+
+    /// Whether the plugin can run without the port being connected.
+    const OPTIONAL: bool = false;
+
+    /// Describe the port handle in the metadata of a port.
+    ///
+    /// The default implementation leaves the description untouched.
+    fn describe(_info: &mut PortInfo) {}
+
+    /// Write silence to the port, if it's an output.
+    ///
+    /// The default implementation does nothing.
+    fn silence(&mut self) {}
+}
+
+/// The direction of a port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PortDirection {
+    Input,
+    Output,
+}
+
+/// Metadata of a port in a port collection.
+///
+/// This information is retrieved with [`PortCollection::port_info`](trait.PortCollection.html#method.port_info) and is used to generate the description of a plugin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortInfo {
+    /// The index of the port.
+    pub index: u32,
+    /// The symbol of the port, which is the name of the field in the port collection.
+    pub symbol: &'static str,
+    /// The URI of the port class, if it's known.
+    pub class: Option<&'static Uri>,
+    /// The direction of the port, if it's known.
+    pub direction: Option<PortDirection>,
+    /// Whether the plugin can run without this port being connected.
+    pub optional: bool,
+    /// The URI of the unit of the port's values, if it has one.
+    pub unit: Option<&'static Uri>,
+    /// The group of the port, if it belongs to one.
+    pub group: Option<PortGroupInfo>,
+    /// The designation of the port, if it has one.
+    pub designation: Option<PortDesignation>,
+    /// Whether the port is a side-chain input, which isn't a part of the main signal path.
+    pub side_chain: bool,
+}
+
+impl PortInfo {
+    /// Create the metadata of a port whose handle is of type `T`.
+    pub fn new<T: PortHandle>(index: u32, symbol: &'static str) -> Self {
+        let mut info = Self {
+            index,
+            symbol,
+            class: None,
+            direction: None,
+            optional: false,
+            unit: None,
+            group: None,
+            designation: None,
+            side_chain: false,
+        };
+        T::describe(&mut info);
+        info
+    }
+
+    /// Add the port to a group.
+    pub fn with_group(mut self, group: PortGroupInfo) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Set the designation of the port.
+    pub fn with_designation(mut self, designation: PortDesignation) -> Self {
+        self.designation = Some(designation);
+        self
+    }
+
+    /// Mark the port as a side-chain input.
+    pub fn with_side_chain(mut self) -> Self {
+        self.side_chain = true;
+        self
+    }
+}
+
+/// Static description of a port in a port collection.
+///
+/// The descriptors of a port collection are available as the [`PORTS`](trait.PortCollection.html#associatedconstant.PORTS) constant, which can be used to enumerate the ports of any plugin type without creating an instance of it. The class and the unit of a port are part of its complete [metadata](#method.info).
+#[derive(Clone, Copy, Debug)]
+pub struct PortDescriptor {
+    /// The index of the port.
+    pub index: u32,
+    /// The symbol of the port, which is the name of the field in the port collection.
+    pub symbol: &'static str,
+    /// The direction of the port, if it's known.
+    pub direction: Option<PortDirection>,
+    /// Whether the plugin can run without this port being connected.
+    pub optional: bool,
+    /// The group of the port, if it belongs to one.
+    pub group: Option<PortGroupInfo>,
+    /// The designation of the port, if it has one.
+    pub designation: Option<PortDesignation>,
+    /// Whether the port is a side-chain input, which isn't a part of the main signal path.
+    pub side_chain: bool,
+    describe: fn(&mut PortInfo),
+}
+
+impl PortDescriptor {
+    /// Create the descriptor of a port whose handle is of type `T`.
+    pub const fn new<T: PortHandle>(index: u32, symbol: &'static str) -> Self {
+        Self {
+            index,
+            symbol,
+            direction: T::DIRECTION,
+            optional: T::OPTIONAL,
+            group: None,
+            designation: None,
+            side_chain: false,
+            describe: T::describe,
+        }
+    }
+
+    /// Add the port to a group.
+    pub const fn with_group(mut self, group: PortGroupInfo) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Set the designation of the port.
+    pub const fn with_designation(mut self, designation: PortDesignation) -> Self {
+        self.designation = Some(designation);
+        self
+    }
+
+    /// Mark the port as a side-chain input.
+    pub const fn with_side_chain(mut self) -> Self {
+        self.side_chain = true;
+        self
+    }
+
+    /// Create the complete metadata of the port.
+    pub fn info(&self) -> PortInfo {
+        let mut info = PortInfo {
+            index: self.index,
+            symbol: self.symbol,
+            class: None,
+            direction: self.direction,
+            optional: self.optional,
+            unit: None,
+            group: self.group,
+            designation: self.designation,
+            side_chain: self.side_chain,
+        };
+        (self.describe)(&mut info);
+        info
+    }
+}
+
+/// Handle for input ports.
+///
+/// Fields of this type can be dereferenced to the input type of the port type.
+pub struct InputPort<T: PortType> {
+    port: T::InputPortType,
+}
+
+impl<T: PortType> Deref for InputPort<T> {
+    type Target = T::InputPortType;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.port
+    }
+}
+
+impl<T: PortType> PortHandle for InputPort<T> {
+    #[inline]
+    unsafe fn from_raw(pointer: *mut c_void, sample_count: u32) -> Option<Self> {
+        if let Some(pointer) = NonNull::new(pointer) {
+            Some(Self {
+                port: T::input_from_raw(pointer, sample_count),
+            })
+        } else {
+            None
+        }
+    }
+
+    const DIRECTION: Option<PortDirection> = Some(PortDirection::Input);
+
+    fn describe(info: &mut PortInfo) {
+        info.class = T::class();
+        info.direction = Self::DIRECTION;
+        info.unit = T::unit();
+    }
+}
+
+/// Handle for output ports.
+///
+/// Fields of this type can be dereferenced to the output type of the port type.
+pub struct OutputPort<T: PortType> {
+    port: T::OutputPortType,
+}
+
+impl<T: PortType> Deref for OutputPort<T> {
+    type Target = T::OutputPortType;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.port
+    }
+}
+
+impl<T: PortType> DerefMut for OutputPort<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.port
+    }
+}
+
+impl<T: PortType> PortHandle for OutputPort<T> {
+    #[inline]
+    unsafe fn from_raw(pointer: *mut c_void, sample_count: u32) -> Option<Self> {
+        if let Some(pointer) = NonNull::new(pointer) {
+            Some(Self {
+                port: T::output_from_raw(pointer, sample_count),
+            })
+        } else {
+            None
+        }
+    }
+
+    const DIRECTION: Option<PortDirection> = Some(PortDirection::Output);
+
+    fn describe(info: &mut PortInfo) {
+        info.class = T::class();
+        info.direction = Self::DIRECTION;
+        info.unit = T::unit();
+    }
+
+    fn silence(&mut self) {
+        T::silence(&mut self.port)
+    }
+}
+
+/// Port handles whose value is a toggle, like the ports with the [`Enabled`](enum.PortDesignation.html#variant.Enabled) or the [`FreeWheeling`](enum.PortDesignation.html#variant.FreeWheeling) designation.
+pub trait ToggledPort {
+    /// Check whether the toggle is on, which is the case if the value of the port is positive.
+    fn is_on(&self) -> bool;
+}
+
+impl ToggledPort for InputPort<Control> {
+    fn is_on(&self) -> bool {
+        self.port > 0.0
+    }
+}
+
+/// Optional toggles are off if they aren't connected.
+impl<T: ToggledPort> ToggledPort for Option<T> {
+    fn is_on(&self) -> bool {
+        self.as_ref().map(T::is_on).unwrap_or(false)
+    }
+}
+
+impl<T: PortHandle> PortHandle for Option<T> {
+    unsafe fn from_raw(pointer: *mut c_void, sample_count: u32) -> Option<Self> {
+        Some(T::from_raw(pointer, sample_count))
+    }
+
+    const DIRECTION: Option<PortDirection> = T::DIRECTION;
+
+    const OPTIONAL: bool = true;
+
+    fn describe(info: &mut PortInfo) {
+        T::describe(info);
+        info.optional = true;
+    }
+
+    fn silence(&mut self) {
+        if let Some(port) = self {
+            port.silence();
+        }
+    }
+}
+
+/// Collection of IO ports.
+///
+/// Plugins do not handle port management on their own. Instead, they define a struct with all of the required ports. Then, the plugin instance will collect the port pointers from the host and create a `PortCollection` instance for every `run` call. Using this instance, plugins have access to all of their required ports.
+///
+/// # Implementing
+///
+/// The most convenient way to create a port collections is to define a struct with port types from the [`port`](index.html) module and then simply derive `PortCollection` for it. An example:
+///
+///     use lv2_core::port::*;
+///
+///     #[derive(PortCollection)]
+///     struct MyPortCollection {
+///         audio_input: InputPort<Audio>,
+///         audio_output: OutputPort<Audio>,
+///         control_input: InputPort<Control>,
+///         control_output: OutputPort<Control>,
+///         optional_control_input: Option<InputPort<Control>>,
+///     }
+///
+/// Please note that port indices are mapped in the order of occurence; In our example, the implementation will treat `audio_input` as port `0`, `audio_output` as port `1` and so on. Therefore, your plugin definition and your port collection have to match. Otherwise, undefined behaviour will occur.
+///
+/// # Port indices
+///
+/// Since the indices of the ports are stored in sessions, adding a field in the middle of the struct would silently renumber all following ports. To avoid this, the index of a port can be pinned with the `port` attribute. A field without a pinned index gets the index after the one of the previous field, like the discriminants of an enum. The indices of all ports have to be unique and contiguous:
+///
+///     use lv2_core::port::*;
+///     use std::convert::TryFrom;
+///
+///     #[derive(PortCollection)]
+///     pub struct Ports {
+///         input: InputPort<Audio>,
+///         output: OutputPort<Audio>,
+///         // A port that was added later.
+///         #[port(index = 3)]
+///         dry_wet: InputPort<Control>,
+///         #[port(index = 2)]
+///         gain: InputPort<Control>,
+///     }
+///
+///     assert_eq!(Ports::PORT_INDEX_GAIN, 2);
+///     assert_eq!(u32::from(PortsIndex::DryWet), 3);
+///     assert_eq!(PortsIndex::try_from(1), Ok(PortsIndex::Output));
+///
+/// The derive macro generates a `PORT_INDEX_<FIELD>` constant for every port and an enum of all ports, which is named after the struct with an `Index` suffix and can be converted from and to the index of a port.
+///
+/// # Port designations
+///
+/// The `port` attribute also declares the [designation](enum.PortDesignation.html) of a port, which tells the host what the port is used for, and whether a port is a side-chain input. Ports in a group can't have a designation, since their channel is their designation:
+///
+///     use lv2_core::port::*;
+///
+///     #[derive(PortCollection)]
+///     struct Ports {
+///         input: InputPort<Audio>,
+///         #[port(side_chain)]
+///         sidechain: InputPort<Audio>,
+///         output: OutputPort<Audio>,
+///         #[port(designation = Enabled)]
+///         enabled: InputPort<Control>,
+///         #[port(designation = Latency)]
+///         latency: OutputPort<Control>,
+///     }
+///
+///     assert_eq!(Ports::PORTS[3].designation, Some(PortDesignation::Enabled));
+///     assert!(Ports::PORTS[1].side_chain);
+///
+/// If a collection has a port with the `FreeWheeling` designation, its value is returned by [`is_freewheeling`](#method.is_freewheeling).
+///
+/// # Port groups
+///
+/// Ports can be organized in groups, like the left and right channel of a stereo input, by adding a `port_group` attribute to their fields. The attribute takes the symbol of the `group`, the `channel` of the port and the `kind` of the group, which only needs to be declared by one of its ports. A group that is the side-chain of another group declares this with `side_chain_of`:
+///
+///     use lv2_core::port::*;
+///
+///     #[derive(PortCollection)]
+///     struct StereoPorts {
+///         #[port_group(group = "main_in", kind = Stereo, channel = Left)]
+///         input_left: InputPort<Audio>,
+///         #[port_group(group = "main_in", channel = Right)]
+///         input_right: InputPort<Audio>,
+///         #[port_group(group = "sidechain", kind = Mono, channel = Center, side_chain_of = "main_in")]
+///         sidechain: InputPort<Audio>,
+///         #[port_group(group = "main_out", kind = Stereo, channel = Left)]
+///         output_left: OutputPort<Audio>,
+///         #[port_group(group = "main_out", channel = Right)]
+///         output_right: OutputPort<Audio>,
+///     }
+///
+///     fn run(ports: &mut StereoPorts) {
+///         let mut inputs: [&[f32]; 2] = [&[], &[]];
+///         ports.for_each_in_group("main_in", |channel, port: &InputPort<Audio>| match channel {
+///             PortChannel::Left => inputs[0] = **port,
+///             _ => inputs[1] = **port,
+///         });
+///         let mut index = 0;
+///         ports.for_each_in_group_mut("main_out", |_, port: &mut OutputPort<Audio>| {
+///             port.copy_from_slice(inputs[index]);
+///             index += 1;
+///         });
+///     }
+///
+/// Audio and CV ports of a group can also be read and written frame by frame, which is the layout most DSP libraries expect. [`input_group`](#method.input_group) and [`output_group`](#method.output_group) collect the ports of a group into a fixed number of channels, without allocating memory:
+///
+///     # use lv2_core::port::*;
+///     # #[derive(PortCollection)]
+///     # struct StereoPorts {
+///     #     #[port_group(group = "main_in", kind = Stereo, channel = Left)]
+///     #     input_left: InputPort<Audio>,
+///     #     #[port_group(group = "main_in", channel = Right)]
+///     #     input_right: InputPort<Audio>,
+///     #     #[port_group(group = "main_out", kind = Stereo, channel = Left)]
+///     #     output_left: OutputPort<Audio>,
+///     #     #[port_group(group = "main_out", channel = Right)]
+///     #     output_right: OutputPort<Audio>,
+///     # }
+///     fn run(ports: &mut StereoPorts) {
+///         let input: InputGroup<Audio, 2> = ports.input_group("main_in").unwrap();
+///         let mut output: OutputGroup<Audio, 2> = ports.output_group("main_out").unwrap();
+///         // Swap the channels.
+///         output.write_frames(input.frames().map(|[left, right]| [right, left]));
+///     }
+///
+/// The group information is part of the ports' [descriptors](#associatedconstant.PORTS) and [metadata](#method.port_info).
+pub trait PortCollection: Sized {
+    /// The type of the port pointer cache.
+    ///
+    /// The host passes port pointers to the plugin one by one and in an undefined order. Therefore, the plugin instance can not collect these pointers in the port collection directly. Instead, the pointers are stored in a cache which is then used to create the proper port collection.
+    type Cache: PortPointerCache;
+
+    /// Try to construct a port collection instance from a port pointer cache.
+    ///
+    /// If one of the port connection pointers is null, this method will return `None`, because a `PortCollection` can not be constructed.
+    ///
+    /// # Safety
+    ///
+    /// Since the pointer cache is only storing the pointers, implementing this method requires the de-referencation of raw pointers and therefore, this method is unsafe.
+    unsafe fn from_connections(cache: &Self::Cache, sample_count: u32) -> Option<Self>;
+
+    /// The descriptors of all ports of the collection, in the order of their indices.
+    ///
+    /// The derive macro creates a descriptor for every field of the struct. The default value is an empty list.
+    const PORTS: &'static [PortDescriptor] = &[];
+
+    /// Describe all ports of the collection, in the order of their indices.
+    ///
+    /// The default implementation creates the metadata from the [port descriptors](#associatedconstant.PORTS).
+    fn port_info() -> Vec<PortInfo> {
+        Self::PORTS.iter().map(PortDescriptor::info).collect()
+    }
+
+    /// Check whether the host is freewheeling, which means that it runs the plugin as fast as possible instead of in real time, for example to render a project offline.
+    ///
+    /// While freewheeling, plugins may switch to higher-quality processing that takes more time. The derive macro reads the port with the [`FreeWheeling`](enum.PortDesignation.html#variant.FreeWheeling) designation, which has to be a [toggle](trait.ToggledPort.html). The default implementation, which is also used if there is no such port, returns `false`.
+    fn is_freewheeling(&self) -> bool {
+        false
+    }
+
+    /// Write silence to all outputs, which is done by the framework after a [failed cycle](../plugin/struct.RunContext.html#method.fail).
+    ///
+    /// Audio and CV outputs are filled with zeros, while other outputs, like control or atom ports, are left untouched. The derive macro silences every port of the collection and the default implementation does nothing.
+    fn silence(&mut self) {}
+
+    /// Call the visitor with the channel and the handle of every port in the given group, in the order of their indices.
+    ///
+    /// The derive macro visits all fields with a `port_group` attribute of the same group. The default implementation doesn't visit any ports.
+    fn visit_group(&self, _group: &str, _visitor: &mut dyn FnMut(PortChannel, &dyn Any)) {}
+
+    /// Call the visitor with the channel and the mutable handle of every port in the given group, in the order of their indices.
+    ///
+    /// The derive macro visits all fields with a `port_group` attribute of the same group. The default implementation doesn't visit any ports.
+    fn visit_group_mut(
+        &mut self,
+        _group: &str,
+        _visitor: &mut dyn FnMut(PortChannel, &mut dyn Any),
+    ) {
+    }
+
+    /// Call `f` for every port of the given group whose handle is of type `T`.
+    ///
+    /// Since this method doesn't allocate memory, it can be used in the `run` method of a plugin.
+    fn for_each_in_group<T: Any, F: FnMut(PortChannel, &T)>(&self, group: &str, mut f: F) {
+        self.visit_group(group, &mut |channel, port| {
+            if let Some(port) = port.downcast_ref::<T>() {
+                f(channel, port);
+            }
+        });
+    }
+
+    /// Call `f` for every port of the given group whose handle is of type `T`, with mutable access to the handle.
+    ///
+    /// Since this method doesn't allocate memory, it can be used in the `run` method of a plugin.
+    fn for_each_in_group_mut<T: Any, F: FnMut(PortChannel, &mut T)>(
+        &mut self,
+        group: &str,
+        mut f: F,
+    ) {
+        self.visit_group_mut(group, &mut |channel, port| {
+            if let Some(port) = port.downcast_mut::<T>() {
+                f(channel, port);
+            }
+        });
+    }
+
+    /// Collect the input ports of type `T` in the given group, to read them frame by frame.
+    ///
+    /// The group has to contain exactly `N` input ports of type `T`, which become the channels in the order of their indices. Otherwise, `None` is returned. Since this method doesn't allocate memory, it can be used in the `run` method of a plugin.
+    fn input_group<T: SamplePortType, const N: usize>(
+        &self,
+        group: &str,
+    ) -> Option<InputGroup<'static, T, N>> {
+        let mut channels: [&'static [T::Sample]; N] = [&[]; N];
+        let mut count = 0;
+        self.for_each_in_group(group, |_, port: &InputPort<T>| {
+            if count < N {
+                channels[count] = **port;
+            }
+            count += 1;
+        });
+        if count == N {
+            Some(InputGroup::new(channels))
+        } else {
+            None
+        }
+    }
+
+    /// Collect the output ports of type `T` in the given group, to write them frame by frame.
+    ///
+    /// The group has to contain exactly `N` output ports of type `T`, which become the channels in the order of their indices. Otherwise, `None` is returned. Since this method doesn't allocate memory, it can be used in the `run` method of a plugin.
+    fn output_group<T: SamplePortType, const N: usize>(
+        &mut self,
+        group: &str,
+    ) -> Option<OutputGroup<'_, T, N>> {
+        let mut pointers: [(*mut T::Sample, usize); N] = [(null_mut(), 0); N];
+        let mut count = 0;
+        self.for_each_in_group_mut(group, |_, port: &mut OutputPort<T>| {
+            if count < N {
+                pointers[count] = (port.as_mut_ptr(), port.len());
+            }
+            count += 1;
+        });
+        if count != N {
+            return None;
+        }
+        // Every channel is the data of another port, and the ports stay borrowed as long as the group exists.
+        let channels =
+            pointers.map(|(pointer, len)| unsafe { core::slice::from_raw_parts_mut(pointer, len) });
+        Some(OutputGroup::new(channels))
+    }
+}
+
+impl PortCollection for () {
+    type Cache = ();
+
+    unsafe fn from_connections(_cache: &(), _sample_count: u32) -> Option<Self> {
+        Some(())
+    }
+}
+
+/// Cache for port connection pointers.
+///
+/// The host will pass the port connection pointers one by one and in an undefined order. Therefore, the `PortCollection` struct can not be created instantly. Instead, the pointers will be stored in a cache, which is then used to create a proper port collection for the plugin.
+pub trait PortPointerCache: Sized + Default {
+    /// Store the connection pointer for the port with index `index`.
+    ///
+    /// The passed pointer may not be valid yet and therefore, implementors should only store the pointer, not dereference it.
+    fn connect(&mut self, index: u32, pointer: *mut c_void);
+}
+
+impl PortPointerCache for () {
+    fn connect(&mut self, _index: u32, _pointer: *mut c_void) {}
+}
@@ -0,0 +1,167 @@
+//! Smoothing of control values.
+use super::{Control, InputPort};
+
+/// The shape of the ramp a [`SmoothedControl`](struct.SmoothedControl.html) follows to a new value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ramp {
+    /// The value changes by the same amount in every frame.
+    Linear,
+    /// The value approaches the new value like a one-pole low-pass filter: It changes quickly at first and slows down towards the end. The last step of the ramp is less than one percent of the whole change.
+    Exponential,
+}
+
+/// A control value that follows changes of its port smoothly.
+///
+/// Control ports only have one value per `run` call. Applying a new value immediately creates steps in the signal, which are audible as clicks or zipper noise. A `SmoothedControl` is kept by the plugin and updated with the value of the port in every `run` call. It then ramps from its current value to the new one over a configurable number of frames, either sample by sample with [`next_value`](#method.next_value) and [`fill`](#method.fill) or block by block with [`advance`](#method.advance).
+///
+/// # Example
+///
+/// ```
+/// use lv2_core::prelude::*;
+///
+/// #[derive(PortCollection)]
+/// struct Ports {
+///     gain: InputPort<Control>,
+///     input: InputPort<Audio>,
+///     output: OutputPort<Audio>,
+/// }
+///
+/// fn run(gain: &mut SmoothedControl, ports: &mut Ports) {
+///     gain.update(&ports.gain);
+///     for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+///         *output = input * gain.next_value();
+///     }
+/// }
+///
+/// // Ramp to new values over 20 ms at 48 kHz.
+/// let mut gain = SmoothedControl::from_duration(Ramp::Linear, 1.0, 48000.0, 0.02);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SmoothedControl {
+    ramp: Ramp,
+    length: u32,
+    value: f32,
+    target: f32,
+    remaining: u32,
+    step: f32,
+}
+
+impl SmoothedControl {
+    /// Create a smoothed control with the given initial value that ramps to new values over the given number of frames.
+    ///
+    /// A ramp length of zero applies new values immediately.
+    pub fn new(ramp: Ramp, value: f32, length: u32) -> Self {
+        Self {
+            ramp,
+            length,
+            value,
+            target: value,
+            remaining: 0,
+            step: 0.0,
+        }
+    }
+
+    /// Create a smoothed control with the given initial value that ramps to new values over the given number of seconds.
+    pub fn from_duration(ramp: Ramp, value: f32, sample_rate: f64, seconds: f64) -> Self {
+        Self::new(ramp, value, (sample_rate * seconds + 0.5) as u32)
+    }
+
+    /// Return the current value.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Return the value the control is ramping to.
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Check whether the control is ramping to a new value.
+    pub fn is_smoothing(&self) -> bool {
+        self.remaining > 0
+    }
+
+    /// Set the number of frames a ramp takes, which is used from the next new value on.
+    pub fn set_ramp_length(&mut self, length: u32) {
+        self.length = length;
+    }
+
+    /// Start a ramp to a new value.
+    ///
+    /// If the value is the current target, the ramp that is in progress continues unchanged.
+    pub fn set_target(&mut self, target: f32) {
+        if target == self.target {
+            return;
+        }
+        self.target = target;
+        if self.length == 0 {
+            self.reset(target);
+            return;
+        }
+        self.remaining = self.length;
+        self.step = match self.ramp {
+            Ramp::Linear => (target - self.value) / self.length as f32,
+            // After `length` frames, less than one percent of the change is missing.
+            Ramp::Exponential => (5.0 / self.length as f32).min(1.0),
+        };
+    }
+
+    /// Start a ramp to the current value of a control port.
+    pub fn update(&mut self, port: &InputPort<Control>) {
+        self.set_target(**port);
+    }
+
+    /// Jump to a value, without a ramp.
+    pub fn reset(&mut self, value: f32) {
+        self.value = value;
+        self.target = value;
+        self.remaining = 0;
+    }
+
+    /// Advance the ramp by one frame and return the new value.
+    #[inline]
+    pub fn next_value(&mut self) -> f32 {
+        if self.remaining == 0 {
+            return self.value;
+        }
+        self.remaining -= 1;
+        self.value = if self.remaining == 0 {
+            self.target
+        } else {
+            match self.ramp {
+                Ramp::Linear => self.value + self.step,
+                Ramp::Exponential => self.value + (self.target - self.value) * self.step,
+            }
+        };
+        self.value
+    }
+
+    /// Fill a buffer with the values of the next frames.
+    pub fn fill(&mut self, buffer: &mut [f32]) {
+        for value in buffer.iter_mut() {
+            *value = self.next_value();
+        }
+    }
+
+    /// Advance the ramp by a block of frames and return the value at its end.
+    ///
+    /// This is useful for parameters that are only updated once per block, like filter coefficients.
+    pub fn advance(&mut self, frames: u32) -> f32 {
+        if frames >= self.remaining {
+            self.reset(self.target);
+            return self.value;
+        }
+        match self.ramp {
+            Ramp::Linear => {
+                self.remaining -= frames;
+                self.value = self.target - self.step * self.remaining as f32;
+            }
+            Ramp::Exponential => {
+                for _ in 0..frames {
+                    self.next_value();
+                }
+            }
+        }
+        self.value
+    }
+}
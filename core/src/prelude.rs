@@ -1,9 +1,16 @@
 //! Prelude for wildcard use, containing many important types.
+#[doc(hidden)]
+pub use crate::__derive;
 pub use crate::extension::ExtensionDescriptor;
-pub use crate::feature::{FeatureCache, FeatureCollection, MissingFeatureError, ThreadingClass};
+pub use crate::fail_cycle;
+pub use crate::feature::{
+    FeatureCache, FeatureCollection, FeatureError, FeatureInfo, ThreadingClass,
+};
 pub use crate::match_extensions;
 pub use crate::plugin::{
-    lv2_descriptors, Plugin, PluginInfo, PluginInstance, PluginInstanceDescriptor, PortCollection,
+    extensions, flush_denormal, lv2_descriptors, lv2_library, plugin_version, DenormalGuard,
+    EmbeddedPlugin, InstantiationError, Plugin, PluginClass, PluginInfo, PluginInstance,
+    PluginInstanceDescriptor, PluginVersion, PortCollection, RunContext, TransportState,
 };
 pub use crate::port::*;
 pub use crate::sys::LV2_Descriptor;
@@ -1,4 +1,4 @@
-use lv2_core::feature::{FeatureCache, FeatureCollection, MissingFeatureError};
+use lv2_core::feature::{FeatureCache, FeatureCollection, FeatureError};
 use lv2_core::feature::{HardRTCapable, IsLive};
 use lv2_core::prelude::*;
 use std::ops::Drop;
@@ -53,7 +53,7 @@ impl Plugin for Amp {
     }
 
     #[inline]
-    fn run(&mut self, ports: &mut AmpPorts, _: &mut ()) {
+    fn run(&mut self, ports: &mut AmpPorts, _: &mut RunContext<()>) {
         assert!(self.activated);
 
         let coef = *(ports.gain);
@@ -0,0 +1,64 @@
+use lv2_core::prelude::*;
+
+fn run(bypass: &mut Bypass, enabled: f32, dry: &[f32], output: &mut [f32]) {
+    let dry = InputGroup::<Audio, 1>::new([dry]);
+    let mut output = OutputGroup::<Audio, 1>::new([output]);
+    bypass.apply(enabled, &dry, &mut output);
+}
+
+#[test]
+fn test_bypass_ramp() {
+    let dry = [0.0; 6];
+    let mut bypass = Bypass::new(4);
+    assert_eq!(bypass.mix(), 1.0);
+
+    // Enabled plugins keep their output.
+    let mut output = [1.0; 6];
+    run(&mut bypass, 1.0, &dry, &mut output);
+    assert_eq!(output, [1.0; 6]);
+    assert!(!bypass.is_bypassed(0.0));
+
+    // Bypassing fades to the dry signal.
+    let mut output = [1.0; 6];
+    run(&mut bypass, 0.0, &dry, &mut output);
+    assert_eq!(output, [0.75, 0.5, 0.25, 0.0, 0.0, 0.0]);
+    assert!(bypass.is_bypassed(0.0));
+    assert!(!bypass.is_bypassed(1.0));
+
+    // The output of a bypassed plugin is ignored.
+    let mut output = [f32::NAN; 6];
+    run(&mut bypass, 0.0, &dry, &mut output);
+    assert_eq!(output, [0.0; 6]);
+
+    // Enabling fades back in.
+    let mut output = [1.0; 2];
+    run(&mut bypass, 1.0, &dry[..2], &mut output);
+    assert_eq!(output, [0.25, 0.5]);
+    assert_eq!(bypass.mix(), 0.5);
+}
+
+#[test]
+fn test_bypass_channels() {
+    let left = [1.0; 2];
+    let right = [-1.0; 2];
+    let mut output_left = [0.0; 2];
+    let mut output_right = [0.0; 2];
+
+    let mut bypass = Bypass::new(2);
+    let dry = InputGroup::<Audio, 2>::new([&left, &right]);
+    let mut output = OutputGroup::<Audio, 2>::new([&mut output_left, &mut output_right]);
+    bypass.apply(0.0, &dry, &mut output);
+
+    // Both channels are faded with the same ramp.
+    assert_eq!(output_left, [0.5, 1.0]);
+    assert_eq!(output_right, [-0.5, -1.0]);
+}
+
+#[test]
+fn test_bypass_without_ramp() {
+    let mut bypass = Bypass::default();
+    let mut output = [1.0; 2];
+    run(&mut bypass, 0.0, &[0.0; 2], &mut output);
+    assert_eq!(output, [0.0; 2]);
+    assert_eq!(Bypass::from_duration(48000.0, 0.01), Bypass::new(480));
+}
@@ -0,0 +1,142 @@
+use core::hint::black_box;
+use lv2_core::prelude::*;
+use std::path::Path;
+use urid::*;
+
+/// Halve the smallest normal number, which results in a denormal number unless it's flushed.
+fn halve_minimum() -> f32 {
+    black_box(f32::MIN_POSITIVE) * black_box(0.5)
+}
+
+#[test]
+fn test_denormal_guard() {
+    assert!(halve_minimum().is_subnormal());
+    {
+        let _guard = DenormalGuard::new();
+        if DenormalGuard::is_supported() {
+            assert_eq!(halve_minimum(), 0.0);
+        }
+        {
+            let _nested = DenormalGuard::new();
+        }
+        if DenormalGuard::is_supported() {
+            assert_eq!(halve_minimum(), 0.0);
+        }
+    }
+    // The previous modes are restored.
+    assert!(halve_minimum().is_subnormal());
+}
+
+#[test]
+fn test_flush_denormal() {
+    assert_eq!(flush_denormal(f32::MIN_POSITIVE / 2.0), 0.0);
+    assert_eq!(flush_denormal(-f32::MIN_POSITIVE / 4.0), 0.0);
+    assert_eq!(flush_denormal(f32::MIN_POSITIVE), f32::MIN_POSITIVE);
+    assert_eq!(flush_denormal(-1.5), -1.5);
+}
+
+#[derive(PortCollection)]
+struct Ports {
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+/// What a plugin has seen in its cycles.
+#[derive(Default)]
+struct Cycles {
+    run: u32,
+    empty: u32,
+    denormal: bool,
+}
+
+impl Cycles {
+    fn record(&mut self, ports: &mut Ports, context: &RunContext<()>) {
+        assert_eq!(context.sample_count() as usize, ports.input.len());
+        assert!(!context.is_freewheeling());
+        self.run += 1;
+        if context.is_empty() {
+            self.empty += 1;
+        }
+        self.denormal = halve_minimum().is_subnormal();
+        ports.output.copy_from_slice(&ports.input);
+    }
+}
+
+/// A plugin that lets the framework flush denormal numbers and skip empty cycles.
+#[uri("urn:lv2_core:test:guarded")]
+struct Guarded(Cycles);
+
+impl Plugin for Guarded {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    const FLUSH_DENORMALS: bool = true;
+    const SKIP_EMPTY_CYCLES: bool = true;
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self(Cycles::default()))
+    }
+
+    fn run(&mut self, ports: &mut Ports, context: &mut RunContext<()>) {
+        self.0.record(ports, context);
+    }
+}
+
+/// A plugin with the default behaviour.
+#[uri("urn:lv2_core:test:unguarded")]
+struct Unguarded(Cycles);
+
+impl Plugin for Unguarded {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self(Cycles::default()))
+    }
+
+    fn run(&mut self, ports: &mut Ports, context: &mut RunContext<()>) {
+        self.0.record(ports, context);
+    }
+}
+
+/// Run the plugin for an empty cycle and a cycle of four frames, and inspect it afterwards.
+fn run_cycles<T: Plugin>(inspect: impl FnOnce(&T)) {
+    let info = PluginInfo::new(T::uri(), Path::new("/"), 44100.0);
+    let mut plugin = unsafe { EmbeddedPlugin::<T>::new(&info, std::ptr::null()) }.unwrap();
+    let mut input = [1.0f32, 0.5, -0.5, -1.0];
+    let mut output = [0.0f32; 4];
+    unsafe {
+        plugin.connect_port(0, input.as_mut_ptr());
+        plugin.connect_port(1, output.as_mut_ptr());
+    }
+    plugin.activate();
+    assert!(unsafe { plugin.run(0) });
+    assert!(unsafe { plugin.run(4) });
+    assert_eq!(output, input);
+    inspect(plugin.plugin());
+}
+
+#[test]
+fn test_guarded_cycles() {
+    run_cycles(|Guarded(cycles)| {
+        assert_eq!(cycles.run, 1);
+        assert_eq!(cycles.empty, 0);
+        if DenormalGuard::is_supported() {
+            assert!(!cycles.denormal);
+        }
+    });
+
+    // The floating-point modes are restored after the cycle.
+    assert!(halve_minimum().is_subnormal());
+}
+
+#[test]
+fn test_unguarded_cycles() {
+    run_cycles(|Unguarded(cycles)| {
+        assert_eq!(cycles.run, 2);
+        assert_eq!(cycles.empty, 1);
+        assert!(cycles.denormal);
+    });
+}
@@ -0,0 +1,126 @@
+use lv2_core::extension::ExtensionInterface;
+use lv2_core::feature::*;
+use lv2_core::prelude::*;
+use std::any::Any;
+use std::path::Path;
+use urid::*;
+
+#[repr(C)]
+pub struct LatencyInterface {
+    latency: u32,
+}
+
+unsafe impl ExtensionInterface for LatencyInterface {
+    const URI: &'static [u8] = b"urn:lv2_core:test:latency\0";
+}
+
+#[derive(PortCollection)]
+struct Ports {
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+#[derive(FeatureCollection)]
+struct Features {
+    _hard_rt: HardRTCapable,
+}
+
+#[uri("urn:lv2_core:test:delay")]
+struct Delay {
+    last: f32,
+    activations: u32,
+}
+
+impl Plugin for Delay {
+    type Ports = Ports;
+    type InitFeatures = Features;
+    type AudioFeatures = ();
+
+    fn new(plugin_info: &PluginInfo, _: &mut Features) -> Option<Self> {
+        if plugin_info.sample_rate() <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            last: 0.0,
+            activations: 0,
+        })
+    }
+
+    fn activate(&mut self, _: &mut Features) {
+        self.last = 0.0;
+        self.activations += 1;
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = self.last;
+            self.last = *input;
+        }
+    }
+
+    fn extension_data(uri: &Uri) -> Option<&'static dyn Any> {
+        if uri == LatencyInterface::uri() {
+            Some(&LatencyInterface { latency: 1 })
+        } else {
+            None
+        }
+    }
+}
+
+fn info(sample_rate: f64) -> PluginInfo<'static> {
+    PluginInfo::new(Delay::uri(), Path::new("/"), sample_rate)
+}
+
+/// Create the hard real-time capability feature and its null-terminated list.
+fn hard_rt() -> (Box<lv2_sys::LV2_Feature>, [*const lv2_sys::LV2_Feature; 2]) {
+    let feature = Box::new(lv2_sys::LV2_Feature {
+        URI: HardRTCapable::URI.as_ptr() as *const std::ffi::c_char,
+        data: std::ptr::null_mut(),
+    });
+    let list = [feature.as_ref() as *const _, std::ptr::null()];
+    (feature, list)
+}
+
+#[test]
+fn test_embedded_lifecycle() {
+    let (_feature, features) = hard_rt();
+    let mut plugin =
+        unsafe { EmbeddedPlugin::<Delay>::new(&info(44100.0), features.as_ptr()) }.unwrap();
+    let mut input = [1.0f32, 2.0, 3.0];
+    let mut output = [0.0f32; 3];
+
+    // The plugin isn't run while it's inactive or while a port isn't connected.
+    unsafe { plugin.connect_port(0, input.as_mut_ptr()) };
+    plugin.activate();
+    assert!(!unsafe { plugin.run(3) });
+    plugin.deactivate();
+    unsafe { plugin.connect_port(1, output.as_mut_ptr()) };
+    assert!(!unsafe { plugin.run(3) });
+
+    plugin.activate();
+    plugin.activate();
+    assert!(plugin.is_active());
+    assert_eq!(plugin.plugin().activations, 2);
+    assert!(unsafe { plugin.run(3) });
+    assert_eq!(output, [0.0, 1.0, 2.0]);
+    assert_eq!(plugin.plugin_mut().last, 3.0);
+
+    assert_eq!(plugin.extension::<LatencyInterface>().unwrap().latency, 1);
+    assert!(plugin
+        .extension::<lv2_sys::LV2_Worker_Interface>()
+        .is_none());
+    assert!(!plugin.handle().is_null());
+}
+
+#[test]
+fn test_embedded_instantiation_errors() {
+    let result = unsafe { EmbeddedPlugin::<Delay>::new(&info(44100.0), std::ptr::null()) };
+    match result.err() {
+        Some(InstantiationError::Feature(FeatureError::Missing { .. })) => (),
+        _ => panic!("The missing feature wasn't reported"),
+    }
+
+    let (_feature, features) = hard_rt();
+    let result = unsafe { EmbeddedPlugin::<Delay>::new(&info(0.0), features.as_ptr()) };
+    assert_eq!(result.err(), Some(InstantiationError::PluginFailed));
+}
@@ -0,0 +1,235 @@
+use lv2_core::extension::{resolve_features, ExtensionCall};
+use lv2_core::prelude::*;
+use lv2_sys::LV2_Feature;
+use std::any::Any;
+use std::ffi::{c_char, c_void, CStr};
+use std::marker::PhantomData;
+use urid::*;
+
+/// An extension that reports a number to the host.
+pub trait Number: Plugin {
+    const NUMBER: u32;
+}
+
+#[repr(C)]
+pub struct NumberInterface {
+    number: u32,
+}
+
+/// The regular descriptor of the `Number` extension.
+pub struct NumberDescriptor<P: Number> {
+    plugin: PhantomData<P>,
+}
+
+unsafe impl<P: Number> UriBound for NumberDescriptor<P> {
+    const URI: &'static [u8] = b"urn:lv2_core:test:number\0";
+}
+
+impl<P: Number> ExtensionDescriptor for NumberDescriptor<P> {
+    type ExtensionInterface = NumberInterface;
+
+    const INTERFACE: &'static NumberInterface = &NumberInterface { number: P::NUMBER };
+}
+
+/// Another descriptor with the same URI, which always reports 42.
+pub struct AnswerDescriptor<P: Number> {
+    plugin: PhantomData<P>,
+}
+
+unsafe impl<P: Number> UriBound for AnswerDescriptor<P> {
+    const URI: &'static [u8] = b"urn:lv2_core:test:number\0";
+}
+
+impl<P: Number> ExtensionDescriptor for AnswerDescriptor<P> {
+    type ExtensionInterface = NumberInterface;
+
+    const INTERFACE: &'static NumberInterface = &NumberInterface { number: 42 };
+}
+
+/// A descriptor that isn't generic over the plugin.
+pub struct NameDescriptor;
+
+unsafe impl UriBound for NameDescriptor {
+    const URI: &'static [u8] = b"urn:lv2_core:test:name\0";
+}
+
+impl ExtensionDescriptor for NameDescriptor {
+    type ExtensionInterface = &'static str;
+
+    const INTERFACE: &'static &'static str = &"extensions";
+}
+
+#[uri("urn:lv2_core:test:ordered")]
+struct Ordered;
+
+#[extensions(NumberDescriptor, AnswerDescriptor, NameDescriptor<>)]
+impl Plugin for Ordered {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+impl Number for Ordered {
+    const NUMBER: u32 = 1;
+}
+
+#[uri("urn:lv2_core:test:prioritized")]
+struct Prioritized;
+
+#[extensions(NumberDescriptor, AnswerDescriptor<Self>(priority = 1))]
+impl Plugin for Prioritized {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+impl Number for Prioritized {
+    const NUMBER: u32 = 2;
+}
+
+fn number<P: Plugin>() -> Option<u32> {
+    let uri = CStr::from_bytes_with_nul(b"urn:lv2_core:test:number\0").unwrap();
+    P::extension_data(uri)
+        .and_then(|interface: &dyn Any| interface.downcast_ref::<NumberInterface>())
+        .map(|interface| interface.number)
+}
+
+#[test]
+fn test_extension_data() {
+    assert_eq!(number::<Ordered>(), Some(1));
+    assert_eq!(number::<Prioritized>(), Some(42));
+
+    let name = Ordered::extension_data(NameDescriptor::uri())
+        .and_then(|interface| interface.downcast_ref::<&str>());
+    assert_eq!(name, Some(&"extensions"));
+
+    let unknown = CStr::from_bytes_with_nul(b"urn:lv2_core:test:unknown\0").unwrap();
+    assert!(Ordered::extension_data(unknown).is_none());
+    assert!(Prioritized::extension_data(NameDescriptor::uri()).is_none());
+}
+
+#[test]
+fn test_extension_table() {
+    assert_eq!(
+        Ordered::EXTENSIONS,
+        &[
+            b"urn:lv2_core:test:number\0" as &[u8],
+            b"urn:lv2_core:test:number\0",
+            b"urn:lv2_core:test:name\0",
+        ]
+    );
+    assert_eq!(Prioritized::EXTENSIONS.len(), 2);
+}
+
+#[test]
+fn test_raw_extension_data() {
+    let uri = CStr::from_bytes_with_nul(b"urn:lv2_core:test:number\0").unwrap();
+    let interface = unsafe { PluginInstance::<Prioritized>::extension_data(uri.as_ptr()) };
+    assert!(!interface.is_null());
+    assert_eq!(
+        unsafe { (*(interface as *const NumberInterface)).number },
+        42
+    );
+}
+
+/// An extension that counts how often it was called.
+pub trait Counter: Plugin {
+    fn count(&mut self) -> u32;
+}
+
+#[repr(C)]
+pub struct CounterInterface {
+    count: unsafe extern "C" fn(handle: *mut c_void) -> u32,
+}
+
+pub struct CounterDescriptor<P: Counter> {
+    plugin: PhantomData<P>,
+}
+
+unsafe impl<P: Counter> UriBound for CounterDescriptor<P> {
+    const URI: &'static [u8] = b"urn:lv2_core:test:counter\0";
+}
+
+impl<P: Counter> CounterDescriptor<P> {
+    unsafe extern "C" fn extern_count(handle: *mut c_void) -> u32 {
+        ExtensionCall::<P>::from_handle(handle)
+            .audio_class(|plugin, _| plugin.count())
+            .unwrap_or(0)
+    }
+}
+
+impl<P: Counter> ExtensionDescriptor for CounterDescriptor<P> {
+    type ExtensionInterface = CounterInterface;
+
+    const INTERFACE: &'static CounterInterface = &CounterInterface {
+        count: Self::extern_count,
+    };
+}
+
+#[uri("urn:lv2_core:test:counting")]
+struct Counting {
+    calls: u32,
+}
+
+#[extensions(CounterDescriptor)]
+impl Plugin for Counting {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self { calls: 0 })
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+impl Counter for Counting {
+    fn count(&mut self) -> u32 {
+        self.calls += 1;
+        self.calls
+    }
+}
+
+lv2_descriptors!(Counting);
+
+#[test]
+fn test_extension_call() {
+    let features: &[*const LV2_Feature] = &[std::ptr::null()];
+    unsafe {
+        let descriptor = lv2_descriptor(0).as_ref().unwrap();
+        let handle = (descriptor.instantiate.unwrap())(
+            descriptor,
+            44100.0,
+            b"/\0".as_ptr() as *const c_char,
+            features.as_ptr(),
+        );
+        assert!(!handle.is_null());
+
+        let interface = (descriptor.extension_data.unwrap())(
+            CounterDescriptor::<Counting>::URI.as_ptr() as *const c_char,
+        ) as *const CounterInterface;
+        let count = (*interface).count;
+        assert_eq!(count(handle), 1);
+        assert_eq!(count(handle), 2);
+        assert_eq!(count(std::ptr::null_mut()), 0);
+
+        (descriptor.cleanup.unwrap())(handle);
+
+        let resolved: Result<(), FeatureError> =
+            resolve_features(features.as_ptr(), ThreadingClass::Other);
+        assert!(resolved.is_ok());
+    }
+}
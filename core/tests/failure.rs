@@ -0,0 +1,126 @@
+use lv2_core::prelude::*;
+use lv2_sys::*;
+use std::os::raw::c_char;
+use urid::*;
+
+#[uri("urn:lv2_core:test:gate")]
+struct Gate {
+    failures: u32,
+}
+
+#[derive(PortCollection)]
+struct Ports {
+    threshold: InputPort<Control>,
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+    level: OutputPort<Control>,
+    cv: Option<OutputPort<CV>>,
+}
+
+const NEGATIVE_THRESHOLD: &str = "the threshold is negative";
+
+impl Plugin for Gate {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self { failures: 0 })
+    }
+
+    fn run(&mut self, ports: &mut Ports, context: &mut RunContext<()>) {
+        // The output is written before the cycle fails, and then silenced by the framework.
+        ports.output.copy_from_slice(&ports.input);
+        **ports.level = 1.0;
+        if *ports.threshold < 0.0 {
+            fail_cycle!(context, NEGATIVE_THRESHOLD);
+        }
+        if ports.threshold.is_nan() {
+            fail_cycle!(context, "the threshold isn't a number");
+        }
+        for sample in ports.output.iter_mut() {
+            if sample.abs() < *ports.threshold {
+                *sample = 0.0;
+            }
+        }
+    }
+
+    fn cycle_failed(&mut self, ports: &mut Ports, context: &mut RunContext<()>) {
+        assert!(context.failure().is_some());
+        assert!(ports.output.iter().all(|sample| *sample == 0.0));
+        self.failures += 1;
+    }
+}
+
+lv2_descriptors!(Gate);
+
+#[test]
+fn test_failed_cycles() {
+    let mut threshold: Box<f32> = Box::new(0.5);
+    let mut input: [f32; 4] = [0.25, 1.0, -0.75, 0.0];
+    let mut output: [f32; 4] = [0.0; 4];
+    let mut level: Box<f32> = Box::new(0.0);
+    let features: &[*const LV2_Feature] = &[std::ptr::null()];
+
+    unsafe {
+        let descriptor: &LV2_Descriptor = lv2_descriptor(0).as_ref().unwrap();
+        let plugin: LV2_Handle = (descriptor.instantiate.unwrap())(
+            descriptor,
+            44100.0,
+            "/home/lv2/gate.lv2/\0".as_ptr() as *const c_char,
+            features.as_ptr(),
+        );
+        assert_ne!(plugin, std::ptr::null_mut());
+        let instance = &mut *(plugin as *mut PluginInstance<Gate>);
+
+        let connect_port = descriptor.connect_port.unwrap();
+        (connect_port)(plugin, 0, (&mut *threshold) as *mut f32 as *mut _);
+        (connect_port)(plugin, 1, input.as_mut_ptr() as *mut _);
+        (connect_port)(plugin, 2, output.as_mut_ptr() as *mut _);
+        (connect_port)(plugin, 3, (&mut *level) as *mut f32 as *mut _);
+        (descriptor.activate.unwrap())(plugin);
+        let run = descriptor.run.unwrap();
+
+        // A cycle that succeeds.
+        run(plugin, 4);
+        assert_eq!(output, [0.0, 1.0, -0.75, 0.0]);
+        assert_eq!(instance.last_failure(), None);
+        assert_eq!(instance.plugin_handle().failures, 0);
+
+        // A cycle that fails with a constant reason. Audio outputs are silenced, while control outputs are left untouched.
+        *threshold = -1.0;
+        *level = 0.0;
+        run(plugin, 4);
+        assert_eq!(output, [0.0; 4]);
+        assert_eq!(*level, 1.0);
+        assert_eq!(instance.last_failure(), Some(NEGATIVE_THRESHOLD));
+        assert_eq!(instance.plugin_handle().failures, 1);
+
+        // A cycle that fails with a literal reason, which is prefixed with the location.
+        *threshold = f32::NAN;
+        run(plugin, 4);
+        let reason = instance.last_failure().unwrap();
+        assert!(reason.starts_with(file!()));
+        assert!(reason.ends_with(": the threshold isn't a number"));
+        assert_eq!(instance.plugin_handle().failures, 2);
+
+        // The last failure is kept after a successful cycle.
+        *threshold = 0.0;
+        run(plugin, 4);
+        assert_eq!(output, input);
+        assert_eq!(instance.last_failure(), Some(reason));
+
+        (descriptor.deactivate.unwrap())(plugin);
+        (descriptor.cleanup.unwrap())(plugin);
+    }
+}
+
+#[test]
+fn test_context_failure() {
+    let mut features = ();
+    let mut context = RunContext::new(16, &mut features);
+    assert_eq!(context.failure(), None);
+    context.fail("first");
+    context.fail("second");
+    assert_eq!(context.failure(), Some("first"));
+}
@@ -0,0 +1,75 @@
+use lv2_core::abi::*;
+use lv2_core::prelude::*;
+use std::ffi::CStr;
+use urid::*;
+
+#[uri("urn:lv2_core:test:silence")]
+struct Silence;
+
+impl Plugin for Silence {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+#[lv2_library(name = "lv2_core_test")]
+lv2_descriptors!(Silence);
+
+extern "C" {
+    // The same static, looked up by its exported symbol.
+    static rust_lv2_library_lv2_core_test: [u8; 20];
+}
+
+#[test]
+fn test_library_symbol() {
+    let identification = CStr::from_bytes_with_nul(&__LV2_LIBRARY).unwrap();
+    assert_eq!(
+        identification.to_str().unwrap(),
+        format!("lv2_core_test {}", env!("CARGO_PKG_VERSION"))
+    );
+    assert_eq!(
+        unsafe { &rust_lv2_library_lv2_core_test[..] },
+        &__LV2_LIBRARY[..]
+    );
+
+    unsafe {
+        let descriptor = lv2_descriptor(0).as_ref().unwrap();
+        assert_eq!(Uri::from_ptr(descriptor.URI), Silence::uri());
+        assert!(lv2_descriptor(1).is_null());
+    }
+}
+
+#[test]
+fn test_abi_info() {
+    let info = unsafe { AbiInfo::from_function(rust_lv2_abi) }.unwrap();
+    assert!(std::ptr::eq(info, AbiInfo::current()));
+    assert_eq!(info.version, ABI_VERSION);
+    assert_eq!(info.size as usize, std::mem::size_of::<AbiInfo>());
+    assert_eq!(
+        info.core_version(),
+        Some(AbiInfo::current().core_version().unwrap())
+    );
+    assert!(!info.core_version().unwrap().is_empty());
+
+    // Libraries that follow another version of the contract are only driven through LV2.
+    static OTHER: AbiInfo = AbiInfo {
+        size: 16,
+        version: ABI_VERSION + 1,
+        core_version: std::ptr::null(),
+    };
+    unsafe extern "C" fn other_abi() -> *const AbiInfo {
+        &OTHER
+    }
+    unsafe extern "C" fn no_abi() -> *const AbiInfo {
+        std::ptr::null()
+    }
+    assert!(unsafe { AbiInfo::from_function(other_abi) }.is_none());
+    assert!(unsafe { AbiInfo::from_function(no_abi) }.is_none());
+    assert_eq!(OTHER.core_version(), None);
+}
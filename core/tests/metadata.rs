@@ -0,0 +1,106 @@
+use lv2_core::feature::{HardRTCapable, IsLive};
+use lv2_core::prelude::*;
+use urid::*;
+
+#[derive(PortCollection)]
+struct Ports {
+    ceiling: InputPort<Control>,
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+#[derive(FeatureCollection)]
+struct Features {
+    _hard_rt_capable: HardRTCapable,
+    _is_live: Option<IsLive>,
+}
+
+#[uri("urn:lv2_core:test:limiter")]
+struct Limiter;
+
+impl Plugin for Limiter {
+    type Ports = Ports;
+    type InitFeatures = Features;
+    type AudioFeatures = ();
+
+    const CLASSES: &'static [PluginClass] = &[PluginClass::Limiter, PluginClass::Dynamics];
+
+    fn new(_: &PluginInfo, _: &mut Features) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        let ceiling = ports.ceiling.abs();
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = input.clamp(-ceiling, ceiling);
+        }
+    }
+}
+
+#[uri("urn:lv2_core:test:through")]
+struct Through;
+
+impl Plugin for Through {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        ports.output.copy_from_slice(&ports.input);
+    }
+}
+
+#[test]
+fn test_classes() {
+    assert_eq!(
+        Limiter::CLASSES,
+        &[PluginClass::Limiter, PluginClass::Dynamics]
+    );
+    let class = Limiter::CLASSES[0].uri();
+    assert_eq!(
+        class.to_str().unwrap(),
+        "http://lv2plug.in/ns/lv2core#LimiterPlugin"
+    );
+    assert_eq!(PluginClass::from_uri(class), Some(PluginClass::Limiter));
+    assert_eq!(PluginClass::from_uri(Limiter::uri()), None);
+
+    // Plugins without classes are only an `lv2:Plugin`.
+    assert!(Through::CLASSES.is_empty());
+}
+
+#[test]
+fn test_ports() {
+    let ports = Ports::port_info();
+    assert_eq!(ports.len(), 3);
+    assert_eq!(ports[0].index, 0);
+    assert_eq!(ports[0].symbol, "ceiling");
+    assert_eq!(ports[0].class, Some(<Control>::uri()));
+    assert_eq!(ports[0].direction, Some(PortDirection::Input));
+    assert_eq!(ports[0].unit, None);
+    assert_eq!(ports[2].symbol, "output");
+    assert_eq!(ports[2].class, Some(Audio::uri()));
+    assert_eq!(ports[2].direction, Some(PortDirection::Output));
+    assert!(!ports[2].optional);
+    assert!(<() as PortCollection>::port_info().is_empty());
+}
+
+#[test]
+fn test_features() {
+    assert_eq!(
+        Features::features(),
+        vec![
+            FeatureInfo {
+                uri: HardRTCapable::uri(),
+                optional: false,
+            },
+            FeatureInfo {
+                uri: IsLive::uri(),
+                optional: true,
+            },
+        ]
+    );
+}
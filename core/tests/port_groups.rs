@@ -0,0 +1,145 @@
+use lv2_core::prelude::*;
+
+#[derive(PortCollection)]
+struct Ports {
+    gain: InputPort<Control>,
+    #[port_group(group = "main_in", kind = Stereo, channel = Left)]
+    input_left: InputPort<Audio>,
+    #[port_group(group = "main_in", channel = Right)]
+    input_right: InputPort<Audio>,
+    #[port_group(group = "sidechain", kind = Mono, channel = Center, side_chain_of = "main_in")]
+    sidechain: InputPort<Audio>,
+    #[port_group(group = "main_out", channel = Left)]
+    output_left: OutputPort<Audio>,
+    #[port_group(group = "main_out", kind = Stereo, channel = Right)]
+    output_right: OutputPort<Audio>,
+}
+
+#[test]
+fn test_port_group_info() {
+    let ports = Ports::port_info();
+    assert_eq!(ports[0].group, None);
+    assert_eq!(
+        ports[1].group,
+        Some(PortGroupInfo {
+            symbol: "main_in",
+            kind: PortGroupKind::Stereo,
+            channel: PortChannel::Left,
+            side_chain_of: None,
+        })
+    );
+    assert_eq!(
+        ports[3].group,
+        Some(PortGroupInfo {
+            symbol: "sidechain",
+            kind: PortGroupKind::Mono,
+            channel: PortChannel::Center,
+            side_chain_of: Some("main_in"),
+        })
+    );
+    // The kind is declared by another port of the group.
+    assert_eq!(ports[4].group.unwrap().kind, PortGroupKind::Stereo);
+    assert_eq!(
+        PortGroupKind::Stereo.uri().to_str().unwrap(),
+        "http://lv2plug.in/ns/ext/port-groups#StereoGroup"
+    );
+    assert_eq!(
+        PortChannel::Left.uri().to_str().unwrap(),
+        "http://lv2plug.in/ns/ext/port-groups#left"
+    );
+}
+
+#[test]
+fn test_port_group_iteration() {
+    let mut gain: f32 = 0.5;
+    let mut left: Vec<f32> = vec![1.0; 4];
+    let mut right: Vec<f32> = vec![2.0; 4];
+    let mut sidechain: Vec<f32> = vec![3.0; 4];
+    let mut output_left: Vec<f32> = vec![0.0; 4];
+    let mut output_right: Vec<f32> = vec![0.0; 4];
+
+    let mut cache = <Ports as PortCollection>::Cache::default();
+    cache.connect(0, &mut gain as *mut f32 as *mut _);
+    cache.connect(1, left.as_mut_ptr() as *mut _);
+    cache.connect(2, right.as_mut_ptr() as *mut _);
+    cache.connect(3, sidechain.as_mut_ptr() as *mut _);
+    cache.connect(4, output_left.as_mut_ptr() as *mut _);
+    cache.connect(5, output_right.as_mut_ptr() as *mut _);
+    let mut ports = unsafe { Ports::from_connections(&cache, 4) }.unwrap();
+
+    let mut channels: Vec<(PortChannel, f32)> = Vec::new();
+    ports.for_each_in_group("main_in", |channel, port: &InputPort<Audio>| {
+        channels.push((channel, port[0]))
+    });
+    assert_eq!(
+        channels,
+        vec![(PortChannel::Left, 1.0), (PortChannel::Right, 2.0)]
+    );
+
+    // Ports of other types are skipped.
+    let mut count = 0;
+    ports.for_each_in_group("main_in", |_, _: &OutputPort<Audio>| count += 1);
+    ports.for_each_in_group("unknown", |_, _: &InputPort<Audio>| count += 1);
+    assert_eq!(count, 0);
+
+    let gain = *ports.gain;
+    let sidechain_level = ports.sidechain[0];
+    ports.for_each_in_group_mut("main_out", |channel, port: &mut OutputPort<Audio>| {
+        let value = match channel {
+            PortChannel::Left => gain,
+            _ => sidechain_level,
+        };
+        port.iter_mut().for_each(|sample| *sample = value);
+    });
+    assert_eq!(output_left, vec![0.5; 4]);
+    assert_eq!(output_right, vec![3.0; 4]);
+}
+
+#[test]
+fn test_port_group_interleaving() {
+    let mut gain: f32 = 1.0;
+    let mut left: Vec<f32> = vec![1.0, 2.0, 3.0];
+    let mut right: Vec<f32> = vec![-1.0, -2.0, -3.0];
+    let mut sidechain: Vec<f32> = vec![0.0; 3];
+    let mut output_left: Vec<f32> = vec![0.0; 3];
+    let mut output_right: Vec<f32> = vec![0.0; 3];
+
+    let mut cache = <Ports as PortCollection>::Cache::default();
+    cache.connect(0, &mut gain as *mut f32 as *mut _);
+    cache.connect(1, left.as_mut_ptr() as *mut _);
+    cache.connect(2, right.as_mut_ptr() as *mut _);
+    cache.connect(3, sidechain.as_mut_ptr() as *mut _);
+    cache.connect(4, output_left.as_mut_ptr() as *mut _);
+    cache.connect(5, output_right.as_mut_ptr() as *mut _);
+    let mut ports = unsafe { Ports::from_connections(&cache, 3) }.unwrap();
+
+    // The number of channels has to match.
+    assert!(ports.input_group::<Audio, 1>("main_in").is_none());
+    assert!(ports.input_group::<Audio, 2>("main_out").is_none());
+    assert!(ports.output_group::<Audio, 3>("main_out").is_none());
+
+    let input: InputGroup<Audio, 2> = ports.input_group("main_in").unwrap();
+    assert_eq!(input.len(), 3);
+    assert_eq!(input.frame(1), Some([2.0, -2.0]));
+    assert_eq!(input.frame(3), None);
+    let frames: Vec<[f32; 2]> = input.frames().collect();
+    assert_eq!(frames, vec![[1.0, -1.0], [2.0, -2.0], [3.0, -3.0]]);
+
+    let mut interleaved = [0.0; 5];
+    assert_eq!(input.interleave(&mut interleaved), 2);
+    assert_eq!(interleaved, [1.0, -1.0, 2.0, -2.0, 0.0]);
+
+    {
+        let mut output: OutputGroup<Audio, 2> = ports.output_group("main_out").unwrap();
+        assert_eq!(output.write_frames(input.frames().map(|[l, r]| [r, l])), 3);
+        assert!(output.set_frame(2, [0.5, 0.25]));
+        assert!(!output.set_frame(3, [0.5, 0.25]));
+    }
+    assert_eq!(output_left, vec![-1.0, -2.0, 0.5]);
+    assert_eq!(output_right, vec![1.0, 2.0, 0.25]);
+
+    let mut output: OutputGroup<Audio, 2> = ports.output_group("main_out").unwrap();
+    assert_eq!(output.deinterleave(&[4.0, 5.0, 6.0, 7.0, 8.0]), 2);
+    assert_eq!(output_left, vec![4.0, 6.0, 0.5]);
+    assert_eq!(output_right, vec![5.0, 7.0, 0.25]);
+}
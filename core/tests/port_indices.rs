@@ -0,0 +1,134 @@
+use lv2_core::prelude::*;
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use urid::UriBound;
+
+#[derive(PortCollection)]
+struct Ports {
+    input: InputPort<Control>,
+    #[port(index = 3)]
+    output: OutputPort<Control>,
+    #[port(index = 1)]
+    gain: InputPort<Control>,
+    bias: Option<InputPort<Control>>,
+}
+
+#[test]
+fn test_pinned_indices() {
+    assert_eq!(Ports::PORT_INDEX_INPUT, 0);
+    assert_eq!(Ports::PORT_INDEX_GAIN, 1);
+    assert_eq!(Ports::PORT_INDEX_BIAS, 2);
+    assert_eq!(Ports::PORT_INDEX_OUTPUT, 3);
+
+    assert_eq!(u32::from(PortsIndex::Bias), 2);
+    assert_eq!(PortsIndex::try_from(3), Ok(PortsIndex::Output));
+    assert_eq!(PortsIndex::try_from(4), Err(4));
+
+    // The metadata is sorted by index.
+    let symbols: Vec<(u32, &str)> = Ports::port_info()
+        .iter()
+        .map(|info| (info.index, info.symbol))
+        .collect();
+    assert_eq!(
+        symbols,
+        [(0, "input"), (1, "gain"), (2, "bias"), (3, "output")]
+    );
+}
+
+#[test]
+fn test_pinned_connections() {
+    let mut input: f32 = 1.0;
+    let mut gain: f32 = 2.0;
+    let mut output: f32 = 0.0;
+
+    let mut cache = <Ports as PortCollection>::Cache::default();
+    cache.connect(
+        Ports::PORT_INDEX_INPUT,
+        &mut input as *mut f32 as *mut c_void,
+    );
+    cache.connect(
+        PortsIndex::Gain.into(),
+        &mut gain as *mut f32 as *mut c_void,
+    );
+    cache.connect(3, &mut output as *mut f32 as *mut c_void);
+
+    let mut ports = unsafe { Ports::from_connections(&cache, 1) }.unwrap();
+    assert!(ports.bias.is_none());
+    **ports.output = *ports.input * *ports.gain;
+    assert_eq!(output, 2.0);
+}
+
+/// Enumerate the ports of any port collection, without an instance of it.
+fn count_inputs<P: PortCollection>() -> usize {
+    P::PORTS
+        .iter()
+        .filter(|port| port.direction == Some(PortDirection::Input))
+        .count()
+}
+
+#[test]
+fn test_port_descriptors() {
+    const PORTS: &[PortDescriptor] = Ports::PORTS;
+    assert_eq!(PORTS.len(), 4);
+    assert_eq!(PORTS[2].index, 2);
+    assert_eq!(PORTS[2].symbol, "bias");
+    assert_eq!(PORTS[2].direction, Some(PortDirection::Input));
+    assert!(PORTS[2].optional);
+    assert_eq!(PORTS[3].direction, Some(PortDirection::Output));
+    assert!(!PORTS[3].optional);
+    assert_eq!(PORTS[3].info().class, Some(<Control>::uri()));
+    assert_eq!(count_inputs::<Ports>(), 3);
+    assert_eq!(count_inputs::<()>(), 0);
+}
+
+#[derive(PortCollection)]
+struct DesignatedPorts {
+    #[port(side_chain)]
+    _key: InputPort<Audio>,
+    #[port(index = 2, designation = Enabled)]
+    _enabled: InputPort<Control>,
+    #[port(index = 1, designation = FreeWheeling)]
+    _free_wheeling: InputPort<Control>,
+}
+
+#[test]
+fn test_port_designations() {
+    let ports = DesignatedPorts::port_info();
+    assert!(ports[0].side_chain);
+    assert_eq!(ports[0].designation, None);
+    assert_eq!(ports[1].designation, Some(PortDesignation::FreeWheeling));
+    assert!(!ports[1].side_chain);
+    assert_eq!(ports[2].designation, Some(PortDesignation::Enabled));
+    assert_eq!(
+        PortDesignation::Enabled.uri().to_str().unwrap(),
+        "http://lv2plug.in/ns/lv2core#enabled"
+    );
+    assert_eq!(
+        PortDesignation::Control.uri().to_str().unwrap(),
+        "http://lv2plug.in/ns/lv2core#control"
+    );
+}
+
+#[derive(PortCollection)]
+struct FreeWheelingPorts {
+    #[port(designation = FreeWheeling)]
+    free_wheeling: Option<InputPort<Control>>,
+}
+
+#[test]
+fn test_freewheeling() {
+    let mut free_wheeling: f32 = 1.0;
+    let mut cache = <FreeWheelingPorts as PortCollection>::Cache::default();
+    let ports = unsafe { FreeWheelingPorts::from_connections(&cache, 1) }.unwrap();
+    assert!(!ports.is_freewheeling());
+
+    let pointer = &mut free_wheeling as *mut f32;
+    cache.connect(0, pointer as *mut c_void);
+    let ports = unsafe { FreeWheelingPorts::from_connections(&cache, 1) }.unwrap();
+    assert!(ports.is_freewheeling());
+    assert!(ports.free_wheeling.is_on());
+
+    unsafe { *pointer = 0.0 };
+    let ports = unsafe { FreeWheelingPorts::from_connections(&cache, 1) }.unwrap();
+    assert!(!ports.is_freewheeling());
+}
@@ -0,0 +1,60 @@
+use lv2_core::prelude::*;
+
+#[test]
+fn test_linear_ramp() {
+    let mut control = SmoothedControl::new(Ramp::Linear, 0.0, 4);
+    assert!(!control.is_smoothing());
+    assert_eq!(control.next_value(), 0.0);
+
+    control.set_target(1.0);
+    assert!(control.is_smoothing());
+    let mut values = [0.0; 6];
+    control.fill(&mut values);
+    assert_eq!(values, [0.25, 0.5, 0.75, 1.0, 1.0, 1.0]);
+    assert!(!control.is_smoothing());
+
+    // Blocks end at the same value as single frames.
+    control.set_target(0.0);
+    assert_eq!(control.advance(2), 0.5);
+    assert_eq!(control.next_value(), 0.25);
+    assert_eq!(control.advance(16), 0.0);
+    assert!(!control.is_smoothing());
+}
+
+#[test]
+fn test_exponential_ramp() {
+    let mut control = SmoothedControl::new(Ramp::Exponential, 0.0, 100);
+    control.set_target(1.0);
+
+    let mut values = [0.0; 100];
+    control.fill(&mut values);
+    // The steps get smaller towards the end.
+    assert!(values[1] - values[0] > values[51] - values[50]);
+    assert!(values[98] > 0.99 && values[98] < 1.0);
+    assert_eq!(values[99], 1.0);
+
+    control.set_target(0.0);
+    let value = control.advance(50);
+    assert!(value > 0.0 && value < 0.1);
+    assert_eq!(control.advance(50), 0.0);
+}
+
+#[test]
+fn test_immediate_changes() {
+    let mut control = SmoothedControl::new(Ramp::Linear, 0.0, 0);
+    control.set_target(2.0);
+    assert_eq!(control.value(), 2.0);
+    assert!(!control.is_smoothing());
+
+    let mut control = SmoothedControl::from_duration(Ramp::Linear, 0.0, 1000.0, 0.004);
+    control.set_target(1.0);
+    // Setting the same target again doesn't restart the ramp.
+    control.next_value();
+    control.set_target(1.0);
+    assert_eq!(control.advance(3), 1.0);
+
+    control.set_target(0.0);
+    control.reset(0.5);
+    assert_eq!(control.next_value(), 0.5);
+    assert_eq!(control.target(), 0.5);
+}
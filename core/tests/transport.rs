@@ -0,0 +1,85 @@
+use lv2_core::prelude::*;
+use std::path::Path;
+use urid::*;
+
+#[derive(PortCollection)]
+struct Ports {
+    tempo: OutputPort<Control>,
+    rolling: OutputPort<Control>,
+}
+
+/// A plugin that reports the tempo of the transport and whether it's rolling, or -1 if there is no transport.
+#[uri("urn:lv2_core:test:tempo")]
+struct Tempo;
+
+impl Plugin for Tempo {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut Ports, context: &mut RunContext<()>) {
+        if let Some(transport) = context.transport() {
+            **ports.tempo = transport.beats_per_minute;
+            **ports.rolling = if transport.is_rolling() { 1.0 } else { 0.0 };
+        } else {
+            **ports.tempo = -1.0;
+            **ports.rolling = -1.0;
+        }
+    }
+}
+
+#[test]
+fn test_transport_in_run() {
+    let info = PluginInfo::new(Tempo::uri(), Path::new("/"), 48000.0);
+    let mut plugin = unsafe { EmbeddedPlugin::<Tempo>::new(&info, std::ptr::null()) }.unwrap();
+    let mut tempo = 0.0f32;
+    let mut rolling = 0.0f32;
+    unsafe {
+        plugin.connect_port(0, &mut tempo as *mut f32);
+        plugin.connect_port(1, &mut rolling as *mut f32);
+    }
+    plugin.activate();
+
+    // Without a transport from the host, the context has none either.
+    assert!(unsafe { plugin.run(16) });
+    assert_eq!((tempo, rolling), (-1.0, -1.0));
+
+    plugin.set_transport(Some(TransportState {
+        speed: 1.0,
+        beats_per_minute: 140.0,
+        ..Default::default()
+    }));
+    assert_eq!(
+        plugin.instance_mut().transport().unwrap().beats_per_minute,
+        140.0
+    );
+    assert!(unsafe { plugin.run(16) });
+    assert_eq!((tempo, rolling), (140.0, 1.0));
+
+    // The state is kept until the host changes it.
+    assert!(unsafe { plugin.run(16) });
+    assert_eq!((tempo, rolling), (140.0, 1.0));
+
+    plugin.set_transport(Some(TransportState::default()));
+    assert!(unsafe { plugin.run(16) });
+    assert_eq!((tempo, rolling), (120.0, 0.0));
+
+    plugin.set_transport(None);
+    assert!(unsafe { plugin.run(16) });
+    assert_eq!((tempo, rolling), (-1.0, -1.0));
+}
+
+#[test]
+fn test_context_transport() {
+    let mut features = ();
+    let context = RunContext::new(16, &mut features);
+    assert!(context.transport().is_none());
+
+    let transport = TransportState::default();
+    let context = RunContext::new(16, &mut features).with_transport(Some(&transport));
+    assert_eq!(context.transport(), Some(&transport));
+}
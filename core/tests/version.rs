@@ -0,0 +1,95 @@
+use lv2_core::prelude::*;
+use urid::*;
+
+#[derive(PortCollection)]
+struct Ports {
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+#[uri("urn:lv2_core:test:inverter")]
+struct Inverter;
+
+#[plugin_version(minor = 4)]
+impl Plugin for Inverter {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = -*input;
+        }
+    }
+}
+
+#[uri("urn:lv2_core:test:inverter2")]
+struct NextInverter;
+
+#[plugin_version(
+    minor = 1,
+    micro = 3,
+    replaces = "urn:lv2_core:test:inverter",
+    replaces = "urn:ladspa:1049"
+)]
+impl Plugin for NextInverter {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = -*input;
+        }
+    }
+}
+
+#[uri("urn:lv2_core:test:unversioned")]
+struct Unversioned;
+
+impl Plugin for Unversioned {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        ports.output.copy_from_slice(&ports.input);
+    }
+}
+
+#[test]
+fn test_version_attribute() {
+    // The micro version defaults to 0.
+    assert_eq!(Inverter::VERSION, Some(PluginVersion::new(4, 0)));
+    assert!(Inverter::VERSION.unwrap().is_stable());
+    assert_eq!(Inverter::VERSION.unwrap().to_string(), "4.0");
+    assert!(Inverter::REPLACES.is_empty());
+
+    let version = NextInverter::VERSION.unwrap();
+    assert_eq!(version, PluginVersion::new(1, 3));
+    assert!(!version.is_stable());
+    assert!(!version.is_experimental());
+    assert_eq!(
+        NextInverter::REPLACES,
+        &[
+            b"urn:lv2_core:test:inverter\0".as_ref(),
+            b"urn:ladspa:1049\0".as_ref()
+        ]
+    );
+
+    // Plugins without the attribute have no version.
+    assert_eq!(Unversioned::VERSION, None);
+    assert!(Unversioned::REPLACES.is_empty());
+}
@@ -0,0 +1,171 @@
+use crate::HostError;
+use std::convert::TryInto;
+use std::io::{ErrorKind, Read, Write};
+
+/// The magic bytes at the start of every atom log.
+const MAGIC: &[u8; 8] = b"LV2ATOMS";
+
+/// The version of the format, which is written after the magic bytes.
+const VERSION: u32 = 1;
+
+/// An event of an [`AtomLog`](struct.AtomLog.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoggedEvent {
+    time: i64,
+    event_type: u32,
+    body: Vec<u8>,
+}
+
+impl LoggedEvent {
+    /// Create an event with the given time in frames, the URID of its type and its body.
+    pub fn new(time: i64, event_type: u32, body: &[u8]) -> Self {
+        Self {
+            time,
+            event_type,
+            body: body.to_vec(),
+        }
+    }
+
+    /// The time of the event, in frames since the start of its cycle.
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// The URID of the type of the event's atom.
+    pub fn event_type(&self) -> u32 {
+        self.event_type
+    }
+
+    /// The body of the event's atom.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+/// A cycle of an [`AtomLog`](struct.AtomLog.html), with its events in the order they were received.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoggedCycle {
+    frames: u32,
+    events: Vec<LoggedEvent>,
+}
+
+impl LoggedCycle {
+    /// The number of frames that were processed in the cycle.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// The events of the cycle.
+    pub fn events(&self) -> &[LoggedEvent] {
+        &self.events
+    }
+}
+
+/// A log of the atom events a port received, cycle by cycle.
+///
+/// Logs are usually [recorded](struct.ProcessGraph.html#method.record_input) from an atom input of a graph node and [replayed](struct.ProcessGraph.html#method.replay) later, which makes it possible to reproduce bugs that only occur with the event stream of a certain host offline: The events of a session are recorded, [written](#method.write_to) to a file and read again in a test, which sends them to the plugin in the same cycles and at the same frames.
+///
+/// The log stores the URIDs of the events as they are, since their bodies may contain URIDs too. Therefore, the log should be replayed with the same URID map: The map of the recording session can be [saved](fn.save_urid_table.html) along with the log and [loaded](fn.load_urid_table.html) for the replay.
+///
+/// The binary format is little-endian: After the magic bytes `LV2ATOMS` and the version of the format as a `u32`, every cycle is written as its number of frames and its number of events, both as `u32`s, followed by its events. Every event consists of its time as an `i64`, its type and the size of its body as `u32`s and the body itself, without padding.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AtomLog {
+    cycles: Vec<LoggedCycle>,
+}
+
+impl AtomLog {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a cycle with the given number of frames and events.
+    pub fn push_cycle<I: IntoIterator<Item = LoggedEvent>>(&mut self, frames: u32, events: I) {
+        self.cycles.push(LoggedCycle {
+            frames,
+            events: events.into_iter().collect(),
+        });
+    }
+
+    /// The cycles of the log, in the order they were recorded.
+    pub fn cycles(&self) -> &[LoggedCycle] {
+        &self.cycles
+    }
+
+    /// The number of events in all cycles.
+    pub fn event_count(&self) -> usize {
+        self.cycles.iter().map(|cycle| cycle.events.len()).sum()
+    }
+
+    /// Check whether the log contains no cycles.
+    pub fn is_empty(&self) -> bool {
+        self.cycles.is_empty()
+    }
+
+    /// Write the log in its binary format.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), HostError> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        for cycle in self.cycles.iter() {
+            writer.write_all(&cycle.frames.to_le_bytes())?;
+            writer.write_all(&(cycle.events.len() as u32).to_le_bytes())?;
+            for event in cycle.events.iter() {
+                writer.write_all(&event.time.to_le_bytes())?;
+                writer.write_all(&event.event_type.to_le_bytes())?;
+                writer.write_all(&(event.body.len() as u32).to_le_bytes())?;
+                writer.write_all(&event.body)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a log that was written by [`write_to`](#method.write_to).
+    ///
+    /// If the data doesn't start with the magic bytes, has another version or ends within a cycle, an error of the kind `InvalidData` is returned.
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, HostError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Self::parse(&data).ok_or(HostError::Io(ErrorKind::InvalidData))
+    }
+
+    /// Parse the binary format of a log.
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut data = data.strip_prefix(MAGIC.as_ref())?;
+        if read_u32(&mut data)? != VERSION {
+            return None;
+        }
+        let mut log = Self::new();
+        while !data.is_empty() {
+            let frames = read_u32(&mut data)?;
+            let count = read_u32(&mut data)?;
+            let mut events = Vec::new();
+            for _ in 0..count {
+                let time = i64::from_le_bytes(read_bytes(&mut data, 8)?.try_into().ok()?);
+                let event_type = read_u32(&mut data)?;
+                let size = read_u32(&mut data)? as usize;
+                events.push(LoggedEvent::new(
+                    time,
+                    event_type,
+                    read_bytes(&mut data, size)?,
+                ));
+            }
+            log.push_cycle(frames, events);
+        }
+        Some(log)
+    }
+}
+
+/// Split the given number of bytes off the front of the data.
+fn read_bytes<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if data.len() < len {
+        return None;
+    }
+    let (bytes, rest) = data.split_at(len);
+    *data = rest;
+    Some(bytes)
+}
+
+/// Split a little-endian `u32` off the front of the data.
+fn read_u32(data: &mut &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(read_bytes(data, 4)?.try_into().ok()?))
+}
@@ -0,0 +1,320 @@
+use crate::graph::{append_event, reset_atom};
+use crate::worker::Ring;
+use crate::{HostError, HostFeatures, Instance, PluginEntry, PortKind};
+use core::port::PortDirection;
+use std::ffi::c_void;
+use std::fmt::Display;
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use urid::{Map, Uri};
+
+/// The size of the buffer of the MIDI messages that are sent by the host, in bytes.
+const MIDI_QUEUE_SIZE: usize = 4096;
+
+/// The size of the buffers of atom ports, in bytes.
+const ATOM_SIZE: usize = 8192;
+
+/// The state that is shared between the controls of an audition and its audio thread.
+struct SharedControls {
+    /// The symbols and values of all control ports, with the values stored as the bits of `f32`s.
+    values: Vec<(String, AtomicU32)>,
+    /// The MIDI messages that are sent to the plugin at the start of the next cycle.
+    midi: Ring,
+    /// The ring only supports a single writer, which is why the handles take turns.
+    midi_writer: Mutex<()>,
+}
+
+/// A handle to the controls of a running audition.
+///
+/// The handle can be cloned and sent to other threads, like the thread of a user interface. The values are exchanged without locks, which is why the audio thread never waits for the host.
+#[derive(Clone)]
+pub struct AuditionControls(Arc<SharedControls>);
+
+impl AuditionControls {
+    /// Set the value of a control input, which is used from the next cycle on.
+    ///
+    /// Returns `false` if the plugin has no control port with the given symbol.
+    pub fn set_control(&self, symbol: &str, value: f32) -> bool {
+        match self.value(symbol) {
+            Some(control) => {
+                control.store(value.to_bits(), Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The value of a control port.
+    ///
+    /// For inputs, this is the value that was set last and for outputs, it's the value the plugin wrote in the last cycle.
+    pub fn control(&self, symbol: &str) -> Option<f32> {
+        self.value(symbol)
+            .map(|control| f32::from_bits(control.load(Ordering::Relaxed)))
+    }
+
+    /// Send a MIDI message to the first atom input of the plugin.
+    ///
+    /// The message is delivered at the start of the next cycle. Returns `false` if the queue of messages is full.
+    pub fn send_midi(&self, message: &[u8]) -> bool {
+        match self.0.midi_writer.lock() {
+            Ok(_writer) => self.0.midi.push(message),
+            Err(_) => false,
+        }
+    }
+
+    fn value(&self, symbol: &str) -> Option<&AtomicU32> {
+        self.0
+            .values
+            .iter()
+            .find(|(known, _)| known == symbol)
+            .map(|(_, value)| value)
+    }
+}
+
+/// The buffer a port of an audition is connected to.
+enum Buffer {
+    /// Ports of unknown kinds aren't connected.
+    None,
+    /// A buffer of audio or CV samples.
+    Samples(Vec<f32>),
+    /// A buffer of double-precision audio samples.
+    SamplesF64(Vec<f64>),
+    /// A single control value, which is boxed so that it doesn't move.
+    Control(Box<f32>, usize),
+    /// A buffer for an atom, aligned to 64 bits.
+    Atom(Vec<u64>),
+}
+
+/// A port of the instance of an audition.
+struct Port {
+    index: u32,
+    direction: Option<PortDirection>,
+    buffer: Buffer,
+}
+
+/// A plugin instance with the buffers of its ports, which is run by an audio backend.
+///
+/// Every cycle of the backend starts with [`begin`](#method.begin), after which the inputs are written, and ends with [`run`](#method.run), after which the outputs are read.
+pub(crate) struct Engine {
+    instance: Instance<'static>,
+    ports: Vec<Port>,
+    audio_inputs: Vec<(usize, String)>,
+    audio_outputs: Vec<(usize, String)>,
+    midi_input: Option<usize>,
+    controls: AuditionControls,
+    block_size: usize,
+    sequence: u32,
+    chunk: u32,
+    midi_event: u32,
+}
+
+// Instances are created in the host's thread and run in the audio thread, which the LV2 threading rules allow.
+unsafe impl Send for Engine {}
+
+impl Engine {
+    /// Instantiate the plugin and allocate the buffers of its ports for up to `block_size` frames.
+    ///
+    /// Atom ports are only supported if the features contain a URID map. Otherwise, they are left unconnected, just like ports of unknown kinds.
+    pub unsafe fn new(
+        plugin: &PluginEntry,
+        sample_rate: f64,
+        block_size: usize,
+        features: &'static HostFeatures,
+    ) -> Result<Self, HostError> {
+        let instance = plugin.instantiate(sample_rate, features)?;
+        let map = |uri: &'static [u8]| {
+            features
+                .mapper()
+                .and_then(|mapper| mapper.map_uri(Uri::from_bytes_with_nul(uri).ok()?))
+                .map_or(0, |urid| urid.get())
+        };
+        let sequence = map(sys::LV2_ATOM__Sequence);
+        let chunk = map(sys::LV2_ATOM__Chunk);
+        let atoms = sequence != 0 && chunk != 0;
+
+        let mut values = Vec::new();
+        let mut audio_inputs = Vec::new();
+        let mut audio_outputs = Vec::new();
+        let mut midi_input = None;
+        let mut ports = Vec::new();
+        for entry in plugin.ports() {
+            let input = entry.direction() == Some(PortDirection::Input);
+            let buffer = match entry.kind() {
+                PortKind::Audio | PortKind::CV => Buffer::Samples(vec![0.0; block_size]),
+                PortKind::AudioF64 => Buffer::SamplesF64(vec![0.0; block_size]),
+                PortKind::Control => {
+                    let value = entry.default().unwrap_or(0.0);
+                    values.push((entry.symbol().to_owned(), AtomicU32::new(value.to_bits())));
+                    Buffer::Control(Box::new(value), values.len() - 1)
+                }
+                PortKind::Atom if atoms => Buffer::Atom(vec![0; ATOM_SIZE / 8]),
+                _ => Buffer::None,
+            };
+            match (entry.kind(), entry.direction()) {
+                (PortKind::Audio, Some(PortDirection::Input)) => {
+                    audio_inputs.push((ports.len(), entry.symbol().to_owned()))
+                }
+                (PortKind::Audio, Some(PortDirection::Output)) => {
+                    audio_outputs.push((ports.len(), entry.symbol().to_owned()))
+                }
+                (PortKind::Atom, _) if atoms && input && midi_input.is_none() => {
+                    midi_input = Some(ports.len())
+                }
+                _ => (),
+            }
+            ports.push(Port {
+                index: entry.index(),
+                direction: entry.direction(),
+                buffer,
+            });
+        }
+
+        let controls = AuditionControls(Arc::new(SharedControls {
+            values,
+            midi: Ring::new(MIDI_QUEUE_SIZE),
+            midi_writer: Mutex::new(()),
+        }));
+        Ok(Self {
+            instance,
+            ports,
+            audio_inputs,
+            audio_outputs,
+            midi_input,
+            controls,
+            block_size,
+            sequence,
+            chunk,
+            midi_event: map(sys::LV2_MIDI__MidiEvent),
+        })
+    }
+
+    /// The controls of the instance.
+    pub fn controls(&self) -> AuditionControls {
+        self.controls.clone()
+    }
+
+    /// The symbols of the audio inputs.
+    pub fn audio_inputs(&self) -> impl Iterator<Item = &str> {
+        self.audio_inputs.iter().map(|(_, symbol)| symbol.as_str())
+    }
+
+    /// The symbols of the audio outputs.
+    pub fn audio_outputs(&self) -> impl Iterator<Item = &str> {
+        self.audio_outputs.iter().map(|(_, symbol)| symbol.as_str())
+    }
+
+    /// Whether the plugin has an atom input that receives MIDI events.
+    pub fn has_midi_input(&self) -> bool {
+        self.midi_input.is_some()
+    }
+
+    /// The maximal number of frames that are processed at once.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Reallocate the sample buffers for up to `block_size` frames.
+    ///
+    /// This allocates memory and must not be called in a real-time context.
+    pub fn set_block_size(&mut self, block_size: usize) {
+        for port in self.ports.iter_mut() {
+            match &mut port.buffer {
+                Buffer::Samples(samples) => samples.resize(block_size, 0.0),
+                Buffer::SamplesF64(samples) => samples.resize(block_size, 0.0),
+                _ => (),
+            }
+        }
+        self.block_size = block_size;
+    }
+
+    /// Prepare the ports for the next cycle.
+    ///
+    /// The atom buffers are reset, the control inputs receive the values of the controls and the MIDI messages that were sent by the host are written to the MIDI input.
+    pub fn begin(&mut self) {
+        for port in self.ports.iter_mut() {
+            match &mut port.buffer {
+                Buffer::Atom(atom) => reset_atom(atom, port.direction, self.sequence, self.chunk),
+                Buffer::Control(value, control)
+                    if port.direction != Some(PortDirection::Output) =>
+                {
+                    **value =
+                        f32::from_bits(self.controls.0.values[*control].1.load(Ordering::Relaxed));
+                }
+                _ => (),
+            }
+        }
+        let controls = self.controls.clone();
+        while controls
+            .0
+            .midi
+            .pop(|message| self.write_midi(0, message))
+            .is_some()
+        {}
+    }
+
+    /// The buffer of an audio input.
+    pub fn input_mut(&mut self, input: usize) -> &mut [f32] {
+        let port = self.audio_inputs[input].0;
+        match &mut self.ports[port].buffer {
+            Buffer::Samples(samples) => samples.as_mut_slice(),
+            _ => &mut [],
+        }
+    }
+
+    /// The buffer of an audio output.
+    pub fn output(&self, output: usize) -> &[f32] {
+        let port = self.audio_outputs[output].0;
+        match &self.ports[port].buffer {
+            Buffer::Samples(samples) => samples.as_slice(),
+            _ => &[],
+        }
+    }
+
+    /// Append a MIDI event to the sequence of the MIDI input.
+    ///
+    /// The events of a cycle have to be appended in the order of their times. Returns `false` if the plugin has no MIDI input or if the event doesn't fit in the buffer.
+    pub fn write_midi(&mut self, time: u32, message: &[u8]) -> bool {
+        let midi_event = self.midi_event;
+        let atom = match self.midi_input.map(|port| &mut self.ports[port].buffer) {
+            Some(Buffer::Atom(atom)) if midi_event != 0 => atom,
+            _ => return false,
+        };
+        append_event(atom, time.into(), midi_event, message)
+    }
+
+    /// Run the instance for the given number of frames and publish the values of its control outputs.
+    ///
+    /// # Safety
+    ///
+    /// The instance is run without any checks, which means that all ports that aren't optional have to be supported by the engine. The number of frames mustn't exceed the block size.
+    pub unsafe fn run(&mut self, frames: u32) {
+        for port in self.ports.iter_mut() {
+            let data = match &mut port.buffer {
+                Buffer::None => null_mut(),
+                Buffer::Samples(samples) => samples.as_mut_ptr() as *mut c_void,
+                Buffer::SamplesF64(samples) => samples.as_mut_ptr() as *mut c_void,
+                Buffer::Control(value, _) => value.as_mut() as *mut f32 as *mut c_void,
+                Buffer::Atom(atom) => atom.as_mut_ptr() as *mut c_void,
+            };
+            self.instance.connect_port(port.index, data);
+        }
+        self.instance.activate();
+        self.instance.run(frames);
+
+        for port in self.ports.iter() {
+            if let (Buffer::Control(value, control), Some(PortDirection::Output)) =
+                (&port.buffer, port.direction)
+            {
+                self.controls.0.values[*control]
+                    .1
+                    .store(value.to_bits(), Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Convert an error of an audio backend.
+pub(crate) fn backend_error<E: Display>(error: E) -> HostError {
+    HostError::Backend(error.to_string())
+}
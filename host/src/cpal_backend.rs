@@ -0,0 +1,93 @@
+use crate::audition::{backend_error, Engine};
+use crate::{AuditionControls, HostError, HostFeatures, PluginEntry};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{OutputCallbackInfo, Stream};
+
+/// The number of frames the instance processes at once.
+///
+/// The buffers of CPAL may have any size, which is why they're split into blocks of this size.
+const BLOCK_SIZE: usize = 512;
+
+/// A plugin instance that is run by the default output device of CPAL, to listen to it live.
+///
+/// The audio outputs of the plugin are distributed to the channels of the device: If the device has more channels than the plugin has outputs, the outputs are repeated, so that a mono plugin is heard on both channels of a stereo device. The audio inputs of the plugin receive silence, which is why this is most useful for instruments, which can be played with [`AuditionControls::send_midi`](struct.AuditionControls.html#method.send_midi).
+///
+/// The instance is run until the audition is dropped. Errors of the stream are ignored.
+///
+/// This type is only available with the `cpal` feature.
+pub struct CpalAudition {
+    stream: Stream,
+    controls: AuditionControls,
+}
+
+impl CpalAudition {
+    /// Instantiate the plugin with the sample rate of the default output device and start running it.
+    ///
+    /// The features have to live as long as the process, which is possible by leaking them with `Box::leak`.
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialization code and the plugin's functions are called without any checks. The library has to be an LV2 plugin library that follows the specification and all ports of the plugin that aren't optional have to be supported by the audition.
+    pub unsafe fn start(
+        plugin: &PluginEntry,
+        features: &'static HostFeatures,
+    ) -> Result<Self, HostError> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or_else(|| HostError::Backend("No output device available".to_owned()))?;
+        let config = device
+            .default_output_config()
+            .map_err(backend_error)?
+            .config();
+        let channels = usize::from(config.channels).max(1);
+
+        let mut engine = Engine::new(
+            plugin,
+            f64::from(config.sample_rate.0),
+            BLOCK_SIZE,
+            features,
+        )?;
+        let controls = engine.controls();
+        let outputs = engine.audio_outputs().count();
+
+        let stream = device
+            .build_output_stream(
+                &config,
+                move |data: &mut [f32], _: &OutputCallbackInfo| {
+                    for block in data.chunks_mut(BLOCK_SIZE * channels) {
+                        let frames = block.len() / channels;
+                        engine.begin();
+                        engine.run(frames as u32);
+                        for (frame, samples) in block.chunks_mut(channels).enumerate() {
+                            for (channel, sample) in samples.iter_mut().enumerate() {
+                                *sample = if outputs > 0 {
+                                    engine.output(channel % outputs)[frame]
+                                } else {
+                                    0.0
+                                };
+                            }
+                        }
+                    }
+                },
+                |_| (),
+                None,
+            )
+            .map_err(backend_error)?;
+        stream.play().map_err(backend_error)?;
+        Ok(Self { stream, controls })
+    }
+
+    /// The controls of the instance.
+    pub fn controls(&self) -> &AuditionControls {
+        &self.controls
+    }
+
+    /// Pause or resume running the instance.
+    pub fn set_paused(&self, paused: bool) -> Result<(), HostError> {
+        if paused {
+            self.stream.pause().map_err(backend_error)
+        } else {
+            self.stream.play().map_err(backend_error)
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::worker::WorkerFeature;
+use core::feature::InstanceName;
+use lv2_urid::HostMap;
+use std::ffi::{c_void, CString};
+use std::pin::Pin;
+use std::ptr::null_mut;
+use std::sync::Arc;
+use urid::*;
+
+/// The URID map and unmap features of a host.
+///
+/// The interfaces are only accessed by plugins, via the data pointers of the features.
+struct UridFeatures {
+    mapper: Pin<Box<HostMap<Arc<HashURIDMapper>>>>,
+    _map: Box<sys::LV2_URID_Map>,
+    _unmap: Box<sys::LV2_URID_Unmap>,
+}
+
+/// The name of the instances, as a raw string and as a list of options.
+///
+/// The options are only accessed by plugins, via the data pointer of the options feature.
+struct InstanceNameFeatures {
+    _name: CString,
+    _options: Option<Box<[sys::LV2_Options_Option; 2]>>,
+}
+
+/// The features a host provides to the plugins it instantiates.
+///
+/// Features are added with the builder methods of this struct. The data of the features has to live as long as the instances using it, which is why instances borrow the features they were created with.
+pub struct HostFeatures {
+    features: Vec<(CString, *mut c_void)>,
+    urid: Option<UridFeatures>,
+    worker: Option<WorkerFeature>,
+    instance_name: Option<InstanceNameFeatures>,
+}
+
+impl Default for HostFeatures {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HostFeatures {
+    /// Create an empty set of features.
+    pub fn new() -> Self {
+        Self {
+            features: Vec::new(),
+            urid: None,
+            worker: None,
+            instance_name: None,
+        }
+    }
+
+    /// Add the URID map and unmap features, backed by a [`HashURIDMapper`](../urid/struct.HashURIDMapper.html).
+    ///
+    /// The mapper is also available to the host via the [`mapper`](#method.mapper) method, so that both sides use the same URIDs.
+    pub fn with_urid_map(self) -> Self {
+        self.with_shared_urid_map(Arc::new(HashURIDMapper::new()))
+    }
+
+    /// Add the URID map and unmap features, backed by the given mapper.
+    ///
+    /// The mapper may be shared with other features, for example with the [`shared_mapper`](#method.shared_mapper) of other features or with a mapper that was [loaded](fn.load_urid_table.html) from a previous session. This way, all instances use the same URIDs, even if they need features of their own, like a [worker](#method.with_worker). The mapper is thread-safe, which means that the instances may map URIs concurrently.
+    ///
+    /// If the features already contain a URID map, they are returned as they are.
+    pub fn with_shared_urid_map(mut self, mapper: Arc<HashURIDMapper>) -> Self {
+        if self.urid.is_some() {
+            return self;
+        }
+        let mut mapper: Pin<Box<HostMap<Arc<HashURIDMapper>>>> = Box::pin(mapper.into());
+        let mut map = Box::new(mapper.as_mut().make_map_interface());
+        let mut unmap = Box::new(mapper.as_mut().make_unmap_interface());
+
+        let map_data = map.as_mut() as *mut sys::LV2_URID_Map as *mut c_void;
+        let unmap_data = unmap.as_mut() as *mut sys::LV2_URID_Unmap as *mut c_void;
+        self.push(sys::LV2_URID__map, map_data);
+        self.push(sys::LV2_URID__unmap, unmap_data);
+        self.urid = Some(UridFeatures {
+            mapper,
+            _map: map,
+            _unmap: unmap,
+        });
+        self
+    }
+
+    /// Add the worker schedule feature, with ring buffers that can hold at least `capacity` bytes of requests and responses each.
+    ///
+    /// When an instance that provides the [worker interface](https://lv2plug.in/ns/ext/worker/worker.html) is created with these features, a worker thread is started for it, which calls the `work` function of the plugin for every scheduled request. After every [`run`](struct.Instance.html#method.run), the responses of the worker are delivered to the instance and its `end_run` function is called. The thread is stopped when the instance is dropped.
+    ///
+    /// Since the requests identify neither the instance nor the thread, the worker can only be used by one instance at a time. Instantiating another plugin with a worker interface while the first one exists fails with [`HostError::WorkerInUse`](enum.HostError.html#variant.WorkerInUse).
+    pub fn with_worker(mut self, capacity: usize) -> Self {
+        if self.worker.is_some() {
+            return self;
+        }
+        let mut worker = WorkerFeature::new(capacity);
+        self.push(sys::LV2_WORKER__schedule, worker.data());
+        self.worker = Some(worker);
+        self
+    }
+
+    /// Add the name of the instances.
+    ///
+    /// The name is passed as a `ui:windowTitle` option of the [options feature](https://lv2plug.in/ns/ext/options), which is where plugins look for it. Since the keys of options are URIDs, the option is only added if the features already contain a URID map. The name is also passed with the [`InstanceName`](../lv2_core/feature/struct.InstanceName.html) feature, which plugins may fall back to.
+    ///
+    /// The name is cut off at the first null byte. If the features already contain a name, they are returned as they are.
+    pub fn with_instance_name(mut self, name: &str) -> Self {
+        if self.instance_name.is_some() {
+            return self;
+        }
+        let name = CString::new(name.split('\0').next().unwrap_or_default()).unwrap_or_default();
+        let options = self.mapper().and_then(|mapper| {
+            let key = mapper.map_str(uri_str(sys::LV2_UI__windowTitle))?;
+            let string = mapper.map_str(uri_str(sys::LV2_ATOM__String))?;
+            Some(Box::new([
+                sys::LV2_Options_Option {
+                    context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                    subject: 0,
+                    key: key.get(),
+                    size: name.as_bytes_with_nul().len() as u32,
+                    type_: string.get(),
+                    value: name.as_ptr() as *const c_void,
+                },
+                sys::LV2_Options_Option {
+                    context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                    subject: 0,
+                    key: 0,
+                    size: 0,
+                    type_: 0,
+                    value: std::ptr::null(),
+                },
+            ]))
+        });
+        if let Some(options) = options.as_ref() {
+            self.push(sys::LV2_OPTIONS__options, options.as_ptr() as *mut c_void);
+        }
+        self.push(InstanceName::URI, name.as_ptr() as *mut c_void);
+        self.instance_name = Some(InstanceNameFeatures {
+            _name: name,
+            _options: options,
+        });
+        self
+    }
+
+    /// Add a feature without data, like `lv2:isLive` or `lv2:hardRTCapable`.
+    pub fn with_flag(mut self, uri: &Uri) -> Self {
+        self.features.push((uri.to_owned(), null_mut()));
+        self
+    }
+
+    /// Add a feature with raw data.
+    ///
+    /// # Safety
+    ///
+    /// The data has to be valid data of the feature with the given URI and it has to be valid for as long as these features exist.
+    pub unsafe fn with_raw(mut self, uri: &Uri, data: *mut c_void) -> Self {
+        self.features.push((uri.to_owned(), data));
+        self
+    }
+
+    fn push(&mut self, uri: &'static [u8], data: *mut c_void) {
+        let uri = Uri::from_bytes_with_nul(uri).unwrap().to_owned();
+        self.features.push((uri, data));
+    }
+
+    /// The URID mapper of the features, if the URID features were added.
+    pub fn mapper(&self) -> Option<&HashURIDMapper> {
+        self.urid.as_ref().map(|urid| urid.mapper.mapper().as_ref())
+    }
+
+    /// The URID mapper of the features as a shared pointer, which can be passed to [`with_shared_urid_map`](#method.with_shared_urid_map).
+    pub fn shared_mapper(&self) -> Option<Arc<HashURIDMapper>> {
+        self.urid.as_ref().map(|urid| urid.mapper.mapper().clone())
+    }
+
+    /// The worker schedule feature, if it was added.
+    pub(crate) fn worker(&self) -> Option<&WorkerFeature> {
+        self.worker.as_ref()
+    }
+
+    /// Check whether a feature with the given URI is provided.
+    pub fn supports(&self, uri: &str) -> bool {
+        self.features
+            .iter()
+            .any(|(feature, _)| feature.as_bytes() == uri.as_bytes())
+    }
+
+    /// The URIs of all provided features.
+    pub fn uris(&self) -> impl Iterator<Item = &Uri> + '_ {
+        self.features.iter().map(|(uri, _)| uri.as_c_str())
+    }
+
+    /// Create the raw feature structs.
+    ///
+    /// The pointers to these structs, followed by a null pointer, are passed to the plugin. The structs point into these features and are only valid as long as the features exist.
+    pub fn raw_features(&self) -> Vec<sys::LV2_Feature> {
+        self.features
+            .iter()
+            .map(|(uri, data)| sys::LV2_Feature {
+                URI: uri.as_ptr(),
+                data: *data,
+            })
+            .collect()
+    }
+}
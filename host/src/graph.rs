@@ -0,0 +1,661 @@
+use crate::{AtomLog, HostError, HostFeatures, Instance, LoggedEvent, PortEntry, PortKind};
+use core::port::PortDirection;
+use presets::{Preset, PresetBuilder};
+use state::Storage;
+use std::ffi::c_void;
+use std::ptr::null_mut;
+use urid::{uri_str, Map, Uri};
+
+/// The identifier of a node in a [`ProcessGraph`](struct.ProcessGraph.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// Errors that may occur when building or running a [`ProcessGraph`](struct.ProcessGraph.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphError {
+    /// The node isn't part of the graph.
+    UnknownNode,
+    /// The node has no port with the given symbol.
+    UnknownPort(String),
+    /// The ports can't be connected, since they carry different kinds of data or aren't an output and an input.
+    IncompatiblePorts,
+    /// The input port is already connected to another output.
+    AlreadyConnected,
+    /// The connection would create a cycle.
+    Cycle,
+    /// More frames were requested than the block size of the graph.
+    TooManyFrames,
+    /// The buffer of an atom port is too small for another event.
+    BufferFull,
+}
+
+/// The buffer a port is connected to.
+enum Buffer {
+    /// Ports of unknown kinds aren't connected.
+    None,
+    /// A buffer of audio or CV samples.
+    Samples(Vec<f32>),
+    /// A buffer of double-precision audio samples.
+    SamplesF64(Vec<f64>),
+    /// A single control value, which is boxed so that it doesn't move.
+    Control(Box<f32>),
+    /// A buffer for an atom, aligned to 64 bits.
+    Atom(Vec<u64>),
+}
+
+impl Buffer {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        match self {
+            Buffer::None => null_mut(),
+            Buffer::Samples(samples) => samples.as_mut_ptr() as *mut c_void,
+            Buffer::SamplesF64(samples) => samples.as_mut_ptr() as *mut c_void,
+            Buffer::Control(value) => value.as_mut() as *mut f32 as *mut c_void,
+            Buffer::Atom(atom) => atom.as_mut_ptr() as *mut c_void,
+        }
+    }
+}
+
+/// A port of a node, with its buffer and the output it's connected to.
+struct Port {
+    entry: PortEntry,
+    buffer: Buffer,
+    source: Option<(usize, usize)>,
+    /// The log of the received events, if the port is recorded.
+    recording: Option<AtomLog>,
+}
+
+/// A plugin instance in a graph.
+struct Node<'a> {
+    instance: Instance<'a>,
+    ports: Vec<Port>,
+}
+
+/// A graph of plugin instances, which are run in the order of their connections.
+///
+/// Every node of the graph is an [`Instance`](struct.Instance.html), together with the descriptions of its ports. The graph owns the buffers of all ports, which are allocated when a node is added: The outputs of a node can be [connected](#method.connect) to the inputs of other nodes, which then read the buffers of these outputs, and the inputs that aren't connected can be filled by the host. Control inputs start with their default values.
+///
+/// When the graph is [processed](#method.process), the nodes are activated if necessary and run in topological order, which means that every node is run after the nodes it receives data from. Connections that would create a cycle are rejected.
+///
+/// Atom ports are only supported if the features contain a URID map: Atom inputs that aren't connected receive a sequence, to which the host can [write MIDI events](#method.write_midi) and [other events](#method.write_event) and which is cleared after every cycle, and atom outputs receive a chunk of the buffer size before every cycle. Otherwise, they are left unconnected, just like ports of unknown kinds. The events of atom inputs can be [recorded](#method.record_input) and [replayed](#method.replay) for debugging.
+///
+/// ```no_run
+/// use lv2_host::*;
+///
+/// let mut world = World::new();
+/// world.load_all();
+/// let plugin = world.plugin("http://lv2plug.in/plugins/eg-amp").unwrap();
+/// let features = HostFeatures::new().with_urid_map();
+///
+/// let mut graph = ProcessGraph::new(256, &features);
+/// let first = graph.add_node(unsafe { plugin.instantiate(44100.0, &features) }.unwrap(), plugin.ports());
+/// let second = graph.add_node(unsafe { plugin.instantiate(44100.0, &features) }.unwrap(), plugin.ports());
+/// graph.connect(first, "out", second, "in").unwrap();
+/// graph.set_control(first, "gain", -6.0).unwrap();
+///
+/// let output = unsafe { graph.render((first, "in"), (second, "out"), &[1.0; 1024]) }.unwrap();
+/// ```
+pub struct ProcessGraph<'a> {
+    nodes: Vec<Node<'a>>,
+    order: Vec<usize>,
+    block_size: u32,
+    atom_size: usize,
+    sequence: u32,
+    chunk: u32,
+    midi_event: u32,
+}
+
+impl<'a> ProcessGraph<'a> {
+    /// Create an empty graph that processes up to `block_size` frames at once.
+    ///
+    /// The features are only used to map the URIDs of atom buffers; The instances may be created with other features, as long as they use the same URID map.
+    pub fn new(block_size: u32, features: &HostFeatures) -> Self {
+        let map = |uri: &'static [u8]| {
+            features
+                .mapper()
+                .and_then(|mapper| mapper.map_uri(Uri::from_bytes_with_nul(uri).ok()?))
+                .map_or(0, |urid| urid.get())
+        };
+        Self {
+            nodes: Vec::new(),
+            order: Vec::new(),
+            block_size,
+            atom_size: 8192,
+            sequence: map(sys::LV2_ATOM__Sequence),
+            chunk: map(sys::LV2_ATOM__Chunk),
+            midi_event: map(sys::LV2_MIDI__MidiEvent),
+        }
+    }
+
+    /// Set the size of the buffers of atom ports that are created afterwards, in bytes.
+    ///
+    /// The default size is 8192 bytes.
+    pub fn with_atom_size(mut self, size: usize) -> Self {
+        self.atom_size = size;
+        self
+    }
+
+    /// The maximal number of frames the graph processes at once.
+    pub fn block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    /// Add an instance to the graph, together with the descriptions of its ports, like the [ports of its plugin](struct.PluginEntry.html#method.ports).
+    pub fn add_node(&mut self, instance: Instance<'a>, ports: &[PortEntry]) -> NodeId {
+        let frames = self.block_size as usize;
+        let atoms = self.sequence != 0 && self.chunk != 0;
+        let ports = ports
+            .iter()
+            .map(|entry| {
+                let buffer = match entry.kind() {
+                    PortKind::Audio | PortKind::CV => Buffer::Samples(vec![0.0; frames]),
+                    PortKind::AudioF64 => Buffer::SamplesF64(vec![0.0; frames]),
+                    PortKind::Control => Buffer::Control(Box::new(entry.default().unwrap_or(0.0))),
+                    PortKind::Atom if atoms => {
+                        let mut atom = vec![0; self.atom_size.max(16).div_ceil(8)];
+                        reset_atom(&mut atom, entry.direction(), self.sequence, self.chunk);
+                        Buffer::Atom(atom)
+                    }
+                    _ => Buffer::None,
+                };
+                Port {
+                    entry: entry.clone(),
+                    buffer,
+                    source: None,
+                    recording: None,
+                }
+            })
+            .collect();
+        self.nodes.push(Node { instance, ports });
+        self.order.push(self.nodes.len() - 1);
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Find the index of the port of a node with the given symbol.
+    fn port(&self, node: NodeId, symbol: &str) -> Result<usize, GraphError> {
+        self.nodes
+            .get(node.0)
+            .ok_or(GraphError::UnknownNode)?
+            .ports
+            .iter()
+            .position(|port| port.entry.symbol() == symbol)
+            .ok_or_else(|| GraphError::UnknownPort(symbol.to_owned()))
+    }
+
+    /// Connect an output of a node to an input of another node.
+    ///
+    /// Both ports have to carry the same kind of data and the input mustn't be connected yet. An output may be connected to any number of inputs.
+    pub fn connect(
+        &mut self,
+        source: NodeId,
+        output: &str,
+        destination: NodeId,
+        input: &str,
+    ) -> Result<(), GraphError> {
+        let output = self.port(source, output)?;
+        let input = self.port(destination, input)?;
+        let output_entry = &self.nodes[source.0].ports[output].entry;
+        let input_port = &self.nodes[destination.0].ports[input];
+        if output_entry.direction() != Some(PortDirection::Output)
+            || input_port.entry.direction() != Some(PortDirection::Input)
+            || output_entry.kind() != input_port.entry.kind()
+            || matches!(input_port.buffer, Buffer::None)
+        {
+            return Err(GraphError::IncompatiblePorts);
+        }
+        if input_port.source.is_some() {
+            return Err(GraphError::AlreadyConnected);
+        }
+
+        self.nodes[destination.0].ports[input].source = Some((source.0, output));
+        match self.sorted() {
+            Some(order) => {
+                self.order = order;
+                Ok(())
+            }
+            None => {
+                self.nodes[destination.0].ports[input].source = None;
+                Err(GraphError::Cycle)
+            }
+        }
+    }
+
+    /// Disconnect an input from the output it's connected to.
+    ///
+    /// Disconnecting an input that isn't connected has no effect.
+    pub fn disconnect(&mut self, node: NodeId, input: &str) -> Result<(), GraphError> {
+        let input = self.port(node, input)?;
+        self.nodes[node.0].ports[input].source = None;
+        Ok(())
+    }
+
+    /// Sort the nodes topologically, or return `None` if the connections contain a cycle.
+    fn sorted(&self) -> Option<Vec<usize>> {
+        let mut pending: Vec<usize> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                node.ports
+                    .iter()
+                    .filter(|port| port.source.is_some())
+                    .count()
+            })
+            .collect();
+        let mut order: Vec<usize> = (0..self.nodes.len())
+            .filter(|node| pending[*node] == 0)
+            .collect();
+        let mut next = 0;
+        while next < order.len() {
+            let source = order[next];
+            for (index, node) in self.nodes.iter().enumerate() {
+                for port in node.ports.iter() {
+                    if matches!(port.source, Some((node, _)) if node == source) {
+                        pending[index] -= 1;
+                        if pending[index] == 0 {
+                            order.push(index);
+                        }
+                    }
+                }
+            }
+            next += 1;
+        }
+        if order.len() == self.nodes.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    /// The instance of a node.
+    pub fn instance(&self, node: NodeId) -> Option<&Instance<'a>> {
+        self.nodes.get(node.0).map(|node| &node.instance)
+    }
+
+    /// The instance of a node, for example to save or restore its state.
+    pub fn instance_mut(&mut self, node: NodeId) -> Option<&mut Instance<'a>> {
+        self.nodes.get_mut(node.0).map(|node| &mut node.instance)
+    }
+
+    /// Set the value of a control input.
+    pub fn set_control(
+        &mut self,
+        node: NodeId,
+        symbol: &str,
+        value: f32,
+    ) -> Result<(), GraphError> {
+        let port = self.port(node, symbol)?;
+        match &mut self.nodes[node.0].ports[port].buffer {
+            Buffer::Control(control) => {
+                **control = value;
+                Ok(())
+            }
+            _ => Err(GraphError::IncompatiblePorts),
+        }
+    }
+
+    /// The value of a control port.
+    ///
+    /// For inputs, this is the value that was set by the host, even if the input is connected to an output.
+    pub fn control(&self, node: NodeId, symbol: &str) -> Option<f32> {
+        let port = self.port(node, symbol).ok()?;
+        match &self.nodes[node.0].ports[port].buffer {
+            Buffer::Control(control) => Some(**control),
+            _ => None,
+        }
+    }
+
+    /// Apply a preset to a node.
+    ///
+    /// If the preset contains state properties, they are converted with the URID map of the features and the state of the instance is restored from them, which requires the state extension. Afterwards, the control inputs are set to the port values of the preset. The port values are set even if the preset applies to another plugin, as long as the symbols match.
+    pub fn apply_preset(
+        &mut self,
+        node: NodeId,
+        preset: &Preset,
+        features: &HostFeatures,
+    ) -> Result<(), HostError> {
+        // All ports are checked first, so that a preset that doesn't match is rejected without changing the node.
+        for (symbol, _) in preset.port_values() {
+            let port = self.port(node, symbol)?;
+            let port = &self.nodes[node.0].ports[port];
+            if !matches!(port.buffer, Buffer::Control(_))
+                || port.entry.direction() != Some(PortDirection::Input)
+            {
+                return Err(GraphError::IncompatiblePorts.into());
+            }
+        }
+        if preset.state_properties().next().is_some() {
+            let storage = preset.storage(mapper(features)?)?;
+            self.nodes[node.0]
+                .instance
+                .restore_state(&storage, features)?;
+        }
+        for (symbol, value) in preset.port_values() {
+            self.set_control(node, symbol, value)?;
+        }
+        Ok(())
+    }
+
+    /// Capture the current configuration of a node in a preset.
+    ///
+    /// The values of all control inputs are added to the builder, and if the instance provides the state extension, its state is saved and added too, after its properties were converted with the URID map of the features. The preset can then be [saved](../lv2_presets/struct.Preset.html#method.save) to a bundle.
+    pub fn capture_preset(
+        &mut self,
+        node: NodeId,
+        mut builder: PresetBuilder,
+        features: &HostFeatures,
+    ) -> Result<Preset, HostError> {
+        let node = self.nodes.get_mut(node.0).ok_or(GraphError::UnknownNode)?;
+        for port in node.ports.iter() {
+            if let (Buffer::Control(value), Some(PortDirection::Input)) =
+                (&port.buffer, port.entry.direction())
+            {
+                builder = builder.port_value(port.entry.symbol(), **value);
+            }
+        }
+        if node
+            .instance
+            .extension::<sys::LV2_State_Interface>()
+            .is_some()
+        {
+            let mapper = mapper(features)?;
+            let mut storage = Storage::default();
+            node.instance.save_state(&mut storage, features)?;
+            builder = builder.storage(&storage, mapper, mapper)?;
+        }
+        Ok(builder.build())
+    }
+
+    /// The buffer of an audio or CV input, which is passed to the node in the next cycle.
+    ///
+    /// Inputs that are connected to an output don't read their own buffer, which is why `None` is returned for them.
+    pub fn input_mut(&mut self, node: NodeId, symbol: &str) -> Option<&mut [f32]> {
+        let port = self.port(node, symbol).ok()?;
+        let port = &mut self.nodes[node.0].ports[port];
+        match (&mut port.buffer, port.entry.direction(), port.source) {
+            (Buffer::Samples(samples), Some(PortDirection::Input), None) => Some(samples),
+            _ => None,
+        }
+    }
+
+    /// The buffer of an audio or CV output, which contains the samples of the last cycle.
+    pub fn output(&self, node: NodeId, symbol: &str) -> Option<&[f32]> {
+        let port = self.port(node, symbol).ok()?;
+        let port = &self.nodes[node.0].ports[port];
+        match (&port.buffer, port.entry.direction()) {
+            (Buffer::Samples(samples), Some(PortDirection::Output)) => Some(samples),
+            _ => None,
+        }
+    }
+
+    /// Append a MIDI event to the sequence of an atom input, which is passed to the node in the next cycle.
+    ///
+    /// The time of the event is the frame of the cycle it occurs at, and the events of a cycle have to be written in the order of their times. The sequence is cleared after every cycle.
+    pub fn write_midi(
+        &mut self,
+        node: NodeId,
+        symbol: &str,
+        time: u32,
+        message: &[u8],
+    ) -> Result<(), GraphError> {
+        let midi_event = self.midi_event;
+        self.write_event(node, symbol, time.into(), midi_event, message)
+    }
+
+    /// Append an event with the given type and body to the sequence of an atom input, which is passed to the node in the next cycle.
+    ///
+    /// This works like [`write_midi`](#method.write_midi), but the type of the event is the URID of any atom type, like an object or a custom event type, and the body has to be a valid body of that type.
+    pub fn write_event(
+        &mut self,
+        node: NodeId,
+        symbol: &str,
+        time: i64,
+        event_type: u32,
+        body: &[u8],
+    ) -> Result<(), GraphError> {
+        let port = self.port(node, symbol)?;
+        let port = &mut self.nodes[node.0].ports[port];
+        match (&mut port.buffer, port.entry.direction(), port.source) {
+            (Buffer::Atom(atom), Some(PortDirection::Input), None) => {
+                if append_event(atom, time, event_type, body) {
+                    Ok(())
+                } else {
+                    Err(GraphError::BufferFull)
+                }
+            }
+            _ => Err(GraphError::IncompatiblePorts),
+        }
+    }
+
+    /// Start recording the events an atom input receives.
+    ///
+    /// From now on, the events of every cycle are appended to a log, together with the number of frames of the cycle, until the recording is [taken](#method.take_recording). Both events written by the host and events from an output the input is connected to are recorded. If the input is already recorded, the recording starts over.
+    pub fn record_input(&mut self, node: NodeId, symbol: &str) -> Result<(), GraphError> {
+        let port = self.port(node, symbol)?;
+        let port = &mut self.nodes[node.0].ports[port];
+        match (&port.buffer, port.entry.direction()) {
+            (Buffer::Atom(_), Some(PortDirection::Input)) => {
+                port.recording = Some(AtomLog::new());
+                Ok(())
+            }
+            _ => Err(GraphError::IncompatiblePorts),
+        }
+    }
+
+    /// Stop recording an atom input and return the recorded log.
+    ///
+    /// Returns `None` if the input isn't recorded.
+    pub fn take_recording(&mut self, node: NodeId, symbol: &str) -> Option<AtomLog> {
+        let port = self.port(node, symbol).ok()?;
+        self.nodes[node.0].ports[port].recording.take()
+    }
+
+    /// Replay a log to an atom input, by running the graph once for every cycle of the log.
+    ///
+    /// The events of every cycle are [written](#method.write_event) to the input before the graph is [processed](#method.process) for the number of frames of the cycle. This reproduces the event stream the input received while the log was [recorded](#method.record_input), as long as the URIDs of the log have the same meaning, and the input mustn't be connected to an output.
+    ///
+    /// # Safety
+    ///
+    /// This calls [`process`](#method.process), which has the same requirements.
+    pub unsafe fn replay(
+        &mut self,
+        node: NodeId,
+        symbol: &str,
+        log: &AtomLog,
+    ) -> Result<(), GraphError> {
+        for cycle in log.cycles() {
+            for event in cycle.events() {
+                self.write_event(node, symbol, event.time(), event.event_type(), event.body())?;
+            }
+            self.process(cycle.frames())?;
+        }
+        Ok(())
+    }
+
+    /// Run all nodes of the graph for the given number of frames.
+    ///
+    /// Every port is connected to its buffer or to the output it's connected to, and inactive instances are activated. Then, the instances are run in topological order.
+    ///
+    /// # Safety
+    ///
+    /// The instances are run without any checks, which means that all ports that aren't optional have to be supported by the graph.
+    pub unsafe fn process(&mut self, frames: u32) -> Result<(), GraphError> {
+        if frames > self.block_size {
+            return Err(GraphError::TooManyFrames);
+        }
+        for step in 0..self.order.len() {
+            let node = self.order[step];
+            for index in 0..self.nodes[node].ports.len() {
+                let data = match self.nodes[node].ports[index].source {
+                    Some((source, output)) => self.nodes[source].ports[output].buffer.as_mut_ptr(),
+                    None => {
+                        let port = &mut self.nodes[node].ports[index];
+                        let direction = port.entry.direction();
+                        if let Buffer::Atom(atom) = &mut port.buffer {
+                            if direction == Some(PortDirection::Output) {
+                                reset_atom(atom, direction, self.sequence, self.chunk);
+                            }
+                        }
+                        port.buffer.as_mut_ptr()
+                    }
+                };
+                let port_index = self.nodes[node].ports[index].entry.index();
+                self.nodes[node].instance.connect_port(port_index, data);
+            }
+            self.record_inputs(node, frames);
+            let instance = &mut self.nodes[node].instance;
+            instance.activate();
+            instance.run(frames);
+
+            // The events of the inputs have been consumed, so the host may write the events of the next cycle.
+            for port in self.nodes[node].ports.iter_mut() {
+                if let (Buffer::Atom(atom), None) = (&mut port.buffer, port.source) {
+                    if port.entry.direction() != Some(PortDirection::Output) {
+                        reset_atom(atom, port.entry.direction(), self.sequence, self.chunk);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Append the events the recorded inputs of a node receive in this cycle to their logs.
+    fn record_inputs(&mut self, node: usize, frames: u32) {
+        for index in 0..self.nodes[node].ports.len() {
+            let port = &self.nodes[node].ports[index];
+            if port.recording.is_none() {
+                continue;
+            }
+            let buffer = match port.source {
+                Some((source, output)) => &self.nodes[source].ports[output].buffer,
+                None => &port.buffer,
+            };
+            let events = match buffer {
+                Buffer::Atom(atom) => read_events(atom, self.sequence),
+                _ => Vec::new(),
+            };
+            if let Some(log) = &mut self.nodes[node].ports[index].recording {
+                log.push_cycle(frames, events);
+            }
+        }
+    }
+
+    /// Process a signal through the graph.
+    ///
+    /// The input samples are written to the given audio input, block by block, and the samples of the given audio output are collected. The output contains as many samples as the input.
+    ///
+    /// # Safety
+    ///
+    /// This calls [`process`](#method.process), which has the same requirements.
+    pub unsafe fn render(
+        &mut self,
+        input: (NodeId, &str),
+        output: (NodeId, &str),
+        samples: &[f32],
+    ) -> Result<Vec<f32>, GraphError> {
+        self.input_mut(input.0, input.1)
+            .ok_or_else(|| GraphError::UnknownPort(input.1.to_owned()))?;
+        self.output(output.0, output.1)
+            .ok_or_else(|| GraphError::UnknownPort(output.1.to_owned()))?;
+
+        let mut rendered: Vec<f32> = Vec::with_capacity(samples.len());
+        for block in samples.chunks(self.block_size.max(1) as usize) {
+            if let Some(buffer) = self.input_mut(input.0, input.1) {
+                buffer[..block.len()].copy_from_slice(block);
+            }
+            self.process(block.len() as u32)?;
+            if let Some(buffer) = self.output(output.0, output.1) {
+                rendered.extend_from_slice(&buffer[..block.len()]);
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+/// The URID map of the features, which is needed to convert the state of presets.
+fn mapper(features: &HostFeatures) -> Result<&urid::HashURIDMapper, HostError> {
+    features
+        .mapper()
+        .ok_or_else(|| HostError::MissingFeature(uri_str(sys::LV2_URID__map).to_owned()))
+}
+
+/// Prepare the buffer of an atom port for the next cycle.
+///
+/// Inputs receive an empty sequence and outputs receive a chunk that spans the whole buffer, which tells the plugin how much space it may use.
+pub(crate) fn reset_atom(
+    atom: &mut [u64],
+    direction: Option<PortDirection>,
+    sequence: u32,
+    chunk: u32,
+) {
+    let (size, type_) = if direction == Some(PortDirection::Output) {
+        ((atom.len() - 1) * 8, chunk)
+    } else {
+        // The body of a sequence starts with its time unit and a padding, which are both zero.
+        atom[1] = 0;
+        (8, sequence)
+    };
+    let header = sys::LV2_Atom {
+        size: size as u32,
+        type_,
+    };
+    unsafe { (atom.as_mut_ptr() as *mut sys::LV2_Atom).write(header) };
+}
+
+/// Append an event to the sequence in the buffer of an atom input.
+///
+/// Returns `false` if the event doesn't fit in the buffer.
+pub(crate) fn append_event(atom: &mut [u64], time: i64, type_: u32, body: &[u8]) -> bool {
+    let bytes =
+        unsafe { std::slice::from_raw_parts_mut(atom.as_mut_ptr() as *mut u8, atom.len() * 8) };
+    let size = u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+    // The event header consists of the time in frames, the size of the body and its type.
+    let offset = 8 + size;
+    let padded = body.len().div_ceil(8) * 8;
+    if offset + 16 + padded > bytes.len() {
+        return false;
+    }
+    bytes[offset..offset + 8].copy_from_slice(&time.to_ne_bytes());
+    bytes[offset + 8..offset + 12].copy_from_slice(&(body.len() as u32).to_ne_bytes());
+    bytes[offset + 12..offset + 16].copy_from_slice(&type_.to_ne_bytes());
+    bytes[offset + 16..offset + 16 + body.len()].copy_from_slice(body);
+    bytes[0..4].copy_from_slice(&((size + 16 + padded) as u32).to_ne_bytes());
+    true
+}
+
+/// Read the events of the sequence in the buffer of an atom port.
+///
+/// If the buffer doesn't contain a sequence, or an event exceeds the sequence or the buffer, the events before it are returned.
+fn read_events(atom: &[u64], sequence: u32) -> Vec<LoggedEvent> {
+    let bytes = unsafe { std::slice::from_raw_parts(atom.as_ptr() as *const u8, atom.len() * 8) };
+    let read_u32 = |offset: usize| {
+        u32::from_ne_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    };
+    let mut events = Vec::new();
+    if read_u32(4) != sequence {
+        return events;
+    }
+    let end = (8 + read_u32(0) as usize).min(bytes.len());
+    // The body of a sequence starts with its time unit and a padding.
+    let mut offset = 16;
+    while offset + 16 <= end {
+        let mut time = [0; 8];
+        time.copy_from_slice(&bytes[offset..offset + 8]);
+        let size = read_u32(offset + 8) as usize;
+        let event_type = read_u32(offset + 12);
+        let body = offset + 16;
+        if body + size > end {
+            break;
+        }
+        events.push(LoggedEvent::new(
+            i64::from_ne_bytes(time),
+            event_type,
+            &bytes[body..body + size],
+        ));
+        offset = body + size.div_ceil(8) * 8;
+    }
+    events
+}
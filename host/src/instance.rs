@@ -0,0 +1,327 @@
+use crate::store::{extern_retrieve, extern_store};
+use crate::worker::WorkerThread;
+use crate::{HostError, HostFeatures, StateStore};
+use core::abi::{AbiFunction, AbiInfo, ABI_SYMBOL};
+use core::extension::ExtensionInterface;
+use libloading::{Library, Symbol};
+use std::ffi::{c_void, CStr, CString};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::ptr::{null, NonNull};
+use urid::{uri_str, Uri};
+
+/// The type of the `lv2_descriptor` function, which is exported by every plugin library.
+pub type DescriptorFunction = unsafe extern "C" fn(index: u32) -> *const sys::LV2_Descriptor;
+
+/// Find the descriptor of the plugin with the given URI.
+///
+/// The descriptor function is called with increasing indices until it returns the plugin or a null pointer.
+///
+/// # Safety
+///
+/// The function has to be a valid descriptor function of a plugin library and the library has to be loaded for as long as the descriptor is used.
+pub unsafe fn find_descriptor(
+    function: DescriptorFunction,
+    uri: &str,
+) -> Option<&'static sys::LV2_Descriptor> {
+    let mut index: u32 = 0;
+    loop {
+        let descriptor = function(index).as_ref()?;
+        if !descriptor.URI.is_null() && CStr::from_ptr(descriptor.URI).to_bytes() == uri.as_bytes()
+        {
+            return Some(descriptor);
+        }
+        index += 1;
+    }
+}
+
+/// An instance of a plugin.
+///
+/// An instance is created with [`PluginEntry::instantiate`](struct.PluginEntry.html#method.instantiate), which loads the plugin's library, or with [`from_descriptor`](#method.from_descriptor) for plugins that are linked into the host, like in tests. It borrows the features it was created with, since the plugin may use them until the instance is dropped.
+///
+/// Before the instance is run, all of its ports have to be connected to buffers and it has to be activated. When the instance is dropped, it's deactivated if necessary and cleaned up.
+pub struct Instance<'a> {
+    descriptor: &'static sys::LV2_Descriptor,
+    handle: sys::LV2_Handle,
+    active: bool,
+    // The worker thread has to be stopped before the instance is cleaned up.
+    worker: Option<WorkerThread>,
+    abi: Option<&'static AbiInfo>,
+    // The library has to outlive the instance, which is cleaned up in `drop`.
+    _library: Option<Library>,
+    features: PhantomData<&'a HostFeatures>,
+}
+
+impl<'a> Instance<'a> {
+    /// Load the plugin with the given URI from the library and instantiate it.
+    pub(crate) unsafe fn load(
+        binary_path: &Path,
+        uri: &str,
+        bundle_path: &Path,
+        sample_rate: f64,
+        features: &'a HostFeatures,
+    ) -> Result<Self, HostError> {
+        let library =
+            Library::new(binary_path).map_err(|error| HostError::Library(error.to_string()))?;
+        let descriptor = {
+            let function: Symbol<DescriptorFunction> = library
+                .get(b"lv2_descriptor\0")
+                .map_err(|error| HostError::Library(error.to_string()))?;
+            find_descriptor(*function, uri).ok_or(HostError::MissingBinary)?
+        };
+        // Libraries that aren't built with rust-lv2, or with a release before the contract, don't export the function.
+        let abi = library
+            .get::<AbiFunction>(ABI_SYMBOL)
+            .ok()
+            .and_then(|function| AbiInfo::from_function(*function));
+        let mut instance = Self::from_descriptor(descriptor, bundle_path, sample_rate, features)?;
+        instance.abi = abi;
+        instance._library = Some(library);
+        Ok(instance)
+    }
+
+    /// Instantiate the plugin of the given descriptor.
+    ///
+    /// This is useful for plugins that are linked into the host, for example in integration tests that use the `lv2_descriptor` function generated by `lv2_descriptors!`. Unlike [`PluginEntry::instantiate`](struct.PluginEntry.html#method.instantiate), this method doesn't check whether the features contain all required features.
+    ///
+    /// # Safety
+    ///
+    /// The descriptor has to be a valid plugin descriptor whose functions follow the LV2 specification.
+    pub unsafe fn from_descriptor(
+        descriptor: &'static sys::LV2_Descriptor,
+        bundle_path: &Path,
+        sample_rate: f64,
+        features: &'a HostFeatures,
+    ) -> Result<Self, HostError> {
+        // Bundle paths always end with a directory separator.
+        let mut bundle_path = bundle_path.to_str().ok_or(HostError::BadPath)?.to_owned();
+        if !bundle_path.ends_with(std::path::MAIN_SEPARATOR) {
+            bundle_path.push(std::path::MAIN_SEPARATOR);
+        }
+        let bundle_path = CString::new(bundle_path).map_err(|_| HostError::BadPath)?;
+
+        let raw_features = features.raw_features();
+        let feature_pointers = feature_pointers(&raw_features);
+
+        let instantiate = descriptor
+            .instantiate
+            .ok_or(HostError::InstantiationFailed)?;
+        let handle = instantiate(
+            descriptor,
+            sample_rate,
+            bundle_path.as_ptr(),
+            feature_pointers.as_ptr(),
+        );
+        if handle.is_null() {
+            return Err(HostError::InstantiationFailed);
+        }
+
+        let mut instance = Self {
+            descriptor,
+            handle,
+            active: false,
+            worker: None,
+            abi: None,
+            _library: None,
+            features: PhantomData,
+        };
+        if let Some(worker) = features.worker() {
+            if let Some(interface) = instance.extension::<sys::LV2_Worker_Interface>() {
+                let interface = &*(interface as *const sys::LV2_Worker_Interface);
+                instance.worker = Some(
+                    worker
+                        .attach(handle, interface)
+                        .ok_or(HostError::WorkerInUse)?,
+                );
+            }
+        }
+        Ok(instance)
+    }
+
+    /// The URI of the plugin.
+    pub fn uri(&self) -> &str {
+        if self.descriptor.URI.is_null() {
+            ""
+        } else {
+            unsafe { CStr::from_ptr(self.descriptor.URI) }
+                .to_str()
+                .unwrap_or("")
+        }
+    }
+
+    /// The raw handle of the instance, which is needed to call the functions of extension interfaces.
+    pub fn handle(&self) -> sys::LV2_Handle {
+        self.handle
+    }
+
+    /// Connect a port to a buffer.
+    ///
+    /// # Safety
+    ///
+    /// The buffer has to be valid data of the port's type for as long as it's connected. Audio and CV buffers need to hold at least as many samples as are passed to [`run`](#method.run), which are `f64`s for [double-precision audio ports](enum.PortKind.html#variant.AudioF64) and `f32`s otherwise.
+    pub unsafe fn connect_port(&mut self, index: u32, data: *mut c_void) {
+        if let Some(connect_port) = self.descriptor.connect_port {
+            connect_port(self.handle, index, data);
+        }
+    }
+
+    /// Activate the instance, which resets its internal state.
+    ///
+    /// Activating an active instance has no effect.
+    pub fn activate(&mut self) {
+        if !self.active {
+            if let Some(activate) = self.descriptor.activate {
+                unsafe { activate(self.handle) };
+            }
+            self.active = true;
+        }
+    }
+
+    /// Deactivate the instance.
+    ///
+    /// Deactivating an inactive instance has no effect.
+    pub fn deactivate(&mut self) {
+        if self.active {
+            if let Some(deactivate) = self.descriptor.deactivate {
+                unsafe { deactivate(self.handle) };
+            }
+            self.active = false;
+        }
+    }
+
+    /// Check whether the instance is active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Run the instance for the given number of frames.
+    ///
+    /// If the instance has a [worker thread](struct.HostFeatures.html#method.with_worker), the responses of the worker are delivered afterwards and the worker's `end_run` function is called.
+    ///
+    /// # Safety
+    ///
+    /// The instance has to be active and all ports that aren't optional have to be connected to buffers that are big enough for the given number of frames.
+    pub unsafe fn run(&mut self, sample_count: u32) {
+        if let Some(run) = self.descriptor.run {
+            run(self.handle, sample_count);
+        }
+        if let Some(worker) = self.worker.as_ref() {
+            worker.end_run(self.handle);
+        }
+    }
+
+    /// The [binary contract](../lv2_core/abi/index.html) of the plugin's library.
+    ///
+    /// It's only available if the plugin was loaded by [`PluginEntry::instantiate`](struct.PluginEntry.html#method.instantiate) from a library that was built with a compatible rust-lv2 release. Other plugins are driven through their LV2 interfaces only.
+    pub fn abi(&self) -> Option<&'static AbiInfo> {
+        self.abi
+    }
+
+    /// Check whether a worker thread does the work the instance schedules.
+    pub fn has_worker(&self) -> bool {
+        self.worker.is_some()
+    }
+
+    /// Retrieve the data of an extension, usually a struct of function pointers.
+    ///
+    /// If the plugin doesn't provide the extension, `None` is returned.
+    pub fn extension_data(&self, uri: &Uri) -> Option<NonNull<c_void>> {
+        let extension_data = self.descriptor.extension_data?;
+        NonNull::new(unsafe { extension_data(uri.as_ptr()) } as *mut c_void)
+    }
+
+    /// Retrieve the interface of an extension.
+    ///
+    /// The extension is identified by the type of its interface, like `sys::LV2_Worker_Interface`, which lets hosts call the functions of extensions without casting raw pointers. The functions receive the [`handle`](#method.handle) of the instance. If the plugin doesn't provide the extension, `None` is returned.
+    pub fn extension<I: ExtensionInterface>(&self) -> Option<&I> {
+        self.extension_data(I::uri())
+            .map(|interface| unsafe { &*(interface.as_ptr() as *const I) })
+    }
+
+    /// Retrieve the state interface of the plugin.
+    fn state_interface(&self) -> Result<&sys::LV2_State_Interface, HostError> {
+        self.extension::<sys::LV2_State_Interface>().ok_or_else(|| {
+            HostError::MissingExtension(uri_str(sys::LV2_STATE__interface).to_owned())
+        })
+    }
+
+    /// Save the state of the instance to the store.
+    ///
+    /// The plugin has to provide the [state extension](https://lv2plug.in/ns/ext/state/state.html). The features are passed to the plugin's `save` function and only have to live as long as the call. The properties are saved as plain old data, which is portable between machines.
+    pub fn save_state<S: StateStore>(
+        &mut self,
+        store: &mut S,
+        features: &HostFeatures,
+    ) -> Result<(), HostError> {
+        let save = self
+            .state_interface()?
+            .save
+            .ok_or(HostError::State(state::StateErr::BadCallback))?;
+        let raw_features = features.raw_features();
+        let feature_pointers = feature_pointers(&raw_features);
+        let flags: u32 = (sys::LV2_State_Flags::LV2_STATE_IS_POD
+            | sys::LV2_State_Flags::LV2_STATE_IS_PORTABLE)
+            .into();
+
+        let status = unsafe {
+            save(
+                self.handle,
+                Some(extern_store::<S>),
+                store as *mut S as sys::LV2_State_Handle,
+                flags,
+                feature_pointers.as_ptr(),
+            )
+        };
+        state::StateErr::from(status).map_err(HostError::State)
+    }
+
+    /// Restore the state of the instance from the store.
+    ///
+    /// This is the counterpart of [`save_state`](#method.save_state): The plugin has to provide the state extension and retrieves its properties from the store.
+    pub fn restore_state<S: StateStore>(
+        &mut self,
+        store: &S,
+        features: &HostFeatures,
+    ) -> Result<(), HostError> {
+        let restore = self
+            .state_interface()?
+            .restore
+            .ok_or(HostError::State(state::StateErr::BadCallback))?;
+        let raw_features = features.raw_features();
+        let feature_pointers = feature_pointers(&raw_features);
+        let flags: u32 = (sys::LV2_State_Flags::LV2_STATE_IS_POD
+            | sys::LV2_State_Flags::LV2_STATE_IS_PORTABLE)
+            .into();
+
+        let status = unsafe {
+            restore(
+                self.handle,
+                Some(extern_retrieve::<S>),
+                store as *const S as sys::LV2_State_Handle,
+                flags,
+                feature_pointers.as_ptr(),
+            )
+        };
+        state::StateErr::from(status).map_err(HostError::State)
+    }
+}
+
+/// Create the null-terminated array of pointers to the raw features, which is passed to the plugin.
+fn feature_pointers(raw_features: &[sys::LV2_Feature]) -> Vec<*const sys::LV2_Feature> {
+    let mut feature_pointers: Vec<*const sys::LV2_Feature> = raw_features
+        .iter()
+        .map(|feature| feature as *const sys::LV2_Feature)
+        .collect();
+    feature_pointers.push(null());
+    feature_pointers
+}
+
+impl<'a> Drop for Instance<'a> {
+    fn drop(&mut self) {
+        self.worker = None;
+        self.deactivate();
+        if let Some(cleanup) = self.descriptor.cleanup {
+            unsafe { cleanup(self.handle) };
+        }
+    }
+}
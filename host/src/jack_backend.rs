@@ -0,0 +1,124 @@
+use crate::audition::{backend_error, Engine};
+use crate::{AuditionControls, HostError, HostFeatures, PluginEntry};
+use jack::{
+    AsyncClient, AudioIn, AudioOut, Client, ClientOptions, Control, Frames, MidiIn, Port,
+    ProcessHandler, ProcessScope,
+};
+
+/// The process handler of a JACK audition, which runs the instance in JACK's audio thread.
+struct JackProcess {
+    engine: Engine,
+    inputs: Vec<Port<AudioIn>>,
+    outputs: Vec<Port<AudioOut>>,
+    midi: Option<Port<MidiIn>>,
+}
+
+impl ProcessHandler for JackProcess {
+    fn process(&mut self, _: &Client, scope: &ProcessScope) -> Control {
+        let frames = scope.n_frames() as usize;
+        if frames > self.engine.block_size() {
+            return Control::Continue;
+        }
+        self.engine.begin();
+        for (index, port) in self.inputs.iter().enumerate() {
+            self.engine.input_mut(index)[..frames].copy_from_slice(port.as_slice(scope));
+        }
+        if let Some(midi) = self.midi.as_ref() {
+            for event in midi.iter(scope) {
+                self.engine.write_midi(event.time, event.bytes);
+            }
+        }
+        unsafe { self.engine.run(frames as u32) };
+        for (index, port) in self.outputs.iter_mut().enumerate() {
+            port.as_mut_slice(scope)
+                .copy_from_slice(&self.engine.output(index)[..frames]);
+        }
+        Control::Continue
+    }
+
+    fn buffer_size(&mut self, _: &Client, size: Frames) -> Control {
+        self.engine.set_block_size(size as usize);
+        Control::Continue
+    }
+}
+
+/// A plugin instance that is run by a JACK client, to listen to it live.
+///
+/// The client has an audio port for every audio port of the plugin, with the same symbol, and a MIDI input called `midi_in` if the plugin has an atom input. The MIDI events of this port are written to the first atom input of the plugin. The ports aren't connected to anything; This can be done with the [client](#method.client) or with a patchbay.
+///
+/// The instance is run until the audition is dropped.
+///
+/// This type is only available with the `jack` feature.
+pub struct JackAudition {
+    client: AsyncClient<(), JackProcess>,
+    controls: AuditionControls,
+}
+
+impl JackAudition {
+    /// Create a JACK client with the given name, instantiate the plugin with the sample rate of the server and start running it.
+    ///
+    /// The JACK server isn't started if it isn't running already. The features have to live as long as the process, which is possible by leaking them with `Box::leak`.
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialization code and the plugin's functions are called without any checks. The library has to be an LV2 plugin library that follows the specification and all ports of the plugin that aren't optional have to be supported by the audition.
+    pub unsafe fn start(
+        client_name: &str,
+        plugin: &PluginEntry,
+        features: &'static HostFeatures,
+    ) -> Result<Self, HostError> {
+        let (client, _) =
+            Client::new(client_name, ClientOptions::NO_START_SERVER).map_err(backend_error)?;
+        let engine = Engine::new(
+            plugin,
+            client.sample_rate() as f64,
+            client.buffer_size() as usize,
+            features,
+        )?;
+
+        let inputs = engine
+            .audio_inputs()
+            .map(|symbol| client.register_port(symbol, AudioIn::default()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(backend_error)?;
+        let outputs = engine
+            .audio_outputs()
+            .map(|symbol| client.register_port(symbol, AudioOut::default()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(backend_error)?;
+        let midi = if engine.has_midi_input() {
+            Some(
+                client
+                    .register_port("midi_in", MidiIn::default())
+                    .map_err(backend_error)?,
+            )
+        } else {
+            None
+        };
+
+        let controls = engine.controls();
+        let process = JackProcess {
+            engine,
+            inputs,
+            outputs,
+            midi,
+        };
+        let client = client.activate_async((), process).map_err(backend_error)?;
+        Ok(Self { client, controls })
+    }
+
+    /// The controls of the instance.
+    pub fn controls(&self) -> &AuditionControls {
+        &self.controls
+    }
+
+    /// The JACK client, for example to connect its ports.
+    pub fn client(&self) -> &Client {
+        self.client.as_client()
+    }
+
+    /// Stop running the instance and close the client.
+    pub fn stop(self) -> Result<(), HostError> {
+        self.client.deactivate().map(|_| ()).map_err(backend_error)
+    }
+}
@@ -0,0 +1,167 @@
+//! Loading and running LV2 plugins in hosts.
+//!
+//! This crate implements the host side of LV2, without linking to [lilv](https://drobilla.net/software/lilv): It finds the bundles in the `LV2_PATH`, reads the descriptions of the plugins in them, loads their shared libraries and instantiates them. This is enough to write simple hosts and offline renderers, as well as integration tests of plugins.
+//!
+//! The plugins that were found are stored in a [`World`](struct.World.html). Every plugin is described by a [`PluginEntry`](struct.PluginEntry.html), which contains its ports, the features it requires and its UIs. The world also knows the presets of all bundles and the model of all loaded Turtle documents, which can be queried for everything else. The host features are collected in [`HostFeatures`](struct.HostFeatures.html), which are passed to [`PluginEntry::instantiate`](struct.PluginEntry.html#method.instantiate) to create an [`Instance`](struct.Instance.html) of the plugin. Its ports are connected to buffers owned by the host and then, it can be run.
+//!
+//! The URID map of the features can be [shared](struct.HostFeatures.html#method.with_shared_urid_map) with other features, so that all instances of a session use the same URIDs, and it can be [saved](fn.save_urid_table.html) and [loaded](fn.load_urid_table.html) to use the same URIDs in every session.
+//!
+//! Instances can be chained in a [`ProcessGraph`](struct.ProcessGraph.html), which owns the buffers of their ports and runs them in the order of their connections, to render audio through effect chains.
+//!
+//! For debugging, the events an atom input of a graph receives can be recorded in an [`AtomLog`](struct.AtomLog.html), which is saved to a compact binary file and replayed through a graph later, to reproduce problems with the event stream of a host offline.
+//!
+//! If the features contain a [worker](struct.HostFeatures.html#method.with_worker), instances that provide the worker extension get a worker thread, which does the work they schedule and whose responses are delivered after every cycle.
+//!
+//! The state of instances that provide the state extension can be saved to and restored from a [`StateStore`](trait.StateStore.html), which lets session managers and preset converters extract and inject the state of plugins.
+//!
+//! Presets, like the ones the world found in the bundles, are [applied](struct.ProcessGraph.html#method.apply_preset) to the nodes of a graph, which sets their control inputs and restores their state, and the current configuration of a node is [captured](struct.ProcessGraph.html#method.capture_preset) in a new preset, which can be saved to a bundle. This lets session tools switch presets and lets tests check that presets survive a round trip.
+//!
+//! Plugins can also be auditioned live: With the `jack` feature, `JackAudition` runs an instance in a JACK client with ports for its audio and MIDI inputs and outputs, and with the `cpal` feature, `CpalAudition` runs an instance with the default output device of the system. Both provide `AuditionControls`, which change the controls of the instance and send MIDI messages to it while it's running.
+//!
+//! ## Example usage
+//!
+//! ```no_run
+//! use lv2_host::*;
+//! use std::ffi::c_void;
+//!
+//! let mut world = World::new();
+//! world.load_all();
+//! let plugin = world.plugin("http://lv2plug.in/plugins/eg-amp").unwrap();
+//!
+//! let features = HostFeatures::new().with_urid_map();
+//! let mut instance = unsafe { plugin.instantiate(44100.0, &features) }.unwrap();
+//!
+//! let mut gain: f32 = -6.0;
+//! let mut input: Vec<f32> = vec![1.0; 256];
+//! let mut output: Vec<f32> = vec![0.0; 256];
+//! unsafe {
+//!     instance.connect_port(plugin.port("gain").unwrap().index(), &mut gain as *mut f32 as *mut c_void);
+//!     instance.connect_port(plugin.port("in").unwrap().index(), input.as_mut_ptr() as *mut c_void);
+//!     instance.connect_port(plugin.port("out").unwrap().index(), output.as_mut_ptr() as *mut c_void);
+//! }
+//!
+//! instance.activate();
+//! unsafe { instance.run(256) };
+//! instance.deactivate();
+//! ```
+extern crate lv2_core as core;
+extern crate lv2_presets as presets;
+extern crate lv2_state as state;
+extern crate lv2_sys as sys;
+
+mod atom_log;
+pub use atom_log::*;
+
+#[cfg(any(feature = "jack", feature = "cpal"))]
+mod audition;
+#[cfg(any(feature = "jack", feature = "cpal"))]
+pub use audition::AuditionControls;
+
+#[cfg(feature = "cpal")]
+mod cpal_backend;
+#[cfg(feature = "cpal")]
+pub use cpal_backend::*;
+
+mod features;
+pub use features::*;
+
+mod graph;
+pub use graph::*;
+
+mod instance;
+pub use instance::*;
+
+#[cfg(feature = "jack")]
+mod jack_backend;
+#[cfg(feature = "jack")]
+pub use jack_backend::*;
+
+mod plugin;
+pub use plugin::*;
+
+mod store;
+pub use store::*;
+
+mod urid_table;
+pub use urid_table::*;
+
+mod world;
+pub use world::*;
+
+mod worker;
+
+use presets::turtle::TurtleError;
+use presets::PresetError;
+use state::StateErr;
+
+/// Errors that may occur when loading or instantiating plugins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HostError {
+    /// Reading a file failed.
+    Io(std::io::ErrorKind),
+    /// A Turtle document is malformed.
+    Turtle(TurtleError),
+    /// A path can't be converted to an IRI or a C string.
+    BadPath,
+    /// The shared library of a plugin couldn't be loaded.
+    ///
+    /// This includes libraries that don't export a `lv2_descriptor` function.
+    Library(String),
+    /// The plugin doesn't declare a binary or its binary doesn't contain the plugin.
+    MissingBinary,
+    /// The host doesn't provide a feature the plugin requires.
+    MissingFeature(String),
+    /// The plugin failed to instantiate itself.
+    InstantiationFailed,
+    /// The plugin doesn't provide an extension the host tried to use.
+    MissingExtension(String),
+    /// Saving or restoring the state of an instance failed.
+    State(StateErr),
+    /// The worker of the features is already used by another instance.
+    WorkerInUse,
+    /// An audio backend failed, for example since its server isn't running.
+    Backend(String),
+    /// A preset couldn't be converted, for example since its state contains values that aren't supported.
+    Preset(PresetError),
+    /// A node or a port of a process graph doesn't exist or can't be used.
+    Graph(GraphError),
+}
+
+impl From<std::io::Error> for HostError {
+    fn from(error: std::io::Error) -> Self {
+        HostError::Io(error.kind())
+    }
+}
+
+impl From<TurtleError> for HostError {
+    fn from(error: TurtleError) -> Self {
+        HostError::Turtle(error)
+    }
+}
+
+impl From<PresetError> for HostError {
+    fn from(error: PresetError) -> Self {
+        HostError::Preset(error)
+    }
+}
+
+impl From<GraphError> for HostError {
+    fn from(error: GraphError) -> Self {
+        HostError::Graph(error)
+    }
+}
+
+/// Prelude of `lv2_host` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        GraphError, HostError, HostFeatures, Instance, NodeId, PluginEntry, PortEntry, PortKind,
+        ProcessGraph, StateStore, UiEntry, World,
+    };
+
+    #[cfg(any(feature = "jack", feature = "cpal"))]
+    pub use crate::AuditionControls;
+    #[cfg(feature = "cpal")]
+    pub use crate::CpalAudition;
+    #[cfg(feature = "jack")]
+    pub use crate::JackAudition;
+}
@@ -0,0 +1,357 @@
+use crate::{HostError, HostFeatures, Instance};
+use core::port::{AudioF64, PortDirection};
+use presets::turtle::*;
+use std::path::{Path, PathBuf};
+use urid::{uri_str, UriBound};
+
+pub(crate) const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const DOAP_NAME: &str = "http://usefulinc.com/ns/doap#name";
+
+/// The kind of data a port carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PortKind {
+    /// A slice of audio samples.
+    Audio,
+    /// A slice of double-precision audio samples, which is an extension of rust-lv2.
+    AudioF64,
+    /// A single control value.
+    Control,
+    /// A slice of CV samples.
+    CV,
+    /// An atom, usually a sequence of events.
+    Atom,
+    /// A port class that isn't known to this crate.
+    Other,
+}
+
+/// The description of a port of a plugin, as read from its Turtle description.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortEntry {
+    index: u32,
+    symbol: String,
+    name: Option<String>,
+    kind: PortKind,
+    direction: Option<PortDirection>,
+    classes: Vec<String>,
+    optional: bool,
+    default: Option<f32>,
+    minimum: Option<f32>,
+    maximum: Option<f32>,
+}
+
+impl PortEntry {
+    /// Read the description of a port from a graph.
+    ///
+    /// Ports without an index or a symbol are ignored.
+    fn from_graph(graph: &Graph, port: &Term) -> Option<Self> {
+        let index = literal(graph, port, uri_str(sys::LV2_CORE__index))?
+            .parse()
+            .ok()?;
+        let symbol = literal(graph, port, uri_str(sys::LV2_CORE__symbol))?.to_owned();
+        let classes = iris(graph, port, RDF_TYPE);
+        let has_class = |class: &'static [u8]| classes.iter().any(|known| known == uri_str(class));
+
+        let kind = if has_class(sys::LV2_CORE__AudioPort) {
+            PortKind::Audio
+        } else if has_class(AudioF64::URI) {
+            PortKind::AudioF64
+        } else if has_class(sys::LV2_CORE__ControlPort) {
+            PortKind::Control
+        } else if has_class(sys::LV2_CORE__CVPort) {
+            PortKind::CV
+        } else if has_class(sys::LV2_ATOM__AtomPort) {
+            PortKind::Atom
+        } else {
+            PortKind::Other
+        };
+        let direction = if has_class(sys::LV2_CORE__InputPort) {
+            Some(PortDirection::Input)
+        } else if has_class(sys::LV2_CORE__OutputPort) {
+            Some(PortDirection::Output)
+        } else {
+            None
+        };
+        let optional = iris(graph, port, uri_str(sys::LV2_CORE__portProperty))
+            .iter()
+            .any(|property| property == uri_str(sys::LV2_CORE__connectionOptional));
+        let number = |predicate: &'static [u8]| {
+            literal(graph, port, uri_str(predicate)).and_then(|value| value.parse().ok())
+        };
+
+        Some(Self {
+            index,
+            symbol,
+            name: literal(graph, port, uri_str(sys::LV2_CORE__name)).map(str::to_owned),
+            kind,
+            direction,
+            optional,
+            default: number(sys::LV2_CORE__default),
+            minimum: number(sys::LV2_CORE__minimum),
+            maximum: number(sys::LV2_CORE__maximum),
+            classes,
+        })
+    }
+
+    /// The index of the port.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The symbol of the port.
+    pub fn symbol(&self) -> &str {
+        self.symbol.as_ref()
+    }
+
+    /// The human-readable name of the port, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The kind of data the port carries.
+    pub fn kind(&self) -> PortKind {
+        self.kind
+    }
+
+    /// The direction of the port, if it's declared.
+    pub fn direction(&self) -> Option<PortDirection> {
+        self.direction
+    }
+
+    /// The URIs of all classes of the port.
+    pub fn classes(&self) -> &[String] {
+        self.classes.as_ref()
+    }
+
+    /// Whether the port may be left unconnected.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// The default value of the port, if it has one.
+    pub fn default(&self) -> Option<f32> {
+        self.default
+    }
+
+    /// The minimum value of the port, if it has one.
+    pub fn minimum(&self) -> Option<f32> {
+        self.minimum
+    }
+
+    /// The maximum value of the port, if it has one.
+    pub fn maximum(&self) -> Option<f32> {
+        self.maximum
+    }
+}
+
+/// The description of a user interface of a plugin, as read from the Turtle documents of its bundle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiEntry {
+    uri: String,
+    classes: Vec<String>,
+    binary_path: Option<PathBuf>,
+    required_features: Vec<String>,
+    optional_features: Vec<String>,
+}
+
+impl UiEntry {
+    /// Read the description of a UI from a graph.
+    fn from_graph(graph: &Graph, uri: &str) -> Self {
+        let subject = Term::iri(uri);
+        // `ui:binary` is deprecated, but still used by older bundles.
+        let binary_path = graph
+            .object(&subject, uri_str(sys::LV2_CORE__binary))
+            .or_else(|| graph.object(&subject, uri_str(sys::LV2_UI__binary)))
+            .and_then(Term::as_iri)
+            .and_then(iri_path);
+        Self {
+            uri: uri.to_owned(),
+            classes: iris(graph, &subject, RDF_TYPE),
+            binary_path,
+            required_features: iris(graph, &subject, uri_str(sys::LV2_CORE__requiredFeature)),
+            optional_features: iris(graph, &subject, uri_str(sys::LV2_CORE__optionalFeature)),
+        }
+    }
+
+    /// The URI of the UI.
+    pub fn uri(&self) -> &str {
+        self.uri.as_ref()
+    }
+
+    /// The URIs of all classes of the UI, which tell the toolkit it's written with, like `ui:X11UI`.
+    pub fn classes(&self) -> &[String] {
+        self.classes.as_ref()
+    }
+
+    /// The path of the UI's shared library, if it's declared.
+    pub fn binary_path(&self) -> Option<&Path> {
+        self.binary_path.as_deref()
+    }
+
+    /// The URIs of the features the UI requires.
+    pub fn required_features(&self) -> &[String] {
+        self.required_features.as_ref()
+    }
+
+    /// The URIs of the features the UI can use, but doesn't require.
+    pub fn optional_features(&self) -> &[String] {
+        self.optional_features.as_ref()
+    }
+}
+
+/// The description of a plugin, as read from the Turtle documents of its bundle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluginEntry {
+    uri: String,
+    name: Option<String>,
+    classes: Vec<String>,
+    bundle_path: PathBuf,
+    binary_path: Option<PathBuf>,
+    required_features: Vec<String>,
+    optional_features: Vec<String>,
+    extensions: Vec<String>,
+    ports: Vec<PortEntry>,
+    uis: Vec<UiEntry>,
+}
+
+impl PluginEntry {
+    /// Read the description of a plugin from the graph of its bundle.
+    pub(crate) fn from_graph(graph: &Graph, uri: &str, bundle_path: &Path) -> Self {
+        let subject = Term::iri(uri);
+        let mut ports: Vec<PortEntry> = graph
+            .objects(&subject, uri_str(sys::LV2_CORE__port))
+            .filter_map(|port| PortEntry::from_graph(graph, port))
+            .collect();
+        ports.sort_by_key(PortEntry::index);
+
+        Self {
+            uri: uri.to_owned(),
+            name: literal(graph, &subject, DOAP_NAME).map(str::to_owned),
+            classes: iris(graph, &subject, RDF_TYPE),
+            bundle_path: bundle_path.to_owned(),
+            binary_path: graph
+                .object(&subject, uri_str(sys::LV2_CORE__binary))
+                .and_then(Term::as_iri)
+                .and_then(iri_path),
+            required_features: iris(graph, &subject, uri_str(sys::LV2_CORE__requiredFeature)),
+            optional_features: iris(graph, &subject, uri_str(sys::LV2_CORE__optionalFeature)),
+            extensions: iris(graph, &subject, uri_str(sys::LV2_CORE__extensionData)),
+            ports,
+            uis: iris(graph, &subject, uri_str(sys::LV2_UI__ui))
+                .iter()
+                .map(|ui| UiEntry::from_graph(graph, ui))
+                .collect(),
+        }
+    }
+
+    /// The URI of the plugin.
+    pub fn uri(&self) -> &str {
+        self.uri.as_ref()
+    }
+
+    /// The human-readable name of the plugin, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The URIs of all classes of the plugin, like `lv2:AmplifierPlugin`.
+    pub fn classes(&self) -> &[String] {
+        self.classes.as_ref()
+    }
+
+    /// The path of the plugin's bundle.
+    pub fn bundle_path(&self) -> &Path {
+        self.bundle_path.as_ref()
+    }
+
+    /// The path of the plugin's shared library, if it's declared.
+    pub fn binary_path(&self) -> Option<&Path> {
+        self.binary_path.as_deref()
+    }
+
+    /// The URIs of the features the plugin requires.
+    pub fn required_features(&self) -> &[String] {
+        self.required_features.as_ref()
+    }
+
+    /// The URIs of the features the plugin can use, but doesn't require.
+    pub fn optional_features(&self) -> &[String] {
+        self.optional_features.as_ref()
+    }
+
+    /// The URIs of the extensions the plugin provides.
+    pub fn extensions(&self) -> &[String] {
+        self.extensions.as_ref()
+    }
+
+    /// All ports of the plugin, ordered by their index.
+    pub fn ports(&self) -> &[PortEntry] {
+        self.ports.as_ref()
+    }
+
+    /// Find the port with the given symbol.
+    pub fn port(&self, symbol: &str) -> Option<&PortEntry> {
+        self.ports.iter().find(|port| port.symbol() == symbol)
+    }
+
+    /// The user interfaces of the plugin.
+    pub fn uis(&self) -> &[UiEntry] {
+        self.uis.as_ref()
+    }
+
+    /// Check whether the given features contain all features the plugin requires.
+    ///
+    /// If a required feature is missing, its URI is returned as an error.
+    pub fn check_features(&self, features: &HostFeatures) -> Result<(), HostError> {
+        match self
+            .required_features
+            .iter()
+            .find(|feature| !features.supports(feature))
+        {
+            Some(feature) => Err(HostError::MissingFeature(feature.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Load the plugin's shared library and create a new instance of the plugin.
+    ///
+    /// The instance borrows the features, since the plugin may use them until it is dropped.
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialization code and the plugin's functions are called without any checks. The library has to be an LV2 plugin library that follows the specification.
+    pub unsafe fn instantiate<'a>(
+        &self,
+        sample_rate: f64,
+        features: &'a HostFeatures,
+    ) -> Result<Instance<'a>, HostError> {
+        self.check_features(features)?;
+        let binary_path = self.binary_path().ok_or(HostError::MissingBinary)?;
+        Instance::load(
+            binary_path,
+            self.uri(),
+            self.bundle_path(),
+            sample_rate,
+            features,
+        )
+    }
+}
+
+/// Return the lexical form of the first literal object of the given subject and predicate.
+fn literal<'a>(graph: &'a Graph, subject: &'a Term, predicate: &'a str) -> Option<&'a str> {
+    graph
+        .objects(subject, predicate)
+        .find_map(|object| object.as_literal())
+}
+
+/// Return all IRI objects of the given subject and predicate, without duplicates.
+pub(crate) fn iris(graph: &Graph, subject: &Term, predicate: &str) -> Vec<String> {
+    let mut iris: Vec<String> = Vec::new();
+    for object in graph.objects(subject, predicate) {
+        if let Some(iri) = object.as_iri() {
+            if !iris.iter().any(|known| known == iri) {
+                iris.push(iri.to_owned());
+            }
+        }
+    }
+    iris
+}
@@ -0,0 +1,91 @@
+use state::{StateErr, Storage};
+use std::ffi::c_void;
+use std::ptr::null;
+use urid::URID;
+
+/// A store for the state of an instance.
+///
+/// When the state of an [`Instance`](struct.Instance.html) is [saved](struct.Instance.html#method.save_state), the plugin passes its properties to the store, and when it's [restored](struct.Instance.html#method.restore_state), the plugin retrieves them again. Every property is identified by a key and has a type, which are both URIDs, and flags, which are a combination of the raw `LV2_State_Flags`.
+///
+/// Session managers or preset converters implement this trait to save the properties in their own format. The [`Storage`](../lv2_state/struct.Storage.html) of `lv2-state` is a simple store that keeps the properties in memory.
+pub trait StateStore {
+    /// Store a property.
+    fn store(&mut self, key: URID, type_: URID, value: &[u8], flags: u32) -> Result<(), StateErr>;
+
+    /// Retrieve a property, as its type, its value and its flags.
+    ///
+    /// If the property doesn't exist, `None` is returned.
+    fn retrieve(&self, key: URID) -> Option<(URID, &[u8], u32)>;
+}
+
+impl StateStore for Storage {
+    fn store(&mut self, key: URID, type_: URID, value: &[u8], _: u32) -> Result<(), StateErr> {
+        Storage::store(self, key, type_, value);
+        Ok(())
+    }
+
+    fn retrieve(&self, key: URID) -> Option<(URID, &[u8], u32)> {
+        let flags = (sys::LV2_State_Flags::LV2_STATE_IS_POD
+            | sys::LV2_State_Flags::LV2_STATE_IS_PORTABLE)
+            .into();
+        Storage::retrieve(self, key).map(|(type_, value)| (type_, value, flags))
+    }
+}
+
+/// The store function that is passed to the plugin, with a `*mut S` as the handle.
+pub(crate) unsafe extern "C" fn extern_store<S: StateStore>(
+    handle: sys::LV2_State_Handle,
+    key: u32,
+    value: *const c_void,
+    size: usize,
+    type_: u32,
+    flags: u32,
+) -> sys::LV2_State_Status {
+    let store = if let Some(store) = (handle as *mut S).as_mut() {
+        store
+    } else {
+        return sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN;
+    };
+    let (key, type_) = match (URID::new(key), URID::new(type_)) {
+        (Some(key), Some(type_)) => (key, type_),
+        (None, _) => return sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN,
+        (_, None) => return sys::LV2_State_Status_LV2_STATE_ERR_BAD_TYPE,
+    };
+    let value = if size == 0 || value.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(value as *const u8, size)
+    };
+    StateErr::into(store.store(key, type_, value, flags))
+}
+
+/// The retrieve function that is passed to the plugin, with a `*const S` as the handle.
+pub(crate) unsafe extern "C" fn extern_retrieve<S: StateStore>(
+    handle: sys::LV2_State_Handle,
+    key: u32,
+    size: *mut usize,
+    type_: *mut u32,
+    flags: *mut u32,
+) -> *const c_void {
+    let store = if let Some(store) = (handle as *const S).as_ref() {
+        store
+    } else {
+        return null();
+    };
+    let (value_type, value, value_flags) =
+        if let Some(property) = URID::new(key).and_then(|key| store.retrieve(key)) {
+            property
+        } else {
+            return null();
+        };
+    if !size.is_null() {
+        *size = value.len();
+    }
+    if !type_.is_null() {
+        *type_ = value_type.get();
+    }
+    if !flags.is_null() {
+        *flags = value_flags;
+    }
+    value.as_ptr() as *const c_void
+}
@@ -0,0 +1,37 @@
+use crate::HostError;
+use std::io::{BufRead, ErrorKind, Write};
+use urid::*;
+
+/// Write the URIDs of a mapper and their URIs, one per line and ordered by URID.
+///
+/// Every line contains a URID, followed by its URI in angle brackets, like `1 <http://lv2plug.in/ns/ext/atom#Int>`. The table can be loaded with [`load_urid_table`](fn.load_urid_table.html), which lets a host assign the same URIDs in every session.
+pub fn save_urid_table<W: Write>(mapper: &HashURIDMapper, mut writer: W) -> Result<(), HostError> {
+    for (urid, uri) in mapper.table() {
+        writeln!(writer, "{} <{}>", urid.get(), uri.to_string_lossy())?;
+    }
+    Ok(())
+}
+
+/// Create a mapper from a table that was written by [`save_urid_table`](fn.save_urid_table.html).
+///
+/// Empty lines and lines starting with `#` are ignored. If a line is malformed or the table isn't a valid table of a [`HashURIDMapper`](../urid/struct.HashURIDMapper.html), which has to contain the URIDs from one to the number of entries, an error of the kind `InvalidData` is returned.
+pub fn load_urid_table<R: BufRead>(reader: R) -> Result<HashURIDMapper, HostError> {
+    let mut table: Vec<(URID, UriBuf)> = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        table.push(parse_entry(line).ok_or(HostError::Io(ErrorKind::InvalidData))?);
+    }
+    HashURIDMapper::from_table(table).ok_or(HostError::Io(ErrorKind::InvalidData))
+}
+
+/// Parse a line of a URID table.
+fn parse_entry(line: &str) -> Option<(URID, UriBuf)> {
+    let (urid, uri) = line.split_once(char::is_whitespace)?;
+    let urid = URID::new(urid.parse().ok()?)?;
+    let uri = uri.trim_start().strip_prefix('<')?.strip_suffix('>')?;
+    Some((urid, UriBuf::new(uri).ok()?))
+}
@@ -0,0 +1,307 @@
+use std::cell::UnsafeCell;
+use std::ffi::c_void;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle, Thread};
+
+/// The size of the header of a message, which is also the alignment of messages.
+const HEADER_SIZE: usize = size_of::<u64>();
+
+/// The header of the filler that skips the rest of the buffer.
+const SKIP: u64 = u64::MAX;
+
+/// A lock-free ring buffer of byte messages with a single writer and a single reader.
+///
+/// Both positions are counters of the bytes that have been written and read, respectively. They are only reduced to an offset in the buffer when it's accessed, which is why the capacity is a power of two. Every message is its size, followed by its bytes and padded to 64 bits. Messages don't wrap around the end of the buffer, which is why the rest of the buffer is skipped if a message doesn't fit in it anymore.
+pub(crate) struct Ring {
+    buffer: Box<[UnsafeCell<u64>]>,
+    write: AtomicUsize,
+    read: AtomicUsize,
+}
+
+// The writer only accesses free bytes and the reader only accesses written bytes, which are synchronized by the positions.
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(2 * HEADER_SIZE).next_power_of_two();
+        Self {
+            buffer: (0..capacity / HEADER_SIZE)
+                .map(|_| UnsafeCell::new(0))
+                .collect(),
+            write: AtomicUsize::new(0),
+            read: AtomicUsize::new(0),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.buffer.len() * HEADER_SIZE
+    }
+
+    fn ptr(&self, offset: usize) -> *mut u8 {
+        unsafe { (self.buffer.as_ptr() as *mut u8).add(offset) }
+    }
+
+    /// Write a message, which fails if there isn't enough space.
+    ///
+    /// This must only be called by the writer.
+    pub fn push(&self, data: &[u8]) -> bool {
+        let size = HEADER_SIZE + data.len().div_ceil(HEADER_SIZE) * HEADER_SIZE;
+        let write = self.write.load(Ordering::Relaxed);
+        let free = self.capacity() - write.wrapping_sub(self.read.load(Ordering::Acquire));
+        let offset = write & (self.capacity() - 1);
+        let end = self.capacity() - offset;
+
+        let (start, skipped) = if size <= end { (offset, 0) } else { (0, end) };
+        if skipped + size > free {
+            return false;
+        }
+        unsafe {
+            if skipped > 0 {
+                (self.ptr(offset) as *mut u64).write(SKIP);
+            }
+            (self.ptr(start) as *mut u64).write(data.len() as u64);
+            std::ptr::copy_nonoverlapping(data.as_ptr(), self.ptr(start + HEADER_SIZE), data.len());
+        }
+        self.write
+            .store(write.wrapping_add(skipped + size), Ordering::Release);
+        true
+    }
+
+    /// Read the next message with the given closure and remove it afterwards.
+    ///
+    /// This must only be called by the reader.
+    pub fn pop<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> Option<R> {
+        let mut read = self.read.load(Ordering::Relaxed);
+        loop {
+            if read == self.write.load(Ordering::Acquire) {
+                return None;
+            }
+            let offset = read & (self.capacity() - 1);
+            let header = unsafe { (self.ptr(offset) as *const u64).read() };
+            if header == SKIP {
+                read = read.wrapping_add(self.capacity() - offset);
+                self.read.store(read, Ordering::Release);
+                continue;
+            }
+            let len = header as usize;
+            let data = unsafe {
+                std::slice::from_raw_parts(self.ptr(offset + HEADER_SIZE) as *const u8, len)
+            };
+            let result = f(data);
+            let size = HEADER_SIZE + len.div_ceil(HEADER_SIZE) * HEADER_SIZE;
+            self.read.store(read.wrapping_add(size), Ordering::Release);
+            return Some(result);
+        }
+    }
+
+    /// Remove all messages.
+    ///
+    /// This must only be called if neither the writer nor the reader is in use.
+    fn clear(&self) {
+        self.read
+            .store(self.write.load(Ordering::Acquire), Ordering::Release);
+    }
+}
+
+/// The state that is shared between the schedule feature, the worker thread and the instance.
+struct WorkerShared {
+    /// The requests, which are written by `schedule_work` and read by the worker thread.
+    requests: Ring,
+    /// The responses, which are written by the worker thread and read by the instance after it was run.
+    responses: Ring,
+    /// Whether the feature is used by an instance.
+    attached: AtomicBool,
+    /// Whether the worker thread should keep running.
+    running: AtomicBool,
+    /// The worker thread, which is woken up when work is scheduled.
+    thread: Mutex<Option<Thread>>,
+}
+
+/// The worker schedule feature of a host.
+///
+/// The requests are sent to a worker thread, which is started when an instance that provides the worker interface is created. The interface is only accessed by plugins, via the data pointer of the feature.
+pub(crate) struct WorkerFeature {
+    shared: Arc<WorkerShared>,
+    schedule: Box<sys::LV2_Worker_Schedule>,
+}
+
+impl WorkerFeature {
+    /// Create the feature with ring buffers that can hold at least `capacity` bytes each.
+    pub fn new(capacity: usize) -> Self {
+        let shared = Arc::new(WorkerShared {
+            requests: Ring::new(capacity),
+            responses: Ring::new(capacity),
+            attached: AtomicBool::new(false),
+            running: AtomicBool::new(false),
+            thread: Mutex::new(None),
+        });
+        let schedule = Box::new(sys::LV2_Worker_Schedule {
+            handle: Arc::as_ptr(&shared) as *mut c_void,
+            schedule_work: Some(extern_schedule),
+        });
+        Self { shared, schedule }
+    }
+
+    /// The data pointer of the feature.
+    pub fn data(&mut self) -> *mut c_void {
+        self.schedule.as_mut() as *mut sys::LV2_Worker_Schedule as *mut c_void
+    }
+
+    /// Start a worker thread for the instance with the given handle and worker interface.
+    ///
+    /// Returns `None` if the feature is already used by another instance or if the interface has no `work` function.
+    pub unsafe fn attach(
+        &self,
+        handle: sys::LV2_Handle,
+        interface: &'static sys::LV2_Worker_Interface,
+    ) -> Option<WorkerThread> {
+        let work = interface.work?;
+        if self.shared.attached.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        self.shared.requests.clear();
+        self.shared.responses.clear();
+        self.shared.running.store(true, Ordering::Release);
+
+        let shared = self.shared.clone();
+        let instance = SendHandle(handle);
+        let thread = thread::spawn(move || {
+            let respond_handle = Arc::as_ptr(&shared) as *mut c_void;
+            while shared.running.load(Ordering::Acquire) {
+                while shared
+                    .requests
+                    .pop(|request| {
+                        work(
+                            instance.0,
+                            Some(extern_respond),
+                            respond_handle,
+                            request.len() as u32,
+                            request.as_ptr() as *const c_void,
+                        )
+                    })
+                    .is_some()
+                {}
+                thread::park();
+            }
+        });
+        if let Ok(mut waker) = self.shared.thread.lock() {
+            *waker = Some(thread.thread().clone());
+        }
+        // Work that was scheduled before the waker was set must not be missed.
+        thread.thread().unpark();
+
+        Some(WorkerThread {
+            shared: self.shared.clone(),
+            interface,
+            thread: Some(thread),
+        })
+    }
+}
+
+/// The handle of an instance, which is sent to the worker thread.
+struct SendHandle(sys::LV2_Handle);
+
+// The worker interface of a plugin may be called from any thread.
+unsafe impl Send for SendHandle {}
+
+/// The worker thread of an instance.
+///
+/// The thread is stopped when this struct is dropped, which has to happen before the instance is cleaned up.
+pub(crate) struct WorkerThread {
+    shared: Arc<WorkerShared>,
+    interface: &'static sys::LV2_Worker_Interface,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WorkerThread {
+    /// Deliver all responses of the worker to the instance and call `end_run`.
+    ///
+    /// This has to be called in the audio thread, after the instance was run.
+    pub unsafe fn end_run(&self, handle: sys::LV2_Handle) {
+        if let Some(work_response) = self.interface.work_response {
+            while self
+                .shared
+                .responses
+                .pop(|response| {
+                    work_response(
+                        handle,
+                        response.len() as u32,
+                        response.as_ptr() as *const c_void,
+                    )
+                })
+                .is_some()
+            {}
+        }
+        if let Some(end_run) = self.interface.end_run {
+            end_run(handle);
+        }
+    }
+}
+
+impl Drop for WorkerThread {
+    fn drop(&mut self) {
+        self.shared.running.store(false, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+        if let Ok(mut waker) = self.shared.thread.lock() {
+            *waker = None;
+        }
+        self.shared.attached.store(false, Ordering::Release);
+    }
+}
+
+/// The `schedule_work` function of the worker schedule feature.
+///
+/// The handle has to be a pointer to the shared state of the feature. Since it's called from the audio thread, it never blocks: If the worker thread is being started or stopped, it's woken up by the starting thread or not at all.
+unsafe extern "C" fn extern_schedule(
+    handle: sys::LV2_Worker_Schedule_Handle,
+    size: u32,
+    data: *const c_void,
+) -> sys::LV2_Worker_Status {
+    let shared = match (handle as *const WorkerShared).as_ref() {
+        Some(shared) => shared,
+        None => return sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+    };
+    if !shared.running.load(Ordering::Acquire) {
+        return sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
+    }
+    if !shared.requests.push(bytes(size, data)) {
+        return sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE;
+    }
+    if let Ok(waker) = shared.thread.try_lock() {
+        if let Some(thread) = waker.as_ref() {
+            thread.unpark();
+        }
+    }
+    sys::LV2_Worker_Status_LV2_WORKER_SUCCESS
+}
+
+/// The response function that is passed to the `work` function of the plugin.
+///
+/// The handle has to be a pointer to the shared state of the feature.
+unsafe extern "C" fn extern_respond(
+    handle: sys::LV2_Worker_Respond_Handle,
+    size: u32,
+    data: *const c_void,
+) -> sys::LV2_Worker_Status {
+    match (handle as *const WorkerShared).as_ref() {
+        Some(shared) if shared.responses.push(bytes(size, data)) => {
+            sys::LV2_Worker_Status_LV2_WORKER_SUCCESS
+        }
+        Some(_) => sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+        None => sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+    }
+}
+
+/// Create a slice from the data of a request or response.
+unsafe fn bytes<'a>(size: u32, data: *const c_void) -> &'a [u8] {
+    if data.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data as *const u8, size as usize)
+    }
+}
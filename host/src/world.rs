@@ -0,0 +1,184 @@
+use crate::plugin::{iris, RDF_TYPE};
+use crate::{HostError, PluginEntry};
+use presets::turtle::*;
+use presets::Preset;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use urid::uri_str;
+
+const RDFS_SEE_ALSO: &str = "http://www.w3.org/2000/01/rdf-schema#seeAlso";
+
+/// The default search path for bundles, which is used if `LV2_PATH` isn't set.
+///
+/// These are the standard locations of LV2 bundles on the current platform. A leading `~` is replaced with the home directory.
+pub fn default_lv2_path() -> OsString {
+    let home = |path: &str| -> Option<PathBuf> {
+        let home = std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })?;
+        Some(PathBuf::from(home).join(path))
+    };
+    let paths: Vec<Option<PathBuf>> = if cfg!(windows) {
+        vec![
+            std::env::var_os("APPDATA").map(|path| PathBuf::from(path).join("LV2")),
+            std::env::var_os("COMMONPROGRAMFILES").map(|path| PathBuf::from(path).join("LV2")),
+        ]
+    } else if cfg!(target_os = "macos") {
+        vec![
+            home("Library/Audio/Plug-Ins/LV2"),
+            home(".lv2"),
+            Some(PathBuf::from("/Library/Audio/Plug-Ins/LV2")),
+            Some(PathBuf::from("/usr/local/lib/lv2")),
+            Some(PathBuf::from("/usr/lib/lv2")),
+        ]
+    } else {
+        vec![
+            home(".lv2"),
+            Some(PathBuf::from("/usr/local/lib/lv2")),
+            Some(PathBuf::from("/usr/lib/lv2")),
+        ]
+    };
+    std::env::join_paths(paths.into_iter().flatten()).unwrap_or_default()
+}
+
+/// The set of plugins a host knows about.
+///
+/// Plugins are discovered by loading bundles, either all bundles in the directories of the `LV2_PATH` with [`load_all`](#method.load_all), or single bundles with [`load_bundle`](#method.load_bundle). If a plugin is found in more than one bundle, the first one is kept, so that the directories at the beginning of the `LV2_PATH` take precedence.
+///
+/// Besides the plugins, the world collects the [presets](#method.presets_for) of all bundles, including bundles that only contain presets, as well as the [model](#method.model) of all loaded Turtle documents, which can be queried for anything that isn't covered by the [`PluginEntry`](struct.PluginEntry.html).
+#[derive(Clone, Debug, Default)]
+pub struct World {
+    plugins: Vec<PluginEntry>,
+    presets: Vec<Preset>,
+    model: Graph,
+}
+
+impl World {
+    /// Create a world without any plugins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load all bundles in the directories of the `LV2_PATH` environment variable.
+    ///
+    /// If `LV2_PATH` isn't set, the [default path](fn.default_lv2_path.html) is used.
+    pub fn load_all(&mut self) {
+        let path = std::env::var_os("LV2_PATH").unwrap_or_else(default_lv2_path);
+        self.load_path(&path);
+    }
+
+    /// Load all bundles in the directories of the given search path.
+    ///
+    /// The search path has the same format as the `PATH` environment variable of the platform, for example `/usr/lib/lv2:/usr/local/lib/lv2` on Linux. Every subdirectory with a manifest is loaded as a bundle. Directories that don't exist and bundles that can't be loaded are skipped.
+    pub fn load_path(&mut self, path: &std::ffi::OsStr) {
+        for directory in std::env::split_paths(path) {
+            let entries = match fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            let mut bundles: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|bundle| bundle.join("manifest.ttl").is_file())
+                .collect();
+            bundles.sort();
+            for bundle in bundles.iter() {
+                let _ = self.load_bundle(bundle);
+            }
+        }
+    }
+
+    /// Load the plugins of the bundle at the given path.
+    ///
+    /// This parses the manifest of the bundle and all files that are referenced by the plugins, their UIs and the presets declared in it. Presets that are malformed are skipped.
+    pub fn load_bundle(&mut self, path: &Path) -> Result<(), HostError> {
+        let path = if path.is_absolute() {
+            path.to_owned()
+        } else {
+            std::env::current_dir()?.join(path)
+        };
+        let mut graph = Graph::new();
+        let manifest_path = path.join("manifest.ttl");
+        let manifest_iri = file_iri(&manifest_path).ok_or(HostError::BadPath)?;
+        graph.parse(&fs::read_to_string(&manifest_path)?, &manifest_iri)?;
+
+        let uris = instances(&graph, sys::LV2_CORE__Plugin);
+        let preset_uris = instances(&graph, sys::LV2_PRESETS__Preset);
+        let ui_uris: Vec<String> = uris
+            .iter()
+            .flat_map(|uri| iris(&graph, &Term::iri(uri), uri_str(sys::LV2_UI__ui)))
+            .collect();
+
+        let mut files: Vec<String> = Vec::new();
+        for uri in uris.iter().chain(ui_uris.iter()).chain(preset_uris.iter()) {
+            for file in iris(&graph, &Term::iri(uri), RDFS_SEE_ALSO) {
+                if file != manifest_iri && !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+        for file in files.iter() {
+            if let Some(file_path) = iri_path(file) {
+                graph.parse(&fs::read_to_string(file_path)?, file)?;
+            }
+        }
+
+        for uri in uris.iter() {
+            if self.plugin(uri).is_none() {
+                self.plugins
+                    .push(PluginEntry::from_graph(&graph, uri, &path));
+            }
+        }
+        for uri in preset_uris.iter() {
+            if self.presets.iter().all(|preset| preset.uri() != uri) {
+                if let Ok(preset) = Preset::from_graph(&graph, uri) {
+                    self.presets.push(preset);
+                }
+            }
+        }
+        self.model.merge(graph);
+        Ok(())
+    }
+
+    /// All known plugins.
+    pub fn plugins(&self) -> &[PluginEntry] {
+        self.plugins.as_ref()
+    }
+
+    /// Find the plugin with the given URI.
+    pub fn plugin(&self, uri: &str) -> Option<&PluginEntry> {
+        self.plugins.iter().find(|plugin| plugin.uri() == uri)
+    }
+
+    /// All known presets.
+    pub fn presets(&self) -> &[Preset] {
+        self.presets.as_ref()
+    }
+
+    /// Iterate over the presets that apply to the plugin with the given URI.
+    pub fn presets_for<'a>(&'a self, plugin: &'a str) -> impl Iterator<Item = &'a Preset> + 'a {
+        self.presets
+            .iter()
+            .filter(move |preset| preset.plugin() == Some(plugin))
+    }
+
+    /// The triples of all Turtle documents that have been loaded.
+    ///
+    /// The descriptions of plugins, UIs and presets only contain what hosts usually need. Everything else, like the port groups or the author of a plugin, can be queried from the model.
+    pub fn model(&self) -> &Graph {
+        &self.model
+    }
+}
+
+/// Return the IRIs of all resources that are declared as instances of the given class, without duplicates.
+fn instances(graph: &Graph, class: &'static [u8]) -> Vec<String> {
+    let class = Term::iri(uri_str(class));
+    let mut uris: Vec<String> = Vec::new();
+    for subject in graph.subjects(RDF_TYPE, &class) {
+        if let Some(uri) = subject.as_iri() {
+            if !uris.iter().any(|known| known == uri) {
+                uris.push(uri.to_owned());
+            }
+        }
+    }
+    uris
+}
@@ -0,0 +1,857 @@
+use lv2_atom::prelude::*;
+use lv2_core::prelude::*;
+use lv2_host::*;
+use lv2_presets::turtle::Term;
+use lv2_presets::PresetBuilder;
+use lv2_state::*;
+use lv2_ttl_gen::*;
+use lv2_units::prelude::*;
+use lv2_urid::*;
+use lv2_worker::*;
+use std::ffi::c_void;
+use std::path::PathBuf;
+use urid::*;
+
+#[derive(PortCollection)]
+struct Ports {
+    gain: InputPort<Control>,
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+    output_f64: Option<OutputPort<AudioF64>>,
+}
+
+#[derive(FeatureCollection)]
+struct Features<'a> {
+    map: LV2Map<'a>,
+}
+
+#[uri("urn:lv2_host:test:amp")]
+struct Amp {
+    activated: bool,
+    coef: f32,
+    urids: AtomURIDCollection,
+}
+
+#[extensions(StateDescriptor)]
+impl Plugin for Amp {
+    type Ports = Ports;
+    type InitFeatures = Features<'static>;
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+        // The mapper has to be functional.
+        features.map.map_type::<Amp>()?;
+        Some(Self {
+            activated: false,
+            coef: 0.0,
+            urids: features.map.populate_collection()?,
+        })
+    }
+
+    fn activate(&mut self, _: &mut Features<'static>) {
+        self.activated = true;
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        let coef = if self.activated { *ports.gain } else { 0.0 };
+        self.coef = coef;
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = input * coef;
+        }
+        if let Some(output) = ports.output_f64.as_mut() {
+            for (input, output) in ports.input.iter().zip(output.iter_mut()) {
+                *output = f64::from(input * coef);
+            }
+        }
+    }
+}
+
+/// The key of the last applied coefficient in the state of the amp.
+const COEF_KEY: u32 = 1000;
+
+impl State for Amp {
+    type StateFeatures = ();
+
+    fn save(&self, mut store: StoreHandle, _: ()) -> Result<(), StateErr> {
+        store
+            .draft(URID::new(COEF_KEY).unwrap())
+            .init(self.urids.float, self.coef)?;
+        store.commit_all()
+    }
+
+    fn restore(&mut self, store: RetrieveHandle, _: ()) -> Result<(), StateErr> {
+        self.coef = store
+            .retrieve(URID::new(COEF_KEY).unwrap())?
+            .read(self.urids.float, ())?;
+        Ok(())
+    }
+}
+
+#[derive(PortCollection)]
+struct DoublerPorts {
+    total: OutputPort<Control>,
+    end_runs: OutputPort<Control>,
+}
+
+#[derive(FeatureCollection)]
+struct DoublerFeatures<'a> {
+    schedule: Schedule<'a, Doubler>,
+}
+
+/// A plugin that lets its worker double the number of frames of every cycle and sums the responses up.
+#[uri("urn:lv2_host:test:doubler")]
+struct Doubler {
+    total: u32,
+    end_runs: u32,
+}
+
+#[extensions(WorkerDescriptor)]
+impl Plugin for Doubler {
+    type Ports = DoublerPorts;
+    type InitFeatures = ();
+    type AudioFeatures = DoublerFeatures<'static>;
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self {
+            total: 0,
+            end_runs: 0,
+        })
+    }
+
+    fn run(
+        &mut self,
+        ports: &mut DoublerPorts,
+        context: &mut RunContext<DoublerFeatures<'static>>,
+    ) {
+        if context.sample_count() > 0 {
+            let frames = context.sample_count();
+            assert!(context.features().schedule.schedule_work(frames).is_ok());
+        }
+        **ports.total = self.total as f32;
+        **ports.end_runs = self.end_runs as f32;
+    }
+}
+
+impl Worker for Doubler {
+    type WorkData = u32;
+    type ResponseData = u32;
+
+    fn work(response_handler: &ResponseHandler<Self>, data: u32) -> Result<(), WorkerError> {
+        response_handler
+            .respond(data * 2)
+            .map_err(|_| WorkerError::Unknown)
+    }
+
+    fn work_response(
+        &mut self,
+        data: u32,
+        _: &mut DoublerFeatures<'static>,
+    ) -> Result<(), WorkerError> {
+        self.total += data;
+        Ok(())
+    }
+
+    fn end_run(&mut self, _: &mut DoublerFeatures<'static>) -> Result<(), WorkerError> {
+        self.end_runs += 1;
+        Ok(())
+    }
+}
+
+#[derive(PortCollection)]
+struct CounterPorts {
+    events: InputPort<AtomPort>,
+    count: OutputPort<Control>,
+    sum: OutputPort<Control>,
+}
+
+#[derive(URIDCollection)]
+struct CounterURIDs {
+    atom: AtomURIDCollection,
+    units: UnitURIDCollection,
+}
+
+/// A plugin that counts the events it receives and sums up the ones that are integers.
+#[uri("urn:lv2_host:test:counter")]
+struct Counter {
+    urids: CounterURIDs,
+    count: u32,
+    sum: i32,
+}
+
+impl Plugin for Counter {
+    type Ports = CounterPorts;
+    type InitFeatures = Features<'static>;
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+        Some(Self {
+            urids: features.map.populate_collection()?,
+            count: 0,
+            sum: 0,
+        })
+    }
+
+    fn run(&mut self, ports: &mut CounterPorts, _: &mut RunContext<()>) {
+        let events = ports
+            .events
+            .read(self.urids.atom.sequence, self.urids.units.beat)
+            .unwrap();
+        for (_, atom) in events {
+            self.count += 1;
+            if let Some(value) = atom.read(self.urids.atom.int, ()) {
+                self.sum += value;
+            }
+        }
+        **ports.count = self.count as f32;
+        **ports.sum = self.sum as f32;
+    }
+}
+
+lv2_descriptors![Amp, Doubler, Counter];
+
+/// Write the bundle of the test plugin into a fresh directory and return the path of the directory.
+fn write_bundle(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&directory);
+    let amp = PluginDescription::new::<Amp>()
+        .name("Test amplifier")
+        .port_range("gain", 0.0, 1.0, 2.0);
+    BundleDescription::new(&binary_name("lv2_host_test_amp"))
+        .plugin(amp, "amp.ttl")
+        .write(&directory.join("amp.lv2"))
+        .unwrap();
+    directory
+}
+
+#[test]
+fn test_discovery() {
+    let directory = write_bundle("lv2_host_discovery");
+    let mut world = World::new();
+    world.load_path(directory.as_os_str());
+
+    assert_eq!(world.plugins().len(), 1);
+    let plugin = world.plugin("urn:lv2_host:test:amp").unwrap();
+    assert_eq!(plugin.name(), Some("Test amplifier"));
+    assert_eq!(plugin.bundle_path(), directory.join("amp.lv2"));
+    assert_eq!(
+        plugin.binary_path(),
+        Some(
+            directory
+                .join("amp.lv2")
+                .join(binary_name("lv2_host_test_amp"))
+                .as_ref()
+        )
+    );
+    assert_eq!(
+        plugin.required_features(),
+        &["http://lv2plug.in/ns/ext/urid#map".to_owned()]
+    );
+
+    let symbols: Vec<&str> = plugin.ports().iter().map(PortEntry::symbol).collect();
+    assert_eq!(symbols, vec!["gain", "input", "output", "output_f64"]);
+    let gain = plugin.port("gain").unwrap();
+    assert_eq!(gain.index(), 0);
+    assert_eq!(gain.kind(), PortKind::Control);
+    assert_eq!(gain.direction(), Some(PortDirection::Input));
+    assert_eq!(gain.default(), Some(1.0));
+    assert_eq!(gain.minimum(), Some(0.0));
+    assert_eq!(gain.maximum(), Some(2.0));
+    let output = plugin.port("output").unwrap();
+    assert_eq!(output.kind(), PortKind::Audio);
+    assert_eq!(output.direction(), Some(PortDirection::Output));
+    let output_f64 = plugin.port("output_f64").unwrap();
+    assert_eq!(output_f64.kind(), PortKind::AudioF64);
+    assert!(output_f64.is_optional());
+
+    // Loading the bundle again doesn't add the plugin twice.
+    world.load_bundle(&directory.join("amp.lv2")).unwrap();
+    assert_eq!(world.plugins().len(), 1);
+
+    // Missing bundles are errors, missing directories in the path are not.
+    assert!(world.load_bundle(&directory.join("missing.lv2")).is_err());
+    world.load_path(directory.join("missing").as_os_str());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_model() {
+    let directory = write_bundle("lv2_host_model");
+    let bundle = directory.join("amp.lv2");
+
+    // A UI, declared in the manifest and described in a separate file.
+    let mut manifest = std::fs::read_to_string(bundle.join("manifest.ttl")).unwrap();
+    manifest.push_str(
+        "\n<urn:lv2_host:test:amp> <http://lv2plug.in/ns/extensions/ui#ui> <urn:lv2_host:test:amp#ui> .\n\
+         <urn:lv2_host:test:amp#ui> a <http://lv2plug.in/ns/extensions/ui#X11UI> ;\n\
+         \t<http://lv2plug.in/ns/extensions/ui#binary> <amp_ui.so> ;\n\
+         \t<http://www.w3.org/2000/01/rdf-schema#seeAlso> <ui.ttl> .\n",
+    );
+    std::fs::write(bundle.join("manifest.ttl"), manifest).unwrap();
+    std::fs::write(
+        bundle.join("ui.ttl"),
+        "<urn:lv2_host:test:amp#ui> <http://lv2plug.in/ns/lv2core#requiredFeature> <http://lv2plug.in/ns/extensions/ui#idleInterface> .\n",
+    )
+    .unwrap();
+
+    // A preset in a bundle without plugins.
+    lv2_presets::PresetBuilder::new("urn:lv2_host:test:amp#loud", "urn:lv2_host:test:amp")
+        .label("Loud")
+        .port_value("gain", 2.0)
+        .build()
+        .save(&directory.join("presets.lv2"), "loud.ttl")
+        .unwrap();
+
+    let mut world = World::new();
+    world.load_path(directory.as_os_str());
+    let plugin = world.plugin("urn:lv2_host:test:amp").unwrap();
+
+    let ui = &plugin.uis()[0];
+    assert_eq!(plugin.uis().len(), 1);
+    assert_eq!(ui.uri(), "urn:lv2_host:test:amp#ui");
+    assert_eq!(
+        ui.classes(),
+        &["http://lv2plug.in/ns/extensions/ui#X11UI".to_owned()]
+    );
+    assert_eq!(ui.binary_path(), Some(bundle.join("amp_ui.so").as_ref()));
+    assert_eq!(
+        ui.required_features(),
+        &["http://lv2plug.in/ns/extensions/ui#idleInterface".to_owned()]
+    );
+
+    let presets: Vec<&lv2_presets::Preset> = world.presets_for(plugin.uri()).collect();
+    assert_eq!(presets.len(), 1);
+    assert_eq!(presets[0].label(), Some("Loud"));
+    assert_eq!(presets[0].port_value("gain"), Some(2.0));
+    assert_eq!(world.presets_for("urn:lv2_host:test:missing").count(), 0);
+
+    // Everything else can be queried from the model.
+    let subject = lv2_presets::turtle::Term::iri(plugin.uri());
+    let name = world
+        .model()
+        .object(&subject, "http://usefulinc.com/ns/doap#name")
+        .and_then(lv2_presets::turtle::Term::as_literal);
+    assert_eq!(name, Some("Test amplifier"));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_instantiation_errors() {
+    let directory = write_bundle("lv2_host_errors");
+    let mut world = World::new();
+    world.load_bundle(&directory.join("amp.lv2")).unwrap();
+    let plugin = world.plugin("urn:lv2_host:test:amp").unwrap();
+
+    let features = HostFeatures::new();
+    assert_eq!(
+        plugin.check_features(&features).unwrap_err(),
+        HostError::MissingFeature("http://lv2plug.in/ns/ext/urid#map".to_owned())
+    );
+
+    // The bundle doesn't contain the library.
+    let features = HostFeatures::new().with_urid_map();
+    assert!(plugin.check_features(&features).is_ok());
+    match unsafe { plugin.instantiate(44100.0, &features) } {
+        Err(HostError::Library(_)) => (),
+        _ => panic!("Instantiation without a library succeeded"),
+    }
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_instance() {
+    let features = HostFeatures::new().with_urid_map();
+    let mapper = features.mapper().unwrap();
+    let urid = mapper.map_type::<Amp>().unwrap();
+    assert_eq!(mapper.unmap(urid), Some(Amp::uri()));
+
+    let descriptor = unsafe { find_descriptor(lv2_descriptor, "urn:lv2_host:test:amp") }.unwrap();
+    assert!(unsafe { find_descriptor(lv2_descriptor, "urn:lv2_host:test:missing") }.is_none());
+
+    let mut instance =
+        unsafe { Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features) }
+            .unwrap();
+    assert_eq!(instance.uri(), "urn:lv2_host:test:amp");
+    assert!(instance
+        .extension_data(Uri::from_bytes_with_nul(b"urn:lv2_host:test:missing\0").unwrap())
+        .is_none());
+    let state = instance
+        .extension::<lv2_sys::LV2_State_Interface>()
+        .unwrap();
+    assert!(state.save.is_some() && state.restore.is_some());
+    assert!(instance
+        .extension::<lv2_sys::LV2_Worker_Interface>()
+        .is_none());
+
+    let mut gain: f32 = 2.0;
+    let mut input: Vec<f32> = vec![0.25; 8];
+    let mut output: Vec<f32> = vec![0.0; 8];
+    let mut output_f64: Vec<f64> = vec![0.0; 8];
+    unsafe {
+        instance.connect_port(0, &mut gain as *mut f32 as *mut c_void);
+        instance.connect_port(1, input.as_mut_ptr() as *mut c_void);
+        instance.connect_port(2, output.as_mut_ptr() as *mut c_void);
+        instance.connect_port(3, output_f64.as_mut_ptr() as *mut c_void);
+    }
+
+    instance.activate();
+    assert!(instance.is_active());
+    unsafe { instance.run(8) };
+    assert_eq!(output, vec![0.5; 8]);
+    assert_eq!(output_f64, vec![0.5; 8]);
+
+    instance.deactivate();
+    assert!(!instance.is_active());
+}
+
+#[test]
+fn test_instance_name() {
+    use lv2_core::feature::{Feature, InstanceName, ThreadingClass};
+    use std::ffi::{c_char, CStr};
+
+    // Without a URID map, the name is only passed as the raw feature.
+    let features = HostFeatures::new()
+        .with_instance_name("Amp 2\0ignored")
+        .with_instance_name("Amp 3");
+    assert!(features.supports("urn:lv2_core:instanceName"));
+    assert!(!features.supports("http://lv2plug.in/ns/ext/options#options"));
+    let raw = features.raw_features();
+    assert_eq!(raw.len(), 1);
+    let name =
+        unsafe { InstanceName::from_feature_ptr(raw[0].data, ThreadingClass::Instantiation) }
+            .unwrap();
+    assert_eq!(name.as_str(), Some("Amp 2"));
+
+    let features = HostFeatures::new()
+        .with_urid_map()
+        .with_instance_name("Amp 2");
+    let mapper = features.mapper().unwrap();
+    let window_title = mapper
+        .map_str("http://lv2plug.in/ns/extensions/ui#windowTitle")
+        .unwrap();
+    let string = mapper.map_type::<lv2_atom::string::String>().unwrap();
+    let raw = features.raw_features();
+    assert_eq!(raw.len(), 4);
+    assert_eq!(
+        unsafe { CStr::from_ptr(raw[2].URI) }.to_bytes(),
+        b"http://lv2plug.in/ns/ext/options#options"
+    );
+    let options = raw[2].data as *const lv2_sys::LV2_Options_Option;
+    let option = unsafe { &*options };
+    assert_eq!(option.key, window_title.get());
+    assert_eq!(option.type_, string.get());
+    assert_eq!(option.size, 6);
+    assert_eq!(
+        unsafe { CStr::from_ptr(option.value as *const c_char) }.to_bytes(),
+        b"Amp 2"
+    );
+    assert_eq!(unsafe { &*options.add(1) }.key, 0);
+    assert!(unsafe { &*options.add(1) }.value.is_null());
+}
+
+#[test]
+fn test_state() {
+    let features = HostFeatures::new().with_urid_map();
+    let float = features.mapper().unwrap().map_type::<Float>().unwrap();
+    let descriptor = unsafe { find_descriptor(lv2_descriptor, "urn:lv2_host:test:amp") }.unwrap();
+
+    let mut gain: f32 = 0.5;
+    let mut input: Vec<f32> = vec![1.0; 4];
+    let mut output: Vec<f32> = vec![0.0; 4];
+    let mut storage = Storage::default();
+    {
+        let mut instance = unsafe {
+            Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features)
+        }
+        .unwrap();
+        unsafe {
+            instance.connect_port(0, &mut gain as *mut f32 as *mut c_void);
+            instance.connect_port(1, input.as_mut_ptr() as *mut c_void);
+            instance.connect_port(2, output.as_mut_ptr() as *mut c_void);
+        }
+        instance.activate();
+        unsafe { instance.run(4) };
+        instance.save_state(&mut storage, &features).unwrap();
+    }
+
+    let (type_, value) = storage.retrieve(URID::new(COEF_KEY).unwrap()).unwrap();
+    assert_eq!(type_, float.into_general());
+    assert_eq!(value, &0.5f32.to_ne_bytes()[..]);
+
+    let mut instance =
+        unsafe { Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features) }
+            .unwrap();
+    instance.restore_state(&storage, &features).unwrap();
+    let mut saved = Storage::default();
+    instance.save_state(&mut saved, &features).unwrap();
+    assert_eq!(
+        saved.retrieve(URID::new(COEF_KEY).unwrap()),
+        Some((float.into_general(), &0.5f32.to_ne_bytes()[..]))
+    );
+
+    // Missing properties are reported by the plugin.
+    assert_eq!(
+        instance.restore_state(&Storage::default(), &features),
+        Err(HostError::State(StateErr::NoProperty))
+    );
+}
+
+#[test]
+fn test_worker() {
+    let features = HostFeatures::new().with_worker(256);
+    assert!(features.supports("http://lv2plug.in/ns/ext/worker#schedule"));
+    let descriptor =
+        unsafe { find_descriptor(lv2_descriptor, "urn:lv2_host:test:doubler") }.unwrap();
+
+    let mut total: f32 = 0.0;
+    let mut end_runs: f32 = 0.0;
+    {
+        let mut instance = unsafe {
+            Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features)
+        }
+        .unwrap();
+        assert!(instance.has_worker());
+        unsafe {
+            instance.connect_port(0, &mut total as *mut f32 as *mut c_void);
+            instance.connect_port(1, &mut end_runs as *mut f32 as *mut c_void);
+        }
+        instance.activate();
+        unsafe { instance.run(8) };
+        unsafe { instance.run(4) };
+
+        // The work is done in another thread, which is why the responses take a while.
+        let mut cycles = 0;
+        loop {
+            unsafe { instance.run(0) };
+            cycles += 1;
+            if total >= 24.0 {
+                break;
+            }
+            assert!(cycles < 10000, "The worker didn't respond");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(total, 24.0);
+        // The plugin writes the number of `end_run` calls before the current one.
+        assert_eq!(end_runs, (2 + cycles - 1) as f32);
+
+        // The worker can't be shared by instances.
+        match unsafe {
+            Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features)
+        } {
+            Err(HostError::WorkerInUse) => (),
+            _ => panic!("The worker was used by two instances"),
+        }
+    }
+
+    // Once the instance is dropped, the worker is free again.
+    let instance =
+        unsafe { Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features) }
+            .unwrap();
+    assert!(instance.has_worker());
+
+    // Plugins without a worker interface don't get a worker thread.
+    let features = HostFeatures::new().with_urid_map().with_worker(256);
+    let descriptor = unsafe { find_descriptor(lv2_descriptor, "urn:lv2_host:test:amp") }.unwrap();
+    let instance =
+        unsafe { Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features) }
+            .unwrap();
+    assert!(!instance.has_worker());
+}
+
+#[test]
+fn test_shared_urid_map() {
+    let features = HostFeatures::new().with_urid_map();
+    let other = HostFeatures::new()
+        .with_shared_urid_map(features.shared_mapper().unwrap())
+        .with_worker(256);
+    let float = features.mapper().unwrap().map_type::<Float>().unwrap();
+    assert_eq!(other.mapper().unwrap().map_type::<Float>(), Some(float));
+
+    // The instances of both features use the same mapper.
+    let descriptor = unsafe { find_descriptor(lv2_descriptor, "urn:lv2_host:test:amp") }.unwrap();
+    let _instance =
+        unsafe { Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &other) }
+            .unwrap();
+    let amp = features.mapper().unwrap().map_type::<Amp>().unwrap();
+    assert_eq!(amp, 2);
+
+    // The table can be saved and loaded.
+    let mut table: Vec<u8> = Vec::new();
+    save_urid_table(features.mapper().unwrap(), &mut table).unwrap();
+    let table = std::string::String::from_utf8(table).unwrap();
+    assert!(
+        table.starts_with("1 <http://lv2plug.in/ns/ext/atom#Float>\n2 <urn:lv2_host:test:amp>\n")
+    );
+
+    let mapper = load_urid_table(format!("# A saved session\n\n{}", table).as_bytes()).unwrap();
+    assert_eq!(mapper.table(), features.mapper().unwrap().table());
+    let restored = HostFeatures::new().with_shared_urid_map(std::sync::Arc::new(mapper));
+    assert_eq!(restored.mapper().unwrap().map_type::<Amp>(), Some(amp));
+
+    // Malformed tables are rejected.
+    for table in &[
+        "1 urn:a\n",
+        "0 <urn:a>\n",
+        "2 <urn:a>\n",
+        "1 <urn:a>\n1 <urn:b>\n",
+    ] {
+        assert_eq!(
+            load_urid_table(table.as_bytes()).err(),
+            Some(HostError::Io(std::io::ErrorKind::InvalidData))
+        );
+    }
+}
+
+#[test]
+fn test_graph() {
+    let directory = write_bundle("lv2_host_graph");
+    let mut world = World::new();
+    world.load_bundle(&directory.join("amp.lv2")).unwrap();
+    let ports = world.plugin("urn:lv2_host:test:amp").unwrap().ports();
+
+    let features = HostFeatures::new().with_urid_map();
+    let descriptor = unsafe { find_descriptor(lv2_descriptor, "urn:lv2_host:test:amp") }.unwrap();
+    let instance = || {
+        unsafe { Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features) }
+            .unwrap()
+    };
+
+    let mut graph = ProcessGraph::new(4, &features);
+    // The second amplifier is added first, but it's run after the first one.
+    let second = graph.add_node(instance(), ports);
+    let first = graph.add_node(instance(), ports);
+    assert_eq!(graph.control(first, "gain"), Some(1.0));
+    graph.set_control(first, "gain", 0.5).unwrap();
+    graph.set_control(second, "gain", 4.0).unwrap();
+
+    graph.connect(first, "output", second, "input").unwrap();
+    assert!(graph.input_mut(second, "input").is_none());
+    let input: Vec<f32> = (0..10).map(|sample| sample as f32).collect();
+    let output = unsafe { graph.render((first, "input"), (second, "output"), &input) }.unwrap();
+    assert_eq!(
+        output,
+        input
+            .iter()
+            .map(|sample| sample * 2.0)
+            .collect::<Vec<f32>>()
+    );
+    assert!(graph.instance(first).unwrap().is_active());
+
+    // Invalid connections are rejected.
+    assert_eq!(
+        graph.connect(second, "output", first, "input"),
+        Err(GraphError::Cycle)
+    );
+    assert_eq!(
+        graph.connect(first, "output", second, "input"),
+        Err(GraphError::AlreadyConnected)
+    );
+    assert_eq!(
+        graph.connect(first, "output", second, "gain"),
+        Err(GraphError::IncompatiblePorts)
+    );
+    assert_eq!(
+        graph.connect(first, "missing", second, "input"),
+        Err(GraphError::UnknownPort("missing".to_owned()))
+    );
+    assert_eq!(unsafe { graph.process(5) }, Err(GraphError::TooManyFrames));
+
+    // Once the amplifiers are disconnected, the second one amplifies silence.
+    graph.disconnect(second, "input").unwrap();
+    graph
+        .input_mut(second, "input")
+        .unwrap()
+        .copy_from_slice(&[0.0; 4]);
+    unsafe { graph.process(4) }.unwrap();
+    assert_eq!(graph.output(second, "output"), Some(&[0.0; 4][..]));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+/// The datatype of `xsd:float` literals.
+const FLOAT: &str = "http://www.w3.org/2001/XMLSchema#float";
+
+#[test]
+fn test_presets() {
+    let directory = write_bundle("lv2_host_presets");
+    PresetBuilder::new("urn:lv2_host:test:quiet", "urn:lv2_host:test:amp")
+        .label("Quiet")
+        .port_value("gain", 0.25)
+        .state_property("urn:lv2_host:test:coef", Term::typed("0.75", FLOAT))
+        .build()
+        .save(&directory.join("presets.lv2"), "quiet.ttl")
+        .unwrap();
+    let mut world = World::new();
+    world.load_path(directory.as_os_str());
+    let plugin = world.plugin("urn:lv2_host:test:amp").unwrap();
+    let preset = world.presets_for(plugin.uri()).next().unwrap();
+
+    // The amp stores its coefficient with a fixed key, which has to be mapped to the key of the preset. The URIDs of a table have to be contiguous, so the ones below are filled up.
+    let mapper = HashURIDMapper::from_table((1..=COEF_KEY).map(|urid| {
+        let uri = if urid == COEF_KEY {
+            "urn:lv2_host:test:coef".to_owned()
+        } else {
+            format!("urn:lv2_host:test:key{}", urid)
+        };
+        (URID::new(urid).unwrap(), UriBuf::new(uri).unwrap())
+    }))
+    .unwrap();
+    let features = HostFeatures::new().with_shared_urid_map(std::sync::Arc::new(mapper));
+    let descriptor = unsafe { find_descriptor(lv2_descriptor, plugin.uri()) }.unwrap();
+    let instance = || {
+        unsafe { Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features) }
+            .unwrap()
+    };
+
+    let mut graph = ProcessGraph::new(4, &features);
+    let node = graph.add_node(instance(), plugin.ports());
+    graph.apply_preset(node, preset, &features).unwrap();
+    assert_eq!(graph.control(node, "gain"), Some(0.25));
+    let captured = graph
+        .capture_preset(
+            node,
+            PresetBuilder::new("urn:lv2_host:test:captured", plugin.uri()),
+            &features,
+        )
+        .unwrap();
+    assert_eq!(captured.port_value("gain"), Some(0.25));
+    assert_eq!(
+        captured.state_properties().collect::<Vec<_>>(),
+        vec![("urn:lv2_host:test:coef", &Term::typed("0.75", FLOAT))]
+    );
+
+    // Running the amp changes its state, which is captured and survives a round trip through a bundle.
+    unsafe { graph.process(4) }.unwrap();
+    let captured = graph
+        .capture_preset(
+            node,
+            PresetBuilder::new("urn:lv2_host:test:captured", plugin.uri()).label("Captured"),
+            &features,
+        )
+        .unwrap();
+    captured
+        .save(&directory.join("captured.lv2"), "captured.ttl")
+        .unwrap();
+    let mut world = World::new();
+    world.load_bundle(&directory.join("captured.lv2")).unwrap();
+    let reloaded = world.presets_for(plugin.uri()).next().unwrap();
+    assert_eq!(reloaded, &captured);
+
+    let mut graph = ProcessGraph::new(4, &features);
+    let node = graph.add_node(instance(), plugin.ports());
+    graph.apply_preset(node, reloaded, &features).unwrap();
+    let mut storage = Storage::default();
+    graph
+        .instance_mut(node)
+        .unwrap()
+        .save_state(&mut storage, &features)
+        .unwrap();
+    assert_eq!(
+        storage.retrieve(URID::new(COEF_KEY).unwrap()).unwrap().1,
+        &0.25f32.to_ne_bytes()[..]
+    );
+
+    // Presets with ports the node doesn't have are rejected without changing the node.
+    let mismatched = PresetBuilder::new("urn:lv2_host:test:mismatched", plugin.uri())
+        .port_value("gain", 2.0)
+        .port_value("missing", 1.0)
+        .build();
+    assert_eq!(
+        graph.apply_preset(node, &mismatched, &features),
+        Err(HostError::Graph(GraphError::UnknownPort(
+            "missing".to_owned()
+        )))
+    );
+    assert_eq!(graph.control(node, "gain"), Some(0.25));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_atom_log() {
+    let directory = std::env::temp_dir().join("lv2_host_atom_log");
+    let _ = std::fs::remove_dir_all(&directory);
+    BundleDescription::new(&binary_name("lv2_host_test_counter"))
+        .plugin(PluginDescription::new::<Counter>(), "counter.ttl")
+        .write(&directory.join("counter.lv2"))
+        .unwrap();
+    let mut world = World::new();
+    world.load_bundle(&directory.join("counter.lv2")).unwrap();
+    let ports = world.plugin("urn:lv2_host:test:counter").unwrap().ports();
+
+    let features = HostFeatures::new().with_urid_map();
+    let int = features
+        .mapper()
+        .unwrap()
+        .map_uri(Int::uri())
+        .unwrap()
+        .get();
+    let descriptor =
+        unsafe { find_descriptor(lv2_descriptor, "urn:lv2_host:test:counter") }.unwrap();
+    let instance = || {
+        unsafe { Instance::from_descriptor(descriptor, &std::env::temp_dir(), 44100.0, &features) }
+            .unwrap()
+    };
+
+    // Record a session with integer events and a MIDI event, which is only counted.
+    let mut graph = ProcessGraph::new(4, &features);
+    let counter = graph.add_node(instance(), ports);
+    assert_eq!(
+        graph.record_input(counter, "count"),
+        Err(GraphError::IncompatiblePorts)
+    );
+    graph.record_input(counter, "events").unwrap();
+    graph
+        .write_event(counter, "events", 1, int, &5i32.to_ne_bytes())
+        .unwrap();
+    unsafe { graph.process(4) }.unwrap();
+    graph
+        .write_event(counter, "events", 0, int, &(-2i32).to_ne_bytes())
+        .unwrap();
+    graph
+        .write_midi(counter, "events", 1, &[0x90, 60, 100])
+        .unwrap();
+    unsafe { graph.process(2) }.unwrap();
+    unsafe { graph.process(4) }.unwrap();
+    assert_eq!(graph.control(counter, "count"), Some(3.0));
+    assert_eq!(graph.control(counter, "sum"), Some(3.0));
+
+    let log = graph.take_recording(counter, "events").unwrap();
+    assert!(graph.take_recording(counter, "events").is_none());
+    let cycles = log.cycles();
+    assert_eq!(
+        cycles.iter().map(LoggedCycle::frames).collect::<Vec<u32>>(),
+        vec![4, 2, 4]
+    );
+    assert_eq!(
+        cycles[0].events(),
+        &[LoggedEvent::new(1, int, &5i32.to_ne_bytes())]
+    );
+    assert_eq!(cycles[1].events()[1].body(), &[0x90, 60, 100]);
+    assert!(cycles[2].events().is_empty());
+    assert_eq!(log.event_count(), 3);
+
+    // The log survives a round trip through its binary format.
+    let mut data: Vec<u8> = Vec::new();
+    log.write_to(&mut data).unwrap();
+    assert_eq!(AtomLog::read_from(data.as_slice()).unwrap(), log);
+    assert_eq!(
+        AtomLog::read_from(&data[..data.len() - 1]),
+        Err(HostError::Io(std::io::ErrorKind::InvalidData))
+    );
+    assert_eq!(
+        AtomLog::read_from(&b"LV2ATOMS\x02\0\0\0"[..]),
+        Err(HostError::Io(std::io::ErrorKind::InvalidData))
+    );
+
+    // Replaying the log to a new instance leads to the same result.
+    let mut graph = ProcessGraph::new(4, &features);
+    let counter = graph.add_node(instance(), ports);
+    unsafe { graph.replay(counter, "events", &log) }.unwrap();
+    assert_eq!(graph.control(counter, "count"), Some(3.0));
+    assert_eq!(graph.control(counter, "sum"), Some(3.0));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,533 @@
+//! Extension for LV2 plugins to draw small images in the mixer strip of the host.
+//!
+//! The inline display extension was introduced by Harrison Consoles and Ardour and is also supported by other hosts, like the MOD devices. A plugin that implements the [`InlineDisplay`](trait.InlineDisplay.html) trait renders an image, like a waveform or a level meter, which the host shows next to the plugin's name. The image is rendered into an [`Image`](struct.Image.html), a framebuffer of premultiplied [`Argb`](struct.Argb.html) pixels, which is stored in the plugin's [`Surface`](struct.Surface.html).
+//!
+//! The host renders the image in a non-realtime thread, usually its GUI thread, which may run at the same time as the plugin's `run` method. This is why [`render`](trait.InlineDisplay.html#tymethod.render) only gets a shared reference to the plugin, which has to share the displayed data with the audio thread, for example with atomic types. The plugin asks the host to render a new image with the [`QueueDraw`](struct.QueueDraw.html) feature, which may be used in `run`.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_inline_display::*;
+//! use std::sync::atomic::{AtomicU32, Ordering};
+//! use urid::*;
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     input: InputPort<Audio>,
+//!     output: OutputPort<Audio>,
+//! }
+//!
+//! #[derive(FeatureCollection)]
+//! pub struct AudioFeatures<'a> {
+//!     queue_draw: Option<QueueDraw<'a>>,
+//! }
+//!
+//! #[uri("urn:lv2_inline_display:meter")]
+//! struct Meter {
+//!     // The peak of the last block, stored as the bits of a `f32`.
+//!     peak: AtomicU32,
+//!     surface: Surface,
+//! }
+//!
+//! #[extensions(InlineDisplayDescriptor)]
+//! impl Plugin for Meter {
+//!     type Ports = Ports;
+//!     type InitFeatures = ();
+//!     type AudioFeatures = AudioFeatures<'static>;
+//!
+//!     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+//!         Some(Self {
+//!             peak: AtomicU32::new(0),
+//!             surface: Surface::default(),
+//!         })
+//!     }
+//!
+//!     fn run(&mut self, ports: &mut Ports, context: &mut RunContext<AudioFeatures<'static>>) {
+//!         ports.output.copy_from_slice(&ports.input);
+//!         let peak = ports.input.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+//!         self.peak.store(peak.to_bits(), Ordering::Relaxed);
+//!         if let Some(queue_draw) = context.features().queue_draw.as_ref() {
+//!             queue_draw.queue_draw();
+//!         }
+//!     }
+//! }
+//!
+//! impl InlineDisplay for Meter {
+//!     fn surface(&self) -> &Surface {
+//!         &self.surface
+//!     }
+//!
+//!     fn render(&self, image: &mut Image, max_width: u32, max_height: u32) -> bool {
+//!         let peak = f32::from_bits(self.peak.load(Ordering::Relaxed)).min(1.0);
+//!         image.resize(max_width, max_height.min(16));
+//!         image.fill(Argb::BLACK);
+//!         let width = (peak * image.width() as f32) as u32;
+//!         image.fill_rect(0, 0, width, image.height(), Argb::from_rgba(0, 200, 0, 255));
+//!         true
+//!     }
+//! }
+//! ```
+extern crate lv2_core as core;
+
+use core::extension::{ExtensionDescriptor, ExtensionInterface};
+use core::feature::*;
+use core::plugin::Plugin;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+use urid::*;
+
+/// Raw types of the inline display extension.
+///
+/// The extension isn't part of the official LV2 specifications and therefore, its header isn't included in `lv2-sys`. These types mirror the header that is distributed with Ardour.
+pub mod sys {
+    #![allow(non_upper_case_globals, non_camel_case_types)]
+
+    use std::os::raw::{c_int, c_uchar, c_void};
+
+    pub const LV2_INLINEDISPLAY__interface: &[u8] =
+        b"http://harrisonconsoles.com/lv2/inlinedisplay#interface\0";
+    pub const LV2_INLINEDISPLAY__queue_draw: &[u8] =
+        b"http://harrisonconsoles.com/lv2/inlinedisplay#queue_draw\0";
+    pub const LV2_INLINEDISPLAY__in_gui: &[u8] =
+        b"http://harrisonconsoles.com/lv2/inlinedisplay#in_gui\0";
+
+    /// An image in the ARGB32 format of Cairo, with premultiplied alpha and native byte order.
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
+    pub struct LV2_Inline_Display_Image_Surface {
+        pub data: *mut c_uchar,
+        pub width: c_int,
+        pub height: c_int,
+        /// The length of a row in bytes.
+        pub stride: c_int,
+    }
+
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
+    pub struct LV2_Inline_Display_Interface {
+        pub render: Option<
+            unsafe extern "C" fn(
+                instance: lv2_sys::LV2_Handle,
+                w: u32,
+                h: u32,
+            ) -> *mut LV2_Inline_Display_Image_Surface,
+        >,
+    }
+
+    pub type LV2_Inline_Display_Handle = *mut c_void;
+
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
+    pub struct LV2_Inline_Display {
+        pub handle: LV2_Inline_Display_Handle,
+        pub queue_draw: Option<unsafe extern "C" fn(handle: LV2_Inline_Display_Handle)>,
+    }
+}
+
+/// A color with premultiplied alpha, as it's stored in an [`Image`](struct.Image.html).
+///
+/// The alpha value is stored in the most significant byte, followed by red, green and blue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct Argb(pub u32);
+
+impl Argb {
+    pub const TRANSPARENT: Self = Self(0);
+    pub const BLACK: Self = Self(0xff00_0000);
+    pub const WHITE: Self = Self(0xffff_ffff);
+
+    /// Create a color from its components with straight alpha, which are premultiplied.
+    pub fn from_rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        let premultiply = |component: u8| (component as u32 * alpha as u32 + 127) / 255;
+        Self(
+            (alpha as u32) << 24
+                | premultiply(red) << 16
+                | premultiply(green) << 8
+                | premultiply(blue),
+        )
+    }
+
+    /// The alpha component.
+    pub fn alpha(self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+}
+
+/// A framebuffer for the inline display.
+///
+/// The image contains one [`Argb`](struct.Argb.html) value per pixel, row by row from the top left corner. All drawing methods clip to the size of the image.
+pub struct Image {
+    pixels: Vec<Argb>,
+    width: u32,
+    height: u32,
+    raw: sys::LV2_Inline_Display_Image_Surface,
+}
+
+// The raw surface only points to the pixels of the image.
+unsafe impl Send for Image {}
+
+impl Default for Image {
+    fn default() -> Self {
+        Self {
+            pixels: Vec::new(),
+            width: 0,
+            height: 0,
+            raw: sys::LV2_Inline_Display_Image_Surface {
+                data: std::ptr::null_mut(),
+                width: 0,
+                height: 0,
+                stride: 0,
+            },
+        }
+    }
+}
+
+impl Image {
+    /// The width of the image in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the image in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Change the size of the image.
+    ///
+    /// If the size changes, all pixels are transparent afterwards. Otherwise, the image is unchanged.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width != self.width || height != self.height {
+            self.pixels.clear();
+            self.pixels
+                .resize(width as usize * height as usize, Argb::TRANSPARENT);
+            self.width = width;
+            self.height = height;
+        }
+    }
+
+    /// All pixels of the image.
+    pub fn pixels(&self) -> &[Argb] {
+        self.pixels.as_ref()
+    }
+
+    /// All pixels of the image, mutably.
+    pub fn pixels_mut(&mut self) -> &mut [Argb] {
+        self.pixels.as_mut()
+    }
+
+    /// The pixels of a row, or `None` if the row is outside of the image.
+    pub fn row_mut(&mut self, y: u32) -> Option<&mut [Argb]> {
+        if y >= self.height {
+            return None;
+        }
+        let start = y as usize * self.width as usize;
+        Some(&mut self.pixels[start..start + self.width as usize])
+    }
+
+    /// The color of a pixel, or `None` if the pixel is outside of the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Argb> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.pixels[y as usize * self.width as usize + x as usize])
+    }
+
+    /// Set the color of a pixel, if it's inside of the image.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Argb) {
+        if let Some(pixel) = self.row_mut(y).and_then(|row| row.get_mut(x as usize)) {
+            *pixel = color;
+        }
+    }
+
+    /// Set all pixels to the same color.
+    pub fn fill(&mut self, color: Argb) {
+        for pixel in self.pixels.iter_mut() {
+            *pixel = color;
+        }
+    }
+
+    /// Set the pixels of a rectangle to the same color.
+    pub fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Argb) {
+        let x_end = x.saturating_add(width).min(self.width) as usize;
+        let y_end = y.saturating_add(height).min(self.height);
+        for row in y..y_end {
+            if let Some(row) = self.row_mut(row) {
+                if let Some(pixels) = row.get_mut(x as usize..x_end) {
+                    for pixel in pixels {
+                        *pixel = color;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Update the raw surface and return a pointer to it.
+    fn as_raw(&mut self) -> *mut sys::LV2_Inline_Display_Image_Surface {
+        self.raw = sys::LV2_Inline_Display_Image_Surface {
+            data: self.pixels.as_mut_ptr() as *mut u8,
+            width: self.width as i32,
+            height: self.height as i32,
+            stride: self.width as i32 * 4,
+        };
+        &mut self.raw
+    }
+}
+
+/// The storage of the rendered image, which is owned by the plugin.
+///
+/// The host reads the image after `render` has returned, which is why it can't be owned by the render call. The surface has to stay in the plugin as long as it lives.
+#[derive(Default)]
+pub struct Surface {
+    image: Mutex<Image>,
+}
+
+/// A plugin extension to draw an image in the mixer strip of the host.
+pub trait InlineDisplay: Plugin {
+    /// The surface that stores the rendered image.
+    fn surface(&self) -> &Surface;
+
+    /// Render the image.
+    ///
+    /// The image still contains the last rendered image. The plugin has to [`resize`](struct.Image.html#method.resize) it to a size that is not bigger than `max_width` and `max_height`, which is the space the host has, and draw into it. If nothing should be displayed, the plugin returns `false`.
+    ///
+    /// This method is called from a non-realtime thread, possibly while the plugin runs.
+    fn render(&self, image: &mut Image, max_width: u32, max_height: u32) -> bool;
+}
+
+unsafe impl ExtensionInterface for sys::LV2_Inline_Display_Interface {
+    const URI: &'static [u8] = sys::LV2_INLINEDISPLAY__interface;
+}
+
+/// Raw wrapper of the [`InlineDisplay`](trait.InlineDisplay.html) extension.
+pub struct InlineDisplayDescriptor<P: InlineDisplay> {
+    plugin: PhantomData<P>,
+}
+
+unsafe impl<P: InlineDisplay> UriBound for InlineDisplayDescriptor<P> {
+    const URI: &'static [u8] = sys::LV2_INLINEDISPLAY__interface;
+}
+
+impl<P: InlineDisplay> InlineDisplayDescriptor<P> {
+    /// Render the image and return the raw surface, or null if nothing should be displayed.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it is an interface for hosts written in C and since it dereferences the plugin handle.
+    pub unsafe extern "C" fn extern_render(
+        handle: lv2_sys::LV2_Handle,
+        max_width: u32,
+        max_height: u32,
+    ) -> *mut sys::LV2_Inline_Display_Image_Surface {
+        // The plugin may run at the same time, which is why only a shared reference is created. The plugin is the first field of the instance.
+        let plugin = if let Some(plugin) = (handle as *const P).as_ref() {
+            plugin
+        } else {
+            return std::ptr::null_mut();
+        };
+        let mut image = if let Ok(image) = plugin.surface().image.lock() {
+            image
+        } else {
+            return std::ptr::null_mut();
+        };
+
+        if !plugin.render(&mut image, max_width, max_height)
+            || image.width() == 0
+            || image.height() == 0
+            || image.width() > max_width
+            || image.height() > max_height
+        {
+            return std::ptr::null_mut();
+        }
+        // The pointer stays valid after the lock is released, since the image is only changed by the next call.
+        image.as_raw()
+    }
+}
+
+impl<P: InlineDisplay> ExtensionDescriptor for InlineDisplayDescriptor<P> {
+    type ExtensionInterface = sys::LV2_Inline_Display_Interface;
+
+    const INTERFACE: &'static sys::LV2_Inline_Display_Interface =
+        &sys::LV2_Inline_Display_Interface {
+            render: Some(Self::extern_render),
+        };
+}
+
+/// Host feature to request a new image.
+///
+/// This feature may be used in all threading classes, including the audio class. The host renders the image later, in its own thread.
+#[repr(transparent)]
+pub struct QueueDraw<'a> {
+    internal: &'a sys::LV2_Inline_Display,
+}
+
+unsafe impl<'a> UriBound for QueueDraw<'a> {
+    const URI: &'static [u8] = sys::LV2_INLINEDISPLAY__queue_draw;
+}
+
+unsafe impl<'a> Feature for QueueDraw<'a> {
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        (feature as *const sys::LV2_Inline_Display)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+impl<'a> QueueDraw<'a> {
+    /// Ask the host to call the plugin's `render` method.
+    pub fn queue_draw(&self) {
+        if let Some(queue_draw) = self.internal.queue_draw {
+            unsafe { (queue_draw)(self.internal.handle) };
+        }
+    }
+}
+
+/// The feature that a plugin declares if its inline display should also be shown in the plugin's generic GUI.
+pub struct InGui;
+
+unsafe impl UriBound for InGui {
+    const URI: &'static [u8] = sys::LV2_INLINEDISPLAY__in_gui;
+}
+
+/// Prelude of `lv2_inline_display` for wildcard usage.
+pub mod prelude {
+    pub use crate::{Argb, Image, InlineDisplay, InlineDisplayDescriptor, QueueDraw, Surface};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use core::prelude::*;
+
+    #[test]
+    fn test_argb() {
+        assert_eq!(Argb::from_rgba(255, 255, 255, 255), Argb::WHITE);
+        assert_eq!(Argb::from_rgba(0, 0, 0, 255), Argb::BLACK);
+        assert_eq!(Argb::from_rgba(255, 0, 128, 0), Argb::TRANSPARENT);
+        assert_eq!(Argb::from_rgba(255, 0, 128, 128), Argb(0x8080_0040));
+        assert_eq!(Argb::from_rgba(255, 0, 128, 128).alpha(), 128);
+    }
+
+    #[test]
+    fn test_image() {
+        let mut image = Image::default();
+        image.resize(4, 3);
+        assert_eq!(image.pixels().len(), 12);
+        assert!(image
+            .pixels()
+            .iter()
+            .all(|pixel| *pixel == Argb::TRANSPARENT));
+
+        image.fill(Argb::BLACK);
+        image.set_pixel(3, 2, Argb::WHITE);
+        image.set_pixel(4, 2, Argb::WHITE);
+        image.fill_rect(1, 1, 10, 1, Argb(1));
+        assert_eq!(image.pixel(3, 2), Some(Argb::WHITE));
+        assert_eq!(image.pixel(4, 2), None);
+        assert_eq!(
+            image.row_mut(1).unwrap(),
+            &[Argb::BLACK, Argb(1), Argb(1), Argb(1)]
+        );
+        assert!(image.row_mut(3).is_none());
+
+        // Resizing to the same size keeps the image.
+        image.resize(4, 3);
+        assert_eq!(image.pixel(0, 0), Some(Argb::BLACK));
+        image.resize(2, 2);
+        assert_eq!(image.pixel(0, 0), Some(Argb::TRANSPARENT));
+    }
+
+    #[uri("urn:lv2_inline_display:test")]
+    struct Display {
+        level: u32,
+        surface: Surface,
+    }
+
+    impl Plugin for Display {
+        type Ports = ();
+        type InitFeatures = ();
+        type AudioFeatures = ();
+
+        fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+            Some(Self {
+                level: 0,
+                surface: Surface::default(),
+            })
+        }
+
+        fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+    }
+
+    impl InlineDisplay for Display {
+        fn surface(&self) -> &Surface {
+            &self.surface
+        }
+
+        fn render(&self, image: &mut Image, max_width: u32, _: u32) -> bool {
+            if self.level == 0 {
+                return false;
+            }
+            image.resize(max_width, self.level);
+            image.fill(Argb::WHITE);
+            true
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut plugin = Display {
+            level: 0,
+            surface: Surface::default(),
+        };
+        let render = InlineDisplayDescriptor::<Display>::INTERFACE
+            .render
+            .unwrap();
+        let handle = &mut plugin as *mut Display as lv2_sys::LV2_Handle;
+
+        unsafe {
+            assert!(render(handle, 8, 8).is_null());
+            assert!(render(std::ptr::null_mut(), 8, 8).is_null());
+
+            plugin.level = 4;
+            let surface = render(handle, 8, 8).as_ref().unwrap();
+            assert_eq!(surface.width, 8);
+            assert_eq!(surface.height, 4);
+            assert_eq!(surface.stride, 32);
+            let pixels = std::slice::from_raw_parts(surface.data as *const u32, 32);
+            assert!(pixels.iter().all(|pixel| *pixel == 0xffff_ffff));
+
+            // The image is bigger than the available space.
+            plugin.level = 16;
+            assert!(render(handle, 8, 8).is_null());
+        }
+    }
+
+    unsafe extern "C" fn queue_draw(handle: sys::LV2_Inline_Display_Handle) {
+        *(handle as *mut u32) += 1;
+    }
+
+    #[test]
+    fn test_queue_draw() {
+        let mut count: u32 = 0;
+        let raw = sys::LV2_Inline_Display {
+            handle: &mut count as *mut u32 as *mut c_void,
+            queue_draw: Some(queue_draw),
+        };
+        let feature = unsafe {
+            QueueDraw::from_feature_ptr(
+                &raw as *const sys::LV2_Inline_Display as *const c_void,
+                ThreadingClass::Audio,
+            )
+        }
+        .unwrap();
+        feature.queue_draw();
+        feature.queue_draw();
+        assert_eq!(count, 2);
+    }
+}
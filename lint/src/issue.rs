@@ -0,0 +1,181 @@
+use host::HostError;
+use std::fmt;
+
+/// A mismatch or mistake found in a bundle.
+///
+/// Most issues are errors, since they lead to hosts refusing to load the plugin or even to undefined behaviour. Some issues are only warnings, which point to descriptions that are valid, but probably not what the author intended.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Issue {
+    /// The bundle couldn't be loaded.
+    Bundle(HostError),
+    /// The shared library of the plugin couldn't be loaded.
+    Library { plugin: String, error: HostError },
+    /// The plugin is described, but the library doesn't contain its descriptor.
+    MissingDescriptor { plugin: String },
+    /// The library contains a descriptor of a plugin that isn't described.
+    UndeclaredPlugin { plugin: String },
+    /// No port has the given index, although ports with greater indices exist.
+    MissingPortIndex { plugin: String, index: u32 },
+    /// More than one port has the given index.
+    DuplicatePortIndex { plugin: String, index: u32 },
+    /// More than one port has the given symbol.
+    DuplicateSymbol { plugin: String, symbol: String },
+    /// The port is neither declared as an input nor as an output.
+    MissingDirection { plugin: String, symbol: String },
+    /// The class of the port isn't known to rust-lv2.
+    UnknownPortKind { plugin: String, symbol: String },
+    /// The default value of the port isn't between its minimum and its maximum.
+    InvalidRange { plugin: String, symbol: String },
+    /// The number of described ports differs from the number of ports in the code.
+    PortCount {
+        plugin: String,
+        described: usize,
+        compiled: usize,
+    },
+    /// The described port with the given index differs from the port in the code.
+    PortMismatch {
+        plugin: String,
+        index: u32,
+        described: String,
+        compiled: String,
+    },
+    /// The plugin requires a feature that isn't described as required.
+    UndeclaredFeature { plugin: String, feature: String },
+    /// A feature is described as required, but the plugin doesn't require it.
+    UnusedFeature { plugin: String, feature: String },
+    /// An extension is described, but the plugin doesn't provide it.
+    MissingExtension { plugin: String, extension: String },
+    /// The plugin provides an extension that isn't described.
+    UndeclaredExtension { plugin: String, extension: String },
+    /// The plugin failed to instantiate itself with the described features.
+    InstantiationFailed { plugin: String },
+    /// A required feature can't be provided by the linter, which is why the plugin couldn't be instantiated.
+    UntestedFeature { plugin: String, feature: String },
+}
+
+impl Issue {
+    /// The URI of the plugin the issue belongs to, if it belongs to one.
+    pub fn plugin(&self) -> Option<&str> {
+        match self {
+            Issue::Bundle(_) => None,
+            Issue::Library { plugin, .. }
+            | Issue::MissingDescriptor { plugin }
+            | Issue::UndeclaredPlugin { plugin }
+            | Issue::MissingPortIndex { plugin, .. }
+            | Issue::DuplicatePortIndex { plugin, .. }
+            | Issue::DuplicateSymbol { plugin, .. }
+            | Issue::MissingDirection { plugin, .. }
+            | Issue::UnknownPortKind { plugin, .. }
+            | Issue::InvalidRange { plugin, .. }
+            | Issue::PortCount { plugin, .. }
+            | Issue::PortMismatch { plugin, .. }
+            | Issue::UndeclaredFeature { plugin, .. }
+            | Issue::UnusedFeature { plugin, .. }
+            | Issue::MissingExtension { plugin, .. }
+            | Issue::UndeclaredExtension { plugin, .. }
+            | Issue::InstantiationFailed { plugin }
+            | Issue::UntestedFeature { plugin, .. } => Some(plugin.as_ref()),
+        }
+    }
+
+    /// Check whether the issue is an error.
+    ///
+    /// Issues that aren't errors are warnings.
+    pub fn is_error(&self) -> bool {
+        !matches!(
+            self,
+            Issue::UndeclaredPlugin { .. }
+                | Issue::UnknownPortKind { .. }
+                | Issue::UnusedFeature { .. }
+                | Issue::UndeclaredExtension { .. }
+                | Issue::UntestedFeature { .. }
+        )
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::Bundle(error) => write!(f, "The bundle couldn't be loaded: {:?}", error),
+            Issue::Library { error, .. } => {
+                write!(f, "The library couldn't be loaded: {:?}", error)
+            }
+            Issue::MissingDescriptor { .. } => {
+                write!(f, "The library doesn't contain a descriptor of the plugin")
+            }
+            Issue::UndeclaredPlugin { .. } => {
+                write!(f, "The library contains the plugin, but it isn't described")
+            }
+            Issue::MissingPortIndex { index, .. } => {
+                write!(f, "No port has the index {}", index)
+            }
+            Issue::DuplicatePortIndex { index, .. } => {
+                write!(f, "More than one port has the index {}", index)
+            }
+            Issue::DuplicateSymbol { symbol, .. } => {
+                write!(f, "More than one port has the symbol \"{}\"", symbol)
+            }
+            Issue::MissingDirection { symbol, .. } => write!(
+                f,
+                "The port \"{}\" is neither an input nor an output",
+                symbol
+            ),
+            Issue::UnknownPortKind { symbol, .. } => {
+                write!(f, "The port \"{}\" has an unknown class", symbol)
+            }
+            Issue::InvalidRange { symbol, .. } => write!(
+                f,
+                "The default value of the port \"{}\" isn't between its minimum and maximum",
+                symbol
+            ),
+            Issue::PortCount {
+                described,
+                compiled,
+                ..
+            } => write!(
+                f,
+                "{} ports are described, but the plugin has {}",
+                described, compiled
+            ),
+            Issue::PortMismatch {
+                index,
+                described,
+                compiled,
+                ..
+            } => write!(
+                f,
+                "The port with the index {} is described as {}, but it's {} in the plugin",
+                index, described, compiled
+            ),
+            Issue::UndeclaredFeature { feature, .. } => write!(
+                f,
+                "The plugin requires the feature <{}>, but it isn't described as required",
+                feature
+            ),
+            Issue::UnusedFeature { feature, .. } => write!(
+                f,
+                "The feature <{}> is described as required, but the plugin doesn't require it",
+                feature
+            ),
+            Issue::MissingExtension { extension, .. } => write!(
+                f,
+                "The extension <{}> is described, but the plugin doesn't provide it",
+                extension
+            ),
+            Issue::UndeclaredExtension { extension, .. } => write!(
+                f,
+                "The plugin provides the extension <{}>, but it isn't described",
+                extension
+            ),
+            Issue::InstantiationFailed { .. } => write!(
+                f,
+                "The plugin failed to instantiate itself with the described features"
+            ),
+            Issue::UntestedFeature { feature, .. } => write!(
+                f,
+                "The required feature <{}> can't be provided, the plugin wasn't instantiated",
+                feature
+            ),
+        }
+    }
+}
@@ -0,0 +1,493 @@
+//! Validation of LV2 bundles built with rust-lv2.
+//!
+//! A bundle consists of Turtle files, which describe the plugins, and a shared library, which contains the compiled plugins. Hosts trust the description: If it doesn't match the code, the plugin is rejected, or worse, its ports are connected to the wrong buffers. This crate finds these mismatches, just like `lv2lint` does for plugins written in C.
+//!
+//! The checks are split into three levels:
+//!
+//! * [`lint_entry`](fn.lint_entry.html) checks that the description of a plugin is consistent in itself, for example that the port indices are contiguous and that the port symbols are unique.
+//! * [`lint_library`](fn.lint_library.html) checks the descriptions against the descriptors of a library: Every described plugin has to be in the library, every described extension has to be provided by the plugin's `extension_data` function and the plugin has to instantiate itself with the described features. [`lint_bundle`](fn.lint_bundle.html) runs these checks, as well as the checks of `lint_entry`, for a bundle on disk.
+//! * [`lint_description`](fn.lint_description.html) compares a description with a [`PluginDescription`](../lv2_ttl_gen/struct.PluginDescription.html), which is created from the plugin's Rust types. This is the only way to check the ports, since the C interface of a plugin doesn't contain them. It has to be run by code that is linked with the plugin, like a test of the plugin crate.
+//!
+//! The crate also contains the `lv2-lint` command, which runs `lint_bundle` for the bundles that are passed as arguments, prints all issues and fails if one of them is an error:
+//!
+//! ```sh
+//! cargo run -p lv2-lint -- ~/.lv2/amp.lv2
+//! ```
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_host::World;
+//! use lv2_lint::*;
+//! use lv2_ttl_gen::*;
+//! use urid::*;
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     gain: InputPort<Control>,
+//!     input: InputPort<Audio>,
+//!     output: OutputPort<Audio>,
+//! }
+//!
+//! #[uri("urn:lv2_lint:amp")]
+//! struct Amp;
+//!
+//! impl Plugin for Amp {
+//!     type Ports = Ports;
+//!     type InitFeatures = ();
+//!     type AudioFeatures = ();
+//!
+//!     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+//!         Some(Self)
+//!     }
+//!
+//!     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+//!         let coef = *ports.gain;
+//!         for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+//!             *output = input * coef;
+//!         }
+//!     }
+//! }
+//!
+//! lv2_descriptors!(Amp);
+//!
+//! // The bundle would usually be written by the build tooling of the plugin.
+//! let bundle_path = std::env::temp_dir().join("lv2_lint_example.lv2");
+//! BundleDescription::new(&binary_name("amp"))
+//!     .plugin(PluginDescription::new::<Amp>(), "amp.ttl")
+//!     .write(&bundle_path)
+//!     .unwrap();
+//!
+//! let mut world = World::new();
+//! world.load_bundle(&bundle_path).unwrap();
+//! let entry = world.plugin("urn:lv2_lint:amp").unwrap();
+//!
+//! assert!(lint_entry(entry).is_empty());
+//! assert!(lint_description(&PluginDescription::new::<Amp>(), entry).is_empty());
+//! assert!(unsafe { lint_library(world.plugins(), lv2_descriptor) }.is_empty());
+//! # std::fs::remove_dir_all(&bundle_path).unwrap();
+//! ```
+extern crate lv2_core as core;
+extern crate lv2_host as host;
+extern crate lv2_sys as sys;
+extern crate lv2_ttl_gen as ttl_gen;
+
+mod issue;
+pub use issue::*;
+
+use core::port::{AudioF64, PortDirection};
+use host::{DescriptorFunction, HostError, HostFeatures, Instance, PluginEntry, PortKind, World};
+use libloading::{Library, Symbol};
+use std::ffi::CStr;
+use std::path::{Path, PathBuf};
+use ttl_gen::PluginDescription;
+use urid::{Uri, UriBound};
+
+/// The extensions the plugins are probed for, to find extensions that aren't described.
+const KNOWN_EXTENSIONS: &[&[u8]] = &[
+    sys::LV2_MORPH__interface,
+    sys::LV2_OPTIONS__interface,
+    sys::LV2_STATE__interface,
+    sys::LV2_WORKER__interface,
+];
+
+/// The features without data, which can be provided to plugins when they are instantiated.
+const FLAG_FEATURES: &[&[u8]] = &[
+    sys::LV2_BUF_SIZE__boundedBlockLength,
+    sys::LV2_BUF_SIZE__fixedBlockLength,
+    sys::LV2_BUF_SIZE__powerOf2BlockLength,
+    sys::LV2_CORE__hardRTCapable,
+    sys::LV2_CORE__inPlaceBroken,
+    sys::LV2_CORE__isLive,
+];
+
+/// The sample rate the plugins are instantiated with.
+const SAMPLE_RATE: f64 = 48000.0;
+
+/// Check a bundle on disk.
+///
+/// The bundle is loaded, every plugin in it is checked with [`lint_entry`](fn.lint_entry.html) and the libraries of the plugins are checked with [`lint_library`](fn.lint_library.html).
+///
+/// # Safety
+///
+/// The libraries of the bundle are loaded, which runs their initialization code, and the plugins are instantiated. The libraries have to be LV2 plugin libraries that follow the specification.
+pub unsafe fn lint_bundle(path: &Path) -> Vec<Issue> {
+    let mut world = World::new();
+    if let Err(error) = world.load_bundle(path) {
+        return vec![Issue::Bundle(error)];
+    }
+
+    let mut issues: Vec<Issue> = world.plugins().iter().flat_map(lint_entry).collect();
+
+    let mut binaries: Vec<PathBuf> = Vec::new();
+    for plugin in world.plugins() {
+        match plugin.binary_path() {
+            Some(binary) => {
+                if !binaries.iter().any(|known| known == binary) {
+                    binaries.push(binary.to_owned());
+                }
+            }
+            None => issues.push(Issue::Library {
+                plugin: plugin.uri().to_owned(),
+                error: HostError::MissingBinary,
+            }),
+        }
+    }
+
+    for binary in binaries {
+        let plugins: Vec<PluginEntry> = world
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.binary_path() == Some(binary.as_ref()))
+            .cloned()
+            .collect();
+        match load_descriptor_function(&binary) {
+            Ok((_library, function)) => issues.extend(lint_library(&plugins, function)),
+            Err(error) => issues.extend(plugins.iter().map(|plugin| Issue::Library {
+                plugin: plugin.uri().to_owned(),
+                error: error.clone(),
+            })),
+        }
+    }
+
+    issues
+}
+
+/// Load a library and its descriptor function.
+///
+/// The function is only valid as long as the library is loaded.
+unsafe fn load_descriptor_function(
+    binary: &Path,
+) -> Result<(Library, DescriptorFunction), HostError> {
+    let library = Library::new(binary).map_err(|error| HostError::Library(error.to_string()))?;
+    let function = {
+        let function: Symbol<DescriptorFunction> = library
+            .get(b"lv2_descriptor\0")
+            .map_err(|error| HostError::Library(error.to_string()))?;
+        *function
+    };
+    Ok((library, function))
+}
+
+/// Check that the description of a plugin is consistent in itself.
+///
+/// The port indices have to be contiguous and unique, the port symbols have to be unique, every port has to have a direction and default values have to be in the range of their port.
+pub fn lint_entry(entry: &PluginEntry) -> Vec<Issue> {
+    let plugin = || entry.uri().to_owned();
+    let mut issues: Vec<Issue> = Vec::new();
+
+    // The ports are ordered by their index.
+    let mut expected_index: u32 = 0;
+    for port in entry.ports() {
+        if port.index() < expected_index {
+            issues.push(Issue::DuplicatePortIndex {
+                plugin: plugin(),
+                index: port.index(),
+            });
+            continue;
+        }
+        for index in expected_index..port.index() {
+            issues.push(Issue::MissingPortIndex {
+                plugin: plugin(),
+                index,
+            });
+        }
+        expected_index = port.index() + 1;
+    }
+
+    for (position, port) in entry.ports().iter().enumerate() {
+        let symbol = || port.symbol().to_owned();
+        if entry.ports()[..position]
+            .iter()
+            .any(|other| other.symbol() == port.symbol())
+        {
+            issues.push(Issue::DuplicateSymbol {
+                plugin: plugin(),
+                symbol: symbol(),
+            });
+        }
+        if port.direction().is_none() {
+            issues.push(Issue::MissingDirection {
+                plugin: plugin(),
+                symbol: symbol(),
+            });
+        }
+        if port.kind() == PortKind::Other {
+            issues.push(Issue::UnknownPortKind {
+                plugin: plugin(),
+                symbol: symbol(),
+            });
+        }
+        if let (Some(minimum), Some(default), Some(maximum)) =
+            (port.minimum(), port.default(), port.maximum())
+        {
+            if !(minimum <= default && default <= maximum) {
+                issues.push(Issue::InvalidRange {
+                    plugin: plugin(),
+                    symbol: symbol(),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Check the descriptions of plugins against the descriptors of a library.
+///
+/// Every plugin has to be in the library and every plugin in the library has to be described. The plugins are then checked with their descriptors: The described extensions have to be provided by `extension_data`, and the plugin has to be instantiated with the described features, but not without one of the required features. Plugins that require features that can't be provided by the linter aren't instantiated.
+///
+/// # Safety
+///
+/// The function has to be a valid descriptor function of a plugin library and the plugins are instantiated. The library has to be loaded for the whole call.
+pub unsafe fn lint_library(plugins: &[PluginEntry], function: DescriptorFunction) -> Vec<Issue> {
+    let mut descriptors: Vec<(&str, &'static sys::LV2_Descriptor)> = Vec::new();
+    let mut index: u32 = 0;
+    while let Some(descriptor) = function(index).as_ref() {
+        if !descriptor.URI.is_null() {
+            if let Ok(uri) = CStr::from_ptr(descriptor.URI).to_str() {
+                descriptors.push((uri, descriptor));
+            }
+        }
+        index += 1;
+    }
+
+    let mut issues: Vec<Issue> = descriptors
+        .iter()
+        .filter(|(uri, _)| !plugins.iter().any(|plugin| plugin.uri() == *uri))
+        .map(|(uri, _)| Issue::UndeclaredPlugin {
+            plugin: (*uri).to_owned(),
+        })
+        .collect();
+
+    for plugin in plugins {
+        match descriptors.iter().find(|(uri, _)| *uri == plugin.uri()) {
+            Some((_, descriptor)) => issues.extend(lint_descriptor(plugin, descriptor)),
+            None => issues.push(Issue::MissingDescriptor {
+                plugin: plugin.uri().to_owned(),
+            }),
+        }
+    }
+
+    issues
+}
+
+/// Check the extensions and features of a plugin with its descriptor.
+unsafe fn lint_descriptor(
+    entry: &PluginEntry,
+    descriptor: &'static sys::LV2_Descriptor,
+) -> Vec<Issue> {
+    let plugin = || entry.uri().to_owned();
+    let mut issues: Vec<Issue> = Vec::new();
+
+    let provides = |extension: &CStr| match descriptor.extension_data {
+        Some(extension_data) => !extension_data(extension.as_ptr()).is_null(),
+        None => false,
+    };
+    for extension in entry.extensions() {
+        let uri = std::ffi::CString::new(extension.as_str()).unwrap_or_default();
+        if !provides(&uri) {
+            issues.push(Issue::MissingExtension {
+                plugin: plugin(),
+                extension: extension.clone(),
+            });
+        }
+    }
+    for extension in KNOWN_EXTENSIONS {
+        let uri = Uri::from_bytes_with_nul(extension).unwrap();
+        let extension = uri.to_str().unwrap();
+        if !entry.extensions().iter().any(|known| known == extension) && provides(uri) {
+            issues.push(Issue::UndeclaredExtension {
+                plugin: plugin(),
+                extension: extension.to_owned(),
+            });
+        }
+    }
+
+    let described: Vec<&str> = entry
+        .required_features()
+        .iter()
+        .chain(entry.optional_features().iter())
+        .map(String::as_str)
+        .collect();
+    let features = lint_features(described.iter().copied());
+    let untested: Vec<&String> = entry
+        .required_features()
+        .iter()
+        .filter(|feature| !features.supports(feature))
+        .collect();
+    if !untested.is_empty() {
+        issues.extend(untested.into_iter().map(|feature| Issue::UntestedFeature {
+            plugin: plugin(),
+            feature: feature.clone(),
+        }));
+        return issues;
+    }
+
+    let instantiates = |features: &HostFeatures| {
+        Instance::from_descriptor(descriptor, entry.bundle_path(), SAMPLE_RATE, features).is_ok()
+    };
+    if !instantiates(&features) {
+        issues.push(Issue::InstantiationFailed { plugin: plugin() });
+        return issues;
+    }
+    for feature in entry.required_features() {
+        let features = lint_features(
+            described
+                .iter()
+                .copied()
+                .filter(|uri| *uri != feature.as_str()),
+        );
+        // Some features are always provided together, like the URID map and unmap.
+        if !features.supports(feature) && instantiates(&features) {
+            issues.push(Issue::UnusedFeature {
+                plugin: plugin(),
+                feature: feature.clone(),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Create the features with the given URIs, as far as the linter can provide them.
+fn lint_features<'a>(uris: impl Iterator<Item = &'a str>) -> HostFeatures {
+    let mut features = HostFeatures::new();
+    for uri in uris {
+        if features.supports(uri) {
+            continue;
+        }
+        if uri.as_bytes() == &sys::LV2_URID__map[..sys::LV2_URID__map.len() - 1]
+            || uri.as_bytes() == &sys::LV2_URID__unmap[..sys::LV2_URID__unmap.len() - 1]
+        {
+            features = features.with_urid_map();
+        } else if let Some(flag) = FLAG_FEATURES
+            .iter()
+            .map(|flag| Uri::from_bytes_with_nul(flag).unwrap())
+            .find(|flag| flag.to_bytes() == uri.as_bytes())
+        {
+            features = features.with_flag(flag);
+        }
+    }
+    features
+}
+
+/// Compare the description of a plugin with the description that was created from its Rust types.
+///
+/// The ports have to match in number and index, including their symbols, classes and directions. The features the plugin requires have to be described as required, and the described extensions have to match the ones the plugin provides.
+pub fn lint_description(compiled: &PluginDescription, entry: &PluginEntry) -> Vec<Issue> {
+    let plugin = || entry.uri().to_owned();
+    let mut issues: Vec<Issue> = Vec::new();
+
+    if compiled.ports().len() != entry.ports().len() {
+        issues.push(Issue::PortCount {
+            plugin: plugin(),
+            described: entry.ports().len(),
+            compiled: compiled.ports().len(),
+        });
+    }
+    for compiled in compiled.ports() {
+        let compiled_port = port_summary(
+            compiled.symbol(),
+            port_kind(compiled.class()),
+            compiled.direction(),
+            compiled.is_optional(),
+        );
+        let described_port = match entry
+            .ports()
+            .iter()
+            .find(|described| described.index() == compiled.index())
+        {
+            Some(described) => port_summary(
+                described.symbol(),
+                described.kind(),
+                described.direction(),
+                described.is_optional(),
+            ),
+            None => "missing".to_owned(),
+        };
+        if described_port != compiled_port {
+            issues.push(Issue::PortMismatch {
+                plugin: plugin(),
+                index: compiled.index(),
+                described: described_port,
+                compiled: compiled_port,
+            });
+        }
+    }
+
+    for feature in compiled.required_features() {
+        if !entry.required_features().contains(feature) {
+            issues.push(Issue::UndeclaredFeature {
+                plugin: plugin(),
+                feature: feature.clone(),
+            });
+        }
+    }
+    for feature in entry.required_features() {
+        if !compiled.required_features().contains(feature) {
+            issues.push(Issue::UnusedFeature {
+                plugin: plugin(),
+                feature: feature.clone(),
+            });
+        }
+    }
+
+    for extension in entry.extensions() {
+        if !compiled.extensions().contains(extension) {
+            issues.push(Issue::MissingExtension {
+                plugin: plugin(),
+                extension: extension.clone(),
+            });
+        }
+    }
+    for extension in compiled.extensions() {
+        if !entry.extensions().contains(extension) {
+            issues.push(Issue::UndeclaredExtension {
+                plugin: plugin(),
+                extension: extension.clone(),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Return the kind of a port with the given class.
+fn port_kind(class: Option<&str>) -> PortKind {
+    let class = match class {
+        Some(class) => class.as_bytes(),
+        None => return PortKind::Other,
+    };
+    let is = |uri: &[u8]| &uri[..uri.len() - 1] == class;
+    if is(sys::LV2_CORE__AudioPort) {
+        PortKind::Audio
+    } else if is(AudioF64::URI) {
+        PortKind::AudioF64
+    } else if is(sys::LV2_CORE__ControlPort) {
+        PortKind::Control
+    } else if is(sys::LV2_CORE__CVPort) {
+        PortKind::CV
+    } else if is(sys::LV2_ATOM__AtomPort) {
+        PortKind::Atom
+    } else {
+        PortKind::Other
+    }
+}
+
+/// Summarize a port for a mismatch message, like `"gain" (Control, input)`.
+fn port_summary(
+    symbol: &str,
+    kind: PortKind,
+    direction: Option<PortDirection>,
+    optional: bool,
+) -> String {
+    let direction = match direction {
+        Some(PortDirection::Input) => "input",
+        Some(PortDirection::Output) => "output",
+        None => "no direction",
+    };
+    let optional = if optional { ", optional" } else { "" };
+    format!("\"{}\" ({:?}, {}{})", symbol, kind, direction, optional)
+}
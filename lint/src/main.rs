@@ -0,0 +1,34 @@
+use lv2_lint::lint_bundle;
+use std::path::Path;
+use std::process::exit;
+
+fn main() {
+    let bundles: Vec<String> = std::env::args().skip(1).collect();
+    if bundles.is_empty() {
+        eprintln!("Usage: lv2-lint BUNDLE...");
+        exit(2);
+    }
+
+    let mut errors: usize = 0;
+    for bundle in bundles.iter() {
+        // Loading the libraries of the bundle runs their initialization code.
+        let issues = unsafe { lint_bundle(Path::new(bundle)) };
+        for issue in issues.iter() {
+            let severity = if issue.is_error() {
+                errors += 1;
+                "error"
+            } else {
+                "warning"
+            };
+            match issue.plugin() {
+                Some(plugin) => println!("{}: <{}>: {}: {}", bundle, plugin, severity, issue),
+                None => println!("{}: {}: {}", bundle, severity, issue),
+            }
+        }
+    }
+
+    if errors > 0 {
+        eprintln!("Found {} error(s)", errors);
+        exit(1);
+    }
+}
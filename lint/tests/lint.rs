@@ -0,0 +1,239 @@
+use lv2_core::prelude::*;
+use lv2_host::*;
+use lv2_lint::*;
+use lv2_state::*;
+use lv2_ttl_gen::*;
+use lv2_urid::*;
+use std::path::PathBuf;
+use urid::*;
+
+#[derive(PortCollection)]
+struct Ports {
+    gain: InputPort<Control>,
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+#[derive(FeatureCollection)]
+struct Features<'a> {
+    _map: LV2Map<'a>,
+}
+
+#[uri("urn:lv2_lint:test:amp")]
+struct Amp;
+
+#[extensions(StateDescriptor)]
+impl Plugin for Amp {
+    type Ports = Ports;
+    type InitFeatures = Features<'static>;
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut Features<'static>) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        let coef = *ports.gain;
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = input * coef;
+        }
+    }
+}
+
+impl State for Amp {
+    type StateFeatures = ();
+
+    fn save(&self, _: StoreHandle, _: ()) -> Result<(), StateErr> {
+        Ok(())
+    }
+
+    fn restore(&mut self, _: RetrieveHandle, _: ()) -> Result<(), StateErr> {
+        Ok(())
+    }
+}
+
+#[uri("urn:lv2_lint:test:mapped")]
+struct Mapped;
+
+impl Plugin for Mapped {
+    type Ports = ();
+    type InitFeatures = Features<'static>;
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut Features<'static>) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+lv2_descriptors![Amp, Mapped];
+
+/// Create a fresh directory for a bundle and return its path.
+fn bundle_path(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&directory);
+    directory
+}
+
+#[test]
+fn test_valid_bundle() {
+    let path = bundle_path("lv2_lint_valid.lv2");
+    BundleDescription::new(&binary_name("lv2_lint_test"))
+        .plugin(PluginDescription::new::<Amp>(), "amp.ttl")
+        .write(&path)
+        .unwrap();
+    let mut world = World::new();
+    world.load_bundle(&path).unwrap();
+    let entry = world.plugin("urn:lv2_lint:test:amp").unwrap();
+
+    assert_eq!(lint_entry(entry), vec![]);
+    assert_eq!(
+        lint_description(&PluginDescription::new::<Amp>(), entry),
+        vec![]
+    );
+    assert_eq!(
+        unsafe { lint_library(world.plugins(), lv2_descriptor) },
+        vec![Issue::UndeclaredPlugin {
+            plugin: "urn:lv2_lint:test:mapped".to_owned()
+        }]
+    );
+
+    // The bundle doesn't contain the library.
+    let issues = unsafe { lint_bundle(&path) };
+    assert_eq!(issues.len(), 1);
+    assert!(matches!(issues[0], Issue::Library { .. }));
+    assert!(issues[0].is_error());
+
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+const BROKEN_MANIFEST: &str = r#"
+@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix urid: <http://lv2plug.in/ns/ext/urid#> .
+
+<urn:lv2_lint:test:amp>
+    a lv2:Plugin ;
+    lv2:requiredFeature urid:map , lv2:isLive ;
+    lv2:extensionData <http://lv2plug.in/ns/ext/worker#interface> ;
+    lv2:port [
+        a lv2:InputPort , lv2:ControlPort ;
+        lv2:index 0 ;
+        lv2:symbol "gain" ;
+        lv2:default 3.0 ;
+        lv2:minimum 0.0 ;
+        lv2:maximum 2.0
+    ] , [
+        a lv2:InputPort , lv2:AudioPort ;
+        lv2:index 2 ;
+        lv2:symbol "input"
+    ] , [
+        a lv2:AudioPort ;
+        lv2:index 3 ;
+        lv2:symbol "gain"
+    ] .
+
+<urn:lv2_lint:test:mapped>
+    a lv2:Plugin .
+
+<urn:lv2_lint:test:missing>
+    a lv2:Plugin .
+"#;
+
+#[test]
+fn test_broken_bundle() {
+    let path = bundle_path("lv2_lint_broken.lv2");
+    std::fs::create_dir_all(&path).unwrap();
+    std::fs::write(path.join("manifest.ttl"), BROKEN_MANIFEST).unwrap();
+    let mut world = World::new();
+    world.load_bundle(&path).unwrap();
+    let entry = world.plugin("urn:lv2_lint:test:amp").unwrap();
+
+    let amp = "urn:lv2_lint:test:amp".to_owned();
+    assert_eq!(
+        lint_entry(entry),
+        vec![
+            Issue::MissingPortIndex {
+                plugin: amp.clone(),
+                index: 1
+            },
+            Issue::InvalidRange {
+                plugin: amp.clone(),
+                symbol: "gain".to_owned()
+            },
+            Issue::DuplicateSymbol {
+                plugin: amp.clone(),
+                symbol: "gain".to_owned()
+            },
+            Issue::MissingDirection {
+                plugin: amp.clone(),
+                symbol: "gain".to_owned()
+            },
+        ]
+    );
+
+    let issues = lint_description(&PluginDescription::new::<Amp>(), entry);
+    assert_eq!(
+        issues,
+        vec![
+            Issue::PortMismatch {
+                plugin: amp.clone(),
+                index: 1,
+                described: "missing".to_owned(),
+                compiled: "\"input\" (Audio, input)".to_owned(),
+            },
+            Issue::PortMismatch {
+                plugin: amp.clone(),
+                index: 2,
+                described: "\"input\" (Audio, input)".to_owned(),
+                compiled: "\"output\" (Audio, output)".to_owned(),
+            },
+            Issue::UnusedFeature {
+                plugin: amp.clone(),
+                feature: "http://lv2plug.in/ns/lv2core#isLive".to_owned(),
+            },
+            Issue::MissingExtension {
+                plugin: amp.clone(),
+                extension: "http://lv2plug.in/ns/ext/worker#interface".to_owned(),
+            },
+            Issue::UndeclaredExtension {
+                plugin: amp.clone(),
+                extension: "http://lv2plug.in/ns/ext/state#interface".to_owned(),
+            },
+        ]
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "The port with the index 1 is described as missing, but it's \"input\" (Audio, input) in the plugin"
+    );
+
+    let issues = unsafe { lint_library(world.plugins(), lv2_descriptor) };
+    for issue in [
+        Issue::MissingExtension {
+            plugin: amp.clone(),
+            extension: "http://lv2plug.in/ns/ext/worker#interface".to_owned(),
+        },
+        Issue::UndeclaredExtension {
+            plugin: amp.clone(),
+            extension: "http://lv2plug.in/ns/ext/state#interface".to_owned(),
+        },
+        Issue::UnusedFeature {
+            plugin: amp,
+            feature: "http://lv2plug.in/ns/lv2core#isLive".to_owned(),
+        },
+        Issue::InstantiationFailed {
+            plugin: "urn:lv2_lint:test:mapped".to_owned(),
+        },
+        Issue::MissingDescriptor {
+            plugin: "urn:lv2_lint:test:missing".to_owned(),
+        },
+    ]
+    .iter()
+    {
+        assert!(issues.contains(issue), "{:?} wasn't found", issue);
+    }
+    assert_eq!(issues.len(), 5);
+    assert!(issues.iter().all(|issue| issue.plugin().is_some()));
+
+    std::fs::remove_dir_all(&path).unwrap();
+}
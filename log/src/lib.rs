@@ -0,0 +1,376 @@
+//! Logging of messages through the host, which is safe in the audio thread.
+//!
+//! Plugins shouldn't print to the standard output: It may not be visible at all, and printing in the audio thread locks and allocates, which may lead to dropouts. Instead, the [log extension](https://lv2plug.in/ns/ext/log/log.html) lets plugins pass their messages to the host, which shows them to the user. Every message has a type: Errors, warnings and notes may only be logged in non-realtime contexts, while traces may be logged anywhere, since hosts have to handle them without blocking.
+//!
+//! This crate takes care of this distinction: The [`Log`](struct.Log.html) feature remembers the threading class it was created for, and the [`plugin_log`](macro.plugin_log.html) macro logs every message as a trace in the "Audio" threading class, which covers `run` as well as the responses of a worker. This way, the same macro can be used everywhere, and no message can accidentally block the audio thread. Messages are formatted into a fixed buffer on the stack and are truncated if they don't fit, which means that logging never allocates.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_log::*;
+//! use lv2_urid::*;
+//! use urid::*;
+//!
+//! #[derive(FeatureCollection)]
+//! struct InitFeatures<'a> {
+//!     map: LV2Map<'a>,
+//!     log: Log<'a>,
+//! }
+//!
+//! #[derive(FeatureCollection)]
+//! struct AudioFeatures<'a> {
+//!     log: Log<'a>,
+//! }
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     input: InputPort<Audio>,
+//! }
+//!
+//! #[uri("urn:lv2_log:clipper")]
+//! struct Clipper {
+//!     urids: LogURIDCollection,
+//! }
+//!
+//! impl Plugin for Clipper {
+//!     type Ports = Ports;
+//!     type InitFeatures = InitFeatures<'static>;
+//!     type AudioFeatures = AudioFeatures<'static>;
+//!
+//!     fn new(_: &PluginInfo, features: &mut InitFeatures<'static>) -> Option<Self> {
+//!         let urids: LogURIDCollection = features.map.populate_collection()?;
+//!         // Logged as a note, since this isn't the audio thread.
+//!         plugin_log!(features.log, &urids, Note, "Clipper created");
+//!         Some(Self { urids })
+//!     }
+//!
+//!     fn run(&mut self, ports: &mut Ports, context: &mut RunContext<AudioFeatures<'static>>) {
+//!         let clipped = ports.input.iter().filter(|sample| sample.abs() > 1.0).count();
+//!         if clipped > 0 {
+//!             // Logged as a trace, since this is the audio thread.
+//!             plugin_log!(context.features().log, &self.urids, Warning, "{} samples clipped", clipped);
+//!         }
+//!     }
+//! }
+//! ```
+extern crate lv2_core as core;
+extern crate lv2_sys as sys;
+
+use core::feature::*;
+use std::ffi::c_void;
+use std::fmt;
+use std::os::raw::c_char;
+use urid::*;
+
+/// The types of log entries.
+pub mod entry {
+    use urid::UriBound;
+
+    /// An error message.
+    pub struct Error;
+
+    unsafe impl UriBound for Error {
+        const URI: &'static [u8] = sys::LV2_LOG__Error;
+    }
+
+    /// A warning.
+    pub struct Warning;
+
+    unsafe impl UriBound for Warning {
+        const URI: &'static [u8] = sys::LV2_LOG__Warning;
+    }
+
+    /// An informative note.
+    pub struct Note;
+
+    unsafe impl UriBound for Note {
+        const URI: &'static [u8] = sys::LV2_LOG__Note;
+    }
+
+    /// A debugging trace, which may be logged in the audio thread.
+    pub struct Trace;
+
+    unsafe impl UriBound for Trace {
+        const URI: &'static [u8] = sys::LV2_LOG__Trace;
+    }
+}
+
+/// Collection with the URIDs of the log entry types.
+#[derive(URIDCollection, Clone, Copy)]
+pub struct LogURIDCollection {
+    pub error: URID<entry::Error>,
+    pub warning: URID<entry::Warning>,
+    pub note: URID<entry::Note>,
+    pub trace: URID<entry::Trace>,
+}
+
+impl LogURIDCollection {
+    /// Return the URID of the entry type of the given level.
+    pub fn level(&self, level: LogLevel) -> URID {
+        match level {
+            LogLevel::Error => self.error.into_general(),
+            LogLevel::Warning => self.warning.into_general(),
+            LogLevel::Note => self.note.into_general(),
+            LogLevel::Trace => self.trace.into_general(),
+        }
+    }
+}
+
+/// The level of a log message, which is logged as the corresponding entry type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Error,
+    Warning,
+    Note,
+    Trace,
+}
+
+/// The maximal length of a message in bytes, including the appended newline and the null terminator.
+///
+/// Percent signs take two bytes, since they have to be escaped.
+pub const MESSAGE_CAPACITY: usize = 256;
+
+/// A message that is formatted on the stack.
+///
+/// Percent signs are escaped, since the message is passed to the host as a format string, and null bytes are dropped. If the message doesn't fit, it's truncated at a character boundary.
+struct Message {
+    buffer: [u8; MESSAGE_CAPACITY],
+    length: usize,
+}
+
+impl Message {
+    fn new() -> Self {
+        Self {
+            buffer: [0; MESSAGE_CAPACITY],
+            length: 0,
+        }
+    }
+
+    /// Append the newline and the null terminator and return the message.
+    fn terminate(&mut self) -> *const c_char {
+        self.buffer[self.length] = b'\n';
+        self.buffer[self.length + 1] = 0;
+        self.buffer.as_ptr() as *const c_char
+    }
+}
+
+impl fmt::Write for Message {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        for character in string.chars() {
+            let mut encoded = [0; 4];
+            let encoded: &[u8] = match character {
+                '\0' => continue,
+                '%' => b"%%",
+                character => character.encode_utf8(&mut encoded).as_bytes(),
+            };
+            // Two bytes are reserved for the newline and the null terminator.
+            if self.length + encoded.len() > MESSAGE_CAPACITY - 2 {
+                return Err(fmt::Error);
+            }
+            self.buffer[self.length..self.length + encoded.len()].copy_from_slice(encoded);
+            self.length += encoded.len();
+        }
+        Ok(())
+    }
+}
+
+/// Host feature to log messages.
+///
+/// The feature can be used in all threading classes. In the "Audio" threading class, all messages are logged as traces, regardless of their level. [See also the crate documentation.](index.html)
+pub struct Log<'a> {
+    internal: &'a sys::LV2_Log_Log,
+    realtime: bool,
+}
+
+unsafe impl<'a> UriBound for Log<'a> {
+    const URI: &'static [u8] = sys::LV2_LOG__log;
+}
+
+unsafe impl<'a> Feature for Log<'a> {
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        (feature as *const sys::LV2_Log_Log)
+            .as_ref()
+            .map(|internal| Self {
+                internal,
+                realtime: class == ThreadingClass::Audio,
+            })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+impl<'a> Log<'a> {
+    /// Check whether the feature was created for the "Audio" threading class, where all messages are logged as traces.
+    pub fn is_realtime(&self) -> bool {
+        self.realtime
+    }
+
+    /// Return the level a message of the given level is actually logged with.
+    pub fn effective_level(&self, level: LogLevel) -> LogLevel {
+        if self.realtime {
+            LogLevel::Trace
+        } else {
+            level
+        }
+    }
+
+    /// Log a formatted message.
+    ///
+    /// A newline is appended to the message. This method is usually called by the [`plugin_log`](macro.plugin_log.html) macro. It returns `false` if the host doesn't provide a logging function or reports an error.
+    pub fn print(&self, urids: &LogURIDCollection, level: LogLevel, args: fmt::Arguments) -> bool {
+        let printf = match self.internal.printf {
+            Some(printf) => printf,
+            None => return false,
+        };
+        let mut message = Message::new();
+        // A formatting error only means that the message was truncated.
+        let _ = fmt::write(&mut message, args);
+        let level = urids.level(self.effective_level(level));
+        unsafe { printf(self.internal.handle, level.get(), message.terminate()) >= 0 }
+    }
+}
+
+/// Log a formatted message through the [`Log`](struct.Log.html) feature.
+///
+/// The first argument is the feature, the second one a reference to the [`LogURIDCollection`](struct.LogURIDCollection.html) and the third one the [level](enum.LogLevel.html), like `Error` or `Note`. The remaining arguments are formatted like the arguments of `println!`. In the "Audio" threading class, the message is logged as a trace, whatever its level is.
+///
+/// The macro evaluates to `true` if the message was passed to the host.
+#[macro_export]
+macro_rules! plugin_log {
+    ($log:expr, $urids:expr, $level:ident, $($arg:tt)+) => {
+        $crate::Log::print(&$log, $urids, $crate::LogLevel::$level, format_args!($($arg)+))
+    };
+}
+
+/// Prelude of `lv2_log` for wildcard usage.
+pub mod prelude {
+    pub use crate::plugin_log;
+    pub use crate::{Log, LogLevel, LogURIDCollection};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::ffi::CStr;
+    use std::os::raw::c_int;
+
+    type Messages = Vec<(u32, String)>;
+
+    /// Record the format string of a message, ignoring any further arguments.
+    unsafe extern "C" fn record(
+        handle: sys::LV2_Log_Handle,
+        type_: u32,
+        fmt: *const c_char,
+    ) -> c_int {
+        let messages = &mut *(handle as *mut Messages);
+        let message = CStr::from_ptr(fmt).to_str().unwrap().to_owned();
+        let length = message.len() as c_int;
+        messages.push((type_, message));
+        length
+    }
+
+    type RecordFunction = unsafe extern "C" fn(sys::LV2_Log_Handle, u32, *const c_char) -> c_int;
+    type PrintfFunction =
+        unsafe extern "C" fn(sys::LV2_Log_Handle, u32, *const c_char, ...) -> c_int;
+
+    fn log_messages(class: ThreadingClass, log: impl FnOnce(&Log, &LogURIDCollection)) -> Messages {
+        let mut messages = Messages::new();
+        let raw = sys::LV2_Log_Log {
+            handle: &mut messages as *mut Messages as *mut c_void,
+            printf: Some(unsafe {
+                std::mem::transmute::<RecordFunction, PrintfFunction>(record as RecordFunction)
+            }),
+            vprintf: None,
+        };
+        let feature = unsafe {
+            Log::from_feature_ptr(&raw as *const sys::LV2_Log_Log as *const c_void, class)
+        }
+        .unwrap();
+
+        let urids: LogURIDCollection = HashURIDMapper::new().populate_collection().unwrap();
+        log(&feature, &urids);
+
+        // Replace the URIDs with recognizable numbers.
+        messages
+            .into_iter()
+            .map(|(type_, message)| {
+                let level = [urids.error.get(), urids.warning.get(), urids.note.get()]
+                    .iter()
+                    .position(|urid| *urid == type_)
+                    .unwrap_or(3);
+                (level as u32, message)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_levels() {
+        let messages = log_messages(ThreadingClass::Instantiation, |log, urids| {
+            assert!(!log.is_realtime());
+            assert!(plugin_log!(log, urids, Error, "error {}", 1));
+            plugin_log!(log, urids, Warning, "warning");
+            plugin_log!(log, urids, Note, "note");
+            plugin_log!(log, urids, Trace, "trace");
+        });
+        assert_eq!(
+            messages,
+            vec![
+                (0, "error 1\n".to_owned()),
+                (1, "warning\n".to_owned()),
+                (2, "note\n".to_owned()),
+                (3, "trace\n".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_realtime_traces() {
+        let messages = log_messages(ThreadingClass::Audio, |log, urids| {
+            assert!(log.is_realtime());
+            assert_eq!(log.effective_level(LogLevel::Error), LogLevel::Trace);
+            plugin_log!(log, urids, Error, "error");
+            plugin_log!(log, urids, Note, "note");
+        });
+        assert_eq!(
+            messages,
+            vec![(3, "error\n".to_owned()), (3, "note\n".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_escaping_and_truncation() {
+        let messages = log_messages(ThreadingClass::Other, |log, urids| {
+            plugin_log!(log, urids, Note, "100% done\0");
+            plugin_log!(log, urids, Note, "{}", "ä".repeat(200));
+        });
+        assert_eq!(messages[0].1, "100%% done\n");
+        // Two-byte characters are never split.
+        assert_eq!(messages[1].1, format!("{}\n", "ä".repeat(127)));
+    }
+
+    #[test]
+    fn test_missing_printf() {
+        let raw = sys::LV2_Log_Log {
+            handle: std::ptr::null_mut(),
+            printf: None,
+            vprintf: None,
+        };
+        let log = unsafe {
+            Log::from_feature_ptr(
+                &raw as *const sys::LV2_Log_Log as *const c_void,
+                ThreadingClass::Instantiation,
+            )
+        }
+        .unwrap();
+        let urids = LogURIDCollection {
+            error: unsafe { URID::new_unchecked(1) },
+            warning: unsafe { URID::new_unchecked(2) },
+            note: unsafe { URID::new_unchecked(3) },
+            trace: unsafe { URID::new_unchecked(4) },
+        };
+        assert!(!plugin_log!(log, &urids, Error, "lost"));
+    }
+}
@@ -0,0 +1,430 @@
+//! Helpers for MIDI clock and transport messages.
+//!
+//! Hardware and other applications synchronize to a tempo with the system real-time messages of MIDI: A master sends 24 clock messages per quarter note, start, stop and continue messages to control the transport and song position pointers to jump to a position while the transport is stopped. The messages carry no tempo, which means that a receiver has to measure the time between the clock messages, and since the messages are quantized to the cycles of the host, these times jitter.
+//!
+//! The [`MidiClock`](struct.MidiClock.html) follows the messages of a master and turns them into a smoothed tempo estimate and the state of the transport. The [`ClockGenerator`](struct.ClockGenerator.html) does the opposite and calculates at which frames clock messages have to be sent for a tempo, which are written with the [`MidiWriter`](../writer/struct.MidiWriter.html). Both work on the raw bytes of the messages and don't allocate memory, which means that they can be used in the `run` method of a plugin.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::sequence::SequenceIterator;
+//! use lv2_midi::clock::*;
+//! use lv2_midi::prelude::*;
+//!
+//! /// Something like a part of a plugin's run method, which follows an external clock.
+//! fn process(clock: &mut MidiClock, events: SequenceIterator, frames: u32, urids: &MidiURIDCollection) {
+//!     for (stamp, atom) in events {
+//!         let message = match atom.read(urids.raw, ()) {
+//!             Some(message) => message,
+//!             None => continue,
+//!         };
+//!         let frame = stamp.as_frames().unwrap_or(0) as u32;
+//!         if let Some(ClockEvent::Start) = clock.process(message, frame) {
+//!             println!("The transport was started");
+//!         }
+//!     }
+//!     if let Some(tempo) = clock.tempo() {
+//!         println!("Playing at {:.1} BPM, beat {:.2}", tempo, clock.beat());
+//!     }
+//!     // The clock has to know when a cycle ends, to measure the times between the messages.
+//!     clock.advance(frames);
+//! }
+//! ```
+
+/// The number of clock messages per quarter note.
+pub const CLOCKS_PER_QUARTER: u32 = 24;
+
+/// The number of clock messages per MIDI beat, the unit of song position pointers, which is a sixteenth note.
+pub const CLOCKS_PER_SIXTEENTH: u32 = 6;
+
+/// The default weight of a new clock interval in the tempo estimate.
+const DEFAULT_SMOOTHING: f64 = 0.1;
+
+/// The relative deviation from the estimated interval above which the estimate jumps to a new interval.
+const TEMPO_JUMP: f64 = 0.5;
+
+/// The number of estimated intervals without clock messages after which the tempo is lost.
+const LOCK_TIMEOUT: f64 = 4.0;
+
+/// A clock or transport message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockEvent {
+    /// A clock message, 24 of which are sent per quarter note.
+    Clock,
+    /// Start the transport from the beginning of the song.
+    Start,
+    /// Continue the transport from the current position.
+    Continue,
+    /// Stop the transport.
+    Stop,
+    /// Jump to a position, which is measured in sixteenth notes since the beginning of the song.
+    SongPosition(u16),
+}
+
+impl ClockEvent {
+    /// Parse a clock or transport message.
+    ///
+    /// The message has to be complete, including its status byte. Returns `None` for all other messages.
+    pub fn parse(message: &[u8]) -> Option<Self> {
+        match *message {
+            [0xf8, ..] => Some(ClockEvent::Clock),
+            [0xfa, ..] => Some(ClockEvent::Start),
+            [0xfb, ..] => Some(ClockEvent::Continue),
+            [0xfc, ..] => Some(ClockEvent::Stop),
+            [0xf2, lsb, msb, ..] if lsb < 0x80 && msb < 0x80 => Some(ClockEvent::SongPosition(
+                u16::from(lsb) | (u16::from(msb) << 7),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Follows the clock and transport messages of a MIDI master.
+///
+/// Messages are handed to the clock with [`process`](#method.process), together with the frame they were received at, and the end of every cycle is announced with [`advance`](#method.advance). The clock measures the number of frames between the clock messages and smooths them with an exponential moving average, which removes the jitter that's caused by the cycles of the host. If the interval changes by more than half, the tempo was changed on purpose and the estimate jumps to the new interval instead of sliding towards it. If no clock message was received for four intervals, the master has stopped sending them and the tempo is unknown again.
+///
+/// The transport is started by start and continue messages and stopped by stop messages. Following the MIDI specification, playback begins with the next clock message after a start or continue message, which is the position the transport was started at, and every further clock message advances the position by a 24th of a quarter note.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiClock {
+    sample_rate: f64,
+    smoothing: f64,
+    cycle_start: u64,
+    last_clock: Option<u64>,
+    interval: Option<f64>,
+    playing: bool,
+    pending: bool,
+    clocks: u64,
+}
+
+impl MidiClock {
+    /// Create a clock for the given sample rate, which is stopped and has no tempo yet.
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            sample_rate,
+            smoothing: DEFAULT_SMOOTHING,
+            cycle_start: 0,
+            last_clock: None,
+            interval: None,
+            playing: false,
+            pending: false,
+            clocks: 0,
+        }
+    }
+
+    /// Set the weight of a new interval in the tempo estimate, from 0 to 1.
+    ///
+    /// Smaller weights remove more jitter, but follow gradual tempo changes more slowly. A weight of 1 disables the smoothing. The default weight is 0.1.
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing.clamp(f64::EPSILON, 1.0);
+        self
+    }
+
+    /// Update the clock with a MIDI message, which was received at the given frame of the current cycle.
+    ///
+    /// The messages of a cycle have to be processed in the order of their frames. If the message is a clock or transport message, it's returned.
+    pub fn process(&mut self, message: &[u8], frame: u32) -> Option<ClockEvent> {
+        let event = ClockEvent::parse(message)?;
+        match event {
+            ClockEvent::Clock => self.clock(self.cycle_start + u64::from(frame)),
+            ClockEvent::Start => {
+                self.clocks = 0;
+                self.playing = true;
+                self.pending = true;
+            }
+            ClockEvent::Continue => {
+                self.playing = true;
+                self.pending = true;
+            }
+            ClockEvent::Stop => {
+                self.playing = false;
+                self.pending = false;
+            }
+            ClockEvent::SongPosition(position) => {
+                self.clocks = u64::from(position) * u64::from(CLOCKS_PER_SIXTEENTH);
+            }
+        }
+        Some(event)
+    }
+
+    /// Handle a clock message at the given absolute frame.
+    fn clock(&mut self, now: u64) {
+        if let Some(last) = self.last_clock.filter(|last| now > *last) {
+            let measured = (now - last) as f64;
+            self.interval = Some(match self.interval {
+                Some(interval) if (measured - interval).abs() <= interval * TEMPO_JUMP => {
+                    interval + (measured - interval) * self.smoothing
+                }
+                _ => measured,
+            });
+        }
+        self.last_clock = Some(now);
+        if self.playing {
+            if self.pending {
+                self.pending = false;
+            } else {
+                self.clocks += 1;
+            }
+        }
+    }
+
+    /// End the current cycle, which had the given number of frames.
+    pub fn advance(&mut self, frames: u32) {
+        self.cycle_start += u64::from(frames);
+        if let (Some(last), Some(interval)) = (self.last_clock, self.interval) {
+            if (self.cycle_start - last) as f64 > interval * LOCK_TIMEOUT {
+                self.last_clock = None;
+                self.interval = None;
+            }
+        }
+    }
+
+    /// Forget the tempo and stop the transport at the beginning of the song.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate).with_smoothing(self.smoothing);
+    }
+
+    /// The estimated tempo in beats per minute, or `None` if it isn't known.
+    ///
+    /// The tempo is known after the second clock message.
+    pub fn tempo(&self) -> Option<f64> {
+        self.interval
+            .map(|interval| 60.0 * self.sample_rate / (interval * f64::from(CLOCKS_PER_QUARTER)))
+    }
+
+    /// Check whether the transport is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// The position of the last clock message, in quarter notes since the beginning of the song.
+    pub fn beat(&self) -> f64 {
+        self.clocks as f64 / f64::from(CLOCKS_PER_QUARTER)
+    }
+
+    /// The position at a frame of the current cycle, in quarter notes since the beginning of the song.
+    ///
+    /// While the transport is playing, the position is interpolated from the last clock message with the estimated tempo, but it doesn't pass the position of the next clock message, which may arrive later than expected. Otherwise, this is the same as [`beat`](#method.beat).
+    pub fn beat_at(&self, frame: u32) -> f64 {
+        let fraction = match (self.last_clock, self.interval) {
+            (Some(last), Some(interval)) if self.playing && !self.pending => {
+                let elapsed = (self.cycle_start + u64::from(frame)).saturating_sub(last);
+                (elapsed as f64 / interval).min(1.0)
+            }
+            _ => 0.0,
+        };
+        (self.clocks as f64 + fraction) / f64::from(CLOCKS_PER_QUARTER)
+    }
+
+    /// The position of the last clock message as a song position, in sixteenth notes since the beginning of the song.
+    ///
+    /// Song positions are limited to 14 bits, which is why `None` is returned if the position is beyond it.
+    pub fn song_position(&self) -> Option<u16> {
+        let position = self.clocks / u64::from(CLOCKS_PER_SIXTEENTH);
+        if position < 0x4000 {
+            Some(position as u16)
+        } else {
+            None
+        }
+    }
+}
+
+/// Calculates the frames at which clock messages are sent for a tempo.
+///
+/// The generator keeps the phase of the clock across cycles, which means that the intervals between the messages are as even as the frames allow, even if the cycles have different lengths. The messages themselves are written with the [`MidiWriter`](../writer/struct.MidiWriter.html):
+///
+/// ```
+/// use lv2_midi::clock::ClockGenerator;
+/// use lv2_midi::writer::MidiWriter;
+///
+/// fn send_clock(generator: &mut ClockGenerator, midi_out: &mut MidiWriter, frames: u32) -> Option<()> {
+///     for frame in generator.ticks(frames) {
+///         midi_out.at(i64::from(frame)).timing_clock()?;
+///     }
+///     Some(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClockGenerator {
+    sample_rate: f64,
+    interval: f64,
+    next: f64,
+}
+
+impl ClockGenerator {
+    /// Create a generator for the given sample rate and tempo in beats per minute, which sends its first message at the first frame.
+    pub fn new(sample_rate: f64, tempo: f64) -> Self {
+        let mut generator = Self {
+            sample_rate,
+            interval: f64::INFINITY,
+            next: 0.0,
+        };
+        generator.set_tempo(tempo);
+        generator
+    }
+
+    /// The tempo in beats per minute.
+    pub fn tempo(&self) -> f64 {
+        60.0 * self.sample_rate / (self.interval * f64::from(CLOCKS_PER_QUARTER))
+    }
+
+    /// Change the tempo, which takes effect after the next message.
+    ///
+    /// If the tempo isn't positive, no messages are sent.
+    pub fn set_tempo(&mut self, tempo: f64) {
+        self.interval = if tempo > 0.0 {
+            60.0 * self.sample_rate / (tempo * f64::from(CLOCKS_PER_QUARTER))
+        } else {
+            f64::INFINITY
+        };
+    }
+
+    /// Send the next message at the first frame of the next cycle, like after a start message.
+    pub fn reset(&mut self) {
+        self.next = 0.0;
+    }
+
+    /// Return the frames of a cycle with the given length at which messages have to be sent, and move on to the next cycle.
+    pub fn ticks(&mut self, frames: u32) -> ClockTicks {
+        let ticks = ClockTicks {
+            next: self.next,
+            interval: self.interval,
+            frames: f64::from(frames),
+        };
+        let count = if self.next < f64::from(frames) {
+            ((f64::from(frames) - self.next) / self.interval).ceil()
+        } else {
+            0.0
+        };
+        self.next += count * self.interval - f64::from(frames);
+        ticks
+    }
+}
+
+/// Iterator over the frames of a cycle at which clock messages are sent, which is created by [`ClockGenerator::ticks`](struct.ClockGenerator.html#method.ticks).
+#[derive(Clone, Debug)]
+pub struct ClockTicks {
+    next: f64,
+    interval: f64,
+    frames: f64,
+}
+
+impl Iterator for ClockTicks {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.next < self.frames {
+            let frame = self.next as u32;
+            self.next += self.interval;
+            Some(frame)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ClockEvent::parse(&[0xf8]), Some(ClockEvent::Clock));
+        assert_eq!(ClockEvent::parse(&[0xfa]), Some(ClockEvent::Start));
+        assert_eq!(ClockEvent::parse(&[0xfb]), Some(ClockEvent::Continue));
+        assert_eq!(ClockEvent::parse(&[0xfc]), Some(ClockEvent::Stop));
+        assert_eq!(
+            ClockEvent::parse(&[0xf2, 0x01, 0x02]),
+            Some(ClockEvent::SongPosition(0x101))
+        );
+        assert_eq!(ClockEvent::parse(&[0xf2, 0x01]), None);
+        assert_eq!(ClockEvent::parse(&[0x90, 60, 100]), None);
+        assert_eq!(ClockEvent::parse(&[]), None);
+    }
+
+    #[test]
+    fn test_clock() {
+        // At 120 BPM and 48 kHz, a clock message is sent every 1000 frames. The messages are quantized to cycles of 256 frames, like with a host that only delivers events at the start of a cycle.
+        let mut clock = MidiClock::new(48000.0);
+        assert_eq!(clock.tempo(), None);
+        assert_eq!(clock.process(&[0xfa], 0), Some(ClockEvent::Start));
+        assert!(clock.is_playing());
+        let mut next_clock = 0;
+        for cycle in 0..960u64 {
+            let end = (cycle + 1) * 256;
+            while next_clock < end {
+                clock.process(&[0xf8], 0);
+                next_clock += 1000;
+            }
+            clock.advance(256);
+        }
+        // 246 messages were sent, the first of which is the beginning of the song.
+        let tempo = clock.tempo().unwrap();
+        assert!((tempo - 120.0).abs() < 2.0, "{}", tempo);
+        assert_eq!(clock.beat(), 245.0 / 24.0);
+        assert_eq!(clock.song_position(), Some(40));
+
+        // Stopping doesn't change the position, and clock messages don't advance it.
+        clock.process(&[0xfc], 0);
+        clock.process(&[0xf8], 0);
+        assert!(!clock.is_playing());
+        assert_eq!(clock.beat(), 245.0 / 24.0);
+
+        // Jump to the fifth quarter note and continue there.
+        clock.process(&[0xf2, 16, 0], 0);
+        assert_eq!(clock.beat(), 4.0);
+        clock.process(&[0xfb], 0);
+        clock.advance(1000);
+        clock.process(&[0xf8], 0);
+        assert_eq!(clock.beat(), 4.0);
+        clock.advance(1000);
+        clock.process(&[0xf8], 0);
+        assert_eq!(clock.beat(), 4.0 + 1.0 / 24.0);
+        // Halfway to the next message.
+        let between = clock.beat_at(500);
+        assert!((between - (4.0 + 1.5 / 24.0)).abs() < 0.005, "{}", between);
+
+        // A tempo change by more than half is taken over immediately.
+        clock.advance(400);
+        clock.process(&[0xf8], 0);
+        assert_eq!(clock.tempo(), Some(300.0));
+
+        // Without clock messages, the tempo is lost.
+        clock.advance(1601);
+        assert_eq!(clock.tempo(), None);
+
+        clock.reset();
+        assert_eq!(clock, MidiClock::new(48000.0));
+    }
+
+    #[test]
+    fn test_generator() {
+        // 1000 frames per message.
+        let mut generator = ClockGenerator::new(48000.0, 120.0);
+        assert_eq!(generator.tempo(), 120.0);
+        assert_eq!(generator.ticks(256).collect::<Vec<u32>>(), vec![0]);
+        assert_eq!(generator.ticks(256).count(), 0);
+        assert_eq!(generator.ticks(256).count(), 0);
+        assert_eq!(generator.ticks(256).collect::<Vec<u32>>(), vec![232]);
+        assert_eq!(generator.ticks(2048).collect::<Vec<u32>>(), vec![976, 1976]);
+
+        // The clock follows the generator.
+        let mut clock = MidiClock::new(48000.0);
+        let mut generator = ClockGenerator::new(48000.0, 93.0);
+        for _ in 0..200 {
+            for frame in generator.ticks(128) {
+                clock.process(&[0xf8], frame);
+            }
+            clock.advance(128);
+        }
+        let tempo = clock.tempo().unwrap();
+        assert!((tempo - 93.0).abs() < 0.01, "{}", tempo);
+
+        // The message that was already due is still sent.
+        generator.set_tempo(0.0);
+        assert_eq!(generator.ticks(48000).count(), 1);
+        assert_eq!(generator.ticks(48000).count(), 0);
+        generator.reset();
+        generator.set_tempo(120.0);
+        assert_eq!(generator.ticks(1).collect::<Vec<u32>>(), vec![0]);
+    }
+}
@@ -2,6 +2,12 @@
 //!
 //! This crate adds a new atom type for the `lv2_atom` crate: The `MidiEvent`, a message conformant to the [MIDI specification](https://www.midi.org/specifications-old/item/the-midi-1-0-specification). Due to the one-crate-per-spec policy of the `rust-lv2` project, this relatively small crate isn't integrated into the main atom crate.
 //!
+//! MIDI messages can be written to an output sequence with the [`MidiWriter`](writer/struct.MidiWriter.html), which creates the events from channels, notes and other values.
+//!
+//! Messages of MPE controllers, which play every note on its own channel, can be followed with the [`MpeState`](mpe/struct.MpeState.html), which tracks the pitch bend, pressure and timbre of every note.
+//!
+//! Plugins that synchronize to MIDI clock can follow the tempo and transport of a master with the [`MidiClock`](clock/struct.MidiClock.html), and a [`ClockGenerator`](clock/struct.ClockGenerator.html) calculates when clock messages have to be sent for a tempo.
+//!
 //! If compiled with the optional `wmidi` dependency, the crate also has an additional module containing the `WMidiEvent`. This atom uses the `MidiMessage` type defined in by `wmidi` instead of byte slices.
 //!
 //! # Example
@@ -67,7 +73,10 @@ extern crate lv2_sys as sys;
 
 use urid::*;
 
+pub mod clock;
+pub mod mpe;
 pub mod raw;
+pub mod writer;
 
 #[cfg(feature = "wmidi")]
 pub mod wmidi_binding;
@@ -89,5 +98,6 @@ pub mod prelude {
     pub use crate::wmidi_binding::SystemExclusiveWMidiEvent;
     #[cfg(feature = "wmidi")]
     pub use crate::wmidi_binding::WMidiEvent;
+    pub use crate::writer::MidiWriter;
     pub use crate::MidiURIDCollection;
 }
@@ -0,0 +1,543 @@
+//! Helpers for MIDI Polyphonic Expression.
+//!
+//! [MPE](https://www.midi.org/midi-articles/midi-polyphonic-expression-mpe) gives every note its own MIDI channel, which lets controllers bend, press and slide every note on its own. The channels are grouped in up to two zones: The lower zone is managed by the first channel and uses the channels above it for its notes, the upper zone is managed by the last channel and uses the channels below it. Messages on the manager channel of a zone apply to all of its notes.
+//!
+//! The [`MpeState`](struct.MpeState.html) follows the messages of an MPE controller and tracks the zones, the active notes and their [expression](struct.Expression.html): The pitch bend, the pressure and the timbre of every note. It works on the raw bytes of the messages, which means that it doesn't need `wmidi`, and it doesn't allocate memory, which means that it can be used in the `run` method of a plugin.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::sequence::SequenceIterator;
+//! use lv2_midi::mpe::*;
+//! use lv2_midi::prelude::*;
+//!
+//! /// Something like a part of a plugin's run method.
+//! fn process(state: &mut MpeState, events: SequenceIterator, urids: &MidiURIDCollection) {
+//!     for (_, atom) in events {
+//!         let message = match atom.read(urids.raw, ()) {
+//!             Some(message) => message,
+//!             None => continue,
+//!         };
+//!         match state.process(message) {
+//!             Some(MpeEvent::NoteOn(voice)) => println!("Start {} on channel {}", voice.note, voice.channel),
+//!             Some(MpeEvent::Expression(voice)) => println!("Bend {} by {} semitones", voice.note, voice.expression.pitch_bend),
+//!             _ => (),
+//!         }
+//!     }
+//!     // The voices can also be read at any time, for example to render them.
+//!     for voice in state.voices() {
+//!         let _frequency = 440.0 * 2f32.powf((voice.pitch() - 69.0) / 12.0);
+//!     }
+//! }
+//! ```
+
+/// The default pitch bend range of member channels, in semitones.
+const DEFAULT_MEMBER_BEND_RANGE: f32 = 48.0;
+/// The default pitch bend range of manager channels, in semitones.
+const DEFAULT_MANAGER_BEND_RANGE: f32 = 2.0;
+
+/// A group of channels that is controlled by one manager channel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MpeZone {
+    /// The manager channel, which is 0 for the lower zone and 15 for the upper zone.
+    pub manager: u8,
+    /// The number of member channels, which carry the notes of the zone.
+    pub members: u8,
+    /// The pitch bend range of the member channels, in semitones.
+    pub member_bend_range: f32,
+    /// The pitch bend range of the manager channel, in semitones.
+    pub manager_bend_range: f32,
+}
+
+impl MpeZone {
+    /// Create the lower zone, which is managed by channel 0 and uses the channels from 1 upwards.
+    ///
+    /// There are at most 15 member channels.
+    pub fn lower(members: u8) -> Self {
+        Self::new(0, members)
+    }
+
+    /// Create the upper zone, which is managed by channel 15 and uses the channels from 14 downwards.
+    ///
+    /// There are at most 15 member channels.
+    pub fn upper(members: u8) -> Self {
+        Self::new(15, members)
+    }
+
+    fn new(manager: u8, members: u8) -> Self {
+        Self {
+            manager,
+            members: members.min(15),
+            member_bend_range: DEFAULT_MEMBER_BEND_RANGE,
+            manager_bend_range: DEFAULT_MANAGER_BEND_RANGE,
+        }
+    }
+
+    /// Check whether the channel is a member channel of the zone.
+    pub fn is_member(&self, channel: u8) -> bool {
+        if self.manager == 0 {
+            channel >= 1 && channel <= self.members
+        } else {
+            channel < 15 && channel >= 15 - self.members
+        }
+    }
+}
+
+/// The expression of a note.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Expression {
+    /// The pitch bend of the note, in semitones.
+    ///
+    /// This is the sum of the pitch bend of the note's channel and the pitch bend of the manager channel, each scaled by the bend range of its channel.
+    pub pitch_bend: f32,
+    /// The pressure of the note, from 0 to 1, which is sent as channel pressure.
+    pub pressure: f32,
+    /// The timbre of the note, from 0 to 1, which is sent as controller 74.
+    pub timbre: f32,
+}
+
+impl Default for Expression {
+    fn default() -> Self {
+        Self {
+            pitch_bend: 0.0,
+            pressure: 0.0,
+            timbre: 64.0 / 127.0,
+        }
+    }
+}
+
+/// An active note.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Voice {
+    /// The channel of the note, which is a member channel of a zone.
+    pub channel: u8,
+    /// The note number.
+    pub note: u8,
+    /// The velocity the note was started with.
+    pub velocity: u8,
+    /// The current expression of the note.
+    pub expression: Expression,
+}
+
+impl Voice {
+    /// The pitch of the note, as a fractional note number that includes the pitch bend.
+    pub fn pitch(&self) -> f32 {
+        f32::from(self.note) + self.expression.pitch_bend
+    }
+}
+
+/// A change of the MPE state, which is returned by [`MpeState::process`](struct.MpeState.html#method.process).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MpeEvent {
+    /// A note was started.
+    NoteOn(Voice),
+    /// A note was stopped with the given release velocity.
+    NoteOff { voice: Voice, velocity: u8 },
+    /// The expression of a note changed.
+    Expression(Voice),
+    /// The manager channel of the zone changed the expression of all notes of the zone.
+    ZoneExpression(MpeZone),
+    /// A zone was configured. Notes on the channels of the zone were stopped without `NoteOff` events.
+    ZoneChanged(MpeZone),
+}
+
+/// The values of a member channel.
+#[derive(Clone, Copy)]
+struct ChannelState {
+    /// The pitch bend, from -1 to 1.
+    bend: f32,
+    pressure: f32,
+    timbre: f32,
+    /// The registered parameter that is selected with controllers 101 and 100.
+    parameter: [u8; 2],
+}
+
+impl Default for ChannelState {
+    fn default() -> Self {
+        let expression = Expression::default();
+        Self {
+            bend: 0.0,
+            pressure: expression.pressure,
+            timbre: expression.timbre,
+            parameter: [0x7f, 0x7f],
+        }
+    }
+}
+
+/// The state of an MPE controller.
+///
+/// The state is updated with every MIDI message of the controller, which is passed to [`process`](#method.process). Every channel carries at most one note; A new note on a busy channel replaces the old one. Messages on channels that aren't part of a zone are ignored.
+///
+/// Initially, there's a lower zone with 15 member channels, which is what most MPE controllers use. Zones are changed with MPE configuration messages, which are sent as the registered parameter 6 on a manager channel, and the pitch bend ranges are changed with the registered parameter 0.
+///
+/// [See also the module documentation.](index.html)
+#[derive(Clone)]
+pub struct MpeState {
+    lower: Option<MpeZone>,
+    upper: Option<MpeZone>,
+    channels: [ChannelState; 16],
+    voices: [Option<Voice>; 16],
+}
+
+impl Default for MpeState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MpeState {
+    /// Create a state with a lower zone with 15 member channels.
+    pub fn new() -> Self {
+        Self::with_zones(Some(MpeZone::lower(15)), None)
+    }
+
+    /// Create a state with the given zones.
+    ///
+    /// If both zones are given, the upper zone is shrunk to the channels that aren't used by the lower zone.
+    pub fn with_zones(lower: Option<MpeZone>, upper: Option<MpeZone>) -> Self {
+        let mut state = Self {
+            lower: None,
+            upper: None,
+            channels: [ChannelState::default(); 16],
+            voices: [None; 16],
+        };
+        state.upper = upper.map(|zone| MpeZone {
+            manager: 15,
+            ..zone
+        });
+        if let Some(lower) = lower {
+            state.set_zone(MpeZone {
+                manager: 0,
+                ..lower
+            });
+        }
+        state
+    }
+
+    /// The lower zone, if there is one.
+    pub fn lower_zone(&self) -> Option<&MpeZone> {
+        self.lower.as_ref()
+    }
+
+    /// The upper zone, if there is one.
+    pub fn upper_zone(&self) -> Option<&MpeZone> {
+        self.upper.as_ref()
+    }
+
+    /// The zone the channel belongs to, either as a manager or as a member channel.
+    pub fn zone_of(&self, channel: u8) -> Option<&MpeZone> {
+        self.lower
+            .iter()
+            .chain(self.upper.iter())
+            .find(|zone| zone.manager == channel || zone.is_member(channel))
+    }
+
+    /// The active note on the channel.
+    pub fn voice(&self, channel: u8) -> Option<&Voice> {
+        self.voices.get(usize::from(channel))?.as_ref()
+    }
+
+    /// Iterate over all active notes.
+    pub fn voices(&self) -> impl Iterator<Item = &Voice> {
+        self.voices.iter().flatten()
+    }
+
+    /// Update the state with a MIDI message.
+    ///
+    /// The message has to be complete, including its status byte. If it changed the state in a way that is relevant for a synthesizer, the change is returned.
+    pub fn process(&mut self, message: &[u8]) -> Option<MpeEvent> {
+        let status = *message.first()?;
+        if !(0x80..0xf0).contains(&status) {
+            return None;
+        }
+        let channel = status & 0x0f;
+        let data = |index: usize| message.get(index).map(|byte| byte & 0x7f);
+        let zone = match self.zone_of(channel) {
+            Some(zone) => *zone,
+            // Zones are created with configuration messages on the channel that becomes their manager.
+            None if (channel == 0 || channel == 15) && status & 0xf0 == 0xb0 => {
+                MpeZone::new(channel, 0)
+            }
+            None => return None,
+        };
+        let is_manager = zone.manager == channel;
+
+        match status & 0xf0 {
+            0x80 => self.note_off(channel, data(1)?, data(2)?),
+            0x90 if data(2)? == 0 => self.note_off(channel, data(1)?, 64),
+            0x90 if !is_manager => {
+                let voice = Voice {
+                    channel,
+                    note: data(1)?,
+                    velocity: data(2)?,
+                    expression: self.expression(channel),
+                };
+                self.voices[usize::from(channel)] = Some(voice);
+                Some(MpeEvent::NoteOn(voice))
+            }
+            0xb0 => self.control_change(zone, channel, data(1)?, data(2)?),
+            0xd0 => {
+                let pressure = f32::from(data(1)?) / 127.0;
+                self.update_channel(zone, channel, |state| state.pressure = pressure)
+            }
+            0xe0 => {
+                let value = u16::from(data(1)?) | (u16::from(data(2)?) << 7);
+                let bend = (f32::from(value) - 8192.0) / 8192.0;
+                self.update_channel(zone, channel, |state| state.bend = bend)
+            }
+            _ => None,
+        }
+    }
+
+    fn note_off(&mut self, channel: u8, note: u8, velocity: u8) -> Option<MpeEvent> {
+        let slot = &mut self.voices[usize::from(channel)];
+        if slot.map(|voice| voice.note) != Some(note) {
+            return None;
+        }
+        slot.take()
+            .map(|voice| MpeEvent::NoteOff { voice, velocity })
+    }
+
+    fn control_change(
+        &mut self,
+        zone: MpeZone,
+        channel: u8,
+        controller: u8,
+        value: u8,
+    ) -> Option<MpeEvent> {
+        let state = &mut self.channels[usize::from(channel)];
+        match controller {
+            74 => {
+                let timbre = f32::from(value) / 127.0;
+                self.update_channel(zone, channel, |state| state.timbre = timbre)
+            }
+            101 => {
+                state.parameter[0] = value;
+                None
+            }
+            100 => {
+                state.parameter[1] = value;
+                None
+            }
+            6 => match state.parameter {
+                // Pitch bend sensitivity
+                [0, 0] if zone.members > 0 => {
+                    let mut zone = zone;
+                    if zone.manager == channel {
+                        zone.manager_bend_range = f32::from(value);
+                    } else {
+                        zone.member_bend_range = f32::from(value);
+                    }
+                    self.store_zone(zone);
+                    self.refresh_voices(zone);
+                    Some(MpeEvent::ZoneExpression(zone))
+                }
+                // MPE configuration message
+                [0, 6] if zone.manager == channel => {
+                    let zone = MpeZone::new(channel, value);
+                    self.set_zone(zone);
+                    Some(MpeEvent::ZoneChanged(zone))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Change the values of a channel and update the expression of the affected notes.
+    fn update_channel(
+        &mut self,
+        zone: MpeZone,
+        channel: u8,
+        update: impl FnOnce(&mut ChannelState),
+    ) -> Option<MpeEvent> {
+        update(&mut self.channels[usize::from(channel)]);
+        if zone.manager == channel {
+            self.refresh_voices(zone);
+            Some(MpeEvent::ZoneExpression(zone))
+        } else {
+            let expression = self.expression(channel);
+            let voice = self.voices[usize::from(channel)].as_mut()?;
+            voice.expression = expression;
+            Some(MpeEvent::Expression(*voice))
+        }
+    }
+
+    /// Calculate the expression of notes on a member channel.
+    fn expression(&self, channel: u8) -> Expression {
+        let state = &self.channels[usize::from(channel)];
+        let mut pitch_bend = 0.0;
+        if let Some(zone) = self.zone_of(channel) {
+            let manager = &self.channels[usize::from(zone.manager)];
+            pitch_bend =
+                state.bend * zone.member_bend_range + manager.bend * zone.manager_bend_range;
+        }
+        Expression {
+            pitch_bend,
+            pressure: state.pressure,
+            timbre: state.timbre,
+        }
+    }
+
+    /// Recalculate the expression of all notes of the zone.
+    fn refresh_voices(&mut self, zone: MpeZone) {
+        for channel in 0..16 {
+            if zone.is_member(channel) {
+                let expression = self.expression(channel);
+                if let Some(voice) = self.voices[usize::from(channel)].as_mut() {
+                    voice.expression = expression;
+                }
+            }
+        }
+    }
+
+    fn store_zone(&mut self, zone: MpeZone) {
+        if zone.manager == 0 {
+            self.lower = Some(zone);
+        } else {
+            self.upper = Some(zone);
+        }
+    }
+
+    /// Configure a zone and shrink the other zone if they overlap.
+    ///
+    /// A zone without member channels is removed. The notes and the expression of all channels of the zone are reset.
+    fn set_zone(&mut self, zone: MpeZone) {
+        let configured = if zone.members == 0 { None } else { Some(zone) };
+        let other = if zone.manager == 0 {
+            self.lower = configured;
+            &mut self.upper
+        } else {
+            self.upper = configured;
+            &mut self.lower
+        };
+        // The managers of both zones can't be members, which leaves 14 channels for the members of both zones.
+        if let Some(other_zone) = *other {
+            let members = other_zone.members.min(14u8.saturating_sub(zone.members));
+            *other = if members == 0 {
+                None
+            } else {
+                Some(MpeZone {
+                    members,
+                    ..other_zone
+                })
+            };
+        }
+
+        for channel in 0..16u8 {
+            if channel == zone.manager || zone.is_member(channel) {
+                let state = &mut self.channels[usize::from(channel)];
+                *state = ChannelState {
+                    parameter: state.parameter,
+                    ..ChannelState::default()
+                };
+                self.voices[usize::from(channel)] = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mpe::*;
+
+    #[test]
+    fn test_zones() {
+        let lower = MpeZone::lower(5);
+        assert!(!lower.is_member(0));
+        assert!(lower.is_member(1) && lower.is_member(5));
+        assert!(!lower.is_member(6));
+        let upper = MpeZone::upper(3);
+        assert!(upper.is_member(12) && upper.is_member(14));
+        assert!(!upper.is_member(11) && !upper.is_member(15));
+
+        let mut state = MpeState::new();
+        assert_eq!(state.lower_zone().unwrap().members, 15);
+        assert!(state.upper_zone().is_none());
+
+        // Channel 15 is a member of the lower zone, which is why it can't configure the upper zone.
+        assert!(state.process(&[0xbf, 101, 0]).is_none());
+        assert!(state.process(&[0xbf, 100, 6]).is_none());
+        assert!(state.process(&[0xbf, 6, 4]).is_none());
+        assert!(state.upper_zone().is_none());
+
+        // Reconfigure the lower zone with 10 members and add the upper zone.
+        state.process(&[0xb0, 101, 0]);
+        state.process(&[0xb0, 100, 6]);
+        assert_eq!(
+            state.process(&[0xb0, 6, 10]),
+            Some(MpeEvent::ZoneChanged(MpeZone::lower(10)))
+        );
+        state.process(&[0xbf, 101, 0]);
+        state.process(&[0xbf, 100, 6]);
+        assert_eq!(
+            state.process(&[0xbf, 6, 8]),
+            Some(MpeEvent::ZoneChanged(MpeZone::upper(8)))
+        );
+        assert_eq!(state.lower_zone().unwrap().members, 6);
+        assert_eq!(state.zone_of(7).unwrap().manager, 15);
+        assert_eq!(state.zone_of(6).unwrap().manager, 0);
+
+        // Remove the upper zone.
+        state.process(&[0xbf, 6, 0]);
+        assert!(state.upper_zone().is_none());
+        assert!(state.zone_of(7).is_none());
+        assert!(state.process(&[0x97, 60, 100]).is_none());
+    }
+
+    #[test]
+    fn test_expression() {
+        let mut state = MpeState::new();
+
+        // The expression that is sent before the note is used for it.
+        assert!(state.process(&[0xe1, 0x00, 0x50]).is_none());
+        let voice = match state.process(&[0x91, 60, 100]) {
+            Some(MpeEvent::NoteOn(voice)) => voice,
+            event => panic!("Unexpected event {:?}", event),
+        };
+        assert_eq!(voice.channel, 1);
+        assert_eq!(voice.expression.pitch_bend, 12.0);
+        assert_eq!(voice.pitch(), 72.0);
+
+        state.process(&[0x92, 64, 90]).unwrap();
+        assert_eq!(state.voices().count(), 2);
+
+        // Expression of a single note.
+        match state.process(&[0xd2, 127]) {
+            Some(MpeEvent::Expression(voice)) => {
+                assert_eq!(voice.note, 64);
+                assert_eq!(voice.expression.pressure, 1.0);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+        state.process(&[0xb2, 74, 0]).unwrap();
+        assert_eq!(state.voice(2).unwrap().expression.timbre, 0.0);
+
+        // The manager channel bends all notes, with its own range.
+        assert_eq!(
+            state.process(&[0xe0, 0x00, 0x60]),
+            Some(MpeEvent::ZoneExpression(MpeZone::lower(15)))
+        );
+        assert_eq!(state.voice(1).unwrap().expression.pitch_bend, 13.0);
+        assert_eq!(state.voice(2).unwrap().expression.pitch_bend, 1.0);
+
+        // Change the pitch bend range of the members.
+        state.process(&[0xb3, 101, 0]);
+        state.process(&[0xb3, 100, 0]);
+        state.process(&[0xb3, 6, 24]).unwrap();
+        assert_eq!(state.lower_zone().unwrap().member_bend_range, 24.0);
+        assert_eq!(state.voice(1).unwrap().expression.pitch_bend, 7.0);
+
+        // Note offs have to match the note of the channel.
+        assert!(state.process(&[0x81, 61, 0]).is_none());
+        match state.process(&[0x91, 60, 0]) {
+            Some(MpeEvent::NoteOff { voice, velocity }) => {
+                assert_eq!(voice.note, 60);
+                assert_eq!(velocity, 64);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+        assert!(state.voice(1).is_none());
+        assert_eq!(state.voices().count(), 1);
+    }
+}
@@ -0,0 +1,334 @@
+//! A writer to send MIDI messages with a sequence.
+//!
+//! Writing a MIDI message to an output sequence involves a time stamp, the URID of the MIDI event and the raw bytes of the message. The [`MidiWriter`](struct.MidiWriter.html) wraps a [`SequenceWriter`](../../lv2_atom/sequence/struct.SequenceWriter.html) and handles all of this internally. It doesn't depend on `wmidi`.
+//!
+//! # Example
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_core::prelude::*;
+//! use lv2_midi::prelude::*;
+//! use lv2_units::prelude::*;
+//! use urid::*;
+//!
+//! #[derive(PortCollection)]
+//! struct MyPorts {
+//!     output: OutputPort<AtomPort>,
+//! }
+//!
+//! #[derive(URIDCollection)]
+//! struct MyURIDs {
+//!     atom: AtomURIDCollection,
+//!     midi: MidiURIDCollection,
+//!     units: UnitURIDCollection,
+//! }
+//!
+//! /// Play a short note at the start of the block.
+//! fn run(ports: &mut MyPorts, urids: &MyURIDs) -> Option<()> {
+//!     let sequence = ports.output.init(
+//!         urids.atom.sequence,
+//!         TimeStampURID::Frames(urids.units.frame),
+//!     )?;
+//!     let mut midi_out = MidiWriter::new(sequence, urids.midi.raw);
+//!
+//!     midi_out.at(0).note_on(0, 60, 100)?;
+//!     midi_out.at(32).note_off(0, 60, 0)?;
+//!     Some(())
+//! }
+//! ```
+use crate::raw::MidiEvent;
+use atom::prelude::*;
+use atom::sequence::SequenceWriter;
+use urid::*;
+
+/// A MIDI message with up to three bytes.
+///
+/// This atom is only used to write the message in one go, which lets the sequence remove the whole event if there's not enough space for the message.
+struct ShortMessage;
+
+unsafe impl UriBound for ShortMessage {
+    const URI: &'static [u8] = sys::LV2_MIDI__MidiEvent;
+}
+
+impl<'a, 'b> Atom<'a, 'b> for ShortMessage
+where
+    'a: 'b,
+{
+    type ReadParameter = ();
+    type ReadHandle = &'a [u8];
+    type WriteParameter = ([u8; 3], usize);
+    type WriteHandle = ();
+
+    fn read(body: Space<'a>, _: ()) -> Option<&'a [u8]> {
+        body.data()
+    }
+
+    fn init(mut frame: FramedMutSpace<'a, 'b>, (bytes, len): ([u8; 3], usize)) -> Option<()> {
+        frame.write_raw(&bytes[..len], false).map(|_| ())
+    }
+}
+
+/// Writer for MIDI messages to a sequence.
+///
+/// Messages are written with a [`TimedWriter`](struct.TimedWriter.html), which is created for a time stamp with the [`at`](#method.at) method. The time stamps are measured in frames, which means that the sequence has to be initialized with a frame unit.
+///
+/// [See also the module documentation.](index.html)
+pub struct MidiWriter<'a, 'b> {
+    sequence: SequenceWriter<'a, 'b>,
+    urid: URID<ShortMessage>,
+}
+
+impl<'a, 'b> MidiWriter<'a, 'b> {
+    /// Create a new writer that writes MIDI events to the sequence.
+    pub fn new(sequence: SequenceWriter<'a, 'b>, urid: URID<MidiEvent>) -> Self {
+        Self {
+            sequence,
+            // Both atoms are bound to the URI of MIDI events.
+            urid: unsafe { URID::new_unchecked(urid.get()) },
+        }
+    }
+
+    /// Write messages at the given frame.
+    ///
+    /// The frame can be given as a [`FrameTime`](../../lv2_atom/sequence/struct.FrameTime.html) or as a plain number of frames. Just like for the sequence, the frame must not be smaller than the frame of the last written event.
+    pub fn at<'c>(&'c mut self, frame: impl Into<FrameTime>) -> TimedWriter<'c, 'a, 'b> {
+        TimedWriter {
+            writer: self,
+            stamp: frame.into().into(),
+        }
+    }
+
+    /// Return the sequence writer, which can be used to write or forward other events.
+    pub fn sequence(&mut self) -> &mut SequenceWriter<'a, 'b> {
+        &mut self.sequence
+    }
+
+    /// Return the sequence writer and consume the MIDI writer.
+    pub fn into_sequence(self) -> SequenceWriter<'a, 'b> {
+        self.sequence
+    }
+}
+
+/// Writer for MIDI messages at a certain time stamp.
+///
+/// Channels are counted from 0 to 15 and all other values, like notes and velocities, have to be in the range from 0 to 127. Every method returns `None` if a value is out of range, or if the sequence rejects the event since its time stamp is out of order or there isn't enough space for it. Otherwise, the writer is returned again to write more messages at the same time stamp.
+pub struct TimedWriter<'c, 'a, 'b> {
+    writer: &'c mut MidiWriter<'a, 'b>,
+    stamp: TimeStamp,
+}
+
+impl<'c, 'a, 'b> TimedWriter<'c, 'a, 'b> {
+    fn write(&mut self, status: u8, channel: u8, data: &[u8]) -> Option<&mut Self> {
+        if channel > 0x0f || data.iter().any(|byte| *byte > 0x7f) {
+            return None;
+        }
+        let mut bytes = [status | channel, 0, 0];
+        bytes[1..=data.len()].copy_from_slice(data);
+        self.writer
+            .sequence
+            .init(self.stamp, self.writer.urid, (bytes, data.len() + 1))?;
+        Some(self)
+    }
+
+    /// Stop playing a note.
+    pub fn note_off(&mut self, channel: u8, note: u8, velocity: u8) -> Option<&mut Self> {
+        self.write(0x80, channel, &[note, velocity])
+    }
+
+    /// Start playing a note.
+    ///
+    /// Note that a velocity of 0 is interpreted as a note off message by most receivers.
+    pub fn note_on(&mut self, channel: u8, note: u8, velocity: u8) -> Option<&mut Self> {
+        self.write(0x90, channel, &[note, velocity])
+    }
+
+    /// Change the pressure of a single note, also known as polyphonic aftertouch.
+    pub fn poly_pressure(&mut self, channel: u8, note: u8, pressure: u8) -> Option<&mut Self> {
+        self.write(0xa0, channel, &[note, pressure])
+    }
+
+    /// Change the value of a controller.
+    pub fn control_change(&mut self, channel: u8, controller: u8, value: u8) -> Option<&mut Self> {
+        self.write(0xb0, channel, &[controller, value])
+    }
+
+    /// Change the program of a channel.
+    pub fn program_change(&mut self, channel: u8, program: u8) -> Option<&mut Self> {
+        self.write(0xc0, channel, &[program])
+    }
+
+    /// Change the pressure of all notes of a channel, also known as channel aftertouch.
+    pub fn channel_pressure(&mut self, channel: u8, pressure: u8) -> Option<&mut Self> {
+        self.write(0xd0, channel, &[pressure])
+    }
+
+    /// Change the pitch bend of a channel.
+    ///
+    /// The value has 14 bits, which means that it has to be smaller than `0x4000`. The center, where the pitch isn't bent, is `0x2000`.
+    pub fn pitch_bend(&mut self, channel: u8, value: u16) -> Option<&mut Self> {
+        if value > 0x3fff {
+            return None;
+        }
+        self.write(0xe0, channel, &[(value & 0x7f) as u8, (value >> 7) as u8])
+    }
+
+    /// Send a clock message, 24 of which are sent per quarter note.
+    ///
+    /// The frames of the clock messages for a tempo are calculated by a [`ClockGenerator`](../clock/struct.ClockGenerator.html).
+    pub fn timing_clock(&mut self) -> Option<&mut Self> {
+        self.write(0xf8, 0, &[])
+    }
+
+    /// Start the transport of the receivers from the beginning of the song.
+    pub fn start(&mut self) -> Option<&mut Self> {
+        self.write(0xfa, 0, &[])
+    }
+
+    /// Continue the transport of the receivers from the current position, which is done with the "Continue" message.
+    pub fn resume(&mut self) -> Option<&mut Self> {
+        self.write(0xfb, 0, &[])
+    }
+
+    /// Stop the transport of the receivers.
+    pub fn stop(&mut self) -> Option<&mut Self> {
+        self.write(0xfc, 0, &[])
+    }
+
+    /// Move the receivers to a position, in sixteenth notes since the beginning of the song.
+    ///
+    /// The position has 14 bits, which means that it has to be smaller than `0x4000`. Receivers only accept this message while the transport is stopped.
+    pub fn song_position(&mut self, position: u16) -> Option<&mut Self> {
+        if position > 0x3fff {
+            return None;
+        }
+        self.write(0xf2, 0, &[(position & 0x7f) as u8, (position >> 7) as u8])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::ClockEvent;
+    use crate::prelude::*;
+    use atom::prelude::*;
+    use atom::space::RootMutSpace;
+    use lv2_units::prelude::*;
+    use urid::*;
+
+    #[derive(URIDCollection)]
+    struct TestURIDs {
+        atom: AtomURIDCollection,
+        midi: MidiURIDCollection,
+        units: UnitURIDCollection,
+    }
+
+    #[test]
+    fn test_midi_writer() {
+        let map = HashURIDMapper::new();
+        let urids = TestURIDs::from_map(&map).unwrap();
+
+        let mut raw_space: Box<[u8]> = Box::new([0; 112]);
+
+        // writing
+        {
+            let mut space = RootMutSpace::new(raw_space.as_mut());
+            let sequence = (&mut space as &mut dyn MutSpace)
+                .init(
+                    urids.atom.sequence,
+                    TimeStampURID::Frames(urids.units.frame),
+                )
+                .unwrap();
+            let mut midi_out = MidiWriter::new(sequence, urids.midi.raw);
+
+            midi_out
+                .at(0)
+                .note_on(1, 60, 100)
+                .unwrap()
+                .program_change(15, 3)
+                .unwrap();
+            midi_out.at(4).pitch_bend(0, 0x2001).unwrap();
+
+            // Invalid values aren't written.
+            assert!(midi_out.at(4).note_on(16, 60, 100).is_none());
+            assert!(midi_out.at(4).note_on(0, 128, 100).is_none());
+            assert!(midi_out.at(4).pitch_bend(0, 0x4000).is_none());
+            // Neither are events before the last one.
+            assert!(midi_out.at(3).note_off(0, 60, 0).is_none());
+            // There's only space for one more event.
+            midi_out
+                .at(FrameTime(4).checked_add(4).unwrap())
+                .control_change(2, 123, 0)
+                .unwrap();
+            assert!(midi_out.at(8).channel_pressure(0, 1).is_none());
+            assert_eq!(midi_out.sequence().statistics().written, 4);
+        }
+
+        // reading
+        {
+            let space = Space::from_reference(raw_space.as_ref());
+            let (body, _) = space.split_atom_body(urids.atom.sequence).unwrap();
+            let events: Vec<(i64, &[u8])> = Sequence::read(body, urids.units.beat)
+                .unwrap()
+                .map(|(stamp, atom)| {
+                    (
+                        stamp.as_frames().unwrap(),
+                        atom.read(urids.midi.raw, ()).unwrap(),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                events,
+                vec![
+                    (0, &[0x91, 60, 100][..]),
+                    (0, &[0xcf, 3][..]),
+                    (4, &[0xe0, 0x01, 0x40][..]),
+                    (8, &[0xb2, 123, 0][..]),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_transport_messages() {
+        let map = HashURIDMapper::new();
+        let urids = TestURIDs::from_map(&map).unwrap();
+        let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+
+        {
+            let mut space = RootMutSpace::new(raw_space.as_mut());
+            let sequence = (&mut space as &mut dyn MutSpace)
+                .init(
+                    urids.atom.sequence,
+                    TimeStampURID::Frames(urids.units.frame),
+                )
+                .unwrap();
+            let mut midi_out = MidiWriter::new(sequence, urids.midi.raw);
+            midi_out
+                .at(0)
+                .stop()
+                .unwrap()
+                .song_position(0x101)
+                .unwrap()
+                .resume()
+                .unwrap();
+            midi_out.at(2).timing_clock().unwrap().start().unwrap();
+            assert!(midi_out.at(2).song_position(0x4000).is_none());
+        }
+
+        let space = Space::from_reference(raw_space.as_ref());
+        let (body, _) = space.split_atom_body(urids.atom.sequence).unwrap();
+        let events: Vec<Option<ClockEvent>> = Sequence::read(body, urids.units.beat)
+            .unwrap()
+            .map(|(_, atom)| ClockEvent::parse(atom.read(urids.midi.raw, ()).unwrap()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                Some(ClockEvent::Stop),
+                Some(ClockEvent::SongPosition(0x101)),
+                Some(ClockEvent::Continue),
+                Some(ClockEvent::Clock),
+                Some(ClockEvent::Start),
+            ]
+        );
+    }
+}
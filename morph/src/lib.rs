@@ -0,0 +1,543 @@
+//! Extension for LV2 plugins with ports that change their type at runtime.
+//!
+//! The [morph extension](https://lv2plug.in/ns/ext/morph/morph.html) allows ports to switch between being control and CV ports. The type of a [`MorphPort`](struct.MorphPort.html) is changed by the host, for example to modulate a parameter with a CV signal instead of a single value per block, while the type of an [`AutoMorphPort`](struct.AutoMorphPort.html) is chosen by the plugin, usually depending on the types of its inputs.
+//!
+//! The current type of a port is an option with the port as its subject and `morph:currentType` as its key, which is handled by the plugin's [`Options`](../lv2_options/trait.Options.html) implementation. [`MorphPortTypes`](struct.MorphPortTypes.html) keeps track of the current types and handles these options, while the ports themselves use the [`Morph`](struct.Morph.html) port type, which reads and writes the port's data as the current type.
+//!
+//! Hosts only change the type of ports that are declared as morphing in the plugin's metadata, for example:
+//!
+//! ```turtle
+//! lv2:port [
+//!     a lv2:InputPort , lv2:ControlPort , morph:MorphPort ;
+//!     morph:supportsType lv2:CVPort ;
+//!     lv2:index 0 ;
+//!     lv2:symbol "modulation" ;
+//!     lv2:name "Modulation"
+//! ]
+//! ```
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_morph::*;
+//! use lv2_options::*;
+//! use lv2_urid::*;
+//! use urid::*;
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     modulation: InputPort<Morph>,
+//!     output: OutputPort<Audio>,
+//! }
+//!
+//! #[derive(FeatureCollection)]
+//! pub struct Features<'a> {
+//!     map: LV2Map<'a>,
+//! }
+//!
+//! #[uri("urn:lv2_morph:modulated")]
+//! struct Modulated {
+//!     types: MorphPortTypes,
+//!     urids: MorphURIDCollection,
+//! }
+//!
+//! impl Plugin for Modulated {
+//!     type Ports = Ports;
+//!     type InitFeatures = Features<'static>;
+//!     type AudioFeatures = ();
+//!
+//!     fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+//!         let urids: MorphURIDCollection = features.map.populate_collection()?;
+//!         // The port with the index 0 may be switched to CV by the host.
+//!         let types = MorphPortTypes::new(&[0], &[], &urids);
+//!         Some(Self { types, urids })
+//!     }
+//!
+//!     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+//!         let port_type = self.types.get(0).unwrap();
+//!         // This is safe since the host connects the port according to its current type.
+//!         let modulation = unsafe { ports.modulation.read(port_type) };
+//!         for (frame, sample) in ports.output.iter_mut().enumerate() {
+//!             *sample = modulation.value_at(frame);
+//!         }
+//!     }
+//!
+//!     fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+//!         match_extensions!(uri, OptionsDescriptor<Self>)
+//!     }
+//! }
+//!
+//! impl Options for Modulated {
+//!     fn get_option<'a>(
+//!         &'a self,
+//!         subject: Subject,
+//!         key: URID,
+//!         _: &mut Features<'static>,
+//!     ) -> Result<OptionData<'a>, OptionsError> {
+//!         self.types.get_option(subject, key, &self.urids)
+//!     }
+//!
+//!     fn set_option(
+//!         &mut self,
+//!         option: OptionValue,
+//!         _: &mut Features<'static>,
+//!     ) -> Result<(), OptionsError> {
+//!         self.types.set_option(&option, &self.urids).map(|_| ())
+//!     }
+//! }
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_options as options;
+extern crate lv2_sys as sys;
+
+use atom::scalar::AtomURID;
+use core::port::{Control, PortType, CV};
+use options::{OptionData, OptionValue, OptionsError, Subject};
+use std::ffi::c_void;
+use std::ptr::NonNull;
+use urid::*;
+
+/// The class of ports whose type may be changed by the host.
+pub struct MorphPort;
+
+unsafe impl UriBound for MorphPort {
+    const URI: &'static [u8] = sys::LV2_MORPH__MorphPort;
+}
+
+/// The class of ports whose type is chosen by the plugin.
+///
+/// The type of these ports usually depends on the types of other ports. The host can retrieve it, but it can't change it.
+pub struct AutoMorphPort;
+
+unsafe impl UriBound for AutoMorphPort {
+    const URI: &'static [u8] = sys::LV2_MORPH__AutoMorphPort;
+}
+
+/// The property of a morph port that lists the types it supports.
+pub struct SupportsType;
+
+unsafe impl UriBound for SupportsType {
+    const URI: &'static [u8] = sys::LV2_MORPH__supportsType;
+}
+
+/// The current type of a port, as an option key.
+///
+/// The subject of this option is the port and its value is the URID of the port class.
+pub struct CurrentType;
+
+unsafe impl UriBound for CurrentType {
+    const URI: &'static [u8] = sys::LV2_MORPH__currentType;
+}
+
+/// A URID cache containing all URIDs needed to handle the types of morph ports.
+#[derive(URIDCollection)]
+pub struct MorphURIDCollection {
+    pub current_type: URID<CurrentType>,
+    pub control: URID<Control>,
+    pub cv: URID<CV>,
+    pub urid: URID<AtomURID>,
+}
+
+/// The type a morph port currently has.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MorphType {
+    /// The port has a single value per `run` call.
+    Control,
+    /// The port has one value per frame.
+    CV,
+}
+
+impl MorphType {
+    /// Try to find the type with the given port class URID.
+    pub fn from_urid(urid: URID, urids: &MorphURIDCollection) -> Option<Self> {
+        if urid == urids.control {
+            Some(MorphType::Control)
+        } else if urid == urids.cv {
+            Some(MorphType::CV)
+        } else {
+            None
+        }
+    }
+
+    /// The URID of the type's port class.
+    pub fn urid(self, urids: &MorphURIDCollection) -> URID {
+        match self {
+            MorphType::Control => urids.control.into_general(),
+            MorphType::CV => urids.cv.into_general(),
+        }
+    }
+
+    /// The URI of the type's port class.
+    pub fn uri(self) -> &'static Uri {
+        match self {
+            MorphType::Control => Control::<f32>::uri(),
+            MorphType::CV => CV::uri(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct PortState {
+    index: u32,
+    automatic: bool,
+    port_type: MorphType,
+    urid: URID,
+}
+
+/// The current types of the morph ports of a plugin.
+///
+/// All ports start as control ports, which should be their class in the plugin's metadata. The types of morph ports are changed by the host with the [`set_option`](#method.set_option) method, while the types of auto-morph ports are changed by the plugin with the [`set`](#method.set) method.
+#[derive(Clone)]
+pub struct MorphPortTypes {
+    ports: Vec<PortState>,
+}
+
+impl MorphPortTypes {
+    /// Create a new store for the morph and auto-morph ports with the given indices.
+    pub fn new(morph_ports: &[u32], auto_morph_ports: &[u32], urids: &MorphURIDCollection) -> Self {
+        let make_state = |automatic: bool| {
+            move |index: &u32| PortState {
+                index: *index,
+                automatic,
+                port_type: MorphType::Control,
+                urid: MorphType::Control.urid(urids),
+            }
+        };
+        let ports = morph_ports
+            .iter()
+            .map(make_state(false))
+            .chain(auto_morph_ports.iter().map(make_state(true)))
+            .collect();
+        Self { ports }
+    }
+
+    /// Return the current type of the port with the given index.
+    ///
+    /// If the port is not a morph or auto-morph port, `None` is returned.
+    pub fn get(&self, index: u32) -> Option<MorphType> {
+        self.state(index).map(|state| state.port_type)
+    }
+
+    /// Set the type of the port with the given index.
+    ///
+    /// This is meant for auto-morph ports, whose type is chosen by the plugin, but it works with all known ports. If the port is not a morph or auto-morph port, `false` is returned.
+    pub fn set(&mut self, index: u32, port_type: MorphType, urids: &MorphURIDCollection) -> bool {
+        if let Some(state) = self.ports.iter_mut().find(|state| state.index == index) {
+            state.port_type = port_type;
+            state.urid = port_type.urid(urids);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Handle a request of the host to get the current type of a port.
+    ///
+    /// This is meant to be called from the plugin's [`Options::get_option`](../lv2_options/trait.Options.html#tymethod.get_option) implementation. It returns `BadKey` if the key isn't `morph:currentType`, so that plugins with other options can try them afterwards, and `BadSubject` if the subject isn't a known port.
+    pub fn get_option<'a>(
+        &'a self,
+        subject: Subject,
+        key: URID,
+        urids: &MorphURIDCollection,
+    ) -> Result<OptionData<'a>, OptionsError> {
+        if key != urids.current_type {
+            return Err(OptionsError::BadKey);
+        }
+        match subject {
+            Subject::Port(index) => self
+                .state(index)
+                .map(|state| OptionData::scalar(urids.urid, &state.urid))
+                .ok_or(OptionsError::BadSubject),
+            _ => Err(OptionsError::BadSubject),
+        }
+    }
+
+    /// Handle a request of the host to change the type of a port.
+    ///
+    /// This is meant to be called from the plugin's [`Options::set_option`](../lv2_options/trait.Options.html#tymethod.set_option) implementation. If the type was changed, the index of the port and its new type are returned, which the plugin may use to update the types of its auto-morph ports.
+    ///
+    /// It returns `BadKey` if the key isn't `morph:currentType`, `BadSubject` if the subject isn't a known morph port and `BadValue` if the new type isn't a control or CV port.
+    pub fn set_option(
+        &mut self,
+        option: &OptionValue,
+        urids: &MorphURIDCollection,
+    ) -> Result<(u32, MorphType), OptionsError> {
+        if option.key() != urids.current_type {
+            return Err(OptionsError::BadKey);
+        }
+        let index = match option.subject() {
+            Subject::Port(index) => index,
+            _ => return Err(OptionsError::BadSubject),
+        };
+        match self.state(index) {
+            Some(state) if !state.automatic => (),
+            _ => return Err(OptionsError::BadSubject),
+        }
+        let port_type = option
+            .read(urids.urid, ())
+            .and_then(|urid| MorphType::from_urid(urid, urids))
+            .ok_or(OptionsError::BadValue)?;
+        self.set(index, port_type, urids);
+        Ok((index, port_type))
+    }
+
+    fn state(&self, index: u32) -> Option<&PortState> {
+        self.ports.iter().find(|state| state.index == index)
+    }
+}
+
+/// Morph port type.
+///
+/// The data of a morph port is either a single control value or a slice of CV samples, depending on the current type of the port. Since this type is only known to the plugin, the data has to be accessed with the type that is stored in the plugin's [`MorphPortTypes`](struct.MorphPortTypes.html).
+///
+/// In the plugin's metadata, morph ports are described as control ports, which is the type they have until the host changes it.
+pub struct Morph;
+
+impl PortType for Morph {
+    type InputPortType = MorphInput;
+    type OutputPortType = MorphOutput;
+
+    #[inline]
+    unsafe fn input_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> MorphInput {
+        MorphInput {
+            pointer: pointer.cast(),
+            sample_count,
+        }
+    }
+
+    #[inline]
+    unsafe fn output_from_raw(pointer: NonNull<c_void>, sample_count: u32) -> MorphOutput {
+        MorphOutput {
+            pointer: pointer.cast(),
+            sample_count,
+        }
+    }
+
+    fn class() -> Option<&'static Uri> {
+        Some(MorphType::Control.uri())
+    }
+}
+
+/// The data of a morph input port.
+pub struct MorphInput {
+    pointer: NonNull<f32>,
+    sample_count: u32,
+}
+
+impl MorphInput {
+    /// Read the data of the port as the given type.
+    ///
+    /// # Safety
+    ///
+    /// The type has to be the current type of the port, as accepted by [`MorphPortTypes::set_option`](struct.MorphPortTypes.html#method.set_option). Otherwise, a control port would be read as a slice of samples, which reads beyond the port's buffer.
+    pub unsafe fn read(&self, port_type: MorphType) -> MorphValue<'_> {
+        match port_type {
+            MorphType::Control => MorphValue::Control(*self.pointer.as_ptr()),
+            MorphType::CV => MorphValue::CV(std::slice::from_raw_parts(
+                self.pointer.as_ptr(),
+                self.sample_count as usize,
+            )),
+        }
+    }
+}
+
+/// The data of a morph output port.
+pub struct MorphOutput {
+    pointer: NonNull<f32>,
+    sample_count: u32,
+}
+
+impl MorphOutput {
+    /// Create a writable reference to the data of the port as the given type.
+    ///
+    /// # Safety
+    ///
+    /// The type has to be the current type of the port, as set with [`MorphPortTypes::set`](struct.MorphPortTypes.html#method.set). Otherwise, a control port would be written as a slice of samples, which writes beyond the port's buffer.
+    pub unsafe fn write(&mut self, port_type: MorphType) -> MorphValueMut<'_> {
+        match port_type {
+            MorphType::Control => MorphValueMut::Control(&mut *self.pointer.as_ptr()),
+            MorphType::CV => MorphValueMut::CV(std::slice::from_raw_parts_mut(
+                self.pointer.as_ptr(),
+                self.sample_count as usize,
+            )),
+        }
+    }
+}
+
+/// The data of a morph input port, read with its current type.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MorphValue<'a> {
+    Control(f32),
+    CV(&'a [f32]),
+}
+
+impl<'a> MorphValue<'a> {
+    /// Return the value of the port at the given frame.
+    ///
+    /// A control port has the same value for every frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the port is a CV port and the frame is out of bounds.
+    pub fn value_at(&self, frame: usize) -> f32 {
+        match self {
+            MorphValue::Control(value) => *value,
+            MorphValue::CV(samples) => samples[frame],
+        }
+    }
+}
+
+/// The data of a morph output port, written with its current type.
+#[derive(Debug, PartialEq)]
+pub enum MorphValueMut<'a> {
+    Control(&'a mut f32),
+    CV(&'a mut [f32]),
+}
+
+impl<'a> MorphValueMut<'a> {
+    /// Set the value of the port at the given frame.
+    ///
+    /// A control port only has one value, which is overwritten for every frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the port is a CV port and the frame is out of bounds.
+    pub fn set_at(&mut self, frame: usize, value: f32) {
+        match self {
+            MorphValueMut::Control(control) => **control = value,
+            MorphValueMut::CV(samples) => samples[frame] = value,
+        }
+    }
+}
+
+/// Prelude of `lv2_morph` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        AutoMorphPort, Morph, MorphPort, MorphPortTypes, MorphType, MorphURIDCollection,
+        MorphValue, MorphValueMut,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use atom::space::Space;
+    use core::port::{InputPort, OutputPort, PortHandle};
+    use std::mem::size_of;
+
+    fn urid_option<'a>(
+        subject: Subject,
+        key: URID,
+        type_: URID,
+        value: &'a URID,
+    ) -> OptionValue<'a> {
+        let data = unsafe {
+            std::slice::from_raw_parts(value as *const URID as *const u8, size_of::<URID>())
+        };
+        OptionValue::new(subject, key, type_, Space::from_slice(data))
+    }
+
+    #[test]
+    fn test_port_types() {
+        let map = HashURIDMapper::new();
+        let urids: MorphURIDCollection = map.populate_collection().unwrap();
+        let other = map
+            .map_uri(Uri::from_bytes_with_nul(b"urn:other\0").unwrap())
+            .unwrap();
+        let mut types = MorphPortTypes::new(&[1], &[2], &urids);
+
+        assert_eq!(types.get(1), Some(MorphType::Control));
+        assert_eq!(types.get(2), Some(MorphType::Control));
+        assert_eq!(types.get(0), None);
+
+        // Changing the type of the morph port.
+        let cv = urids.cv.into_general();
+        let option = urid_option(
+            Subject::Port(1),
+            urids.current_type.into_general(),
+            urids.urid.into_general(),
+            &cv,
+        );
+        assert_eq!(types.set_option(&option, &urids), Ok((1, MorphType::CV)));
+        assert_eq!(types.get(1), Some(MorphType::CV));
+
+        let data = types
+            .get_option(Subject::Port(1), urids.current_type.into_general(), &urids)
+            .unwrap();
+        assert_eq!(data.type_(), urids.urid);
+        assert_eq!(data.data(), &cv.get().to_ne_bytes());
+
+        // Invalid requests.
+        let option = urid_option(
+            Subject::Port(2),
+            urids.current_type.into_general(),
+            urids.urid.into_general(),
+            &cv,
+        );
+        assert_eq!(
+            types.set_option(&option, &urids),
+            Err(OptionsError::BadSubject)
+        );
+        let option = urid_option(
+            Subject::Instance,
+            urids.current_type.into_general(),
+            urids.urid.into_general(),
+            &cv,
+        );
+        assert_eq!(
+            types.set_option(&option, &urids),
+            Err(OptionsError::BadSubject)
+        );
+        let option = urid_option(Subject::Port(1), other, urids.urid.into_general(), &cv);
+        assert_eq!(types.set_option(&option, &urids), Err(OptionsError::BadKey));
+        let option = urid_option(
+            Subject::Port(1),
+            urids.current_type.into_general(),
+            urids.urid.into_general(),
+            &other,
+        );
+        assert_eq!(
+            types.set_option(&option, &urids),
+            Err(OptionsError::BadValue)
+        );
+        assert!(types
+            .get_option(Subject::Port(0), urids.current_type.into_general(), &urids)
+            .is_err());
+        assert!(types.get_option(Subject::Port(1), other, &urids).is_err());
+
+        // Changing the type of the auto-morph port.
+        assert!(types.set(2, MorphType::CV, &urids));
+        assert_eq!(types.get(2), Some(MorphType::CV));
+        assert!(!types.set(0, MorphType::CV, &urids));
+    }
+
+    #[test]
+    fn test_morph_port() {
+        let mut control: f32 = 0.5;
+        let mut samples: Vec<f32> = vec![0.0, 1.0, 2.0, 3.0];
+
+        unsafe {
+            let input =
+                InputPort::<Morph>::from_raw(&mut control as *mut f32 as *mut c_void, 4).unwrap();
+            let value = input.read(MorphType::Control);
+            assert_eq!(value, MorphValue::Control(0.5));
+            assert_eq!(value.value_at(3), 0.5);
+
+            let input =
+                InputPort::<Morph>::from_raw(samples.as_mut_ptr() as *mut c_void, 4).unwrap();
+            assert_eq!(input.read(MorphType::CV).value_at(2), 2.0);
+
+            let mut output =
+                OutputPort::<Morph>::from_raw(&mut control as *mut f32 as *mut c_void, 4).unwrap();
+            output.write(MorphType::Control).set_at(2, 1.5);
+            let mut output =
+                OutputPort::<Morph>::from_raw(samples.as_mut_ptr() as *mut c_void, 4).unwrap();
+            output.write(MorphType::CV).set_at(1, 4.0);
+        }
+
+        assert_eq!(control, 1.5);
+        assert_eq!(samples, vec![0.0, 4.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::option::*;
+use crate::OptionsURIDCollection;
+use atom::prelude::*;
+use core::feature::*;
+use std::ffi::c_void;
+use urid::*;
+
+/// The options feature.
+///
+/// Hosts use this feature to pass options to a plugin when it's instantiated. These are properties like the sample rate or the bounds of the block length that don't have a place in the plugin instantiation function. The options are a list of [`OptionValue`s](struct.OptionValue.html), which can be retrieved by iterating over the list or by searching for a specific key.
+///
+/// Since the host is not required to keep the options alive after the plugin is instantiated, you should read all relevant options in the plugin's `new` method and copy them into the plugin. Therefore, this feature may only be used in the instantiation threading class.
+pub struct OptionsList<'a> {
+    options: &'a [sys::LV2_Options_Option],
+}
+
+unsafe impl<'a> UriBound for OptionsList<'a> {
+    const URI: &'static [u8] = sys::LV2_OPTIONS__options;
+}
+
+unsafe impl<'a> Feature for OptionsList<'a> {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[
+        ThreadingClass::Discovery,
+        ThreadingClass::Instantiation,
+        ThreadingClass::Other,
+    ];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+
+        let first = (feature as *const sys::LV2_Options_Option)
+            .as_ref()
+            .ok_or_else(FeatureError::invalid_data::<Self>)?;
+        let mut len: usize = 0;
+        let mut option = first as *const sys::LV2_Options_Option;
+        while (*option).key != 0 || !(*option).value.is_null() {
+            len += 1;
+            option = option.add(1);
+        }
+
+        Ok(Self {
+            options: std::slice::from_raw_parts(first, len),
+        })
+    }
+}
+
+impl<'a> OptionsList<'a> {
+    /// Create an options list from a slice of raw options.
+    ///
+    /// The slice must not contain the terminating, zeroed option.
+    pub fn from_raw(options: &'a [sys::LV2_Options_Option]) -> Self {
+        Self { options }
+    }
+
+    /// Return an iterator over all valid options in the list.
+    ///
+    /// Options with an invalid subject, key or type are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = OptionValue<'a>> {
+        self.options
+            .iter()
+            .filter_map(|option| unsafe { OptionValue::from_raw(option) })
+    }
+
+    /// Try to find an option that applies to the plugin instance.
+    pub fn get<K: ?Sized>(&self, key: URID<K>) -> Option<OptionValue<'a>> {
+        self.iter()
+            .find(|option| option.subject() == Subject::Instance && option.key() == key)
+    }
+
+    /// Try to find an option that applies to the plugin instance and read it as an atom.
+    ///
+    /// This method returns `None` if there is no such option or if the option doesn't have the requested type.
+    pub fn read<K: ?Sized, A: Atom<'a, 'a>>(
+        &self,
+        key: URID<K>,
+        urid: URID<A>,
+        parameter: A::ReadParameter,
+    ) -> Option<A::ReadHandle> {
+        self.get(key)?.read(urid, parameter)
+    }
+
+    /// Try to find the name the host shows for the plugin instance.
+    ///
+    /// The name is read from the [`WindowTitle`](struct.WindowTitle.html) option. Like all options, it has to be copied if the plugin needs it after its instantiation.
+    pub fn instance_name(
+        &self,
+        urids: &OptionsURIDCollection,
+        string: URID<atom::string::String>,
+    ) -> Option<&'a str> {
+        self.read(urids.window_title, string, ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use atom::prelude::*;
+    use core::feature::*;
+    use std::ffi::c_void;
+    use std::mem::size_of;
+    use urid::*;
+
+    #[test]
+    fn test_options_list() {
+        let map = HashURIDMapper::new();
+        let urids = map.populate_collection::<OptionsURIDCollection>().unwrap();
+        let float = map.map_type::<Float>().unwrap();
+        let int = map.map_type::<Int>().unwrap();
+        let string = map.map_type::<atom::string::String>().unwrap();
+        let block_length = map.map_str("http://example.org/blockLength").unwrap();
+
+        let sample_rate: f32 = 44100.0;
+        let length: i32 = 512;
+        let title = b"Amp 2\0";
+        let raw_options = [
+            sys::LV2_Options_Option {
+                context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                subject: 0,
+                key: urids.sample_rate.get(),
+                size: size_of::<f32>() as u32,
+                type_: float.get(),
+                value: &sample_rate as *const f32 as *const c_void,
+            },
+            sys::LV2_Options_Option {
+                context: sys::LV2_Options_Context_LV2_OPTIONS_PORT,
+                subject: 3,
+                key: block_length.get(),
+                size: size_of::<i32>() as u32,
+                type_: int.get(),
+                value: &length as *const i32 as *const c_void,
+            },
+            sys::LV2_Options_Option {
+                context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                subject: 0,
+                key: urids.window_title.get(),
+                size: title.len() as u32,
+                type_: string.get(),
+                value: title.as_ptr() as *const c_void,
+            },
+            sys::LV2_Options_Option {
+                context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                subject: 0,
+                key: 0,
+                size: 0,
+                type_: 0,
+                value: std::ptr::null(),
+            },
+        ];
+
+        let options = unsafe {
+            OptionsList::from_feature_ptr(
+                raw_options.as_ptr() as *const c_void,
+                ThreadingClass::Instantiation,
+            )
+        }
+        .unwrap();
+
+        assert_eq!(options.iter().count(), 3);
+        assert_eq!(
+            options.read(urids.sample_rate, float, ()).unwrap(),
+            sample_rate
+        );
+        assert!(options.read(urids.sample_rate, int, ()).is_none());
+        assert!(options.get(urids.scale_factor).is_none());
+        assert_eq!(options.instance_name(&urids, string), Some("Amp 2"));
+        assert!(OptionsList::from_raw(&raw_options[..2])
+            .instance_name(&urids, string)
+            .is_none());
+
+        // The block length option applies to a port, not to the instance.
+        assert!(options.get(block_length).is_none());
+        let option = options
+            .iter()
+            .find(|option| option.key() == block_length)
+            .unwrap();
+        assert_eq!(option.subject(), Subject::Port(3));
+        assert_eq!(option.read(int, ()).unwrap(), length);
+    }
+}
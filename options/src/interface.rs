@@ -0,0 +1,151 @@
+use crate::option::*;
+use crate::OptionsError;
+use core::extension::ExtensionDescriptor;
+use core::plugin::PluginInstance;
+use core::prelude::*;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use urid::*;
+
+/// A plugin extension that lets the host get and set options at runtime.
+///
+/// While the [`OptionsList`](struct.OptionsList.html) feature only provides options when the plugin is instantiated, this extension lets the host query the current value of an option with [`get_option`](#method.get_option) and change it with [`set_option`](#method.set_option). Both methods are called in the instantiation threading class and therefore receive the plugin's initialization features.
+///
+/// Both methods have a default implementation that rejects every key, so you only have to implement the ones you need.
+pub trait Options: Plugin {
+    /// Retrieve the value of an option.
+    ///
+    /// The returned data is borrowed from the plugin, since the host reads it after this method has returned.
+    fn get_option<'a>(
+        &'a self,
+        _subject: Subject,
+        _key: URID,
+        _features: &mut Self::InitFeatures,
+    ) -> Result<OptionData<'a>, OptionsError> {
+        Err(OptionsError::BadKey)
+    }
+
+    /// Change the value of an option.
+    ///
+    /// The value of the option is only valid for the duration of this call; It has to be copied if it needs to be kept.
+    fn set_option(
+        &mut self,
+        _option: OptionValue,
+        _features: &mut Self::InitFeatures,
+    ) -> Result<(), OptionsError> {
+        Err(OptionsError::BadKey)
+    }
+}
+
+/// Raw wrapper of the [`Options`](trait.Options.html) extension.
+///
+/// This is a marker type that has the required external methods for the extension.
+pub struct OptionsDescriptor<P: Options> {
+    plugin: PhantomData<P>,
+}
+
+unsafe impl<P: Options> UriBound for OptionsDescriptor<P> {
+    const URI: &'static [u8] = sys::LV2_OPTIONS__interface;
+}
+
+impl<P: Options> OptionsDescriptor<P> {
+    /// Handle a get request by the host.
+    ///
+    /// Every option in the zero-terminated array is passed to the plugin and the returned data is written back to the option. The returned status is the combination of all errors that occurred.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it is an interface for hosts written in C and since it dereferences raw pointers.
+    pub unsafe extern "C" fn extern_get(
+        handle: sys::LV2_Handle,
+        options: *mut sys::LV2_Options_Option,
+    ) -> sys::LV2_Options_Status {
+        let plugin_instance =
+            if let Some(plugin_instance) = (handle as *mut PluginInstance<P>).as_mut() {
+                plugin_instance
+            } else {
+                return sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN;
+            };
+        let (plugin, features) = plugin_instance.init_class_handle();
+
+        let mut status = sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS;
+        let mut option = options;
+        while let Some(raw) = option.as_mut() {
+            if raw.key == 0 {
+                break;
+            }
+
+            let result = match (
+                Subject::from_raw(raw.context, raw.subject),
+                URID::new(raw.key),
+            ) {
+                (Some(subject), Some(key)) => plugin.get_option(subject, key, features),
+                (None, _) => Err(OptionsError::BadSubject),
+                (_, None) => Err(OptionsError::BadKey),
+            };
+
+            match result {
+                Ok(data) => {
+                    raw.type_ = data.type_().get();
+                    raw.size = data.data().len() as u32;
+                    raw.value = data.data().as_ptr() as *const c_void;
+                }
+                Err(error) => status |= OptionsError::into(Err(error)),
+            }
+
+            option = option.add(1);
+        }
+        status
+    }
+
+    /// Handle a set request by the host.
+    ///
+    /// Every option in the zero-terminated array is passed to the plugin. The returned status is the combination of all errors that occurred.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it is an interface for hosts written in C and since it dereferences raw pointers.
+    pub unsafe extern "C" fn extern_set(
+        handle: sys::LV2_Handle,
+        options: *const sys::LV2_Options_Option,
+    ) -> sys::LV2_Options_Status {
+        let plugin_instance =
+            if let Some(plugin_instance) = (handle as *mut PluginInstance<P>).as_mut() {
+                plugin_instance
+            } else {
+                return sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN;
+            };
+        let (plugin, features) = plugin_instance.init_class_handle();
+
+        let mut status = sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS;
+        let mut option = options;
+        while let Some(raw) = option.as_ref() {
+            if raw.key == 0 && raw.value.is_null() {
+                break;
+            }
+
+            let result = if Subject::from_raw(raw.context, raw.subject).is_none() {
+                Err(OptionsError::BadSubject)
+            } else if let Some(value) = OptionValue::from_raw(raw) {
+                plugin.set_option(value, features)
+            } else if raw.key == 0 {
+                Err(OptionsError::BadKey)
+            } else {
+                Err(OptionsError::BadValue)
+            };
+            status |= OptionsError::into(result);
+
+            option = option.add(1);
+        }
+        status
+    }
+}
+
+impl<P: Options> ExtensionDescriptor for OptionsDescriptor<P> {
+    type ExtensionInterface = sys::LV2_Options_Interface;
+
+    const INTERFACE: &'static sys::LV2_Options_Interface = &sys::LV2_Options_Interface {
+        get: Some(Self::extern_get),
+        set: Some(Self::extern_set),
+    };
+}
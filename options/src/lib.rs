@@ -0,0 +1,238 @@
+//! Extension for LV2 plugins to receive and change options.
+//!
+//! Options are properties like the sample rate, the bounds of the block length or the scale factor of a UI, which are provided by the host. There are two ways to handle them: The host passes a list of options when the plugin is instantiated, which can be read with the [`OptionsList`](struct.OptionsList.html) feature, and the host may get and set options at runtime, which is handled by the [`Options`](trait.Options.html) extension.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_core::prelude::*;
+//! use lv2_options::*;
+//! use lv2_urid::*;
+//! use urid::*;
+//!
+//! #[derive(URIDCollection)]
+//! struct URIDs {
+//!     atom: AtomURIDCollection,
+//!     options: OptionsURIDCollection,
+//! }
+//!
+//! /// A plugin that needs to know the sample rate.
+//! #[uri("urn:lv2_options:sample-rate-aware")]
+//! struct SampleRateAware {
+//!     sample_rate: f32,
+//!     urids: URIDs,
+//! }
+//!
+//! #[derive(FeatureCollection)]
+//! pub struct Features<'a> {
+//!     map: LV2Map<'a>,
+//!     options: OptionsList<'a>,
+//! }
+//!
+//! impl Plugin for SampleRateAware {
+//!     type Ports = ();
+//!     type InitFeatures = Features<'static>;
+//!     type AudioFeatures = ();
+//!
+//!     fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+//!         let urids: URIDs = features.map.populate_collection()?;
+//!         // Read the sample rate from the options the host provided.
+//!         let sample_rate = features
+//!             .options
+//!             .read(urids.options.sample_rate, urids.atom.float, ())?;
+//!         Some(Self { sample_rate, urids })
+//!     }
+//!
+//!     fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+//!
+//!     fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+//!         // Export the options extension. Otherwise, the host won't use it.
+//!         match_extensions!(uri, OptionsDescriptor<Self>)
+//!     }
+//! }
+//!
+//! impl Options for SampleRateAware {
+//!     fn get_option<'a>(
+//!         &'a self,
+//!         subject: Subject,
+//!         key: URID,
+//!         _: &mut Features<'static>,
+//!     ) -> Result<OptionData<'a>, OptionsError> {
+//!         if subject != Subject::Instance {
+//!             Err(OptionsError::BadSubject)
+//!         } else if key != self.urids.options.sample_rate {
+//!             Err(OptionsError::BadKey)
+//!         } else {
+//!             Ok(OptionData::scalar(self.urids.atom.float, &self.sample_rate))
+//!         }
+//!     }
+//!
+//!     fn set_option(
+//!         &mut self,
+//!         option: OptionValue,
+//!         _: &mut Features<'static>,
+//!     ) -> Result<(), OptionsError> {
+//!         if option.subject() != Subject::Instance {
+//!             return Err(OptionsError::BadSubject);
+//!         }
+//!         if option.key() != self.urids.options.sample_rate {
+//!             return Err(OptionsError::BadKey);
+//!         }
+//!         self.sample_rate = option
+//!             .read(self.urids.atom.float, ())
+//!             .ok_or(OptionsError::BadValue)?;
+//!         Ok(())
+//!     }
+//! }
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_sys as sys;
+
+mod feature;
+pub use feature::*;
+
+mod interface;
+pub use interface::*;
+
+mod option;
+pub use option::*;
+
+use urid::*;
+
+/// Kinds of errors that may occur in the crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionsError {
+    /// The kind of the error is unknown or doesn't have a representation.
+    Unknown,
+    /// The subject of an option is invalid or not supported.
+    BadSubject,
+    /// The key of an option is unknown or not supported.
+    BadKey,
+    /// The value of an option is invalid or has the wrong type.
+    BadValue,
+}
+
+impl OptionsError {
+    /// Convert an options status into a result.
+    pub fn from(value: sys::LV2_Options_Status) -> Result<(), OptionsError> {
+        match value {
+            sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS => Ok(()),
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_SUBJECT => Err(OptionsError::BadSubject),
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY => Err(OptionsError::BadKey),
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE => Err(OptionsError::BadValue),
+            _ => Err(OptionsError::Unknown),
+        }
+    }
+
+    /// Convert a result into an options status.
+    pub fn into(result: Result<(), OptionsError>) -> sys::LV2_Options_Status {
+        match result {
+            Ok(()) => sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS,
+            Err(OptionsError::Unknown) => sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN,
+            Err(OptionsError::BadSubject) => sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_SUBJECT,
+            Err(OptionsError::BadKey) => sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY,
+            Err(OptionsError::BadValue) => sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE,
+        }
+    }
+}
+
+/// The sample rate of the host, as an option key.
+///
+/// The value of this option is usually a float.
+pub struct SampleRate;
+
+unsafe impl UriBound for SampleRate {
+    const URI: &'static [u8] = sys::LV2_PARAMETERS__sampleRate;
+}
+
+/// The scale factor of a UI, as an option key.
+///
+/// The value of this option is a float.
+pub struct ScaleFactor;
+
+unsafe impl UriBound for ScaleFactor {
+    const URI: &'static [u8] = sys::LV2_UI__scaleFactor;
+}
+
+/// The update rate of a UI in Hz, as an option key.
+///
+/// The value of this option is a float.
+pub struct UpdateRate;
+
+unsafe impl UriBound for UpdateRate {
+    const URI: &'static [u8] = sys::LV2_UI__updateRate;
+}
+
+/// The title of the window a host shows for a plugin instance, as an option key.
+///
+/// Hosts pass the name they show for an instance as this option, which is why it's read by [`OptionsList::instance_name`](struct.OptionsList.html#method.instance_name). The value of this option is a string.
+pub struct WindowTitle;
+
+unsafe impl UriBound for WindowTitle {
+    const URI: &'static [u8] = sys::LV2_UI__windowTitle;
+}
+
+/// A URID cache containing the keys of common options.
+#[derive(URIDCollection)]
+pub struct OptionsURIDCollection {
+    pub sample_rate: URID<SampleRate>,
+    pub scale_factor: URID<ScaleFactor>,
+    pub update_rate: URID<UpdateRate>,
+    pub window_title: URID<WindowTitle>,
+}
+
+/// Prelude of `lv2_options` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        Options, OptionsDescriptor, OptionsError, OptionsList, OptionsURIDCollection, Subject,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::OptionsError;
+
+    #[test]
+    fn test_options_error_conversion() {
+        assert_eq!(
+            Ok(()),
+            OptionsError::from(sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS)
+        );
+        assert_eq!(
+            Err(OptionsError::BadSubject),
+            OptionsError::from(sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_SUBJECT)
+        );
+        assert_eq!(
+            Err(OptionsError::BadKey),
+            OptionsError::from(sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY)
+        );
+        assert_eq!(
+            Err(OptionsError::BadValue),
+            OptionsError::from(sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE)
+        );
+        assert_eq!(Err(OptionsError::Unknown), OptionsError::from(u32::MAX));
+
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS,
+            OptionsError::into(Ok(()))
+        );
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_UNKNOWN,
+            OptionsError::into(Err(OptionsError::Unknown))
+        );
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_SUBJECT,
+            OptionsError::into(Err(OptionsError::BadSubject))
+        );
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY,
+            OptionsError::into(Err(OptionsError::BadKey))
+        );
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE,
+            OptionsError::into(Err(OptionsError::BadValue))
+        );
+    }
+}
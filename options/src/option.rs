@@ -0,0 +1,161 @@
+use atom::prelude::*;
+use atom::scalar::ScalarAtom;
+use atom::space::Space;
+use std::convert::TryFrom;
+use urid::*;
+
+/// The subject an option applies to.
+///
+/// Every option is a statement about something: The plugin instance itself, a port of the instance or some other resource. This is expressed by the subject of the option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Subject {
+    /// The option applies to the plugin instance itself.
+    Instance,
+    /// The option applies to the named resource with the given URID.
+    Resource(URID),
+    /// The option applies to a blank node with the given, local identifier.
+    Blank(u32),
+    /// The option applies to the port with the given index.
+    Port(u32),
+}
+
+impl Subject {
+    /// Try to create a subject from the raw context and subject of an option.
+    ///
+    /// This method returns `None` if the context is unknown or if a resource subject has a URID of zero.
+    pub fn from_raw(context: sys::LV2_Options_Context, subject: u32) -> Option<Self> {
+        match context {
+            sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE => Some(Subject::Instance),
+            sys::LV2_Options_Context_LV2_OPTIONS_RESOURCE => {
+                URID::new(subject).map(Subject::Resource)
+            }
+            sys::LV2_Options_Context_LV2_OPTIONS_BLANK => Some(Subject::Blank(subject)),
+            sys::LV2_Options_Context_LV2_OPTIONS_PORT => Some(Subject::Port(subject)),
+            _ => None,
+        }
+    }
+
+    /// Return the raw context and subject of this subject.
+    pub fn into_raw(self) -> (sys::LV2_Options_Context, u32) {
+        match self {
+            Subject::Instance => (sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE, 0),
+            Subject::Resource(urid) => (sys::LV2_Options_Context_LV2_OPTIONS_RESOURCE, urid.get()),
+            Subject::Blank(id) => (sys::LV2_Options_Context_LV2_OPTIONS_BLANK, id),
+            Subject::Port(index) => (sys::LV2_Options_Context_LV2_OPTIONS_PORT, index),
+        }
+    }
+}
+
+/// A single option, as passed by the host.
+///
+/// An option is a property with a subject: It has a key, which identifies the option, a subject it applies to and a value. The value is the body of an atom and can be read with the [`read`](#method.read) method, just like any other atom.
+#[derive(Clone, Copy)]
+pub struct OptionValue<'a> {
+    subject: Subject,
+    key: URID,
+    type_: URID,
+    body: Space<'a>,
+}
+
+impl<'a> OptionValue<'a> {
+    /// Create a new option value.
+    pub fn new(subject: Subject, key: URID, type_: URID, body: Space<'a>) -> Self {
+        Self {
+            subject,
+            key,
+            type_,
+            body,
+        }
+    }
+
+    /// Try to create an option value from a raw option.
+    ///
+    /// This method returns `None` if the subject, key or type of the option are invalid.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it dereferences the value pointer of the option, which has to point to `size` readable bytes.
+    pub unsafe fn from_raw(raw: &'a sys::LV2_Options_Option) -> Option<Self> {
+        let subject = Subject::from_raw(raw.context, raw.subject)?;
+        let key = URID::try_from(raw.key).ok()?;
+        let type_ = URID::try_from(raw.type_).ok()?;
+        let data: &'a [u8] = if raw.value.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(raw.value as *const u8, raw.size as usize)
+        };
+        Some(Self::new(subject, key, type_, Space::from_slice(data)))
+    }
+
+    /// Return the subject of the option.
+    pub fn subject(&self) -> Subject {
+        self.subject
+    }
+
+    /// Return the key of the option.
+    pub fn key(&self) -> URID {
+        self.key
+    }
+
+    /// Return the type of the option's value.
+    pub fn type_(&self) -> URID {
+        self.type_
+    }
+
+    /// Return the space containing the value of the option.
+    pub fn body(&self) -> Space<'a> {
+        self.body
+    }
+
+    /// Try to interpret the value of the option as an atom.
+    ///
+    /// This works like any atom reader: You pass the URID of the atom type as well as the type-specific argument, and if the desired type is the actual type of the value, a read handle is returned. Otherwise, `None` is returned.
+    pub fn read<A: Atom<'a, 'a>>(
+        &self,
+        urid: URID<A>,
+        parameter: A::ReadParameter,
+    ) -> Option<A::ReadHandle> {
+        if urid == self.type_ {
+            A::read(self.body, parameter)
+        } else {
+            None
+        }
+    }
+}
+
+/// The value of an option, as returned by a plugin.
+///
+/// This is the counterpart of [`OptionValue`](struct.OptionValue.html): It references the data of an option a plugin reports to the host. Since the host reads the data after the plugin method has returned, the data has to be borrowed from the plugin.
+#[derive(Clone, Copy)]
+pub struct OptionData<'a> {
+    type_: URID,
+    data: &'a [u8],
+}
+
+impl<'a> OptionData<'a> {
+    /// Create new option data from a type URID and the raw body of an atom.
+    pub fn new(type_: URID, data: &'a [u8]) -> Self {
+        Self { type_, data }
+    }
+
+    /// Create new option data from the value of a scalar atom.
+    pub fn scalar<A: ScalarAtom>(urid: URID<A>, value: &'a A::InternalType) -> Self {
+        let data = unsafe {
+            std::slice::from_raw_parts(
+                value as *const A::InternalType as *const u8,
+                std::mem::size_of::<A::InternalType>(),
+            )
+        };
+        Self::new(urid.into_general(), data)
+    }
+
+    /// Return the type of the option's value.
+    pub fn type_(&self) -> URID {
+        self.type_
+    }
+
+    /// Return the raw data of the option's value.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
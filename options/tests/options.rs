@@ -0,0 +1,255 @@
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_options as options;
+extern crate lv2_sys as sys;
+
+use atom::prelude::*;
+use core::prelude::*;
+use lv2_urid::*;
+use options::*;
+use urid::*;
+
+#[derive(FeatureCollection)]
+struct Features<'a> {
+    map: LV2Map<'a>,
+    options: OptionsList<'a>,
+}
+
+#[derive(URIDCollection)]
+struct URIDs {
+    atom: AtomURIDCollection,
+    options: OptionsURIDCollection,
+}
+
+#[uri("urn:rust-lv2:options-plugin")]
+struct OptionsPlugin {
+    sample_rate: f32,
+    urids: URIDs,
+}
+
+impl Plugin for OptionsPlugin {
+    type Ports = ();
+    type InitFeatures = Features<'static>;
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+        let urids: URIDs = features.map.populate_collection()?;
+        let sample_rate = features
+            .options
+            .read(urids.options.sample_rate, urids.atom.float, ())?;
+        Some(Self { sample_rate, urids })
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+
+    fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+        match_extensions!(uri, OptionsDescriptor<Self>)
+    }
+}
+
+impl Options for OptionsPlugin {
+    fn get_option<'a>(
+        &'a self,
+        subject: Subject,
+        key: URID,
+        _: &mut Features<'static>,
+    ) -> Result<OptionData<'a>, OptionsError> {
+        if subject != Subject::Instance {
+            Err(OptionsError::BadSubject)
+        } else if key != self.urids.options.sample_rate {
+            Err(OptionsError::BadKey)
+        } else {
+            Ok(OptionData::scalar(self.urids.atom.float, &self.sample_rate))
+        }
+    }
+
+    fn set_option(
+        &mut self,
+        option: OptionValue,
+        _: &mut Features<'static>,
+    ) -> Result<(), OptionsError> {
+        if option.subject() != Subject::Instance {
+            return Err(OptionsError::BadSubject);
+        }
+        if option.key() != self.urids.options.sample_rate {
+            return Err(OptionsError::BadKey);
+        }
+        self.sample_rate = option
+            .read(self.urids.atom.float, ())
+            .ok_or(OptionsError::BadValue)?;
+        Ok(())
+    }
+}
+
+lv2_descriptors![OptionsPlugin];
+
+#[test]
+fn main() {
+    use std::ffi::{c_void, CStr};
+    use std::mem::size_of;
+    use std::pin::Pin;
+
+    fn option(
+        context: sys::LV2_Options_Context,
+        key: u32,
+        type_: u32,
+        value: &f32,
+    ) -> sys::LV2_Options_Option {
+        sys::LV2_Options_Option {
+            context,
+            subject: 0,
+            key,
+            size: size_of::<f32>() as u32,
+            type_,
+            value: value as *const f32 as *const c_void,
+        }
+    }
+
+    fn terminator() -> sys::LV2_Options_Option {
+        sys::LV2_Options_Option {
+            context: 0,
+            subject: 0,
+            key: 0,
+            size: 0,
+            type_: 0,
+            value: std::ptr::null(),
+        }
+    }
+
+    // Instantiating all features.
+    let mut mapper: Pin<Box<HostMap<HashURIDMapper>>> = Box::pin(HashURIDMapper::new().into());
+    let map_interface = Box::pin(mapper.as_mut().make_map_interface());
+    let map = LV2Map::new(map_interface.as_ref().get_ref());
+    let urids: URIDs = map.populate_collection().unwrap();
+
+    let mut map_feature_interface = Box::pin(mapper.as_mut().make_map_interface());
+    let map_feature = Box::pin(sys::LV2_Feature {
+        URI: LV2Map::URI.as_ptr() as *const i8,
+        data: map_feature_interface.as_mut().get_mut() as *mut _ as *mut c_void,
+    });
+
+    let initial_rate: f32 = 44100.0;
+    let mut raw_options = [
+        option(
+            sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+            urids.options.sample_rate.get(),
+            urids.atom.float.get(),
+            &initial_rate,
+        ),
+        terminator(),
+    ];
+    let options_feature = Box::pin(sys::LV2_Feature {
+        URI: OptionsList::URI.as_ptr() as *const i8,
+        data: raw_options.as_mut_ptr() as *mut c_void,
+    });
+
+    let features_list: &[*const sys::LV2_Feature] = &[
+        map_feature.as_ref().get_ref(),
+        options_feature.as_ref().get_ref(),
+        std::ptr::null(),
+    ];
+
+    unsafe {
+        // Retrieving the descriptor.
+        let plugin_descriptor = &*lv2_descriptor(0);
+        assert_eq!(
+            CStr::from_ptr(plugin_descriptor.URI).to_str().unwrap(),
+            "urn:rust-lv2:options-plugin"
+        );
+
+        // Instantiating the plugin.
+        let plugin = (plugin_descriptor.instantiate.unwrap())(
+            plugin_descriptor,
+            44100.0,
+            b"\0".as_ptr() as *const i8,
+            features_list.as_ptr(),
+        );
+        assert!(!plugin.is_null());
+
+        // Retrieving the interface.
+        let interface = &*((plugin_descriptor.extension_data.unwrap())(
+            OptionsDescriptor::<OptionsPlugin>::URI.as_ptr() as *const i8,
+        ) as *const sys::LV2_Options_Interface);
+
+        // Getting the initial sample rate.
+        let mut query = [
+            sys::LV2_Options_Option {
+                context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                subject: 0,
+                key: urids.options.sample_rate.get(),
+                size: 0,
+                type_: 0,
+                value: std::ptr::null(),
+            },
+            terminator(),
+        ];
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS,
+            (interface.get.unwrap())(plugin, query.as_mut_ptr())
+        );
+        assert_eq!(query[0].type_, urids.atom.float);
+        assert_eq!(query[0].size as usize, size_of::<f32>());
+        assert_eq!(*(query[0].value as *const f32), initial_rate);
+
+        // Setting a new sample rate.
+        let new_rate: f32 = 48000.0;
+        let update = [
+            option(
+                sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                urids.options.sample_rate.get(),
+                urids.atom.float.get(),
+                &new_rate,
+            ),
+            terminator(),
+        ];
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS,
+            (interface.set.unwrap())(plugin, update.as_ptr())
+        );
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_SUCCESS,
+            (interface.get.unwrap())(plugin, query.as_mut_ptr())
+        );
+        assert_eq!(*(query[0].value as *const f32), new_rate);
+
+        // Setting unknown options and subjects.
+        let update = [
+            option(
+                sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                urids.options.scale_factor.get(),
+                urids.atom.float.get(),
+                &new_rate,
+            ),
+            option(
+                sys::LV2_Options_Context_LV2_OPTIONS_PORT,
+                urids.options.sample_rate.get(),
+                urids.atom.float.get(),
+                &new_rate,
+            ),
+            terminator(),
+        ];
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_KEY
+                | sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_SUBJECT,
+            (interface.set.unwrap())(plugin, update.as_ptr())
+        );
+
+        // Setting a value with the wrong type.
+        let update = [
+            option(
+                sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+                urids.options.sample_rate.get(),
+                urids.atom.int.get(),
+                &new_rate,
+            ),
+            terminator(),
+        ];
+        assert_eq!(
+            sys::LV2_Options_Status_LV2_OPTIONS_ERR_BAD_VALUE,
+            (interface.set.unwrap())(plugin, update.as_ptr())
+        );
+
+        // Cleanup.
+        (plugin_descriptor.cleanup.unwrap())(plugin);
+    }
+}
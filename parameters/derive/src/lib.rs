@@ -0,0 +1,12 @@
+//! Procedural macros for `lv2-parameters`.
+#![recursion_limit = "128"]
+
+mod parameter_collection_derive;
+
+use proc_macro::TokenStream;
+
+/// Implement the `ParameterCollection` trait for a parameter struct.
+#[proc_macro_derive(ParameterCollection, attributes(parameter))]
+pub fn parameter_collection_derive(input: TokenStream) -> TokenStream {
+    parameter_collection_derive::parameter_collection_derive_impl(input)
+}
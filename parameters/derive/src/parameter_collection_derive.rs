@@ -0,0 +1,260 @@
+use proc_macro::TokenStream;
+use proc_macro2::Literal;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Expr, ExprLit, Field, Ident, Lit};
+use syn::{Token, Type};
+
+/// A single `name = value` argument of the `parameter` attribute.
+struct ParameterArgument {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for ParameterArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: Expr = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
+
+/// A field in the struct we implement `ParameterCollection` for.
+struct ParameterField<'a> {
+    identifier: &'a Ident,
+    value_type: &'a Type,
+    uri: Literal,
+    default: Expr,
+    minimum: Option<Expr>,
+    maximum: Option<Expr>,
+}
+
+impl<'a> ParameterField<'a> {
+    /// Create a `Self` instance from a field object.
+    ///
+    /// The field has to have a `parameter` attribute with at least the `uri` and the `default` argument.
+    fn from_input_field(input: &'a Field) -> Self {
+        let identifier = input.ident.as_ref().unwrap();
+        let attribute = input
+            .attrs
+            .iter()
+            .find(|attribute| attribute.path.is_ident("parameter"))
+            .unwrap_or_else(|| panic!("Field `{}` has no `parameter` attribute", identifier));
+        let arguments = attribute
+            .parse_args_with(Punctuated::<ParameterArgument, Token![,]>::parse_terminated)
+            .expect("The `parameter` attribute has to be a list of `name = value` pairs");
+
+        let mut uri = None;
+        let mut default = None;
+        let mut minimum = None;
+        let mut maximum = None;
+        for argument in arguments {
+            match argument.name.to_string().as_str() {
+                "uri" => uri = Some(Self::make_uri(&argument.value)),
+                "default" => default = Some(argument.value),
+                "min" => minimum = Some(argument.value),
+                "max" => maximum = Some(argument.value),
+                name => panic!("Unknown parameter argument `{}`", name),
+            }
+        }
+
+        Self {
+            identifier,
+            value_type: &input.ty,
+            uri: uri.unwrap_or_else(|| panic!("Parameter `{}` has no URI", identifier)),
+            default: default
+                .unwrap_or_else(|| panic!("Parameter `{}` has no default value", identifier)),
+            minimum,
+            maximum,
+        }
+    }
+
+    /// Create the null-terminated byte string literal of the URI.
+    fn make_uri(value: &Expr) -> Literal {
+        const PARSING_ERROR: &str = "A parameter URI has to be a string literal";
+
+        let uri = match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(uri), ..
+            }) => uri.value(),
+            _ => panic!("{}", PARSING_ERROR),
+        };
+        if uri.contains('\0') {
+            panic!("Unexpected Null terminator");
+        }
+
+        let mut uri_vec: Vec<u8> = Vec::with_capacity(uri.len() + 1);
+        uri_vec.extend(uri.as_bytes());
+        uri_vec.push(0);
+        Literal::byte_string(uri_vec.as_ref())
+    }
+
+    /// Create an `Option` expression of a bound.
+    fn make_bound(bound: &Option<Expr>) -> impl quote::ToTokens {
+        match bound {
+            Some(bound) => quote! {Some(#bound)},
+            None => quote! {None},
+        }
+    }
+
+    /// Create the mapping expression of the URI for the cache.
+    fn make_mapping(&self) -> impl quote::ToTokens {
+        let uri = &self.uri;
+        quote! {
+            map.map_uri(Uri::from_bytes_with_nul(#uri).ok()?)?,
+        }
+    }
+
+    /// Create the field initialization with the default value.
+    fn make_default_initialization(&self) -> impl quote::ToTokens {
+        let identifier = self.identifier;
+        let default = &self.default;
+        quote! {
+            #identifier: #default,
+        }
+    }
+
+    /// Create the info object of the parameter.
+    fn make_info(&self) -> impl quote::ToTokens {
+        let value_type = self.value_type;
+        let uri = &self.uri;
+        let default = &self.default;
+        let minimum = Self::make_bound(&self.minimum);
+        let maximum = Self::make_bound(&self.maximum);
+        quote! {
+            ParameterInfo::new::<#value_type>(#uri, #default, #minimum, #maximum),
+        }
+    }
+
+    /// Create the matching arm to set the value of the parameter.
+    fn make_setter(&self, index: usize) -> impl quote::ToTokens {
+        let identifier = self.identifier;
+        let value_type = self.value_type;
+        let minimum = Self::make_bound(&self.minimum);
+        let maximum = Self::make_bound(&self.maximum);
+        quote! {
+            if key == cache[#index] {
+                let value: #value_type = source
+                    .read_value(urids)
+                    .ok_or(ParameterError::BadValue)?;
+                self.#identifier = ParameterValue::clamp(value, #minimum, #maximum);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Create the matching arm to write the value of the parameter.
+    fn make_writer(&self, index: usize) -> impl quote::ToTokens {
+        let identifier = self.identifier;
+        quote! {
+            if key == cache[#index] {
+                return sink.write_value(key, self.#identifier, urids);
+            }
+        }
+    }
+}
+
+/// Representation of a struct we implement `ParameterCollection` for.
+struct ParameterCollectionStruct<'a> {
+    struct_name: &'a Ident,
+    fields: Vec<ParameterField<'a>>,
+}
+
+impl<'a> ParameterCollectionStruct<'a> {
+    /// Construct a `Self` instance from a `DeriveInput`.
+    fn from_derive_input(input: &'a DeriveInput) -> Self {
+        let struct_name = &input.ident;
+        let fields = match &input.data {
+            Data::Struct(DataStruct { fields, .. }) => fields,
+            _ => panic!("Only structs can implement `ParameterCollection`"),
+        };
+
+        Self {
+            struct_name,
+            fields: fields
+                .iter()
+                .map(ParameterField::from_input_field)
+                .collect(),
+        }
+    }
+
+    /// Implement `ParameterCollection` for the struct.
+    fn make_implementation(&self) -> TokenStream {
+        let struct_name = self.struct_name;
+        let count = self.fields.len();
+        let mappings = self.fields.iter().map(ParameterField::make_mapping);
+        let default_initializations = self
+            .fields
+            .iter()
+            .map(ParameterField::make_default_initialization);
+        let infos = self.fields.iter().map(ParameterField::make_info);
+        let setters = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field.make_setter(index));
+        let writers = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field.make_writer(index));
+
+        (quote! {
+            impl ParameterCollection for #struct_name {
+                type Cache = [URID; #count];
+
+                fn map_cache<M: Map + ?Sized>(map: &M) -> Option<Self::Cache> {
+                    Some([
+                        #(#mappings)*
+                    ])
+                }
+
+                fn default_values() -> Self {
+                    Self {
+                        #(#default_initializations)*
+                    }
+                }
+
+                fn infos() -> Vec<ParameterInfo> {
+                    vec![
+                        #(#infos)*
+                    ]
+                }
+
+                #[allow(unused_variables)]
+                fn set_value<S: ParameterSource>(
+                    &mut self,
+                    cache: &Self::Cache,
+                    key: URID,
+                    source: &S,
+                    urids: &AtomURIDCollection,
+                ) -> Result<(), ParameterError> {
+                    #(#setters)*
+                    Err(ParameterError::UnknownParameter)
+                }
+
+                #[allow(unused_variables)]
+                fn write_value<W: ParameterSink>(
+                    &self,
+                    cache: &Self::Cache,
+                    key: URID,
+                    sink: &mut W,
+                    urids: &AtomURIDCollection,
+                ) -> Result<(), ParameterError> {
+                    #(#writers)*
+                    Err(ParameterError::UnknownParameter)
+                }
+            }
+        })
+        .into()
+    }
+}
+
+/// Implement `ParameterCollection` for a struct.
+pub fn parameter_collection_derive_impl(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    let collection = ParameterCollectionStruct::from_derive_input(&input);
+    collection.make_implementation()
+}
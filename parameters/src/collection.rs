@@ -0,0 +1,69 @@
+use crate::value::*;
+use crate::ParameterError;
+use atom::prelude::*;
+use urid::*;
+
+/// A collection of parameters.
+///
+/// This trait is usually implemented by a struct with one field per parameter, using the derive macro. Every field needs a `parameter` attribute that declares the URI and the default value, and optionally a minimal and a maximal value:
+///
+/// ```
+/// use lv2_parameters::prelude::*;
+/// use urid::*;
+///
+/// #[derive(ParameterCollection)]
+/// struct AmpParameters {
+///     #[parameter(uri = "urn:amp:gain", default = 0.0, min = -90.0, max = 24.0)]
+///     gain: f32,
+///     #[parameter(uri = "urn:amp:channels", default = 2)]
+///     channels: i32,
+/// }
+/// ```
+///
+/// The types of the fields have to implement [`ParameterValue`](trait.ParameterValue.html).
+pub trait ParameterCollection: Sized {
+    /// The URIDs of all parameters, in declaration order.
+    type Cache: AsRef<[URID]> + Send + Sync;
+
+    /// Map the URIs of all parameters.
+    fn map_cache<M: Map + ?Sized>(map: &M) -> Option<Self::Cache>;
+
+    /// Create a new instance with the default values of all parameters.
+    fn default_values() -> Self;
+
+    /// Return the descriptions of all parameters.
+    fn infos() -> Vec<ParameterInfo>;
+
+    /// Set the parameter with the given key to a value read from the source.
+    ///
+    /// The value is restricted to the declared range. If there is no parameter with the given key, `Err(ParameterError::UnknownParameter)` is returned and if the source doesn't contain a value of the right type, `Err(ParameterError::BadValue)` is returned.
+    fn set_value<S: ParameterSource>(
+        &mut self,
+        cache: &Self::Cache,
+        key: URID,
+        source: &S,
+        urids: &AtomURIDCollection,
+    ) -> Result<(), ParameterError>;
+
+    /// Write the value of the parameter with the given key to the sink.
+    fn write_value<W: ParameterSink>(
+        &self,
+        cache: &Self::Cache,
+        key: URID,
+        sink: &mut W,
+        urids: &AtomURIDCollection,
+    ) -> Result<(), ParameterError>;
+
+    /// Write the values of all parameters to the sink.
+    fn write_all<W: ParameterSink>(
+        &self,
+        cache: &Self::Cache,
+        sink: &mut W,
+        urids: &AtomURIDCollection,
+    ) -> Result<(), ParameterError> {
+        for key in cache.as_ref() {
+            self.write_value(cache, *key, sink, urids)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::value::*;
+use atom::prelude::*;
+use urid::*;
+
+/// A change of a parameter's value, sent as an event to the control input of a plugin.
+///
+/// Patch messages are objects with several properties, which makes them expensive to write and to read for a simple change of a value. A control change is a fixed-size event that only contains the URID of the parameter and its new value, and is therefore the cheap alternative for hosts and UIs that automate parameters. The value is transmitted as a `f64`, which represents the values of all parameter types exactly, except for `i64` values beyond 2^53.
+///
+/// Control changes are sent to the atom input with the [`Control`](../lv2_core/port/enum.PortDesignation.html#variant.Control) designation, just like patch messages, and are applied by [`Parameters::handle`](struct.Parameters.html#method.handle). A plugin that reads all its controls from this port doesn't need a control port for every parameter.
+///
+/// # Example
+///
+/// ```
+/// use lv2_atom::prelude::*;
+/// use lv2_atom::space::*;
+/// use lv2_parameters::prelude::*;
+/// use urid::*;
+///
+/// #[derive(ParameterCollection)]
+/// struct AmpParameters {
+///     #[parameter(uri = "urn:lv2_parameters:amp#gain", default = 0.0, min = -90.0, max = 24.0)]
+///     gain: f32,
+/// }
+///
+/// let map = HashURIDMapper::new();
+/// let urids: AtomURIDCollection = map.populate_collection().unwrap();
+/// let control_change: URID<ControlChange> = map.map_type().unwrap();
+/// let mut parameters: Parameters<AmpParameters> = Parameters::new(&map).unwrap();
+///
+/// // Write a control change, like a host does...
+/// let mut buffer = vec![0u64; 8];
+/// let bytes = unsafe {
+///     std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8)
+/// };
+/// let gain = parameters.keys()[0];
+/// (&mut RootMutSpace::new(bytes) as &mut dyn MutSpace)
+///     .init(control_change, ControlChange::new(gain, -6.0_f32))
+///     .unwrap();
+///
+/// // ...and apply it.
+/// let atom = UnidentifiedAtom::new(Space::from_slice(bytes));
+/// assert_eq!(parameters.handle(atom, None), Ok(true));
+/// assert_eq!(parameters.gain, -6.0);
+/// ```
+#[derive(EventBody, Clone, Copy, Debug, PartialEq)]
+#[event(uri = "urn:lv2_parameters:ControlChange")]
+pub struct ControlChange {
+    /// The URID of the parameter.
+    pub parameter: URID,
+    /// The new value of the parameter.
+    pub value: f64,
+}
+
+impl ControlChange {
+    /// Create a control change of the given parameter.
+    pub fn new<K: ?Sized, V: ParameterValue>(parameter: URID<K>, value: V) -> Self {
+        Self {
+            parameter: parameter.into_general(),
+            value: value.into_f64(),
+        }
+    }
+}
+
+impl ParameterSource for ControlChange {
+    fn read_value<V: ParameterValue>(&self, _: &AtomURIDCollection) -> Option<V> {
+        Some(V::from_f64(self.value))
+    }
+}
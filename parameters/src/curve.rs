@@ -0,0 +1,261 @@
+use crate::ParameterError;
+use atom::object::{ObjectHeader, ObjectWriter};
+use atom::prelude::*;
+use urid::*;
+
+/// All automation URI bounds.
+///
+/// These URIs aren't part of the LV2 specification, but a vocabulary of `rust-lv2` to exchange automation curves between hosts, UIs and plugins. All structs suffixed by `Class` are classes, others are properties.
+pub mod automation {
+    use urid::UriBound;
+
+    /// An automation curve of a single parameter.
+    pub struct CurveClass;
+    unsafe impl UriBound for CurveClass {
+        const URI: &'static [u8] = b"https://github.com/RustAudio/rust-lv2/automation#Curve\0";
+    }
+
+    /// The parameter that is automated by a curve, as a URID atom.
+    pub struct Parameter;
+    unsafe impl UriBound for Parameter {
+        const URI: &'static [u8] = b"https://github.com/RustAudio/rust-lv2/automation#parameter\0";
+    }
+
+    /// The frames of the breakpoints of a curve, as a vector of longs.
+    pub struct Frames;
+    unsafe impl UriBound for Frames {
+        const URI: &'static [u8] = b"https://github.com/RustAudio/rust-lv2/automation#frames\0";
+    }
+
+    /// The values of the breakpoints of a curve, as a vector of floats.
+    pub struct Values;
+    unsafe impl UriBound for Values {
+        const URI: &'static [u8] = b"https://github.com/RustAudio/rust-lv2/automation#values\0";
+    }
+
+    /// The curvatures of the segments that start at the breakpoints of a curve, as a vector of floats.
+    pub struct Curvatures;
+    unsafe impl UriBound for Curvatures {
+        const URI: &'static [u8] = b"https://github.com/RustAudio/rust-lv2/automation#curvatures\0";
+    }
+}
+
+use automation::*;
+
+/// A URID cache containing all automation classes and properties.
+#[derive(URIDCollection)]
+pub struct AutomationURIDCollection {
+    pub curve_class: URID<CurveClass>,
+    pub parameter: URID<Parameter>,
+    pub frames: URID<Frames>,
+    pub values: URID<Values>,
+    pub curvatures: URID<Curvatures>,
+}
+
+impl AutomationURIDCollection {
+    /// The header of an object that contains a curve.
+    pub fn curve_header(&self) -> ObjectHeader {
+        ObjectHeader {
+            id: None,
+            otype: self.curve_class.into_general(),
+        }
+    }
+}
+
+/// A single point of an automation curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Breakpoint {
+    /// The frame of the point, relative to the time stamp of the event that contains the curve.
+    pub frame: i64,
+    /// The value of the parameter at the frame.
+    pub value: f32,
+    /// The shape of the segment from this point to the next one.
+    ///
+    /// A curvature of zero is a straight line. Positive curvatures start slowly and end fast, negative curvatures start fast and end slowly.
+    pub curvature: f32,
+}
+
+/// An automation curve of a parameter, as sent over an atom port.
+///
+/// A curve is an object of the `automation:Curve` class with the automated parameter, as well as the frames, values and curvatures of its breakpoints as vectors of the same length. The curvatures are optional and default to straight lines. The frames are relative to the time stamp of the event that contains the curve and have to be sorted, which lets a plugin play back a curve with [`value_at`](#method.value_at) and lets a host record the values of a parameter by collecting them as breakpoints.
+///
+/// A curve is read without copying, which makes it usable in the audio threading class.
+#[derive(Clone, Copy, Debug)]
+pub struct AutomationCurve<'a> {
+    parameter: URID,
+    frames: &'a [i64],
+    values: &'a [f32],
+    curvatures: Option<&'a [f32]>,
+}
+
+impl<'a> AutomationCurve<'a> {
+    /// Read a curve from an atom.
+    ///
+    /// Returns [`BadMessage`](enum.ParameterError.html#variant.BadMessage) if the atom isn't a curve, if a property is missing or malformed, or if the frames aren't sorted.
+    pub fn read(
+        atom: UnidentifiedAtom<'a>,
+        atom_urids: &AtomURIDCollection,
+        automation_urids: &AutomationURIDCollection,
+    ) -> Result<Self, ParameterError> {
+        let (header, reader) = atom
+            .read(atom_urids.object, ())
+            .ok_or(ParameterError::BadMessage)?;
+        if header.otype != automation_urids.curve_class {
+            return Err(ParameterError::BadMessage);
+        }
+
+        let mut parameter = None;
+        let mut frames = None;
+        let mut values = None;
+        let mut curvatures = None;
+        for (header, value) in reader {
+            if header.key == automation_urids.parameter {
+                parameter = value.read(atom_urids.urid, ());
+            } else if header.key == automation_urids.frames {
+                frames = value.read(atom_urids.vector(), atom_urids.long);
+            } else if header.key == automation_urids.values {
+                values = value.read(atom_urids.vector(), atom_urids.float);
+            } else if header.key == automation_urids.curvatures {
+                curvatures = Some(
+                    value
+                        .read(atom_urids.vector(), atom_urids.float)
+                        .ok_or(ParameterError::BadMessage)?,
+                );
+            }
+        }
+
+        let (parameter, frames, values) = match (parameter, frames, values) {
+            (Some(parameter), Some(frames), Some(values)) => (parameter, frames, values),
+            _ => return Err(ParameterError::BadMessage),
+        };
+        if frames.len() != values.len()
+            || curvatures.is_some_and(|curvatures| curvatures.len() != frames.len())
+            || frames.windows(2).any(|pair| pair[0] > pair[1])
+        {
+            return Err(ParameterError::BadMessage);
+        }
+        Ok(Self {
+            parameter,
+            frames,
+            values,
+            curvatures,
+        })
+    }
+
+    /// Write the properties of a curve to an object.
+    ///
+    /// The object has to be initialized with the [curve header](struct.AutomationURIDCollection.html#method.curve_header). The breakpoints have to be sorted by their frames, otherwise the curve can't be read again.
+    pub fn write(
+        writer: &mut ObjectWriter,
+        parameter: URID,
+        breakpoints: &[Breakpoint],
+        atom_urids: &AtomURIDCollection,
+        automation_urids: &AutomationURIDCollection,
+    ) -> Result<(), ParameterError> {
+        writer
+            .init(automation_urids.parameter, None, atom_urids.urid, parameter)
+            .ok_or(ParameterError::NoSpace)?;
+
+        let mut frames = writer
+            .init(
+                automation_urids.frames,
+                None,
+                atom_urids.vector(),
+                atom_urids.long,
+            )
+            .ok_or(ParameterError::NoSpace)?;
+        for breakpoint in breakpoints {
+            frames
+                .push(breakpoint.frame)
+                .ok_or(ParameterError::NoSpace)?;
+        }
+
+        let mut values = writer
+            .init(
+                automation_urids.values,
+                None,
+                atom_urids.vector(),
+                atom_urids.float,
+            )
+            .ok_or(ParameterError::NoSpace)?;
+        for breakpoint in breakpoints {
+            values
+                .push(breakpoint.value)
+                .ok_or(ParameterError::NoSpace)?;
+        }
+
+        // Straight curves don't need curvatures.
+        if breakpoints
+            .iter()
+            .any(|breakpoint| breakpoint.curvature != 0.0)
+        {
+            let mut curvatures = writer
+                .init(
+                    automation_urids.curvatures,
+                    None,
+                    atom_urids.vector(),
+                    atom_urids.float,
+                )
+                .ok_or(ParameterError::NoSpace)?;
+            for breakpoint in breakpoints {
+                curvatures
+                    .push(breakpoint.curvature)
+                    .ok_or(ParameterError::NoSpace)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The automated parameter.
+    pub fn parameter(&self) -> URID {
+        self.parameter
+    }
+
+    /// The number of breakpoints.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if the curve has no breakpoints.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The breakpoint with the given index.
+    pub fn breakpoint(&self, index: usize) -> Option<Breakpoint> {
+        Some(Breakpoint {
+            frame: *self.frames.get(index)?,
+            value: self.values[index],
+            curvature: self.curvatures.map_or(0.0, |curvatures| curvatures[index]),
+        })
+    }
+
+    /// Iterate over all breakpoints, sorted by their frames.
+    pub fn breakpoints(&self) -> impl Iterator<Item = Breakpoint> + 'a {
+        let curve = *self;
+        (0..self.len()).filter_map(move |index| curve.breakpoint(index))
+    }
+
+    /// The value of the parameter at the given frame.
+    ///
+    /// Before the first breakpoint, the value of the first breakpoint is returned, and after the last breakpoint, the value of the last one. Between two breakpoints, the value follows the curvature of the first one. `None` is only returned if the curve has no breakpoints.
+    pub fn value_at(&self, frame: i64) -> Option<f32> {
+        let next = self.frames.partition_point(|&point| point <= frame);
+        if next == 0 {
+            return self.values.first().copied();
+        }
+        let start = self.breakpoint(next - 1)?;
+        let end = match self.breakpoint(next) {
+            Some(end) => end,
+            None => return Some(start.value),
+        };
+
+        let position = (frame - start.frame) as f32 / (end.frame - start.frame) as f32;
+        let shape = if start.curvature.abs() < 1e-3 {
+            position
+        } else {
+            (1.0 - (start.curvature * position).exp()) / (1.0 - start.curvature.exp())
+        };
+        Some(start.value + (end.value - start.value) * shape)
+    }
+}
@@ -0,0 +1,212 @@
+use crate::collection::*;
+use crate::control::ControlChange;
+use crate::value::*;
+use crate::ParameterError;
+use atom::object::ObjectReader;
+use atom::prelude::*;
+use atom::sequence::SequenceWriter;
+use patch::prelude::*;
+use state::{RetrieveHandle, StateErr, StoreHandle};
+use std::ops::{Deref, DerefMut};
+use urid::*;
+
+/// A sink that writes every parameter value as a `patch:Set` message to a sequence.
+struct SetMessageSink<'c, 'a, 'b> {
+    stamp: TimeStamp,
+    writer: &'c mut SequenceWriter<'a, 'b>,
+    sequence_number: Option<i32>,
+    patch_urids: &'c PatchURIDCollection,
+}
+
+impl<'c, 'a, 'b> ParameterSink for SetMessageSink<'c, 'a, 'b> {
+    fn write_value<V: ParameterValue>(
+        &mut self,
+        key: URID,
+        value: V,
+        urids: &AtomURIDCollection,
+    ) -> Result<(), ParameterError> {
+        let object = self
+            .writer
+            .init(
+                self.stamp,
+                urids.object,
+                MessageType::Set.header(self.patch_urids),
+            )
+            .ok_or(ParameterError::NoSpace)?;
+        let mut message = MessageWriter::new(object, self.patch_urids, urids);
+        if let Some(sequence_number) = self.sequence_number {
+            message
+                .sequence_number(sequence_number)
+                .ok_or(ParameterError::NoSpace)?;
+        }
+        message.property(key).ok_or(ParameterError::NoSpace)?;
+        message
+            .value(V::atom_urid(urids), value)
+            .ok_or(ParameterError::NoSpace)?;
+        Ok(())
+    }
+}
+
+/// The parameters of a plugin, with everything that is needed to control them.
+///
+/// This struct contains a [parameter collection](trait.ParameterCollection.html) as well as the required URIDs. It dereferences to the collection, so the current values of the parameters can be accessed directly.
+///
+/// The values are changed by passing incoming patch messages to the [`handle`](#method.handle) method: `patch:Set` messages change a single parameter, `patch:Put` and `patch:Patch` messages change all parameters in their body and `patch:Get` messages are answered with `patch:Set` messages containing the current values. [Control changes](struct.ControlChange.html) change a single parameter too, without the overhead of a message. The values can also be saved to and restored from a plugin's state.
+pub struct Parameters<P: ParameterCollection> {
+    values: P,
+    cache: P::Cache,
+    atom_urids: AtomURIDCollection,
+    patch_urids: PatchURIDCollection,
+    control_change: URID<ControlChange>,
+}
+
+impl<P: ParameterCollection> Parameters<P> {
+    /// Create a new parameter handler with the default values of all parameters.
+    pub fn new<M: Map + ?Sized>(map: &M) -> Option<Self> {
+        Some(Self {
+            values: P::default_values(),
+            cache: P::map_cache(map)?,
+            atom_urids: map.populate_collection()?,
+            patch_urids: map.populate_collection()?,
+            control_change: map.map_type()?,
+        })
+    }
+
+    /// Return the URIDs of all parameters, in declaration order.
+    pub fn keys(&self) -> &[URID] {
+        self.cache.as_ref()
+    }
+
+    /// Return the descriptions of all parameters.
+    pub fn infos(&self) -> Vec<ParameterInfo> {
+        P::infos()
+    }
+
+    /// Handle a patch message or a control change.
+    ///
+    /// If the atom is a [`ControlChange`](struct.ControlChange.html) or a `patch:Set`, `patch:Put`, `patch:Patch` or `patch:Get` message, it is handled and `Ok(true)` is returned. The properties added by a `patch:Patch` are applied like the body of a `patch:Put`, while removed properties are ignored since every parameter always has a value. Responses to `patch:Get` messages carry the sequence number of the request and are written to the response sequence with the given time stamp, or dropped if there is none. All other atoms are ignored and `Ok(false)` is returned.
+    pub fn handle(
+        &mut self,
+        atom: UnidentifiedAtom,
+        response: Option<(TimeStamp, &mut SequenceWriter)>,
+    ) -> Result<bool, ParameterError> {
+        if let Some(change) = atom.read(self.control_change, ()) {
+            self.values
+                .set_value(&self.cache, change.parameter, &change, &self.atom_urids)?;
+            return Ok(true);
+        }
+
+        let (header, message) = match Message::read(atom, &self.patch_urids, &self.atom_urids) {
+            Ok(message) => message,
+            Err(PatchError::NotAMessage) => return Ok(false),
+            Err(_) => return Err(ParameterError::BadMessage),
+        };
+
+        match message {
+            Message::Set { property, value } => {
+                self.values
+                    .set_value(&self.cache, property, &value, &self.atom_urids)?;
+            }
+            Message::Put { body } => self.apply(body)?,
+            Message::Patch { add, .. } => {
+                if let Some(add) = add {
+                    self.apply(add)?;
+                }
+            }
+            Message::Get { property } => {
+                if let Some((stamp, writer)) = response {
+                    self.write_set_messages(stamp, writer, header.sequence_number, property)?;
+                }
+            }
+            Message::Delete => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Write the current values of all parameters as `patch:Set` messages.
+    ///
+    /// This can be used to notify the host or a UI of the current values, for example after the state has been restored.
+    pub fn write_all(
+        &self,
+        stamp: TimeStamp,
+        writer: &mut SequenceWriter,
+    ) -> Result<(), ParameterError> {
+        self.write_set_messages(stamp, writer, None, None)
+    }
+
+    /// Save the values of all parameters to the plugin's state.
+    ///
+    /// Every parameter is stored as a property with the parameter's URID as the key. All drafted properties are committed afterwards.
+    pub fn save(&self, store: &mut StoreHandle) -> Result<(), StateErr> {
+        self.values
+            .write_all(&self.cache, store, &self.atom_urids)?;
+        store.commit_all()
+    }
+
+    /// Restore the values of all parameters from the plugin's state.
+    ///
+    /// Parameters that are missing in the state keep their current value.
+    pub fn restore(&mut self, store: &RetrieveHandle) -> Result<(), StateErr> {
+        for key in self.cache.as_ref() {
+            let property = match store.retrieve(*key) {
+                Ok(property) => property,
+                Err(StateErr::NoProperty) => continue,
+                Err(error) => return Err(error),
+            };
+            self.values
+                .set_value(&self.cache, *key, &property, &self.atom_urids)?;
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, properties: ObjectReader) -> Result<(), ParameterError> {
+        // Apply every known parameter, even if some other property is invalid.
+        let mut result = Ok(());
+        for (header, atom) in properties {
+            if let Err(error) =
+                self.values
+                    .set_value(&self.cache, header.key, &atom, &self.atom_urids)
+            {
+                result = Err(error);
+            }
+        }
+        result
+    }
+
+    fn write_set_messages(
+        &self,
+        stamp: TimeStamp,
+        writer: &mut SequenceWriter,
+        sequence_number: Option<i32>,
+        property: Option<URID>,
+    ) -> Result<(), ParameterError> {
+        let mut sink = SetMessageSink {
+            stamp,
+            writer,
+            sequence_number,
+            patch_urids: &self.patch_urids,
+        };
+        match property {
+            Some(key) => self
+                .values
+                .write_value(&self.cache, key, &mut sink, &self.atom_urids),
+            None => self
+                .values
+                .write_all(&self.cache, &mut sink, &self.atom_urids),
+        }
+    }
+}
+
+impl<P: ParameterCollection> Deref for Parameters<P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        &self.values
+    }
+}
+
+impl<P: ParameterCollection> DerefMut for Parameters<P> {
+    fn deref_mut(&mut self) -> &mut P {
+        &mut self.values
+    }
+}
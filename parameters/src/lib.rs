@@ -0,0 +1,145 @@
+//! Typed parameters for LV2 plugins.
+//!
+//! Parameters are the modern replacement of control ports: Instead of having a port for every value, a plugin declares a set of parameters that are identified by URIs and controlled by the host or a UI with [patch](https://lv2plug.in/ns/ext/patch/patch.html) messages over an atom port.
+//!
+//! This crate lets you declare the parameters of a plugin as a struct with the [`ParameterCollection`](trait.ParameterCollection.html) derive macro. The [`Parameters`](struct.Parameters.html) handler then stores the values, handles `patch:Get`, `patch:Set`, `patch:Put` and `patch:Patch` messages, and saves and restores the values in the plugin's state.
+//!
+//! Hosts and UIs that change parameters often, for example to automate them, can send [`ControlChange`](struct.ControlChange.html) events instead of patch messages, which only contain the URID of the parameter and its new value. Both are sent to the atom input with the `Control` [designation](../lv2_core/port/enum.PortDesignation.html), which lets plugins receive all their controls without a control port for every parameter.
+//!
+//! Parameters can also be automated with curves: An [`AutomationCurve`](struct.AutomationCurve.html) is a small object of breakpoints with frame offsets and curvatures, which hosts and UIs send over an atom port and plugins play back with [`value_at`](struct.AutomationCurve.html#method.value_at).
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_core::prelude::*;
+//! use lv2_parameters::prelude::*;
+//! use lv2_urid::*;
+//! use urid::*;
+//!
+//! #[derive(ParameterCollection)]
+//! struct AmpParameters {
+//!     #[parameter(uri = "urn:lv2_parameters:amp#gain", default = 0.0, min = -90.0, max = 24.0)]
+//!     gain: f32,
+//! }
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     #[port(designation = Control)]
+//!     control: InputPort<AtomPort>,
+//!     notify: OutputPort<AtomPort>,
+//!     input: InputPort<Audio>,
+//!     output: OutputPort<Audio>,
+//! }
+//!
+//! #[derive(FeatureCollection)]
+//! struct Features<'a> {
+//!     map: LV2Map<'a>,
+//! }
+//!
+//! #[derive(URIDCollection)]
+//! struct URIDs {
+//!     atom: AtomURIDCollection,
+//!     unit: lv2_units::UnitURIDCollection,
+//! }
+//!
+//! #[uri("urn:lv2_parameters:amp")]
+//! struct Amp {
+//!     parameters: Parameters<AmpParameters>,
+//!     urids: URIDs,
+//! }
+//!
+//! impl Plugin for Amp {
+//!     type Ports = Ports;
+//!     type InitFeatures = Features<'static>;
+//!     type AudioFeatures = ();
+//!
+//!     fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+//!         Some(Self {
+//!             parameters: Parameters::new(&features.map)?,
+//!             urids: features.map.populate_collection()?,
+//!         })
+//!     }
+//!
+//!     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+//!         let control = ports
+//!             .control
+//!             .read(self.urids.atom.sequence, self.urids.unit.beat)
+//!             .unwrap();
+//!         let mut notify = ports
+//!             .notify
+//!             .init(
+//!                 self.urids.atom.sequence,
+//!                 TimeStampURID::Frames(self.urids.unit.frame),
+//!             )
+//!             .unwrap();
+//!
+//!         // Apply all incoming patch messages and answer requests.
+//!         for (stamp, atom) in control {
+//!             let _ = self.parameters.handle(atom, Some((stamp, &mut notify)));
+//!         }
+//!
+//!         // The current values are accessible as fields.
+//!         let coef = 10.0_f32.powf(self.parameters.gain * 0.05);
+//!         for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+//!             *output = *input * coef;
+//!         }
+//!     }
+//! }
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_patch as patch;
+extern crate lv2_state as state;
+extern crate lv2_sys as sys;
+
+mod curve;
+pub use curve::*;
+
+mod collection;
+pub use collection::*;
+
+mod control;
+pub use control::*;
+
+mod handler;
+pub use handler::*;
+
+mod value;
+pub use value::*;
+
+pub use lv2_parameters_derive::*;
+
+use state::StateErr;
+
+/// Kinds of errors that may occur in the crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParameterError {
+    /// There is no parameter with the given key.
+    UnknownParameter,
+    /// The value of a parameter has the wrong type.
+    BadValue,
+    /// A patch message is malformed, for example because a required property is missing.
+    BadMessage,
+    /// There isn't enough space to write a value.
+    NoSpace,
+}
+
+impl From<ParameterError> for StateErr {
+    fn from(error: ParameterError) -> StateErr {
+        match error {
+            ParameterError::UnknownParameter => StateErr::NoProperty,
+            ParameterError::BadValue => StateErr::BadType,
+            ParameterError::BadMessage => StateErr::BadData,
+            ParameterError::NoSpace => StateErr::NoSpace,
+        }
+    }
+}
+
+/// Prelude of `lv2_parameters` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        AutomationCurve, AutomationURIDCollection, Breakpoint, ControlChange, ParameterCollection,
+        ParameterError, ParameterInfo, ParameterSink, ParameterSource, ParameterValue, Parameters,
+    };
+    pub use atom::prelude::AtomURIDCollection;
+}
@@ -0,0 +1,220 @@
+use crate::ParameterError;
+use atom::object::ObjectWriter;
+use atom::prelude::*;
+use atom::scalar::ScalarAtom;
+use state::{StatePropertyReader, StoreHandle};
+use urid::*;
+
+/// A type that can be used as the value of a parameter.
+///
+/// Parameter values are transmitted as scalar atoms. This trait links a Rust type to the atom type that represents it.
+pub trait ParameterValue: Copy + PartialOrd + Send + Sync + 'static {
+    /// The atom type that represents values of this type.
+    type Atom: ScalarAtom<InternalType = Self>;
+
+    /// Retrieve the URID of the atom type from the atom URID collection.
+    fn atom_urid(urids: &AtomURIDCollection) -> URID<Self::Atom>;
+
+    /// Convert the value to a `f64`, which is used to describe parameters.
+    fn into_f64(self) -> f64;
+
+    /// Convert a `f64` to a value, which is used to apply [control changes](struct.ControlChange.html).
+    ///
+    /// Integers are rounded to the nearest value and saturate at their bounds.
+    fn from_f64(value: f64) -> Self;
+
+    /// Restrict the value to the given bounds.
+    fn clamp(self, minimum: Option<Self>, maximum: Option<Self>) -> Self {
+        let value = match minimum {
+            Some(minimum) if self < minimum => minimum,
+            _ => self,
+        };
+        match maximum {
+            Some(maximum) if value > maximum => maximum,
+            _ => value,
+        }
+    }
+}
+
+impl ParameterValue for f32 {
+    type Atom = Float;
+
+    fn atom_urid(urids: &AtomURIDCollection) -> URID<Float> {
+        urids.float
+    }
+
+    fn into_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl ParameterValue for f64 {
+    type Atom = Double;
+
+    fn atom_urid(urids: &AtomURIDCollection) -> URID<Double> {
+        urids.double
+    }
+
+    fn into_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+impl ParameterValue for i32 {
+    type Atom = Int;
+
+    fn atom_urid(urids: &AtomURIDCollection) -> URID<Int> {
+        urids.int
+    }
+
+    fn into_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as i32
+    }
+}
+
+impl ParameterValue for i64 {
+    type Atom = Long;
+
+    fn atom_urid(urids: &AtomURIDCollection) -> URID<Long> {
+        urids.long
+    }
+
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as i64
+    }
+}
+
+/// Description of a parameter.
+///
+/// This contains everything that is declared for a parameter: It's URI, the type of it's value, the default value and the range. The values are converted to `f64` to describe parameters of all types uniformly.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterInfo {
+    uri: &'static [u8],
+    value_type: &'static [u8],
+    default: f64,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+}
+
+impl ParameterInfo {
+    /// Create a new parameter description.
+    ///
+    /// The URI has to be null-terminated.
+    pub fn new<V: ParameterValue>(
+        uri: &'static [u8],
+        default: V,
+        minimum: Option<V>,
+        maximum: Option<V>,
+    ) -> Self {
+        Self {
+            uri,
+            value_type: <V::Atom as UriBound>::URI,
+            default: default.into_f64(),
+            minimum: minimum.map(ParameterValue::into_f64),
+            maximum: maximum.map(ParameterValue::into_f64),
+        }
+    }
+
+    /// Return the URI of the parameter.
+    pub fn uri(&self) -> &'static Uri {
+        Uri::from_bytes_with_nul(self.uri).unwrap()
+    }
+
+    /// Return the URI of the atom type of the parameter's value.
+    pub fn value_type(&self) -> &'static Uri {
+        Uri::from_bytes_with_nul(self.value_type).unwrap()
+    }
+
+    /// Return the default value of the parameter.
+    pub fn default(&self) -> f64 {
+        self.default
+    }
+
+    /// Return the minimal value of the parameter, if there is one.
+    pub fn minimum(&self) -> Option<f64> {
+        self.minimum
+    }
+
+    /// Return the maximal value of the parameter, if there is one.
+    pub fn maximum(&self) -> Option<f64> {
+        self.maximum
+    }
+}
+
+/// Something parameter values can be read from.
+///
+/// This is implemented for atoms, which are used by patch messages, and for state properties.
+pub trait ParameterSource {
+    /// Try to read a parameter value.
+    ///
+    /// This returns `None` if the source doesn't contain a value of the requested type.
+    fn read_value<V: ParameterValue>(&self, urids: &AtomURIDCollection) -> Option<V>;
+}
+
+impl<'a> ParameterSource for UnidentifiedAtom<'a> {
+    fn read_value<V: ParameterValue>(&self, urids: &AtomURIDCollection) -> Option<V> {
+        self.read(V::atom_urid(urids), ())
+    }
+}
+
+impl<'a> ParameterSource for StatePropertyReader<'a> {
+    fn read_value<V: ParameterValue>(&self, urids: &AtomURIDCollection) -> Option<V> {
+        self.read(V::atom_urid(urids), ()).ok()
+    }
+}
+
+/// Something parameter values can be written to.
+///
+/// Object writers write parameters as properties of the object and store handles draft a property for every parameter.
+pub trait ParameterSink {
+    /// Write the value of a parameter.
+    fn write_value<V: ParameterValue>(
+        &mut self,
+        key: URID,
+        value: V,
+        urids: &AtomURIDCollection,
+    ) -> Result<(), ParameterError>;
+}
+
+impl<'a, 'b> ParameterSink for ObjectWriter<'a, 'b> {
+    fn write_value<V: ParameterValue>(
+        &mut self,
+        key: URID,
+        value: V,
+        urids: &AtomURIDCollection,
+    ) -> Result<(), ParameterError> {
+        self.init(key, None, V::atom_urid(urids), value)
+            .map(|_| ())
+            .ok_or(ParameterError::NoSpace)
+    }
+}
+
+impl<'a> ParameterSink for StoreHandle<'a> {
+    fn write_value<V: ParameterValue>(
+        &mut self,
+        key: URID,
+        value: V,
+        urids: &AtomURIDCollection,
+    ) -> Result<(), ParameterError> {
+        self.draft(key)
+            .init(V::atom_urid(urids), value)
+            .map(|_| ())
+            .map_err(|_| ParameterError::NoSpace)
+    }
+}
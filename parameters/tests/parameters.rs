@@ -0,0 +1,293 @@
+extern crate lv2_atom as atom;
+extern crate lv2_parameters as parameters;
+extern crate lv2_patch as patch;
+extern crate lv2_state as state;
+extern crate lv2_units as units;
+
+use atom::object::ObjectHeader;
+use atom::prelude::*;
+use atom::space::*;
+use parameters::prelude::*;
+use patch::PatchURIDCollection;
+use state::Storage;
+use units::prelude::*;
+use urid::*;
+
+#[derive(ParameterCollection)]
+struct TestParameters {
+    #[parameter(uri = "urn:rust-lv2:parameters#gain", default = 0.0, min = -90.0, max = 24.0)]
+    gain: f32,
+    #[parameter(uri = "urn:rust-lv2:parameters#voices", default = 8)]
+    voices: i32,
+}
+
+#[derive(URIDCollection)]
+struct URIDs {
+    atom: AtomURIDCollection,
+    patch: PatchURIDCollection,
+    units: UnitURIDCollection,
+}
+
+fn write_set<'a, V: ParameterValue>(
+    space: &'a mut [u8],
+    urids: &URIDs,
+    property: URID,
+    value: V,
+) -> UnidentifiedAtom<'a> {
+    {
+        let mut space = RootMutSpace::new(space);
+        let mut writer = (&mut space as &mut dyn MutSpace)
+            .init(
+                urids.atom.object,
+                ObjectHeader {
+                    id: None,
+                    otype: urids.patch.set_class.into_general(),
+                },
+            )
+            .unwrap();
+        writer
+            .init(urids.patch.property, None, urids.atom.urid, property)
+            .unwrap();
+        writer
+            .init(urids.patch.value, None, V::atom_urid(&urids.atom), value)
+            .unwrap();
+    }
+    UnidentifiedAtom::new(Space::from_slice(space))
+}
+
+#[test]
+fn test_info() {
+    let infos = TestParameters::infos();
+    assert_eq!(infos.len(), 2);
+    assert_eq!(
+        infos[0].uri().to_str().unwrap(),
+        "urn:rust-lv2:parameters#gain"
+    );
+    assert_eq!(infos[0].value_type(), Float::uri());
+    assert_eq!(infos[0].default(), 0.0);
+    assert_eq!(infos[0].minimum(), Some(-90.0));
+    assert_eq!(infos[0].maximum(), Some(24.0));
+    assert_eq!(infos[1].value_type(), Int::uri());
+    assert_eq!(infos[1].default(), 8.0);
+    assert_eq!(infos[1].minimum(), None);
+}
+
+#[test]
+fn test_set_and_get() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+    let mut parameters: Parameters<TestParameters> = Parameters::new(&map).unwrap();
+    let gain = parameters.keys()[0];
+    let voices = parameters.keys()[1];
+
+    assert_eq!(parameters.gain, 0.0);
+    assert_eq!(parameters.voices, 8);
+
+    // Setting values.
+    let mut space: Box<[u8]> = Box::new([0; 256]);
+    let atom = write_set(space.as_mut(), &urids, gain, -6.0f32);
+    assert_eq!(parameters.handle(atom, None), Ok(true));
+    assert_eq!(parameters.gain, -6.0);
+
+    // Values are clamped to their range.
+    let atom = write_set(space.as_mut(), &urids, gain, 100.0f32);
+    assert_eq!(parameters.handle(atom, None), Ok(true));
+    assert_eq!(parameters.gain, 24.0);
+
+    // Values with the wrong type are rejected.
+    let atom = write_set(space.as_mut(), &urids, voices, 4.0f32);
+    assert_eq!(parameters.handle(atom, None), Err(ParameterError::BadValue));
+    assert_eq!(parameters.voices, 8);
+
+    // Unknown parameters are rejected.
+    let unknown = map.map_str("urn:rust-lv2:parameters#unknown").unwrap();
+    let atom = write_set(space.as_mut(), &urids, unknown, 4);
+    assert_eq!(
+        parameters.handle(atom, None),
+        Err(ParameterError::UnknownParameter)
+    );
+
+    // Other atoms are ignored.
+    {
+        let mut root = RootMutSpace::new(space.as_mut());
+        (&mut root as &mut dyn MutSpace)
+            .init(urids.atom.int, 42)
+            .unwrap();
+    }
+    let atom = UnidentifiedAtom::new(Space::from_slice(space.as_ref()));
+    assert_eq!(parameters.handle(atom, None), Ok(false));
+
+    // Requesting all values.
+    {
+        let mut root = RootMutSpace::new(space.as_mut());
+        (&mut root as &mut dyn MutSpace)
+            .init(
+                urids.atom.object,
+                ObjectHeader {
+                    id: None,
+                    otype: urids.patch.get_class.into_general(),
+                },
+            )
+            .unwrap();
+    }
+    let request = UnidentifiedAtom::new(Space::from_slice(space.as_ref()));
+    let mut response_space: Box<[u8]> = Box::new([0; 512]);
+    {
+        let mut root = RootMutSpace::new(response_space.as_mut());
+        let mut writer = (&mut root as &mut dyn MutSpace)
+            .init(
+                urids.atom.sequence,
+                TimeStampURID::Frames(urids.units.frame),
+            )
+            .unwrap();
+        assert_eq!(
+            parameters.handle(request, Some((TimeStamp::Frames(0), &mut writer))),
+            Ok(true)
+        );
+    }
+
+    let (sequence, _) = Space::from_slice(response_space.as_ref())
+        .split_atom_body(urids.atom.sequence)
+        .unwrap();
+    let mut responses = Sequence::read(sequence, urids.units.beat).unwrap();
+    for (key, check) in [gain, voices].iter().zip(&[24.0, 8.0]) {
+        let (_, atom) = responses.next().unwrap();
+        let (header, reader) = atom.read(urids.atom.object, ()).unwrap();
+        assert_eq!(header.otype, urids.patch.set_class);
+        let properties: Vec<(atom::object::PropertyHeader, UnidentifiedAtom)> = reader.collect();
+        assert_eq!(properties[0].0.key, urids.patch.property);
+        assert_eq!(properties[0].1.read(urids.atom.urid, ()).unwrap(), *key);
+        assert_eq!(properties[1].0.key, urids.patch.value);
+        let value = properties[1]
+            .1
+            .read(urids.atom.float, ())
+            .map(f64::from)
+            .or_else(|| properties[1].1.read(urids.atom.int, ()).map(f64::from))
+            .unwrap();
+        assert_eq!(value, *check);
+    }
+    assert!(responses.next().is_none());
+}
+
+#[test]
+fn test_control_change() {
+    let map = HashURIDMapper::new();
+    let control_change: URID<ControlChange> = map.map_type().unwrap();
+    let mut parameters: Parameters<TestParameters> = Parameters::new(&map).unwrap();
+    let gain = parameters.keys()[0];
+    let voices = parameters.keys()[1];
+
+    let mut space: Box<[u8]> = Box::new([0; 64]);
+    let mut handle = |change: ControlChange| {
+        {
+            let mut root = RootMutSpace::new(space.as_mut());
+            (&mut root as &mut dyn MutSpace)
+                .init(control_change, change)
+                .unwrap();
+        }
+        parameters.handle(
+            UnidentifiedAtom::new(Space::from_slice(space.as_ref())),
+            None,
+        )
+    };
+
+    assert_eq!(handle(ControlChange::new(gain, -6.0f32)), Ok(true));
+    // Values are converted to the type of the parameter and clamped to its range.
+    assert_eq!(handle(ControlChange::new(voices, 3.6f64)), Ok(true));
+    assert_eq!(handle(ControlChange::new(gain, 100.0f32)), Ok(true));
+    let unknown = map.map_str("urn:rust-lv2:parameters#unknown").unwrap();
+    assert_eq!(
+        handle(ControlChange::new(unknown, 1)),
+        Err(ParameterError::UnknownParameter)
+    );
+
+    assert_eq!(parameters.gain, 24.0);
+    assert_eq!(parameters.voices, 4);
+}
+
+#[test]
+fn test_state() {
+    let map = HashURIDMapper::new();
+    let mut parameters: Parameters<TestParameters> = Parameters::new(&map).unwrap();
+    parameters.gain = -12.0;
+    parameters.voices = 3;
+
+    let mut storage = Storage::default();
+    parameters.save(&mut storage.store_handle()).unwrap();
+
+    let mut restored: Parameters<TestParameters> = Parameters::new(&map).unwrap();
+    restored.restore(&storage.retrieve_handle()).unwrap();
+    assert_eq!(restored.gain, -12.0);
+    assert_eq!(restored.voices, 3);
+
+    // Missing properties keep their values.
+    let mut restored: Parameters<TestParameters> = Parameters::new(&map).unwrap();
+    restored
+        .restore(&Storage::default().retrieve_handle())
+        .unwrap();
+    assert_eq!(restored.gain, 0.0);
+    assert_eq!(restored.voices, 8);
+}
+
+#[test]
+fn test_automation_curve() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+    let automation_urids: AutomationURIDCollection = map.populate_collection().unwrap();
+    let gain = map.map_str("urn:rust-lv2:parameters#gain").unwrap();
+
+    let breakpoints = [
+        Breakpoint {
+            frame: 0,
+            value: 0.0,
+            curvature: 0.0,
+        },
+        Breakpoint {
+            frame: 100,
+            value: 1.0,
+            curvature: 4.0,
+        },
+        Breakpoint {
+            frame: 200,
+            value: 0.0,
+            curvature: 0.0,
+        },
+    ];
+
+    let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+    {
+        let mut space = RootMutSpace::new(raw_space.as_mut());
+        let mut writer = (&mut space as &mut dyn MutSpace)
+            .init(urids.atom.object, automation_urids.curve_header())
+            .unwrap();
+        AutomationCurve::write(
+            &mut writer,
+            gain,
+            &breakpoints,
+            &urids.atom,
+            &automation_urids,
+        )
+        .unwrap();
+    }
+
+    let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+    let curve = AutomationCurve::read(atom, &urids.atom, &automation_urids).unwrap();
+    assert_eq!(curve.parameter(), gain);
+    assert_eq!(curve.len(), 3);
+    assert!(curve.breakpoints().eq(breakpoints.iter().copied()));
+
+    // The first segment is straight, the second one starts slowly.
+    assert_eq!(curve.value_at(-10), Some(0.0));
+    assert_eq!(curve.value_at(50), Some(0.5));
+    assert_eq!(curve.value_at(100), Some(1.0));
+    let value = curve.value_at(150).unwrap();
+    assert!(value > 0.5 && value < 1.0);
+    assert_eq!(curve.value_at(1000), Some(0.0));
+
+    // Other objects aren't curves.
+    let atom = write_set(raw_space.as_mut(), &urids, gain, 1.0f32);
+    assert_eq!(
+        AutomationCurve::read(atom, &urids.atom, &automation_urids).unwrap_err(),
+        ParameterError::BadMessage
+    );
+}
@@ -0,0 +1,283 @@
+//! Messages to access and manipulate properties, as defined by the LV2 Patch specification.
+//!
+//! The [patch vocabulary](https://lv2plug.in/ns/ext/patch/patch.html) describes messages that are sent as object atoms between plugins, UIs and hosts. A `patch:Get` requests the value of a property or the whole description of a subject, a `patch:Set` sets a single property, a `patch:Put` replaces the description of a subject, a `patch:Patch` adds and removes properties and a `patch:Delete` deletes a subject.
+//!
+//! Incoming messages are read with [`Message::read`](enum.Message.html#method.read), which checks the type of the message and extracts its properties. Outgoing messages are written with a [`MessageWriter`](struct.MessageWriter.html), which wraps the writer of the object atom.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_atom::space::*;
+//! use lv2_patch::prelude::*;
+//! use urid::*;
+//!
+//! #[uri("urn:lv2_patch:gain")]
+//! struct Gain;
+//!
+//! #[derive(URIDCollection)]
+//! struct URIDs {
+//!     atom: AtomURIDCollection,
+//!     patch: PatchURIDCollection,
+//!     gain: URID<Gain>,
+//! }
+//!
+//! let map = HashURIDMapper::new();
+//! let urids: URIDs = map.populate_collection().unwrap();
+//! let mut space: Box<[u8]> = Box::new([0; 256]);
+//!
+//! // Write a `patch:Set` message.
+//! {
+//!     let mut root = RootMutSpace::new(space.as_mut());
+//!     let object = (&mut root as &mut dyn MutSpace)
+//!         .init(urids.atom.object, MessageType::Set.header(&urids.patch))
+//!         .unwrap();
+//!     let mut writer = MessageWriter::new(object, &urids.patch, &urids.atom);
+//!     writer.sequence_number(1).unwrap();
+//!     writer.property(urids.gain).unwrap();
+//!     writer.value(urids.atom.float, -6.0).unwrap();
+//! }
+//!
+//! // Read it again.
+//! let atom = UnidentifiedAtom::new(Space::from_slice(space.as_ref()));
+//! let (header, message) = Message::read(atom, &urids.patch, &urids.atom).unwrap();
+//! assert_eq!(header.sequence_number, Some(1));
+//! if let Message::Set { property, value } = message {
+//!     assert_eq!(property, urids.gain);
+//!     assert_eq!(value.read(urids.atom.float, ()), Some(-6.0));
+//! } else {
+//!     panic!("Not a set message!");
+//! }
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_sys as sys;
+
+mod message;
+pub use message::*;
+
+mod writer;
+pub use writer::*;
+
+use urid::*;
+
+/// All patch URI bounds
+///
+/// All Struct suffixed by `Class` are patch classes, others are patch properties.
+pub mod patch {
+    use urid::UriBound;
+
+    /// An acknowledgement that a request was successful.
+    pub struct AckClass;
+    unsafe impl UriBound for AckClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Ack;
+    }
+
+    /// A request to copy the subject to the destination.
+    pub struct CopyClass;
+    unsafe impl UriBound for CopyClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Copy;
+    }
+
+    /// A request to delete the subject.
+    pub struct DeleteClass;
+    unsafe impl UriBound for DeleteClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Delete;
+    }
+
+    /// A response that indicates that a request failed.
+    pub struct ErrorClass;
+    unsafe impl UriBound for ErrorClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Error;
+    }
+
+    /// A request for the description of the subject, or the value of a property.
+    pub struct GetClass;
+    unsafe impl UriBound for GetClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Get;
+    }
+
+    /// Any message that is sent to access or manipulate a subject.
+    pub struct MessageClass;
+    unsafe impl UriBound for MessageClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Message;
+    }
+
+    /// A request to move the subject to the destination.
+    pub struct MoveClass;
+    unsafe impl UriBound for MoveClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Move;
+    }
+
+    /// A request to add and remove properties of the subject.
+    pub struct PatchClass;
+    unsafe impl UriBound for PatchClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Patch;
+    }
+
+    /// A request to append the body to the subject.
+    pub struct PostClass;
+    unsafe impl UriBound for PostClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Post;
+    }
+
+    /// A request to replace the description of the subject with the body.
+    pub struct PutClass;
+    unsafe impl UriBound for PutClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Put;
+    }
+
+    /// A message that requests an action.
+    pub struct RequestClass;
+    unsafe impl UriBound for RequestClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Request;
+    }
+
+    /// A message that answers a request.
+    pub struct ResponseClass;
+    unsafe impl UriBound for ResponseClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Response;
+    }
+
+    /// A request to set a single property to a value.
+    pub struct SetClass;
+    unsafe impl UriBound for SetClass {
+        const URI: &'static [u8] = sys::LV2_PATCH__Set;
+    }
+
+    /// The type of the requested response.
+    pub struct Accept;
+    unsafe impl UriBound for Accept {
+        const URI: &'static [u8] = sys::LV2_PATCH__accept;
+    }
+
+    /// The properties that are added by a `patch:Patch`.
+    pub struct Add;
+    unsafe impl UriBound for Add {
+        const URI: &'static [u8] = sys::LV2_PATCH__add;
+    }
+
+    /// The body of a message, which describes the subject.
+    pub struct Body;
+    unsafe impl UriBound for Body {
+        const URI: &'static [u8] = sys::LV2_PATCH__body;
+    }
+
+    /// The context of properties in a message.
+    pub struct Context;
+    unsafe impl UriBound for Context {
+        const URI: &'static [u8] = sys::LV2_PATCH__context;
+    }
+
+    /// The destination to copy or move the subject to.
+    pub struct Destination;
+    unsafe impl UriBound for Destination {
+        const URI: &'static [u8] = sys::LV2_PATCH__destination;
+    }
+
+    /// A property that can be read by sending a `patch:Get`.
+    pub struct Readable;
+    unsafe impl UriBound for Readable {
+        const URI: &'static [u8] = sys::LV2_PATCH__readable;
+    }
+
+    /// The properties that are removed by a `patch:Patch`.
+    pub struct Remove;
+    unsafe impl UriBound for Remove {
+        const URI: &'static [u8] = sys::LV2_PATCH__remove;
+    }
+
+    /// The request a response answers.
+    pub struct Request;
+    unsafe impl UriBound for Request {
+        const URI: &'static [u8] = sys::LV2_PATCH__request;
+    }
+
+    /// The subject a message is about.
+    pub struct Subject;
+    unsafe impl UriBound for Subject {
+        const URI: &'static [u8] = sys::LV2_PATCH__subject;
+    }
+
+    /// The number of a message, which is used to associate responses with requests.
+    pub struct SequenceNumber;
+    unsafe impl UriBound for SequenceNumber {
+        const URI: &'static [u8] = sys::LV2_PATCH__sequenceNumber;
+    }
+
+    /// The property a `patch:Get` or `patch:Set` is about.
+    pub struct Property;
+    unsafe impl UriBound for Property {
+        const URI: &'static [u8] = sys::LV2_PATCH__property;
+    }
+
+    /// The value of a property in a `patch:Set`.
+    pub struct Value;
+    unsafe impl UriBound for Value {
+        const URI: &'static [u8] = sys::LV2_PATCH__value;
+    }
+
+    /// A placeholder for any value, used to remove all values of a property.
+    pub struct Wildcard;
+    unsafe impl UriBound for Wildcard {
+        const URI: &'static [u8] = sys::LV2_PATCH__wildcard;
+    }
+
+    /// A property that can be written by sending a `patch:Set`.
+    pub struct Writable;
+    unsafe impl UriBound for Writable {
+        const URI: &'static [u8] = sys::LV2_PATCH__writable;
+    }
+}
+
+use patch::*;
+
+/// A URID cache containing all patch classes and properties.
+#[derive(URIDCollection)]
+pub struct PatchURIDCollection {
+    pub ack_class: URID<AckClass>,
+    pub copy_class: URID<CopyClass>,
+    pub delete_class: URID<DeleteClass>,
+    pub error_class: URID<ErrorClass>,
+    pub get_class: URID<GetClass>,
+    pub message_class: URID<MessageClass>,
+    pub move_class: URID<MoveClass>,
+    pub patch_class: URID<PatchClass>,
+    pub post_class: URID<PostClass>,
+    pub put_class: URID<PutClass>,
+    pub request_class: URID<RequestClass>,
+    pub response_class: URID<ResponseClass>,
+    pub set_class: URID<SetClass>,
+    pub accept: URID<Accept>,
+    pub add: URID<Add>,
+    pub body: URID<Body>,
+    pub context: URID<Context>,
+    pub destination: URID<Destination>,
+    pub property: URID<Property>,
+    pub readable: URID<Readable>,
+    pub remove: URID<Remove>,
+    pub request: URID<Request>,
+    pub sequence_number: URID<SequenceNumber>,
+    pub subject: URID<Subject>,
+    pub value: URID<Value>,
+    pub wildcard: URID<Wildcard>,
+    pub writable: URID<Writable>,
+}
+
+/// Kinds of errors that may occur while reading a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatchError {
+    /// The atom isn't an object, or its type isn't a supported message type.
+    NotAMessage,
+    /// A property that is required by the message type is missing.
+    MissingProperty,
+    /// A property of the message has the wrong type.
+    BadProperty,
+}
+
+/// Prelude of `lv2_patch` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        Message, MessageEvents, MessageHeader, MessageType, MessageWriter, PatchError,
+        PatchURIDCollection,
+    };
+}
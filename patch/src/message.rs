@@ -0,0 +1,224 @@
+use crate::PatchError;
+use crate::PatchURIDCollection;
+use atom::object::{ObjectHeader, ObjectReader};
+use atom::prelude::*;
+use atom::sequence::EventKind;
+use urid::*;
+
+/// The types of messages this crate can read and write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageType {
+    /// A `patch:Get` message.
+    Get,
+    /// A `patch:Set` message.
+    Set,
+    /// A `patch:Put` message.
+    Put,
+    /// A `patch:Patch` message.
+    Patch,
+    /// A `patch:Delete` message.
+    Delete,
+}
+
+impl MessageType {
+    /// Identify the type of a message by the type of its object.
+    pub fn from_urid(urid: URID, urids: &PatchURIDCollection) -> Option<Self> {
+        if urid == urids.get_class {
+            Some(MessageType::Get)
+        } else if urid == urids.set_class {
+            Some(MessageType::Set)
+        } else if urid == urids.put_class {
+            Some(MessageType::Put)
+        } else if urid == urids.patch_class {
+            Some(MessageType::Patch)
+        } else if urid == urids.delete_class {
+            Some(MessageType::Delete)
+        } else {
+            None
+        }
+    }
+
+    /// Return the URID of the message class.
+    pub fn urid(self, urids: &PatchURIDCollection) -> URID {
+        match self {
+            MessageType::Get => urids.get_class.into_general(),
+            MessageType::Set => urids.set_class.into_general(),
+            MessageType::Put => urids.put_class.into_general(),
+            MessageType::Patch => urids.patch_class.into_general(),
+            MessageType::Delete => urids.delete_class.into_general(),
+        }
+    }
+
+    /// Return the header of an object atom containing a message of this type.
+    pub fn header(self, urids: &PatchURIDCollection) -> ObjectHeader {
+        ObjectHeader {
+            id: None,
+            otype: self.urid(urids),
+        }
+    }
+}
+
+/// Properties that every message may have.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MessageHeader {
+    /// The subject the message is about.
+    ///
+    /// If there is none, the message is about the receiver itself, for example the plugin instance.
+    pub subject: Option<URID>,
+    /// The sequence number of the message.
+    ///
+    /// Responses carry the sequence number of the request they answer.
+    pub sequence_number: Option<i32>,
+    /// The type of the response the sender accepts.
+    pub accept: Option<URID>,
+}
+
+/// A message of the patch vocabulary.
+///
+/// The properties that every message may have are stored in the [`MessageHeader`](struct.MessageHeader.html) that is returned by [`read`](#method.read) alongside the message.
+pub enum Message<'a> {
+    /// A request for the value of a property, or for the description of the subject if the property is `None`.
+    Get { property: Option<URID> },
+    /// A request to set a single property to a value.
+    Set {
+        property: URID,
+        value: UnidentifiedAtom<'a>,
+    },
+    /// A request to replace the description of the subject with the properties in the body.
+    Put { body: ObjectReader<'a> },
+    /// A request to remove the properties in `remove` and to add the properties in `add`.
+    ///
+    /// Values of removed properties may be a `patch:wildcard`, which means that all values of the property are removed.
+    Patch {
+        add: Option<ObjectReader<'a>>,
+        remove: Option<ObjectReader<'a>>,
+    },
+    /// A request to delete the subject.
+    Delete,
+}
+
+impl<'a> Message<'a> {
+    /// Try to read a message from an atom.
+    ///
+    /// The atom has to be an object or a blank with one of the supported message types, or `PatchError::NotAMessage` is returned. Unknown properties of the message are ignored.
+    pub fn read(
+        atom: UnidentifiedAtom<'a>,
+        urids: &PatchURIDCollection,
+        atom_urids: &AtomURIDCollection,
+    ) -> Result<(MessageHeader, Self), PatchError> {
+        let (object_header, reader) = atom
+            .read(atom_urids.object, ())
+            .or_else(|| atom.read(atom_urids.blank, ()))
+            .ok_or(PatchError::NotAMessage)?;
+        let message_type =
+            MessageType::from_urid(object_header.otype, urids).ok_or(PatchError::NotAMessage)?;
+
+        let mut header = MessageHeader::default();
+        let mut property = None;
+        let mut value = None;
+        let mut body = None;
+        let mut add = None;
+        let mut remove = None;
+
+        let read_urid = |atom: UnidentifiedAtom| {
+            atom.read(atom_urids.urid, ())
+                .ok_or(PatchError::BadProperty)
+        };
+        let read_object = |atom: UnidentifiedAtom<'a>| {
+            ObjectReader::from_atom(atom, atom_urids).ok_or(PatchError::BadProperty)
+        };
+
+        for (property_header, atom) in reader {
+            let key = property_header.key;
+            if key == urids.subject {
+                header.subject = Some(read_urid(atom)?);
+            } else if key == urids.sequence_number {
+                header.sequence_number = Some(
+                    atom.read(atom_urids.int, ())
+                        .ok_or(PatchError::BadProperty)?,
+                );
+            } else if key == urids.accept {
+                header.accept = Some(read_urid(atom)?);
+            } else if key == urids.property {
+                property = Some(read_urid(atom)?);
+            } else if key == urids.value {
+                value = Some(atom);
+            } else if key == urids.body {
+                body = Some(read_object(atom)?);
+            } else if key == urids.add {
+                add = Some(read_object(atom)?);
+            } else if key == urids.remove {
+                remove = Some(read_object(atom)?);
+            }
+        }
+
+        let message = match message_type {
+            MessageType::Get => Message::Get { property },
+            MessageType::Set => Message::Set {
+                property: property.ok_or(PatchError::MissingProperty)?,
+                value: value.ok_or(PatchError::MissingProperty)?,
+            },
+            MessageType::Put => Message::Put {
+                body: body.ok_or(PatchError::MissingProperty)?,
+            },
+            MessageType::Patch => Message::Patch { add, remove },
+            MessageType::Delete => Message::Delete,
+        };
+        Ok((header, message))
+    }
+
+    /// Return the type of the message.
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            Message::Get { .. } => MessageType::Get,
+            Message::Set { .. } => MessageType::Set,
+            Message::Put { .. } => MessageType::Put,
+            Message::Patch { .. } => MessageType::Patch,
+            Message::Delete => MessageType::Delete,
+        }
+    }
+}
+
+/// The patch messages in a sequence, as an event kind.
+///
+/// This can be used to pick the messages out of a sequence with [`of_type`](../lv2_atom/sequence/struct.SequenceIterator.html#method.of_type), or to tell them apart from other events, like MIDI events, with [`split`](../lv2_atom/sequence/struct.SequenceIterator.html#method.split):
+///
+/// ```
+/// # use lv2_atom::prelude::*;
+/// # use lv2_atom::sequence::*;
+/// # use lv2_patch::prelude::*;
+/// fn handle_events(
+///     input_sequence: SequenceIterator,
+///     urids: &PatchURIDCollection,
+///     atom_urids: &AtomURIDCollection,
+/// ) {
+///     let messages = MessageEvents::new(urids, atom_urids);
+///     for (timestamp, event) in input_sequence.split(messages, atom_urids.int) {
+///         match event {
+///             SplitEvent::First((header, message)) => (),
+///             SplitEvent::Second(integer) => (),
+///             SplitEvent::Other(atom) => (),
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct MessageEvents<'u> {
+    urids: &'u PatchURIDCollection,
+    atom_urids: &'u AtomURIDCollection,
+}
+
+impl<'u> MessageEvents<'u> {
+    /// Create the event kind from the URIDs that are needed to read messages.
+    pub fn new(urids: &'u PatchURIDCollection, atom_urids: &'u AtomURIDCollection) -> Self {
+        Self { urids, atom_urids }
+    }
+}
+
+impl<'a, 'u> EventKind<'a> for MessageEvents<'u> {
+    type Item = (MessageHeader, Message<'a>);
+
+    fn read_event(&self, atom: UnidentifiedAtom<'a>) -> Option<(MessageHeader, Message<'a>)> {
+        Message::read(atom, self.urids, self.atom_urids).ok()
+    }
+}
@@ -0,0 +1,114 @@
+use crate::MessageHeader;
+use crate::PatchURIDCollection;
+use atom::object::{ObjectHeader, ObjectWriter};
+use atom::prelude::*;
+use urid::*;
+
+/// Writing handle for patch messages.
+///
+/// A message is an object atom, so it is initialized like any other object, with a header returned by [`MessageType::header`](enum.MessageType.html#method.header). This handle then wraps the object writer and writes the properties of the message.
+///
+/// Which properties are required depends on the type of the message: A `patch:Set` needs a [`property`](#method.property) and a [`value`](#method.value), a `patch:Put` needs a [`body`](#method.body) and a `patch:Patch` should have both [`add`](#method.add) and [`remove`](#method.remove). All methods return `None` if there isn't enough space left.
+pub struct MessageWriter<'a, 'b, 'c> {
+    object: ObjectWriter<'a, 'b>,
+    urids: &'c PatchURIDCollection,
+    atom_urids: &'c AtomURIDCollection,
+}
+
+impl<'a, 'b, 'c> MessageWriter<'a, 'b, 'c> {
+    /// Create a new message writer from the writer of an object.
+    pub fn new(
+        object: ObjectWriter<'a, 'b>,
+        urids: &'c PatchURIDCollection,
+        atom_urids: &'c AtomURIDCollection,
+    ) -> Self {
+        Self {
+            object,
+            urids,
+            atom_urids,
+        }
+    }
+
+    /// Write all properties of a message header.
+    pub fn header(&mut self, header: &MessageHeader) -> Option<()> {
+        if let Some(subject) = header.subject {
+            self.subject(subject)?;
+        }
+        if let Some(sequence_number) = header.sequence_number {
+            self.sequence_number(sequence_number)?;
+        }
+        if let Some(accept) = header.accept {
+            self.accept(accept)?;
+        }
+        Some(())
+    }
+
+    /// Write the subject of the message.
+    pub fn subject<K: ?Sized>(&mut self, subject: URID<K>) -> Option<()> {
+        self.write_urid(self.urids.subject.into_general(), subject.into_general())
+    }
+
+    /// Write the sequence number of the message.
+    pub fn sequence_number(&mut self, number: i32) -> Option<()> {
+        self.object
+            .init(
+                self.urids.sequence_number,
+                None,
+                self.atom_urids.int,
+                number,
+            )
+            .map(|_| ())
+    }
+
+    /// Write the type of the response the sender accepts.
+    pub fn accept<K: ?Sized>(&mut self, response_type: URID<K>) -> Option<()> {
+        self.write_urid(
+            self.urids.accept.into_general(),
+            response_type.into_general(),
+        )
+    }
+
+    /// Write the property of a `patch:Get` or `patch:Set` message.
+    pub fn property<K: ?Sized>(&mut self, property: URID<K>) -> Option<()> {
+        self.write_urid(self.urids.property.into_general(), property.into_general())
+    }
+
+    /// Initialize the value of a `patch:Set` message.
+    ///
+    /// The returned handle is the writing handle of the value atom.
+    pub fn value<'d, A: Atom<'a, 'd>>(
+        &'d mut self,
+        urid: URID<A>,
+        parameter: A::WriteParameter,
+    ) -> Option<A::WriteHandle> {
+        self.object.init(self.urids.value, None, urid, parameter)
+    }
+
+    /// Initialize the body of a `patch:Put` message.
+    ///
+    /// The body is an object that describes the subject. Its type is usually the type of the subject.
+    pub fn body<'d>(&'d mut self, header: ObjectHeader) -> Option<ObjectWriter<'a, 'd>> {
+        self.object
+            .init(self.urids.body, None, self.atom_urids.object, header)
+    }
+
+    /// Initialize the properties that are added by a `patch:Patch` message.
+    pub fn add<'d>(&'d mut self, header: ObjectHeader) -> Option<ObjectWriter<'a, 'd>> {
+        self.object
+            .init(self.urids.add, None, self.atom_urids.object, header)
+    }
+
+    /// Initialize the properties that are removed by a `patch:Patch` message.
+    ///
+    /// Write a `patch:wildcard` URID as the value of a property to remove all of its values.
+    pub fn remove<'d>(&'d mut self, header: ObjectHeader) -> Option<ObjectWriter<'a, 'd>> {
+        self.object
+            .init(self.urids.remove, None, self.atom_urids.object, header)
+    }
+
+    fn write_urid(&mut self, key: URID, value: URID) -> Option<()> {
+        self.object
+            .init(key, None, self.atom_urids.urid, value)
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,287 @@
+extern crate lv2_atom as atom;
+extern crate lv2_patch as patch;
+
+use atom::object::{ObjectHeader, PropertyHeader};
+use atom::prelude::*;
+use atom::sequence::*;
+use atom::space::*;
+use lv2_units::prelude::*;
+use patch::prelude::*;
+use urid::*;
+
+#[uri("urn:rust-lv2:patch#sample")]
+struct Sample;
+
+#[uri("urn:rust-lv2:patch#gain")]
+struct Gain;
+
+#[derive(URIDCollection)]
+struct URIDs {
+    atom: AtomURIDCollection,
+    patch: PatchURIDCollection,
+    units: UnitURIDCollection,
+    sample: URID<Sample>,
+    gain: URID<Gain>,
+}
+
+fn write_message<F>(space: &mut [u8], urids: &URIDs, message_type: MessageType, f: F)
+where
+    F: for<'a, 'b, 'c> FnOnce(&mut MessageWriter<'a, 'b, 'c>),
+{
+    let mut root = RootMutSpace::new(space);
+    let object = (&mut root as &mut dyn MutSpace)
+        .init(urids.atom.object, message_type.header(&urids.patch))
+        .unwrap();
+    let mut writer = MessageWriter::new(object, &urids.patch, &urids.atom);
+    f(&mut writer);
+}
+
+fn read_message<'a>(
+    space: &'a [u8],
+    urids: &URIDs,
+) -> Result<(MessageHeader, Message<'a>), PatchError> {
+    let atom = UnidentifiedAtom::new(Space::from_slice(space));
+    Message::read(atom, &urids.patch, &urids.atom)
+}
+
+#[test]
+fn test_get() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+    let mut space: Box<[u8]> = Box::new([0; 256]);
+
+    let header = MessageHeader {
+        subject: Some(urids.sample.into_general()),
+        sequence_number: Some(42),
+        accept: Some(urids.patch.set_class.into_general()),
+    };
+    write_message(space.as_mut(), &urids, MessageType::Get, |writer| {
+        writer.header(&header).unwrap();
+        writer.property(urids.gain).unwrap();
+    });
+
+    let (read_header, message) = read_message(space.as_ref(), &urids).unwrap();
+    assert_eq!(read_header, header);
+    assert_eq!(message.message_type(), MessageType::Get);
+    match message {
+        Message::Get { property } => assert_eq!(property, Some(urids.gain.into_general())),
+        _ => panic!("Not a get message!"),
+    }
+
+    // A get message without a property requests the whole description.
+    write_message(space.as_mut(), &urids, MessageType::Get, |_| ());
+    let (read_header, message) = read_message(space.as_ref(), &urids).unwrap();
+    assert_eq!(read_header, MessageHeader::default());
+    match message {
+        Message::Get { property } => assert_eq!(property, None),
+        _ => panic!("Not a get message!"),
+    }
+}
+
+#[test]
+fn test_set() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+    let mut space: Box<[u8]> = Box::new([0; 256]);
+
+    write_message(space.as_mut(), &urids, MessageType::Set, |writer| {
+        writer.property(urids.gain).unwrap();
+        writer.value(urids.atom.float, 0.5).unwrap();
+    });
+    match read_message(space.as_ref(), &urids).unwrap().1 {
+        Message::Set { property, value } => {
+            assert_eq!(property, urids.gain);
+            assert_eq!(value.read(urids.atom.float, ()), Some(0.5));
+        }
+        _ => panic!("Not a set message!"),
+    }
+
+    // The value is required.
+    write_message(space.as_mut(), &urids, MessageType::Set, |writer| {
+        writer.property(urids.gain).unwrap();
+    });
+    assert_eq!(
+        read_message(space.as_ref(), &urids).err(),
+        Some(PatchError::MissingProperty)
+    );
+}
+
+#[test]
+fn test_put_and_patch() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+    let mut space: Box<[u8]> = Box::new([0; 256]);
+    let body_header = || ObjectHeader {
+        id: None,
+        otype: urids.sample.into_general(),
+    };
+
+    write_message(space.as_mut(), &urids, MessageType::Put, |writer| {
+        writer.subject(urids.sample).unwrap();
+        let mut body = writer.body(body_header()).unwrap();
+        body.init(urids.gain, None, urids.atom.float, 0.25).unwrap();
+    });
+    match read_message(space.as_ref(), &urids).unwrap() {
+        (header, Message::Put { body }) => {
+            assert_eq!(header.subject, Some(urids.sample.into_general()));
+            let properties: Vec<(PropertyHeader, UnidentifiedAtom)> = body.collect();
+            assert_eq!(properties.len(), 1);
+            assert_eq!(properties[0].0.key, urids.gain);
+            assert_eq!(properties[0].1.read(urids.atom.float, ()), Some(0.25));
+        }
+        _ => panic!("Not a put message!"),
+    }
+
+    write_message(space.as_mut(), &urids, MessageType::Patch, |writer| {
+        writer
+            .remove(body_header())
+            .unwrap()
+            .init(
+                urids.gain,
+                None,
+                urids.atom.urid,
+                urids.patch.wildcard.into_general(),
+            )
+            .unwrap();
+        writer
+            .add(body_header())
+            .unwrap()
+            .init(urids.gain, None, urids.atom.float, 1.0)
+            .unwrap();
+    });
+    match read_message(space.as_ref(), &urids).unwrap().1 {
+        Message::Patch {
+            add: Some(add),
+            remove: Some(remove),
+        } => {
+            let (header, value) = remove.into_iter().next().unwrap();
+            assert_eq!(header.key, urids.gain);
+            assert_eq!(
+                value.read(urids.atom.urid, ()),
+                Some(urids.patch.wildcard.into_general())
+            );
+            let (header, value) = add.into_iter().next().unwrap();
+            assert_eq!(header.key, urids.gain);
+            assert_eq!(value.read(urids.atom.float, ()), Some(1.0));
+        }
+        _ => panic!("Not a complete patch message!"),
+    }
+}
+
+#[test]
+fn test_delete_and_errors() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+    let mut space: Box<[u8]> = Box::new([0; 256]);
+
+    write_message(space.as_mut(), &urids, MessageType::Delete, |writer| {
+        writer.subject(urids.sample).unwrap();
+    });
+    match read_message(space.as_ref(), &urids).unwrap() {
+        (header, Message::Delete) => {
+            assert_eq!(header.subject, Some(urids.sample.into_general()))
+        }
+        _ => panic!("Not a delete message!"),
+    }
+
+    // Properties with the wrong type are rejected.
+    {
+        let mut root = RootMutSpace::new(space.as_mut());
+        let mut object = (&mut root as &mut dyn MutSpace)
+            .init(urids.atom.object, MessageType::Get.header(&urids.patch))
+            .unwrap();
+        object
+            .init(urids.patch.property, None, urids.atom.int, 17)
+            .unwrap();
+    }
+    assert_eq!(
+        read_message(space.as_ref(), &urids).err(),
+        Some(PatchError::BadProperty)
+    );
+
+    // Other objects and atoms aren't messages.
+    {
+        let mut root = RootMutSpace::new(space.as_mut());
+        (&mut root as &mut dyn MutSpace)
+            .init(
+                urids.atom.object,
+                ObjectHeader {
+                    id: None,
+                    otype: urids.sample.into_general(),
+                },
+            )
+            .unwrap();
+    }
+    assert_eq!(
+        read_message(space.as_ref(), &urids).err(),
+        Some(PatchError::NotAMessage)
+    );
+    {
+        let mut root = RootMutSpace::new(space.as_mut());
+        (&mut root as &mut dyn MutSpace)
+            .init(urids.atom.int, 42)
+            .unwrap();
+    }
+    assert_eq!(
+        read_message(space.as_ref(), &urids).err(),
+        Some(PatchError::NotAMessage)
+    );
+}
+
+#[test]
+fn test_message_events() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+    let mut space: Box<[u8]> = Box::new([0; 256]);
+
+    {
+        let mut root = RootMutSpace::new(space.as_mut());
+        let mut sequence = (&mut root as &mut dyn MutSpace)
+            .init(
+                urids.atom.sequence,
+                TimeStampURID::Frames(urids.units.frame),
+            )
+            .unwrap();
+        sequence
+            .init(TimeStamp::Frames(0), urids.atom.int, 42)
+            .unwrap();
+        let object = sequence
+            .init(
+                TimeStamp::Frames(4),
+                urids.atom.object,
+                MessageType::Set.header(&urids.patch),
+            )
+            .unwrap();
+        let mut writer = MessageWriter::new(object, &urids.patch, &urids.atom);
+        writer.property(urids.gain).unwrap();
+        writer.value(urids.atom.float, 0.5).unwrap();
+    }
+
+    let (body, _) = Space::from_slice(space.as_ref())
+        .split_atom_body(urids.atom.sequence)
+        .unwrap();
+    let messages = MessageEvents::new(&urids.patch, &urids.atom);
+
+    let mut events = Sequence::read(body, urids.units.beat)
+        .unwrap()
+        .of_type(messages);
+    let (stamp, (_, message)) = events.next().unwrap();
+    assert_eq!(stamp.as_frames(), Some(4));
+    assert_eq!(message.message_type(), MessageType::Set);
+    assert!(events.next().is_none());
+
+    let mut events = Sequence::read(body, urids.units.beat)
+        .unwrap()
+        .split(urids.atom.int, messages);
+    match events.next().unwrap().1 {
+        SplitEvent::First(integer) => assert_eq!(integer, 42),
+        _ => panic!("Not an integer!"),
+    }
+    match events.next().unwrap().1 {
+        SplitEvent::Second((_, Message::Set { property, .. })) => {
+            assert_eq!(property, urids.gain)
+        }
+        _ => panic!("Not a set message!"),
+    }
+    assert!(events.next().is_none());
+}
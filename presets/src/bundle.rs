@@ -0,0 +1,75 @@
+use crate::turtle::*;
+use crate::{file_iri, iri_path, Preset, PresetError};
+use std::fs;
+use std::path::Path;
+use urid::uri_str;
+
+/// The presets of a bundle.
+///
+/// Presets are declared in the manifest of a bundle, which usually only contains the type of the preset, the plugin it applies to and a `rdfs:seeAlso` reference to the file with the actual preset data. The bundle is either the bundle of the plugin itself, which contains the factory presets, or a separate bundle with presets saved by the user.
+///
+/// A plugin can find its factory presets with the bundle path from its [`PluginInfo`](../lv2_core/plugin/struct.PluginInfo.html#method.bundle_path).
+pub struct Bundle {
+    presets: Vec<Preset>,
+}
+
+impl Bundle {
+    /// Load the presets of the bundle at the given path.
+    ///
+    /// This parses the manifest of the bundle and all files that are referenced by the presets declared in it. Referenced files outside of the local file system are ignored.
+    pub fn load(path: &Path) -> Result<Self, PresetError> {
+        let mut graph = Graph::new();
+        let manifest_path = path.join("manifest.ttl");
+        let manifest_iri = file_iri(&manifest_path)?;
+        graph.parse(&fs::read_to_string(&manifest_path)?, &manifest_iri)?;
+
+        let preset_class = Term::iri(uri_str(sys::LV2_PRESETS__Preset));
+        let mut uris: Vec<String> = Vec::new();
+        for subject in graph.subjects(RDF_TYPE, &preset_class) {
+            if let Some(uri) = subject.as_iri() {
+                if !uris.iter().any(|known| known == uri) {
+                    uris.push(uri.to_owned());
+                }
+            }
+        }
+
+        let mut files: Vec<String> = Vec::new();
+        for uri in uris.iter() {
+            for file in graph.objects(&Term::iri(uri), RDFS_SEE_ALSO) {
+                if let Some(file) = file.as_iri() {
+                    if file != manifest_iri && !files.iter().any(|known| known == file) {
+                        files.push(file.to_owned());
+                    }
+                }
+            }
+        }
+        for file in files.iter() {
+            if let Some(file_path) = iri_path(file) {
+                graph.parse(&fs::read_to_string(file_path)?, file)?;
+            }
+        }
+
+        let presets = uris
+            .iter()
+            .map(|uri| Preset::from_graph(&graph, uri))
+            .collect::<Result<Vec<Preset>, PresetError>>()?;
+        Ok(Self { presets })
+    }
+
+    /// All presets of the bundle.
+    pub fn presets(&self) -> &[Preset] {
+        self.presets.as_ref()
+    }
+
+    /// Iterate over the presets that apply to the given plugin.
+    pub fn presets_for<'a>(&'a self, plugin: &'a str) -> impl Iterator<Item = &'a Preset> + 'a {
+        self.presets
+            .iter()
+            .filter(move |preset| preset.plugin() == Some(plugin))
+    }
+
+    /// Find the preset with the given URI.
+    pub fn preset(&self, uri: &str) -> Option<&Preset> {
+        self.presets.iter().find(|preset| preset.uri() == uri)
+    }
+}
@@ -0,0 +1,161 @@
+//! Presets for LV2 plugins, as defined by the LV2 Presets specification.
+//!
+//! The [presets extension](https://lv2plug.in/ns/ext/presets/presets.html) describes presets as resources of a bundle: A preset has a label, the values of the plugin's input ports and optionally the state of the plugin. Factory presets are shipped in the bundle of the plugin, while presets saved by the user live in bundles of their own.
+//!
+//! The presets of a bundle are loaded with [`Bundle::load`](struct.Bundle.html#method.load). The port values of a [`Preset`](struct.Preset.html) are applied by the host and its state is restored with [`Preset::restore`](struct.Preset.html#method.restore), which calls the plugin's [`State`](../lv2_state/trait.State.html) implementation. New presets are created with a [`PresetBuilder`](struct.PresetBuilder.html), which can also save the current state of a plugin, and are written to a bundle with [`Preset::save`](struct.Preset.html#method.save).
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_presets::*;
+//!
+//! let bundle_path = std::env::temp_dir().join("lv2_presets_example.lv2");
+//! # std::fs::remove_dir_all(&bundle_path).ok();
+//!
+//! // Save a preset, for example when the user clicks "Save preset".
+//! PresetBuilder::new("urn:lv2_presets:loud", "urn:lv2_presets:amp")
+//!     .label("Loud")
+//!     .port_value("gain", 12.0)
+//!     .build()
+//!     .save(&bundle_path, "loud.ttl")
+//!     .unwrap();
+//!
+//! // Load it again.
+//! let bundle = Bundle::load(&bundle_path).unwrap();
+//! let preset = bundle.presets_for("urn:lv2_presets:amp").next().unwrap();
+//! assert_eq!(preset.label(), Some("Loud"));
+//! assert_eq!(preset.port_value("gain"), Some(12.0));
+//! # std::fs::remove_dir_all(&bundle_path).unwrap();
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_state as state;
+extern crate lv2_sys as sys;
+
+mod bundle;
+pub use bundle::*;
+
+mod preset;
+pub use preset::*;
+
+pub mod turtle;
+
+use state::StateErr;
+use std::path::Path;
+use turtle::TurtleError;
+use urid::*;
+
+pub(crate) use turtle::{encode_iri_path, iri_path};
+
+/// All presets URI bounds
+///
+/// All Struct suffixed by `Class` are preset classes, others are preset properties.
+pub mod pset {
+    use urid::UriBound;
+
+    /// A bank of presets.
+    pub struct BankClass;
+    unsafe impl UriBound for BankClass {
+        const URI: &'static [u8] = sys::LV2_PRESETS__Bank;
+    }
+
+    /// A preset of a plugin.
+    pub struct PresetClass;
+    unsafe impl UriBound for PresetClass {
+        const URI: &'static [u8] = sys::LV2_PRESETS__Preset;
+    }
+
+    /// The bank a preset belongs to.
+    pub struct Bank;
+    unsafe impl UriBound for Bank {
+        const URI: &'static [u8] = sys::LV2_PRESETS__bank;
+    }
+
+    /// The preset that is currently applied to a plugin.
+    pub struct Preset;
+    unsafe impl UriBound for Preset {
+        const URI: &'static [u8] = sys::LV2_PRESETS__preset;
+    }
+
+    /// The value of a port in a preset.
+    pub struct Value;
+    unsafe impl UriBound for Value {
+        const URI: &'static [u8] = sys::LV2_PRESETS__value;
+    }
+}
+
+/// A URID cache containing all preset classes and properties.
+#[derive(URIDCollection)]
+pub struct PresetURIDCollection {
+    pub bank_class: URID<pset::BankClass>,
+    pub preset_class: URID<pset::PresetClass>,
+    pub bank: URID<pset::Bank>,
+    pub preset: URID<pset::Preset>,
+    pub value: URID<pset::Value>,
+}
+
+/// Errors that may occur when loading, saving or applying presets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresetError {
+    /// Reading or writing a file failed.
+    Io(std::io::ErrorKind),
+    /// A Turtle document is malformed.
+    Turtle(TurtleError),
+    /// The resource isn't declared as a preset.
+    NotAPreset,
+    /// A port value of the preset doesn't have a symbol or a numeric value.
+    BadPortValue,
+    /// A state property has a value that can't be converted.
+    UnsupportedValue,
+    /// A URI couldn't be mapped, or a URID couldn't be unmapped.
+    BadUri,
+    /// The plugin failed to save or restore its state.
+    State(StateErr),
+}
+
+impl From<std::io::Error> for PresetError {
+    fn from(error: std::io::Error) -> Self {
+        PresetError::Io(error.kind())
+    }
+}
+
+impl From<TurtleError> for PresetError {
+    fn from(error: TurtleError) -> Self {
+        PresetError::Turtle(error)
+    }
+}
+
+/// Create the `file:` IRI of a path.
+///
+/// Relative paths are resolved against the current working directory.
+pub(crate) fn file_iri(path: &Path) -> Result<String, PresetError> {
+    let path = if path.is_absolute() {
+        path.to_owned()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+    turtle::file_iri(&path).ok_or(PresetError::BadUri)
+}
+
+/// Prelude of `lv2_presets` for wildcard usage.
+pub mod prelude {
+    pub use crate::{Bundle, Preset, PresetBuilder, PresetError, PresetURIDCollection};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_file_iri() {
+        let path = Path::new("/home/user/my presets.lv2/ä.ttl");
+        let iri = file_iri(path).unwrap();
+        assert_eq!(iri, "file:///home/user/my%20presets.lv2/%C3%A4.ttl");
+        assert_eq!(iri_path(&iri), Some(path.to_owned()));
+        assert_eq!(
+            iri_path("file://localhost/home/user/a.ttl#preset"),
+            Some(PathBuf::from("/home/user/a.ttl"))
+        );
+        assert_eq!(iri_path("http://example.org/a.ttl"), None);
+    }
+}
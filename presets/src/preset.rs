@@ -0,0 +1,490 @@
+use crate::turtle::*;
+use crate::{encode_iri_path, PresetError};
+use atom::AtomURIDCollection;
+use state::{State, Storage};
+use std::convert::TryFrom;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::Path;
+use std::str::FromStr;
+use urid::*;
+
+/// A preset of a plugin.
+///
+/// A preset is a named set of port values and state properties, which is applied to a plugin to bring it into a certain configuration. Presets are either loaded from a [`Bundle`](struct.Bundle.html) or created with a [`PresetBuilder`](struct.PresetBuilder.html).
+///
+/// The port values of a preset are applied by the host, since plugins can't change the values of their own input ports, but the state properties are restored with the plugin's [`State`](../lv2_state/trait.State.html) implementation, using [`restore`](#method.restore).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preset {
+    uri: String,
+    plugin: Option<String>,
+    label: Option<String>,
+    bank: Option<String>,
+    port_values: Vec<(String, f32)>,
+    state: Vec<(String, Term)>,
+}
+
+impl Preset {
+    /// Read the preset with the given URI from a graph.
+    ///
+    /// This fails if the resource isn't declared as a preset or one of its port values is malformed.
+    pub fn from_graph(graph: &Graph, uri: &str) -> Result<Self, PresetError> {
+        let subject = Term::iri(uri);
+        let preset_class = Term::iri(uri_str(sys::LV2_PRESETS__Preset));
+        if !graph
+            .objects(&subject, RDF_TYPE)
+            .any(|class| class == &preset_class)
+        {
+            return Err(PresetError::NotAPreset);
+        }
+
+        let iri = |predicate: &'static [u8]| {
+            graph
+                .object(&subject, uri_str(predicate))
+                .and_then(Term::as_iri)
+                .map(str::to_owned)
+        };
+        let plugin = iri(sys::LV2_CORE__appliesTo);
+        let bank = iri(sys::LV2_PRESETS__bank);
+        let label = graph
+            .object(&subject, RDFS_LABEL)
+            .and_then(Term::as_literal)
+            .map(str::to_owned);
+
+        let mut port_values = Vec::new();
+        for port in graph.objects(&subject, uri_str(sys::LV2_CORE__port)) {
+            let symbol = graph
+                .object(port, uri_str(sys::LV2_CORE__symbol))
+                .and_then(Term::as_literal)
+                .ok_or(PresetError::BadPortValue)?;
+            let value = graph
+                .object(port, uri_str(sys::LV2_PRESETS__value))
+                .and_then(Term::as_literal)
+                .and_then(|value| value.parse::<f32>().ok())
+                .ok_or(PresetError::BadPortValue)?;
+            port_values.push((symbol.to_owned(), value));
+        }
+
+        let state = graph
+            .objects(&subject, uri_str(sys::LV2_STATE__state))
+            .flat_map(|state| {
+                graph
+                    .triples()
+                    .iter()
+                    .filter(move |triple| &triple.subject == state)
+            })
+            .map(|triple| (triple.predicate.clone(), triple.object.clone()))
+            .collect();
+
+        Ok(Self {
+            uri: uri.to_owned(),
+            plugin,
+            label,
+            bank,
+            port_values,
+            state,
+        })
+    }
+
+    /// The URI of the preset.
+    pub fn uri(&self) -> &str {
+        self.uri.as_str()
+    }
+
+    /// The URI of the plugin this preset applies to.
+    pub fn plugin(&self) -> Option<&str> {
+        self.plugin.as_deref()
+    }
+
+    /// The human-readable name of the preset.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// The URI of the bank this preset belongs to.
+    pub fn bank(&self) -> Option<&str> {
+        self.bank.as_deref()
+    }
+
+    /// Iterate over the port values of the preset, as pairs of port symbols and values.
+    pub fn port_values(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.port_values
+            .iter()
+            .map(|(symbol, value)| (symbol.as_str(), *value))
+    }
+
+    /// Return the value of the port with the given symbol.
+    pub fn port_value(&self, symbol: &str) -> Option<f32> {
+        self.port_values()
+            .find(|(port, _)| *port == symbol)
+            .map(|(_, value)| value)
+    }
+
+    /// Iterate over the state properties of the preset, as pairs of property URIs and values.
+    pub fn state_properties(&self) -> impl Iterator<Item = (&str, &Term)> {
+        self.state.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Convert the state properties of the preset to a state storage.
+    ///
+    /// Literals are converted to the corresponding atoms, for example an `xsd:float` to an `atom:Float` and a plain string to an `atom:String`, and IRIs are converted to `atom:URID`s. Properties with other values, like blank nodes, can't be converted and let the conversion fail.
+    pub fn storage<M: Map>(&self, map: &M) -> Result<Storage, PresetError> {
+        let types = ValueTypes::new(map)?;
+        let mut storage = Storage::default();
+        for (key, value) in self.state.iter() {
+            let key = map.map_str(key).ok_or(PresetError::BadUri)?;
+            let (type_, data) = types.to_atom(value, map)?;
+            storage.store(key, type_, data.as_ref());
+        }
+        Ok(storage)
+    }
+
+    /// Restore the state of a plugin from the preset.
+    ///
+    /// The state properties are converted like in [`storage`](#method.storage) and then passed to the plugin's [`restore`](../lv2_state/trait.State.html#tymethod.restore) method, along with the given features.
+    pub fn restore<P: State, M: Map>(
+        &self,
+        plugin: &mut P,
+        features: P::StateFeatures,
+        map: &M,
+    ) -> Result<(), PresetError> {
+        let mut storage = self.storage(map)?;
+        plugin
+            .restore(storage.retrieve_handle(), features)
+            .map_err(PresetError::State)
+    }
+
+    /// Serialize the preset to a Turtle document.
+    pub fn to_turtle(&self) -> String {
+        let mut document = String::new();
+        for (prefix, namespace) in &[
+            ("atom", uri_str(sys::LV2_ATOM_PREFIX)),
+            ("lv2", uri_str(sys::LV2_CORE_PREFIX)),
+            ("pset", uri_str(sys::LV2_PRESETS_PREFIX)),
+            ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+            ("state", uri_str(sys::LV2_STATE_PREFIX)),
+            ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+        ] {
+            writeln!(document, "@prefix {}: <{}> .", prefix, namespace).unwrap();
+        }
+
+        let mut statements = vec!["a pset:Preset".to_owned()];
+        if let Some(plugin) = &self.plugin {
+            statements.push(format!("lv2:appliesTo {}", Term::iri(plugin)));
+        }
+        if let Some(label) = &self.label {
+            statements.push(format!("rdfs:label {}", Term::string(label)));
+        }
+        if let Some(bank) = &self.bank {
+            statements.push(format!("pset:bank {}", Term::iri(bank)));
+        }
+        for (symbol, value) in self.port_values.iter() {
+            statements.push(format!(
+                "lv2:port [\n\t\tlv2:symbol {} ;\n\t\tpset:value {}\n\t]",
+                Term::string(symbol),
+                format_float(*value)
+            ));
+        }
+        if !self.state.is_empty() {
+            let properties: Vec<String> = self
+                .state
+                .iter()
+                .map(|(key, value)| format!("\t\t{} {}", Term::iri(key), value))
+                .collect();
+            statements.push(format!("state:state [\n{}\n\t]", properties.join(" ;\n")));
+        }
+
+        write!(
+            document,
+            "\n{}\n\t{} .\n",
+            Term::iri(&self.uri),
+            statements.join(" ;\n\t")
+        )
+        .unwrap();
+        document
+    }
+
+    /// Save the preset to a bundle.
+    ///
+    /// The preset is written to a file with the given name in the bundle and an entry for it is appended to the bundle's manifest, which is created if it doesn't exist yet. The bundle directory is created too, if necessary.
+    pub fn save(&self, bundle_path: &Path, file_name: &str) -> Result<(), PresetError> {
+        fs::create_dir_all(bundle_path)?;
+        fs::write(bundle_path.join(file_name), self.to_turtle())?;
+
+        let mut entry = format!(
+            "\n{}\n\t{} {}",
+            Term::iri(&self.uri),
+            Term::iri(RDF_TYPE),
+            Term::iri(uri_str(sys::LV2_PRESETS__Preset))
+        );
+        if let Some(plugin) = &self.plugin {
+            write!(
+                entry,
+                " ;\n\t{} {}",
+                Term::iri(uri_str(sys::LV2_CORE__appliesTo)),
+                Term::iri(plugin)
+            )
+            .unwrap();
+        }
+        // The file is referenced relative to the manifest, which keeps the bundle relocatable.
+        writeln!(
+            entry,
+            " ;\n\t{} <{}> .",
+            Term::iri(RDFS_SEE_ALSO),
+            encode_iri_path(file_name)
+        )
+        .unwrap();
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(bundle_path.join("manifest.ttl"))?
+            .write_all(entry.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// A builder for presets, for example to save the current configuration of a plugin.
+///
+/// ```
+/// use lv2_presets::*;
+///
+/// let preset = PresetBuilder::new("urn:my-presets:loud", "urn:my-plugin")
+///     .label("Loud")
+///     .port_value("gain", 12.0)
+///     .build();
+///
+/// assert_eq!(preset.port_value("gain"), Some(12.0));
+/// ```
+pub struct PresetBuilder {
+    preset: Preset,
+}
+
+impl PresetBuilder {
+    /// Create a new builder for a preset with the given URI, which applies to the given plugin.
+    ///
+    /// Presets that are saved by the user usually have the `file:` URI of their preset file.
+    pub fn new(uri: &str, plugin: &str) -> Self {
+        Self {
+            preset: Preset {
+                uri: uri.to_owned(),
+                plugin: Some(plugin.to_owned()),
+                label: None,
+                bank: None,
+                port_values: Vec::new(),
+                state: Vec::new(),
+            },
+        }
+    }
+
+    /// Set the human-readable name of the preset.
+    pub fn label(mut self, label: &str) -> Self {
+        self.preset.label = Some(label.to_owned());
+        self
+    }
+
+    /// Set the bank the preset belongs to.
+    pub fn bank(mut self, bank: &str) -> Self {
+        self.preset.bank = Some(bank.to_owned());
+        self
+    }
+
+    /// Add the value of a port, identified by its symbol.
+    pub fn port_value(mut self, symbol: &str, value: f32) -> Self {
+        self.preset.port_values.push((symbol.to_owned(), value));
+        self
+    }
+
+    /// Add a state property.
+    pub fn state_property(mut self, key: &str, value: Term) -> Self {
+        self.preset.state.push((key.to_owned(), value));
+        self
+    }
+
+    /// Add the current state of a plugin.
+    ///
+    /// The state is saved with the plugin's [`save`](../lv2_state/trait.State.html#tymethod.save) method and the stored atoms are converted to literals, which is the reverse of [`Preset::storage`](struct.Preset.html#method.storage). This fails if the plugin stores atoms of other types.
+    pub fn state<P: State, M: Map, U: Unmap>(
+        self,
+        plugin: &P,
+        features: P::StateFeatures,
+        map: &M,
+        unmap: &U,
+    ) -> Result<Self, PresetError> {
+        let mut storage = Storage::default();
+        plugin
+            .save(storage.store_handle(), features)
+            .map_err(PresetError::State)?;
+        self.storage(&storage, map, unmap)
+    }
+
+    /// Add the properties of a state storage.
+    ///
+    /// This is used for plugins whose state isn't saved with a `State` implementation, like instances in a host, and converts the stored atoms like [`state`](#method.state).
+    pub fn storage<M: Map, U: Unmap>(
+        mut self,
+        storage: &Storage,
+        map: &M,
+        unmap: &U,
+    ) -> Result<Self, PresetError> {
+        let types = ValueTypes::new(map)?;
+        let mut properties = Vec::new();
+        for (key, (type_, data)) in storage.iter() {
+            let key = unmap_str(unmap, *key)?;
+            let value = types.to_term(*type_, data.as_ref(), unmap)?;
+            properties.push((key, value));
+        }
+        // The storage is unordered, but the preset files should be stable.
+        properties.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.preset.state.extend(properties);
+        Ok(self)
+    }
+
+    /// Create the preset.
+    pub fn build(self) -> Preset {
+        self.preset
+    }
+}
+
+/// Format a port value as a Turtle number, or as a typed literal if it isn't finite.
+fn format_float(value: f32) -> String {
+    if value.is_finite() {
+        format!("{:?}", value)
+    } else {
+        Term::typed(&value.to_string(), XSD_FLOAT).to_string()
+    }
+}
+
+fn unmap_str<U: Unmap>(unmap: &U, urid: URID) -> Result<String, PresetError> {
+    unmap
+        .unmap(urid)
+        .and_then(|uri| uri.to_str().ok())
+        .map(str::to_owned)
+        .ok_or(PresetError::BadUri)
+}
+
+/// The atom types state properties are converted to and from.
+struct ValueTypes {
+    atom: AtomURIDCollection,
+    path: URID,
+}
+
+impl ValueTypes {
+    fn new<M: Map>(map: &M) -> Result<Self, PresetError> {
+        Ok(Self {
+            atom: map.populate_collection().ok_or(PresetError::BadUri)?,
+            path: map
+                .map_str(uri_str(sys::LV2_ATOM__Path))
+                .ok_or(PresetError::BadUri)?,
+        })
+    }
+
+    fn to_atom<M: Map>(&self, value: &Term, map: &M) -> Result<(URID, Vec<u8>), PresetError> {
+        let (lexical, datatype) = match value {
+            Term::Iri(iri) => {
+                let urid = map.map_str(iri).ok_or(PresetError::BadUri)?;
+                return Ok((
+                    self.atom.urid.into_general(),
+                    urid.get().to_ne_bytes().to_vec(),
+                ));
+            }
+            Term::Blank(_) => return Err(PresetError::UnsupportedValue),
+            Term::Literal {
+                value, datatype, ..
+            } => (value.as_str(), datatype.as_deref()),
+        };
+
+        let atom_type = |name: &str| format!("{}{}", uri_str(sys::LV2_ATOM_PREFIX), name);
+        let datatype = datatype.unwrap_or(XSD_STRING);
+        let converted =
+            if datatype == XSD_FLOAT || datatype == XSD_DECIMAL || datatype == atom_type("Float") {
+                let value: f32 = parse_value(lexical)?;
+                (self.atom.float.into_general(), value.to_ne_bytes().to_vec())
+            } else if datatype == XSD_DOUBLE || datatype == atom_type("Double") {
+                let value: f64 = parse_value(lexical)?;
+                (
+                    self.atom.double.into_general(),
+                    value.to_ne_bytes().to_vec(),
+                )
+            } else if datatype == XSD_INT || datatype == XSD_INTEGER || datatype == atom_type("Int")
+            {
+                let value: i32 = parse_value(lexical)?;
+                (self.atom.int.into_general(), value.to_ne_bytes().to_vec())
+            } else if datatype == XSD_LONG || datatype == atom_type("Long") {
+                let value: i64 = parse_value(lexical)?;
+                (self.atom.long.into_general(), value.to_ne_bytes().to_vec())
+            } else if datatype == XSD_BOOLEAN || datatype == atom_type("Bool") {
+                let value: i32 = match lexical {
+                    "true" | "1" => 1,
+                    "false" | "0" => 0,
+                    _ => return Err(PresetError::UnsupportedValue),
+                };
+                (self.atom.bool.into_general(), value.to_ne_bytes().to_vec())
+            } else if datatype == XSD_STRING || datatype == atom_type("String") {
+                (self.atom.string.into_general(), nul_terminated(lexical))
+            } else if datatype == atom_type("Path") {
+                (self.path, nul_terminated(lexical))
+            } else {
+                return Err(PresetError::UnsupportedValue);
+            };
+        Ok(converted)
+    }
+
+    fn to_term<U: Unmap>(&self, type_: URID, data: &[u8], unmap: &U) -> Result<Term, PresetError> {
+        let text = |data: &[u8]| {
+            let data = data.strip_suffix(&[0]).unwrap_or(data);
+            std::str::from_utf8(data)
+                .map(str::to_owned)
+                .map_err(|_| PresetError::UnsupportedValue)
+        };
+
+        let term = if type_ == self.atom.float {
+            Term::typed(
+                &f32::from_ne_bytes(value_bytes(data)?).to_string(),
+                XSD_FLOAT,
+            )
+        } else if type_ == self.atom.double {
+            Term::typed(
+                &f64::from_ne_bytes(value_bytes(data)?).to_string(),
+                XSD_DOUBLE,
+            )
+        } else if type_ == self.atom.int {
+            Term::typed(&i32::from_ne_bytes(value_bytes(data)?).to_string(), XSD_INT)
+        } else if type_ == self.atom.long {
+            Term::typed(
+                &i64::from_ne_bytes(value_bytes(data)?).to_string(),
+                XSD_LONG,
+            )
+        } else if type_ == self.atom.bool {
+            let value = i32::from_ne_bytes(value_bytes(data)?) != 0;
+            Term::typed(&value.to_string(), XSD_BOOLEAN)
+        } else if type_ == self.atom.string {
+            Term::string(&text(data)?)
+        } else if type_ == self.path {
+            let datatype = format!("{}Path", uri_str(sys::LV2_ATOM_PREFIX));
+            Term::typed(&text(data)?, &datatype)
+        } else if type_ == self.atom.urid {
+            let urid =
+                URID::new(u32::from_ne_bytes(value_bytes(data)?)).ok_or(PresetError::BadUri)?;
+            Term::Iri(unmap_str(unmap, urid)?)
+        } else {
+            return Err(PresetError::UnsupportedValue);
+        };
+        Ok(term)
+    }
+}
+
+fn parse_value<T: FromStr>(lexical: &str) -> Result<T, PresetError> {
+    lexical.parse().map_err(|_| PresetError::UnsupportedValue)
+}
+
+fn value_bytes<'a, T: TryFrom<&'a [u8]>>(data: &'a [u8]) -> Result<T, PresetError> {
+    T::try_from(data).map_err(|_| PresetError::UnsupportedValue)
+}
+
+fn nul_terminated(value: &str) -> Vec<u8> {
+    let mut data = value.as_bytes().to_owned();
+    data.push(0);
+    data
+}
@@ -0,0 +1,995 @@
+//! A small reader and writer for the Turtle documents of LV2 bundles.
+//!
+//! LV2 bundles describe their content with [Turtle](https://www.w3.org/TR/turtle/) documents. This module implements the subset of Turtle that is used by these documents: Prefix and base directives, IRIs, prefixed names, blank nodes, string, numeric and boolean literals, as well as predicate and object lists. Collections aren't supported.
+//!
+//! Documents are parsed into a [`Graph`](struct.Graph.html), which is a plain list of triples with some methods to query it.
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub(crate) const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+pub(crate) const RDFS_LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+pub(crate) const RDFS_SEE_ALSO: &str = "http://www.w3.org/2000/01/rdf-schema#seeAlso";
+pub(crate) const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+pub(crate) const XSD_DECIMAL: &str = "http://www.w3.org/2001/XMLSchema#decimal";
+pub(crate) const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+pub(crate) const XSD_FLOAT: &str = "http://www.w3.org/2001/XMLSchema#float";
+pub(crate) const XSD_INT: &str = "http://www.w3.org/2001/XMLSchema#int";
+pub(crate) const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+pub(crate) const XSD_LONG: &str = "http://www.w3.org/2001/XMLSchema#long";
+pub(crate) const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// Errors that may occur while parsing a Turtle document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TurtleError {
+    /// The document ended in the middle of a statement.
+    UnexpectedEnd,
+    /// An unexpected character was found in the given line.
+    UnexpectedCharacter { line: usize },
+    /// A prefixed name with an undeclared prefix was found in the given line.
+    UnknownPrefix { line: usize },
+    /// A valid, but unsupported construct, like a collection, was found in the given line.
+    Unsupported { line: usize },
+}
+
+/// A node of a graph.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Term {
+    /// A resource, identified by an absolute IRI.
+    Iri(String),
+    /// A resource without an IRI.
+    ///
+    /// The identifier is unique within the graph, but has no meaning outside of it.
+    Blank(String),
+    /// A literal value, like a string or a number.
+    ///
+    /// The value is stored in its lexical form and is interpreted according to its datatype. Literals with a language tag don't have a datatype.
+    Literal {
+        value: String,
+        datatype: Option<String>,
+        language: Option<String>,
+    },
+}
+
+impl Term {
+    /// Create an IRI term.
+    pub fn iri(iri: &str) -> Self {
+        Term::Iri(iri.to_owned())
+    }
+
+    /// Create a plain string literal.
+    pub fn string(value: &str) -> Self {
+        Term::Literal {
+            value: value.to_owned(),
+            datatype: None,
+            language: None,
+        }
+    }
+
+    /// Create a literal with the given datatype.
+    pub fn typed(value: &str, datatype: &str) -> Self {
+        Term::Literal {
+            value: value.to_owned(),
+            datatype: Some(datatype.to_owned()),
+            language: None,
+        }
+    }
+
+    /// Return the IRI of the term, if it's an IRI.
+    pub fn as_iri(&self) -> Option<&str> {
+        match self {
+            Term::Iri(iri) => Some(iri.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the lexical value of the term, if it's a literal.
+    pub fn as_literal(&self) -> Option<&str> {
+        match self {
+            Term::Literal { value, .. } => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the datatype of the term, if it's a typed literal.
+    pub fn datatype(&self) -> Option<&str> {
+        match self {
+            Term::Literal {
+                datatype: Some(datatype),
+                ..
+            } => Some(datatype.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the term in its N-Triples form, which is also valid Turtle.
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Term::Iri(iri) => write!(f, "<{}>", iri),
+            Term::Blank(id) => write!(f, "_:{}", id),
+            Term::Literal {
+                value,
+                datatype,
+                language,
+            } => {
+                f.write_str("\"")?;
+                for c in value.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                f.write_str("\"")?;
+                if let Some(language) = language {
+                    write!(f, "@{}", language)
+                } else if let Some(datatype) = datatype {
+                    write!(f, "^^<{}>", datatype)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// A statement of a graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Triple {
+    pub subject: Term,
+    pub predicate: String,
+    pub object: Term,
+}
+
+/// A set of triples, parsed from one or more Turtle documents.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    triples: Vec<Triple>,
+    blank_nodes: usize,
+}
+
+impl Graph {
+    /// Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a Turtle document and add its triples to the graph.
+    ///
+    /// Relative IRIs in the document are resolved against the base IRI, which is usually the IRI of the document itself. Blank nodes of different documents are always different nodes, even if they have the same label.
+    pub fn parse(&mut self, source: &str, base: &str) -> Result<(), TurtleError> {
+        Parser {
+            source,
+            position: 0,
+            line: 1,
+            base: base.to_owned(),
+            prefixes: HashMap::new(),
+            blank_labels: HashMap::new(),
+            graph: self,
+        }
+        .document()
+    }
+
+    /// Add a triple to the graph.
+    pub fn insert(&mut self, subject: Term, predicate: &str, object: Term) {
+        self.triples.push(Triple {
+            subject,
+            predicate: predicate.to_owned(),
+            object,
+        });
+    }
+
+    /// Add all triples of another graph to this graph.
+    ///
+    /// The blank nodes of the other graph are replaced with new blank nodes of this graph, so that they stay distinct from the blank nodes that are already in it.
+    pub fn merge(&mut self, other: Graph) {
+        let mut nodes: HashMap<Term, Term> = HashMap::new();
+        for triple in other.triples {
+            let subject = self.merged_node(&mut nodes, triple.subject);
+            let object = self.merged_node(&mut nodes, triple.object);
+            self.triples.push(Triple {
+                subject,
+                predicate: triple.predicate,
+                object,
+            });
+        }
+    }
+
+    /// Replace a blank node of a merged graph with a blank node of this graph.
+    fn merged_node(&mut self, nodes: &mut HashMap<Term, Term>, term: Term) -> Term {
+        match term {
+            Term::Blank(_) => match nodes.get(&term) {
+                Some(node) => node.clone(),
+                None => {
+                    let node = self.blank_node();
+                    nodes.insert(term, node.clone());
+                    node
+                }
+            },
+            term => term,
+        }
+    }
+
+    /// Create a new blank node that is unique within the graph.
+    pub fn blank_node(&mut self) -> Term {
+        self.blank_nodes += 1;
+        Term::Blank(format!("b{}", self.blank_nodes))
+    }
+
+    /// All triples of the graph.
+    pub fn triples(&self) -> &[Triple] {
+        self.triples.as_ref()
+    }
+
+    /// Iterate over the objects of all triples with the given subject and predicate.
+    pub fn objects<'a>(
+        &'a self,
+        subject: &'a Term,
+        predicate: &'a str,
+    ) -> impl Iterator<Item = &'a Term> + 'a {
+        self.triples
+            .iter()
+            .filter(move |triple| &triple.subject == subject && triple.predicate == predicate)
+            .map(|triple| &triple.object)
+    }
+
+    /// Return the object of the first triple with the given subject and predicate.
+    pub fn object<'a>(&'a self, subject: &'a Term, predicate: &'a str) -> Option<&'a Term> {
+        self.objects(subject, predicate).next()
+    }
+
+    /// Iterate over the subjects of all triples with the given predicate and object.
+    pub fn subjects<'a>(
+        &'a self,
+        predicate: &'a str,
+        object: &'a Term,
+    ) -> impl Iterator<Item = &'a Term> + 'a {
+        self.triples
+            .iter()
+            .filter(move |triple| triple.predicate == predicate && &triple.object == object)
+            .map(|triple| &triple.subject)
+    }
+}
+
+/// Percent-encode all characters of a path that aren't allowed in IRIs.
+pub fn encode_iri_path(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/!$&'()*+,;=:@".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Create the `file:` IRI of an absolute path.
+///
+/// This method returns `None` if the path isn't valid UTF-8.
+pub fn file_iri(path: &Path) -> Option<String> {
+    let path = path.to_str()?.replace('\\', "/");
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    Some(format!("file://{}{}", separator, encode_iri_path(&path)))
+}
+
+/// Return the local path of a `file:` IRI.
+///
+/// This method returns `None` if the IRI doesn't have the `file:` scheme or isn't properly encoded. The fragment of the IRI is ignored.
+pub fn iri_path(iri: &str) -> Option<PathBuf> {
+    let path = iri.strip_prefix("file://")?;
+    let path = path.strip_prefix("localhost").unwrap_or(path);
+    let path = path.split('#').next()?;
+
+    let mut decoded: Vec<u8> = Vec::new();
+    let mut bytes = path.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let high = (bytes.next()? as char).to_digit(16)?;
+            let low = (bytes.next()? as char).to_digit(16)?;
+            decoded.push((high * 16 + low) as u8);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+    // Windows paths don't start with a slash, like `C:/presets`.
+    if path.len() > 2 && path.as_bytes()[2] == b':' {
+        Some(PathBuf::from(&path[1..]))
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+/// Resolve a relative IRI reference against a base IRI.
+///
+/// Absolute IRIs are returned unchanged. Dot segments in the reference aren't removed, except for a leading `./`.
+pub fn resolve_iri(base: &str, reference: &str) -> String {
+    let has_scheme = reference
+        .find(':')
+        .map(|index| {
+            let scheme = &reference[..index];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        })
+        .unwrap_or(false);
+    if has_scheme {
+        return reference.to_owned();
+    }
+
+    let base = base.split('#').next().unwrap_or(base);
+    if reference.is_empty() {
+        return base.to_owned();
+    }
+    if reference.starts_with('#') {
+        return format!("{}{}", base, reference);
+    }
+    if reference.starts_with("//") {
+        let scheme = base.split(':').next().unwrap_or("");
+        return format!("{}:{}", scheme, reference);
+    }
+    if reference.starts_with('/') {
+        // Keep the scheme and authority of the base.
+        let authority_end = base
+            .find("//")
+            .map(|start| {
+                base[start + 2..]
+                    .find('/')
+                    .map(|end| start + 2 + end)
+                    .unwrap_or_else(|| base.len())
+            })
+            .or_else(|| base.find(':').map(|index| index + 1))
+            .unwrap_or(0);
+        return format!("{}{}", &base[..authority_end], reference);
+    }
+
+    let directory = match base.rfind('/') {
+        Some(index) => &base[..=index],
+        None => base,
+    };
+    let reference = reference.trim_start_matches("./");
+    format!("{}{}", directory, reference)
+}
+
+struct Parser<'a, 'b> {
+    source: &'a str,
+    position: usize,
+    line: usize,
+    base: String,
+    prefixes: HashMap<String, String>,
+    blank_labels: HashMap<String, Term>,
+    graph: &'b mut Graph,
+}
+
+impl<'a, 'b> Parser<'a, 'b> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.source[self.position..].chars().nth(1)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn unexpected(&self) -> TurtleError {
+        if self.peek().is_some() {
+            TurtleError::UnexpectedCharacter { line: self.line }
+        } else {
+            TurtleError::UnexpectedEnd
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TurtleError> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.bump();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '#' {
+                while let Some(c) = self.bump() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Check whether the rest starts with the keyword, followed by whitespace.
+    fn keyword(&self, keyword: &str, case_sensitive: bool) -> bool {
+        let rest = self.rest();
+        if rest.len() <= keyword.len() || !rest.is_char_boundary(keyword.len()) {
+            return false;
+        }
+        let (head, tail) = rest.split_at(keyword.len());
+        let matches = if case_sensitive {
+            head == keyword
+        } else {
+            head.eq_ignore_ascii_case(keyword)
+        };
+        matches && tail.starts_with(char::is_whitespace)
+    }
+
+    fn document(&mut self) -> Result<(), TurtleError> {
+        loop {
+            self.skip_whitespace();
+            if self.peek().is_none() {
+                return Ok(());
+            }
+
+            if self.keyword("@prefix", true) {
+                self.position += "@prefix".len();
+                self.prefix_directive()?;
+                self.expect('.')?;
+            } else if self.keyword("@base", true) {
+                self.position += "@base".len();
+                self.base_directive()?;
+                self.expect('.')?;
+            } else if self.keyword("PREFIX", false) {
+                self.position += "PREFIX".len();
+                self.prefix_directive()?;
+            } else if self.keyword("BASE", false) {
+                self.position += "BASE".len();
+                self.base_directive()?;
+            } else {
+                self.triples()?;
+                self.expect('.')?;
+            }
+        }
+    }
+
+    fn prefix_directive(&mut self) -> Result<(), TurtleError> {
+        self.skip_whitespace();
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if c == ':' {
+                break;
+            } else if is_name_char(c) {
+                self.bump();
+            } else {
+                return Err(self.unexpected());
+            }
+        }
+        let prefix = self.source[start..self.position].to_owned();
+        self.expect(':')?;
+        self.skip_whitespace();
+        let iri = self.iri_ref()?;
+        self.prefixes.insert(prefix, iri);
+        Ok(())
+    }
+
+    fn base_directive(&mut self) -> Result<(), TurtleError> {
+        self.skip_whitespace();
+        self.base = self.iri_ref()?;
+        Ok(())
+    }
+
+    fn triples(&mut self) -> Result<(), TurtleError> {
+        if self.peek() == Some('[') {
+            let subject = self.blank_node_property_list()?;
+            self.skip_whitespace();
+            if self.peek() != Some('.') {
+                self.predicate_object_list(&subject)?;
+            }
+            Ok(())
+        } else {
+            let subject = match self.peek() {
+                Some('_') => self.blank_node_label()?,
+                Some(_) => Term::Iri(self.iri()?),
+                None => return Err(TurtleError::UnexpectedEnd),
+            };
+            self.predicate_object_list(&subject)
+        }
+    }
+
+    fn predicate_object_list(&mut self, subject: &Term) -> Result<(), TurtleError> {
+        loop {
+            self.skip_whitespace();
+            let predicate = self.verb()?;
+            loop {
+                self.skip_whitespace();
+                let object = self.object()?;
+                self.graph.insert(subject.clone(), &predicate, object);
+                self.skip_whitespace();
+                if self.peek() == Some(',') {
+                    self.bump();
+                } else {
+                    break;
+                }
+            }
+
+            if self.peek() != Some(';') {
+                return Ok(());
+            }
+            while self.peek() == Some(';') {
+                self.bump();
+                self.skip_whitespace();
+            }
+            match self.peek() {
+                Some('.') | Some(']') | None => return Ok(()),
+                _ => (),
+            }
+        }
+    }
+
+    fn verb(&mut self) -> Result<String, TurtleError> {
+        if self.peek() == Some('a') && matches!(self.peek_second(), Some(c) if c.is_whitespace()) {
+            self.bump();
+            Ok(RDF_TYPE.to_owned())
+        } else {
+            self.iri()
+        }
+    }
+
+    fn object(&mut self) -> Result<Term, TurtleError> {
+        match self.peek() {
+            Some('<') => self.iri_ref().map(Term::Iri),
+            Some('_') => self.blank_node_label(),
+            Some('[') => self.blank_node_property_list(),
+            Some('"') | Some('\'') => self.literal(),
+            Some('(') => Err(TurtleError::Unsupported { line: self.line }),
+            Some(c) if c.is_ascii_digit() || c == '+' || c == '-' || c == '.' => self.number(),
+            Some(_) if self.boolean("true") => Ok(Term::typed("true", XSD_BOOLEAN)),
+            Some(_) if self.boolean("false") => Ok(Term::typed("false", XSD_BOOLEAN)),
+            Some(_) => self.prefixed_name().map(Term::Iri),
+            None => Err(TurtleError::UnexpectedEnd),
+        }
+    }
+
+    fn boolean(&mut self, keyword: &str) -> bool {
+        let rest = self.rest();
+        if rest.starts_with(keyword)
+            && !rest[keyword.len()..].starts_with(|c: char| is_name_char(c) || c == ':')
+        {
+            self.position += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn iri(&mut self) -> Result<String, TurtleError> {
+        if self.peek() == Some('<') {
+            self.iri_ref()
+        } else {
+            self.prefixed_name()
+        }
+    }
+
+    fn iri_ref(&mut self) -> Result<String, TurtleError> {
+        if self.peek() != Some('<') {
+            return Err(self.unexpected());
+        }
+        self.bump();
+        let start = self.position;
+        loop {
+            match self.bump() {
+                Some('>') => break,
+                Some(c) if c.is_whitespace() || c == '<' => {
+                    return Err(TurtleError::UnexpectedCharacter { line: self.line })
+                }
+                Some(_) => (),
+                None => return Err(TurtleError::UnexpectedEnd),
+            }
+        }
+        let reference = &self.source[start..self.position - 1];
+        Ok(resolve_iri(&self.base, reference))
+    }
+
+    fn prefixed_name(&mut self) -> Result<String, TurtleError> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if c == ':' {
+                break;
+            } else if is_name_char(c) {
+                self.bump();
+            } else {
+                return Err(self.unexpected());
+            }
+        }
+        let prefix = &self.source[start..self.position];
+        if self.bump() != Some(':') {
+            return Err(TurtleError::UnexpectedEnd);
+        }
+        let local = self.local_name();
+        match self.prefixes.get(prefix) {
+            Some(namespace) => Ok(format!("{}{}", namespace, local)),
+            None => Err(TurtleError::UnknownPrefix { line: self.line }),
+        }
+    }
+
+    fn local_name(&mut self) -> &'a str {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if is_name_char(c) || c == ':' || c == '%' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        // A local name may not end with a dot, since it's the end of the statement.
+        while self.source[start..self.position].ends_with('.') {
+            self.position -= 1;
+        }
+        &self.source[start..self.position]
+    }
+
+    fn blank_node_label(&mut self) -> Result<Term, TurtleError> {
+        if !self.rest().starts_with("_:") {
+            return Err(self.unexpected());
+        }
+        self.position += 2;
+        let label = self.local_name().to_owned();
+        if let Some(node) = self.blank_labels.get(&label) {
+            return Ok(node.clone());
+        }
+        let node = self.graph.blank_node();
+        self.blank_labels.insert(label, node.clone());
+        Ok(node)
+    }
+
+    fn blank_node_property_list(&mut self) -> Result<Term, TurtleError> {
+        self.expect('[')?;
+        let node = self.graph.blank_node();
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            self.predicate_object_list(&node)?;
+        }
+        self.expect(']')?;
+        Ok(node)
+    }
+
+    fn literal(&mut self) -> Result<Term, TurtleError> {
+        let value = self.string()?;
+        if self.rest().starts_with("^^") {
+            self.position += 2;
+            let datatype = self.iri()?;
+            Ok(Term::Literal {
+                value,
+                datatype: Some(datatype),
+                language: None,
+            })
+        } else if self.peek() == Some('@') {
+            self.bump();
+            let start = self.position;
+            while let Some(c) = self.peek() {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    self.bump();
+                } else {
+                    break;
+                }
+            }
+            Ok(Term::Literal {
+                value,
+                datatype: None,
+                language: Some(self.source[start..self.position].to_owned()),
+            })
+        } else {
+            Ok(Term::Literal {
+                value,
+                datatype: None,
+                language: None,
+            })
+        }
+    }
+
+    fn string(&mut self) -> Result<String, TurtleError> {
+        let quote = self.bump().ok_or(TurtleError::UnexpectedEnd)?;
+        let long_quote = quote.to_string().repeat(3);
+        let long = self.rest().starts_with(&long_quote[1..]);
+        if long {
+            self.position += 2;
+        }
+
+        let mut value = String::new();
+        loop {
+            if long && self.rest().starts_with(&long_quote) {
+                self.position += 3;
+                return Ok(value);
+            }
+            match self.bump() {
+                Some(c) if c == quote && !long => return Ok(value),
+                Some('\n') | Some('\r') if !long => {
+                    return Err(TurtleError::UnexpectedCharacter { line: self.line })
+                }
+                Some('\\') => value.push(self.escape()?),
+                Some(c) => value.push(c),
+                None => return Err(TurtleError::UnexpectedEnd),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, TurtleError> {
+        let escaped = match self.bump().ok_or(TurtleError::UnexpectedEnd)? {
+            't' => '\t',
+            'b' => '\u{8}',
+            'n' => '\n',
+            'r' => '\r',
+            'f' => '\u{c}',
+            '"' => '"',
+            '\'' => '\'',
+            '\\' => '\\',
+            'u' => return self.unicode_escape(4),
+            'U' => return self.unicode_escape(8),
+            _ => return Err(TurtleError::UnexpectedCharacter { line: self.line }),
+        };
+        Ok(escaped)
+    }
+
+    fn unicode_escape(&mut self, digits: usize) -> Result<char, TurtleError> {
+        let rest = self.rest();
+        if rest.len() < digits || !rest.is_char_boundary(digits) {
+            return Err(TurtleError::UnexpectedEnd);
+        }
+        let c = u32::from_str_radix(&rest[..digits], 16)
+            .ok()
+            .and_then(std::char::from_u32)
+            .ok_or(TurtleError::UnexpectedCharacter { line: self.line })?;
+        self.position += digits;
+        Ok(c)
+    }
+
+    fn number(&mut self) -> Result<Term, TurtleError> {
+        let start = self.position;
+        if let Some('+') | Some('-') = self.peek() {
+            self.bump();
+        }
+        let mut digits = self.digits();
+        let mut datatype = XSD_INTEGER;
+        if self.peek() == Some('.') && matches!(self.peek_second(), Some(c) if c.is_ascii_digit()) {
+            self.bump();
+            digits += self.digits();
+            datatype = XSD_DECIMAL;
+        }
+        if let Some('e') | Some('E') = self.peek() {
+            self.bump();
+            if let Some('+') | Some('-') = self.peek() {
+                self.bump();
+            }
+            if self.digits() == 0 {
+                return Err(self.unexpected());
+            }
+            datatype = XSD_DOUBLE;
+        }
+        if digits == 0 {
+            return Err(self.unexpected());
+        }
+        Ok(Term::typed(&self.source[start..self.position], datatype))
+    }
+
+    fn digits(&mut self) -> usize {
+        let mut count = 0;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.bump();
+            count += 1;
+        }
+        count
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::turtle::*;
+
+    #[test]
+    fn test_parse() {
+        let source = r#"
+            @prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+            PREFIX ex: <http://example.org/>
+
+            # A comment.
+            <preset.ttl>
+                a ex:Preset ;
+                rdfs:label "A \"quoted\" name"@en , """multi
+line""" ;
+                ex:numbers 42 , -1.5 , 2e3 , true ;
+                lv2:port [
+                    lv2:symbol "gain" ;
+                    ex:value 0.5
+                ] ;
+                ex:other _:node .
+
+            _:node ex:value 'single' .
+            [] ex:anonymous ex:thing.
+        "#;
+
+        let mut graph = Graph::new();
+        graph.parse(source, "file:///bundle/manifest.ttl").unwrap();
+
+        let subject = Term::iri("file:///bundle/preset.ttl");
+        assert_eq!(
+            graph.object(&subject, RDF_TYPE),
+            Some(&Term::iri("http://example.org/Preset"))
+        );
+
+        let labels: Vec<&Term> = graph.objects(&subject, RDFS_LABEL).collect();
+        assert_eq!(
+            labels,
+            vec![
+                &Term::Literal {
+                    value: "A \"quoted\" name".to_owned(),
+                    datatype: None,
+                    language: Some("en".to_owned())
+                },
+                &Term::string("multi\nline")
+            ]
+        );
+
+        let numbers: Vec<&Term> = graph
+            .objects(&subject, "http://example.org/numbers")
+            .collect();
+        assert_eq!(
+            numbers,
+            vec![
+                &Term::typed("42", XSD_INTEGER),
+                &Term::typed("-1.5", XSD_DECIMAL),
+                &Term::typed("2e3", XSD_DOUBLE),
+                &Term::typed("true", XSD_BOOLEAN),
+            ]
+        );
+
+        let port = graph
+            .object(&subject, "http://lv2plug.in/ns/lv2core#port")
+            .unwrap();
+        assert_eq!(
+            graph.object(port, "http://lv2plug.in/ns/lv2core#symbol"),
+            Some(&Term::string("gain"))
+        );
+        assert_eq!(
+            graph.object(port, "http://example.org/value"),
+            Some(&Term::typed("0.5", XSD_DECIMAL))
+        );
+
+        let other = graph.object(&subject, "http://example.org/other").unwrap();
+        assert_eq!(
+            graph.object(other, "http://example.org/value"),
+            Some(&Term::string("single"))
+        );
+
+        let thing = Term::iri("http://example.org/thing");
+        assert_eq!(
+            graph
+                .subjects("http://example.org/anonymous", &thing)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut graph = Graph::new();
+        assert_eq!(
+            graph.parse("<a> <b> <c>", "file:///"),
+            Err(TurtleError::UnexpectedEnd)
+        );
+        assert_eq!(
+            graph.parse("<a> ex:b <c> .", "file:///"),
+            Err(TurtleError::UnknownPrefix { line: 1 })
+        );
+        assert_eq!(
+            graph.parse("<a>\n<b> ! .", "file:///"),
+            Err(TurtleError::UnexpectedCharacter { line: 2 })
+        );
+        assert_eq!(
+            graph.parse("<a> <b> (<c>) .", "file:///"),
+            Err(TurtleError::Unsupported { line: 1 })
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut graph = Graph::new();
+        graph
+            .parse("<urn:a> <urn:p> [ <urn:q> 1 ] .", "file:///a.ttl")
+            .unwrap();
+        let mut other = Graph::new();
+        other
+            .parse("<urn:b> <urn:p> [ <urn:q> 2 ] .", "file:///b.ttl")
+            .unwrap();
+        graph.merge(other);
+        assert_eq!(graph.triples().len(), 4);
+
+        // The blank nodes of both graphs stay distinct.
+        let a = graph.object(&Term::iri("urn:a"), "urn:p").unwrap().clone();
+        let b = graph.object(&Term::iri("urn:b"), "urn:p").unwrap().clone();
+        assert_ne!(a, b);
+        assert_eq!(
+            graph.object(&a, "urn:q").and_then(Term::as_literal),
+            Some("1")
+        );
+        assert_eq!(
+            graph.object(&b, "urn:q").and_then(Term::as_literal),
+            Some("2")
+        );
+    }
+
+    #[test]
+    fn test_resolve_iri() {
+        let base = "file:///home/user/bundle.lv2/manifest.ttl#frag";
+        assert_eq!(
+            resolve_iri(base, "preset.ttl"),
+            "file:///home/user/bundle.lv2/preset.ttl"
+        );
+        assert_eq!(
+            resolve_iri(base, "./preset.ttl"),
+            "file:///home/user/bundle.lv2/preset.ttl"
+        );
+        assert_eq!(
+            resolve_iri(base, "#preset"),
+            "file:///home/user/bundle.lv2/manifest.ttl#preset"
+        );
+        assert_eq!(
+            resolve_iri(base, ""),
+            "file:///home/user/bundle.lv2/manifest.ttl"
+        );
+        assert_eq!(
+            resolve_iri(base, "/etc/preset.ttl"),
+            "file:///etc/preset.ttl"
+        );
+        assert_eq!(
+            resolve_iri("http://example.org/a/b", "/c"),
+            "http://example.org/c"
+        );
+        assert_eq!(resolve_iri(base, "urn:preset"), "urn:preset");
+    }
+
+    #[test]
+    fn test_display() {
+        let mut graph = Graph::new();
+        let terms = vec![
+            Term::iri("http://example.org/a"),
+            Term::string("a \"string\"\nwith\\escapes"),
+            Term::typed("0.5", XSD_FLOAT),
+            Term::Literal {
+                value: "Hallo".to_owned(),
+                datatype: None,
+                language: Some("de".to_owned()),
+            },
+        ];
+        let source: String = terms
+            .iter()
+            .map(|term| format!("<urn:s> <urn:p> {} .\n", term))
+            .collect();
+        graph.parse(&source, "file:///").unwrap();
+        let parsed: Vec<Term> = graph
+            .triples()
+            .iter()
+            .map(|triple| triple.object.clone())
+            .collect();
+        assert_eq!(parsed, terms);
+    }
+}
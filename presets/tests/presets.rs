@@ -0,0 +1,281 @@
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_presets as presets;
+extern crate lv2_state as state;
+
+use atom::AtomURIDCollection;
+use core::prelude::*;
+use presets::turtle::Term;
+use presets::*;
+use state::*;
+use std::path::PathBuf;
+use urid::*;
+
+#[uri("urn:lv2_presets:test:sample")]
+struct Sample;
+
+#[uri("urn:lv2_presets:test:gain")]
+struct Gain;
+
+#[uri("urn:lv2_presets:test:name")]
+struct Name;
+
+#[uri("urn:lv2_presets:test:mode")]
+struct Mode;
+
+#[uri("urn:lv2_presets:test:loop")]
+struct Loop;
+
+#[derive(URIDCollection)]
+struct URIDs {
+    atom: AtomURIDCollection,
+    gain: URID<Gain>,
+    name: URID<Name>,
+    mode: URID<Mode>,
+    looping: URID<Loop>,
+}
+
+#[uri("urn:lv2_presets:test:plugin")]
+struct Stateful {
+    urids: URIDs,
+    gain: f32,
+    name: String,
+    mode: URID,
+    looping: bool,
+}
+
+impl Plugin for Stateful {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        None
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+impl State for Stateful {
+    type StateFeatures = ();
+
+    fn save(&self, mut store: StoreHandle, _: ()) -> Result<(), StateErr> {
+        store
+            .draft(self.urids.gain.into_general())
+            .init(self.urids.atom.float, self.gain)?;
+        store
+            .draft(self.urids.name.into_general())
+            .init(self.urids.atom.string, ())?
+            .append(&self.name)
+            .ok_or(StateErr::Unknown)?;
+        store
+            .draft(self.urids.mode.into_general())
+            .init(self.urids.atom.urid, self.mode)?;
+        store
+            .draft(self.urids.looping.into_general())
+            .init(self.urids.atom.bool, self.looping as i32)?;
+        store.commit_all()
+    }
+
+    fn restore(&mut self, store: RetrieveHandle, _: ()) -> Result<(), StateErr> {
+        self.gain = store
+            .retrieve(self.urids.gain.into_general())?
+            .read(self.urids.atom.float, ())?;
+        self.name = store
+            .retrieve(self.urids.name.into_general())?
+            .read(self.urids.atom.string, ())?
+            .to_owned();
+        self.mode = store
+            .retrieve(self.urids.mode.into_general())?
+            .read(self.urids.atom.urid, ())?;
+        self.looping = store
+            .retrieve(self.urids.looping.into_general())?
+            .read(self.urids.atom.bool, ())?
+            != 0;
+        Ok(())
+    }
+}
+
+fn bundle_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("lv2_presets_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&path).ok();
+    path
+}
+
+#[test]
+fn test_save_and_restore_state() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+    let sample: URID<Sample> = map.map_type().unwrap();
+
+    let original = Stateful {
+        urids: map.populate_collection().unwrap(),
+        gain: -6.5,
+        name: "My \"favourite\" sound".to_owned(),
+        mode: sample.into_general(),
+        looping: true,
+    };
+
+    let path = bundle_path("state");
+    PresetBuilder::new(
+        "urn:lv2_presets:test:preset",
+        Stateful::uri().to_str().unwrap(),
+    )
+    .label("Favourite")
+    .port_value("gain", 0.25)
+    .port_value("mix", 1.0)
+    .state(&original, (), &map, &map)
+    .unwrap()
+    .build()
+    .save(&path, "favourite.ttl")
+    .unwrap();
+
+    let bundle = Bundle::load(&path).unwrap();
+    assert_eq!(bundle.presets().len(), 1);
+    assert_eq!(bundle.presets_for("urn:lv2_presets:test:other").count(), 0);
+
+    let preset = bundle.preset("urn:lv2_presets:test:preset").unwrap();
+    assert_eq!(preset.label(), Some("Favourite"));
+    assert_eq!(preset.plugin(), Some("urn:lv2_presets:test:plugin"));
+    assert_eq!(
+        preset.port_values().collect::<Vec<(&str, f32)>>(),
+        vec![("gain", 0.25), ("mix", 1.0)]
+    );
+    assert_eq!(preset.state_properties().count(), 4);
+
+    let mut restored = Stateful {
+        urids: map.populate_collection().unwrap(),
+        gain: 0.0,
+        name: String::new(),
+        mode: urids.gain.into_general(),
+        looping: false,
+    };
+    preset.restore(&mut restored, (), &map).unwrap();
+    assert_eq!(restored.gain, -6.5);
+    assert_eq!(restored.name, "My \"favourite\" sound");
+    assert_eq!(restored.mode, sample);
+    assert!(restored.looping);
+
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[test]
+fn test_factory_presets() {
+    let path = bundle_path("factory");
+    std::fs::create_dir_all(&path).unwrap();
+    std::fs::write(
+        path.join("manifest.ttl"),
+        r#"
+@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix pset: <http://lv2plug.in/ns/ext/presets#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+
+<urn:lv2_presets:test:plugin>
+    a lv2:Plugin ;
+    lv2:binary <plugin.so> ;
+    rdfs:seeAlso <plugin.ttl> .
+
+<urn:lv2_presets:test:quiet>
+    a pset:Preset ;
+    lv2:appliesTo <urn:lv2_presets:test:plugin> ;
+    rdfs:seeAlso <presets.ttl> .
+
+<urn:lv2_presets:test:silent>
+    a pset:Preset ;
+    lv2:appliesTo <urn:lv2_presets:test:plugin> ;
+    rdfs:seeAlso <presets.ttl> .
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        path.join("presets.ttl"),
+        r#"
+@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix pset: <http://lv2plug.in/ns/ext/presets#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix state: <http://lv2plug.in/ns/ext/state#> .
+
+<urn:lv2_presets:test:quiet>
+    a pset:Preset ;
+    rdfs:label "Quiet" ;
+    lv2:port [
+        lv2:symbol "gain" ;
+        pset:value -12.0
+    ] ;
+    state:state [
+        <urn:lv2_presets:test:gain> 0.5 ;
+        <urn:lv2_presets:test:name> "quiet" ;
+        <urn:lv2_presets:test:mode> <urn:lv2_presets:test:sample> ;
+        <urn:lv2_presets:test:loop> false
+    ] .
+
+<urn:lv2_presets:test:silent>
+    a pset:Preset ;
+    rdfs:label "Silent" ;
+    lv2:port [
+        lv2:symbol "gain" ;
+        pset:value -90
+    ] ;
+    state:state [
+        <urn:lv2_presets:test:gain> [ a <urn:lv2_presets:test:nested> ]
+    ] .
+"#,
+    )
+    .unwrap();
+
+    let map = HashURIDMapper::new();
+    let bundle = Bundle::load(&path).unwrap();
+    assert_eq!(bundle.presets_for("urn:lv2_presets:test:plugin").count(), 2);
+
+    let quiet = bundle.preset("urn:lv2_presets:test:quiet").unwrap();
+    assert_eq!(quiet.label(), Some("Quiet"));
+    assert_eq!(quiet.port_value("gain"), Some(-12.0));
+    assert_eq!(
+        quiet.state_properties().nth(2),
+        Some((
+            "urn:lv2_presets:test:mode",
+            &Term::iri("urn:lv2_presets:test:sample")
+        ))
+    );
+
+    let mut plugin = Stateful {
+        urids: map.populate_collection().unwrap(),
+        gain: 0.0,
+        name: String::new(),
+        mode: map.map_str("urn:lv2_presets:test:gain").unwrap(),
+        looping: true,
+    };
+    quiet.restore(&mut plugin, (), &map).unwrap();
+    assert_eq!(plugin.gain, 0.5);
+    assert_eq!(plugin.name, "quiet");
+    assert_eq!(
+        plugin.mode,
+        map.map_str("urn:lv2_presets:test:sample").unwrap()
+    );
+    assert!(!plugin.looping);
+
+    // Nested objects aren't supported.
+    let silent = bundle.preset("urn:lv2_presets:test:silent").unwrap();
+    assert_eq!(silent.port_value("gain"), Some(-90.0));
+    assert_eq!(
+        silent.restore(&mut plugin, (), &map),
+        Err(PresetError::UnsupportedValue)
+    );
+
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[test]
+fn test_load_errors() {
+    let path = bundle_path("errors");
+    assert_eq!(
+        Bundle::load(&path).err(),
+        Some(PresetError::Io(std::io::ErrorKind::NotFound))
+    );
+
+    std::fs::create_dir_all(&path).unwrap();
+    std::fs::write(path.join("manifest.ttl"), "<urn:a> <urn:b> .").unwrap();
+    assert!(matches!(Bundle::load(&path), Err(PresetError::Turtle(_))));
+
+    std::fs::remove_dir_all(&path).unwrap();
+}
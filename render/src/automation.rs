@@ -0,0 +1,49 @@
+use crate::RenderError;
+use std::io::BufRead;
+
+/// A change of a control input at a point in time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AutomationPoint {
+    /// The time of the change, in seconds from the start of the rendering.
+    pub time: f64,
+    /// The symbol of the control input.
+    pub symbol: String,
+    /// The new value of the control input.
+    pub value: f32,
+}
+
+/// Read automation points from a CSV file.
+///
+/// Every line contains the time, the symbol and the value of a point, separated by commas. Empty lines, lines starting with `#` and a header line that starts with `time` are ignored. The points are returned in the order of their times.
+pub fn read_automation<R: BufRead>(reader: R) -> Result<Vec<AutomationPoint>, RenderError> {
+    let mut points = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields[0] == "time" {
+            continue;
+        }
+        let point = match fields.as_slice() {
+            [time, symbol, value] if !symbol.is_empty() => time
+                .parse()
+                .ok()
+                .filter(|time: &f64| *time >= 0.0)
+                .and_then(|time| {
+                    Some(AutomationPoint {
+                        time,
+                        symbol: (*symbol).to_owned(),
+                        value: value.parse().ok()?,
+                    })
+                }),
+            _ => None,
+        };
+        points.push(point.ok_or(RenderError::Automation(index + 1))?);
+    }
+    // The sort is stable, which keeps the order of points at the same time.
+    points.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+    Ok(points)
+}
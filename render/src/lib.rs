@@ -0,0 +1,118 @@
+//! Offline rendering of LV2 plugins.
+//!
+//! This crate runs a plugin over a signal as fast as possible, instead of in real time, which makes it possible to check the output of plugins in continuous integration, to create demos and to process files in batches. The input and output signals are stored as [`Wav`](struct.Wav.html)s, which are read from and written to WAV files.
+//!
+//! The plugin can be driven by [MIDI events](struct.MidiEvent.html), which are read from standard MIDI files and sent to its first atom input, and by [automation](struct.AutomationPoint.html) of its control inputs, which is read from CSV files with the columns `time`, `symbol` and `value`. The time of every automation point is in seconds, and the control keeps its value until the next point of the same control.
+//!
+//! The instance is run by a [`Renderer`](struct.Renderer.html), which is built on a [`ProcessGraph`](../lv2_host/struct.ProcessGraph.html) of `lv2-host`. The audio inputs of the plugin receive the channels of the input in order, which are repeated if the plugin has more inputs than the input has channels, and every audio output of the plugin becomes a channel of the output.
+//!
+//! The crate also contains the `lv2-render` command, which renders a plugin from the `LV2_PATH`:
+//!
+//! ```sh
+//! cargo run -p lv2-render -- http://lv2plug.in/plugins/eg-amp in.wav out.wav --control gain=-6
+//! ```
+//!
+//! ## Example usage
+//!
+//! ```no_run
+//! use lv2_host::*;
+//! use lv2_render::*;
+//!
+//! let mut world = World::new();
+//! world.load_all();
+//! let plugin = world.plugin("http://lv2plug.in/plugins/eg-amp").unwrap();
+//!
+//! let input = Wav::open("in.wav").unwrap();
+//! let automation = read_automation("0.0,gain,0\n1.0,gain,-12\n".as_bytes()).unwrap();
+//!
+//! let features = HostFeatures::new().with_urid_map();
+//! let instance = unsafe { plugin.instantiate(f64::from(input.sample_rate), &features) }.unwrap();
+//! let mut renderer = Renderer::new(instance, plugin.ports(), input.sample_rate, &features);
+//! let output = unsafe { renderer.render(&input, &[], &automation, 0) }.unwrap();
+//! output.save("out.wav").unwrap();
+//! ```
+extern crate lv2_core as core;
+extern crate lv2_host as host;
+
+mod automation;
+pub use automation::*;
+
+mod midi_file;
+pub use midi_file::*;
+
+mod renderer;
+pub use renderer::*;
+
+mod wav;
+pub use wav::*;
+
+use host::{GraphError, HostError};
+use std::fmt;
+
+/// Errors that may occur when reading inputs, rendering or writing the output.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RenderError {
+    /// Reading or writing a file failed.
+    Io(std::io::ErrorKind),
+    /// A WAV file is malformed or uses an unsupported format.
+    Wav(String),
+    /// A MIDI file is malformed.
+    Midi(String),
+    /// A line of an automation file is malformed. The line numbers start at one.
+    Automation(usize),
+    /// No plugin with the given URI was found.
+    UnknownPlugin(String),
+    /// The plugin couldn't be instantiated.
+    Host(HostError),
+    /// Running the instance failed.
+    Graph(GraphError),
+    /// The plugin has no control input with the given symbol.
+    UnknownControl(String),
+    /// There are MIDI events, but the plugin has no atom input to receive them.
+    NoMidiInput,
+    /// The sample rate of the input differs from the sample rate of the instance.
+    SampleRate { instance: u32, input: u32 },
+}
+
+impl From<std::io::Error> for RenderError {
+    fn from(error: std::io::Error) -> Self {
+        RenderError::Io(error.kind())
+    }
+}
+
+impl From<HostError> for RenderError {
+    fn from(error: HostError) -> Self {
+        RenderError::Host(error)
+    }
+}
+
+impl From<GraphError> for RenderError {
+    fn from(error: GraphError) -> Self {
+        RenderError::Graph(error)
+    }
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderError::Io(kind) => write!(f, "I/O error: {:?}", kind),
+            RenderError::Wav(message) => write!(f, "Invalid WAV file: {}", message),
+            RenderError::Midi(message) => write!(f, "Invalid MIDI file: {}", message),
+            RenderError::Automation(line) => {
+                write!(f, "Invalid automation in line {}", line)
+            }
+            RenderError::UnknownPlugin(uri) => write!(f, "Unknown plugin <{}>", uri),
+            RenderError::Host(error) => write!(f, "Host error: {:?}", error),
+            RenderError::Graph(error) => write!(f, "Processing error: {:?}", error),
+            RenderError::UnknownControl(symbol) => {
+                write!(f, "The plugin has no control input `{}`", symbol)
+            }
+            RenderError::NoMidiInput => write!(f, "The plugin has no input for MIDI events"),
+            RenderError::SampleRate { instance, input } => write!(
+                f,
+                "The input has a sample rate of {} Hz, but the instance runs at {} Hz",
+                input, instance
+            ),
+        }
+    }
+}
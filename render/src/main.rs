@@ -0,0 +1,113 @@
+use lv2_host::{HostFeatures, World};
+use lv2_render::*;
+use std::fs::File;
+use std::io::BufReader;
+use std::process::exit;
+
+const USAGE: &str = "Usage: lv2-render PLUGIN INPUT OUTPUT [OPTIONS]
+
+Renders the INPUT WAV file through the plugin with the URI PLUGIN and writes the result to OUTPUT.
+
+Options:
+    --midi FILE             Send the events of a standard MIDI file to the plugin
+    --automation FILE       Change controls at the times of a CSV file with lines `time,symbol,value`
+    --control SYMBOL=VALUE  Set a control before rendering
+    --tail SECONDS          Render the given time after the end of the input";
+
+/// The size of the worker's buffers, in bytes.
+const WORKER_CAPACITY: usize = 1 << 16;
+
+/// The parsed command line.
+struct Arguments {
+    plugin: String,
+    input: String,
+    output: String,
+    midi: Option<String>,
+    automation: Option<String>,
+    controls: Vec<(String, f32)>,
+    tail: f64,
+}
+
+impl Arguments {
+    fn parse(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut positional = Vec::new();
+        let mut arguments = Arguments {
+            plugin: String::new(),
+            input: String::new(),
+            output: String::new(),
+            midi: None,
+            automation: None,
+            controls: Vec::new(),
+            tail: 0.0,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--midi" => arguments.midi = Some(args.next()?),
+                "--automation" => arguments.automation = Some(args.next()?),
+                "--control" => {
+                    let control = args.next()?;
+                    let mut parts = control.splitn(2, '=');
+                    let symbol = parts.next()?.to_owned();
+                    let value = parts.next()?.parse().ok()?;
+                    arguments.controls.push((symbol, value));
+                }
+                "--tail" => arguments.tail = args.next()?.parse().ok()?,
+                _ if arg.starts_with("--") => return None,
+                _ => positional.push(arg),
+            }
+        }
+        if positional.len() != 3 {
+            return None;
+        }
+        arguments.output = positional.pop()?;
+        arguments.input = positional.pop()?;
+        arguments.plugin = positional.pop()?;
+        Some(arguments)
+    }
+}
+
+fn run(arguments: Arguments) -> Result<(), RenderError> {
+    let input = Wav::open(&arguments.input)?;
+    let midi = match &arguments.midi {
+        Some(path) => read_midi_file(BufReader::new(File::open(path)?))?,
+        None => Vec::new(),
+    };
+    let automation = match &arguments.automation {
+        Some(path) => read_automation(BufReader::new(File::open(path)?))?,
+        None => Vec::new(),
+    };
+
+    let mut world = World::new();
+    world.load_all();
+    let plugin = world
+        .plugin(&arguments.plugin)
+        .ok_or_else(|| RenderError::UnknownPlugin(arguments.plugin.clone()))?;
+
+    let features = HostFeatures::new()
+        .with_urid_map()
+        .with_worker(WORKER_CAPACITY);
+    // Loading the library of the plugin runs its initialization code.
+    let instance = unsafe { plugin.instantiate(f64::from(input.sample_rate), &features) }?;
+    let mut renderer = Renderer::new(instance, plugin.ports(), input.sample_rate, &features);
+    for (symbol, value) in arguments.controls.iter() {
+        renderer.set_control(symbol, *value)?;
+    }
+
+    let tail = (arguments.tail * f64::from(input.sample_rate)).round() as usize;
+    let output = unsafe { renderer.render(&input, &midi, &automation, tail) }?;
+    output.save(&arguments.output)
+}
+
+fn main() {
+    let arguments = match Arguments::parse(std::env::args().skip(1)) {
+        Some(arguments) => arguments,
+        None => {
+            eprintln!("{}", USAGE);
+            exit(2);
+        }
+    };
+    if let Err(error) = run(arguments) {
+        eprintln!("{}", error);
+        exit(1);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::RenderError;
+use std::io::Read;
+
+/// The tempo of MIDI files that don't set a tempo, in microseconds per quarter note.
+const DEFAULT_TEMPO: u32 = 500_000;
+
+/// A MIDI message at a point in time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiEvent {
+    /// The time of the event, in seconds from the start of the rendering.
+    pub time: f64,
+    /// The bytes of the message, including its status byte.
+    pub message: Vec<u8>,
+}
+
+/// An event of a track, at a time in ticks.
+enum TrackEvent {
+    Tempo(u32),
+    Message(Vec<u8>),
+}
+
+/// Read the events of a standard MIDI file.
+///
+/// The events of all tracks are merged and their times are converted to seconds, following the tempo changes of the file. Meta events aren't returned, but system exclusive messages are.
+pub fn read_midi_file<R: Read>(mut reader: R) -> Result<Vec<MidiEvent>, RenderError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let mut cursor = Cursor { bytes: &bytes };
+
+    if cursor.take(4)? != b"MThd" {
+        return Err(invalid("Not a standard MIDI file"));
+    }
+    let length = cursor.u32()? as usize;
+    let header = cursor.take(length)?;
+    if header.len() < 6 {
+        return Err(invalid("Header is too short"));
+    }
+    let tracks = u16::from_be_bytes([header[2], header[3]]);
+    let division = u16::from_be_bytes([header[4], header[5]]);
+
+    let mut events: Vec<(u64, TrackEvent)> = Vec::new();
+    for _ in 0..tracks {
+        let kind = cursor.take(4)?;
+        let length = cursor.u32()? as usize;
+        let track = cursor.take(length)?;
+        if kind == b"MTrk" {
+            read_track(Cursor { bytes: track }, &mut events)?;
+        }
+    }
+    // The sort is stable, which keeps the order of events at the same time.
+    events.sort_by_key(|(tick, _)| *tick);
+
+    // With a positive division, ticks are fractions of a quarter note, whose length depends on the tempo. Otherwise, they're fractions of SMPTE frames.
+    let smpte = if division & 0x8000 != 0 {
+        let frames_per_second = -f64::from((division >> 8) as u8 as i8);
+        let ticks_per_frame = f64::from(division & 0xFF);
+        Some(1.0 / (frames_per_second * ticks_per_frame))
+    } else {
+        None
+    };
+    let ticks_per_quarter = f64::from(division.max(1));
+
+    let mut midi_events = Vec::new();
+    let mut tempo = DEFAULT_TEMPO;
+    let mut last_tick = 0;
+    let mut time = 0.0;
+    for (tick, event) in events {
+        let seconds_per_tick =
+            smpte.unwrap_or_else(|| f64::from(tempo) / 1_000_000.0 / ticks_per_quarter);
+        time += (tick - last_tick) as f64 * seconds_per_tick;
+        last_tick = tick;
+        match event {
+            TrackEvent::Tempo(new_tempo) => tempo = new_tempo,
+            TrackEvent::Message(message) => midi_events.push(MidiEvent { time, message }),
+        }
+    }
+    Ok(midi_events)
+}
+
+/// Read the events of a track, with their absolute times in ticks.
+fn read_track(mut cursor: Cursor, events: &mut Vec<(u64, TrackEvent)>) -> Result<(), RenderError> {
+    let mut tick: u64 = 0;
+    let mut running_status: Option<u8> = None;
+    while !cursor.bytes.is_empty() {
+        tick += u64::from(cursor.variable()?);
+        let mut status = cursor.byte()?;
+        match status {
+            0xFF => {
+                let kind = cursor.byte()?;
+                let length = cursor.variable()? as usize;
+                let data = cursor.take(length)?;
+                running_status = None;
+                match kind {
+                    // End of track
+                    0x2F => return Ok(()),
+                    // Set tempo
+                    0x51 if data.len() == 3 => events.push((
+                        tick,
+                        TrackEvent::Tempo(u32::from_be_bytes([0, data[0], data[1], data[2]])),
+                    )),
+                    _ => (),
+                }
+            }
+            0xF0 | 0xF7 => {
+                let length = cursor.variable()? as usize;
+                let data = cursor.take(length)?;
+                running_status = None;
+                // Escaped events that start with 0xF7 contain arbitrary bytes, which aren't sent.
+                if status == 0xF0 {
+                    let mut message = vec![0xF0];
+                    message.extend_from_slice(data);
+                    events.push((tick, TrackEvent::Message(message)));
+                }
+            }
+            _ => {
+                let mut message = Vec::with_capacity(3);
+                if status < 0x80 {
+                    // The status byte is omitted and this is already the first data byte.
+                    message.push(status);
+                    status = running_status.ok_or_else(|| invalid("Missing status byte"))?;
+                    message.insert(0, status);
+                } else {
+                    message.push(status);
+                    running_status = Some(status);
+                }
+                let length = match status & 0xF0 {
+                    0xC0 | 0xD0 => 2,
+                    _ => 3,
+                };
+                while message.len() < length {
+                    message.push(cursor.byte()?);
+                }
+                events.push((tick, TrackEvent::Message(message)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A reader of the bytes of a MIDI file.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], RenderError> {
+        if self.bytes.len() < length {
+            return Err(invalid("Unexpected end of file"));
+        }
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8, RenderError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, RenderError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read a variable-length quantity, which stores seven bits in every byte and sets the highest bit of all bytes but the last.
+    fn variable(&mut self) -> Result<u32, RenderError> {
+        let mut value: u32 = 0;
+        for _ in 0..4 {
+            let byte = self.byte()?;
+            value = (value << 7) | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("Variable-length quantity is too long"))
+    }
+}
+
+fn invalid(message: &str) -> RenderError {
+    RenderError::Midi(message.to_owned())
+}
@@ -0,0 +1,158 @@
+use crate::{AutomationPoint, MidiEvent, RenderError, Wav};
+use core::port::PortDirection;
+use host::{HostFeatures, Instance, NodeId, PortEntry, PortKind, ProcessGraph};
+
+/// The default number of frames that are processed at once.
+const BLOCK_SIZE: u32 = 512;
+
+/// A plugin instance that renders signals offline.
+///
+/// The renderer runs the instance in blocks of up to 512 frames. Blocks are split at automation points, which means that controls change at the exact frame of their points, and MIDI events are sent at their exact frames within a block.
+pub struct Renderer<'a> {
+    graph: ProcessGraph<'a>,
+    node: NodeId,
+    sample_rate: u32,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    controls: Vec<String>,
+    midi_input: Option<String>,
+}
+
+impl<'a> Renderer<'a> {
+    /// Create a renderer for an instance, together with the descriptions of its ports, like the [ports of its plugin](../lv2_host/struct.PluginEntry.html#method.ports).
+    ///
+    /// The sample rate has to be the one the instance was created with. The features are only used to map the URIDs of atom buffers, which are required to send MIDI events.
+    pub fn new(
+        instance: Instance<'a>,
+        ports: &[PortEntry],
+        sample_rate: u32,
+        features: &HostFeatures,
+    ) -> Self {
+        let symbols = |kind: PortKind, direction: PortDirection| -> Vec<String> {
+            ports
+                .iter()
+                .filter(|port| port.kind() == kind && port.direction() == Some(direction))
+                .map(|port| port.symbol().to_owned())
+                .collect()
+        };
+        let inputs = symbols(PortKind::Audio, PortDirection::Input);
+        let outputs = symbols(PortKind::Audio, PortDirection::Output);
+        let controls = symbols(PortKind::Control, PortDirection::Input);
+        let midi_input = symbols(PortKind::Atom, PortDirection::Input)
+            .into_iter()
+            .next()
+            .filter(|_| features.mapper().is_some());
+
+        let mut graph = ProcessGraph::new(BLOCK_SIZE, features);
+        let node = graph.add_node(instance, ports);
+        Self {
+            graph,
+            node,
+            sample_rate,
+            inputs,
+            outputs,
+            controls,
+            midi_input,
+        }
+    }
+
+    /// The symbols of the audio inputs, in the order they receive the channels of the input.
+    pub fn audio_inputs(&self) -> &[String] {
+        self.inputs.as_slice()
+    }
+
+    /// The symbols of the audio outputs, in the order of the channels of the output.
+    pub fn audio_outputs(&self) -> &[String] {
+        self.outputs.as_slice()
+    }
+
+    /// Set the value of a control input, which is kept until it's changed by automation.
+    pub fn set_control(&mut self, symbol: &str, value: f32) -> Result<(), RenderError> {
+        if !self.controls.iter().any(|control| control == symbol) {
+            return Err(RenderError::UnknownControl(symbol.to_owned()));
+        }
+        self.graph.set_control(self.node, symbol, value)?;
+        Ok(())
+    }
+
+    /// Render the input, followed by `tail` frames of silence, with the given MIDI events and automation points.
+    ///
+    /// The output has one channel for every audio output and as many frames as the input, plus the tail. Events and points have to be sorted by their time, like the ones that are read by [`read_midi_file`](fn.read_midi_file.html) and [`read_automation`](fn.read_automation.html), and the ones after the end of the output are ignored.
+    ///
+    /// # Safety
+    ///
+    /// The instance is run without any checks, which means that all ports that aren't optional have to be supported by the renderer.
+    pub unsafe fn render(
+        &mut self,
+        input: &Wav,
+        midi: &[MidiEvent],
+        automation: &[AutomationPoint],
+        tail: usize,
+    ) -> Result<Wav, RenderError> {
+        if input.sample_rate != self.sample_rate {
+            return Err(RenderError::SampleRate {
+                instance: self.sample_rate,
+                input: input.sample_rate,
+            });
+        }
+        if let Some(point) = automation
+            .iter()
+            .find(|point| !self.controls.contains(&point.symbol))
+        {
+            return Err(RenderError::UnknownControl(point.symbol.clone()));
+        }
+        if !midi.is_empty() && self.midi_input.is_none() {
+            return Err(RenderError::NoMidiInput);
+        }
+
+        let sample_rate = f64::from(self.sample_rate);
+        let to_frame = |time: f64| (time * sample_rate).round() as usize;
+        let length = input.frames() + tail;
+        let mut output = vec![Vec::with_capacity(length); self.outputs.len()];
+        let mut midi = midi.iter().peekable();
+        let mut automation = automation.iter().peekable();
+
+        let mut frame = 0;
+        while frame < length {
+            while let Some(point) = automation.next_if(|point| to_frame(point.time) <= frame) {
+                self.graph
+                    .set_control(self.node, &point.symbol, point.value)?;
+            }
+            let mut end = length.min(frame + BLOCK_SIZE as usize);
+            if let Some(point) = automation.peek() {
+                end = end.min(to_frame(point.time));
+            }
+
+            for (index, symbol) in self.inputs.iter().enumerate() {
+                let channel = match input.channels.len() {
+                    0 => None,
+                    channels => input.channels.get(index % channels),
+                };
+                if let Some(buffer) = self.graph.input_mut(self.node, symbol) {
+                    for (offset, sample) in buffer[..end - frame].iter_mut().enumerate() {
+                        *sample = channel
+                            .and_then(|channel| channel.get(frame + offset))
+                            .copied()
+                            .unwrap_or(0.0);
+                    }
+                }
+            }
+            if let Some(symbol) = self.midi_input.as_ref() {
+                while let Some(event) = midi.next_if(|event| to_frame(event.time) < end) {
+                    let time = to_frame(event.time).saturating_sub(frame) as u32;
+                    self.graph
+                        .write_midi(self.node, symbol, time, &event.message)?;
+                }
+            }
+
+            self.graph.process((end - frame) as u32)?;
+            for (channel, symbol) in output.iter_mut().zip(self.outputs.iter()) {
+                if let Some(buffer) = self.graph.output(self.node, symbol) {
+                    channel.extend_from_slice(&buffer[..end - frame]);
+                }
+            }
+            frame = end;
+        }
+        Ok(Wav::new(self.sample_rate, output))
+    }
+}
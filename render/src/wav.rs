@@ -0,0 +1,217 @@
+use crate::RenderError;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// The format tag of integer samples.
+const FORMAT_PCM: u16 = 1;
+/// The format tag of floating-point samples.
+const FORMAT_FLOAT: u16 = 3;
+/// The format tag of formats that store the actual format tag in an extension.
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// A multi-channel signal, as stored in a WAV file.
+///
+/// WAV files with 8, 16, 24 or 32 bit integer samples and 32 or 64 bit floating-point samples can be read, which are converted to `f32`s in the range from -1 to 1. Signals are always written as 32 bit floating-point samples, so that nothing is lost.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wav {
+    /// The sample rate of the signal, in Hertz.
+    pub sample_rate: u32,
+    /// The samples of every channel. All channels have the same length.
+    pub channels: Vec<Vec<f32>>,
+}
+
+impl Wav {
+    /// Create a signal with the given sample rate and channels.
+    pub fn new(sample_rate: u32, channels: Vec<Vec<f32>>) -> Self {
+        Self {
+            sample_rate,
+            channels,
+        }
+    }
+
+    /// The number of samples per channel.
+    pub fn frames(&self) -> usize {
+        self.channels.first().map_or(0, Vec::len)
+    }
+
+    /// Read a signal from a WAV file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, RenderError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Write the signal to a WAV file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), RenderError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a signal in the WAV format.
+    ///
+    /// Chunks other than the format and the data are skipped.
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, RenderError> {
+        let mut header = [0; 12];
+        reader.read_exact(&mut header)?;
+        if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+            return Err(invalid("Not a RIFF WAVE file"));
+        }
+
+        let mut format: Option<Format> = None;
+        loop {
+            let mut chunk = [0; 8];
+            if let Err(error) = reader.read_exact(&mut chunk) {
+                return Err(match error.kind() {
+                    std::io::ErrorKind::UnexpectedEof => invalid("Missing data chunk"),
+                    _ => error.into(),
+                });
+            }
+            let size = u32_at(&chunk, 4) as usize;
+            let mut body = vec![0; size];
+            reader.read_exact(&mut body)?;
+            if size % 2 == 1 {
+                // Chunks are padded to an even size, but some writers omit the padding of the last chunk.
+                let _ = reader.read(&mut [0]);
+            }
+
+            match &chunk[0..4] {
+                b"fmt " => format = Some(Format::parse(&body)?),
+                b"data" => {
+                    let format = format.ok_or_else(|| invalid("Data before format chunk"))?;
+                    return Ok(format.decode(&body));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Write the signal in the WAV format, with 32 bit floating-point samples.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), RenderError> {
+        let channels = self.channels.len() as u16;
+        let frames = self.frames();
+        let data_size = frames * self.channels.len() * 4;
+
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(4 + 26 + 12 + 8 + data_size as u32).to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+
+        writer.write_all(b"fmt ")?;
+        writer.write_all(&18u32.to_le_bytes())?;
+        writer.write_all(&FORMAT_FLOAT.to_le_bytes())?;
+        writer.write_all(&channels.to_le_bytes())?;
+        writer.write_all(&self.sample_rate.to_le_bytes())?;
+        writer.write_all(&(self.sample_rate * u32::from(channels) * 4).to_le_bytes())?;
+        writer.write_all(&(channels * 4).to_le_bytes())?;
+        writer.write_all(&32u16.to_le_bytes())?;
+        writer.write_all(&0u16.to_le_bytes())?;
+
+        // Files with floating-point samples need to state their number of frames.
+        writer.write_all(b"fact")?;
+        writer.write_all(&4u32.to_le_bytes())?;
+        writer.write_all(&(frames as u32).to_le_bytes())?;
+
+        writer.write_all(b"data")?;
+        writer.write_all(&(data_size as u32).to_le_bytes())?;
+        for frame in 0..frames {
+            for channel in self.channels.iter() {
+                writer.write_all(&channel[frame].to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The format of the samples in a WAV file.
+#[derive(Clone, Copy)]
+struct Format {
+    float: bool,
+    channels: usize,
+    sample_rate: u32,
+    bits: usize,
+}
+
+impl Format {
+    /// Parse the body of a format chunk.
+    fn parse(body: &[u8]) -> Result<Self, RenderError> {
+        if body.len() < 16 {
+            return Err(invalid("Format chunk is too short"));
+        }
+        let mut tag = u16_at(body, 0);
+        if tag == FORMAT_EXTENSIBLE {
+            // The extension starts with its size, the valid bits and the channel mask, followed by the GUID of the format, which starts with the format tag.
+            if body.len() < 26 {
+                return Err(invalid("Format extension is too short"));
+            }
+            tag = u16_at(body, 24);
+        }
+        let format = Self {
+            float: tag == FORMAT_FLOAT,
+            channels: usize::from(u16_at(body, 2)),
+            sample_rate: u32_at(body, 4),
+            bits: usize::from(u16_at(body, 14)),
+        };
+        let supported = match tag {
+            FORMAT_PCM => [8, 16, 24, 32].contains(&format.bits),
+            FORMAT_FLOAT => [32, 64].contains(&format.bits),
+            _ => false,
+        };
+        if !supported {
+            return Err(RenderError::Wav(format!(
+                "Unsupported format {} with {} bits",
+                tag, format.bits
+            )));
+        }
+        if format.channels == 0 {
+            return Err(invalid("No channels"));
+        }
+        Ok(format)
+    }
+
+    /// Convert the body of a data chunk to a signal.
+    fn decode(&self, data: &[u8]) -> Wav {
+        let width = self.bits / 8;
+        let frames = data.len() / (width * self.channels);
+        let mut channels = vec![Vec::with_capacity(frames); self.channels];
+        for (index, sample) in data.chunks_exact(width).enumerate() {
+            if index >= frames * self.channels {
+                break;
+            }
+            let value = match (self.float, self.bits) {
+                (true, 32) => f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]),
+                (true, _) => {
+                    let mut bytes = [0; 8];
+                    bytes.copy_from_slice(sample);
+                    f64::from_le_bytes(bytes) as f32
+                }
+                // Samples with 8 bits are unsigned, while all other integer samples are signed.
+                (false, 8) => (f32::from(sample[0]) - 128.0) / 128.0,
+                (false, _) => {
+                    // The sample is moved to the upper bytes of an `i32`, so that its sign is kept.
+                    let mut bytes = [0; 4];
+                    bytes[4 - width..].copy_from_slice(sample);
+                    (i32::from_le_bytes(bytes) as f64 / 2_147_483_648.0) as f32
+                }
+            };
+            channels[index % self.channels].push(value);
+        }
+        Wav::new(self.sample_rate, channels)
+    }
+}
+
+fn invalid(message: &str) -> RenderError {
+    RenderError::Wav(message.to_owned())
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
@@ -0,0 +1,336 @@
+use lv2_atom::prelude::*;
+use lv2_core::prelude::*;
+use lv2_host::*;
+use lv2_midi::MidiURIDCollection;
+use lv2_render::*;
+use lv2_ttl_gen::*;
+use lv2_units::prelude::*;
+use lv2_urid::*;
+use std::path::PathBuf;
+use urid::*;
+
+#[derive(PortCollection)]
+struct AmpPorts {
+    gain: InputPort<Control>,
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+#[uri("urn:lv2_render:test:amp")]
+struct Amp;
+
+impl Plugin for Amp {
+    type Ports = AmpPorts;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut AmpPorts, _: &mut RunContext<()>) {
+        let coef = *ports.gain;
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = input * coef;
+        }
+    }
+}
+
+#[derive(PortCollection)]
+struct GatePorts {
+    control: InputPort<AtomPort>,
+    output: OutputPort<Audio>,
+}
+
+#[derive(FeatureCollection)]
+struct GateFeatures<'a> {
+    map: LV2Map<'a>,
+}
+
+#[derive(URIDCollection)]
+struct GateURIDs {
+    atom: AtomURIDCollection,
+    midi: MidiURIDCollection,
+    unit: UnitURIDCollection,
+}
+
+/// Outputs one while a note is on and zero otherwise, with sample accuracy.
+#[uri("urn:lv2_render:test:gate")]
+struct Gate {
+    level: f32,
+    urids: GateURIDs,
+}
+
+impl Plugin for Gate {
+    type Ports = GatePorts;
+    type InitFeatures = GateFeatures<'static>;
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, features: &mut GateFeatures<'static>) -> Option<Self> {
+        Some(Self {
+            level: 0.0,
+            urids: features.map.populate_collection()?,
+        })
+    }
+
+    fn run(&mut self, ports: &mut GatePorts, _: &mut RunContext<()>) {
+        let sequence = ports
+            .control
+            .read(self.urids.atom.sequence, self.urids.unit.beat)
+            .unwrap();
+        let mut offset = 0;
+        for (timestamp, event) in sequence {
+            let time = timestamp.as_frames().unwrap() as usize;
+            let message = event.read(self.urids.midi.raw, ()).unwrap();
+            for sample in ports.output[offset..time].iter_mut() {
+                *sample = self.level;
+            }
+            offset = time;
+            match message {
+                [status, _, velocity] if status & 0xF0 == 0x90 && *velocity > 0 => self.level = 1.0,
+                [status, _, _] if status & 0xF0 == 0x80 || status & 0xF0 == 0x90 => {
+                    self.level = 0.0
+                }
+                _ => (),
+            }
+        }
+        for sample in ports.output[offset..].iter_mut() {
+            *sample = self.level;
+        }
+    }
+}
+
+lv2_descriptors![Amp, Gate];
+
+fn write_bundle(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&directory);
+    BundleDescription::new(&binary_name("lv2_render_test"))
+        .plugin(
+            PluginDescription::new::<Amp>().port_range("gain", 0.0, 1.0, 4.0),
+            "amp.ttl",
+        )
+        .plugin(PluginDescription::new::<Gate>(), "gate.ttl")
+        .write(&directory.join("render.lv2"))
+        .unwrap();
+    directory
+}
+
+fn renderer<'a>(
+    world: &World,
+    uri: &str,
+    sample_rate: u32,
+    features: &'a HostFeatures,
+) -> Renderer<'a> {
+    let ports = world.plugin(uri).unwrap().ports();
+    let descriptor = unsafe { find_descriptor(lv2_descriptor, uri) }.unwrap();
+    let instance = unsafe {
+        Instance::from_descriptor(
+            descriptor,
+            &std::env::temp_dir(),
+            f64::from(sample_rate),
+            features,
+        )
+    }
+    .unwrap();
+    Renderer::new(instance, ports, sample_rate, features)
+}
+
+#[test]
+fn test_wav() {
+    let wav = Wav::new(48000, vec![vec![0.0, 0.5, -1.0], vec![0.25, -0.25, 1.0]]);
+    let mut bytes = Vec::new();
+    wav.write(&mut bytes).unwrap();
+    assert_eq!(Wav::read(bytes.as_slice()).unwrap(), wav);
+
+    // A mono file with 16 bit samples and an unknown chunk before the data.
+    let mut pcm: Vec<u8> = Vec::new();
+    pcm.extend_from_slice(b"RIFF");
+    pcm.extend_from_slice(&46u32.to_le_bytes());
+    pcm.extend_from_slice(b"WAVE");
+    pcm.extend_from_slice(b"fmt ");
+    pcm.extend_from_slice(&16u32.to_le_bytes());
+    for field in [1u16, 1].iter() {
+        pcm.extend_from_slice(&field.to_le_bytes());
+    }
+    pcm.extend_from_slice(&8000u32.to_le_bytes());
+    pcm.extend_from_slice(&16000u32.to_le_bytes());
+    for field in [2u16, 16].iter() {
+        pcm.extend_from_slice(&field.to_le_bytes());
+    }
+    pcm.extend_from_slice(b"LIST");
+    pcm.extend_from_slice(&1u32.to_le_bytes());
+    pcm.extend_from_slice(&[0, 0]);
+    pcm.extend_from_slice(b"data");
+    pcm.extend_from_slice(&4u32.to_le_bytes());
+    for sample in [16384i16, -32768].iter() {
+        pcm.extend_from_slice(&sample.to_le_bytes());
+    }
+    assert_eq!(
+        Wav::read(pcm.as_slice()).unwrap(),
+        Wav::new(8000, vec![vec![0.5, -1.0]])
+    );
+
+    assert_eq!(
+        Wav::read(&pcm[..pcm.len() - 12]),
+        Err(RenderError::Wav("Missing data chunk".to_owned()))
+    );
+    assert!(Wav::read(&b"RIFX\0\0\0\0WAVE"[..]).is_err());
+}
+
+#[test]
+fn test_midi_file() {
+    let mut file: Vec<u8> = Vec::new();
+    // Format 1 with two tracks and 96 ticks per quarter note.
+    file.extend_from_slice(b"MThd\0\0\0\x06\0\x01\0\x02\0\x60");
+    // The tempo track sets 120 BPM at first and 60 BPM after a quarter note.
+    let tempo: &[u8] = &[
+        0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20, 0x60, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40, 0x00,
+        0xFF, 0x2F, 0x00,
+    ];
+    file.extend_from_slice(b"MTrk");
+    file.extend_from_slice(&(tempo.len() as u32).to_be_bytes());
+    file.extend_from_slice(tempo);
+    // The note track uses running status and a variable-length delta of 192 ticks.
+    let notes: &[u8] = &[
+        0x00, 0x90, 0x3C, 0x64, 0x81, 0x40, 0x3C, 0x00, 0x00, 0xC0, 0x05, 0x00, 0xF0, 0x02, 0x7E,
+        0xF7, 0x00, 0xFF, 0x2F, 0x00,
+    ];
+    file.extend_from_slice(b"MTrk");
+    file.extend_from_slice(&(notes.len() as u32).to_be_bytes());
+    file.extend_from_slice(notes);
+
+    let events = read_midi_file(file.as_slice()).unwrap();
+    let expected = [
+        (0.0, vec![0x90, 0x3C, 0x64]),
+        (1.5, vec![0x90, 0x3C, 0x00]),
+        (1.5, vec![0xC0, 0x05]),
+        (1.5, vec![0xF0, 0x7E, 0xF7]),
+    ];
+    assert_eq!(events.len(), expected.len());
+    for (event, (time, message)) in events.iter().zip(expected.iter()) {
+        assert!((event.time - time).abs() < 1e-9);
+        assert_eq!(&event.message, message);
+    }
+
+    assert_eq!(
+        read_midi_file(&file[..30]),
+        Err(RenderError::Midi("Unexpected end of file".to_owned()))
+    );
+}
+
+#[test]
+fn test_automation() {
+    let csv = "time,symbol,value\n# Fade out\n1.5, gain, 0\n\n0,gain,1\n";
+    assert_eq!(
+        read_automation(csv.as_bytes()).unwrap(),
+        vec![
+            AutomationPoint {
+                time: 0.0,
+                symbol: "gain".to_owned(),
+                value: 1.0,
+            },
+            AutomationPoint {
+                time: 1.5,
+                symbol: "gain".to_owned(),
+                value: 0.0,
+            },
+        ]
+    );
+    assert_eq!(
+        read_automation("0,gain,1\n1,gain\n".as_bytes()),
+        Err(RenderError::Automation(2))
+    );
+    assert_eq!(
+        read_automation("-1,gain,1\n".as_bytes()),
+        Err(RenderError::Automation(1))
+    );
+}
+
+#[test]
+fn test_render() {
+    let directory = write_bundle("lv2_render_render");
+    let mut world = World::new();
+    world.load_path(directory.as_os_str());
+    let features = HostFeatures::new().with_urid_map();
+    let mut renderer = renderer(&world, "urn:lv2_render:test:amp", 1000, &features);
+    assert_eq!(renderer.audio_inputs(), ["input"]);
+    assert_eq!(renderer.audio_outputs(), ["output"]);
+
+    let input = Wav::new(1000, vec![vec![1.0; 1000]]);
+    let automation = read_automation("0.25,gain,2\n0.6,gain,0.5\n".as_bytes()).unwrap();
+    let output = unsafe { renderer.render(&input, &[], &automation, 100) }.unwrap();
+    assert_eq!(output.sample_rate, 1000);
+    assert_eq!(output.channels.len(), 1);
+    let output = &output.channels[0];
+    assert_eq!(output.len(), 1100);
+    assert!(output[..250].iter().all(|sample| *sample == 1.0));
+    assert!(output[250..600].iter().all(|sample| *sample == 2.0));
+    assert!(output[600..1000].iter().all(|sample| *sample == 0.5));
+    assert!(output[1000..].iter().all(|sample| *sample == 0.0));
+
+    renderer.set_control("gain", 3.0).unwrap();
+    let output = unsafe { renderer.render(&input, &[], &[], 0) }.unwrap();
+    assert!(output.channels[0].iter().all(|sample| *sample == 3.0));
+
+    assert_eq!(
+        renderer.set_control("output", 1.0),
+        Err(RenderError::UnknownControl("output".to_owned()))
+    );
+    let automation = read_automation("0,volume,1\n".as_bytes()).unwrap();
+    assert_eq!(
+        unsafe { renderer.render(&input, &[], &automation, 0) },
+        Err(RenderError::UnknownControl("volume".to_owned()))
+    );
+    let midi = [MidiEvent {
+        time: 0.0,
+        message: vec![0x90, 0x3C, 0x64],
+    }];
+    assert_eq!(
+        unsafe { renderer.render(&input, &midi, &[], 0) },
+        Err(RenderError::NoMidiInput)
+    );
+    assert_eq!(
+        unsafe { renderer.render(&Wav::new(44100, vec![]), &[], &[], 0) },
+        Err(RenderError::SampleRate {
+            instance: 1000,
+            input: 44100
+        })
+    );
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_render_midi() {
+    let directory = write_bundle("lv2_render_midi");
+    let mut world = World::new();
+    world.load_path(directory.as_os_str());
+    let features = HostFeatures::new().with_urid_map();
+    let mut renderer = renderer(&world, "urn:lv2_render:test:gate", 1000, &features);
+    assert!(renderer.audio_inputs().is_empty());
+
+    // The events span multiple blocks and the last one is after the end.
+    let midi = [
+        (0.1, vec![0x90, 0x3C, 0x64]),
+        (0.7, vec![0x80, 0x3C, 0x00]),
+        (0.9, vec![0x90, 0x3E, 0x64]),
+        (0.95, vec![0x90, 0x3E, 0x00]),
+        (2.0, vec![0x90, 0x3C, 0x64]),
+    ]
+    .iter()
+    .map(|(time, message)| MidiEvent {
+        time: *time,
+        message: message.clone(),
+    })
+    .collect::<Vec<MidiEvent>>();
+    let output = unsafe { renderer.render(&Wav::new(1000, vec![]), &midi, &[], 1000) }.unwrap();
+    let output = &output.channels[0];
+    assert_eq!(output.len(), 1000);
+    for (frame, sample) in output.iter().enumerate() {
+        let expected = (100..700).contains(&frame) || (900..950).contains(&frame);
+        assert_eq!(*sample, if expected { 1.0 } else { 0.0 }, "frame {}", frame);
+    }
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,190 @@
+//! Extension for LV2 plugins to request bigger port buffers from the host.
+//!
+//! The size of the buffers of atom ports is chosen by the host, but plugins that write a variable amount of data, for example big messages to their UI, may need more space than the host provides. The [resize-port extension](https://lv2plug.in/ns/ext/resize-port/resize-port.html) covers two ways to solve this: A plugin can declare the minimum size of a port's buffer in its metadata with `rsz:minimumSize`, and it can request a bigger buffer at runtime with the [`ResizePort`](struct.ResizePort.html) feature.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_resize_port::*;
+//! use urid::*;
+//!
+//! #[derive(FeatureCollection)]
+//! pub struct AudioFeatures<'a> {
+//!     resize: ResizePort<'a>,
+//! }
+//!
+//! #[uri("urn:lv2_resize_port:dumper")]
+//! struct Dumper {
+//!     buffer_size: usize,
+//!     dump: Vec<u8>,
+//! }
+//!
+//! impl Plugin for Dumper {
+//!     type Ports = ();
+//!     type InitFeatures = ();
+//!     type AudioFeatures = AudioFeatures<'static>;
+//!
+//!     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+//!         Some(Self {
+//!             buffer_size: 8192,
+//!             dump: Vec::with_capacity(65536),
+//!         })
+//!     }
+//!
+//!     fn run(&mut self, _: &mut (), context: &mut RunContext<AudioFeatures<'static>>) {
+//!         // Request a bigger buffer for the output port with the index 0 if the dump doesn't fit.
+//!         if self.dump.len() > self.buffer_size {
+//!             if context.features().resize.resize(0, self.dump.len()).is_ok() {
+//!                 // The new buffer is passed to the next call of `run`.
+//!                 self.buffer_size = self.dump.len();
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+extern crate lv2_core as core;
+extern crate lv2_sys as sys;
+
+use core::feature::*;
+use std::ffi::c_void;
+use urid::*;
+
+/// The property of a port that declares the minimum size of its buffer in bytes.
+pub struct MinimumSize;
+
+unsafe impl UriBound for MinimumSize {
+    const URI: &'static [u8] = sys::LV2_RESIZE_PORT__minimumSize;
+}
+
+/// The property of a port that declares that its buffer is at least as large as the buffer of another port.
+pub struct AsLargeAs;
+
+unsafe impl UriBound for AsLargeAs {
+    const URI: &'static [u8] = sys::LV2_RESIZE_PORT__asLargeAs;
+}
+
+/// Errors potentially generated by the [`ResizePort::resize`](struct.ResizePort.html#method.resize) method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResizePortError {
+    /// The kind of the error is unknown or doesn't have a representation.
+    Unknown,
+    /// The host doesn't have enough space for the new buffer.
+    NoSpace,
+}
+
+/// Host feature to resize the buffers of ports.
+///
+/// This feature is only available in the audio threading class.
+#[repr(transparent)]
+pub struct ResizePort<'a> {
+    internal: &'a sys::LV2_Resize_Port_Resize,
+}
+
+unsafe impl<'a> UriBound for ResizePort<'a> {
+    const URI: &'static [u8] = sys::LV2_RESIZE_PORT__resize;
+}
+
+unsafe impl<'a> Feature for ResizePort<'a> {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[ThreadingClass::Audio];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+        (feature as *const sys::LV2_Resize_Port_Resize)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+impl<'a> ResizePort<'a> {
+    /// Request the host to resize the buffer of the port with the given index to at least `size` bytes.
+    ///
+    /// If successful, the host connects the port to the new buffer and preserves the contents of the old one. Since the ports of a plugin are only collected at the start of a `run` call, the new buffer is passed to the next call. The port's handle must not be used for the rest of the current call, since the old buffer may already be gone.
+    ///
+    /// If the request fails, the port is still connected to its old buffer and the plugin has to cope with its size.
+    pub fn resize(&self, index: u32, size: usize) -> Result<(), ResizePortError> {
+        let resize = self.internal.resize.ok_or(ResizePortError::Unknown)?;
+        match unsafe { (resize)(self.internal.data, index, size) } {
+            sys::LV2_Resize_Port_Status_LV2_RESIZE_PORT_SUCCESS => Ok(()),
+            sys::LV2_Resize_Port_Status_LV2_RESIZE_PORT_ERR_NO_SPACE => {
+                Err(ResizePortError::NoSpace)
+            }
+            _ => Err(ResizePortError::Unknown),
+        }
+    }
+}
+
+/// Prelude of `lv2_resize_port` for wildcard usage.
+pub mod prelude {
+    pub use crate::{ResizePort, ResizePortError};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    unsafe extern "C" fn resize(
+        data: sys::LV2_Resize_Port_Feature_Data,
+        index: u32,
+        size: usize,
+    ) -> sys::LV2_Resize_Port_Status {
+        let sizes = &mut *(data as *mut Vec<usize>);
+        match sizes.get_mut(index as usize) {
+            Some(port_size) if size <= 1024 => {
+                *port_size = (*port_size).max(size);
+                sys::LV2_Resize_Port_Status_LV2_RESIZE_PORT_SUCCESS
+            }
+            Some(_) => sys::LV2_Resize_Port_Status_LV2_RESIZE_PORT_ERR_NO_SPACE,
+            None => sys::LV2_Resize_Port_Status_LV2_RESIZE_PORT_ERR_UNKNOWN,
+        }
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut sizes: Vec<usize> = vec![128, 256];
+        let raw = sys::LV2_Resize_Port_Resize {
+            data: &mut sizes as *mut Vec<usize> as *mut c_void,
+            resize: Some(resize),
+        };
+        let feature = unsafe {
+            ResizePort::from_feature_ptr(
+                &raw as *const sys::LV2_Resize_Port_Resize as *const c_void,
+                ThreadingClass::Audio,
+            )
+        }
+        .unwrap();
+
+        assert_eq!(feature.resize(0, 512), Ok(()));
+        assert_eq!(feature.resize(1, 64), Ok(()));
+        assert_eq!(feature.resize(1, 2048), Err(ResizePortError::NoSpace));
+        assert_eq!(feature.resize(2, 512), Err(ResizePortError::Unknown));
+        assert_eq!(sizes, vec![512, 256]);
+    }
+
+    #[test]
+    fn test_resize_outside_audio_class() {
+        let raw = sys::LV2_Resize_Port_Resize {
+            data: std::ptr::null_mut(),
+            resize: Some(resize),
+        };
+        let result = unsafe {
+            ResizePort::from_feature_ptr(
+                &raw as *const sys::LV2_Resize_Port_Resize as *const c_void,
+                ThreadingClass::Instantiation,
+            )
+        };
+        assert_eq!(
+            result.err(),
+            Some(FeatureError::WrongThreadingClass {
+                uri: ResizePort::uri(),
+                class: ThreadingClass::Instantiation,
+            })
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{Sample, SampleError};
+use std::io::{self, Read};
+
+/// The format tag of integer samples in a WAV file.
+const WAV_PCM: u16 = 1;
+/// The format tag of floating-point samples in a WAV file.
+const WAV_FLOAT: u16 = 3;
+/// The format tag of WAV files that store the actual format tag in an extension.
+const WAV_EXTENSIBLE: u16 = 0xFFFE;
+
+/// The container of an audio file.
+#[derive(Clone, Copy, PartialEq)]
+enum Container {
+    Wav,
+    Aiff,
+    Aifc,
+}
+
+/// How a single sample is encoded.
+#[derive(Clone, Copy, PartialEq)]
+enum Encoding {
+    /// Unsigned integers with an offset, as used by WAV files with 8 bits.
+    Unsigned,
+    /// Signed integers in two's complement.
+    Signed,
+    /// IEEE floating-point numbers.
+    Float,
+}
+
+/// The format of the samples in an audio file.
+#[derive(Clone, Copy)]
+struct Format {
+    encoding: Encoding,
+    big_endian: bool,
+    channels: usize,
+    sample_rate: f64,
+    bits: usize,
+}
+
+/// Read a sample from a WAV or AIFF file.
+///
+/// The container is detected from the header of the file. Chunks other than the format and the data are skipped.
+pub fn read<R: Read>(mut reader: R) -> Result<Sample, SampleError> {
+    let mut header = [0; 12];
+    reader.read_exact(&mut header)?;
+    let container = match (&header[0..4], &header[8..12]) {
+        (b"RIFF", b"WAVE") => Container::Wav,
+        (b"FORM", b"AIFF") => Container::Aiff,
+        (b"FORM", b"AIFC") => Container::Aifc,
+        _ => return Err(invalid("Not a WAV or AIFF file")),
+    };
+    let (format_id, data_id): (&[u8], &[u8]) = if container == Container::Wav {
+        (b"fmt ", b"data")
+    } else {
+        (b"COMM", b"SSND")
+    };
+
+    // AIFF files may store the sound data before the format, which is why both are collected first.
+    let mut format: Option<Format> = None;
+    let mut data: Option<Vec<u8>> = None;
+    while format.is_none() || data.is_none() {
+        let mut chunk = [0; 8];
+        if let Err(error) = reader.read_exact(&mut chunk) {
+            return Err(match error.kind() {
+                io::ErrorKind::UnexpectedEof if format.is_none() => invalid("Missing format chunk"),
+                io::ErrorKind::UnexpectedEof => invalid("Missing data chunk"),
+                _ => error.into(),
+            });
+        }
+        let size = if container == Container::Wav {
+            u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]])
+        } else {
+            u32::from_be_bytes([chunk[4], chunk[5], chunk[6], chunk[7]])
+        };
+
+        let mut chunk_reader = (&mut reader).take(u64::from(size));
+        if &chunk[0..4] == format_id || &chunk[0..4] == data_id {
+            // The body is read up to its end, which may be missing if the file is truncated.
+            let mut body = Vec::new();
+            chunk_reader.read_to_end(&mut body)?;
+            if &chunk[0..4] == format_id {
+                format = Some(match container {
+                    Container::Wav => parse_wav_format(&body)?,
+                    _ => parse_aiff_format(&body, container == Container::Aifc)?,
+                });
+            } else {
+                data = Some(body);
+            }
+        } else {
+            io::copy(&mut chunk_reader, &mut io::sink())?;
+        }
+        if size % 2 == 1 {
+            // Chunks are padded to an even size, but some writers omit the padding of the last chunk.
+            let _ = reader.read(&mut [0]);
+        }
+    }
+
+    let (format, mut data) = (format.unwrap(), data.unwrap());
+    if container != Container::Wav {
+        // The sound data chunk starts with the offset of the first sample and the block size.
+        let offset = data
+            .get(0..4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+            .ok_or_else(|| invalid("Sound data chunk is too short"))?;
+        data.drain(..(8 + offset).min(data.len()));
+    }
+    Ok(format.decode(&data))
+}
+
+/// Parse the body of the format chunk of a WAV file.
+fn parse_wav_format(body: &[u8]) -> Result<Format, SampleError> {
+    if body.len() < 16 {
+        return Err(invalid("Format chunk is too short"));
+    }
+    let mut tag = u16::from_le_bytes([body[0], body[1]]);
+    if tag == WAV_EXTENSIBLE {
+        // The extension starts with its size, the valid bits and the channel mask, followed by the GUID of the format, which starts with the format tag.
+        if body.len() < 26 {
+            return Err(invalid("Format extension is too short"));
+        }
+        tag = u16::from_le_bytes([body[24], body[25]]);
+    }
+    let bits = usize::from(u16::from_le_bytes([body[14], body[15]]));
+    let encoding = match (tag, bits) {
+        (WAV_PCM, 8) => Encoding::Unsigned,
+        (WAV_PCM, 16) | (WAV_PCM, 24) | (WAV_PCM, 32) => Encoding::Signed,
+        (WAV_FLOAT, 32) | (WAV_FLOAT, 64) => Encoding::Float,
+        _ => {
+            return Err(SampleError::Format(format!(
+                "Unsupported WAV format {} with {} bits",
+                tag, bits
+            )))
+        }
+    };
+    Format {
+        encoding,
+        big_endian: false,
+        channels: usize::from(u16::from_le_bytes([body[2], body[3]])),
+        sample_rate: f64::from(u32::from_le_bytes([body[4], body[5], body[6], body[7]])),
+        bits,
+    }
+    .validate()
+}
+
+/// Parse the body of the common chunk of an AIFF or AIFF-C file.
+fn parse_aiff_format(body: &[u8], compressed: bool) -> Result<Format, SampleError> {
+    if body.len() < 18 || (compressed && body.len() < 22) {
+        return Err(invalid("Common chunk is too short"));
+    }
+    // Samples that don't fill their bytes are stored in the upper bits.
+    let bits = usize::from(u16::from_be_bytes([body[6], body[7]])).div_ceil(8) * 8;
+    let compression: &[u8] = if compressed { &body[18..22] } else { b"NONE" };
+    let (encoding, big_endian, bits) = match (compression, bits) {
+        (b"NONE", 8..=32) | (b"twos", 8..=32) => (Encoding::Signed, true, bits),
+        (b"sowt", 8..=32) => (Encoding::Signed, false, bits),
+        (b"fl32", _) | (b"FL32", _) => (Encoding::Float, true, 32),
+        (b"fl64", _) | (b"FL64", _) => (Encoding::Float, true, 64),
+        _ => {
+            return Err(SampleError::Format(format!(
+                "Unsupported AIFF compression `{}` with {} bits",
+                String::from_utf8_lossy(compression),
+                bits
+            )))
+        }
+    };
+    Format {
+        encoding,
+        big_endian,
+        channels: usize::from(u16::from_be_bytes([body[0], body[1]])),
+        sample_rate: extended(&body[8..18]),
+        bits,
+    }
+    .validate()
+}
+
+impl Format {
+    fn validate(self) -> Result<Self, SampleError> {
+        if self.channels == 0 {
+            Err(invalid("No channels"))
+        } else if self.sample_rate.is_nan() || self.sample_rate <= 0.0 {
+            Err(invalid("Invalid sample rate"))
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Convert the sample data to a sample.
+    fn decode(&self, data: &[u8]) -> Sample {
+        let width = self.bits / 8;
+        let frames = data.len() / (width * self.channels);
+        let mut samples = vec![0.0; frames * self.channels];
+        for (index, sample) in data.chunks_exact(width).take(samples.len()).enumerate() {
+            // The bytes are brought into little-endian order first.
+            let mut bytes = [0; 8];
+            bytes[..width].copy_from_slice(sample);
+            if self.big_endian {
+                bytes[..width].reverse();
+            }
+            let value = match (self.encoding, width) {
+                (Encoding::Float, 4) => {
+                    f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                }
+                (Encoding::Float, _) => f64::from_le_bytes(bytes) as f32,
+                (Encoding::Unsigned, _) => (f32::from(bytes[0]) - 128.0) / 128.0,
+                (Encoding::Signed, _) => {
+                    // The sample is moved to the upper bytes of an `i32`, so that its sign is kept.
+                    let mut upper = [0; 4];
+                    upper[4 - width..].copy_from_slice(&bytes[..width]);
+                    (i32::from_le_bytes(upper) as f64 / 2_147_483_648.0) as f32
+                }
+            };
+            samples[(index % self.channels) * frames + index / self.channels] = value;
+        }
+        Sample::from_planar(self.sample_rate, self.channels, samples.into_boxed_slice())
+    }
+}
+
+/// Convert an 80 bit extended precision number, as used for the sample rate of AIFF files.
+fn extended(bytes: &[u8]) -> f64 {
+    let exponent = i32::from(u16::from_be_bytes([bytes[0], bytes[1]]) & 0x7FFF);
+    let mut mantissa = [0; 8];
+    mantissa.copy_from_slice(&bytes[2..10]);
+    let mantissa = u64::from_be_bytes(mantissa);
+    if exponent == 0 && mantissa == 0 {
+        return 0.0;
+    }
+    let value = mantissa as f64 * 2f64.powi(exponent - 16383 - 63);
+    if bytes[0] & 0x80 != 0 {
+        -value
+    } else {
+        value
+    }
+}
+
+fn invalid(message: &str) -> SampleError {
+    SampleError::Format(message.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended() {
+        assert_eq!(
+            extended(&[0x40, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0]),
+            44100.0
+        );
+        assert_eq!(
+            extended(&[0x40, 0x0E, 0xBB, 0x80, 0, 0, 0, 0, 0, 0]),
+            48000.0
+        );
+        assert_eq!(extended(&[0xBF, 0xFF, 0x80, 0, 0, 0, 0, 0, 0, 0]), -1.0);
+        assert_eq!(extended(&[0; 10]), 0.0);
+    }
+}
@@ -0,0 +1,134 @@
+//! Loading of audio samples in the worker thread.
+//!
+//! Plugins like samplers and convolution reverbs play back audio files, but these can't be loaded in the `run()` context: Reading a file blocks and allocating the buffer isn't real-time safe. Instead, the file is loaded by the [worker](../lv2_worker/index.html), which sends the finished buffer to the `run()` context in a response.
+//!
+//! This crate contains the part that runs in the worker: A [`SampleLoader`](struct.SampleLoader.html) reads a WAV or AIFF file, resamples it to the sample rate of the plugin and optionally normalizes it. The result is a [`Sample`](struct.Sample.html), which owns a single buffer and can be read in the `run()` context without allocating or locking. Since a boxed sample is sent as a pointer, it can be used as the response data of the worker as it is, and swapped in with an [`RtSwapCell`](../lv2_worker/struct.RtSwapCell.html).
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_sample::prelude::*;
+//! use lv2_worker::*;
+//! use std::path::PathBuf;
+//! use urid::*;
+//!
+//! #[derive(FeatureCollection)]
+//! struct AudioFeatures<'a> {
+//!     schedule: Schedule<'a, Sampler>,
+//! }
+//!
+//! enum Work {
+//!     /// Load a file for the given sample rate.
+//!     Load(PathBuf, f64),
+//!     /// Deallocate a sample that is no longer played.
+//!     Free(Retired<Sample>),
+//! }
+//!
+//! struct Sampler {
+//!     sample: RtSwapCell<Sample>,
+//!     position: f64,
+//! }
+//!
+//! unsafe impl UriBound for Sampler {
+//!     const URI: &'static [u8] = b"urn:rust-lv2-example:sampler\0";
+//! }
+//!
+//! impl Plugin for Sampler {
+//!     type Ports = ();
+//!     type InitFeatures = ();
+//!     type AudioFeatures = AudioFeatures<'static>;
+//!
+//!     fn new(_plugin_info: &PluginInfo, _features: &mut ()) -> Option<Self> {
+//!         Some(Self {
+//!             sample: RtSwapCell::empty(),
+//!             position: 0.0,
+//!         })
+//!     }
+//!
+//!     fn run(&mut self, _ports: &mut (), _context: &mut RunContext<AudioFeatures<'static>>) {
+//!         if let Some(sample) = self.sample.get() {
+//!             // Reading the sample is real-time safe.
+//!             let _value = sample.read(0, self.position);
+//!             self.position += 1.0;
+//!         }
+//!     }
+//! }
+//!
+//! impl Worker for Sampler {
+//!     type WorkData = Work;
+//!     type ResponseData = Box<Sample>;
+//!
+//!     fn work(response_handler: &ResponseHandler<Self>, data: Work) -> Result<(), WorkerError> {
+//!         match data {
+//!             Work::Load(path, sample_rate) => {
+//!                 let sample = SampleLoader::new()
+//!                     .sample_rate(sample_rate)
+//!                     .normalize(1.0)
+//!                     .open(path)
+//!                     .map_err(|_| WorkerError::Unknown)?;
+//!                 response_handler
+//!                     .respond(Box::new(sample))
+//!                     .map_err(|_| WorkerError::Unknown)
+//!             }
+//!             Work::Free(retired) => {
+//!                 drop(retired.into_inner());
+//!                 Ok(())
+//!             }
+//!         }
+//!     }
+//!
+//!     fn work_response(
+//!         &mut self,
+//!         data: Box<Sample>,
+//!         features: &mut AudioFeatures<'static>,
+//!     ) -> Result<(), WorkerError> {
+//!         self.position = 0.0;
+//!         if let Some(retired) = self.sample.swap(data) {
+//!             // The old sample is sent back to the worker to be deallocated there.
+//!             let _ = features.schedule.schedule_work(Work::Free(retired));
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//! ```
+mod format;
+
+mod loader;
+pub use loader::*;
+
+mod resample;
+
+mod sample;
+pub use sample::*;
+
+use std::fmt;
+
+/// Errors that may occur when loading a sample.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SampleError {
+    /// Reading the file failed.
+    Io(std::io::ErrorKind),
+    /// The file is malformed or uses an unsupported format.
+    Format(String),
+}
+
+impl From<std::io::Error> for SampleError {
+    fn from(error: std::io::Error) -> Self {
+        SampleError::Io(error.kind())
+    }
+}
+
+impl fmt::Display for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SampleError::Io(kind) => write!(f, "I/O error: {:?}", kind),
+            SampleError::Format(message) => write!(f, "Invalid audio file: {}", message),
+        }
+    }
+}
+
+/// Prelude of `lv2_sample` for wildcard usage.
+pub mod prelude {
+    pub use crate::{Sample, SampleError, SampleLoader};
+}
@@ -0,0 +1,51 @@
+use crate::{format, Sample, SampleError};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// Loads audio files and prepares them for playback.
+///
+/// The loader reads WAV files with 8, 16, 24 or 32 bit integer samples or 32 or 64 bit floating-point samples, as well as AIFF and AIFF-C files with integer samples of up to 32 bits or floating-point samples. The samples are converted to `f32`s in the range from -1 to 1.
+///
+/// After the file is read, the sample is resampled to the sample rate of the plugin, if one was set with [`sample_rate`](#method.sample_rate), and normalized, if a peak was set with [`normalize`](#method.normalize). Loading blocks and allocates, so it should be done by the worker.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SampleLoader {
+    sample_rate: Option<f64>,
+    peak: Option<f32>,
+}
+
+impl SampleLoader {
+    /// Create a loader that keeps the samples as they are stored.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resample the loaded samples to the given sample rate, which is usually the one of the plugin.
+    pub fn sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    /// Normalize the loaded samples so that their peak has the given absolute value.
+    pub fn normalize(mut self, peak: f32) -> Self {
+        self.peak = Some(peak);
+        self
+    }
+
+    /// Load a sample from a file.
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<Sample, SampleError> {
+        self.read(BufReader::new(File::open(path)?))
+    }
+
+    /// Load a sample in the WAV or AIFF format.
+    pub fn read<R: Read>(&self, reader: R) -> Result<Sample, SampleError> {
+        let mut sample = format::read(reader)?;
+        if let Some(sample_rate) = self.sample_rate {
+            sample = sample.resample(sample_rate);
+        }
+        if let Some(peak) = self.peak {
+            sample.normalize(peak);
+        }
+        Ok(sample)
+    }
+}
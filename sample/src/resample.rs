@@ -0,0 +1,56 @@
+use std::f64::consts::PI;
+
+/// The number of zero crossings of the sinc function on each side of the kernel.
+const ZERO_CROSSINGS: f64 = 16.0;
+
+/// The cutoff frequency relative to the lower Nyquist frequency, which leaves room for the transition band of the filter.
+const ROLLOFF: f64 = 0.95;
+
+/// The number of frames of a signal with the given length after resampling it from one sample rate to another.
+pub fn frames(frames: usize, from: f64, to: f64) -> usize {
+    // The length is multiplied before dividing, so that the result is exact if the duration is.
+    (frames as f64 * to / from).ceil() as usize
+}
+
+/// Resample a signal from one sample rate to another.
+///
+/// Every output frame is the sum of the neighbouring input frames, weighted with a sinc function that is stretched to the lower of both Nyquist frequencies and faded out with a Blackman window. Frames outside of the input are treated as silence.
+pub fn resample(input: &[f32], from: f64, to: f64) -> impl Iterator<Item = f32> + '_ {
+    let cutoff = (to / from).min(1.0) * ROLLOFF;
+    let half_width = ZERO_CROSSINGS / cutoff;
+    let last = input.len() as f64 - 1.0;
+
+    (0..frames(input.len(), from, to)).map(move |frame| {
+        let position = frame as f64 * from / to;
+        let first = (position - half_width).ceil().max(0.0) as usize;
+        let end = (position + half_width).floor().min(last) as usize;
+        let value: f64 = (first..=end)
+            .map(|index| {
+                let distance = position - index as f64;
+                f64::from(input[index])
+                    * cutoff
+                    * sinc(cutoff * distance)
+                    * blackman(distance / half_width)
+            })
+            .sum();
+        value as f32
+    })
+}
+
+/// The normalized sinc function.
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// A Blackman window, which is one at zero and falls to zero at -1 and 1.
+fn blackman(x: f64) -> f64 {
+    if x.abs() >= 1.0 {
+        0.0
+    } else {
+        0.42 + 0.5 * (PI * x).cos() + 0.08 * (2.0 * PI * x).cos()
+    }
+}
@@ -0,0 +1,133 @@
+use crate::resample;
+
+/// An audio sample that can be read in the real-time context.
+///
+/// All channels are stored one after another in a single buffer, which is allocated when the sample is created and never changed in size. Reading the sample therefore never allocates, and since the sample is only moved as a box, handing it from the worker to the `run()` context doesn't copy it either.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    sample_rate: f64,
+    channels: usize,
+    frames: usize,
+    data: Box<[f32]>,
+}
+
+impl Sample {
+    /// Create a sample from the given channels.
+    ///
+    /// All channels need to have the same length, or `None` is returned.
+    pub fn new(sample_rate: f64, channels: &[Vec<f32>]) -> Option<Self> {
+        let frames = channels.first().map_or(0, Vec::len);
+        if channels.iter().any(|channel| channel.len() != frames) {
+            return None;
+        }
+        Some(Self::from_planar(
+            sample_rate,
+            channels.len(),
+            channels.concat().into_boxed_slice(),
+        ))
+    }
+
+    /// Create a sample from a buffer that contains all channels one after another.
+    pub(crate) fn from_planar(sample_rate: f64, channels: usize, data: Box<[f32]>) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            frames: data.len().checked_div(channels).unwrap_or(0),
+            data,
+        }
+    }
+
+    /// The sample rate of the sample, in Hertz.
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// The number of channels.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// The number of frames, which is the length of every channel.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// The length of the sample, in seconds.
+    pub fn duration(&self) -> f64 {
+        self.frames as f64 / self.sample_rate
+    }
+
+    /// Check if the sample has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    /// Return the samples of a channel, or `None` if there's no channel with that index.
+    pub fn channel(&self, index: usize) -> Option<&[f32]> {
+        if index < self.channels {
+            Some(&self.data[index * self.frames..(index + 1) * self.frames])
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over all channels.
+    pub fn iter_channels(&self) -> impl Iterator<Item = &[f32]> {
+        (0..self.channels).filter_map(move |index| self.channel(index))
+    }
+
+    /// Read a channel at a fractional position, in frames.
+    ///
+    /// The value is interpolated linearly between the neighbouring frames, which lets plugins play back the sample at a different speed. Positions before the start or after the end of the sample, as well as channels that don't exist, read as silence.
+    pub fn read(&self, channel: usize, position: f64) -> f32 {
+        let channel = match self.channel(channel) {
+            Some(channel) => channel,
+            None => return 0.0,
+        };
+        if position.is_nan() || position < 0.0 || position >= self.frames as f64 {
+            return 0.0;
+        }
+        let index = position as usize;
+        let fraction = (position - index as f64) as f32;
+        let current = channel[index];
+        let next = channel.get(index + 1).copied().unwrap_or(0.0);
+        current + (next - current) * fraction
+    }
+
+    /// The highest absolute value of all channels.
+    pub fn peak(&self) -> f32 {
+        self.data
+            .iter()
+            .fold(0.0, |peak: f32, value| peak.max(value.abs()))
+    }
+
+    /// Scale the sample so that its peak has the given absolute value.
+    ///
+    /// Silent samples are left as they are.
+    pub fn normalize(&mut self, peak: f32) {
+        let current = self.peak();
+        if current > 0.0 {
+            let gain = peak / current;
+            self.data.iter_mut().for_each(|value| *value *= gain);
+        }
+    }
+
+    /// Create a copy of the sample with a different sample rate.
+    ///
+    /// The sample is resampled with a windowed sinc filter, which also removes everything above the new Nyquist frequency if the rate is lowered. This is much too slow for the `run()` context and should be done by the worker.
+    pub fn resample(&self, sample_rate: f64) -> Self {
+        if sample_rate == self.sample_rate
+            || self.is_empty()
+            || sample_rate.is_nan()
+            || sample_rate <= 0.0
+        {
+            return self.clone();
+        }
+        let frames = resample::frames(self.frames, self.sample_rate, sample_rate);
+        let mut data = Vec::with_capacity(frames * self.channels);
+        for channel in self.iter_channels() {
+            data.extend(resample::resample(channel, self.sample_rate, sample_rate));
+        }
+        Self::from_planar(sample_rate, self.channels, data.into_boxed_slice())
+    }
+}
@@ -0,0 +1,144 @@
+use lv2_sample::*;
+use std::f64::consts::PI;
+
+/// Build a WAV file with 16 bit integer samples.
+fn wav_16(sample_rate: u32, channels: u16, frames: &[i16]) -> Vec<u8> {
+    let data_size = frames.len() as u32 * 2;
+    let mut file = Vec::new();
+    file.extend_from_slice(b"RIFF");
+    file.extend_from_slice(&(4 + 24 + 8 + 9 + 1 + 8 + data_size).to_le_bytes());
+    file.extend_from_slice(b"WAVE");
+    file.extend_from_slice(b"fmt ");
+    file.extend_from_slice(&16u32.to_le_bytes());
+    file.extend_from_slice(&1u16.to_le_bytes());
+    file.extend_from_slice(&channels.to_le_bytes());
+    file.extend_from_slice(&sample_rate.to_le_bytes());
+    file.extend_from_slice(&(sample_rate * u32::from(channels) * 2).to_le_bytes());
+    file.extend_from_slice(&(channels * 2).to_le_bytes());
+    file.extend_from_slice(&16u16.to_le_bytes());
+    // An unknown chunk with an odd size, which has to be skipped with its padding.
+    file.extend_from_slice(b"junk");
+    file.extend_from_slice(&9u32.to_le_bytes());
+    file.extend_from_slice(&[0; 10]);
+    file.extend_from_slice(b"data");
+    file.extend_from_slice(&data_size.to_le_bytes());
+    for frame in frames {
+        file.extend_from_slice(&frame.to_le_bytes());
+    }
+    file
+}
+
+/// Build an AIFF-C file with 32 bit floating-point samples at 44.1 kHz, with the sound data before the common chunk.
+fn aifc_float(channels: u16, frames: &[f32]) -> Vec<u8> {
+    let data_size = frames.len() as u32 * 4;
+    let mut file = Vec::new();
+    file.extend_from_slice(b"FORM");
+    file.extend_from_slice(&(4 + 16 + data_size + 8 + 32).to_be_bytes());
+    file.extend_from_slice(b"AIFC");
+    file.extend_from_slice(b"SSND");
+    file.extend_from_slice(&(8 + data_size).to_be_bytes());
+    file.extend_from_slice(&[0; 8]);
+    for frame in frames {
+        file.extend_from_slice(&frame.to_be_bytes());
+    }
+    file.extend_from_slice(b"COMM");
+    file.extend_from_slice(&24u32.to_be_bytes());
+    file.extend_from_slice(&channels.to_be_bytes());
+    file.extend_from_slice(&(frames.len() as u32 / u32::from(channels)).to_be_bytes());
+    file.extend_from_slice(&32u16.to_be_bytes());
+    file.extend_from_slice(&[0x40, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0]);
+    file.extend_from_slice(b"fl32");
+    file.extend_from_slice(&[0, 0]);
+    file
+}
+
+#[test]
+fn test_wav() {
+    let file = wav_16(48000, 2, &[16384, -16384, -32768, 0, 0, 8192]);
+    let sample = SampleLoader::new().read(file.as_slice()).unwrap();
+
+    assert_eq!(sample.sample_rate(), 48000.0);
+    assert_eq!(sample.channels(), 2);
+    assert_eq!(sample.frames(), 3);
+    assert_eq!(sample.channel(0).unwrap(), &[0.5, -1.0, 0.0]);
+    assert_eq!(sample.channel(1).unwrap(), &[-0.5, 0.0, 0.25]);
+    assert_eq!(sample.channel(2), None);
+
+    // Reading between frames interpolates, and reading outside of the sample is silent.
+    assert_eq!(sample.read(0, 0.5), -0.25);
+    assert_eq!(sample.read(1, 2.0), 0.25);
+    assert_eq!(sample.read(1, 3.0), 0.0);
+    assert_eq!(sample.read(1, -1.0), 0.0);
+    assert_eq!(sample.read(2, 0.0), 0.0);
+
+    // Normalization scales all channels by the same gain.
+    let sample = SampleLoader::new()
+        .normalize(0.5)
+        .read(file.as_slice())
+        .unwrap();
+    assert_eq!(sample.channel(0).unwrap(), &[0.25, -0.5, 0.0]);
+    assert_eq!(sample.channel(1).unwrap(), &[-0.25, 0.0, 0.125]);
+}
+
+#[test]
+fn test_aiff() {
+    let sample = SampleLoader::new()
+        .read(aifc_float(2, &[0.5, -0.5, 0.25, 1.0]).as_slice())
+        .unwrap();
+
+    assert_eq!(sample.sample_rate(), 44100.0);
+    assert_eq!(sample.frames(), 2);
+    assert_eq!(sample.channel(0).unwrap(), &[0.5, 0.25]);
+    assert_eq!(sample.channel(1).unwrap(), &[-0.5, 1.0]);
+}
+
+#[test]
+fn test_invalid_files() {
+    assert_eq!(
+        SampleLoader::new().read(&b"OggS\0\0\0\0\0\0\0\0"[..]),
+        Err(SampleError::Format("Not a WAV or AIFF file".to_owned()))
+    );
+
+    let mut file = wav_16(48000, 1, &[0; 4]);
+    file.truncate(36);
+    assert_eq!(
+        SampleLoader::new().read(file.as_slice()),
+        Err(SampleError::Format("Missing data chunk".to_owned()))
+    );
+
+    assert_eq!(
+        SampleLoader::new().open("does-not-exist.wav"),
+        Err(SampleError::Io(std::io::ErrorKind::NotFound))
+    );
+}
+
+#[test]
+fn test_resampling() {
+    // One second of a 1 kHz sine at 44.1 kHz.
+    let sine: Vec<f32> = (0..44100)
+        .map(|frame| (2.0 * PI * 1000.0 * frame as f64 / 44100.0).sin() as f32)
+        .collect();
+    let sample = Sample::new(44100.0, &[sine]).unwrap();
+
+    let resampled = sample.resample(48000.0);
+    assert_eq!(resampled.sample_rate(), 48000.0);
+    assert_eq!(resampled.frames(), 48000);
+    assert!((resampled.duration() - 1.0).abs() < 1e-9);
+
+    // Away from the edges, the resampled signal is the same sine at the new rate.
+    let channel = resampled.channel(0).unwrap();
+    for (frame, value) in channel.iter().enumerate().take(47000).skip(1000) {
+        let expected = (2.0 * PI * 1000.0 * frame as f64 / 48000.0).sin() as f32;
+        assert!((value - expected).abs() < 1e-3);
+    }
+
+    // Frequencies above the new Nyquist frequency are removed when the rate is lowered.
+    let high: Vec<f32> = (0..48000)
+        .map(|frame| (2.0 * PI * 15000.0 * frame as f64 / 48000.0).sin() as f32)
+        .collect();
+    let resampled = Sample::new(48000.0, &[high]).unwrap().resample(22050.0);
+    let peak = resampled.channel(0).unwrap()[1000..21000]
+        .iter()
+        .fold(0.0f32, |peak, value| peak.max(value.abs()));
+    assert!(peak < 1e-2);
+}
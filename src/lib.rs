@@ -68,7 +68,7 @@
 //!
 //!     // Process a chunk of audio. The audio ports are dereferenced to slices, which the plugin
 //!     // iterates over.
-//!     fn run(&mut self, ports: &mut Ports, _features: &mut ()) {
+//!     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
 //!         let coef = if *(ports.gain) > -90.0 {
 //!             10.0_f32.powf(*(ports.gain) * 0.05)
 //!         } else {
@@ -96,11 +96,26 @@
 //! Internally, this framework is built of several sub-crates which are re-exported by the `lv2` crate. All dependencies are optional and can be enabled via features. These are:
 //!
 //! * `lv2-atom`: General data IO.
+//! * `lv2-buf-size`: Extension for LV2 plugins to negotiate the block length with the host.
+//! * `lv2-clap`: Export of LV2 plugins in the CLAP format, from the same implementation.
 //! * `lv2-core`: Implementation of the core LV2 specification.
+//! * `lv2-host`: Discovery, loading and instantiation of LV2 plugins for hosts written in Rust.
+//! * `lv2-inline-display`: Extension for LV2 plugins to draw small images, like meters or waveforms, in the mixer strip of hosts like Ardour.
+//! * `lv2-log`: Logging of messages through the host, which is safe in the audio thread.
 //! * `lv2-midi`: MIDI message extension for `lv2-midi`. Support for the [`wmidi` crate](https://crates.io/crates/wmidi) can be enabled with the `wmidi` feature.
+//! * `lv2-morph`: Extension for LV2 plugins with ports that switch between control and CV at runtime.
+//! * `lv2-options`: Extension for LV2 plugins to receive and change options like the sample rate.
+//! * `lv2-parameters`: Typed plugin parameters, controlled with patch messages.
+//! * `lv2-patch`: Typed messages to access and manipulate properties of plugins and other subjects.
+//! * `lv2-presets`: Loading, applying and saving plugin presets.
+//! * `lv2-resize-port`: Extension for LV2 plugins to request bigger port buffers from the host.
+//! * `lv2-sample`: Loading of WAV and AIFF samples in the worker thread, resampled and normalized for real-time playback.
 //! * `lv2-state`: Extension for LV2 plugins to store their state.
+//! * `lv2-test`: In-process host to unit-test LV2 plugins without an external host.
 //! * `lv2-time`: Specification to describe position in time and passage of time, in both real and musical terms.
-//! * `lv2-units`: Measuring unit definitions.
+//! * `lv2-ttl-gen`: Generation of the Turtle metadata of plugins from their Rust types.
+//! * `lv2-ui`: Library to implement user interfaces for LV2 plugins. Support for the [`raw-window-handle` crate](https://crates.io/crates/raw-window-handle) can be enabled with the `raw-window-handle` feature.
+//! * `lv2-units`: Measuring unit definitions and unit-carrying values for control ports.
 //! * `lv2-urid`: LV2 integration of the URID concept.
 //! * `lv2-worker`: Work scheduling library that allows real-time capable LV2 plugins to execute non-real-time actions.
 //! * `urid`: Idiomatic URID support.
@@ -120,14 +135,44 @@
 pub mod prelude {
     #[cfg(feature = "lv2-atom")]
     pub use ::lv2_atom::prelude::*;
+    #[cfg(feature = "lv2-buf-size")]
+    pub use ::lv2_buf_size::prelude::*;
+    #[cfg(feature = "lv2-clap")]
+    pub use ::lv2_clap::prelude::*;
     #[cfg(feature = "lv2-core")]
     pub use ::lv2_core::prelude::*;
+    #[cfg(feature = "lv2-host")]
+    pub use ::lv2_host::prelude::*;
+    #[cfg(feature = "lv2-inline-display")]
+    pub use ::lv2_inline_display::prelude::*;
+    #[cfg(feature = "lv2-log")]
+    pub use ::lv2_log::prelude::*;
     #[cfg(feature = "lv2-midi")]
     pub use ::lv2_midi::prelude::*;
+    #[cfg(feature = "lv2-morph")]
+    pub use ::lv2_morph::prelude::*;
+    #[cfg(feature = "lv2-options")]
+    pub use ::lv2_options::prelude::*;
+    #[cfg(feature = "lv2-parameters")]
+    pub use ::lv2_parameters::prelude::*;
+    #[cfg(feature = "lv2-patch")]
+    pub use ::lv2_patch::prelude::*;
+    #[cfg(feature = "lv2-presets")]
+    pub use ::lv2_presets::prelude::*;
+    #[cfg(feature = "lv2-resize-port")]
+    pub use ::lv2_resize_port::prelude::*;
+    #[cfg(feature = "lv2-sample")]
+    pub use ::lv2_sample::prelude::*;
     #[cfg(feature = "lv2-state")]
     pub use ::lv2_state::*;
+    #[cfg(feature = "lv2-test")]
+    pub use ::lv2_test::prelude::*;
     #[cfg(feature = "lv2-time")]
     pub use ::lv2_time::prelude::*;
+    #[cfg(feature = "lv2-ttl-gen")]
+    pub use ::lv2_ttl_gen::prelude::*;
+    #[cfg(feature = "lv2-ui")]
+    pub use ::lv2_ui::prelude::*;
     #[cfg(feature = "lv2-units")]
     pub use ::lv2_units::prelude::*;
     #[cfg(feature = "lv2-urid")]
@@ -141,24 +186,68 @@ pub mod prelude {
 #[cfg(feature = "lv2-atom")]
 pub extern crate lv2_atom;
 
+#[cfg(feature = "lv2-buf-size")]
+pub extern crate lv2_buf_size;
+
+#[cfg(feature = "lv2-clap")]
+pub extern crate lv2_clap;
+
 #[cfg(feature = "lv2-core")]
 pub extern crate lv2_core;
 
+#[cfg(feature = "lv2-host")]
+pub extern crate lv2_host;
+#[cfg(feature = "lv2-inline-display")]
+pub extern crate lv2_inline_display;
+
+#[cfg(feature = "lv2-log")]
+pub extern crate lv2_log;
+
 #[cfg(feature = "lv2-midi")]
 pub extern crate lv2_midi;
 
+#[cfg(feature = "lv2-morph")]
+pub extern crate lv2_morph;
+
+#[cfg(feature = "lv2-options")]
+pub extern crate lv2_options;
+
+#[cfg(feature = "lv2-parameters")]
+pub extern crate lv2_parameters;
+
+#[cfg(feature = "lv2-patch")]
+pub extern crate lv2_patch;
+
+#[cfg(feature = "lv2-presets")]
+pub extern crate lv2_presets;
+
+#[cfg(feature = "lv2-resize-port")]
+pub extern crate lv2_resize_port;
+
+#[cfg(feature = "lv2-sample")]
+pub extern crate lv2_sample;
+
 #[cfg(feature = "lv2-state")]
 pub extern crate lv2_state;
 
 #[cfg(feature = "lv2-sys")]
 pub extern crate lv2_sys;
 
+#[cfg(feature = "lv2-test")]
+pub extern crate lv2_test;
+
 #[cfg(feature = "lv2-time")]
 pub extern crate lv2_time;
 
 #[cfg(feature = "urid")]
 pub extern crate urid;
 
+#[cfg(feature = "lv2-ttl-gen")]
+pub extern crate lv2_ttl_gen;
+
+#[cfg(feature = "lv2-ui")]
+pub extern crate lv2_ui;
+
 #[cfg(feature = "lv2-units")]
 pub extern crate lv2_units;
 
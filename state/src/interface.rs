@@ -1,3 +1,4 @@
+use crate::path::*;
 use crate::raw::*;
 use crate::StateErr;
 use core::extension::ExtensionDescriptor;
@@ -43,7 +44,7 @@ unsafe impl<P: State> UriBound for StateDescriptor<P> {
 impl<P: State> StateDescriptor<P> {
     /// Handle a save request by the host.
     ///
-    /// This involves creating the plugin reference, constructing the store handle and discovering the required host features.
+    /// This involves creating the plugin reference, constructing the store handle and discovering the required host features. If the host provides the features to map paths, the store handle maps the paths in the saved properties.
     ///
     /// # Safety
     ///
@@ -67,9 +68,17 @@ impl<P: State> StateDescriptor<P> {
             return sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN;
         };
 
-        let store = StoreHandle::new(store, handle);
+        let mut store = StoreHandle::new(store, handle);
 
         let mut feature_container = core::feature::FeatureCache::from_raw(features);
+        // The path features don't need exclusive access and are used in this call only.
+        if let Some(paths) = feature_container
+            .lend::<PathFeatures>(ThreadingClass::Other)
+            .ok()
+            .and_then(PathMapper::from_features)
+        {
+            store.set_path_mapper(paths);
+        }
         let features = if let Ok(features) =
             P::StateFeatures::from_cache(&mut feature_container, ThreadingClass::Other)
         {
@@ -83,7 +92,7 @@ impl<P: State> StateDescriptor<P> {
 
     /// Handle a restore request by the host.
     ///
-    /// This involves creating the plugin reference, constructing the retrieve handle and discovering the required host features.
+    /// This involves creating the plugin reference, constructing the retrieve handle and discovering the required host features. If the host provides the features to map paths, the retrieve handle maps the paths in the restored properties.
     ///
     /// # Safety
     ///
@@ -107,9 +116,17 @@ impl<P: State> StateDescriptor<P> {
             return sys::LV2_State_Status_LV2_STATE_ERR_UNKNOWN;
         };
 
-        let store = RetrieveHandle::new(retrieve, handle);
+        let mut store = RetrieveHandle::new(retrieve, handle);
 
         let mut feature_container = core::feature::FeatureCache::from_raw(features);
+        // The path features don't need exclusive access and are used in this call only.
+        if let Some(paths) = feature_container
+            .lend::<PathFeatures>(ThreadingClass::Other)
+            .ok()
+            .and_then(PathMapper::from_features)
+        {
+            store.set_path_mapper(paths);
+        }
         let features = if let Ok(features) =
             P::StateFeatures::from_cache(&mut feature_container, ThreadingClass::Other)
         {
@@ -152,7 +169,7 @@ mod tests {
         }
 
         #[cfg_attr(tarpaulin, skip)]
-        fn run(&mut self, _: &mut (), _: &mut ()) {}
+        fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
     }
 
     #[derive(FeatureCollection)]
@@ -2,6 +2,10 @@
 //!
 //! This is a rather classic extension to LV2 plugins: There is a trait called [`State`](trait.State.html) which requires the methods [`save`](trait.State.html#tymethod.save) and [`restore`](trait.State.html#tymethiod.restore) to be implemented. These methods will be called by the host to save and restore the state of the plugin.
 //!
+//! Every property is an atom. It can be a simple value, but also a whole sequence or any other atom with nested atoms, which is how plugins persist things like undo histories or recorded automation. Atoms that already exist can be saved with [`StatePropertyWriter::forward`](struct.StatePropertyWriter.html#method.forward) and properties can be copied back into atom space with [`StatePropertyReader::copy_to`](struct.StatePropertyReader.html#method.copy_to).
+//!
+//! Paths in the state have to be abstract, so that the host can move the files they point to. If the host provides the [`MapPath`](struct.MapPath.html) feature, all `atom:Path` atoms in a property are mapped automatically, even if they are nested in objects, tuples or sequences: They are mapped to abstract paths when the property is saved and back to absolute paths when it's restored.
+//!
 //! ## Example usage
 //!
 //! ```
@@ -55,7 +59,7 @@
 //!         })
 //!     }
 //!
-//!     fn run(&mut self, _: &mut (), _: &mut ()) {
+//!     fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {
 //!         // Set the float to a different value than the previous one.
 //!         self.internal += 1.0;
 //!     }
@@ -82,6 +86,9 @@ extern crate lv2_sys as sys;
 mod interface;
 pub use interface::*;
 
+mod path;
+pub use path::*;
+
 mod raw;
 pub use raw::*;
 
@@ -0,0 +1,276 @@
+use atom::prelude::AtomURIDCollection;
+use atom::space::{FramedMutSpace, MutSpace, Space};
+use core::feature::*;
+use core::prelude::*;
+use std::ffi::{c_void, CStr, CString};
+use std::mem::size_of;
+use std::os::raw::c_char;
+use urid::*;
+
+extern "C" {
+    fn free(ptr: *mut c_void);
+}
+
+/// Host feature to map absolute paths to abstract paths and back.
+///
+/// Paths that are saved in the state of a plugin have to be abstract, so that the host can move the files they point to, for example when a session is copied to a different machine. Properties that contain `atom:Path` atoms are mapped automatically if the host provides this feature, but it can also be used to map paths manually, for example if they are stored as strings.
+pub struct MapPath<'a> {
+    internal: &'a sys::LV2_State_Map_Path,
+}
+
+unsafe impl<'a> UriBound for MapPath<'a> {
+    const URI: &'static [u8] = sys::LV2_STATE__mapPath;
+}
+
+unsafe impl<'a> Feature for MapPath<'a> {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[ThreadingClass::Other];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+        (feature as *const sys::LV2_State_Map_Path)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+/// Host feature to free paths that were returned by other state features.
+///
+/// Hosts that don't provide this feature expect the paths to be freed with the `free` function of the C library, which is what [`MapPath`](struct.MapPath.html) does if this feature isn't passed to it.
+pub struct FreePath<'a> {
+    internal: &'a sys::LV2_State_Free_Path,
+}
+
+unsafe impl<'a> UriBound for FreePath<'a> {
+    const URI: &'static [u8] = sys::LV2_STATE__freePath;
+}
+
+unsafe impl<'a> Feature for FreePath<'a> {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[
+        ThreadingClass::Instantiation,
+        ThreadingClass::Audio,
+        ThreadingClass::Other,
+    ];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+        (feature as *const sys::LV2_State_Free_Path)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+impl<'a> FreePath<'a> {
+    /// Free a path that was returned by a state feature.
+    ///
+    /// # Safety
+    ///
+    /// The path has to be returned by a feature of the same host and must not be used afterwards.
+    pub unsafe fn free_path(&self, path: *mut c_char) {
+        if let Some(free_path) = self.internal.free_path {
+            (free_path)(self.internal.handle, path);
+        }
+    }
+}
+
+impl<'a> MapPath<'a> {
+    /// Map an absolute path to an abstract path, which can be saved in the state.
+    ///
+    /// The returned path is copied and freed with the `free_path` feature if it's given.
+    pub fn abstract_path(
+        &self,
+        absolute_path: &str,
+        free_path: Option<&FreePath>,
+    ) -> Option<String> {
+        let map = self.internal.abstract_path?;
+        let absolute_path = CString::new(absolute_path).ok()?;
+        unsafe {
+            Self::take_path(
+                (map)(self.internal.handle, absolute_path.as_ptr()),
+                free_path,
+            )
+        }
+    }
+
+    /// Map an abstract path from the state to an absolute path, which can be used to open the file.
+    ///
+    /// The returned path is copied and freed with the `free_path` feature if it's given.
+    pub fn absolute_path(
+        &self,
+        abstract_path: &str,
+        free_path: Option<&FreePath>,
+    ) -> Option<String> {
+        let map = self.internal.absolute_path?;
+        let abstract_path = CString::new(abstract_path).ok()?;
+        unsafe {
+            Self::take_path(
+                (map)(self.internal.handle, abstract_path.as_ptr()),
+                free_path,
+            )
+        }
+    }
+
+    unsafe fn take_path(path: *mut c_char, free_path: Option<&FreePath>) -> Option<String> {
+        if path.is_null() {
+            return None;
+        }
+        let owned = CStr::from_ptr(path).to_str().ok().map(str::to_owned);
+        match free_path {
+            Some(free_path) => free_path.free_path(path),
+            None => free(path as *mut c_void),
+        }
+        owned
+    }
+}
+
+/// Maps the paths in properties between their absolute and abstract forms.
+///
+/// This is used by the [`StoreHandle`](struct.StoreHandle.html) and the [`RetrieveHandle`](struct.RetrieveHandle.html) if the host provides the [`MapPath`](struct.MapPath.html) feature: Every `atom:Path` in a property, including paths that are nested in objects, tuples and sequences, is mapped to an abstract path when the property is committed and back to an absolute path when it's retrieved.
+pub struct PathMapper<'a> {
+    map_path: MapPath<'a>,
+    free_path: Option<FreePath<'a>>,
+    urids: AtomURIDCollection,
+}
+
+/// The features that are needed to map paths, which are lent from the features of a save or restore call.
+#[derive(FeatureCollection)]
+pub(crate) struct PathFeatures<'a> {
+    map_path: MapPath<'a>,
+    free_path: Option<FreePath<'a>>,
+    map: lv2_urid::LV2Map<'a>,
+}
+
+impl<'a> PathMapper<'a> {
+    /// Create a new path mapper.
+    ///
+    /// The map is used to map the URIDs of the atom types that may contain paths.
+    pub fn new<M: Map + ?Sized>(
+        map_path: MapPath<'a>,
+        free_path: Option<FreePath<'a>>,
+        map: &M,
+    ) -> Option<Self> {
+        Some(Self {
+            map_path,
+            free_path,
+            urids: map.populate_collection()?,
+        })
+    }
+
+    pub(crate) fn from_features(features: PathFeatures<'a>) -> Option<Self> {
+        Self::new(features.map_path, features.free_path, &features.map)
+    }
+
+    /// Map an absolute path to an abstract path.
+    pub fn abstract_path(&self, absolute_path: &str) -> Option<String> {
+        self.map_path
+            .abstract_path(absolute_path, self.free_path.as_ref())
+    }
+
+    /// Map an abstract path to an absolute path.
+    pub fn absolute_path(&self, abstract_path: &str) -> Option<String> {
+        self.map_path
+            .absolute_path(abstract_path, self.free_path.as_ref())
+    }
+
+    /// Check if the body of an atom with the given type contains a path.
+    pub fn contains_paths(&self, type_: URID, body: Space) -> bool {
+        type_ == self.urids.path
+            || self
+                .split_container(type_, body)
+                .is_some_and(|(_, children)| {
+                    children.into_iter().any(|(_, child_type, child_body)| {
+                        self.contains_paths(child_type, child_body)
+                    })
+                })
+    }
+
+    /// Copy an atom to a space, with all absolute paths in it mapped to abstract paths.
+    ///
+    /// The atom is described by its type and body. If a path can't be mapped, `None` is returned.
+    pub fn write_abstract(&self, type_: URID, body: Space, space: &mut dyn MutSpace) -> Option<()> {
+        self.rewrite(type_, body, space, &|path| self.abstract_path(path))
+    }
+
+    /// Copy an atom to a space, with all abstract paths in it mapped to absolute paths.
+    ///
+    /// The atom is described by its type and body. If a path can't be mapped, `None` is returned.
+    pub fn write_absolute(&self, type_: URID, body: Space, space: &mut dyn MutSpace) -> Option<()> {
+        self.rewrite(type_, body, space, &|path| self.absolute_path(path))
+    }
+
+    /// Split the body of a container atom into its header and its children.
+    ///
+    /// Every child is returned with the header in front of it, like the key of a property or the time stamp of an event, as well as its type and body. `None` is returned if the atom isn't a container.
+    #[allow(clippy::type_complexity)]
+    fn split_container<'b>(
+        &self,
+        type_: URID,
+        body: Space<'b>,
+    ) -> Option<(&'b [u8], Vec<(&'b [u8], URID, Space<'b>)>)> {
+        let (header_size, child_header_size) =
+            if type_ == self.urids.object || type_ == self.urids.blank {
+                (
+                    size_of::<sys::LV2_Atom_Object_Body>(),
+                    size_of::<[u32; 2]>(),
+                )
+            } else if type_ == self.urids.sequence {
+                (size_of::<sys::LV2_Atom_Sequence_Body>(), size_of::<i64>())
+            } else if type_ == self.urids.tuple {
+                (0, 0)
+            } else {
+                return None;
+            };
+
+        let (header, mut space) = body.split_raw(header_size)?;
+        let mut children = Vec::new();
+        while space.data().is_some_and(|data| !data.is_empty()) {
+            let (child_header, rest) = space.split_raw(child_header_size)?;
+            let (atom, rest) = rest.split_atom()?;
+            let (atom_header, atom_body) = atom.split_type::<sys::LV2_Atom>()?;
+            let (atom_body, _) = atom_body.split_space(atom_header.size as usize)?;
+            children.push((child_header, URID::new(atom_header.type_)?, atom_body));
+            space = rest;
+        }
+        Some((header, children))
+    }
+
+    fn rewrite(
+        &self,
+        type_: URID,
+        body: Space,
+        space: &mut dyn MutSpace,
+        map: &dyn Fn(&str) -> Option<String>,
+    ) -> Option<()> {
+        let mut frame = FramedMutSpace::new(space, type_)?;
+        let frame = &mut frame as &mut dyn MutSpace;
+
+        if type_ == self.urids.path {
+            let path = CStr::from_bytes_until_nul(body.data()?)
+                .ok()?
+                .to_str()
+                .ok()?;
+            frame.write_raw(map(path)?.as_bytes(), false)?;
+            frame.write_raw(&[0], false)?;
+        } else if let Some((header, children)) = self.split_container(type_, body) {
+            frame.write_raw(header, false)?;
+            for (child_header, child_type, child_body) in children {
+                frame.write_raw(child_header, true)?;
+                self.rewrite(child_type, child_body, frame, map)?;
+            }
+        } else {
+            frame.write_raw(body.data()?, false)?;
+        }
+        Some(())
+    }
+}
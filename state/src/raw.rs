@@ -1,6 +1,7 @@
-use crate::StateErr;
+use crate::{PathMapper, StateErr};
 use atom::prelude::*;
 use atom::space::*;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ffi::c_void;
 use std::marker::PhantomData;
@@ -11,10 +12,13 @@ use urid::*;
 /// This handle can be used to store the properties of a plugin. It uses the atom system to encode the properties and is backed by a storage callback function.
 ///
 /// The written properties a buffered and flushed when requested. Create new properties by calling [`draft`](#method.draft) and write them like any other atom. Once you are done, you can commit your properties by calling [`commit_all`](#method.commit_all) or [`commit`](#method.commit). You have to commit manually: Uncommitted properties will be discarded when the handle is dropped.
+///
+/// If the handle has a [path mapper](struct.PathMapper.html), all `atom:Path` atoms in the committed properties are mapped to abstract paths, even if they are nested in objects, tuples or sequences.
 pub struct StoreHandle<'a> {
     properties: HashMap<URID, SpaceElement>,
     store_fn: sys::LV2_State_Store_Function,
     handle: sys::LV2_State_Handle,
+    paths: Option<PathMapper<'a>>,
     lifetime: PhantomData<&'a mut c_void>,
 }
 
@@ -25,10 +29,46 @@ impl<'a> StoreHandle<'a> {
             properties: HashMap::new(),
             store_fn,
             handle,
+            paths: None,
             lifetime: PhantomData,
         }
     }
 
+    /// Map the paths in all properties that are committed from now on.
+    ///
+    /// This is done automatically if the host provides the required features to the `save` method.
+    pub fn set_path_mapper(&mut self, paths: PathMapper<'a>) {
+        self.paths = Some(paths);
+    }
+
+    /// Internal helper function to map the paths in a property to abstract paths.
+    fn map_paths(
+        paths: Option<&PathMapper>,
+        space: SpaceElement,
+    ) -> Result<SpaceElement, StateErr> {
+        let paths = match paths {
+            Some(paths) => paths,
+            None => return Ok(space),
+        };
+        let data = space.to_vec();
+        let (header, body) = Space::from_slice(data.as_ref())
+            .split_type::<sys::LV2_Atom>()
+            .ok_or(StateErr::BadData)?;
+        let (body, _) = body
+            .split_space(header.size as usize)
+            .ok_or(StateErr::BadData)?;
+        let type_ = URID::new(header.type_).ok_or(StateErr::BadData)?;
+        if !paths.contains_paths(type_, body) {
+            return Ok(space);
+        }
+
+        let mut mapped = SpaceElement::default();
+        paths
+            .write_abstract(type_, body, &mut SpaceHead::new(&mut mapped))
+            .ok_or(StateErr::BadData)?;
+        Ok(mapped)
+    }
+
     /// Draft a new property.
     ///
     /// This will return a new handle to create a property. Once the property is completely written, you can commit it by calling [`commit`](#method.commit) or [`commit_all`](#method.commit_all). Then, and only then, it will be saved by the host.
@@ -75,6 +115,7 @@ impl<'a> StoreHandle<'a> {
     /// This will also clear the property buffer.
     pub fn commit_all(&mut self) -> Result<(), StateErr> {
         for (key, space) in self.properties.drain() {
+            let space = Self::map_paths(self.paths.as_ref(), space)?;
             Self::commit_pair(self.store_fn, self.handle, key, space)?;
         }
         Ok(())
@@ -85,7 +126,10 @@ impl<'a> StoreHandle<'a> {
     /// This method returns `None` if the requested property was not marked for commit, `Some(Ok(()))` if the property was stored and `Some(Err(_))` if an error occured while storing the property.
     pub fn commit(&mut self, key: URID) -> Option<Result<(), StateErr>> {
         let space = self.properties.remove(&key)?;
-        Some(Self::commit_pair(self.store_fn, self.handle, key, space))
+        Some(
+            Self::map_paths(self.paths.as_ref(), space)
+                .and_then(|space| Self::commit_pair(self.store_fn, self.handle, key, space)),
+        )
     }
 
     /// Discard all drafted properties.
@@ -133,12 +177,35 @@ impl<'a> StatePropertyWriter<'a> {
             Err(StateErr::Unknown)
         }
     }
+
+    /// Write a complete atom as the property.
+    ///
+    /// The atom is copied with its header and its whole body, which makes it possible to save atoms that were written or received earlier, like a recorded sequence of automation events or an undo history that contains nested atoms. If the property has already been initialized or if the atom is malformed, `Err(StateErr::Unknown)` is returned.
+    pub fn forward(&mut self, atom: UnidentifiedAtom) -> Result<(), StateErr> {
+        if self.initialized {
+            return Err(StateErr::Unknown);
+        }
+        atom.type_urid().ok_or(StateErr::Unknown)?;
+        let data = atom
+            .atom_space()
+            .and_then(|space| space.data())
+            .ok_or(StateErr::Unknown)?;
+        self.initialized = true;
+        (&mut self.head as &mut dyn MutSpace)
+            .write_raw(data, true)
+            .map(|_| ())
+            .ok_or(StateErr::Unknown)
+    }
 }
 
 /// Property retrieval handle.
+///
+/// If the handle has a [path mapper](struct.PathMapper.html), all `atom:Path` atoms in the retrieved properties are mapped to absolute paths. Since the mapped properties have to be copied, they are kept until the handle is dropped.
 pub struct RetrieveHandle<'a> {
     retrieve_fn: sys::LV2_State_Retrieve_Function,
     handle: sys::LV2_State_Handle,
+    paths: Option<PathMapper<'a>>,
+    mapped: RefCell<Vec<AtomSpace>>,
     lifetime: PhantomData<&'a mut c_void>,
 }
 
@@ -151,10 +218,19 @@ impl<'a> RetrieveHandle<'a> {
         RetrieveHandle {
             retrieve_fn,
             handle,
+            paths: None,
+            mapped: RefCell::new(Vec::new()),
             lifetime: PhantomData,
         }
     }
 
+    /// Map the paths in all properties that are retrieved from now on.
+    ///
+    /// This is done automatically if the host provides the required features to the `restore` method.
+    pub fn set_path_mapper(&mut self, paths: PathMapper<'a>) {
+        self.paths = Some(paths);
+    }
+
     /// Try to retrieve a property from the host.
     ///
     /// This method calls the internal retrieve callback with the given URID. If there's no property with the given URID, `Err(StateErr::NoProperty)` is returned. Otherwise, a reading handle is returned that contains the type and the data of the property and can interpret it as an atom.
@@ -171,14 +247,38 @@ impl<'a> RetrieveHandle<'a> {
             )
         };
 
-        let type_ = URID::new(type_).ok_or(StateErr::Unknown)?;
         let space = if !property_ptr.is_null() {
             unsafe { std::slice::from_raw_parts(property_ptr as *const u8, size) }
         } else {
             return Err(StateErr::NoProperty);
         };
-
-        Ok(StatePropertyReader::new(type_, Space::from_slice(space)))
+        let type_ = URID::new(type_).ok_or(StateErr::Unknown)?;
+        let body = Space::from_slice(space);
+
+        match self.paths.as_ref() {
+            Some(paths) if paths.contains_paths(type_, body) => {
+                let mut element = SpaceElement::default();
+                paths
+                    .write_absolute(type_, body, &mut SpaceHead::new(&mut element))
+                    .ok_or(StateErr::BadData)?;
+                let data = element.to_vec();
+                let mut mapped = AtomSpace::new(data.len());
+                (&mut mapped.writer() as &mut dyn MutSpace)
+                    .write_raw(data.as_ref(), true)
+                    .ok_or(StateErr::NoSpace)?;
+                let (_, body) = mapped
+                    .atom()
+                    .and_then(|atom| atom.atom_space())
+                    .and_then(|space| space.split_type::<sys::LV2_Atom>())
+                    .ok_or(StateErr::BadData)?;
+                let body = body.data().unwrap_or(&[]);
+                // The data of the atom space is on the heap and the space is only dropped with the handle, which outlives the returned reader.
+                let body = unsafe { std::slice::from_raw_parts(body.as_ptr(), body.len()) };
+                self.mapped.borrow_mut().push(mapped);
+                Ok(StatePropertyReader::new(type_, Space::from_slice(body)))
+            }
+            _ => Ok(StatePropertyReader::new(type_, body)),
+        }
     }
 }
 
@@ -222,13 +322,30 @@ impl<'a> StatePropertyReader<'a> {
             Err(StateErr::BadType)
         }
     }
+
+    /// Copy the property to a space, as a complete atom.
+    ///
+    /// This is the counterpart of [`StatePropertyWriter::forward`](struct.StatePropertyWriter.html#method.forward): The property is written with an atom header, so that it can be kept after the state has been restored and used like any other atom. If the space is too small, `Err(StateErr::Unknown)` is returned.
+    pub fn copy_to(&self, space: &mut dyn MutSpace) -> Result<(), StateErr> {
+        let body = self.body.data().ok_or(StateErr::BadData)?;
+        let mut frame = FramedMutSpace::new(space, self.type_).ok_or(StateErr::Unknown)?;
+        (&mut frame as &mut dyn MutSpace)
+            .write_raw(body, false)
+            .map(|_| ())
+            .ok_or(StateErr::Unknown)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::path::*;
     use crate::raw::*;
     use crate::storage::Storage;
     use atom::space::Space;
+    use core::feature::{Feature, ThreadingClass};
+    use lv2_units::prelude::*;
+    use std::ffi::{CStr, CString};
+    use std::os::raw::c_char;
 
     fn store(storage: &mut Storage, urids: &AtomURIDCollection) {
         let mut store_handle = storage.store_handle();
@@ -319,4 +436,228 @@ mod tests {
 
         retrieve(&mut storage, &urids);
     }
+
+    #[test]
+    fn test_sequence_property() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+        let units = UnitURIDCollection::from_map(&map).unwrap();
+        let mut storage = Storage::default();
+
+        // A recorded sequence, which is forwarded to the state later.
+        let mut recording: Box<[u8]> = Box::new([0; 256]);
+        {
+            let mut space = RootMutSpace::new(recording.as_mut());
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(urids.sequence, TimeStampURID::Frames(units.frame))
+                .unwrap();
+            writer.init(TimeStamp::Frames(3), urids.int, 42).unwrap();
+        }
+        let recording = UnidentifiedAtom::new(Space::from_slice(recording.as_ref()));
+
+        {
+            let mut store_handle = storage.store_handle();
+
+            // A sequence with nested atoms, written in place.
+            let mut property = store_handle.draft(URID::new(1).unwrap());
+            let mut sequence = property
+                .init(urids.sequence, TimeStampURID::Frames(units.frame))
+                .unwrap();
+            {
+                let mut tuple = sequence
+                    .init(TimeStamp::Frames(0), urids.tuple, ())
+                    .unwrap();
+                tuple.init(urids.int, 1).unwrap();
+                tuple.init(urids.float, 2.0).unwrap();
+            }
+            sequence.init(TimeStamp::Frames(5), urids.long, 3).unwrap();
+
+            let mut property = store_handle.draft(URID::new(2).unwrap());
+            property.forward(recording).unwrap();
+            assert!(property.forward(recording).is_err());
+
+            store_handle.commit_all().unwrap();
+        }
+
+        let retrieve_handle = storage.retrieve_handle();
+
+        let property = retrieve_handle.retrieve(URID::new(1).unwrap()).unwrap();
+        let mut events = property.read(urids.sequence, units.beat).unwrap();
+        let (stamp, atom) = events.next().unwrap();
+        assert_eq!(stamp.as_frames(), Some(0));
+        let mut tuple = atom.read(urids.tuple, ()).unwrap();
+        assert_eq!(tuple.next().unwrap().read(urids.int, ()), Some(1));
+        assert_eq!(tuple.next().unwrap().read(urids.float, ()), Some(2.0));
+        assert!(tuple.next().is_none());
+        let (stamp, atom) = events.next().unwrap();
+        assert_eq!(stamp.as_frames(), Some(5));
+        assert_eq!(atom.read(urids.long, ()), Some(3));
+        assert!(events.next().is_none());
+
+        let property = retrieve_handle.retrieve(URID::new(2).unwrap()).unwrap();
+        assert_eq!(property.type_(), urids.sequence);
+        let mut copy: Box<[u8]> = Box::new([0; 256]);
+        property
+            .copy_to(&mut RootMutSpace::new(copy.as_mut()))
+            .unwrap();
+        let copy = UnidentifiedAtom::new(Space::from_slice(copy.as_ref()));
+        assert_eq!(
+            copy.atom_space().unwrap().data(),
+            recording.atom_space().unwrap().data()
+        );
+        let events: Vec<_> = copy.read(urids.sequence, units.beat).unwrap().collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0.as_frames(), Some(3));
+        assert_eq!(events[0].1.read(urids.int, ()), Some(42));
+    }
+
+    unsafe extern "C" fn abstract_path(_: *mut c_void, path: *const c_char) -> *mut c_char {
+        let path = CStr::from_ptr(path).to_str().unwrap();
+        let path = path.strip_prefix("/home/user/").unwrap_or(path);
+        CString::new(path).unwrap().into_raw()
+    }
+
+    unsafe extern "C" fn absolute_path(_: *mut c_void, path: *const c_char) -> *mut c_char {
+        let path = CStr::from_ptr(path).to_str().unwrap();
+        CString::new(format!("/mnt/user/{}", path))
+            .unwrap()
+            .into_raw()
+    }
+
+    unsafe extern "C" fn free_path(_: *mut c_void, path: *mut c_char) {
+        drop(CString::from_raw(path));
+    }
+
+    #[test]
+    fn test_path_mapping() {
+        let map = HashURIDMapper::new();
+        let urids = AtomURIDCollection::from_map(&map).unwrap();
+        let units = UnitURIDCollection::from_map(&map).unwrap();
+        let mut storage = Storage::default();
+
+        let map_path = sys::LV2_State_Map_Path {
+            handle: std::ptr::null_mut(),
+            abstract_path: Some(abstract_path),
+            absolute_path: Some(absolute_path),
+        };
+        let free_path = sys::LV2_State_Free_Path {
+            handle: std::ptr::null_mut(),
+            free_path: Some(free_path),
+        };
+        let path_mapper = || unsafe {
+            PathMapper::new(
+                MapPath::from_feature_ptr(
+                    &map_path as *const _ as *const c_void,
+                    ThreadingClass::Other,
+                )
+                .unwrap(),
+                Some(
+                    FreePath::from_feature_ptr(
+                        &free_path as *const _ as *const c_void,
+                        ThreadingClass::Other,
+                    )
+                    .unwrap(),
+                ),
+                &map,
+            )
+            .unwrap()
+        };
+
+        {
+            let mut store_handle = storage.store_handle();
+            store_handle.set_path_mapper(path_mapper());
+
+            store_handle
+                .draft(URID::new(1).unwrap())
+                .init(urids.path, ())
+                .unwrap()
+                .append("/home/user/sample.wav")
+                .unwrap();
+
+            // A list of samples with their root notes, as a sequence of objects.
+            let mut property = store_handle.draft(URID::new(2).unwrap());
+            let mut sequence = property
+                .init(urids.sequence, TimeStampURID::Frames(units.frame))
+                .unwrap();
+            for (frame, file) in [(0, "kick.wav"), (7, "snare.wav")].iter() {
+                let mut object = sequence
+                    .init(
+                        TimeStamp::Frames(*frame),
+                        urids.object,
+                        ObjectHeader {
+                            id: None,
+                            otype: URID::new(10).unwrap(),
+                        },
+                    )
+                    .unwrap();
+                object
+                    .init(URID::new(11).unwrap(), None, urids.int, 36)
+                    .unwrap();
+                object
+                    .init(URID::new(12).unwrap(), None, urids.path, ())
+                    .unwrap()
+                    .append(&format!("/home/user/{}", file))
+                    .unwrap();
+            }
+
+            store_handle
+                .draft(URID::new(3).unwrap())
+                .init(urids.string, ())
+                .unwrap()
+                .append("/home/user/notes.txt")
+                .unwrap();
+
+            store_handle.commit_all().unwrap();
+        }
+
+        // The stored paths are abstract, other strings are kept.
+        let retrieve_handle = storage.retrieve_handle();
+        assert_eq!(
+            retrieve_handle
+                .retrieve(URID::new(1).unwrap())
+                .unwrap()
+                .read(urids.path, ())
+                .unwrap(),
+            "sample.wav"
+        );
+        assert_eq!(
+            retrieve_handle
+                .retrieve(URID::new(3).unwrap())
+                .unwrap()
+                .read(urids.string, ())
+                .unwrap(),
+            "/home/user/notes.txt"
+        );
+
+        let mut retrieve_handle = storage.retrieve_handle();
+        retrieve_handle.set_path_mapper(path_mapper());
+        assert_eq!(
+            retrieve_handle
+                .retrieve(URID::new(1).unwrap())
+                .unwrap()
+                .read(urids.path, ())
+                .unwrap(),
+            "/mnt/user/sample.wav"
+        );
+
+        let property = retrieve_handle.retrieve(URID::new(2).unwrap()).unwrap();
+        let events: Vec<_> = property
+            .read(urids.sequence, units.beat)
+            .unwrap()
+            .map(|(stamp, atom)| {
+                let (header, object) = atom.read(urids.object, ()).unwrap();
+                assert_eq!(header.otype, 10);
+                let properties: Vec<_> = object.collect();
+                assert_eq!(properties[0].1.read(urids.int, ()), Some(36));
+                (
+                    stamp.as_frames().unwrap(),
+                    properties[1].1.read(urids.path, ()).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            events,
+            [(0, "/mnt/user/kick.wav"), (7, "/mnt/user/snare.wav")]
+        );
+    }
 }
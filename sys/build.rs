@@ -0,0 +1,86 @@
+//! Regenerate the bindings if the `regenerate-bindings` feature is enabled.
+//!
+//! By default, the pre-generated bindings in `src` are used and nothing needs to be done here.
+
+#[cfg(feature = "regenerate-bindings")]
+fn main() {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let source_dir = manifest_dir.join("lv2");
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("bindings.rs");
+    let target = env::var("TARGET").unwrap();
+    let host = env::var("HOST").unwrap();
+
+    // These are the same settings as the ones of the `systool`, which generates the pre-generated bindings.
+    let mut bindings = bindgen::Builder::default()
+        .size_t_is_usize(true)
+        .use_core()
+        .ctypes_prefix("::core::ffi")
+        .whitelist_type("LV2.*")
+        .whitelist_function("LV2.*")
+        .whitelist_var("LV2.*")
+        // The layouts can only be tested on the machine the bindings are generated for.
+        .layout_tests(target == host)
+        .bitfield_enum("LV2_State_Flags")
+        // The headers include each other as `lv2/<spec>/<header>.h`.
+        .clang_arg(format!("-I{}", manifest_dir.to_str().unwrap()))
+        .clang_arg(format!("--target={}", clang_target(&target)));
+
+    // Additional arguments, like the `--sysroot` of a cross-compilation toolchain.
+    if let Ok(args) = env::var("LV2_SYS_CLANG_ARGS") {
+        bindings = bindings.clang_args(args.split_whitespace());
+    }
+
+    let mut dirs: Vec<PathBuf> = fs::read_dir(&source_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    dirs.sort_unstable();
+    for spec_dir in dirs.iter() {
+        let mut files: Vec<PathBuf> = fs::read_dir(spec_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        files.sort_unstable();
+
+        for file in files {
+            if file.extension().map_or(false, |extension| extension == "h") {
+                bindings = bindings.header(file.to_str().unwrap());
+            }
+        }
+    }
+
+    bindings
+        .generate()
+        .expect("Unable to generate bindings, is libclang installed?")
+        .write_to_file(out)
+        .expect("Couldn't write bindings!");
+
+    println!("cargo:rerun-if-changed={}", source_dir.to_str().unwrap());
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=LV2_SYS_CLANG_ARGS");
+}
+
+/// Translate a Rust target triple to one that clang understands.
+///
+/// Most triples are the same, but clang calls 64-bit ARM on Apple platforms `arm64` and doesn't know Rust's names for RISC-V.
+#[cfg(feature = "regenerate-bindings")]
+fn clang_target(target: &str) -> String {
+    if let Some(rest) = target.strip_prefix("aarch64-apple-") {
+        format!("arm64-apple-{}", rest)
+    } else if let Some(rest) = target.strip_prefix("riscv64gc-") {
+        format!("riscv64-{}", rest)
+    } else if let Some(rest) = target.strip_prefix("riscv32imac-") {
+        format!("riscv32-{}", rest)
+    } else {
+        target.to_owned()
+    }
+}
+
+#[cfg(not(feature = "regenerate-bindings"))]
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+}
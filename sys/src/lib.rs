@@ -1,16 +1,29 @@
 //! Raw bindings of all LV2 specification headers.
 //!
 //! Bindings to the official [LV2](https://lv2plug.in/) API headers, used by [`rust-lv2`](https://crates.io/crates/lv2), a safe, fast, and ergonomic framework to create [LV2 plugins](http://lv2plug.in/) for audio processing, written in Rust. The crate uses the version 1.18.0 of the specification, as pulled from the [project's website](https://lv2plug.in/lv2-1-18-0.html).
+//!
+//! The crate contains pre-generated bindings for the supported targets, which are selected automatically, so neither clang nor bindgen is needed to build it. For other targets, or to check the pre-generated bindings, the `regenerate-bindings` feature generates the bindings with bindgen at build time, which requires libclang.
+//!
+//! The bindings only depend on `core`, which is why the crate is `no_std`.
+#![no_std]
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #![allow(clippy::all)]
 
+#[cfg(feature = "regenerate-bindings")]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+#[cfg(not(feature = "regenerate-bindings"))]
 #[cfg_attr(target_os = "linux", path = "linux/mod.rs")]
+#[cfg_attr(target_os = "macos", path = "macos/mod.rs")]
 #[cfg_attr(target_os = "windows", path = "windows.rs")]
 mod unsupported;
+#[cfg(not(feature = "regenerate-bindings"))]
 pub use unsupported::*;
 
+pub mod specs;
+
 impl From<u32> for LV2_State_Flags {
     fn from(flags: u32) -> Self {
         Self(flags as _)
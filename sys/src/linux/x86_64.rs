@@ -679,11 +679,11 @@ pub const LV2_WORKER__interface: &'static [u8; 42usize] =
     b"http://lv2plug.in/ns/ext/worker#interface\0";
 pub const LV2_WORKER__schedule: &'static [u8; 41usize] =
     b"http://lv2plug.in/ns/ext/worker#schedule\0";
-pub type __uint8_t = ::std::os::raw::c_uchar;
-pub type __uint16_t = ::std::os::raw::c_ushort;
-pub type __int32_t = ::std::os::raw::c_int;
-pub type __uint32_t = ::std::os::raw::c_uint;
-pub type __int64_t = ::std::os::raw::c_long;
+pub type __uint8_t = ::core::ffi::c_uchar;
+pub type __uint16_t = ::core::ffi::c_ushort;
+pub type __int32_t = ::core::ffi::c_int;
+pub type __uint32_t = ::core::ffi::c_uint;
+pub type __int64_t = ::core::ffi::c_long;
 #[doc = " The header of an atom:Atom."]
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -891,9 +891,9 @@ extern "C" {
     pub static LV2_ATOM_OBJECT_QUERY_END: LV2_Atom_Object_Query;
 }
 #[doc = "Opaque pointer to host data for LV2_URID_Map."]
-pub type LV2_URID_Map_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_URID_Map_Handle = *mut ::core::ffi::c_void;
 #[doc = "Opaque pointer to host data for LV2_URID_Unmap."]
-pub type LV2_URID_Unmap_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_URID_Unmap_Handle = *mut ::core::ffi::c_void;
 #[doc = "URI mapped to an integer."]
 pub type LV2_URID = u32;
 #[doc = "URID Map Feature (LV2_URID__map)"]
@@ -924,10 +924,10 @@ pub struct LV2_URID_Map {
     #[doc = ""]
     #[doc = "@param handle Must be the callback_data member of this struct."]
     #[doc = "@param uri The URI to be mapped to an integer ID."]
-    pub map: ::std::option::Option<
+    pub map: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2_URID_Map_Handle,
-            uri: *const ::std::os::raw::c_char,
+            uri: *const ::core::ffi::c_char,
         ) -> LV2_URID,
     >,
 }
@@ -953,27 +953,27 @@ pub struct LV2_URID_Unmap {
     #[doc = ""]
     #[doc = "@param handle Must be the callback_data member of this struct."]
     #[doc = "@param urid The ID to be mapped back to the URI string."]
-    pub unmap: ::std::option::Option<
+    pub unmap: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2_URID_Unmap_Handle,
             urid: LV2_URID,
-        ) -> *const ::std::os::raw::c_char,
+        ) -> *const ::core::ffi::c_char,
     >,
 }
 #[doc = " Handle for LV2_Atom_Forge_Sink."]
-pub type LV2_Atom_Forge_Sink_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_Atom_Forge_Sink_Handle = *mut ::core::ffi::c_void;
 #[doc = " A reference to a chunk of written output."]
 pub type LV2_Atom_Forge_Ref = isize;
 #[doc = " Sink function for writing output.  See lv2_atom_forge_set_sink()."]
-pub type LV2_Atom_Forge_Sink = ::std::option::Option<
+pub type LV2_Atom_Forge_Sink = ::core::option::Option<
     unsafe extern "C" fn(
         handle: LV2_Atom_Forge_Sink_Handle,
-        buf: *const ::std::os::raw::c_void,
+        buf: *const ::core::ffi::c_void,
         size: u32,
     ) -> LV2_Atom_Forge_Ref,
 >;
 #[doc = " Function for resolving a reference.  See lv2_atom_forge_set_sink()."]
-pub type LV2_Atom_Forge_Deref_Func = ::std::option::Option<
+pub type LV2_Atom_Forge_Deref_Func = ::core::option::Option<
     unsafe extern "C" fn(
         handle: LV2_Atom_Forge_Sink_Handle,
         ref_: LV2_Atom_Forge_Ref,
@@ -1021,7 +1021,7 @@ pub struct LV2_Atom_Forge {
 #[doc = "This is a handle for one particular instance of a plugin.  It is valid to"]
 #[doc = "compare to NULL (or 0 for C++) but otherwise the host MUST NOT attempt to"]
 #[doc = "interpret it."]
-pub type LV2_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_Handle = *mut ::core::ffi::c_void;
 #[doc = "Feature."]
 #[doc = ""]
 #[doc = "Features allow hosts to make additional functionality available to plugins"]
@@ -1034,12 +1034,12 @@ pub struct LV2_Feature {
     #[doc = "A globally unique, case-sensitive identifier (URI) for this feature."]
     #[doc = ""]
     #[doc = "This MUST be a valid URI string as defined by RFC 3986."]
-    pub URI: *const ::std::os::raw::c_char,
+    pub URI: *const ::core::ffi::c_char,
     #[doc = "Pointer to arbitrary data."]
     #[doc = ""]
     #[doc = "The format of this data is defined by the extension which describes the"]
     #[doc = "feature with the given `URI`."]
-    pub data: *mut ::std::os::raw::c_void,
+    pub data: *mut ::core::ffi::c_void,
 }
 #[doc = "Plugin Descriptor."]
 #[doc = ""]
@@ -1053,7 +1053,7 @@ pub struct LV2_Descriptor {
     #[doc = "This MUST be a valid URI string as defined by RFC 3986.  All plugins with"]
     #[doc = "the same URI MUST be compatible to some degree, see"]
     #[doc = "http://lv2plug.in/ns/lv2core for details."]
-    pub URI: *const ::std::os::raw::c_char,
+    pub URI: *const ::core::ffi::c_char,
     #[doc = "Instantiate the plugin."]
     #[doc = ""]
     #[doc = "Note that instance initialisation should generally occur in activate()"]
@@ -1079,11 +1079,11 @@ pub struct LV2_Descriptor {
     #[doc = ""]
     #[doc = "@return A handle for the new plugin instance, or NULL if instantiation"]
     #[doc = "has failed."]
-    pub instantiate: ::std::option::Option<
+    pub instantiate: ::core::option::Option<
         unsafe extern "C" fn(
             descriptor: *const LV2_Descriptor,
             sample_rate: f64,
-            bundle_path: *const ::std::os::raw::c_char,
+            bundle_path: *const ::core::ffi::c_char,
             features: *const *const LV2_Feature,
         ) -> LV2_Handle,
     >,
@@ -1119,11 +1119,11 @@ pub struct LV2_Descriptor {
     #[doc = "lv2:AudioPort). This pointer must be stored by the plugin instance and"]
     #[doc = "used to read/write data when run() is called. Data present at the time"]
     #[doc = "of the connect_port() call MUST NOT be considered meaningful."]
-    pub connect_port: ::std::option::Option<
+    pub connect_port: ::core::option::Option<
         unsafe extern "C" fn(
             instance: LV2_Handle,
             port: u32,
-            data_location: *mut ::std::os::raw::c_void,
+            data_location: *mut ::core::ffi::c_void,
         ),
     >,
     #[doc = "Initialise a plugin instance and activate it for use."]
@@ -1145,7 +1145,7 @@ pub struct LV2_Descriptor {
     #[doc = "called first. If a host calls activate(), it MUST call deactivate() at"]
     #[doc = "some point in the future. Note that connect_port() may be called before"]
     #[doc = "or after activate()."]
-    pub activate: ::std::option::Option<unsafe extern "C" fn(instance: LV2_Handle)>,
+    pub activate: ::core::option::Option<unsafe extern "C" fn(instance: LV2_Handle)>,
     #[doc = "Run a plugin instance for a block."]
     #[doc = ""]
     #[doc = "Note that if an activate() function exists then it must be called before"]
@@ -1167,7 +1167,7 @@ pub struct LV2_Descriptor {
     #[doc = ""]
     #[doc = "@param sample_count The block size (in samples) for which the plugin"]
     #[doc = "instance must run."]
-    pub run: ::std::option::Option<unsafe extern "C" fn(instance: LV2_Handle, sample_count: u32)>,
+    pub run: ::core::option::Option<unsafe extern "C" fn(instance: LV2_Handle, sample_count: u32)>,
     #[doc = "Deactivate a plugin instance (counterpart to activate())."]
     #[doc = ""]
     #[doc = "Hosts MUST deactivate all activated instances after they have been run()"]
@@ -1184,7 +1184,7 @@ pub struct LV2_Descriptor {
     #[doc = "Hosts MUST NOT call deactivate() unless activate() was previously"]
     #[doc = "called. Note that connect_port() may be called before or after"]
     #[doc = "deactivate()."]
-    pub deactivate: ::std::option::Option<unsafe extern "C" fn(instance: LV2_Handle)>,
+    pub deactivate: ::core::option::Option<unsafe extern "C" fn(instance: LV2_Handle)>,
     #[doc = "Clean up a plugin instance (counterpart to instantiate())."]
     #[doc = ""]
     #[doc = "Once an instance of a plugin has been finished with it must be deleted"]
@@ -1194,7 +1194,7 @@ pub struct LV2_Descriptor {
     #[doc = "If activate() was called for a plugin instance then a corresponding call"]
     #[doc = "to deactivate() MUST be made before cleanup() is called. Hosts MUST NOT"]
     #[doc = "call cleanup() unless instantiate() was previously called."]
-    pub cleanup: ::std::option::Option<unsafe extern "C" fn(instance: LV2_Handle)>,
+    pub cleanup: ::core::option::Option<unsafe extern "C" fn(instance: LV2_Handle)>,
     #[doc = "Return additional plugin data defined by some extenion."]
     #[doc = ""]
     #[doc = "A typical use of this facility is to return a struct containing function"]
@@ -1206,15 +1206,15 @@ pub struct LV2_Descriptor {
     #[doc = "field may be NULL."]
     #[doc = ""]
     #[doc = "The host is never responsible for freeing the returned value."]
-    pub extension_data: ::std::option::Option<
-        unsafe extern "C" fn(uri: *const ::std::os::raw::c_char) -> *const ::std::os::raw::c_void,
+    pub extension_data: ::core::option::Option<
+        unsafe extern "C" fn(uri: *const ::core::ffi::c_char) -> *const ::core::ffi::c_void,
     >,
 }
 #[doc = "Type of the lv2_descriptor() function in a library (old discovery API)."]
 pub type LV2_Descriptor_Function =
-    ::std::option::Option<unsafe extern "C" fn(index: u32) -> *const LV2_Descriptor>;
+    ::core::option::Option<unsafe extern "C" fn(index: u32) -> *const LV2_Descriptor>;
 #[doc = "Handle for a library descriptor."]
-pub type LV2_Lib_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_Lib_Handle = *mut ::core::ffi::c_void;
 #[doc = "Descriptor for a plugin library."]
 #[doc = ""]
 #[doc = "To access a plugin library, the host creates an LV2_Lib_Descriptor via the"]
@@ -1231,20 +1231,20 @@ pub struct LV2_Lib_Descriptor {
     #[doc = "struct beyond get_plugin() unless this field indicates they are present."]
     pub size: u32,
     #[doc = "Destroy this library descriptor and free all related resources."]
-    pub cleanup: ::std::option::Option<unsafe extern "C" fn(handle: LV2_Lib_Handle)>,
+    pub cleanup: ::core::option::Option<unsafe extern "C" fn(handle: LV2_Lib_Handle)>,
     #[doc = "Plugin accessor."]
     #[doc = ""]
     #[doc = "Plugins are accessed by index using values from 0 upwards.  Out of range"]
     #[doc = "indices MUST result in this function returning NULL, so the host can"]
     #[doc = "enumerate plugins by increasing `index` until NULL is returned."]
-    pub get_plugin: ::std::option::Option<
+    pub get_plugin: ::core::option::Option<
         unsafe extern "C" fn(handle: LV2_Lib_Handle, index: u32) -> *const LV2_Descriptor,
     >,
 }
 #[doc = "Type of the lv2_lib_descriptor() function in an LV2 library."]
-pub type LV2_Lib_Descriptor_Function = ::std::option::Option<
+pub type LV2_Lib_Descriptor_Function = ::core::option::Option<
     unsafe extern "C" fn(
-        bundle_path: *const ::std::os::raw::c_char,
+        bundle_path: *const ::core::ffi::c_char,
         features: *const *const LV2_Feature,
     ) -> *const LV2_Lib_Descriptor,
 >;
@@ -1266,8 +1266,8 @@ pub struct LV2_Extension_Data_Feature {
     #[doc = "This is for access to large data that can only possibly work if the UI"]
     #[doc = "and plugin are running in the same process.  For all other things, use"]
     #[doc = "the normal LV2 UI communication system."]
-    pub data_access: ::std::option::Option<
-        unsafe extern "C" fn(uri: *const ::std::os::raw::c_char) -> *const ::std::os::raw::c_void,
+    pub data_access: ::core::option::Option<
+        unsafe extern "C" fn(uri: *const ::core::ffi::c_char) -> *const ::core::ffi::c_void,
     >,
 }
 #[doc = "Dynamic manifest generator handle."]
@@ -1276,7 +1276,7 @@ pub struct LV2_Extension_Data_Feature {
 #[doc = "The host MUST NOT attempt to interpret it and, unlikely LV2_Handle, it is"]
 #[doc = "NOT even valid to compare this to NULL. The dynamic manifest generator MAY"]
 #[doc = "use it to reference internal data."]
-pub type LV2_Dyn_Manifest_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_Dyn_Manifest_Handle = *mut ::core::ffi::c_void;
 pub const LV2_EVENT_PPQN: u32 = 3136573440;
 #[doc = "An LV2 event (header only)."]
 #[doc = ""]
@@ -1397,7 +1397,7 @@ pub struct LV2_Event_Buffer {
     #[doc = "Any initial value should be ignored by the plugin."]
     pub size: u32,
 }
-pub type LV2_Event_Callback_Data = *mut ::std::os::raw::c_void;
+pub type LV2_Event_Callback_Data = *mut ::core::ffi::c_void;
 #[doc = "Non-POD events feature."]
 #[doc = ""]
 #[doc = "To support this feature the host must pass an LV2_Feature struct to the"]
@@ -1432,7 +1432,7 @@ pub struct LV2_Event_Feature {
     #[doc = "realtime safe."]
     #[doc = ""]
     #[doc = "PLUGINS THAT VIOLATE THESE RULES MAY CAUSE CRASHES AND MEMORY LEAKS."]
-    pub lv2_event_ref: ::std::option::Option<
+    pub lv2_event_ref: ::core::option::Option<
         unsafe extern "C" fn(callback_data: LV2_Event_Callback_Data, event: *mut LV2_Event) -> u32,
     >,
     #[doc = "Drop a reference to a non-POD event."]
@@ -1453,7 +1453,7 @@ pub struct LV2_Event_Feature {
     #[doc = "realtime safe."]
     #[doc = ""]
     #[doc = "PLUGINS THAT VIOLATE THESE RULES MAY CAUSE CRASHES AND MEMORY LEAKS."]
-    pub lv2_event_unref: ::std::option::Option<
+    pub lv2_event_unref: ::core::option::Option<
         unsafe extern "C" fn(callback_data: LV2_Event_Callback_Data, event: *mut LV2_Event) -> u32,
     >,
 }
@@ -1468,7 +1468,7 @@ pub struct LV2_Event_Iterator {
     pub offset: u32,
 }
 #[doc = "Opaque data to host data for LV2_Log_Log."]
-pub type LV2_Log_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_Log_Handle = *mut ::core::ffi::c_void;
 #[doc = "Log feature (LV2_LOG__log)"]
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -1484,13 +1484,13 @@ pub struct LV2_Log_Log {
     #[doc = "except for the addition of the first two parameters.  This function may"]
     #[doc = "be called from any non-realtime context, or from any context if `type`"]
     #[doc = "is @ref LV2_LOG__Trace."]
-    pub printf: ::std::option::Option<
+    pub printf: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2_Log_Handle,
             type_: LV2_URID,
-            fmt: *const ::std::os::raw::c_char,
+            fmt: *const ::core::ffi::c_char,
             ...
-        ) -> ::std::os::raw::c_int,
+        ) -> ::core::ffi::c_int,
     >,
     #[doc = "Log a message, passing format parameters in a va_list."]
     #[doc = ""]
@@ -1498,13 +1498,13 @@ pub struct LV2_Log_Log {
     #[doc = "function, except for the addition of the first two parameters.  This"]
     #[doc = "function may be called from any non-realtime context, or from any"]
     #[doc = "context if `type` is @ref LV2_LOG__Trace."]
-    pub vprintf: ::std::option::Option<
+    pub vprintf: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2_Log_Handle,
             type_: LV2_URID,
-            fmt: *const ::std::os::raw::c_char,
+            fmt: *const ::core::ffi::c_char,
             ap: *mut __va_list_tag,
-        ) -> ::std::os::raw::c_int,
+        ) -> ::core::ffi::c_int,
     >,
 }
 #[doc = "Logger convenience API state."]
@@ -1746,7 +1746,7 @@ pub struct LV2_Options_Option {
     #[doc = "< Type of value (datatype)."]
     pub type_: LV2_URID,
     #[doc = "< Pointer to value (object)."]
-    pub value: *const ::std::os::raw::c_void,
+    pub value: *const ::core::ffi::c_void,
 }
 #[doc = "< Completed successfully."]
 pub const LV2_Options_Status_LV2_OPTIONS_SUCCESS: LV2_Options_Status = 0;
@@ -1774,7 +1774,7 @@ pub struct LV2_Options_Interface {
     #[doc = "instance functions may be called concurrently."]
     #[doc = ""]
     #[doc = "@return Bitwise OR of LV2_Options_Status values."]
-    pub get: ::std::option::Option<
+    pub get: ::core::option::Option<
         unsafe extern "C" fn(instance: LV2_Handle, options: *mut LV2_Options_Option) -> u32,
     >,
     #[doc = "Set the given options."]
@@ -1783,7 +1783,7 @@ pub struct LV2_Options_Interface {
     #[doc = "instance functions may be called concurrently."]
     #[doc = ""]
     #[doc = "@return Bitwise OR of LV2_Options_Status values."]
-    pub set: ::std::option::Option<
+    pub set: ::core::option::Option<
         unsafe extern "C" fn(instance: LV2_Handle, options: *const LV2_Options_Option) -> u32,
     >,
 }
@@ -1796,7 +1796,7 @@ pub const LV2_Resize_Port_Status_LV2_RESIZE_PORT_ERR_NO_SPACE: LV2_Resize_Port_S
 #[doc = " A status code for state functions."]
 pub type LV2_Resize_Port_Status = u32;
 #[doc = " Opaque data for resize method."]
-pub type LV2_Resize_Port_Feature_Data = *mut ::std::os::raw::c_void;
+pub type LV2_Resize_Port_Feature_Data = *mut ::core::ffi::c_void;
 #[doc = " Host feature to allow plugins to resize their port buffers."]
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -1815,7 +1815,7 @@ pub struct LV2_Resize_Port_Resize {
     #[doc = ""]
     #[doc = "Plugins MAY resize a port many times in a single run callback.  Hosts"]
     #[doc = "SHOULD make this as inexpensive as possible."]
-    pub resize: ::std::option::Option<
+    pub resize: ::core::option::Option<
         unsafe extern "C" fn(
             data: LV2_Resize_Port_Feature_Data,
             index: u32,
@@ -1823,10 +1823,10 @@ pub struct LV2_Resize_Port_Resize {
         ) -> LV2_Resize_Port_Status,
     >,
 }
-pub type LV2_State_Handle = *mut ::std::os::raw::c_void;
-pub type LV2_State_Free_Path_Handle = *mut ::std::os::raw::c_void;
-pub type LV2_State_Map_Path_Handle = *mut ::std::os::raw::c_void;
-pub type LV2_State_Make_Path_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_State_Handle = *mut ::core::ffi::c_void;
+pub type LV2_State_Free_Path_Handle = *mut ::core::ffi::c_void;
+pub type LV2_State_Map_Path_Handle = *mut ::core::ffi::c_void;
+pub type LV2_State_Make_Path_Handle = *mut ::core::ffi::c_void;
 impl LV2_State_Flags {
     #[doc = "Plain Old Data."]
     #[doc = ""]
@@ -1860,27 +1860,27 @@ impl LV2_State_Flags {
     #[doc = "and portability."]
     pub const LV2_STATE_IS_NATIVE: LV2_State_Flags = LV2_State_Flags(4);
 }
-impl ::std::ops::BitOr<LV2_State_Flags> for LV2_State_Flags {
+impl ::core::ops::BitOr<LV2_State_Flags> for LV2_State_Flags {
     type Output = Self;
     #[inline]
     fn bitor(self, other: Self) -> Self {
         LV2_State_Flags(self.0 | other.0)
     }
 }
-impl ::std::ops::BitOrAssign for LV2_State_Flags {
+impl ::core::ops::BitOrAssign for LV2_State_Flags {
     #[inline]
     fn bitor_assign(&mut self, rhs: LV2_State_Flags) {
         self.0 |= rhs.0;
     }
 }
-impl ::std::ops::BitAnd<LV2_State_Flags> for LV2_State_Flags {
+impl ::core::ops::BitAnd<LV2_State_Flags> for LV2_State_Flags {
     type Output = Self;
     #[inline]
     fn bitand(self, other: Self) -> Self {
         LV2_State_Flags(self.0 & other.0)
     }
 }
-impl ::std::ops::BitAndAssign for LV2_State_Flags {
+impl ::core::ops::BitAndAssign for LV2_State_Flags {
     #[inline]
     fn bitand_assign(&mut self, rhs: LV2_State_Flags) {
         self.0 &= rhs.0;
@@ -1940,11 +1940,11 @@ pub type LV2_State_Status = u32;
 #[doc = ""]
 #[doc = "The plugin MUST NOT attempt to use this function outside of the"]
 #[doc = "LV2_State_Interface.restore() context."]
-pub type LV2_State_Store_Function = ::std::option::Option<
+pub type LV2_State_Store_Function = ::core::option::Option<
     unsafe extern "C" fn(
         handle: LV2_State_Handle,
         key: u32,
-        value: *const ::std::os::raw::c_void,
+        value: *const ::core::ffi::c_void,
         size: usize,
         type_: u32,
         flags: u32,
@@ -1966,14 +1966,14 @@ pub type LV2_State_Store_Function = ::std::option::Option<
 #[doc = "The returned value MUST remain valid until LV2_State_Interface.restore()"]
 #[doc = "returns.  The plugin MUST NOT attempt to use this function, or any value"]
 #[doc = "returned from it, outside of the LV2_State_Interface.restore() context."]
-pub type LV2_State_Retrieve_Function = ::std::option::Option<
+pub type LV2_State_Retrieve_Function = ::core::option::Option<
     unsafe extern "C" fn(
         handle: LV2_State_Handle,
         key: u32,
         size: *mut usize,
         type_: *mut u32,
         flags: *mut u32,
-    ) -> *const ::std::os::raw::c_void,
+    ) -> *const ::core::ffi::c_void,
 >;
 #[doc = "LV2 Plugin State Interface."]
 #[doc = ""]
@@ -2028,7 +2028,7 @@ pub struct LV2_State_Interface {
     #[doc = "Plugins that dynamically modify state while running, however, must take"]
     #[doc = "care to do so in such a way that a concurrent call to save() will save a"]
     #[doc = "consistent representation of plugin state for a single instant in time."]
-    pub save: ::std::option::Option<
+    pub save: ::core::option::Option<
         unsafe extern "C" fn(
             instance: LV2_Handle,
             store: LV2_State_Store_Function,
@@ -2060,7 +2060,7 @@ pub struct LV2_State_Interface {
     #[doc = "This function is in the \"Instantiation\" threading class as defined by"]
     #[doc = "LV2. This means it MUST NOT be called concurrently with any other"]
     #[doc = "function on the same plugin instance."]
-    pub restore: ::std::option::Option<
+    pub restore: ::core::option::Option<
         unsafe extern "C" fn(
             instance: LV2_Handle,
             retrieve: LV2_State_Retrieve_Function,
@@ -2093,11 +2093,11 @@ pub struct LV2_State_Map_Path {
     #[doc = "This function may only be called within the context of"]
     #[doc = "LV2_State_Interface methods.  The caller must free the returned value"]
     #[doc = "with LV2_State_Free_Path.free_path()."]
-    pub abstract_path: ::std::option::Option<
+    pub abstract_path: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2_State_Map_Path_Handle,
-            absolute_path: *const ::std::os::raw::c_char,
-        ) -> *mut ::std::os::raw::c_char,
+            absolute_path: *const ::core::ffi::c_char,
+        ) -> *mut ::core::ffi::c_char,
     >,
     #[doc = "Map an abstract path from plugin state to an absolute path."]
     #[doc = "@param handle MUST be the `handle` member of this struct."]
@@ -2110,11 +2110,11 @@ pub struct LV2_State_Map_Path {
     #[doc = "This function may only be called within the context of"]
     #[doc = "LV2_State_Interface methods.  The caller must free the returned value"]
     #[doc = "with LV2_State_Free_Path.free_path()."]
-    pub absolute_path: ::std::option::Option<
+    pub absolute_path: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2_State_Map_Path_Handle,
-            abstract_path: *const ::std::os::raw::c_char,
-        ) -> *mut ::std::os::raw::c_char,
+            abstract_path: *const ::core::ffi::c_char,
+        ) -> *mut ::core::ffi::c_char,
     >,
 }
 #[doc = "Feature data for state:makePath (@ref LV2_STATE__makePath)."]
@@ -2145,11 +2145,11 @@ pub struct LV2_State_Make_Path {
     #[doc = ""]
     #[doc = "The caller must free the returned value with"]
     #[doc = "LV2_State_Free_Path.free_path()."]
-    pub path: ::std::option::Option<
+    pub path: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2_State_Make_Path_Handle,
-            path: *const ::std::os::raw::c_char,
-        ) -> *mut ::std::os::raw::c_char,
+            path: *const ::core::ffi::c_char,
+        ) -> *mut ::core::ffi::c_char,
     >,
 }
 #[doc = "Feature data for state:freePath (@ref LV2_STATE__freePath)."]
@@ -2166,24 +2166,24 @@ pub struct LV2_State_Free_Path {
     #[doc = "This function can be used by plugins to free paths allocated by the host"]
     #[doc = "and returned by state features (LV2_State_Map_Path.abstract_path(),"]
     #[doc = "LV2_State_Map_Path.absolute_path(), and LV2_State_Make_Path.path())."]
-    pub free_path: ::std::option::Option<
-        unsafe extern "C" fn(handle: LV2_State_Free_Path_Handle, path: *mut ::std::os::raw::c_char),
+    pub free_path: ::core::option::Option<
+        unsafe extern "C" fn(handle: LV2_State_Free_Path_Handle, path: *mut ::core::ffi::c_char),
     >,
 }
 #[doc = "A pointer to some widget or other type of UI handle."]
 #[doc = ""]
 #[doc = "The actual type is defined by the type of the UI."]
-pub type LV2UI_Widget = *mut ::std::os::raw::c_void;
+pub type LV2UI_Widget = *mut ::core::ffi::c_void;
 #[doc = "A pointer to UI instance internals."]
 #[doc = ""]
 #[doc = "The host may compare this to NULL, but otherwise MUST NOT interpret it."]
-pub type LV2UI_Handle = *mut ::std::os::raw::c_void;
+pub type LV2UI_Handle = *mut ::core::ffi::c_void;
 #[doc = "A pointer to a controller provided by the host."]
 #[doc = ""]
 #[doc = "The UI may compare this to NULL, but otherwise MUST NOT interpret it."]
-pub type LV2UI_Controller = *mut ::std::os::raw::c_void;
+pub type LV2UI_Controller = *mut ::core::ffi::c_void;
 #[doc = "A pointer to opaque data for a feature."]
-pub type LV2UI_Feature_Handle = *mut ::std::os::raw::c_void;
+pub type LV2UI_Feature_Handle = *mut ::core::ffi::c_void;
 #[doc = "A host-provided function that sends data to a plugin's input ports."]
 #[doc = ""]
 #[doc = "@param controller The opaque controller pointer passed to"]
@@ -2201,13 +2201,13 @@ pub type LV2UI_Feature_Handle = *mut ::std::os::raw::c_void;
 #[doc = "be sizeof(float).  The UI SHOULD NOT use a protocol not supported by the"]
 #[doc = "host, but the host MUST gracefully ignore any protocol it does not"]
 #[doc = "understand."]
-pub type LV2UI_Write_Function = ::std::option::Option<
+pub type LV2UI_Write_Function = ::core::option::Option<
     unsafe extern "C" fn(
         controller: LV2UI_Controller,
         port_index: u32,
         buffer_size: u32,
         port_protocol: u32,
-        buffer: *const ::std::os::raw::c_void,
+        buffer: *const ::core::ffi::c_void,
     ),
 >;
 #[doc = "A plugin UI."]
@@ -2218,7 +2218,7 @@ pub type LV2UI_Write_Function = ::std::option::Option<
 #[derive(Debug, Copy, Clone)]
 pub struct LV2UI_Descriptor {
     #[doc = "The URI for this UI (not for the plugin it controls)."]
-    pub URI: *const ::std::os::raw::c_char,
+    pub URI: *const ::core::ffi::c_char,
     #[doc = "Create a new UI and return a handle to it.  This function works"]
     #[doc = "similarly to LV2_Descriptor::instantiate()."]
     #[doc = ""]
@@ -2243,11 +2243,11 @@ pub struct LV2UI_Descriptor {
     #[doc = "in LV2_Descriptor::instantiate().  Note that UI features and plugin"]
     #[doc = "features are not necessarily the same."]
     #[doc = ""]
-    pub instantiate: ::std::option::Option<
+    pub instantiate: ::core::option::Option<
         unsafe extern "C" fn(
             descriptor: *const LV2UI_Descriptor,
-            plugin_uri: *const ::std::os::raw::c_char,
-            bundle_path: *const ::std::os::raw::c_char,
+            plugin_uri: *const ::core::ffi::c_char,
+            bundle_path: *const ::core::ffi::c_char,
             write_function: LV2UI_Write_Function,
             controller: LV2UI_Controller,
             widget: *mut LV2UI_Widget,
@@ -2256,7 +2256,7 @@ pub struct LV2UI_Descriptor {
     >,
     #[doc = "Destroy the UI.  The host must not try to access the widget after"]
     #[doc = "calling this function."]
-    pub cleanup: ::std::option::Option<unsafe extern "C" fn(ui: LV2UI_Handle)>,
+    pub cleanup: ::core::option::Option<unsafe extern "C" fn(ui: LV2UI_Handle)>,
     #[doc = "Tell the UI that something interesting has happened at a plugin port."]
     #[doc = ""]
     #[doc = "What is \"interesting\" and how it is written to `buffer` is defined by"]
@@ -2274,13 +2274,13 @@ pub struct LV2UI_Descriptor {
     #[doc = "returns, it is only valid for the duration of the call."]
     #[doc = ""]
     #[doc = "This member may be NULL if the UI is not interested in any port events."]
-    pub port_event: ::std::option::Option<
+    pub port_event: ::core::option::Option<
         unsafe extern "C" fn(
             ui: LV2UI_Handle,
             port_index: u32,
             buffer_size: u32,
             format: u32,
-            buffer: *const ::std::os::raw::c_void,
+            buffer: *const ::core::ffi::c_void,
         ),
     >,
     #[doc = "Return a data structure associated with an extension URI, typically an"]
@@ -2289,8 +2289,8 @@ pub struct LV2UI_Descriptor {
     #[doc = "This member may be set to NULL if the UI is not interested in supporting"]
     #[doc = "any extensions. This is similar to LV2_Descriptor::extension_data()."]
     #[doc = ""]
-    pub extension_data: ::std::option::Option<
-        unsafe extern "C" fn(uri: *const ::std::os::raw::c_char) -> *const ::std::os::raw::c_void,
+    pub extension_data: ::core::option::Option<
+        unsafe extern "C" fn(uri: *const ::core::ffi::c_char) -> *const ::core::ffi::c_void,
     >,
 }
 #[doc = "Feature/interface for resizable UIs (LV2_UI__resize)."]
@@ -2313,12 +2313,12 @@ pub struct LV2UI_Resize {
     #[doc = "must pass the LV2UI_Handle to provide access to the UI instance."]
     #[doc = ""]
     #[doc = "@return 0 on success."]
-    pub ui_resize: ::std::option::Option<
+    pub ui_resize: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2UI_Feature_Handle,
-            width: ::std::os::raw::c_int,
-            height: ::std::os::raw::c_int,
-        ) -> ::std::os::raw::c_int,
+            width: ::core::ffi::c_int,
+            height: ::core::ffi::c_int,
+        ) -> ::core::ffi::c_int,
     >,
 }
 #[doc = "Feature to map port symbols to UIs."]
@@ -2335,10 +2335,10 @@ pub struct LV2UI_Port_Map {
     #[doc = ""]
     #[doc = "@return The index of the port, or LV2UI_INVALID_PORT_INDEX if no such"]
     #[doc = "port is found."]
-    pub port_index: ::std::option::Option<
+    pub port_index: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2UI_Feature_Handle,
-            symbol: *const ::std::os::raw::c_char,
+            symbol: *const ::core::ffi::c_char,
         ) -> u32,
     >,
 }
@@ -2362,7 +2362,7 @@ pub struct LV2UI_Port_Subscribe {
     #[doc = "@param port_protocol The URID of the ui:PortProtocol."]
     #[doc = "@param features Features for this subscription."]
     #[doc = "@return 0 on success."]
-    pub subscribe: ::std::option::Option<
+    pub subscribe: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2UI_Feature_Handle,
             port_index: u32,
@@ -2383,7 +2383,7 @@ pub struct LV2UI_Port_Subscribe {
     #[doc = "@param port_protocol The URID of the ui:PortProtocol."]
     #[doc = "@param features Features for this subscription."]
     #[doc = "@return 0 on success."]
-    pub unsubscribe: ::std::option::Option<
+    pub unsubscribe: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2UI_Feature_Handle,
             port_index: u32,
@@ -2407,7 +2407,7 @@ pub struct LV2UI_Touch {
     #[doc = "@param port_index The index of the port associated with the control."]
     #[doc = "@param grabbed If true, the control has been grabbed, otherwise the"]
     #[doc = "control has been released."]
-    pub touch: ::std::option::Option<
+    pub touch: ::core::option::Option<
         unsafe extern "C" fn(handle: LV2UI_Feature_Handle, port_index: u32, grabbed: bool),
     >,
 }
@@ -2474,7 +2474,7 @@ pub struct LV2UI_Request_Value {
     #[doc = "@param features Additional features for this request, or NULL."]
     #[doc = ""]
     #[doc = "@return A status code which is 0 on success."]
-    pub request: ::std::option::Option<
+    pub request: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2UI_Feature_Handle,
             key: LV2_URID,
@@ -2500,8 +2500,7 @@ pub struct LV2UI_Idle_Interface {
     #[doc = "@return non-zero if the UI has been closed, in which case the host"]
     #[doc = "should stop calling idle(), and can either completely destroy the UI, or"]
     #[doc = "re-show it and resume calling idle()."]
-    pub idle:
-        ::std::option::Option<unsafe extern "C" fn(ui: LV2UI_Handle) -> ::std::os::raw::c_int>,
+    pub idle: ::core::option::Option<unsafe extern "C" fn(ui: LV2UI_Handle) -> ::core::ffi::c_int>,
 }
 #[doc = "UI Show Interface (LV2_UI__showInterface)"]
 #[doc = ""]
@@ -2524,13 +2523,11 @@ pub struct LV2UI_Show_Interface {
     #[doc = "LV2_UI__windowTitle."]
     #[doc = ""]
     #[doc = "@return 0 on success, or anything else to stop being called."]
-    pub show:
-        ::std::option::Option<unsafe extern "C" fn(ui: LV2UI_Handle) -> ::std::os::raw::c_int>,
+    pub show: ::core::option::Option<unsafe extern "C" fn(ui: LV2UI_Handle) -> ::core::ffi::c_int>,
     #[doc = "Hide the window for this UI."]
     #[doc = ""]
     #[doc = "@return 0 on success, or anything else to stop being called."]
-    pub hide:
-        ::std::option::Option<unsafe extern "C" fn(ui: LV2UI_Handle) -> ::std::os::raw::c_int>,
+    pub hide: ::core::option::Option<unsafe extern "C" fn(ui: LV2UI_Handle) -> ::core::ffi::c_int>,
 }
 #[doc = "Peak data for a slice of time, the update format for ui:peakProtocol."]
 #[repr(C)]
@@ -2548,8 +2545,8 @@ pub struct LV2UI_Peak_Data {
 }
 #[doc = "The type of the lv2ui_descriptor() function."]
 pub type LV2UI_DescriptorFunction =
-    ::std::option::Option<unsafe extern "C" fn(index: u32) -> *const LV2UI_Descriptor>;
-pub type LV2_URI_Map_Callback_Data = *mut ::std::os::raw::c_void;
+    ::core::option::Option<unsafe extern "C" fn(index: u32) -> *const LV2UI_Descriptor>;
+pub type LV2_URI_Map_Callback_Data = *mut ::core::ffi::c_void;
 #[doc = "URI Map Feature."]
 #[doc = ""]
 #[doc = "To support this feature the host must pass an LV2_Feature struct to the"]
@@ -2588,11 +2585,11 @@ pub struct LV2_URI_Map_Feature {
     #[doc = "SHOULD NOT return 0 from this function in non-exceptional circumstances"]
     #[doc = "(e.g. the URI map SHOULD be dynamic). Hosts that statically support only"]
     #[doc = "a fixed set of URIs should not expect plugins to function correctly."]
-    pub uri_to_id: ::std::option::Option<
+    pub uri_to_id: ::core::option::Option<
         unsafe extern "C" fn(
             callback_data: LV2_URI_Map_Callback_Data,
-            map: *const ::std::os::raw::c_char,
-            uri: *const ::std::os::raw::c_char,
+            map: *const ::core::ffi::c_char,
+            uri: *const ::core::ffi::c_char,
         ) -> u32,
     >,
 }
@@ -2605,17 +2602,17 @@ pub const LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE: LV2_Worker_Status = 2;
 #[doc = "Status code for worker functions."]
 pub type LV2_Worker_Status = u32;
 #[doc = " Opaque handle for LV2_Worker_Interface::work()."]
-pub type LV2_Worker_Respond_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_Worker_Respond_Handle = *mut ::core::ffi::c_void;
 #[doc = "A function to respond to run() from the worker method."]
 #[doc = ""]
 #[doc = "The `data` MUST be safe for the host to copy and later pass to"]
 #[doc = "work_response(), and the host MUST guarantee that it will be eventually"]
 #[doc = "passed to work_response() if this function returns LV2_WORKER_SUCCESS."]
-pub type LV2_Worker_Respond_Function = ::std::option::Option<
+pub type LV2_Worker_Respond_Function = ::core::option::Option<
     unsafe extern "C" fn(
         handle: LV2_Worker_Respond_Handle,
         size: u32,
-        data: *const ::std::os::raw::c_void,
+        data: *const ::core::ffi::c_void,
     ) -> LV2_Worker_Status,
 >;
 #[doc = "Plugin Worker Interface."]
@@ -2641,13 +2638,13 @@ pub struct LV2_Worker_Interface {
     #[doc = "@param handle   Must be passed to `respond` if it is called."]
     #[doc = "@param size     The size of `data`."]
     #[doc = "@param data     Data from run(), or NULL."]
-    pub work: ::std::option::Option<
+    pub work: ::core::option::Option<
         unsafe extern "C" fn(
             instance: LV2_Handle,
             respond: LV2_Worker_Respond_Function,
             handle: LV2_Worker_Respond_Handle,
             size: u32,
-            data: *const ::std::os::raw::c_void,
+            data: *const ::core::ffi::c_void,
         ) -> LV2_Worker_Status,
     >,
     #[doc = "Handle a response from the worker.  This is called by the host in the"]
@@ -2656,11 +2653,11 @@ pub struct LV2_Worker_Interface {
     #[doc = "@param instance The LV2 instance this is a method on."]
     #[doc = "@param size     The size of `body`."]
     #[doc = "@param body     Message body, or NULL."]
-    pub work_response: ::std::option::Option<
+    pub work_response: ::core::option::Option<
         unsafe extern "C" fn(
             instance: LV2_Handle,
             size: u32,
-            body: *const ::std::os::raw::c_void,
+            body: *const ::core::ffi::c_void,
         ) -> LV2_Worker_Status,
     >,
     #[doc = "Called when all responses for this cycle have been delivered."]
@@ -2672,10 +2669,10 @@ pub struct LV2_Worker_Interface {
     #[doc = "host MUST call it after every run(), regardless of whether or not any"]
     #[doc = "responses were sent that cycle."]
     pub end_run:
-        ::std::option::Option<unsafe extern "C" fn(instance: LV2_Handle) -> LV2_Worker_Status>,
+        ::core::option::Option<unsafe extern "C" fn(instance: LV2_Handle) -> LV2_Worker_Status>,
 }
 #[doc = " Opaque handle for LV2_Worker_Schedule."]
-pub type LV2_Worker_Schedule_Handle = *mut ::std::os::raw::c_void;
+pub type LV2_Worker_Schedule_Handle = *mut ::core::ffi::c_void;
 #[doc = "Schedule Worker Host Feature."]
 #[doc = ""]
 #[doc = "The host passes this feature to provide a schedule_work() function, which"]
@@ -2709,11 +2706,11 @@ pub struct LV2_Worker_Schedule {
     #[doc = "@param handle The handle field of this struct."]
     #[doc = "@param size   The size of `data`."]
     #[doc = "@param data   Message to pass to work(), or NULL."]
-    pub schedule_work: ::std::option::Option<
+    pub schedule_work: ::core::option::Option<
         unsafe extern "C" fn(
             handle: LV2_Worker_Schedule_Handle,
             size: u32,
-            data: *const ::std::os::raw::c_void,
+            data: *const ::core::ffi::c_void,
         ) -> LV2_Worker_Status,
     >,
 }
@@ -2721,8 +2718,8 @@ pub type __builtin_va_list = [__va_list_tag; 1usize];
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct __va_list_tag {
-    pub gp_offset: ::std::os::raw::c_uint,
-    pub fp_offset: ::std::os::raw::c_uint,
-    pub overflow_arg_area: *mut ::std::os::raw::c_void,
-    pub reg_save_area: *mut ::std::os::raw::c_void,
+    pub gp_offset: ::core::ffi::c_uint,
+    pub fp_offset: ::core::ffi::c_uint,
+    pub overflow_arg_area: *mut ::core::ffi::c_void,
+    pub reg_save_area: *mut ::core::ffi::c_void,
 }
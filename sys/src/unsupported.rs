@@ -1 +1 @@
-compile_error!("The targeted operation system is not supported by rust-lv2!");
+compile_error!("The targeted operation system is not supported by the pre-generated bindings of rust-lv2! Enable the `regenerate-bindings` feature of `lv2-sys` to generate them.");
@@ -11,6 +11,8 @@ where
 {
     let mut bindings = bindgen::Builder::default()
         .size_t_is_usize(true)
+        .use_core()
+        .ctypes_prefix("::core::ffi")
         .whitelist_type("LV2.*")
         .whitelist_function("LV2.*")
         .whitelist_var("LV2.*")
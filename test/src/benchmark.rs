@@ -0,0 +1,227 @@
+use crate::realtime::{self, RtCheck};
+use crate::{TestError, TestHost};
+use core::prelude::*;
+use std::ffi::c_void;
+use std::time::{Duration, Instant};
+
+/// The time it took to run one cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CycleTime {
+    /// The number of frames of the cycle.
+    pub frames: u32,
+    /// The time the call to `run` took, including the handling of immediate work.
+    pub time: Duration,
+}
+
+/// The results of a benchmark.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchmarkReport {
+    /// The measurements of all cycles, in the order they were run.
+    pub cycles: Vec<CycleTime>,
+    /// The number of allocations, reallocations and deallocations of the plugin, or `None` if they weren't detected.
+    pub allocations: Option<u64>,
+    /// The number of page faults while the plugin ran, or `None` if they weren't detected.
+    pub page_faults: Option<u64>,
+}
+
+impl BenchmarkReport {
+    /// The sum of all cycle times.
+    pub fn total(&self) -> Duration {
+        self.cycles.iter().map(|cycle| cycle.time).sum()
+    }
+
+    /// The total number of frames that were processed.
+    pub fn frames(&self) -> u64 {
+        self.cycles.iter().map(|cycle| cycle.frames as u64).sum()
+    }
+
+    /// The cycle time below which the given percentage of cycle times lie, using the nearest-rank method.
+    ///
+    /// The percentage is clamped to the range from 0 to 100. For example, `percentile(50.0)` is the median and `percentile(100.0)` is the worst cycle time. If no cycles were run, the duration is zero.
+    pub fn percentile(&self, percentage: f64) -> Duration {
+        let mut times: Vec<Duration> = self.cycles.iter().map(|cycle| cycle.time).collect();
+        if times.is_empty() {
+            return Duration::default();
+        }
+        times.sort_unstable();
+        let rank = (percentage.clamp(0.0, 100.0) / 100.0 * times.len() as f64).ceil() as usize;
+        times[rank.max(1) - 1]
+    }
+
+    /// The longest cycle time.
+    pub fn worst(&self) -> Duration {
+        self.percentile(100.0)
+    }
+
+    /// Whether a real-time violation was detected.
+    pub fn has_violations(&self) -> bool {
+        self.allocations.unwrap_or(0) > 0 || self.page_faults.unwrap_or(0) > 0
+    }
+}
+
+/// Measures how long the `run` method of a plugin takes.
+///
+/// A benchmark runs a plugin for many cycles with random block lengths, just like hosts with variable block sizes do, and measures every cycle. Before the measurement, a number of warm-up cycles with the maximal block length are run, which fill the caches and let the plugin touch all of its memory. The audio input ports are connected to white noise.
+///
+/// The benchmark can also detect [violations of real-time constraints](enum.RtCheck.html) in the measured cycles.
+///
+/// [`time`](#method.time) can be used as the routine of criterion's `iter_custom`:
+///
+/// ```ignore
+/// let mut host = TestHost::<Amp>::new().unwrap();
+/// let benchmark = Benchmark::new(&[1], &[2]);
+/// criterion.bench_function("amp", |b| {
+///     b.iter_custom(|iterations| benchmark.time(&mut host, iterations).unwrap())
+/// });
+/// ```
+pub struct Benchmark {
+    inputs: Vec<u32>,
+    outputs: Vec<u32>,
+    min_block_length: u32,
+    max_block_length: u32,
+    warmup: usize,
+    seed: u64,
+    rt_check: RtCheck,
+}
+
+impl Benchmark {
+    /// Create a benchmark with the indices of the audio input and output ports.
+    ///
+    /// By default, the block lengths are between 1 and 1024 frames, 16 warm-up cycles are run and real-time violations aren't detected. All other ports have to be connected by the test.
+    pub fn new(inputs: &[u32], outputs: &[u32]) -> Self {
+        Self {
+            inputs: inputs.to_owned(),
+            outputs: outputs.to_owned(),
+            min_block_length: 1,
+            max_block_length: 1024,
+            warmup: 16,
+            seed: 0x853c_49e6_748f_ea9b,
+            rt_check: RtCheck::Off,
+        }
+    }
+
+    /// Set the range of the block lengths, including both bounds.
+    ///
+    /// Using the same minimum and maximum gives a fixed block length.
+    pub fn block_lengths(mut self, min: u32, max: u32) -> Self {
+        self.min_block_length = min.max(1);
+        self.max_block_length = max.max(self.min_block_length);
+        self
+    }
+
+    /// Set the number of warm-up cycles, which aren't measured.
+    pub fn warmup(mut self, cycles: usize) -> Self {
+        self.warmup = cycles;
+        self
+    }
+
+    /// Set the seed of the random block lengths and input noise.
+    ///
+    /// Benchmarks with the same seed run the same cycles.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set how real-time violations in the measured cycles are treated.
+    pub fn rt_check(mut self, rt_check: RtCheck) -> Self {
+        self.rt_check = rt_check;
+        self
+    }
+
+    /// Run the given number of measured cycles and report the results.
+    pub fn run<P: Plugin>(
+        &self,
+        host: &mut TestHost<P>,
+        cycles: usize,
+    ) -> Result<BenchmarkReport, TestError> {
+        let mut random = XorShift(self.seed.max(1));
+        let max_length = self.max_block_length as usize;
+
+        let mut input_buffers: Vec<Vec<f32>> = (0..self.inputs.len())
+            .map(|_| (0..max_length).map(|_| random.next_sample()).collect())
+            .collect();
+        let mut output_buffers: Vec<Vec<f32>> = vec![vec![0.0; max_length]; self.outputs.len()];
+        let lengths: Vec<u32> = (0..cycles)
+            .map(|_| random.next_length(self.min_block_length, self.max_block_length))
+            .collect();
+        // The measurements are stored in preallocated memory, so that the benchmark doesn't allocate between the cycles.
+        let mut report = BenchmarkReport {
+            cycles: Vec::with_capacity(cycles),
+            allocations: None,
+            page_faults: None,
+        };
+
+        let result: Result<(), TestError> = (|| {
+            unsafe {
+                for (port, buffer) in self.inputs.iter().zip(input_buffers.iter_mut()) {
+                    host.connect_port(*port, buffer.as_mut_ptr());
+                }
+                for (port, buffer) in self.outputs.iter().zip(output_buffers.iter_mut()) {
+                    host.connect_port(*port, buffer.as_mut_ptr());
+                }
+            }
+            for _ in 0..self.warmup {
+                host.run_cycle(self.max_block_length)?;
+            }
+
+            realtime::set_check(self.rt_check);
+            for length in lengths {
+                let start = Instant::now();
+                let result = host.run_cycle(length);
+                let time = start.elapsed();
+                result?;
+                report.cycles.push(CycleTime {
+                    frames: length,
+                    time,
+                });
+            }
+            if self.rt_check != RtCheck::Off {
+                report.allocations = realtime::allocations();
+                report.page_faults = realtime::page_faults();
+            }
+            Ok(())
+        })();
+        realtime::set_check(RtCheck::Off);
+
+        // The buffers are dropped after the benchmark, so the plugin must not access them anymore.
+        for port in self.inputs.iter().chain(self.outputs.iter()) {
+            unsafe { host.connect_port(*port, std::ptr::null_mut::<c_void>()) };
+        }
+
+        result.map(|_| report)
+    }
+
+    /// Run the given number of measured cycles and return the total time.
+    ///
+    /// This has the signature criterion expects from the routine of `Bencher::iter_custom`.
+    pub fn time<P: Plugin>(
+        &self,
+        host: &mut TestHost<P>,
+        iterations: u64,
+    ) -> Result<Duration, TestError> {
+        self.run(host, iterations as usize)
+            .map(|report| report.total())
+    }
+}
+
+/// A xorshift random number generator, which is good enough to vary block lengths, generate noise and generate test atoms.
+pub(crate) struct XorShift(pub(crate) u64);
+
+impl XorShift {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn next_length(&mut self, min: u32, max: u32) -> u32 {
+        let range = (max - min) as u64 + 1;
+        min + (self.next() % range) as u32
+    }
+
+    fn next_sample(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+}
@@ -0,0 +1,503 @@
+use atom::object::{ObjectHeader, ObjectReader};
+use atom::scalar::Int;
+use atom::sequence::TimeStamp;
+use atom::string::LiteralInfo;
+use atom::{AtomURIDCollection, UnidentifiedAtom};
+use std::convert::TryInto;
+use std::fmt;
+use std::mem::size_of;
+use units::prelude::*;
+use urid::*;
+
+/// The first difference between two atoms, as found by [`diff_atoms`](fn.diff_atoms.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtomDiff {
+    /// The path from the compared atoms to the mismatching atoms.
+    ///
+    /// Elements of tuples, vectors and sequences are written as `[index]` and properties of objects as `.<key URI>`. For example, `[2].<http://lv2plug.in/ns/ext/patch#value>` is the value property of the object in the third event of a sequence. The path is empty if the compared atoms themselves differ.
+    pub path: String,
+    /// The expected value at the path.
+    pub expected: String,
+    /// The actual value at the path.
+    pub actual: String,
+}
+
+impl fmt::Display for AtomDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "atom"
+        } else {
+            self.path.as_str()
+        };
+        write!(
+            f,
+            "atoms differ at {}: expected {}, found {}",
+            path, self.expected, self.actual
+        )
+    }
+}
+
+/// Compare two atoms structurally and return the first difference.
+///
+/// Sequences, tuples and vectors are compared element by element, including the time stamps of events. Objects are compared by their type, their id and their properties, which may be in any order. Scalars, strings and literals are compared by value and atoms of other types are compared by their bodies. Floats are compared exactly, but `NaN`s are equal to each other.
+///
+/// URIDs are written as their URIs, which are looked up with the mapper that was used to create the atoms, like the [`mapper`](struct.TestHost.html#method.mapper) of the test host. Returns `None` if the atoms are equal.
+pub fn diff_atoms<M: Map + Unmap>(
+    mapper: &M,
+    expected: UnidentifiedAtom,
+    actual: UnidentifiedAtom,
+) -> Option<AtomDiff> {
+    let differ = Differ {
+        mapper,
+        atom_urids: mapper.populate_collection()?,
+        beat: mapper.map_type()?,
+    };
+    differ
+        .diff(expected, actual)
+        .err()
+        .map(|(path, expected, actual)| AtomDiff {
+            path: path.into_iter().rev().collect(),
+            expected,
+            actual,
+        })
+}
+
+/// Assert that two atoms are equal.
+///
+/// # Panics
+///
+/// Panics with the first difference, as found by [`diff_atoms`](fn.diff_atoms.html), if the atoms aren't equal.
+pub fn assert_atoms_eq<M: Map + Unmap>(
+    mapper: &M,
+    expected: UnidentifiedAtom,
+    actual: UnidentifiedAtom,
+) {
+    if let Some(diff) = diff_atoms(mapper, expected, actual) {
+        panic!("{}", diff);
+    }
+}
+
+/// A mismatch with the reversed segments of its path and the formatted expected and actual values.
+type Mismatch = (Vec<String>, String, String);
+
+/// Compares atoms with the URIDs of one mapper.
+struct Differ<'a, M> {
+    mapper: &'a M,
+    atom_urids: AtomURIDCollection,
+    beat: URID<Beat>,
+}
+
+impl<'a, M: Unmap> Differ<'a, M> {
+    /// Write a URID as its URI, or as its number if it isn't mapped.
+    fn uri<T: ?Sized>(&self, urid: URID<T>) -> String {
+        match self.mapper.unmap(urid).and_then(|uri| uri.to_str().ok()) {
+            Some(uri) => format!("<{}>", uri),
+            None => format!("URID {}", urid.get()),
+        }
+    }
+
+    fn optional_uri(&self, urid: Option<URID>) -> String {
+        urid.map(|urid| self.uri(urid))
+            .unwrap_or_else(|| "none".to_owned())
+    }
+
+    fn mismatch<T: ToString>(expected: T, actual: T) -> Result<(), Mismatch> {
+        Err((Vec::new(), expected.to_string(), actual.to_string()))
+    }
+
+    /// Add a segment in front of the path of a mismatch.
+    fn within<T>(segment: String, result: Result<T, Mismatch>) -> Result<T, Mismatch> {
+        result.map_err(|(mut path, expected, actual)| {
+            path.push(segment);
+            (path, expected, actual)
+        })
+    }
+
+    /// Return the body of an atom.
+    fn body<'b>(atom: UnidentifiedAtom<'b>) -> &'b [u8] {
+        let size = atom.as_raw().map(|header| header.size as usize);
+        atom.atom_space()
+            .and_then(|space| space.data())
+            .zip(size)
+            .and_then(|(data, size)| data.get(size_of::<sys::LV2_Atom>()..)?.get(..size))
+            .unwrap_or(&[])
+    }
+
+    /// Return the child size, the child type and the children of a vector.
+    fn vector_body<'b>(atom: UnidentifiedAtom<'b>) -> Option<(u32, u32, &'b [u8])> {
+        let body = Self::body(atom);
+        let child_size = u32::from_ne_bytes(body.get(0..4)?.try_into().ok()?);
+        let child_type = u32::from_ne_bytes(body.get(4..8)?.try_into().ok()?);
+        Some((child_size, child_type, &body[8..]))
+    }
+
+    fn diff(&self, expected: UnidentifiedAtom, actual: UnidentifiedAtom) -> Result<(), Mismatch> {
+        let urids = &self.atom_urids;
+        let (expected_type, actual_type) = match (expected.type_urid(), actual.type_urid()) {
+            (Some(expected_type), Some(actual_type)) => (expected_type, actual_type),
+            (None, None) => return Ok(()),
+            (expected_type, actual_type) => {
+                return Self::mismatch(
+                    format!("an atom of type {}", self.optional_uri(expected_type)),
+                    format!("an atom of type {}", self.optional_uri(actual_type)),
+                )
+            }
+        };
+        if expected_type != actual_type {
+            return Self::mismatch(
+                format!("an atom of type {}", self.uri(expected_type)),
+                format!("an atom of type {}", self.uri(actual_type)),
+            );
+        }
+
+        if expected_type == urids.int {
+            self.diff_value(expected.read(urids.int, ()), actual.read(urids.int, ()))
+        } else if expected_type == urids.long {
+            self.diff_value(expected.read(urids.long, ()), actual.read(urids.long, ()))
+        } else if expected_type == urids.bool {
+            self.diff_value(
+                expected.read(urids.bool, ()).map(|value| value != 0),
+                actual.read(urids.bool, ()).map(|value| value != 0),
+            )
+        } else if expected_type == urids.float {
+            self.diff_float(
+                expected.read(urids.float, ()).map(f64::from),
+                actual.read(urids.float, ()).map(f64::from),
+            )
+        } else if expected_type == urids.double {
+            self.diff_float(
+                expected.read(urids.double, ()),
+                actual.read(urids.double, ()),
+            )
+        } else if expected_type == urids.urid {
+            let expected = expected.read(urids.urid, ()).map(|urid| self.uri(urid));
+            let actual = actual.read(urids.urid, ()).map(|urid| self.uri(urid));
+            if expected != actual {
+                return Self::mismatch(
+                    expected.unwrap_or_else(|| "a malformed URID".to_owned()),
+                    actual.unwrap_or_else(|| "a malformed URID".to_owned()),
+                );
+            }
+            Ok(())
+        } else if expected_type == urids.string {
+            self.diff_value(
+                expected.read(urids.string, ()),
+                actual.read(urids.string, ()),
+            )
+        } else if expected_type == urids.path {
+            self.diff_value(expected.read(urids.path, ()), actual.read(urids.path, ()))
+        } else if expected_type == urids.literal {
+            self.diff_literal(
+                expected.read(urids.literal, ()),
+                actual.read(urids.literal, ()),
+            )
+        } else if expected_type == urids.object || expected_type == urids.blank {
+            self.diff_object(expected, actual)
+        } else if expected_type == urids.tuple {
+            match (expected.read(urids.tuple, ()), actual.read(urids.tuple, ())) {
+                (Some(expected), Some(actual)) => self.diff_elements(expected, actual),
+                _ => self.diff_bodies(expected, actual),
+            }
+        } else if expected_type == urids.sequence {
+            self.diff_sequence(expected, actual)
+        } else if expected_type == urids.vector::<Int>() {
+            self.diff_vector(expected, actual)
+        } else {
+            self.diff_bodies(expected, actual)
+        }
+    }
+
+    fn diff_value<T: PartialEq + fmt::Debug>(
+        &self,
+        expected: Option<T>,
+        actual: Option<T>,
+    ) -> Result<(), Mismatch> {
+        if expected != actual {
+            return Self::mismatch(Self::format_value(expected), Self::format_value(actual));
+        }
+        Ok(())
+    }
+
+    fn format_value<T: fmt::Debug>(value: Option<T>) -> String {
+        value
+            .map(|value| format!("{:?}", value))
+            .unwrap_or_else(|| "a malformed atom".to_owned())
+    }
+
+    fn diff_float(&self, expected: Option<f64>, actual: Option<f64>) -> Result<(), Mismatch> {
+        match (expected, actual) {
+            (Some(expected), Some(actual))
+                if expected == actual || (expected.is_nan() && actual.is_nan()) =>
+            {
+                Ok(())
+            }
+            (expected, actual) => self.diff_value(expected, actual),
+        }
+    }
+
+    fn diff_literal(
+        &self,
+        expected: Option<(LiteralInfo, &str)>,
+        actual: Option<(LiteralInfo, &str)>,
+    ) -> Result<(), Mismatch> {
+        if expected == actual {
+            return Ok(());
+        }
+        let format = |literal: Option<(LiteralInfo, &str)>| match literal {
+            Some((LiteralInfo::Language(language), text)) => {
+                format!("{:?} in language {}", text, self.uri(language))
+            }
+            Some((LiteralInfo::Datatype(datatype), text)) => {
+                format!("{:?} of datatype {}", text, self.uri(datatype))
+            }
+            None => "a malformed literal".to_owned(),
+        };
+        Self::mismatch(format(expected), format(actual))
+    }
+
+    /// Read the header and the properties of an object or blank.
+    fn read_object<'b>(
+        &self,
+        atom: UnidentifiedAtom<'b>,
+    ) -> Option<(ObjectHeader, ObjectReader<'b>)> {
+        let urids = &self.atom_urids;
+        let (header, _) = atom
+            .read(urids.object, ())
+            .or_else(|| atom.read(urids.blank, ()))?;
+        Some((header, ObjectReader::from_atom(atom, urids)?))
+    }
+
+    fn diff_object(
+        &self,
+        expected: UnidentifiedAtom,
+        actual: UnidentifiedAtom,
+    ) -> Result<(), Mismatch> {
+        let ((expected_header, expected_reader), (actual_header, actual_reader)) =
+            match (self.read_object(expected), self.read_object(actual)) {
+                (Some(expected), Some(actual)) => (expected, actual),
+                _ => return self.diff_bodies(expected, actual),
+            };
+
+        if expected_header.otype != actual_header.otype {
+            return Self::mismatch(
+                format!("an object of type {}", self.uri(expected_header.otype)),
+                format!("an object of type {}", self.uri(actual_header.otype)),
+            );
+        }
+        if expected_header.id != actual_header.id {
+            return Self::mismatch(
+                format!(
+                    "an object with id {}",
+                    self.optional_uri(expected_header.id)
+                ),
+                format!("an object with id {}", self.optional_uri(actual_header.id)),
+            );
+        }
+
+        let mut actual_properties: Vec<_> = actual_reader.collect();
+        for (header, expected_value) in expected_reader {
+            let segment = format!(".{}", self.uri(header.key));
+            let position = actual_properties.iter().position(|(actual_header, _)| {
+                actual_header.key == header.key && actual_header.context == header.context
+            });
+            let (_, actual_value) = match position {
+                Some(position) => actual_properties.remove(position),
+                None => return Self::within(segment, Self::mismatch("a property", "no property")),
+            };
+            Self::within(segment, self.diff(expected_value, actual_value))?;
+        }
+        if let Some((header, _)) = actual_properties.first() {
+            return Self::within(
+                format!(".{}", self.uri(header.key)),
+                Self::mismatch("no property", "a property"),
+            );
+        }
+        Ok(())
+    }
+
+    fn diff_elements<'b>(
+        &self,
+        expected: impl Iterator<Item = UnidentifiedAtom<'b>>,
+        actual: impl Iterator<Item = UnidentifiedAtom<'b>>,
+    ) -> Result<(), Mismatch> {
+        let mut actual = actual.fuse();
+        let mut count = 0;
+        for (index, expected) in expected.enumerate() {
+            let segment = format!("[{}]", index);
+            match actual.next() {
+                Some(actual) => Self::within(segment, self.diff(expected, actual))?,
+                None => return Self::within(segment, Self::mismatch("an element", "no element")),
+            }
+            count += 1;
+        }
+        if actual.next().is_some() {
+            return Self::within(
+                format!("[{}]", count),
+                Self::mismatch("no element", "an element"),
+            );
+        }
+        Ok(())
+    }
+
+    fn diff_sequence(
+        &self,
+        expected: UnidentifiedAtom,
+        actual: UnidentifiedAtom,
+    ) -> Result<(), Mismatch> {
+        let urids = &self.atom_urids;
+        let (expected_events, actual_events) = match (
+            expected.read(urids.sequence, self.beat),
+            actual.read(urids.sequence, self.beat),
+        ) {
+            (Some(expected), Some(actual)) => (expected, actual),
+            _ => return self.diff_bodies(expected, actual),
+        };
+        if expected_events.unit() != actual_events.unit() {
+            return Self::mismatch(
+                format!("time stamps in {:?}", expected_events.unit()),
+                format!("time stamps in {:?}", actual_events.unit()),
+            );
+        }
+
+        let format_stamp = |stamp: TimeStamp| match stamp {
+            TimeStamp::Frames(frames) => format!("an event at frame {}", frames),
+            TimeStamp::BeatsPerMinute(beats) => format!("an event at beat {}", beats),
+        };
+        let mut actual_events = actual_events.fuse();
+        let mut count = 0;
+        for (index, (expected_stamp, expected_atom)) in expected_events.enumerate() {
+            let segment = format!("[{}]", index);
+            let (actual_stamp, actual_atom) = match actual_events.next() {
+                Some(event) => event,
+                None => {
+                    return Self::within(
+                        segment,
+                        Self::mismatch(format_stamp(expected_stamp), "no event".to_owned()),
+                    )
+                }
+            };
+            let same_stamp = match (expected_stamp, actual_stamp) {
+                (TimeStamp::Frames(expected), TimeStamp::Frames(actual)) => expected == actual,
+                (TimeStamp::BeatsPerMinute(expected), TimeStamp::BeatsPerMinute(actual)) => {
+                    expected == actual
+                }
+                _ => false,
+            };
+            if !same_stamp {
+                return Self::within(
+                    segment,
+                    Self::mismatch(format_stamp(expected_stamp), format_stamp(actual_stamp)),
+                );
+            }
+            Self::within(segment, self.diff(expected_atom, actual_atom))?;
+            count += 1;
+        }
+        if let Some((stamp, _)) = actual_events.next() {
+            return Self::within(
+                format!("[{}]", count),
+                Self::mismatch("no event".to_owned(), format_stamp(stamp)),
+            );
+        }
+        Ok(())
+    }
+
+    fn diff_vector(
+        &self,
+        expected: UnidentifiedAtom,
+        actual: UnidentifiedAtom,
+    ) -> Result<(), Mismatch> {
+        let (
+            (expected_size, expected_type, expected_data),
+            (actual_size, actual_type, actual_data),
+        ) = match (Self::vector_body(expected), Self::vector_body(actual)) {
+            (Some(expected), Some(actual)) => (expected, actual),
+            _ => return self.diff_bodies(expected, actual),
+        };
+        if expected_type != actual_type || expected_size != actual_size {
+            let format = |child_type: u32| {
+                format!("a vector of {}", self.optional_uri(URID::new(child_type)))
+            };
+            return Self::mismatch(format(expected_type), format(actual_type));
+        }
+        let child_size = expected_size as usize;
+        if child_size == 0 {
+            return self.diff_bodies(expected, actual);
+        }
+
+        let format = |child: &[u8]| -> String {
+            let urids = &self.atom_urids;
+            let child_type = expected_type;
+            if child_type == urids.int {
+                format!(
+                    "{:?}",
+                    i32::from_ne_bytes(child.try_into().unwrap_or_default())
+                )
+            } else if child_type == urids.float {
+                format!(
+                    "{:?}",
+                    f32::from_ne_bytes(child.try_into().unwrap_or_default())
+                )
+            } else if child_type == urids.long {
+                format!(
+                    "{:?}",
+                    i64::from_ne_bytes(child.try_into().unwrap_or_default())
+                )
+            } else if child_type == urids.double {
+                format!(
+                    "{:?}",
+                    f64::from_ne_bytes(child.try_into().unwrap_or_default())
+                )
+            } else {
+                format!("{:02x?}", child)
+            }
+        };
+        let mut expected_children = expected_data.chunks(child_size);
+        let mut actual_children = actual_data.chunks(child_size);
+        let mut index = 0;
+        loop {
+            let segment = format!("[{}]", index);
+            match (expected_children.next(), actual_children.next()) {
+                (Some(expected), Some(actual)) if expected != actual => {
+                    return Self::within(segment, Self::mismatch(format(expected), format(actual)))
+                }
+                (Some(_), Some(_)) => (),
+                (Some(expected), None) => {
+                    return Self::within(
+                        segment,
+                        Self::mismatch(format(expected), "no element".to_owned()),
+                    )
+                }
+                (None, Some(actual)) => {
+                    return Self::within(
+                        segment,
+                        Self::mismatch("no element".to_owned(), format(actual)),
+                    )
+                }
+                (None, None) => return Ok(()),
+            }
+            index += 1;
+        }
+    }
+
+    fn diff_bodies(
+        &self,
+        expected: UnidentifiedAtom,
+        actual: UnidentifiedAtom,
+    ) -> Result<(), Mismatch> {
+        let expected = Self::body(expected);
+        let actual = Self::body(actual);
+        if expected.len() != actual.len() {
+            return Self::mismatch(
+                format!("a body of {} bytes", expected.len()),
+                format!("a body of {} bytes", actual.len()),
+            );
+        }
+        match expected.iter().zip(actual).position(|(a, b)| a != b) {
+            Some(offset) => Self::mismatch(
+                format!("byte {:#04x} at offset {}", expected[offset], offset),
+                format!("byte {:#04x} at offset {}", actual[offset], offset),
+            ),
+            None => Ok(()),
+        }
+    }
+}
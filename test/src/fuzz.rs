@@ -0,0 +1,504 @@
+use crate::benchmark::XorShift;
+use atom::matrix::MatrixURIDs;
+use atom::object::ObjectReader;
+use atom::prelude::*;
+use atom::space::Space;
+use units::prelude::*;
+use urid::*;
+
+/// A byte buffer that is aligned like an atom buffer of a host.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtomBuffer {
+    data: Box<[u64]>,
+    len: usize,
+}
+
+impl AtomBuffer {
+    /// Copy bytes into a new, 64-bit-aligned buffer.
+    pub fn new(bytes: &[u8]) -> Self {
+        let mut data = vec![0u64; bytes.len().div_ceil(8)].into_boxed_slice();
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), data.as_mut_ptr() as *mut u8, bytes.len())
+        };
+        Self {
+            data,
+            len: bytes.len(),
+        }
+    }
+
+    /// The bytes of the buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr() as *const u8, self.len) }
+    }
+
+    /// The atom at the start of the buffer.
+    ///
+    /// The buffer may not actually contain an atom, which the readers have to handle.
+    pub fn atom(&self) -> UnidentifiedAtom<'_> {
+        UnidentifiedAtom::new(Space::from_slice(self.as_bytes()))
+    }
+}
+
+/// Generates random atoms and byte buffers to test atom readers with.
+///
+/// The generated buffers are either [random bytes](#method.bytes), [valid atoms](#method.valid_atom) of all types of the atom crate, or [mutated atoms](#method.mutated_atom), which are valid atoms with flipped bytes, corrupted sizes, or truncated bodies. The mutated atoms get past the first checks of the readers, which makes them find more errors than random bytes. Generators with the same seed generate the same buffers, which makes failures reproducible.
+///
+/// Authors of custom atom types can add their types with [`custom_type`](#method.custom_type) and feed the generated [`cases`](#method.cases) to their readers.
+///
+/// # Example
+///
+/// ```
+/// use lv2_test::*;
+///
+/// let fuzzer = AtomFuzzer::new();
+/// let mut generator = fuzzer.generator(42);
+/// for buffer in generator.cases(100) {
+///     // Panics if a reader panics, reads outside the buffer or accepts an atom of the wrong type.
+///     fuzzer.check_atom(buffer.as_bytes());
+/// }
+/// ```
+pub struct AtomGenerator {
+    random: XorShift,
+    urids: AtomURIDCollection,
+    frame: URID<Frame>,
+    beat: URID<Beat>,
+    custom_types: Vec<URID>,
+    max_depth: usize,
+    max_length: usize,
+}
+
+impl AtomGenerator {
+    /// Create a generator with the URIDs of the atom types and time units and a seed.
+    pub fn new(urids: AtomURIDCollection, units: &UnitURIDCollection, seed: u64) -> Self {
+        Self {
+            random: XorShift(seed.max(1)),
+            urids,
+            frame: units.frame,
+            beat: units.beat,
+            custom_types: Vec::new(),
+            max_depth: 3,
+            max_length: 8,
+        }
+    }
+
+    /// Add a custom atom type, whose atoms are generated with random bodies.
+    pub fn custom_type<T: ?Sized>(mut self, urid: URID<T>) -> Self {
+        self.custom_types.push(urid.into_general());
+        self
+    }
+
+    /// Set how deeply containers like tuples, objects and sequences are nested.
+    ///
+    /// The default depth is 3.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Set the maximal number of elements of containers, and of bytes of strings and chunks divided by four.
+    ///
+    /// The default length is 8.
+    pub fn max_length(mut self, length: usize) -> Self {
+        self.max_length = length.max(1);
+        self
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.random.next() % bound.max(1) as u64) as usize
+    }
+
+    fn length(&mut self) -> usize {
+        self.below(self.max_length + 1)
+    }
+
+    /// Generate up to `max_length` bytes of random data.
+    pub fn bytes(&mut self, max_length: usize) -> Vec<u8> {
+        let length = self.below(max_length + 1);
+        (0..length).map(|_| self.random.next() as u8).collect()
+    }
+
+    /// Generate a valid atom of a random type.
+    pub fn valid_atom(&mut self) -> AtomBuffer {
+        let depth = self.max_depth;
+        AtomBuffer::new(&self.atom(depth))
+    }
+
+    /// Generate a valid atom and corrupt it.
+    pub fn mutated_atom(&mut self) -> AtomBuffer {
+        let depth = self.max_depth;
+        let mut bytes = self.atom(depth);
+        for _ in 0..=self.below(3) {
+            match self.below(4) {
+                // Flip a byte.
+                0 => {
+                    let index = self.below(bytes.len());
+                    bytes[index] ^= self.random.next() as u8 | 1;
+                }
+                // Overwrite a word, which may be a size or a type.
+                1 if bytes.len() >= 4 => {
+                    let index = self.below(bytes.len() / 4) * 4;
+                    let value = match self.below(4) {
+                        0 => 0,
+                        1 => u32::MAX,
+                        2 => self.below(bytes.len() + 16) as u32,
+                        _ => self.random.next() as u32,
+                    };
+                    bytes[index..index + 4].copy_from_slice(&value.to_ne_bytes());
+                }
+                // Truncate the atom.
+                1 | 2 => {
+                    let length = self.below(bytes.len());
+                    bytes.truncate(length);
+                }
+                // Append garbage.
+                _ => {
+                    let garbage = self.bytes(16);
+                    bytes.extend(garbage);
+                }
+            }
+            if bytes.is_empty() {
+                break;
+            }
+        }
+        AtomBuffer::new(&bytes)
+    }
+
+    /// Generate a number of test cases, which are a mix of random bytes, valid atoms and mutated atoms.
+    pub fn cases(&mut self, count: usize) -> Vec<AtomBuffer> {
+        (0..count)
+            .map(|_| match self.below(4) {
+                0 => {
+                    let bytes = self.bytes(64);
+                    AtomBuffer::new(&bytes)
+                }
+                1 => self.valid_atom(),
+                _ => self.mutated_atom(),
+            })
+            .collect()
+    }
+
+    /// Write the header and the padded body of an atom.
+    fn frame(type_: u32, body: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + body.len() + 7);
+        bytes.extend_from_slice(&(body.len() as u32).to_ne_bytes());
+        bytes.extend_from_slice(&type_.to_ne_bytes());
+        bytes.extend_from_slice(body);
+        bytes.resize(bytes.len().div_ceil(8) * 8, 0);
+        bytes
+    }
+
+    fn text(&mut self) -> Vec<u8> {
+        let length = self.length() * 4;
+        let mut text: Vec<u8> = (0..length).map(|_| b' ' + self.below(95) as u8).collect();
+        text.push(0);
+        text
+    }
+
+    fn scalar_type(&mut self) -> (u32, usize) {
+        match self.below(4) {
+            0 => (self.urids.int.get(), 4),
+            1 => (self.urids.float.get(), 4),
+            2 => (self.urids.long.get(), 8),
+            _ => (self.urids.double.get(), 8),
+        }
+    }
+
+    fn atom(&mut self, depth: usize) -> Vec<u8> {
+        let urids = self.urids.clone();
+        // Containers are only generated if they may contain other atoms.
+        let builtin = if depth == 0 { 11 } else { 14 };
+        let kind = self.below(builtin + self.custom_types.len());
+        let random = self.random.next();
+        if kind >= builtin {
+            let custom_type = self.custom_types[kind - builtin];
+            let max_length = self.max_length * 4;
+            let body = self.bytes(max_length);
+            return Self::frame(custom_type.get(), &body);
+        }
+        match kind {
+            0 => Self::frame(urids.int.get(), &(random as i32).to_ne_bytes()),
+            1 => Self::frame(urids.float.get(), &(random as f32).to_ne_bytes()),
+            2 => Self::frame(urids.long.get(), &(random as i64).to_ne_bytes()),
+            3 => Self::frame(urids.double.get(), &(random as f64).to_ne_bytes()),
+            4 => Self::frame(urids.bool.get(), &((random % 2) as i32).to_ne_bytes()),
+            5 => Self::frame(urids.urid.get(), &urids.int.get().to_ne_bytes()),
+            6 => {
+                let text = self.text();
+                Self::frame(urids.string.get(), &text)
+            }
+            7 => {
+                let text = self.text();
+                Self::frame(urids.path.get(), &text)
+            }
+            8 => {
+                let mut body = Vec::new();
+                if random.is_multiple_of(2) {
+                    body.extend_from_slice(&urids.string.get().to_ne_bytes());
+                    body.extend_from_slice(&0u32.to_ne_bytes());
+                } else {
+                    body.extend_from_slice(&0u32.to_ne_bytes());
+                    body.extend_from_slice(&urids.string.get().to_ne_bytes());
+                }
+                body.extend(self.text());
+                Self::frame(urids.literal.get(), &body)
+            }
+            9 => {
+                let max_length = self.max_length * 4;
+                let body = self.bytes(max_length);
+                Self::frame(urids.chunk.get(), &body)
+            }
+            10 => {
+                let (child_type, child_size) = self.scalar_type();
+                let mut body = Vec::new();
+                body.extend_from_slice(&(child_size as u32).to_ne_bytes());
+                body.extend_from_slice(&child_type.to_ne_bytes());
+                let length = self.length() * child_size;
+                body.extend((0..length).map(|_| self.random.next() as u8));
+                Self::frame(urids.vector::<Int>().get(), &body)
+            }
+            11 => {
+                let body: Vec<u8> = (0..self.length())
+                    .flat_map(|_| self.atom(depth - 1))
+                    .collect();
+                Self::frame(urids.tuple.get(), &body)
+            }
+            12 => {
+                let mut body = Vec::new();
+                body.extend_from_slice(&((random % 2) as u32 * urids.int.get()).to_ne_bytes());
+                body.extend_from_slice(&urids.chunk.get().to_ne_bytes());
+                for _ in 0..self.length() {
+                    body.extend_from_slice(&urids.bool.get().to_ne_bytes());
+                    body.extend_from_slice(&0u32.to_ne_bytes());
+                    body.extend(self.atom(depth - 1));
+                }
+                let object_type = if random.is_multiple_of(4) {
+                    urids.blank.into_general()
+                } else {
+                    urids.object.into_general()
+                };
+                Self::frame(object_type.get(), &body)
+            }
+            _ => {
+                let beats = random.is_multiple_of(2);
+                let mut body = Vec::new();
+                let unit = if beats {
+                    self.beat.get()
+                } else {
+                    self.frame.get()
+                };
+                body.extend_from_slice(&unit.to_ne_bytes());
+                body.extend_from_slice(&0u32.to_ne_bytes());
+                let mut time = 0;
+                for _ in 0..self.length() {
+                    time += self.below(64);
+                    if beats {
+                        body.extend_from_slice(&(time as f64 / 4.0).to_ne_bytes());
+                    } else {
+                        body.extend_from_slice(&(time as i64).to_ne_bytes());
+                    }
+                    body.extend(self.atom(depth - 1));
+                }
+                Self::frame(urids.sequence.get(), &body)
+            }
+        }
+    }
+}
+
+/// Checks that the atom readers of the atom crate handle arbitrary data.
+///
+/// The readers are fed with an atom, and every atom nested in it, and the checks panic if one of the readers panics, returns data outside of the buffer or accepts an atom of another type. Buffers can come from an [`AtomGenerator`](struct.AtomGenerator.html), from a fuzzer like `cargo-fuzz`, or from a regression test.
+pub struct AtomFuzzer {
+    mapper: HashURIDMapper,
+    urids: AtomURIDCollection,
+    units: UnitURIDCollection,
+}
+
+impl Default for AtomFuzzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AtomFuzzer {
+    /// Create a fuzzer with its own URID mapper.
+    pub fn new() -> Self {
+        let mapper = HashURIDMapper::new();
+        let urids = mapper.populate_collection().unwrap();
+        let units = mapper.populate_collection().unwrap();
+        Self {
+            mapper,
+            urids,
+            units,
+        }
+    }
+
+    /// The URID mapper of the fuzzer, which can map the types of custom atoms.
+    pub fn mapper(&self) -> &HashURIDMapper {
+        &self.mapper
+    }
+
+    /// The URIDs of the atom types.
+    pub fn urids(&self) -> &AtomURIDCollection {
+        &self.urids
+    }
+
+    /// Create a generator with the URIDs of the fuzzer.
+    pub fn generator(&self, seed: u64) -> AtomGenerator {
+        AtomGenerator::new(self.urids.clone(), &self.units, seed)
+    }
+
+    /// Check the readers with arbitrary bytes.
+    ///
+    /// The bytes are checked at a 64-bit-aligned address, as well as at an address that is only 32-bit-aligned, like the buffers of some legacy hosts.
+    pub fn check_atom(&self, bytes: &[u8]) {
+        let buffer = AtomBuffer::new(bytes);
+        self.check(buffer.atom(), buffer.as_bytes(), false);
+
+        let mut misaligned = vec![0; 4];
+        misaligned.extend_from_slice(bytes);
+        let buffer = AtomBuffer::new(&misaligned);
+        let bytes = &buffer.as_bytes()[4..];
+        self.check(
+            UnidentifiedAtom::new(Space::from_slice(bytes)),
+            bytes,
+            false,
+        );
+    }
+
+    /// Check the readers with the body of an atom of the given type.
+    ///
+    /// This makes a fuzzer generate data for a specific reader, instead of having to guess the type URID first.
+    pub fn check_body<T: ?Sized>(&self, urid: URID<T>, body: &[u8]) {
+        let mut bytes = Vec::with_capacity(body.len() + 8);
+        bytes.extend_from_slice(&(body.len() as u32).to_ne_bytes());
+        bytes.extend_from_slice(&urid.get().to_ne_bytes());
+        bytes.extend_from_slice(body);
+        self.check_atom(&bytes);
+    }
+
+    /// Check the readers with a valid atom, like one created by [`AtomGenerator::valid_atom`](struct.AtomGenerator.html#method.valid_atom).
+    ///
+    /// In addition to the checks of [`check_atom`](#method.check_atom), this checks that every nested atom of a known type can be read.
+    pub fn check_valid_atom(&self, buffer: &AtomBuffer) {
+        self.check(buffer.atom(), buffer.as_bytes(), true);
+    }
+
+    fn check(&self, atom: UnidentifiedAtom, buffer: &[u8], valid: bool) {
+        let urids = &self.urids;
+        let within = |data: &[u8]| {
+            let start = buffer.as_ptr() as usize;
+            let end = start + buffer.len();
+            let data_start = data.as_ptr() as usize;
+            assert!(
+                data.is_empty() || (data_start >= start && data_start + data.len() <= end),
+                "a reader returned data outside of the buffer"
+            );
+        };
+        let type_urid = atom.type_urid();
+        if let Some(space) = atom.atom_space() {
+            within(space.data().unwrap_or(&[]));
+        }
+        let mut readable = false;
+
+        macro_rules! check_scalar {
+            ($urid:expr) => {
+                let value = atom.read($urid, ());
+                readable |= value.is_some();
+                if type_urid != Some($urid.into_general()) {
+                    assert!(value.is_none(), "a reader accepted an atom of another type");
+                }
+            };
+        }
+        check_scalar!(urids.int);
+        check_scalar!(urids.long);
+        check_scalar!(urids.float);
+        check_scalar!(urids.double);
+        check_scalar!(urids.bool);
+        check_scalar!(urids.urid);
+
+        macro_rules! check_slice {
+            ($urid:expr, $parameter:expr, |$value:pat| $data:expr) => {
+                let value = atom.read($urid, $parameter);
+                if let Some($value) = value {
+                    readable = true;
+                    within($data);
+                }
+                if type_urid != Some($urid.into_general()) {
+                    assert!(value.is_none(), "a reader accepted an atom of another type");
+                }
+            };
+        }
+        check_slice!(urids.string, (), |text| text.as_bytes());
+        check_slice!(urids.path, (), |text| text.as_bytes());
+        check_slice!(urids.literal, (), |(_, text)| text.as_bytes());
+        check_slice!(urids.chunk, (), |data| data);
+        check_slice!(urids.vector::<Int>(), urids.int, |data| as_bytes(data));
+        check_slice!(urids.vector::<Float>(), urids.float, |data| as_bytes(data));
+        check_slice!(urids.vector::<Long>(), urids.long, |data| as_bytes(data));
+        check_slice!(urids.vector::<Double>(), urids.double, |data| as_bytes(
+            data
+        ));
+        check_slice!(
+            urids.matrix::<Float>(),
+            MatrixURIDs::new(urids, urids.float),
+            |matrix| as_bytes(matrix.elements())
+        );
+        check_slice!(
+            urids.matrix::<Double>(),
+            MatrixURIDs::new(urids, urids.double),
+            |matrix| as_bytes(matrix.elements())
+        );
+
+        if let Some(tuple) = atom.read(urids.tuple, ()) {
+            readable = true;
+            for child in tuple {
+                self.check(child, buffer, valid);
+            }
+        }
+        macro_rules! check_object {
+            ($urid:expr) => {
+                let object = atom.read($urid, ());
+                if type_urid != Some($urid.into_general()) {
+                    assert!(
+                        object.is_none(),
+                        "a reader accepted an atom of another type"
+                    );
+                }
+                if let Some((_, properties)) = object {
+                    readable = true;
+                    for (_, value) in properties {
+                        self.check(value, buffer, valid);
+                    }
+                }
+            };
+        }
+        check_object!(urids.object);
+        check_object!(urids.blank);
+        if let Some(properties) = ObjectReader::from_atom(atom, urids) {
+            for (_, value) in properties {
+                self.check(value, buffer, valid);
+            }
+        }
+        let sequence = atom.read(urids.sequence, self.units.beat);
+        if type_urid != Some(urids.sequence.into_general()) {
+            assert!(
+                sequence.is_none(),
+                "a reader accepted an atom of another type"
+            );
+        }
+        if let Some(events) = sequence {
+            readable = true;
+            for (_, event) in events {
+                self.check(event, buffer, valid);
+            }
+        }
+
+        if valid {
+            assert!(readable, "a valid atom couldn't be read");
+        }
+    }
+}
+
+fn as_bytes<T>(data: &[T]) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data)) }
+}
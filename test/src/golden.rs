@@ -0,0 +1,448 @@
+use crate::{TestError, TestHost};
+use atom::prelude::*;
+use atom::sequence::{TimeStamp, TimeStampURID};
+use atom::space::RootMutSpace;
+use core::prelude::*;
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use midi::raw::MidiEvent;
+use std::ffi::c_void;
+use std::path::Path;
+use units::prelude::*;
+use urid::*;
+
+/// The environment variable that makes [`check_golden`](fn.check_golden.html) update the golden files instead of comparing against them.
+pub const UPDATE_GOLDEN_VAR: &str = "LV2_TEST_UPDATE_GOLDEN";
+
+/// Errors that may occur when rendering audio or comparing it against golden files.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GoldenError {
+    /// Reading or writing a file failed.
+    Io(std::io::ErrorKind),
+    /// A WAV file is malformed or has an unsupported format.
+    Wav(std::string::String),
+    /// A line of a MIDI event file is malformed. The line number starts at 1.
+    BadEvent(usize),
+    /// The MIDI events of a cycle don't fit into the sequence buffer.
+    SequenceOverflow,
+    /// The test host failed to run the plugin.
+    Host(TestError),
+    /// The number of channels differs.
+    ChannelCount { expected: usize, actual: usize },
+    /// The number of frames differs.
+    Length { expected: usize, actual: usize },
+    /// A sample differs by more than the tolerance. This is the first sample that does.
+    Mismatch {
+        channel: usize,
+        frame: usize,
+        expected: f32,
+        actual: f32,
+    },
+}
+
+impl From<std::io::Error> for GoldenError {
+    fn from(error: std::io::Error) -> Self {
+        GoldenError::Io(error.kind())
+    }
+}
+
+impl From<hound::Error> for GoldenError {
+    fn from(error: hound::Error) -> Self {
+        match error {
+            hound::Error::IoError(error) => GoldenError::Io(error.kind()),
+            error => GoldenError::Wav(error.to_string()),
+        }
+    }
+}
+
+impl From<TestError> for GoldenError {
+    fn from(error: TestError) -> Self {
+        GoldenError::Host(error)
+    }
+}
+
+/// The amount two samples may differ by.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tolerance {
+    /// The samples may be this many representable `f32` values apart. `Ulp(0)` requires bit-exact results.
+    Ulp(u32),
+    /// The absolute difference of the samples has to be below this level, in decibels relative to full scale. For example, `Decibels(-90.0)` accepts differences below about 0.00003.
+    Decibels(f32),
+}
+
+impl Tolerance {
+    /// Check whether the actual sample is close enough to the expected one.
+    ///
+    /// `NaN`s are never accepted.
+    pub fn accepts(self, expected: f32, actual: f32) -> bool {
+        if expected.is_nan() || actual.is_nan() {
+            return false;
+        }
+        match self {
+            Tolerance::Ulp(ulps) => ulp_distance(expected, actual) <= ulps,
+            Tolerance::Decibels(level) => (expected - actual).abs() < 10f32.powf(level / 20.0),
+        }
+    }
+}
+
+/// The number of representable `f32` values between two samples.
+fn ulp_distance(a: f32, b: f32) -> u32 {
+    // Map the bit patterns to integers that are ordered like the floats, with both zeros mapped to 0.
+    let ordered = |value: f32| -> i64 {
+        let bits = value.to_bits() as i32;
+        if bits < 0 {
+            -i64::from(bits & i32::MAX)
+        } else {
+            i64::from(bits)
+        }
+    };
+    let distance = (ordered(a) - ordered(b)).unsigned_abs();
+    distance.min(u64::from(u32::MAX)) as u32
+}
+
+/// Audio data with multiple channels.
+///
+/// This is the input and output of a [`Render`](struct.Render.html) and the content of golden files.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioData {
+    /// The sample rate of the audio, in Hz.
+    pub sample_rate: u32,
+    /// The samples of every channel. All channels have the same length.
+    pub channels: Vec<Vec<f32>>,
+}
+
+impl AudioData {
+    /// Create audio data from the samples of every channel.
+    pub fn new(sample_rate: u32, channels: Vec<Vec<f32>>) -> Self {
+        Self {
+            sample_rate,
+            channels,
+        }
+    }
+
+    /// Create silent audio data.
+    pub fn silence(sample_rate: u32, channel_count: usize, frames: usize) -> Self {
+        Self::new(sample_rate, vec![vec![0.0; frames]; channel_count])
+    }
+
+    /// The number of frames, which is the length of the channels.
+    pub fn frames(&self) -> usize {
+        self.channels.first().map(Vec::len).unwrap_or(0)
+    }
+
+    /// Read a WAV file.
+    ///
+    /// Integer samples are scaled to the range of -1.0 to 1.0.
+    pub fn read_wav(path: &Path) -> Result<Self, GoldenError> {
+        let mut reader = WavReader::open(path)?;
+        let spec = reader.spec();
+        let samples: Vec<f32> = match spec.sample_format {
+            SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+            SampleFormat::Int => {
+                let scale = (1u64 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .samples::<i32>()
+                    .map(|sample| sample.map(|sample| sample as f32 / scale))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+
+        let channel_count = spec.channels as usize;
+        let mut channels: Vec<Vec<f32>> =
+            vec![Vec::with_capacity(samples.len() / channel_count.max(1)); channel_count];
+        for frame in samples.chunks(channel_count.max(1)) {
+            for (channel, sample) in channels.iter_mut().zip(frame.iter()) {
+                channel.push(*sample);
+            }
+        }
+        Ok(Self::new(spec.sample_rate, channels))
+    }
+
+    /// Write the audio into a WAV file with 32-bit float samples, which preserves the samples exactly.
+    ///
+    /// Missing parent directories are created.
+    pub fn write_wav(&self, path: &Path) -> Result<(), GoldenError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let spec = WavSpec {
+            channels: self.channels.len() as u16,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        };
+        let mut writer = WavWriter::create(path, spec)?;
+        for frame in 0..self.frames() {
+            for channel in self.channels.iter() {
+                writer.write_sample(channel.get(frame).copied().unwrap_or(0.0))?;
+            }
+        }
+        writer.finalize()?;
+        Ok(())
+    }
+
+    /// Compare the audio with the expected audio.
+    ///
+    /// Both have to have the same number of channels and frames, and every sample has to be accepted by the tolerance. Otherwise, the first difference is returned as an error. The sample rates aren't compared.
+    pub fn compare(&self, expected: &AudioData, tolerance: Tolerance) -> Result<(), GoldenError> {
+        if self.channels.len() != expected.channels.len() {
+            return Err(GoldenError::ChannelCount {
+                expected: expected.channels.len(),
+                actual: self.channels.len(),
+            });
+        }
+        if self.frames() != expected.frames() {
+            return Err(GoldenError::Length {
+                expected: expected.frames(),
+                actual: self.frames(),
+            });
+        }
+        for (channel, (actual, expected)) in self
+            .channels
+            .iter()
+            .zip(expected.channels.iter())
+            .enumerate()
+        {
+            for (frame, (actual, expected)) in actual.iter().zip(expected.iter()).enumerate() {
+                if !tolerance.accepts(*expected, *actual) {
+                    return Err(GoldenError::Mismatch {
+                        channel,
+                        frame,
+                        expected: *expected,
+                        actual: *actual,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare audio against a golden file.
+///
+/// If the environment variable `LV2_TEST_UPDATE_GOLDEN` is set, the golden file is written instead, which is how golden files are created and updated after intended changes.
+pub fn check_golden(
+    actual: &AudioData,
+    path: &Path,
+    tolerance: Tolerance,
+) -> Result<(), GoldenError> {
+    if std::env::var_os(UPDATE_GOLDEN_VAR).is_some() {
+        actual.write_wav(path)
+    } else {
+        actual.compare(&AudioData::read_wav(path)?, tolerance)
+    }
+}
+
+/// A MIDI message at a certain frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimedEvent {
+    /// The frame of the event, counted from the beginning of the rendering.
+    pub frame: u64,
+    /// The raw bytes of the MIDI message.
+    pub message: Vec<u8>,
+}
+
+/// Parse MIDI events from text.
+///
+/// Every line contains an event: The frame as a decimal number, followed by the bytes of the message as hexadecimal numbers, all separated by whitespace. Empty lines and everything after a `#` are ignored. For example, this plays a middle C for one second at 48 kHz:
+///
+/// ```text
+/// # frame  status  data
+/// 0        90      3C 7F
+/// 48000    80      3C 00
+/// ```
+///
+/// The events are ordered by their frames.
+pub fn parse_midi_events(text: &str) -> Result<Vec<TimedEvent>, GoldenError> {
+    let mut events: Vec<TimedEvent> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("");
+        let mut fields = line.split_whitespace();
+        let frame = match fields.next() {
+            Some(frame) => frame
+                .parse()
+                .map_err(|_| GoldenError::BadEvent(index + 1))?,
+            None => continue,
+        };
+        let message = fields
+            .map(|byte| u8::from_str_radix(byte, 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| GoldenError::BadEvent(index + 1))?;
+        if message.is_empty() {
+            return Err(GoldenError::BadEvent(index + 1));
+        }
+        events.push(TimedEvent { frame, message });
+    }
+    events.sort_by_key(|event| event.frame);
+    Ok(events)
+}
+
+/// Read MIDI events from a text file.
+///
+/// The [format](fn.parse_midi_events.html) is the same as for parsing.
+pub fn read_midi_events(path: &Path) -> Result<Vec<TimedEvent>, GoldenError> {
+    parse_midi_events(&std::fs::read_to_string(path)?)
+}
+
+/// Render audio through a plugin.
+///
+/// A render feeds the channels of the input audio to the audio input ports and collects the audio of the output ports, running the plugin in blocks. Optionally, MIDI events are passed to an atom input port, in a sequence that is written for every block.
+///
+/// All other ports, like control ports, have to be connected before the render is run. The ports of the render are disconnected afterwards.
+pub struct Render<'a> {
+    inputs: Vec<u32>,
+    outputs: Vec<u32>,
+    midi: Option<(u32, &'a [TimedEvent])>,
+    block_length: u32,
+    frames: Option<usize>,
+    sequence_size: usize,
+}
+
+impl<'a> Render<'a> {
+    /// Create a render with the indices of the audio input and output ports, in the order of the channels.
+    ///
+    /// By default, the block length is 64 frames and the sequence buffer has 8192 bytes.
+    pub fn new(inputs: &[u32], outputs: &[u32]) -> Self {
+        Self {
+            inputs: inputs.to_owned(),
+            outputs: outputs.to_owned(),
+            midi: None,
+            block_length: 64,
+            frames: None,
+            sequence_size: 8192,
+        }
+    }
+
+    /// Set the number of frames in a block. The last block may be shorter.
+    pub fn block_length(mut self, block_length: u32) -> Self {
+        self.block_length = block_length.max(1);
+        self
+    }
+
+    /// Set the number of frames to render.
+    ///
+    /// By default, the length of the input is rendered. If the input is shorter, it's padded with silence, which is useful for instruments without audio inputs or for effects with a tail.
+    pub fn frames(mut self, frames: usize) -> Self {
+        self.frames = Some(frames);
+        self
+    }
+
+    /// Pass MIDI events to the atom input port with the given index.
+    pub fn midi(mut self, port: u32, events: &'a [TimedEvent]) -> Self {
+        self.midi = Some((port, events));
+        self
+    }
+
+    /// Set the size of the sequence buffer in bytes.
+    pub fn sequence_size(mut self, sequence_size: usize) -> Self {
+        self.sequence_size = sequence_size;
+        self
+    }
+
+    /// Run the plugin of the test host and return the output audio.
+    ///
+    /// The input needs to have a channel for every input port. The output has the sample rate of the input.
+    pub fn run<P: Plugin>(
+        &self,
+        host: &mut TestHost<P>,
+        input: &AudioData,
+    ) -> Result<AudioData, GoldenError> {
+        if input.channels.len() != self.inputs.len() {
+            return Err(GoldenError::ChannelCount {
+                expected: self.inputs.len(),
+                actual: input.channels.len(),
+            });
+        }
+        let frames = self.frames.unwrap_or_else(|| input.frames());
+        let block_length = self.block_length as usize;
+
+        let mut input_buffers: Vec<Vec<f32>> = vec![vec![0.0; block_length]; self.inputs.len()];
+        let mut output_buffers: Vec<Vec<f32>> = vec![vec![0.0; block_length]; self.outputs.len()];
+        let mut sequence: Vec<u64> = vec![0; self.sequence_size.div_ceil(8)];
+        let mut output = AudioData::silence(input.sample_rate, self.outputs.len(), 0);
+
+        let result: Result<(), GoldenError> = (|| {
+            unsafe {
+                for (port, buffer) in self.inputs.iter().zip(input_buffers.iter_mut()) {
+                    host.connect_port(*port, buffer.as_mut_ptr());
+                }
+                for (port, buffer) in self.outputs.iter().zip(output_buffers.iter_mut()) {
+                    host.connect_port(*port, buffer.as_mut_ptr());
+                }
+                if let Some((port, _)) = self.midi {
+                    host.connect_port(port, sequence.as_mut_ptr());
+                }
+            }
+
+            let mut start: usize = 0;
+            while start < frames {
+                let length = block_length.min(frames - start);
+                for (buffer, channel) in input_buffers.iter_mut().zip(input.channels.iter()) {
+                    for (index, sample) in buffer.iter_mut().enumerate() {
+                        *sample = channel.get(start + index).copied().unwrap_or(0.0);
+                    }
+                }
+                if let Some((_, events)) = self.midi {
+                    let end = (start + length) as u64;
+                    let block_events = events
+                        .iter()
+                        .filter(|event| event.frame >= start as u64 && event.frame < end);
+                    write_sequence(host, &mut sequence, start as u64, block_events)?;
+                }
+
+                host.run_cycle(length as u32)?;
+                for (channel, buffer) in output.channels.iter_mut().zip(output_buffers.iter()) {
+                    channel.extend_from_slice(&buffer[..length]);
+                }
+                start += length;
+            }
+            Ok(())
+        })();
+
+        // The buffers are dropped after the render, so the plugin must not access them anymore.
+        let ports = self
+            .inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .chain(self.midi.iter().map(|(port, _)| port));
+        for port in ports {
+            unsafe { host.connect_port(*port, std::ptr::null_mut::<c_void>()) };
+        }
+
+        result.map(|_| output)
+    }
+}
+
+/// Write the events of a block into the sequence buffer, with time stamps relative to the start of the block.
+fn write_sequence<'a, P: Plugin>(
+    host: &TestHost<P>,
+    sequence: &mut [u64],
+    start: u64,
+    events: impl Iterator<Item = &'a TimedEvent>,
+) -> Result<(), GoldenError> {
+    // The mapper of the test host never fails.
+    let mapper = host.mapper();
+    let sequence_urid = mapper.map_type::<Sequence>().unwrap();
+    let frame_urid = mapper.map_type::<Frame>().unwrap();
+    let midi_urid = mapper.map_type::<MidiEvent>().unwrap();
+
+    let bytes = unsafe {
+        std::slice::from_raw_parts_mut(sequence.as_mut_ptr() as *mut u8, sequence.len() * 8)
+    };
+    let mut space = RootMutSpace::new(bytes);
+    let mut writer = (&mut space as &mut dyn MutSpace)
+        .init(sequence_urid, TimeStampURID::Frames(frame_urid))
+        .ok_or(GoldenError::SequenceOverflow)?;
+    for event in events {
+        let mut frame = writer
+            .init(
+                TimeStamp::Frames((event.frame - start) as i64),
+                midi_urid,
+                (),
+            )
+            .ok_or(GoldenError::SequenceOverflow)?;
+        (&mut frame as &mut dyn MutSpace)
+            .write_raw(&event.message, false)
+            .ok_or(GoldenError::SequenceOverflow)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,540 @@
+use crate::log::{log_feature, LogMessages};
+use crate::realtime;
+use crate::schedule::{WorkerMode, WorkerQueue};
+use crate::TestError;
+use atom::scalar::ScalarAtom;
+use core::extension::ExtensionInterface;
+use core::plugin::PluginInstance;
+use core::prelude::*;
+use host::HostFeatures;
+use options::{OptionData, Subject};
+use state::{StateErr, Storage};
+use std::ffi::{c_void, CString};
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::path::PathBuf;
+use std::ptr::null;
+use urid::*;
+use worker::WorkerError;
+
+/// An option that is passed to the plugin.
+///
+/// The value is stored in 64-bit words since atom bodies have to be 64-bit-aligned.
+struct TestOption {
+    subject: Subject,
+    key: URID,
+    type_: URID,
+    size: usize,
+    value: Vec<u64>,
+}
+
+/// Builder for a [`TestHost`](struct.TestHost.html).
+///
+/// The builder already contains the URID map of the host, which can be used to create the keys and types of the options passed to the plugin.
+pub struct TestHostBuilder {
+    sample_rate: f64,
+    bundle_path: PathBuf,
+    features: HostFeatures,
+    options: Vec<TestOption>,
+    worker_mode: WorkerMode,
+}
+
+impl Default for TestHostBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestHostBuilder {
+    /// Create a builder with a sample rate of 44100 Hz and the temporary directory as the bundle path.
+    pub fn new() -> Self {
+        Self {
+            sample_rate: 44100.0,
+            bundle_path: std::env::temp_dir(),
+            features: HostFeatures::new().with_urid_map(),
+            options: Vec::new(),
+            worker_mode: WorkerMode::default(),
+        }
+    }
+
+    /// Set the sample rate the plugin is instantiated with.
+    pub fn sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Set the bundle path the plugin is instantiated with.
+    pub fn bundle_path<T: Into<PathBuf>>(mut self, bundle_path: T) -> Self {
+        self.bundle_path = bundle_path.into();
+        self
+    }
+
+    /// Set how work that is scheduled by the plugin is handled.
+    pub fn worker_mode(mut self, mode: WorkerMode) -> Self {
+        self.worker_mode = mode;
+        self
+    }
+
+    /// The URID mapper of the host.
+    pub fn mapper(&self) -> &HashURIDMapper {
+        self.features.mapper().unwrap()
+    }
+
+    /// Add an option with the given key, type and raw value.
+    pub fn option<K: ?Sized, T: ?Sized>(
+        mut self,
+        subject: Subject,
+        key: URID<K>,
+        type_: URID<T>,
+        value: &[u8],
+    ) -> Self {
+        let mut words: Vec<u64> = vec![0; value.len().div_ceil(8)];
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                value.as_ptr(),
+                words.as_mut_ptr() as *mut u8,
+                value.len(),
+            )
+        };
+        self.options.push(TestOption {
+            subject,
+            key: key.into_general(),
+            type_: type_.into_general(),
+            size: value.len(),
+            value: words,
+        });
+        self
+    }
+
+    /// Add an option for the plugin instance with the value of a scalar atom.
+    pub fn scalar_option<K: ?Sized, A: ScalarAtom>(
+        self,
+        key: URID<K>,
+        urid: URID<A>,
+        value: A::InternalType,
+    ) -> Self {
+        let data = OptionData::scalar(urid, &value);
+        self.option(Subject::Instance, key, data.type_(), data.data())
+    }
+
+    /// Instantiate the plugin.
+    ///
+    /// The plugin is created with the URID map, worker schedule, log and options features of the host. If the plugin can't be instantiated, for example because it requires other features, an error is returned.
+    pub fn build<P: Plugin>(self) -> Result<TestHost<P>, TestError> {
+        // Bundle paths always end with a directory separator.
+        let mut bundle_path = self
+            .bundle_path
+            .to_str()
+            .ok_or(TestError::BadPath)?
+            .to_owned();
+        if !bundle_path.ends_with(std::path::MAIN_SEPARATOR) {
+            bundle_path.push(std::path::MAIN_SEPARATOR);
+        }
+        let bundle_path = CString::new(bundle_path).map_err(|_| TestError::BadPath)?;
+
+        let mut worker = Box::new(WorkerQueue::default());
+        worker.mode = self.worker_mode;
+        let mut schedule = Box::new(sys::LV2_Worker_Schedule {
+            handle: worker.as_mut() as *mut WorkerQueue as *mut c_void,
+            schedule_work: Some(WorkerQueue::extern_schedule),
+        });
+        let mut log = Box::new(LogMessages::new());
+        let mut log_interface = Box::new(log_feature(log.as_mut()));
+
+        let mut raw_options: Vec<sys::LV2_Options_Option> = self
+            .options
+            .iter()
+            .map(|option| {
+                let (context, subject) = option.subject.into_raw();
+                sys::LV2_Options_Option {
+                    context,
+                    subject,
+                    key: option.key.get(),
+                    size: option.size as u32,
+                    type_: option.type_.get(),
+                    value: option.value.as_ptr() as *const c_void,
+                }
+            })
+            .collect();
+        raw_options.push(sys::LV2_Options_Option {
+            context: sys::LV2_Options_Context_LV2_OPTIONS_INSTANCE,
+            subject: 0,
+            key: 0,
+            size: 0,
+            type_: 0,
+            value: null(),
+        });
+
+        let features = unsafe {
+            self.features
+                .with_raw(
+                    uri(sys::LV2_WORKER__schedule),
+                    schedule.as_mut() as *mut sys::LV2_Worker_Schedule as *mut c_void,
+                )
+                .with_raw(
+                    uri(sys::LV2_LOG__log),
+                    log_interface.as_mut() as *mut sys::LV2_Log_Log as *mut c_void,
+                )
+                .with_raw(
+                    uri(sys::LV2_OPTIONS__options),
+                    raw_options.as_mut_ptr() as *mut c_void,
+                )
+        };
+
+        let descriptor = Box::new(sys::LV2_Descriptor {
+            URI: P::URI.as_ptr() as *const c_char,
+            instantiate: Some(PluginInstance::<P>::instantiate),
+            connect_port: Some(PluginInstance::<P>::connect_port),
+            activate: Some(PluginInstance::<P>::activate),
+            run: Some(PluginInstance::<P>::run),
+            deactivate: Some(PluginInstance::<P>::deactivate),
+            cleanup: Some(PluginInstance::<P>::cleanup),
+            extension_data: Some(PluginInstance::<P>::extension_data),
+        });
+
+        let raw_features = features.raw_features();
+        let handle = unsafe {
+            PluginInstance::<P>::instantiate(
+                descriptor.as_ref(),
+                self.sample_rate,
+                bundle_path.as_ptr(),
+                feature_pointers(&raw_features).as_ptr(),
+            )
+        };
+        if handle.is_null() {
+            return Err(TestError::InstantiationFailed);
+        }
+
+        let mut host = TestHost {
+            handle: handle as *mut PluginInstance<P>,
+            active: false,
+            sample_rate: self.sample_rate,
+            worker,
+            log,
+            features,
+            descriptor,
+            bundle_path,
+            _schedule: schedule,
+            _log_interface: log_interface,
+            _options: (self.options, raw_options),
+            plugin: PhantomData,
+        };
+        // The instance is needed to do work immediately.
+        host.worker.instance = handle;
+        host.worker.work = host.worker_interface().and_then(|interface| interface.work);
+        Ok(host)
+    }
+}
+
+/// Convert a static URI into a URI reference.
+fn uri(uri: &'static [u8]) -> &'static Uri {
+    Uri::from_bytes_with_nul(uri).unwrap()
+}
+
+/// Create the null-terminated list of feature pointers that is passed to the plugin.
+fn feature_pointers(features: &[sys::LV2_Feature]) -> Vec<*const sys::LV2_Feature> {
+    features
+        .iter()
+        .map(|feature| feature as *const sys::LV2_Feature)
+        .chain(std::iter::once(null()))
+        .collect()
+}
+
+/// An in-process host that drives a plugin through its full lifecycle.
+///
+/// The test host instantiates the plugin directly, without a bundle or a library, and provides mocks of the common host features to it:
+///
+/// * The URID map and unmap features, which are backed by the [`mapper`](#method.mapper) of the host.
+/// * The worker schedule feature. By default, scheduled work is queued until [`drain_worker`](#method.drain_worker) is called, but it can also be done immediately or be rejected, depending on the [`WorkerMode`](enum.WorkerMode.html).
+/// * The log feature. Logged messages can be inspected with [`log_messages`](#method.log_messages).
+/// * The options feature, with the options that were added to the [builder](struct.TestHostBuilder.html).
+///
+/// Ports are connected to buffers owned by the caller, which are processed by [`run_cycle`](#method.run_cycle). The state of the plugin can be saved and restored with [`save_state`](#method.save_state) and [`restore_state`](#method.restore_state), and the sample rate can be changed with [`change_sample_rate`](#method.change_sample_rate). When the host is dropped, the plugin is deactivated if necessary and cleaned up.
+pub struct TestHost<P: Plugin> {
+    handle: *mut PluginInstance<P>,
+    active: bool,
+    sample_rate: f64,
+    worker: Box<WorkerQueue>,
+    log: Box<LogMessages>,
+    features: HostFeatures,
+    descriptor: Box<sys::LV2_Descriptor>,
+    bundle_path: CString,
+    // The following fields are only accessed by the plugin, via the features and the instance.
+    _schedule: Box<sys::LV2_Worker_Schedule>,
+    _log_interface: Box<sys::LV2_Log_Log>,
+    _options: (Vec<TestOption>, Vec<sys::LV2_Options_Option>),
+    plugin: PhantomData<P>,
+}
+
+impl<P: Plugin> TestHost<P> {
+    /// Instantiate the plugin with the default settings of the [builder](struct.TestHostBuilder.html).
+    pub fn new() -> Result<Self, TestError> {
+        TestHostBuilder::new().build()
+    }
+
+    /// The URID mapper of the host.
+    pub fn mapper(&self) -> &HashURIDMapper {
+        self.features.mapper().unwrap()
+    }
+
+    /// The sample rate the plugin was instantiated with.
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Change the sample rate of the plugin.
+    ///
+    /// Just like a real host, the test host deactivates the plugin and creates a new instance with the new sample rate. However, the new instance is created with [`Plugin::migrate`](../lv2_core/plugin/trait.Plugin.html#method.migrate), which lets it take over the state of the previous instance, and the ports stay connected. Pending work is handled before the plugin is deactivated. The options of the host aren't changed.
+    ///
+    /// The host is consumed, since the previous instance is gone even if the new instance can't be created.
+    pub fn change_sample_rate(mut self, sample_rate: f64) -> Result<Self, TestError> {
+        if self.pending_work() > 0 {
+            self.drain_worker()?;
+        }
+        self.deactivate();
+
+        let raw_features = self.features.raw_features();
+        let handle = unsafe {
+            PluginInstance::<P>::migrate(
+                self.handle as sys::LV2_Handle,
+                self.descriptor.as_ref(),
+                sample_rate,
+                self.bundle_path.as_ptr(),
+                feature_pointers(&raw_features).as_ptr(),
+            )
+        };
+        self.handle = handle as *mut PluginInstance<P>;
+        if handle.is_null() {
+            return Err(TestError::InstantiationFailed);
+        }
+        self.sample_rate = sample_rate;
+        self.worker.instance = handle;
+        Ok(self)
+    }
+
+    /// The plugin instance.
+    pub fn plugin(&self) -> &P {
+        // The plugin is the first field of the `repr(C)` plugin instance.
+        unsafe { &*(self.handle as *const P) }
+    }
+
+    /// The mutable plugin instance.
+    pub fn plugin_mut(&mut self) -> &mut P {
+        unsafe { &mut *(self.handle as *mut P) }
+    }
+
+    /// Connect a port to a buffer.
+    ///
+    /// # Safety
+    ///
+    /// The buffer has to be valid data of the port's type for as long as it's connected. Audio and CV buffers need to hold at least as many samples as are passed to [`run_cycle`](#method.run_cycle).
+    pub unsafe fn connect_port<T>(&mut self, index: u32, data: *mut T) {
+        PluginInstance::<P>::connect_port(self.handle as *mut c_void, index, data as *mut c_void);
+    }
+
+    /// Activate the plugin, which resets its internal state.
+    ///
+    /// Activating an active plugin has no effect.
+    pub fn activate(&mut self) {
+        if !self.active {
+            unsafe { PluginInstance::<P>::activate(self.handle as *mut c_void) };
+            self.active = true;
+        }
+    }
+
+    /// Deactivate the plugin.
+    ///
+    /// Deactivating an inactive plugin has no effect.
+    pub fn deactivate(&mut self) {
+        if self.active {
+            unsafe { PluginInstance::<P>::deactivate(self.handle as *mut c_void) };
+            self.active = false;
+        }
+    }
+
+    /// Check whether the plugin is active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Run the plugin for the given number of frames.
+    ///
+    /// The plugin is activated first if it isn't active yet. If not all ports are connected, the plugin isn't run.
+    ///
+    /// In the [immediate worker mode](enum.WorkerMode.html#variant.Immediate), the responses of the work that was done during the cycle are delivered afterwards and `end_run` is called, just like a host would do it. If one of these calls fails, the error is returned.
+    pub fn run_cycle(&mut self, sample_count: u32) -> Result<(), TestError> {
+        self.activate();
+        let handle = self.handle as *mut c_void;
+        realtime::in_run(|| unsafe { PluginInstance::<P>::run(handle, sample_count) });
+        if self.worker.mode == WorkerMode::Immediate {
+            if let Some(interface) = self.worker_interface() {
+                self.handle_work(interface)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The current worker mode.
+    pub fn worker_mode(&self) -> WorkerMode {
+        self.worker.mode
+    }
+
+    /// Change how scheduled work is handled.
+    ///
+    /// Work that is already queued stays in the queue until it is drained or, in the immediate mode, until the end of the next cycle.
+    pub fn set_worker_mode(&mut self, mode: WorkerMode) {
+        self.worker.mode = mode;
+    }
+
+    /// The number of work requests that haven't been handled yet.
+    pub fn pending_work(&self) -> usize {
+        self.worker.requests.len()
+    }
+
+    /// Handle all scheduled work.
+    ///
+    /// This calls the plugin's `work` method for every scheduled request, delivers the responses with `work_response`, and finally calls `end_run`. Work that is scheduled while the responses are delivered is handled too. The number of handled requests is returned.
+    ///
+    /// Requests with a higher priority, as set by `Schedule::schedule_work_with_priority`, are handled first, and requests with the same priority in the order they were scheduled.
+    pub fn drain_worker(&mut self) -> Result<usize, TestError> {
+        let interface = self.worker_interface().ok_or(TestError::MissingExtension)?;
+        self.handle_work(interface)
+    }
+
+    /// The worker interface of the plugin, if it has one.
+    fn worker_interface(&self) -> Option<&'static sys::LV2_Worker_Interface> {
+        self.extension::<sys::LV2_Worker_Interface>()
+    }
+
+    /// Handle all queued requests and responses and call `end_run`.
+    fn handle_work(
+        &mut self,
+        interface: &'static sys::LV2_Worker_Interface,
+    ) -> Result<usize, TestError> {
+        let handle = self.handle as sys::LV2_Handle;
+        let worker = self.worker.as_mut() as *mut WorkerQueue;
+
+        let mut count: usize = 0;
+        loop {
+            // The plugin accesses the queue via its raw pointer, so it must not be borrowed during the calls.
+            if let Some(request) = unsafe { (*worker).pop_request() } {
+                count += 1;
+                let work = interface.work.ok_or(TestError::MissingExtension)?;
+                let status = unsafe {
+                    work(
+                        handle,
+                        Some(WorkerQueue::extern_respond),
+                        worker as *mut c_void,
+                        request.len() as u32,
+                        request.as_ptr() as *const c_void,
+                    )
+                };
+                worker_status(status)?;
+            } else if let Some(response) = unsafe { (*worker).responses.pop_front() } {
+                let work_response = interface.work_response.ok_or(TestError::MissingExtension)?;
+                let status = unsafe {
+                    work_response(
+                        handle,
+                        response.len() as u32,
+                        response.as_ptr() as *const c_void,
+                    )
+                };
+                worker_status(status)?;
+            } else {
+                break;
+            }
+        }
+
+        if let Some(end_run) = interface.end_run {
+            worker_status(unsafe { end_run(handle) })?;
+        }
+        Ok(count)
+    }
+
+    /// All messages the plugin has logged, with the URIDs of their types.
+    ///
+    /// Format arguments aren't expanded; Only the format string of a message is recorded.
+    pub fn log_messages(&self) -> &[(URID, String)] {
+        self.log.as_ref()
+    }
+
+    /// Remove all logged messages.
+    pub fn clear_log(&mut self) {
+        self.log.clear();
+    }
+
+    /// Save the state of the plugin into a new storage.
+    pub fn save_state(&mut self) -> Result<Storage, TestError> {
+        let interface = self
+            .extension::<sys::LV2_State_Interface>()
+            .ok_or(TestError::MissingExtension)?;
+        let save = interface.save.ok_or(TestError::MissingExtension)?;
+        let mut storage = Storage::default();
+        let raw_features = self.features.raw_features();
+        let status = unsafe {
+            save(
+                self.handle as sys::LV2_Handle,
+                Some(Storage::extern_store),
+                &mut storage as *mut Storage as *mut c_void,
+                state_flags(),
+                feature_pointers(&raw_features).as_ptr(),
+            )
+        };
+        StateErr::from(status).map_err(TestError::State)?;
+        Ok(storage)
+    }
+
+    /// Restore the state of the plugin from a storage.
+    pub fn restore_state(&mut self, storage: &mut Storage) -> Result<(), TestError> {
+        let interface = self
+            .extension::<sys::LV2_State_Interface>()
+            .ok_or(TestError::MissingExtension)?;
+        let restore = interface.restore.ok_or(TestError::MissingExtension)?;
+        let raw_features = self.features.raw_features();
+        let status = unsafe {
+            restore(
+                self.handle as sys::LV2_Handle,
+                Some(Storage::extern_retrieve),
+                storage as *mut Storage as *mut c_void,
+                state_flags(),
+                feature_pointers(&raw_features).as_ptr(),
+            )
+        };
+        StateErr::from(status).map_err(TestError::State)
+    }
+
+    /// Retrieve the interface of an extension.
+    fn extension<I: ExtensionInterface>(&self) -> Option<&'static I> {
+        unsafe {
+            (PluginInstance::<P>::extension_data(I::URI.as_ptr() as *const c_char) as *const I)
+                .as_ref()
+        }
+    }
+}
+
+impl<P: Plugin> Drop for TestHost<P> {
+    fn drop(&mut self) {
+        // The instance is gone if changing the sample rate failed.
+        if self.handle.is_null() {
+            return;
+        }
+        self.deactivate();
+        unsafe { PluginInstance::<P>::cleanup(self.handle as *mut c_void) };
+    }
+}
+
+/// The flags the state is saved and restored with.
+fn state_flags() -> u32 {
+    (sys::LV2_State_Flags::LV2_STATE_IS_POD | sys::LV2_State_Flags::LV2_STATE_IS_PORTABLE).into()
+}
+
+/// Convert the status of a worker function to a result.
+fn worker_status(status: sys::LV2_Worker_Status) -> Result<(), TestError> {
+    match status {
+        sys::LV2_Worker_Status_LV2_WORKER_SUCCESS => Ok(()),
+        sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE => {
+            Err(TestError::Worker(WorkerError::NoSpace))
+        }
+        _ => Err(TestError::Worker(WorkerError::Unknown)),
+    }
+}
@@ -0,0 +1,118 @@
+//! An in-process host to test plugins.
+//!
+//! Plugins are usually tested by loading them into a real host, which is slow, hard to automate and doesn't give access to the internals of the plugin. This crate provides a [`TestHost`](struct.TestHost.html) instead, which instantiates a plugin type directly and drives it through its full lifecycle: It provides mocks of the URID map, worker schedule, log and options features, connects ports to buffers owned by the test, runs the plugin, handles scheduled work and saves and restores its state. This way, plugins can be unit-tested entirely in Rust.
+//!
+//! For DSP regression tests, [`Render`](struct.Render.html) feeds audio and MIDI events through a test host block by block and [`check_golden`](fn.check_golden.html) compares the result with a golden WAV file, within a given [`Tolerance`](enum.Tolerance.html). Setting the `LV2_TEST_UPDATE_GOLDEN` environment variable rewrites the golden files instead.
+//!
+//! Atoms written by a plugin, like the events of its output sequence, are checked with [`diff_atoms`](fn.diff_atoms.html) or [`assert_atoms_eq`](fn.assert_atoms_eq.html). They compare the written atoms with the expected ones structurally and report the path to the first mismatch, with URIDs written as URIs, which makes failures of event protocol tests readable.
+//!
+//! The atom readers are checked against malformed data with an [`AtomFuzzer`](struct.AtomFuzzer.html), which is fed with random, valid and corrupted atoms from an [`AtomGenerator`](struct.AtomGenerator.html) or with the input of a fuzzer. Crates with custom atom types can use the generator to test their own readers.
+//!
+//! Performance is measured with a [`Benchmark`](struct.Benchmark.html), which runs a plugin with random block lengths, reports percentiles of the cycle times and detects allocations and page faults in `run`.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_test::*;
+//! use urid::*;
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     gain: InputPort<Control>,
+//!     input: InputPort<Audio>,
+//!     output: OutputPort<Audio>,
+//! }
+//!
+//! #[uri("urn:lv2_test:amp")]
+//! struct Amp;
+//!
+//! impl Plugin for Amp {
+//!     type Ports = Ports;
+//!     type InitFeatures = ();
+//!     type AudioFeatures = ();
+//!
+//!     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+//!         Some(Self)
+//!     }
+//!
+//!     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+//!         for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+//!             *output = input * *ports.gain;
+//!         }
+//!     }
+//! }
+//!
+//! let mut host = TestHost::<Amp>::new().unwrap();
+//!
+//! let mut gain: f32 = 0.5;
+//! let mut input: Vec<f32> = vec![1.0; 64];
+//! let mut output: Vec<f32> = vec![0.0; 64];
+//! unsafe {
+//!     host.connect_port(0, &mut gain);
+//!     host.connect_port(1, input.as_mut_ptr());
+//!     host.connect_port(2, output.as_mut_ptr());
+//! }
+//!
+//! host.run_cycle(64).unwrap();
+//! assert_eq!(output, vec![0.5; 64]);
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_host as host;
+extern crate lv2_midi as midi;
+extern crate lv2_options as options;
+extern crate lv2_state as state;
+extern crate lv2_sys as sys;
+extern crate lv2_units as units;
+extern crate lv2_worker as worker;
+
+mod benchmark;
+pub use benchmark::*;
+
+mod diff;
+pub use diff::*;
+
+mod fuzz;
+pub use fuzz::*;
+
+mod golden;
+pub use golden::*;
+
+mod harness;
+pub use harness::*;
+
+mod log;
+
+mod realtime;
+pub use realtime::{RtAllocator, RtCheck};
+
+mod schedule;
+pub use schedule::WorkerMode;
+
+use state::StateErr;
+use worker::WorkerError;
+
+/// Errors that may occur when testing a plugin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestError {
+    /// The bundle path can't be converted to a C string.
+    BadPath,
+    /// The plugin couldn't be instantiated, for example because a required feature is missing.
+    InstantiationFailed,
+    /// The plugin doesn't provide the extension that is needed for the operation.
+    MissingExtension,
+    /// The worker of the plugin returned an error.
+    Worker(WorkerError),
+    /// Saving or restoring the state of the plugin failed.
+    State(StateErr),
+}
+
+/// Prelude of `lv2_test` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        assert_atoms_eq, diff_atoms, AtomBuffer, AtomDiff, AtomFuzzer, AtomGenerator, AudioData,
+        Benchmark, BenchmarkReport, CycleTime, GoldenError, Render, RtAllocator, RtCheck,
+        TestError, TestHost, TestHostBuilder, TimedEvent, Tolerance, WorkerMode,
+    };
+}
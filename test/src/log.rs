@@ -0,0 +1,54 @@
+use crate::realtime;
+use std::ffi::{c_void, CStr};
+use std::os::raw::{c_char, c_int};
+use urid::*;
+
+/// The messages a plugin has logged, with the URID of their type, like `log:Error`.
+pub(crate) type LogMessages = Vec<(URID, String)>;
+
+/// The non-variadic signature of the logging functions.
+type LogFunction = unsafe extern "C" fn(sys::LV2_Log_Handle, u32, *const c_char) -> c_int;
+
+/// The signature of the `printf` function of the log feature.
+type PrintfFunction = unsafe extern "C" fn(sys::LV2_Log_Handle, u32, *const c_char, ...) -> c_int;
+
+/// Record the format string of a message.
+///
+/// The handle has to be a pointer to `LogMessages`. Messages without a valid type are ignored.
+unsafe extern "C" fn extern_log(
+    handle: sys::LV2_Log_Handle,
+    type_: u32,
+    fmt: *const c_char,
+) -> c_int {
+    let messages = match (handle as *mut LogMessages).as_mut() {
+        Some(messages) => messages,
+        None => return -1,
+    };
+    if fmt.is_null() {
+        return -1;
+    }
+    realtime::outside_run(|| {
+        let message = CStr::from_ptr(fmt).to_string_lossy().into_owned();
+        let length = message.len() as c_int;
+        if let Some(type_) = URID::new(type_) {
+            messages.push((type_, message));
+        }
+        length
+    })
+}
+
+/// Create the log feature that records its messages in the given list.
+///
+/// Stable Rust can't define variadic functions or read `va_list`s, which is why both logging functions are implemented by a function that only takes the fixed arguments: It ignores all format arguments and records the format string as it is. Since the caller cleans up the arguments in the C calling conventions, calling it with additional arguments works on all supported targets.
+// The type of the `va_list` argument of `vprintf` depends on the target.
+#[allow(clippy::missing_transmute_annotations)]
+pub(crate) fn log_feature(messages: &mut LogMessages) -> sys::LV2_Log_Log {
+    let function = extern_log as LogFunction;
+    unsafe {
+        sys::LV2_Log_Log {
+            handle: messages as *mut LogMessages as *mut c_void,
+            printf: Some(std::mem::transmute::<LogFunction, PrintfFunction>(function)),
+            vprintf: Some(std::mem::transmute::<LogFunction, _>(function)),
+        }
+    }
+}
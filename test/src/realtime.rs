@@ -0,0 +1,189 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// How violations of real-time constraints are treated while the plugin runs.
+///
+/// The plugin's `run` method must not allocate or free memory and should not touch memory that isn't mapped yet, since both may block the audio thread for an unbounded time. Allocations can only be detected if the [`RtAllocator`](struct.RtAllocator.html) is the global allocator, and page faults can only be detected on Linux.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RtCheck {
+    /// Violations aren't detected.
+    #[default]
+    Off,
+    /// Violations are counted and reported.
+    Count,
+    /// The process is aborted when a violation is detected. Allocations abort right away, which lets a debugger show where they happened, while page faults are detected when `run` returns.
+    Abort,
+}
+
+thread_local! {
+    /// The check that is applied to the current thread.
+    static CHECK: Cell<RtCheck> = const { Cell::new(RtCheck::Off) };
+    /// Whether the current thread is in the plugin's `run` method.
+    static IN_RUN: Cell<bool> = const { Cell::new(false) };
+    /// The number of allocations, reallocations and deallocations in the `run` method.
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    /// The number of page faults in the `run` method.
+    static PAGE_FAULTS: Cell<u64> = const { Cell::new(0) };
+    /// The page faults of the thread when the plugin's code was last entered.
+    static FAULT_MARK: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Whether the `RtAllocator` is the global allocator.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// A global allocator that detects allocations in the plugin's `run` method.
+///
+/// It forwards all requests to the system allocator. Benchmarks that check for allocations have to install it as the global allocator:
+///
+/// ```
+/// use lv2_test::RtAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: RtAllocator = RtAllocator::new();
+/// ```
+///
+/// Memory that is managed by the test host, like work requests and log messages, is excluded from the check.
+pub struct RtAllocator {
+    system: System,
+}
+
+impl RtAllocator {
+    /// Create a new allocator.
+    pub const fn new() -> Self {
+        Self { system: System }
+    }
+
+    fn record(&self) {
+        INSTALLED.store(true, Ordering::Relaxed);
+        // The thread-local state may already be destroyed if the thread exits.
+        let check = CHECK.try_with(Cell::get).unwrap_or(RtCheck::Off);
+        if check == RtCheck::Off || !IN_RUN.try_with(Cell::get).unwrap_or(false) {
+            return;
+        }
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        if check == RtCheck::Abort {
+            IN_RUN.with(|in_run| in_run.set(false));
+            eprintln!("lv2-test: the plugin allocated or freed memory in `run`");
+            std::process::abort();
+        }
+    }
+}
+
+impl Default for RtAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for RtAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.record();
+        self.system.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.record();
+        self.system.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.record();
+        self.system.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        self.record();
+        self.system.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Apply the check to the current thread and reset the counters.
+pub(crate) fn set_check(check: RtCheck) {
+    CHECK.with(|cell| cell.set(check));
+    ALLOCATIONS.with(|allocations| allocations.set(0));
+    PAGE_FAULTS.with(|page_faults| page_faults.set(0));
+}
+
+/// The number of allocations in the `run` method since the check was set, if they can be detected.
+pub(crate) fn allocations() -> Option<u64> {
+    if INSTALLED.load(Ordering::Relaxed) {
+        Some(ALLOCATIONS.with(Cell::get))
+    } else {
+        None
+    }
+}
+
+/// Run the plugin's `run` method in the given closure, checking the real-time constraints.
+///
+/// Page faults are only counted while the plugin's code runs, not while the closures of [`outside_run`](fn.outside_run.html) run. If the check aborts, the process is aborted after the closure if a page fault occurred.
+pub(crate) fn in_run<R>(f: impl FnOnce() -> R) -> R {
+    let previous = IN_RUN.with(|in_run| in_run.replace(true));
+    let faults_before = PAGE_FAULTS.with(Cell::get);
+    if !previous {
+        enter_plugin();
+    }
+    let result = f();
+    if !previous {
+        leave_plugin();
+    }
+    IN_RUN.with(|in_run| in_run.set(previous));
+
+    if CHECK.with(Cell::get) == RtCheck::Abort && PAGE_FAULTS.with(Cell::get) > faults_before {
+        eprintln!("lv2-test: the plugin caused a page fault in `run`");
+        std::process::abort();
+    }
+    result
+}
+
+/// Run code of the test host in the given closure, which is excluded from the checks.
+///
+/// A real host uses preallocated memory for the features it provides, which the mocks of the test host don't.
+pub(crate) fn outside_run<R>(f: impl FnOnce() -> R) -> R {
+    let previous = IN_RUN.with(|in_run| in_run.replace(false));
+    if previous {
+        leave_plugin();
+    }
+    let result = f();
+    if previous {
+        enter_plugin();
+    }
+    IN_RUN.with(|in_run| in_run.set(previous));
+    result
+}
+
+/// The number of page faults in the `run` method since the check was set, if they can be detected.
+pub(crate) fn page_faults() -> Option<u64> {
+    thread_page_faults().map(|_| PAGE_FAULTS.with(Cell::get))
+}
+
+/// Remember the page faults of the thread when the plugin's code is entered.
+fn enter_plugin() {
+    if CHECK.with(Cell::get) != RtCheck::Off {
+        FAULT_MARK.with(|mark| mark.set(thread_page_faults()));
+    }
+}
+
+/// Count the page faults since the plugin's code was entered.
+fn leave_plugin() {
+    if let (Some(mark), Some(now)) = (FAULT_MARK.with(Cell::take), thread_page_faults()) {
+        PAGE_FAULTS.with(|page_faults| page_faults.set(page_faults.get() + (now - mark)));
+    }
+}
+
+/// The number of page faults of the current thread, if they can be detected.
+#[cfg(target_os = "linux")]
+fn thread_page_faults() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+    if unsafe { libc::getrusage(libc::RUSAGE_THREAD, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let usage = unsafe { usage.assume_init() };
+    Some((usage.ru_minflt + usage.ru_majflt) as u64)
+}
+
+/// The number of page faults of the current thread, if they can be detected.
+#[cfg(not(target_os = "linux"))]
+fn thread_page_faults() -> Option<u64> {
+    None
+}
@@ -0,0 +1,125 @@
+use crate::realtime;
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::ptr::null_mut;
+use worker::Priority;
+
+/// How the test host handles work that is scheduled by the plugin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum WorkerMode {
+    /// The work is done right away, within the call to `schedule_work`, just like a freewheeling host would do it. The responses are delivered at the end of the cycle.
+    Immediate,
+    /// The work is queued until [`TestHost::drain_worker`](struct.TestHost.html#method.drain_worker) is called, which handles urgent work first. This is the default.
+    #[default]
+    Deferred,
+    /// Scheduling work always fails with `ScheduleError::NoSpace`, as if the host's buffer were full.
+    NoSpace,
+}
+
+/// The work requests and responses of a plugin that haven't been handled yet.
+///
+/// Requests and responses are copied byte by byte, just like a real host would copy them into a ring buffer. The plugin's worker takes the ownership of the data again when it's handled.
+pub(crate) struct WorkerQueue {
+    pub mode: WorkerMode,
+    pub requests: VecDeque<Vec<u8>>,
+    pub responses: VecDeque<Vec<u8>>,
+    /// The handle of the plugin instance and its `work` function, which are needed to do work immediately.
+    pub instance: sys::LV2_Handle,
+    pub work: Option<WorkFunction>,
+}
+
+/// The type of the `work` function of the worker interface.
+pub(crate) type WorkFunction = unsafe extern "C" fn(
+    sys::LV2_Handle,
+    sys::LV2_Worker_Respond_Function,
+    sys::LV2_Worker_Respond_Handle,
+    u32,
+    *const c_void,
+) -> sys::LV2_Worker_Status;
+
+impl Default for WorkerQueue {
+    fn default() -> Self {
+        Self {
+            mode: WorkerMode::default(),
+            requests: VecDeque::new(),
+            responses: VecDeque::new(),
+            instance: null_mut(),
+            work: None,
+        }
+    }
+}
+
+/// Copy `size` bytes at `data` into a vector.
+unsafe fn copy_data(size: u32, data: *const c_void) -> Vec<u8> {
+    if data.is_null() {
+        Vec::new()
+    } else {
+        std::slice::from_raw_parts(data as *const u8, size as usize).to_owned()
+    }
+}
+
+impl WorkerQueue {
+    /// Remove the most urgent request from the queue.
+    ///
+    /// Requests with the same priority are removed in the order they were scheduled, and requests without a priority have the normal one.
+    pub fn pop_request(&mut self) -> Option<Vec<u8>> {
+        let priority = |request: &Vec<u8>| Priority::of_request(request).unwrap_or_default();
+        let mut index = 0;
+        for (i, request) in self.requests.iter().enumerate().skip(1) {
+            if priority(request) > priority(&self.requests[index]) {
+                index = i;
+            }
+        }
+        self.requests.remove(index)
+    }
+
+    /// The `schedule_work` function of the worker schedule feature.
+    ///
+    /// The handle has to be a pointer to a `WorkerQueue`.
+    pub unsafe extern "C" fn extern_schedule(
+        handle: sys::LV2_Worker_Schedule_Handle,
+        size: u32,
+        data: *const c_void,
+    ) -> sys::LV2_Worker_Status {
+        // The queue is only accessed via the pointer, since the work function accesses it too when it responds.
+        let queue = handle as *mut Self;
+        if queue.is_null() {
+            return sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
+        }
+        // Work that is done immediately would be done by the worker thread of a real host.
+        realtime::outside_run(|| match (*queue).mode {
+            WorkerMode::Immediate => match (*queue).work {
+                Some(work) => work(
+                    (*queue).instance,
+                    Some(Self::extern_respond),
+                    handle,
+                    size,
+                    data,
+                ),
+                None => sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+            },
+            WorkerMode::Deferred => {
+                (*queue).requests.push_back(copy_data(size, data));
+                sys::LV2_Worker_Status_LV2_WORKER_SUCCESS
+            }
+            WorkerMode::NoSpace => sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+        })
+    }
+
+    /// The response function that is passed to the `work` function of the plugin.
+    ///
+    /// The handle has to be a pointer to a `WorkerQueue`.
+    pub unsafe extern "C" fn extern_respond(
+        handle: sys::LV2_Worker_Respond_Handle,
+        size: u32,
+        data: *const c_void,
+    ) -> sys::LV2_Worker_Status {
+        match (handle as *mut Self).as_mut() {
+            Some(queue) => {
+                realtime::outside_run(|| queue.responses.push_back(copy_data(size, data)));
+                sys::LV2_Worker_Status_LV2_WORKER_SUCCESS
+            }
+            None => sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use lv2_core::prelude::*;
+use lv2_test::*;
+use lv2_worker::*;
+use std::time::Duration;
+use urid::*;
+
+#[global_allocator]
+static ALLOCATOR: RtAllocator = RtAllocator::new();
+
+#[derive(PortCollection)]
+struct Ports {
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+#[uri("urn:lv2_test:benchmark:passthrough")]
+struct Passthrough {
+    frames: u64,
+}
+
+impl Plugin for Passthrough {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self { frames: 0 })
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        ports.output.copy_from_slice(&ports.input);
+        self.frames += ports.input.len() as u64;
+    }
+}
+
+/// A plugin that collects its input in a growing buffer, which allocates on the audio thread.
+#[uri("urn:lv2_test:benchmark:recorder")]
+struct Recorder {
+    recording: Vec<f32>,
+}
+
+impl Plugin for Recorder {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self {
+            recording: Vec::new(),
+        })
+    }
+
+    fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+        self.recording.extend(ports.input.iter());
+        ports.output.copy_from_slice(&ports.input);
+    }
+}
+
+#[derive(FeatureCollection)]
+struct ToucherFeatures<'a> {
+    schedule: Schedule<'a, Toucher>,
+}
+
+/// A plugin whose worker touches fresh memory for every cycle, while its `run` method doesn't.
+#[uri("urn:lv2_test:benchmark:toucher")]
+struct Toucher {
+    touched: u32,
+}
+
+/// The number of bytes the worker touches.
+const TOUCHED_SIZE: usize = 1 << 22;
+
+impl Plugin for Toucher {
+    type Ports = Ports;
+    type InitFeatures = ();
+    type AudioFeatures = ToucherFeatures<'static>;
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self { touched: 0 })
+    }
+
+    fn run(&mut self, ports: &mut Ports, context: &mut RunContext<ToucherFeatures<'static>>) {
+        ports.output.copy_from_slice(&ports.input);
+        let _ = context.features().schedule.schedule_work(());
+    }
+
+    fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+        match_extensions!(uri, WorkerDescriptor<Self>)
+    }
+}
+
+impl Worker for Toucher {
+    type WorkData = ();
+    type ResponseData = u32;
+
+    fn work(response_handler: &ResponseHandler<Self>, _: ()) -> Result<(), WorkerError> {
+        let mut memory = vec![0u8; TOUCHED_SIZE];
+        for byte in memory.iter_mut().step_by(4096) {
+            *byte = 1;
+        }
+        let pages = memory.iter().map(|byte| *byte as u32).sum();
+        response_handler
+            .respond(pages)
+            .map_err(|_| WorkerError::Unknown)
+    }
+
+    fn work_response(
+        &mut self,
+        pages: u32,
+        _: &mut ToucherFeatures<'static>,
+    ) -> Result<(), WorkerError> {
+        self.touched += pages;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_report() {
+    let mut host = TestHost::<Passthrough>::new().unwrap();
+    let report = Benchmark::new(&[0], &[1])
+        .block_lengths(16, 64)
+        .warmup(4)
+        .run(&mut host, 100)
+        .unwrap();
+
+    assert_eq!(report.cycles.len(), 100);
+    assert!(report
+        .cycles
+        .iter()
+        .all(|cycle| (16..=64).contains(&cycle.frames)));
+    assert_eq!(host.plugin().frames, 4 * 64 + report.frames());
+    assert_eq!(report.allocations, None);
+    assert_eq!(report.page_faults, None);
+
+    assert!(report.percentile(0.0) <= report.percentile(50.0));
+    assert!(report.percentile(50.0) <= report.percentile(99.0));
+    assert_eq!(report.worst(), report.percentile(100.0));
+    assert!(report.worst() <= report.total());
+
+    // The same seed gives the same block lengths.
+    let lengths = |seed: u64, host: &mut TestHost<Passthrough>| -> Vec<u32> {
+        Benchmark::new(&[0], &[1])
+            .seed(seed)
+            .run(host, 10)
+            .unwrap()
+            .cycles
+            .iter()
+            .map(|cycle| cycle.frames)
+            .collect()
+    };
+    assert_eq!(lengths(1, &mut host), lengths(1, &mut host));
+    assert_ne!(lengths(1, &mut host), lengths(2, &mut host));
+}
+
+#[test]
+fn test_percentile() {
+    let cycles: Vec<CycleTime> = (1..=10)
+        .map(|millis| CycleTime {
+            frames: 64,
+            time: Duration::from_millis(millis),
+        })
+        .rev()
+        .collect();
+    let report = BenchmarkReport {
+        cycles,
+        allocations: Some(0),
+        page_faults: Some(0),
+    };
+    assert_eq!(report.percentile(0.0), Duration::from_millis(1));
+    assert_eq!(report.percentile(50.0), Duration::from_millis(5));
+    assert_eq!(report.percentile(95.0), Duration::from_millis(10));
+    assert_eq!(report.worst(), Duration::from_millis(10));
+    assert_eq!(report.total(), Duration::from_millis(55));
+    assert_eq!(report.frames(), 640);
+    assert!(!report.has_violations());
+
+    let empty = BenchmarkReport {
+        cycles: Vec::new(),
+        allocations: None,
+        page_faults: None,
+    };
+    assert_eq!(empty.percentile(50.0), Duration::default());
+}
+
+#[test]
+fn test_allocations() {
+    let mut host = TestHost::<Passthrough>::new().unwrap();
+    let report = Benchmark::new(&[0], &[1])
+        .rt_check(RtCheck::Count)
+        .run(&mut host, 100)
+        .unwrap();
+    assert_eq!(report.allocations, Some(0));
+    if cfg!(target_os = "linux") {
+        assert!(report.page_faults.is_some());
+    }
+
+    let mut host = TestHost::<Recorder>::new().unwrap();
+    let report = Benchmark::new(&[0], &[1])
+        .rt_check(RtCheck::Count)
+        .run(&mut host, 100)
+        .unwrap();
+    assert!(report.allocations.unwrap() > 0);
+    assert!(report.has_violations());
+}
+
+#[test]
+fn test_host_page_faults() {
+    // The worker runs in the immediate mode, and the page faults of the work aren't blamed on the plugin.
+    let mut host = TestHostBuilder::new()
+        .worker_mode(WorkerMode::Immediate)
+        .build::<Toucher>()
+        .unwrap();
+    let report = Benchmark::new(&[0], &[1])
+        .warmup(0)
+        .rt_check(RtCheck::Count)
+        .run(&mut host, 16)
+        .unwrap();
+    assert_eq!(report.allocations, Some(0));
+    assert_eq!(host.plugin().touched, 16 * (TOUCHED_SIZE / 4096) as u32);
+    if let Some(page_faults) = report.page_faults {
+        assert!(page_faults < (TOUCHED_SIZE / 4096) as u64);
+    }
+}
+
+#[test]
+fn test_criterion_routine() {
+    let mut host = TestHost::<Passthrough>::new().unwrap();
+    let benchmark = Benchmark::new(&[0], &[1]).block_lengths(64, 64).warmup(0);
+    let time = benchmark.time(&mut host, 10).unwrap();
+    assert!(time > Duration::default());
+    assert_eq!(host.plugin().frames, 640);
+}
@@ -0,0 +1,247 @@
+use lv2_atom::prelude::*;
+use lv2_atom::space::*;
+use lv2_test::*;
+use lv2_units::prelude::*;
+use urid::*;
+
+#[uri("urn:lv2_test:diff:Set")]
+struct Set;
+
+#[uri("urn:lv2_test:diff:value")]
+struct Value;
+
+#[uri("urn:lv2_test:diff:label")]
+struct Label;
+
+#[derive(URIDCollection)]
+struct URIDs {
+    atom: AtomURIDCollection,
+    units: UnitURIDCollection,
+    set: URID<Set>,
+    value: URID<Value>,
+    label: URID<Label>,
+}
+
+/// An event of the written test sequences.
+struct Event {
+    frame: i64,
+    value: f32,
+    label: Option<&'static str>,
+}
+
+fn write_sequence(urids: &URIDs, events: &[Event]) -> Box<[u8]> {
+    let mut raw_space: Box<[u8]> = Box::new([0; 1024]);
+    {
+        let mut space = RootMutSpace::new(raw_space.as_mut());
+        let mut sequence = (&mut space as &mut dyn MutSpace)
+            .init(
+                urids.atom.sequence,
+                TimeStampURID::Frames(urids.units.frame),
+            )
+            .unwrap();
+        for event in events {
+            let mut object = sequence
+                .init(
+                    TimeStamp::Frames(event.frame),
+                    urids.atom.object,
+                    ObjectHeader {
+                        id: None,
+                        otype: urids.set.into_general(),
+                    },
+                )
+                .unwrap();
+            if let Some(label) = event.label {
+                object
+                    .init(urids.label, None, urids.atom.string, ())
+                    .unwrap()
+                    .append(label)
+                    .unwrap();
+            }
+            let mut tuple = object
+                .init(urids.value, None, urids.atom.tuple, ())
+                .unwrap();
+            tuple.init(urids.atom.int, 1).unwrap();
+            tuple.init(urids.atom.float, event.value).unwrap();
+        }
+    }
+    raw_space
+}
+
+fn atom(space: &[u8]) -> UnidentifiedAtom<'_> {
+    UnidentifiedAtom::new(Space::from_slice(space))
+}
+
+#[test]
+fn test_diff_atoms() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+
+    let expected = write_sequence(
+        &urids,
+        &[
+            Event {
+                frame: 0,
+                value: 0.5,
+                label: Some("first"),
+            },
+            Event {
+                frame: 16,
+                value: 1.0,
+                label: None,
+            },
+        ],
+    );
+
+    // Equal atoms, with the properties in the same order.
+    let same = write_sequence(
+        &urids,
+        &[
+            Event {
+                frame: 0,
+                value: 0.5,
+                label: Some("first"),
+            },
+            Event {
+                frame: 16,
+                value: 1.0,
+                label: None,
+            },
+        ],
+    );
+    assert_eq!(diff_atoms(&map, atom(&expected), atom(&same)), None);
+    assert_atoms_eq(&map, atom(&expected), atom(&same));
+
+    // A nested value differs.
+    let different_value = write_sequence(
+        &urids,
+        &[
+            Event {
+                frame: 0,
+                value: 0.5,
+                label: Some("first"),
+            },
+            Event {
+                frame: 16,
+                value: 0.25,
+                label: None,
+            },
+        ],
+    );
+    let diff = diff_atoms(&map, atom(&expected), atom(&different_value)).unwrap();
+    assert_eq!(
+        diff,
+        AtomDiff {
+            path: "[1].<urn:lv2_test:diff:value>[1]".to_owned(),
+            expected: "1.0".to_owned(),
+            actual: "0.25".to_owned(),
+        }
+    );
+    assert_eq!(
+        diff.to_string(),
+        "atoms differ at [1].<urn:lv2_test:diff:value>[1]: expected 1.0, found 0.25"
+    );
+
+    // A property is missing.
+    let missing_property = write_sequence(
+        &urids,
+        &[
+            Event {
+                frame: 0,
+                value: 0.5,
+                label: None,
+            },
+            Event {
+                frame: 16,
+                value: 1.0,
+                label: None,
+            },
+        ],
+    );
+    let diff = diff_atoms(&map, atom(&expected), atom(&missing_property)).unwrap();
+    assert_eq!(diff.path, "[0].<urn:lv2_test:diff:label>");
+    assert_eq!(diff.expected, "a property");
+    assert_eq!(diff.actual, "no property");
+
+    // A time stamp differs and an event is missing.
+    let moved_event = write_sequence(
+        &urids,
+        &[Event {
+            frame: 8,
+            value: 0.5,
+            label: Some("first"),
+        }],
+    );
+    let diff = diff_atoms(&map, atom(&expected), atom(&moved_event)).unwrap();
+    assert_eq!(diff.path, "[0]");
+    assert_eq!(diff.expected, "an event at frame 0");
+    assert_eq!(diff.actual, "an event at frame 8");
+    let diff = diff_atoms(&map, atom(&moved_event), atom(&expected)).unwrap();
+    assert_eq!(diff.actual, "an event at frame 0");
+
+    // The types differ.
+    let mut int: Box<[u8]> = Box::new([0; 16]);
+    (&mut RootMutSpace::new(int.as_mut()) as &mut dyn MutSpace)
+        .init(urids.atom.int, 1)
+        .unwrap();
+    let diff = diff_atoms(&map, atom(&expected), atom(&int)).unwrap();
+    assert_eq!(diff.path, "");
+    assert_eq!(
+        diff.to_string(),
+        "atoms differ at atom: expected an atom of type <http://lv2plug.in/ns/ext/atom#Sequence>, found an atom of type <http://lv2plug.in/ns/ext/atom#Int>"
+    );
+}
+
+#[test]
+#[should_panic(expected = "atoms differ at [0].<urn:lv2_test:diff:label>")]
+fn test_assert_atoms_eq() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+
+    let expected = write_sequence(
+        &urids,
+        &[Event {
+            frame: 0,
+            value: 0.5,
+            label: Some("first"),
+        }],
+    );
+    let actual = write_sequence(
+        &urids,
+        &[Event {
+            frame: 0,
+            value: 0.5,
+            label: Some("second"),
+        }],
+    );
+    assert_atoms_eq(&map, atom(&expected), atom(&actual));
+}
+
+#[test]
+fn test_diff_vectors() {
+    let map = HashURIDMapper::new();
+    let urids: URIDs = map.populate_collection().unwrap();
+
+    let write = |values: &[i32]| {
+        let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+        (&mut RootMutSpace::new(raw_space.as_mut()) as &mut dyn MutSpace)
+            .init(urids.atom.vector(), urids.atom.int)
+            .unwrap()
+            .append(values)
+            .unwrap();
+        raw_space
+    };
+    let expected = write(&[1, 2, 3]);
+    assert_eq!(
+        diff_atoms(&map, atom(&expected), atom(&write(&[1, 2, 3]))),
+        None
+    );
+
+    let diff = diff_atoms(&map, atom(&expected), atom(&write(&[1, 5, 3]))).unwrap();
+    assert_eq!(diff.path, "[1]");
+    assert_eq!(diff.expected, "2");
+    assert_eq!(diff.actual, "5");
+
+    let diff = diff_atoms(&map, atom(&expected), atom(&write(&[1, 2]))).unwrap();
+    assert_eq!(diff.path, "[2]");
+    assert_eq!(diff.actual, "no element");
+}
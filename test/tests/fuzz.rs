@@ -0,0 +1,84 @@
+use lv2_test::*;
+use urid::*;
+
+#[uri("urn:lv2_test:fuzz:Custom")]
+struct Custom;
+
+#[test]
+fn test_generated_cases() {
+    let fuzzer = AtomFuzzer::new();
+    let mut generator = fuzzer.generator(1);
+    for buffer in generator.cases(2000) {
+        fuzzer.check_atom(buffer.as_bytes());
+    }
+}
+
+#[test]
+fn test_valid_atoms() {
+    let fuzzer = AtomFuzzer::new();
+    let mut generator = fuzzer.generator(2).max_depth(4).max_length(4);
+    for _ in 0..500 {
+        let buffer = generator.valid_atom();
+        assert!(buffer.as_bytes().len() >= 8);
+        assert!(buffer.atom().type_urid().is_some());
+        fuzzer.check_valid_atom(&buffer);
+    }
+}
+
+#[test]
+fn test_bodies() {
+    let fuzzer = AtomFuzzer::new();
+    let urids = fuzzer.urids().clone();
+    let mut generator = fuzzer.generator(3);
+    for _ in 0..500 {
+        let body = generator.bytes(48);
+        fuzzer.check_body(urids.sequence, &body);
+        fuzzer.check_body(urids.object, &body);
+        fuzzer.check_body(urids.vector::<lv2_atom::prelude::Int>(), &body);
+        fuzzer.check_body(urids.literal, &body);
+        fuzzer.check_body(urids.chunk, &body);
+        fuzzer.check_body(urids.string, &body);
+    }
+}
+
+#[test]
+fn test_malformed_atoms() {
+    let fuzzer = AtomFuzzer::new();
+    let urids = fuzzer.urids().clone();
+    // Empty strings and literals, without a null terminator.
+    fuzzer.check_body(urids.string, &[]);
+    fuzzer.check_body(urids.literal, &[0, 0, 0, 0, 1, 0, 0, 0]);
+    // A string whose last byte is part of a multi-byte character.
+    fuzzer.check_body(urids.string, "ä".as_bytes());
+    // A vector of ints with a truncated last child.
+    let mut vector = Vec::new();
+    vector.extend_from_slice(&4u32.to_ne_bytes());
+    vector.extend_from_slice(&urids.int.get().to_ne_bytes());
+    vector.extend_from_slice(&[0; 6]);
+    fuzzer.check_body(urids.vector::<lv2_atom::prelude::Int>(), &vector);
+    // An atom that is larger than the buffer.
+    fuzzer.check_atom(&[255, 255, 255, 255, 1, 0, 0, 0]);
+    fuzzer.check_atom(&[]);
+}
+
+#[test]
+fn test_custom_types() {
+    let fuzzer = AtomFuzzer::new();
+    let custom = fuzzer.mapper().map_type::<Custom>().unwrap();
+    let mut generator = fuzzer.generator(4).custom_type(custom);
+    let generated = (0..1000)
+        .map(|_| generator.valid_atom())
+        .filter(|buffer| buffer.atom().type_urid() == Some(custom.into_general()))
+        .count();
+    assert!(generated > 0);
+}
+
+#[test]
+fn test_determinism() {
+    let fuzzer = AtomFuzzer::new();
+    let first = fuzzer.generator(5).cases(100);
+    let second = fuzzer.generator(5).cases(100);
+    let third = fuzzer.generator(6).cases(100);
+    assert_eq!(first, second);
+    assert_ne!(first, third);
+}
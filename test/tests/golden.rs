@@ -0,0 +1,266 @@
+use lv2_atom::prelude::*;
+use lv2_core::prelude::*;
+use lv2_midi::prelude::*;
+use lv2_test::*;
+use lv2_units::prelude::*;
+use lv2_urid::*;
+use std::path::PathBuf;
+use urid::*;
+
+#[derive(PortCollection)]
+struct AmpPorts {
+    gain: InputPort<Control>,
+    input: InputPort<Audio>,
+    output: OutputPort<Audio>,
+}
+
+#[uri("urn:lv2_test:golden:amp")]
+struct Amp;
+
+impl Plugin for Amp {
+    type Ports = AmpPorts;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, ports: &mut AmpPorts, _: &mut RunContext<()>) {
+        for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+            *output = input * *ports.gain;
+        }
+    }
+}
+
+#[derive(PortCollection)]
+struct GatePorts {
+    midi: InputPort<AtomPort>,
+    output: OutputPort<Audio>,
+}
+
+#[derive(FeatureCollection)]
+struct Features<'a> {
+    map: LV2Map<'a>,
+}
+
+#[derive(URIDCollection)]
+struct URIDs {
+    atom: AtomURIDCollection,
+    midi: MidiURIDCollection,
+    units: UnitURIDCollection,
+}
+
+/// An instrument that outputs 1.0 while a note is played.
+#[uri("urn:lv2_test:golden:gate")]
+struct Gate {
+    urids: URIDs,
+    active_notes: u32,
+}
+
+impl Plugin for Gate {
+    type Ports = GatePorts;
+    type InitFeatures = Features<'static>;
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, features: &mut Features<'static>) -> Option<Self> {
+        Some(Self {
+            urids: features.map.populate_collection()?,
+            active_notes: 0,
+        })
+    }
+
+    fn run(&mut self, ports: &mut GatePorts, _: &mut RunContext<()>) {
+        let events = ports
+            .midi
+            .read(self.urids.atom.sequence, self.urids.units.beat)
+            .unwrap();
+        let mut offset: usize = 0;
+        for (timestamp, atom) in events {
+            let frame = timestamp.as_frames().unwrap() as usize;
+            let message = atom.read(self.urids.midi.raw, ()).unwrap();
+            for sample in ports.output[offset..frame].iter_mut() {
+                *sample = if self.active_notes > 0 { 1.0 } else { 0.0 };
+            }
+            offset = frame;
+            match message[0] & 0xf0 {
+                0x90 => self.active_notes += 1,
+                0x80 => self.active_notes -= 1,
+                _ => (),
+            }
+        }
+        for sample in ports.output[offset..].iter_mut() {
+            *sample = if self.active_notes > 0 { 1.0 } else { 0.0 };
+        }
+    }
+}
+
+fn golden_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&directory);
+    directory
+}
+
+#[test]
+fn test_tolerance() {
+    assert!(Tolerance::Ulp(0).accepts(0.5, 0.5));
+    assert!(Tolerance::Ulp(0).accepts(0.0, -0.0));
+    assert!(!Tolerance::Ulp(0).accepts(0.5, 0.5 + f32::EPSILON));
+    assert!(Tolerance::Ulp(2).accepts(1.0, 1.0 + f32::EPSILON));
+    assert!(!Tolerance::Ulp(2).accepts(f32::MIN_POSITIVE / 2.0, -f32::MIN_POSITIVE / 2.0));
+    assert!(!Tolerance::Ulp(u32::MAX).accepts(f32::NAN, f32::NAN));
+
+    assert!(Tolerance::Decibels(-60.0).accepts(0.5, 0.5009));
+    assert!(!Tolerance::Decibels(-60.0).accepts(0.5, 0.5011));
+}
+
+#[test]
+fn test_wav_files() {
+    let directory = golden_directory("lv2_test_wav");
+    let path = directory.join("audio.wav");
+    let audio = AudioData::new(48000, vec![vec![0.0, 0.25, -1.0], vec![1.0, -0.5, 0.125]]);
+    audio.write_wav(&path).unwrap();
+    assert_eq!(AudioData::read_wav(&path).unwrap(), audio);
+
+    assert_eq!(
+        AudioData::read_wav(&directory.join("missing.wav")),
+        Err(GoldenError::Io(std::io::ErrorKind::NotFound))
+    );
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_compare() {
+    let expected = AudioData::new(44100, vec![vec![0.0, 0.5, 1.0]]);
+    let actual = AudioData::new(44100, vec![vec![0.0, 0.5, 0.75]]);
+    assert_eq!(
+        actual.compare(&expected, Tolerance::Ulp(0)),
+        Err(GoldenError::Mismatch {
+            channel: 0,
+            frame: 2,
+            expected: 1.0,
+            actual: 0.75,
+        })
+    );
+    assert!(actual.compare(&expected, Tolerance::Decibels(0.0)).is_ok());
+    assert_eq!(
+        AudioData::silence(44100, 2, 3).compare(&expected, Tolerance::Ulp(0)),
+        Err(GoldenError::ChannelCount {
+            expected: 1,
+            actual: 2
+        })
+    );
+    assert_eq!(
+        AudioData::silence(44100, 1, 2).compare(&expected, Tolerance::Ulp(0)),
+        Err(GoldenError::Length {
+            expected: 3,
+            actual: 2
+        })
+    );
+}
+
+#[test]
+fn test_midi_events() {
+    let events = parse_midi_events(
+        "# frame status data
+        64 80 3C 00
+        0  90 3C 7F # note on
+
+        10 B0 07 64",
+    )
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            TimedEvent {
+                frame: 0,
+                message: vec![0x90, 0x3c, 0x7f]
+            },
+            TimedEvent {
+                frame: 10,
+                message: vec![0xb0, 0x07, 0x64]
+            },
+            TimedEvent {
+                frame: 64,
+                message: vec![0x80, 0x3c, 0x00]
+            },
+        ]
+    );
+
+    assert_eq!(
+        parse_midi_events("0 90 3C 7F\nx 80 3C 00"),
+        Err(GoldenError::BadEvent(2))
+    );
+    assert_eq!(parse_midi_events("0 90 XY"), Err(GoldenError::BadEvent(1)));
+    assert_eq!(parse_midi_events("0"), Err(GoldenError::BadEvent(1)));
+}
+
+#[test]
+fn test_render_amp() {
+    let mut host = TestHost::<Amp>::new().unwrap();
+    let mut gain: f32 = 0.5;
+    unsafe { host.connect_port(0, &mut gain) };
+
+    let input = AudioData::new(48000, vec![(0..100).map(|i| i as f32).collect()]);
+    let output = Render::new(&[1], &[2])
+        .block_length(32)
+        .run(&mut host, &input)
+        .unwrap();
+    assert_eq!(output.sample_rate, 48000);
+    assert_eq!(
+        output.channels,
+        vec![(0..100).map(|i| i as f32 * 0.5).collect::<Vec<f32>>()]
+    );
+
+    // The input is padded with silence.
+    let output = Render::new(&[1], &[2])
+        .frames(120)
+        .run(&mut host, &input)
+        .unwrap();
+    assert_eq!(output.frames(), 120);
+    assert_eq!(output.channels[0][119], 0.0);
+
+    // Every input port needs a channel.
+    assert_eq!(
+        Render::new(&[1], &[2]).run(&mut host, &AudioData::silence(48000, 2, 10)),
+        Err(GoldenError::ChannelCount {
+            expected: 1,
+            actual: 2
+        })
+    );
+
+    // The golden file is created when updating is requested, and compared with otherwise.
+    let directory = golden_directory("lv2_test_golden");
+    let path = directory.join("amp.wav");
+    assert!(check_golden(&output, &path, Tolerance::Ulp(0)).is_err());
+    output.write_wav(&path).unwrap();
+    check_golden(&output, &path, Tolerance::Ulp(0)).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_render_midi() {
+    let mut host = TestHost::<Gate>::new().unwrap();
+    let events = parse_midi_events("10 90 3C 7F\n40 90 40 7F\n50 80 3C 00\n70 80 40 00").unwrap();
+    let output = Render::new(&[], &[1])
+        .block_length(16)
+        .frames(80)
+        .midi(0, &events)
+        .run(&mut host, &AudioData::silence(44100, 0, 0))
+        .unwrap();
+
+    let expected: Vec<f32> = (0..80)
+        .map(|frame| if (10..70).contains(&frame) { 1.0 } else { 0.0 })
+        .collect();
+    assert_eq!(output.channels, vec![expected]);
+
+    // The events have to fit into the sequence.
+    assert_eq!(
+        Render::new(&[], &[1])
+            .frames(80)
+            .midi(0, &events)
+            .sequence_size(24)
+            .run(&mut host, &AudioData::silence(44100, 0, 0)),
+        Err(GoldenError::SequenceOverflow)
+    );
+}
@@ -0,0 +1,538 @@
+use lv2_atom::prelude::*;
+use lv2_core::feature::*;
+use lv2_core::prelude::*;
+use lv2_options::*;
+use lv2_state::*;
+use lv2_test::*;
+use lv2_urid::*;
+use lv2_worker::*;
+use std::ffi::c_void;
+use urid::*;
+
+/// A minimal version of the log feature that only logs notes.
+struct Log<'a> {
+    internal: &'a lv2_sys::LV2_Log_Log,
+}
+
+unsafe impl<'a> UriBound for Log<'a> {
+    const URI: &'static [u8] = lv2_sys::LV2_LOG__log;
+}
+
+unsafe impl<'a> Feature for Log<'a> {
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        _: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        (feature as *const lv2_sys::LV2_Log_Log)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+impl<'a> Log<'a> {
+    fn note(&self, note: URID, message: &'static [u8]) {
+        if let Some(printf) = self.internal.printf {
+            unsafe {
+                printf(
+                    self.internal.handle,
+                    note.get(),
+                    message.as_ptr() as *const std::os::raw::c_char,
+                )
+            };
+        }
+    }
+}
+
+#[uri("http://lv2plug.in/ns/ext/log#Note")]
+struct Note;
+
+#[uri("urn:lv2_test:counter:total")]
+struct Total;
+
+#[derive(URIDCollection)]
+struct URIDs {
+    atom: AtomURIDCollection,
+    options: OptionsURIDCollection,
+    note: URID<Note>,
+    total: URID<Total>,
+}
+
+#[derive(PortCollection)]
+struct Ports {
+    output: OutputPort<Audio>,
+}
+
+#[derive(FeatureCollection)]
+struct InitFeatures<'a> {
+    map: LV2Map<'a>,
+    options: OptionsList<'a>,
+    log: Log<'a>,
+}
+
+#[derive(FeatureCollection)]
+struct AudioFeatures<'a> {
+    schedule: Schedule<'a, Counter>,
+}
+
+/// A plugin that sends the number of frames of every cycle to its worker, which doubles it.
+#[uri("urn:lv2_test:counter")]
+struct Counter {
+    urids: URIDs,
+    sample_rate: f32,
+    total: i32,
+    end_runs: u32,
+    rejected: u32,
+}
+
+impl Plugin for Counter {
+    type Ports = Ports;
+    type InitFeatures = InitFeatures<'static>;
+    type AudioFeatures = AudioFeatures<'static>;
+
+    fn new(_: &PluginInfo, features: &mut InitFeatures<'static>) -> Option<Self> {
+        let urids: URIDs = features.map.populate_collection()?;
+        let sample_rate = features
+            .options
+            .read(urids.options.sample_rate, urids.atom.float, ())
+            .unwrap_or(0.0);
+        features
+            .log
+            .note(urids.note.into_general(), b"instantiated\0");
+        Some(Self {
+            urids,
+            sample_rate,
+            total: 0,
+            end_runs: 0,
+            rejected: 0,
+        })
+    }
+
+    fn migrate(
+        previous: Self,
+        plugin_info: &PluginInfo,
+        _: &mut InitFeatures<'static>,
+    ) -> Option<Self> {
+        Some(Self {
+            sample_rate: plugin_info.sample_rate() as f32,
+            ..previous
+        })
+    }
+
+    fn run(&mut self, ports: &mut Ports, context: &mut RunContext<AudioFeatures<'static>>) {
+        for sample in ports.output.iter_mut() {
+            *sample = self.total as f32;
+        }
+        if let Err(ScheduleError::NoSpace(frames)) = context
+            .features()
+            .schedule
+            .schedule_work(ports.output.len() as u32)
+        {
+            assert_eq!(frames, ports.output.len() as u32);
+            self.rejected += 1;
+        }
+    }
+
+    fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+        match_extensions!(uri, WorkerDescriptor<Self>, StateDescriptor<Self>)
+    }
+}
+
+impl Worker for Counter {
+    type WorkData = u32;
+    type ResponseData = u32;
+
+    fn work(response_handler: &ResponseHandler<Self>, data: u32) -> Result<(), WorkerError> {
+        response_handler
+            .respond(data * 2)
+            .map_err(|_| WorkerError::Unknown)
+    }
+
+    fn work_response(
+        &mut self,
+        data: u32,
+        _: &mut AudioFeatures<'static>,
+    ) -> Result<(), WorkerError> {
+        self.total += data as i32;
+        Ok(())
+    }
+
+    fn end_run(&mut self, _: &mut AudioFeatures<'static>) -> Result<(), WorkerError> {
+        self.end_runs += 1;
+        Ok(())
+    }
+}
+
+impl State for Counter {
+    type StateFeatures = ();
+
+    fn save(&self, mut store: StoreHandle, _: ()) -> Result<(), StateErr> {
+        store
+            .draft(self.urids.total.into_general())
+            .init(self.urids.atom.int, self.total)?;
+        store.commit_all()
+    }
+
+    fn restore(&mut self, store: RetrieveHandle, _: ()) -> Result<(), StateErr> {
+        self.total = store
+            .retrieve(self.urids.total.into_general())?
+            .read(self.urids.atom.int, ())?;
+        Ok(())
+    }
+}
+
+/// A plugin without any extensions.
+#[uri("urn:lv2_test:plain")]
+struct Plain;
+
+impl Plugin for Plain {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+#[derive(FeatureCollection)]
+struct BatchFeatures<'a> {
+    schedule: Schedule<'a, Batcher>,
+}
+
+/// A plugin that schedules a number of jobs every cycle and receives all responses at once.
+#[uri("urn:lv2_test:batcher")]
+struct Batcher {
+    jobs: u32,
+    queue: ResponseQueue<u32>,
+    batches: Vec<Vec<u32>>,
+    end_runs: u32,
+}
+
+impl Plugin for Batcher {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = BatchFeatures<'static>;
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self {
+            jobs: 0,
+            queue: ResponseQueue::with_capacity(4),
+            batches: Vec::new(),
+            end_runs: 0,
+        })
+    }
+
+    fn run(&mut self, _: &mut (), context: &mut RunContext<BatchFeatures<'static>>) {
+        for job in 0..self.jobs {
+            assert!(context.features().schedule.schedule_work(job).is_ok());
+        }
+    }
+
+    fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+        match_extensions!(uri, BatchWorkerDescriptor<Self>)
+    }
+}
+
+impl Worker for Batcher {
+    type WorkData = u32;
+    type ResponseData = u32;
+
+    fn work(response_handler: &ResponseHandler<Self>, data: u32) -> Result<(), WorkerError> {
+        response_handler
+            .respond(data * 10)
+            .map_err(|_| WorkerError::Unknown)
+    }
+
+    fn end_run(&mut self, _: &mut BatchFeatures<'static>) -> Result<(), WorkerError> {
+        // The responses have already been handed over.
+        assert!(self.queue.is_empty());
+        self.end_runs += 1;
+        Ok(())
+    }
+}
+
+impl BatchWorker for Batcher {
+    fn response_queue(&mut self) -> &mut ResponseQueue<u32> {
+        &mut self.queue
+    }
+
+    fn work_responses(
+        &mut self,
+        responses: Responses<u32>,
+        _: &mut BatchFeatures<'static>,
+    ) -> Result<(), WorkerError> {
+        self.batches.push(responses.collect());
+        Ok(())
+    }
+}
+
+#[derive(FeatureCollection)]
+struct PrioritizerFeatures<'a> {
+    schedule: Schedule<'a, Prioritizer>,
+}
+
+/// A plugin that schedules background jobs before an urgent one and records the order in which they are done.
+#[uri("urn:lv2_test:prioritizer")]
+struct Prioritizer {
+    done: Vec<(u32, Priority)>,
+}
+
+impl Plugin for Prioritizer {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = PrioritizerFeatures<'static>;
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self { done: Vec::new() })
+    }
+
+    fn run(&mut self, _: &mut (), context: &mut RunContext<PrioritizerFeatures<'static>>) {
+        let schedule = &context.features().schedule;
+        assert!(schedule
+            .schedule_work_with_priority(0, Priority::BACKGROUND)
+            .is_ok());
+        assert!(schedule
+            .schedule_work_with_priority(1, Priority::BACKGROUND)
+            .is_ok());
+        assert!(schedule.schedule_work(2).is_ok());
+        assert!(schedule
+            .schedule_work_with_priority(3, Priority::URGENT)
+            .is_ok());
+    }
+
+    fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+        match_extensions!(uri, WorkerDescriptor<Self>)
+    }
+}
+
+impl Worker for Prioritizer {
+    type WorkData = u32;
+    type ResponseData = (u32, Priority);
+
+    fn work(response_handler: &ResponseHandler<Self>, data: u32) -> Result<(), WorkerError> {
+        response_handler
+            .respond((data, response_handler.priority()))
+            .map_err(|_| WorkerError::Unknown)
+    }
+
+    fn work_response(
+        &mut self,
+        data: (u32, Priority),
+        _: &mut PrioritizerFeatures<'static>,
+    ) -> Result<(), WorkerError> {
+        self.done.push(data);
+        Ok(())
+    }
+}
+
+fn counter_host(mode: WorkerMode) -> TestHost<Counter> {
+    let builder = TestHostBuilder::new()
+        .sample_rate(48000.0)
+        .worker_mode(mode);
+    let sample_rate = builder
+        .mapper()
+        .populate_collection::<OptionsURIDCollection>()
+        .unwrap()
+        .sample_rate;
+    let float = builder.mapper().map_type::<Float>().unwrap();
+    builder
+        .scalar_option(sample_rate, float, 48000.0)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_instantiation() {
+    let host = counter_host(WorkerMode::Deferred);
+    assert_eq!(host.plugin().sample_rate, 48000.0);
+    assert!(!host.is_active());
+
+    let note = host.mapper().map_type::<Note>().unwrap();
+    assert_eq!(
+        host.log_messages(),
+        &[(note.into_general(), "instantiated".to_owned())]
+    );
+
+    // The bundle path has to be valid.
+    match TestHostBuilder::new()
+        .bundle_path("bad\0path")
+        .build::<Counter>()
+    {
+        Err(TestError::BadPath) => (),
+        _ => panic!("Instantiation with a bad bundle path succeeded"),
+    }
+}
+
+#[test]
+fn test_lifecycle() {
+    let mut host = counter_host(WorkerMode::Deferred);
+    let mut output: Vec<f32> = vec![-1.0; 16];
+    unsafe { host.connect_port(0, output.as_mut_ptr()) };
+
+    // Running the plugin activates it and schedules work.
+    host.run_cycle(16).unwrap();
+    assert!(host.is_active());
+    assert_eq!(output, vec![0.0; 16]);
+    assert_eq!(host.pending_work(), 1);
+
+    // The work is only done when the worker is drained.
+    host.run_cycle(8).unwrap();
+    assert_eq!(host.pending_work(), 2);
+    assert_eq!(host.plugin().total, 0);
+    assert_eq!(host.drain_worker(), Ok(2));
+    assert_eq!(host.pending_work(), 0);
+    assert_eq!(host.plugin().total, 48);
+    assert_eq!(host.plugin().end_runs, 1);
+
+    host.run_cycle(16).unwrap();
+    assert_eq!(output, vec![48.0; 16]);
+
+    host.deactivate();
+    assert!(!host.is_active());
+}
+
+#[test]
+fn test_sample_rate_change() {
+    let mut host = counter_host(WorkerMode::Deferred);
+    let mut output: Vec<f32> = vec![-1.0; 16];
+    unsafe { host.connect_port(0, output.as_mut_ptr()) };
+    host.run_cycle(16).unwrap();
+    host.run_cycle(8).unwrap();
+
+    // The pending work is done by the previous instance, and the new one takes its state over.
+    let mut host = host.change_sample_rate(96000.0).unwrap();
+    assert_eq!(host.sample_rate(), 96000.0);
+    assert!(!host.is_active());
+    assert_eq!(host.pending_work(), 0);
+    assert_eq!(host.plugin().sample_rate, 96000.0);
+    assert_eq!(host.plugin().total, 48);
+    assert_eq!(host.log_messages().len(), 1);
+
+    // The ports are still connected.
+    host.run_cycle(16).unwrap();
+    assert_eq!(output, vec![48.0; 16]);
+    assert_eq!(host.drain_worker(), Ok(1));
+    assert_eq!(host.plugin().total, 80);
+
+    // Plugins that don't migrate are created again.
+    let host = TestHost::<Plain>::new().unwrap();
+    let host = host.change_sample_rate(22050.0).unwrap();
+    assert_eq!(host.sample_rate(), 22050.0);
+}
+
+#[test]
+fn test_worker_modes() {
+    let mut host = counter_host(WorkerMode::Immediate);
+    assert_eq!(host.worker_mode(), WorkerMode::Immediate);
+    let mut output: Vec<f32> = vec![-1.0; 16];
+    unsafe { host.connect_port(0, output.as_mut_ptr()) };
+
+    // The work is done during the cycle and the response is delivered at its end.
+    host.run_cycle(16).unwrap();
+    assert_eq!(output, vec![0.0; 16]);
+    assert_eq!(host.pending_work(), 0);
+    assert_eq!(host.plugin().total, 32);
+    assert_eq!(host.plugin().end_runs, 1);
+    host.run_cycle(16).unwrap();
+    assert_eq!(output, vec![32.0; 16]);
+    assert_eq!(host.plugin().total, 64);
+    assert_eq!(host.plugin().end_runs, 2);
+
+    // Rejected work is returned to the plugin.
+    host.set_worker_mode(WorkerMode::NoSpace);
+    host.run_cycle(16).unwrap();
+    host.run_cycle(16).unwrap();
+    assert_eq!(host.plugin().rejected, 2);
+    assert_eq!(host.pending_work(), 0);
+    assert_eq!(host.drain_worker(), Ok(0));
+    assert_eq!(host.plugin().total, 64);
+
+    // Queued work is handled at the end of the next immediate cycle.
+    host.set_worker_mode(WorkerMode::Deferred);
+    host.run_cycle(8).unwrap();
+    assert_eq!(host.pending_work(), 1);
+    host.set_worker_mode(WorkerMode::Immediate);
+    host.run_cycle(8).unwrap();
+    assert_eq!(host.pending_work(), 0);
+    assert_eq!(host.plugin().total, 96);
+}
+
+#[test]
+fn test_state() {
+    let mut host = counter_host(WorkerMode::Deferred);
+    host.plugin_mut().total = 42;
+    let mut storage = host.save_state().unwrap();
+
+    let mut other = counter_host(WorkerMode::Deferred);
+    other.restore_state(&mut storage).unwrap();
+    assert_eq!(other.plugin().total, 42);
+
+    // Restoring a property that doesn't exist fails.
+    let mut empty = Storage::default();
+    assert_eq!(
+        other.restore_state(&mut empty),
+        Err(TestError::State(StateErr::NoProperty))
+    );
+}
+
+#[test]
+fn test_missing_extensions() {
+    let mut host = TestHost::<Plain>::new().unwrap();
+    host.run_cycle(8).unwrap();
+    assert_eq!(host.drain_worker(), Err(TestError::MissingExtension));
+    assert!(host.save_state().is_err());
+    assert!(host.log_messages().is_empty());
+}
+
+#[test]
+fn test_batched_responses() {
+    let mut host = TestHostBuilder::new()
+        .worker_mode(WorkerMode::Immediate)
+        .build::<Batcher>()
+        .unwrap();
+
+    // All responses of a cycle are handed over at once, before `end_run` is called.
+    host.plugin_mut().jobs = 3;
+    host.run_cycle(8).unwrap();
+    host.run_cycle(8).unwrap();
+    assert_eq!(
+        host.plugin().batches,
+        vec![vec![0, 10, 20], vec![0, 10, 20]]
+    );
+    assert_eq!(host.plugin().end_runs, 2);
+
+    // There's no batch if there are no responses.
+    host.plugin_mut().jobs = 0;
+    host.run_cycle(8).unwrap();
+    assert_eq!(host.plugin().batches.len(), 2);
+    assert_eq!(host.plugin().end_runs, 3);
+
+    // Responses that don't fit into the queue are rejected.
+    host.plugin_mut().jobs = 5;
+    assert_eq!(
+        host.run_cycle(8),
+        Err(TestError::Worker(WorkerError::NoSpace))
+    );
+    assert_eq!(host.drain_worker(), Ok(0));
+    assert_eq!(host.plugin().batches[2], vec![0, 10, 20, 30]);
+    assert_eq!(host.plugin().queue.capacity(), 4);
+}
+
+#[test]
+fn test_work_priorities() {
+    let mut host = TestHost::<Prioritizer>::new().unwrap();
+    host.run_cycle(8).unwrap();
+    assert_eq!(host.drain_worker(), Ok(4));
+    assert_eq!(
+        host.plugin().done,
+        vec![
+            (3, Priority::URGENT),
+            (2, Priority::NORMAL),
+            (0, Priority::BACKGROUND),
+            (1, Priority::BACKGROUND)
+        ]
+    );
+}
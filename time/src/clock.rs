@@ -0,0 +1,254 @@
+use crate::{PositionUpdate, TransportState, TransportStateExt};
+use atom::prelude::*;
+
+/// A clock to synchronize a plugin to the tempo of the host.
+///
+/// The clock tracks the [transport](struct.TransportState.html) of the host and answers the questions a tempo-synced plugin asks in its `run` method, like "How many frames are left until the next sixteenth note?" or "What's the current phase of an LFO that repeats every bar?".
+///
+/// The clock always describes the current frame of a block. Position updates of the host are applied with [`update`](#method.update) at the frame they were received at, and the clock is moved through the block with [`advance`](#method.advance). If the transport is stopped, the clock stands still, and if the host jumps, for example at the end of a loop, [`update`](#method.update) reports it, which lets the plugin reset its LFOs or sequencers.
+///
+/// # Example
+///
+/// ```
+/// use lv2_atom::prelude::*;
+/// use lv2_atom::sequence::SequenceIterator;
+/// use lv2_time::prelude::*;
+///
+/// /// Something like a plugin's run method, with the sequence of the input port.
+/// fn run(
+///     clock: &mut TempoClock,
+///     events: SequenceIterator,
+///     output: &mut [f32],
+///     atom_urids: &AtomURIDCollection,
+///     time_urids: &TimeURIDCollection,
+/// ) {
+///     let mut frame = 0;
+///     for (timestamp, atom) in events {
+///         let event_frame = timestamp.as_frames().unwrap_or(0).max(frame as i64) as usize;
+///         render(clock, &mut output[frame..event_frame]);
+///         frame = event_frame;
+///         if let Some(update) = PositionUpdate::read(atom, atom_urids, time_urids) {
+///             clock.update(&update);
+///         }
+///     }
+///     let end = output.len();
+///     render(clock, &mut output[frame..end]);
+/// }
+///
+/// /// Render a gate that opens for the first half of every eighth note.
+/// fn render(clock: &mut TempoClock, output: &mut [f32]) {
+///     for sample in output.iter_mut() {
+///         *sample = if clock.beat_phase(0.5) < 0.5 { 1.0 } else { 0.0 };
+///         clock.advance(1);
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TempoClock {
+    transport: TransportState,
+    sample_rate: f64,
+}
+
+impl TempoClock {
+    /// Create a clock with a stopped transport, for the given sample rate.
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            transport: TransportState::default(),
+            sample_rate,
+        }
+    }
+
+    /// The current state of the transport.
+    pub fn transport(&self) -> &TransportState {
+        &self.transport
+    }
+
+    /// The sample rate of the clock.
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Apply a position update of the host at the current frame.
+    ///
+    /// Returns `true` if the position jumped, which means that the host relocated the transport or restarted a loop, instead of continuing from where the clock expected it to be. Changes of the tempo or the speed aren't jumps.
+    pub fn update(&mut self, update: &PositionUpdate) -> bool {
+        let expected = self.transport;
+        self.transport.update(update);
+        // Positions that differ by less than a frame are rounding errors.
+        let tolerance = 1.0 / expected.frames_per_beat(self.sample_rate);
+        let beats_per_bar = f64::from(expected.beats_per_bar).max(1.0);
+        (self.transport.beat - expected.beat).abs() > tolerance
+            || (bars(&self.transport) - bars(&expected)).abs() * beats_per_bar > tolerance
+    }
+
+    /// Move the clock forward by the given number of frames.
+    pub fn advance(&mut self, frames: u32) {
+        self.transport.advance(frames, self.sample_rate);
+    }
+
+    /// The number of frames per beat at the current tempo.
+    ///
+    /// Unlike the other methods, this doesn't depend on the speed of the transport, which lets plugins size their delays even if the transport is stopped.
+    pub fn frames_per_beat(&self) -> f64 {
+        self.transport.frames_per_beat(self.sample_rate)
+    }
+
+    /// The number of frames until the next multiple of the given number of beats, counted from the start of the bar.
+    ///
+    /// If the clock is exactly at a multiple, zero is returned. Multiples that cross the end of the bar are moved to the start of the next bar, which keeps the rhythm aligned to bars with odd time signatures. `None` is returned if the transport is stopped or running backwards, since the multiple will never be reached.
+    pub fn frames_until_beats(&self, beats: f64) -> Option<FrameTime> {
+        if beats <= 0.0 || self.transport.speed <= 0.0 {
+            return None;
+        }
+        let bar_beat = f64::from(self.transport.bar_beat);
+        let beats_per_bar = f64::from(self.transport.beats_per_bar);
+        let mut next = (bar_beat / beats).ceil() * beats;
+        if beats_per_bar > 0.0 && next > beats_per_bar {
+            next = beats_per_bar;
+        }
+        let frames_per_beat = self.frames_per_beat() / f64::from(self.transport.speed);
+        Some(FrameTime(
+            ((next - bar_beat) * frames_per_beat).round() as i64
+        ))
+    }
+
+    /// The number of frames until the next note of the given value, like 16 for the next sixteenth note.
+    ///
+    /// The length of the note depends on the beat unit of the time signature: With a beat unit of 4, a sixteenth note lasts a quarter of a beat, but with a beat unit of 8, it lasts half a beat. See [`frames_until_beats`](#method.frames_until_beats) for how the next note is found.
+    pub fn frames_until_note(&self, note_value: u32) -> Option<FrameTime> {
+        if note_value == 0 {
+            return None;
+        }
+        self.frames_until_beats(f64::from(self.transport.beat_unit) / f64::from(note_value))
+    }
+
+    /// The phase of a cycle that lasts the given number of beats, from 0 to 1.
+    ///
+    /// The phase is derived from the global beat of the transport, which means that it stands still while the transport is stopped and follows the host when it jumps.
+    pub fn beat_phase(&self, beats: f64) -> f64 {
+        if beats <= 0.0 {
+            return 0.0;
+        }
+        (self.transport.beat / beats).rem_euclid(1.0)
+    }
+
+    /// The phase of a cycle that lasts the given number of bars, from 0 to 1.
+    ///
+    /// Unlike [`beat_phase`](#method.beat_phase), the phase is derived from the bar and the beat within the bar, which keeps it aligned to bars if the time signature changes.
+    pub fn bar_phase(&self, length: f64) -> f64 {
+        if length <= 0.0 {
+            return 0.0;
+        }
+        (bars(&self.transport) / length).rem_euclid(1.0)
+    }
+}
+
+/// The position of the transport in bars, including the fraction of the current bar.
+fn bars(transport: &TransportState) -> f64 {
+    let beats_per_bar = f64::from(transport.beats_per_bar);
+    if beats_per_bar > 0.0 {
+        transport.bar as f64 + f64::from(transport.bar_beat) / beats_per_bar
+    } else {
+        transport.bar as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use atom::prelude::*;
+
+    fn rolling_clock() -> TempoClock {
+        let mut clock = TempoClock::new(48000.0);
+        clock.update(&PositionUpdate {
+            speed: Some(1.0),
+            ..Default::default()
+        });
+        clock
+    }
+
+    #[test]
+    fn test_note_timing() {
+        let mut clock = rolling_clock();
+
+        // At 120 BPM and 48 kHz, a beat lasts 24000 frames and a sixteenth note 6000 frames.
+        assert_eq!(clock.frames_until_note(16), Some(FrameTime(0)));
+        clock.advance(1000);
+        assert_eq!(clock.frames_until_note(16), Some(FrameTime(5000)));
+        assert_eq!(clock.frames_until_note(1), Some(FrameTime(95000)));
+
+        // With a beat unit of 8, a sixteenth note lasts half a beat.
+        clock.update(&PositionUpdate {
+            beat_unit: Some(8),
+            ..Default::default()
+        });
+        assert_eq!(clock.frames_until_note(16), Some(FrameTime(11000)));
+
+        // In a 3.5/4 bar, the last eighth note is cut short.
+        let mut clock = rolling_clock();
+        clock.update(&PositionUpdate {
+            bar_beat: Some(3.25),
+            beats_per_bar: Some(3.5),
+            ..Default::default()
+        });
+        assert_eq!(clock.frames_until_note(2), Some(FrameTime(6000)));
+
+        // Faster playback reaches the notes earlier, and a stopped transport never.
+        clock.update(&PositionUpdate {
+            speed: Some(2.0),
+            ..Default::default()
+        });
+        assert_eq!(clock.frames_until_note(2), Some(FrameTime(3000)));
+        clock.update(&PositionUpdate {
+            speed: Some(0.0),
+            ..Default::default()
+        });
+        assert_eq!(clock.frames_until_note(2), None);
+    }
+
+    #[test]
+    fn test_phase() {
+        let mut clock = rolling_clock();
+        assert_eq!(clock.bar_phase(1.0), 0.0);
+
+        clock.advance(24000 * 5);
+        assert_eq!(clock.beat_phase(2.0), 0.5);
+        assert_eq!(clock.bar_phase(1.0), 0.25);
+        assert_eq!(clock.bar_phase(2.0), 0.625);
+
+        // A stopped transport freezes the phase.
+        clock.update(&PositionUpdate {
+            speed: Some(0.0),
+            ..Default::default()
+        });
+        clock.advance(12000);
+        assert_eq!(clock.bar_phase(1.0), 0.25);
+    }
+
+    #[test]
+    fn test_jumps() {
+        let mut clock = rolling_clock();
+        clock.advance(24000);
+
+        // An update that matches the position of the clock isn't a jump, even if the tempo changes.
+        assert!(!clock.update(&PositionUpdate {
+            beat: Some(1.0),
+            bar: Some(0),
+            bar_beat: Some(1.0),
+            beats_per_minute: Some(90.0),
+            ..Default::default()
+        }));
+        assert_eq!(clock.frames_per_beat(), 32000.0);
+
+        // The end of a loop moves the transport back.
+        clock.advance(32000 * 7);
+        assert_eq!(clock.transport().bar, 2);
+        assert!(clock.update(&PositionUpdate {
+            beat: Some(0.0),
+            bar: Some(0),
+            bar_beat: Some(0.0),
+            ..Default::default()
+        }));
+        assert_eq!(clock.bar_phase(1.0), 0.0);
+    }
+}
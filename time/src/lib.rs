@@ -4,10 +4,24 @@
 //! The original [specification](https://lv2plug.in/ns/ext/time/time.html) contains means to
 //! describe time for LV2 values in RDF files. This implementation is focused on the stock time
 //! descriptions defined by the specification by binding them to marker types.
+//!
+//! Hosts also send the current transport position to plugins as `time:Position` objects over
+//! atom ports. These can be read with [`PositionUpdate`](struct.PositionUpdate.html) and tracked
+//! with a [`TransportState`](struct.TransportState.html), which is re-exported from `lv2-core`
+//! and updated with the methods of [`TransportStateExt`](trait.TransportStateExt.html).
+//! Tempo-synced plugins can use a [`TempoClock`](struct.TempoClock.html) instead, which tells
+//! them how many frames are left until the next note and what the phase of a tempo-synced LFO is.
+extern crate lv2_atom as atom;
 extern crate lv2_sys as sys;
 
 use urid::*;
 
+mod clock;
+mod position;
+pub use clock::*;
+pub use lv2_core::plugin::TransportState;
+pub use position::*;
+
 /// All time URI bounds
 ///
 /// All Struct suffixed by `Class` are time Classes, others are time properties.
@@ -130,5 +144,7 @@ pub struct TimeURIDCollection {
 /// Prelude of `lv2_time` for wildcard usage.
 pub mod prelude {
     pub use crate::time::*;
-    pub use crate::TimeURIDCollection;
+    pub use crate::{
+        PositionUpdate, TempoClock, TimeURIDCollection, TransportState, TransportStateExt,
+    };
 }
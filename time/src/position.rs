@@ -0,0 +1,393 @@
+use crate::TimeURIDCollection;
+use atom::object::ObjectWriter;
+use atom::prelude::*;
+use lv2_core::plugin::TransportState;
+
+/// Read a number from an atom, regardless of it's concrete numeric type.
+///
+/// Hosts aren't consistent regarding the types of position properties, so every numeric atom type is accepted.
+fn read_number(atom: UnidentifiedAtom, urids: &AtomURIDCollection) -> Option<f64> {
+    atom.read(urids.double, ())
+        .or_else(|| atom.read(urids.float, ()).map(f64::from))
+        .or_else(|| atom.read(urids.long, ()).map(|value| value as f64))
+        .or_else(|| atom.read(urids.int, ()).map(f64::from))
+}
+
+/// The properties of a `time:Position` object.
+///
+/// Hosts send position objects to plugins via atom ports whenever the transport changes, for example when it's started, stopped or relocated, or when the tempo changes. Every property is optional since hosts only send the properties they know or that have changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PositionUpdate {
+    /// The frame of the position.
+    pub frame: Option<i64>,
+    /// The rate of the progress of time as a fraction of normal speed.
+    pub speed: Option<f32>,
+    /// The bar number.
+    pub bar: Option<i64>,
+    /// The beat within the bar, from 0 to beats per bar.
+    pub bar_beat: Option<f32>,
+    /// The global running beat number.
+    pub beat: Option<f64>,
+    /// The note value that counts as one beat, the bottom number of the time signature.
+    pub beat_unit: Option<u32>,
+    /// The number of beats per bar, the top number of the time signature.
+    pub beats_per_bar: Option<f32>,
+    /// The tempo in beats per minute.
+    pub beats_per_minute: Option<f32>,
+}
+
+impl PositionUpdate {
+    /// Try to read a position update from an atom.
+    ///
+    /// This method returns `None` if the atom isn't an object or blank with the `time:Position` type. Properties with unexpected, non-numeric types are ignored.
+    pub fn read(
+        atom: UnidentifiedAtom,
+        atom_urids: &AtomURIDCollection,
+        time_urids: &TimeURIDCollection,
+    ) -> Option<Self> {
+        let (header, reader) = atom
+            .read(atom_urids.object, ())
+            .or_else(|| atom.read(atom_urids.blank, ()))?;
+        if header.otype != time_urids.position_class {
+            return None;
+        }
+
+        let mut update = Self::default();
+        for (property_header, property) in reader {
+            let key = property_header.key;
+            let value = if let Some(value) = read_number(property, atom_urids) {
+                value
+            } else {
+                continue;
+            };
+
+            if key == time_urids.frame {
+                update.frame = Some(value as i64);
+            } else if key == time_urids.speed {
+                update.speed = Some(value as f32);
+            } else if key == time_urids.bar {
+                update.bar = Some(value as i64);
+            } else if key == time_urids.bar_beat {
+                update.bar_beat = Some(value as f32);
+            } else if key == time_urids.beat {
+                update.beat = Some(value);
+            } else if key == time_urids.beat_unit {
+                update.beat_unit = Some(value as u32);
+            } else if key == time_urids.beats_per_bar {
+                update.beats_per_bar = Some(value as f32);
+            } else if key == time_urids.beats_per_minute {
+                update.beats_per_minute = Some(value as f32);
+            }
+        }
+        Some(update)
+    }
+
+    /// Write all present properties to an object writer.
+    ///
+    /// The object should have been initialized with the `time:Position` type. Frames and bars are written as longs, beats as doubles, beat units as ints and all other properties as floats.
+    ///
+    /// This method returns `None` if the object writer ran out of space.
+    pub fn write(
+        &self,
+        writer: &mut ObjectWriter,
+        atom_urids: &AtomURIDCollection,
+        time_urids: &TimeURIDCollection,
+    ) -> Option<()> {
+        if let Some(frame) = self.frame {
+            writer.init(time_urids.frame, None, atom_urids.long, frame)?;
+        }
+        if let Some(speed) = self.speed {
+            writer.init(time_urids.speed, None, atom_urids.float, speed)?;
+        }
+        if let Some(bar) = self.bar {
+            writer.init(time_urids.bar, None, atom_urids.long, bar)?;
+        }
+        if let Some(bar_beat) = self.bar_beat {
+            writer.init(time_urids.bar_beat, None, atom_urids.float, bar_beat)?;
+        }
+        if let Some(beat) = self.beat {
+            writer.init(time_urids.beat, None, atom_urids.double, beat)?;
+        }
+        if let Some(beat_unit) = self.beat_unit {
+            writer.init(time_urids.beat_unit, None, atom_urids.int, beat_unit as i32)?;
+        }
+        if let Some(beats_per_bar) = self.beats_per_bar {
+            writer.init(
+                time_urids.beats_per_bar,
+                None,
+                atom_urids.float,
+                beats_per_bar,
+            )?;
+        }
+        if let Some(beats_per_minute) = self.beats_per_minute {
+            writer.init(
+                time_urids.beats_per_minute,
+                None,
+                atom_urids.float,
+                beats_per_minute,
+            )?;
+        }
+        Some(())
+    }
+}
+
+/// Methods to track the [`TransportState`](struct.TransportState.html) of the host with position updates.
+///
+/// The state itself is defined by `lv2-core`, since in-process hosts may pass it to plugins with the run context. Plugins that are loaded via the C interface keep an instance of it and update it with the position updates they receive. Between updates, the state can be moved forward with [`advance`](#tymethod.advance) to keep track of the position within a block.
+pub trait TransportStateExt {
+    /// Apply a position update to the state.
+    ///
+    /// Only the properties that are present in the update are changed.
+    fn update(&mut self, update: &PositionUpdate);
+
+    /// Try to read a position update from an atom and apply it to the state.
+    ///
+    /// Returns `true` if the atom was a position object and the state has been updated.
+    fn update_from_atom(
+        &mut self,
+        atom: UnidentifiedAtom,
+        atom_urids: &AtomURIDCollection,
+        time_urids: &TimeURIDCollection,
+    ) -> bool;
+
+    /// Return the beat time at the given frame time of the current block.
+    ///
+    /// The state has to describe the position at the start of the block. The beat time is moved according to the current speed and tempo, which means that it doesn't change if the transport is stopped.
+    fn beat_time_at(&self, frame: FrameTime, sample_rate: f64) -> BeatTime;
+
+    /// Return the frame time of the current block at which the transport reaches the given beat time.
+    ///
+    /// The state has to describe the position at the start of the block. `None` is returned if the transport is stopped or if the beat time has already been passed.
+    fn frame_time_of(&self, beat: BeatTime, sample_rate: f64) -> Option<FrameTime>;
+
+    /// Move the state forward by the given number of frames.
+    ///
+    /// The frame, beat, bar beat and bar are moved according to the current speed and tempo. If the transport is stopped, nothing changes.
+    fn advance(&mut self, frames: u32, sample_rate: f64);
+}
+
+impl TransportStateExt for TransportState {
+    fn update(&mut self, update: &PositionUpdate) {
+        if let Some(frame) = update.frame {
+            self.frame = frame;
+        }
+        if let Some(speed) = update.speed {
+            self.speed = speed;
+        }
+        if let Some(bar) = update.bar {
+            self.bar = bar;
+        }
+        if let Some(bar_beat) = update.bar_beat {
+            self.bar_beat = bar_beat;
+        }
+        if let Some(beat) = update.beat {
+            self.beat = beat;
+        }
+        if let Some(beat_unit) = update.beat_unit {
+            self.beat_unit = beat_unit;
+        }
+        if let Some(beats_per_bar) = update.beats_per_bar {
+            self.beats_per_bar = beats_per_bar;
+        }
+        if let Some(beats_per_minute) = update.beats_per_minute {
+            self.beats_per_minute = beats_per_minute;
+        }
+    }
+
+    fn update_from_atom(
+        &mut self,
+        atom: UnidentifiedAtom,
+        atom_urids: &AtomURIDCollection,
+        time_urids: &TimeURIDCollection,
+    ) -> bool {
+        if let Some(update) = PositionUpdate::read(atom, atom_urids, time_urids) {
+            self.update(&update);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn beat_time_at(&self, frame: FrameTime, sample_rate: f64) -> BeatTime {
+        let frames_per_beat = self.frames_per_beat(sample_rate) / f64::from(self.speed);
+        BeatTime(self.beat + frame.to_beats(frames_per_beat).0)
+    }
+
+    fn frame_time_of(&self, beat: BeatTime, sample_rate: f64) -> Option<FrameTime> {
+        if !self.is_rolling() {
+            return None;
+        }
+        let frames_per_beat = self.frames_per_beat(sample_rate) / f64::from(self.speed);
+        beat.checked_sub(self.beat)?
+            .to_frames(frames_per_beat)
+            .filter(|frame| frame.0 >= 0)
+    }
+
+    fn advance(&mut self, frames: u32, sample_rate: f64) {
+        if !self.is_rolling() {
+            return;
+        }
+
+        let speed = f64::from(self.speed);
+        self.frame += (f64::from(frames) * speed) as i64;
+
+        let beats = f64::from(frames) * speed / self.frames_per_beat(sample_rate);
+        self.beat += beats;
+
+        let beats_per_bar = f64::from(self.beats_per_bar);
+        if beats_per_bar > 0.0 {
+            let bar_beat = f64::from(self.bar_beat) + beats;
+            let bars = (bar_beat / beats_per_bar).floor();
+            self.bar += bars as i64;
+            self.bar_beat = (bar_beat - bars * beats_per_bar) as f32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use atom::object::ObjectHeader;
+    use atom::prelude::*;
+    use atom::space::*;
+
+    #[test]
+    fn test_position_update() {
+        let map = HashURIDMapper::new();
+        let atom_urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let time_urids: TimeURIDCollection = map.populate_collection().unwrap();
+
+        let update = PositionUpdate {
+            frame: Some(44100),
+            speed: Some(1.0),
+            bar: Some(2),
+            bar_beat: Some(1.5),
+            beat: Some(9.5),
+            beat_unit: Some(4),
+            beats_per_bar: Some(4.0),
+            beats_per_minute: Some(140.0),
+        };
+
+        let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+        {
+            let mut space = RootMutSpace::new(raw_space.as_mut());
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(
+                    atom_urids.object,
+                    ObjectHeader {
+                        id: None,
+                        otype: time_urids.position_class.into_general(),
+                    },
+                )
+                .unwrap();
+            update.write(&mut writer, &atom_urids, &time_urids).unwrap();
+        }
+
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        assert_eq!(
+            update,
+            PositionUpdate::read(atom, &atom_urids, &time_urids).unwrap()
+        );
+
+        let mut state = TransportState::default();
+        assert!(!state.is_rolling());
+        assert!(state.update_from_atom(atom, &atom_urids, &time_urids));
+        assert!(state.is_rolling());
+        assert_eq!(state.beats_per_minute, 140.0);
+        assert_eq!(state.bar, 2);
+    }
+
+    #[test]
+    fn test_partial_update() {
+        let map = HashURIDMapper::new();
+        let atom_urids: AtomURIDCollection = map.populate_collection().unwrap();
+        let time_urids: TimeURIDCollection = map.populate_collection().unwrap();
+
+        // Hosts may use other numeric types than the ones written by `PositionUpdate::write`.
+        let mut raw_space: Box<[u8]> = Box::new([0; 256]);
+        {
+            let mut space = RootMutSpace::new(raw_space.as_mut());
+            let mut writer = (&mut space as &mut dyn MutSpace)
+                .init(
+                    atom_urids.blank,
+                    ObjectHeader {
+                        id: None,
+                        otype: time_urids.position_class.into_general(),
+                    },
+                )
+                .unwrap();
+            writer
+                .init(time_urids.beats_per_minute, None, atom_urids.double, 90.0)
+                .unwrap();
+            writer
+                .init(time_urids.speed, None, atom_urids.int, 0)
+                .unwrap();
+        }
+
+        let atom = UnidentifiedAtom::new(Space::from_slice(raw_space.as_ref()));
+        let mut state = TransportState {
+            speed: 1.0,
+            ..Default::default()
+        };
+        assert!(state.update_from_atom(atom, &atom_urids, &time_urids));
+        assert_eq!(
+            TransportState {
+                speed: 0.0,
+                beats_per_minute: 90.0,
+                ..Default::default()
+            },
+            state
+        );
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut state = TransportState {
+            speed: 1.0,
+            bar_beat: 3.0,
+            beat: 3.0,
+            ..Default::default()
+        };
+
+        // At 120 BPM and 48 kHz, a beat lasts 24000 frames.
+        assert_eq!(state.frames_per_beat(48000.0), 24000.0);
+        state.advance(36000, 48000.0);
+        assert_eq!(state.frame, 36000);
+        assert_eq!(state.beat, 4.5);
+        assert_eq!(state.bar, 1);
+        assert_eq!(state.bar_beat, 0.5);
+
+        // A stopped transport doesn't move.
+        state.speed = 0.0;
+        state.advance(36000, 48000.0);
+        assert_eq!(state.frame, 36000);
+    }
+
+    #[test]
+    fn test_time_conversion() {
+        let mut state = TransportState {
+            speed: 1.0,
+            beat: 3.0,
+            ..Default::default()
+        };
+
+        // At 120 BPM and 48 kHz, a beat lasts 24000 frames.
+        assert_eq!(state.beat_time_at(FrameTime(12000), 48000.0), BeatTime(3.5));
+        assert_eq!(
+            state.frame_time_of(BeatTime(4.0), 48000.0),
+            Some(FrameTime(24000))
+        );
+        assert_eq!(state.frame_time_of(BeatTime(2.0), 48000.0), None);
+
+        // At double speed, beats are reached twice as fast.
+        state.speed = 2.0;
+        assert_eq!(
+            state.frame_time_of(BeatTime(4.0), 48000.0),
+            Some(FrameTime(12000))
+        );
+
+        // A stopped transport never reaches a beat.
+        state.speed = 0.0;
+        assert_eq!(state.frame_time_of(BeatTime(4.0), 48000.0), None);
+    }
+}
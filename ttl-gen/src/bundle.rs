@@ -0,0 +1,98 @@
+use crate::{prefix_declarations, write_file_iri, PluginDescription, UIDescription};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Return the file name of the plugin library that is built for the crate with the given name.
+///
+/// Cargo replaces the dashes of the crate name with underscores and adds the platform-specific prefix and suffix, for example `libmy_plugin.so` on Linux and `my_plugin.dll` on Windows.
+pub fn binary_name(crate_name: &str) -> String {
+    format!(
+        "{}{}{}",
+        std::env::consts::DLL_PREFIX,
+        crate_name.replace('-', "_"),
+        std::env::consts::DLL_SUFFIX
+    )
+}
+
+/// The description of a bundle and its plugins.
+///
+/// All plugins of a bundle live in the same binary, while UIs usually live in binaries of their own. Every plugin and UI is described in a file of its own, which is referenced by the manifest.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BundleDescription {
+    binary: String,
+    plugins: Vec<(PluginDescription, String)>,
+    uis: Vec<(UIDescription, String, String)>,
+}
+
+impl BundleDescription {
+    /// Create the description of a bundle with the given binary.
+    ///
+    /// The binary name is relative to the bundle. You can use [`binary_name`](fn.binary_name.html) to get the name of the library Cargo builds.
+    pub fn new(binary: &str) -> Self {
+        Self {
+            binary: binary.to_owned(),
+            plugins: Vec::new(),
+            uis: Vec::new(),
+        }
+    }
+
+    /// Add a plugin which is described in the file with the given name.
+    pub fn plugin(mut self, plugin: PluginDescription, file_name: &str) -> Self {
+        self.plugins.push((plugin, file_name.to_owned()));
+        self
+    }
+
+    /// Add a UI which lives in the given binary and is described in the file with the given name.
+    ///
+    /// The binary name is relative to the bundle, just like the binary of the plugins.
+    pub fn ui(mut self, ui: UIDescription, binary: &str, file_name: &str) -> Self {
+        self.uis.push((ui, binary.to_owned(), file_name.to_owned()));
+        self
+    }
+
+    /// The UIs of the bundle and the names of their files.
+    pub fn uis(&self) -> impl Iterator<Item = (&UIDescription, &str)> {
+        self.uis
+            .iter()
+            .map(|(ui, _, file_name)| (ui, file_name.as_ref()))
+    }
+
+    /// The plugins of the bundle and the names of their files.
+    pub fn plugins(&self) -> impl Iterator<Item = (&PluginDescription, &str)> {
+        self.plugins
+            .iter()
+            .map(|(plugin, file_name)| (plugin, file_name.as_ref()))
+    }
+
+    /// Create the content of the bundle's `manifest.ttl`.
+    pub fn manifest(&self) -> String {
+        let binary = write_file_iri(&self.binary);
+        let mut manifest = prefix_declarations();
+        for (plugin, file_name) in self.plugins.iter() {
+            manifest.push('\n');
+            manifest.push_str(&plugin.manifest_entry(&binary, &write_file_iri(file_name)));
+        }
+        for (ui, binary, file_name) in self.uis.iter() {
+            manifest.push('\n');
+            manifest
+                .push_str(&ui.manifest_entry(&write_file_iri(binary), &write_file_iri(file_name)));
+        }
+        manifest
+    }
+
+    /// Write the manifest and the descriptions of the plugins and UIs to the bundle at the given path.
+    ///
+    /// The directory of the bundle is created if it doesn't exist yet. Existing files are overwritten.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)?;
+        fs::write(path.join("manifest.ttl"), self.manifest())?;
+        for (plugin, file_name) in self.plugins.iter() {
+            fs::write(path.join(file_name), plugin.to_turtle())?;
+        }
+        for (ui, _, file_name) in self.uis.iter() {
+            fs::write(path.join(file_name), ui.to_turtle())?;
+        }
+        Ok(())
+    }
+}
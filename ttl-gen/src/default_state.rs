@@ -0,0 +1,135 @@
+use crate::write_iri;
+use parameters::ParameterCollection;
+use presets::turtle::Term;
+use presets::{PresetBuilder, PresetError};
+use state::{State, Storage};
+use urid::{Map, Unmap};
+
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_FLOAT: &str = "http://www.w3.org/2001/XMLSchema#float";
+const XSD_INT: &str = "http://www.w3.org/2001/XMLSchema#int";
+const XSD_LONG: &str = "http://www.w3.org/2001/XMLSchema#long";
+
+/// The default state of a plugin, which is written to the plugin's description as `state:state`.
+///
+/// Hosts that support default states restore it right after they've instantiated the plugin, which gives every new instance the same, sensible configuration. The properties of the state are usually the defaults of the plugin's [parameters](../lv2_parameters/trait.ParameterCollection.html), but they can also be taken from a live instance, for example after a sound designer has set it up, and are then converted like the state of a [preset](../lv2_presets/struct.PresetBuilder.html#method.state).
+///
+/// ```
+/// use lv2_parameters::prelude::*;
+/// use lv2_ttl_gen::*;
+/// use urid::*;
+///
+/// #[derive(ParameterCollection)]
+/// struct AmpParameters {
+///     #[parameter(uri = "urn:lv2_ttl_gen:amp#gain", default = -6.0, min = -90.0, max = 24.0)]
+///     gain: f32,
+///     #[parameter(uri = "urn:lv2_ttl_gen:amp#oversampling", default = 2)]
+///     oversampling: i32,
+/// }
+///
+/// let state = DefaultState::from_parameters::<AmpParameters>();
+/// let (key, value) = state.properties().next().unwrap();
+/// assert_eq!(key, "urn:lv2_ttl_gen:amp#gain");
+/// assert_eq!(value.as_literal(), Some("-6"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DefaultState {
+    properties: Vec<(String, Term)>,
+}
+
+impl DefaultState {
+    /// Create an empty state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a state with the default values of the given parameters, in declaration order.
+    pub fn from_parameters<P: ParameterCollection>() -> Self {
+        let properties = P::infos()
+            .iter()
+            .filter_map(|info| {
+                let value = info.default();
+                let value_type = info.value_type().to_bytes_with_nul();
+                let term = if value_type == sys::LV2_ATOM__Float {
+                    Term::typed(&(value as f32).to_string(), XSD_FLOAT)
+                } else if value_type == sys::LV2_ATOM__Double {
+                    Term::typed(&value.to_string(), XSD_DOUBLE)
+                } else if value_type == sys::LV2_ATOM__Int {
+                    Term::typed(&(value as i32).to_string(), XSD_INT)
+                } else if value_type == sys::LV2_ATOM__Long {
+                    Term::typed(&(value as i64).to_string(), XSD_LONG)
+                } else {
+                    return None;
+                };
+                Some((info.uri().to_str().unwrap().to_owned(), term))
+            })
+            .collect();
+        Self { properties }
+    }
+
+    /// Create a state from the current state of a plugin instance.
+    ///
+    /// The state is saved with the plugin's [`save`](../lv2_state/trait.State.html#tymethod.save) method and the stored atoms are converted to literals, which fails if the plugin stores atoms of other types than scalars, strings, paths and URIDs.
+    pub fn from_instance<P: State, M: Map, U: Unmap>(
+        plugin: &P,
+        features: P::StateFeatures,
+        map: &M,
+        unmap: &U,
+    ) -> Result<Self, PresetError> {
+        let mut storage = Storage::default();
+        plugin
+            .save(storage.store_handle(), features)
+            .map_err(PresetError::State)?;
+        Self::from_storage(&storage, map, unmap)
+    }
+
+    /// Create a state from the properties of a state storage, like the state of an instance in a host.
+    ///
+    /// The properties are converted like in [`from_instance`](#method.from_instance) and sorted by their keys.
+    pub fn from_storage<M: Map, U: Unmap>(
+        storage: &Storage,
+        map: &M,
+        unmap: &U,
+    ) -> Result<Self, PresetError> {
+        let preset = PresetBuilder::new("", "")
+            .storage(storage, map, unmap)?
+            .build();
+        Ok(Self {
+            properties: preset
+                .state_properties()
+                .map(|(key, value)| (key.to_owned(), value.clone()))
+                .collect(),
+        })
+    }
+
+    /// Set a property of the state, replacing a property with the same key.
+    pub fn property(mut self, key: &str, value: Term) -> Self {
+        match self.properties.iter_mut().find(|(known, _)| known == key) {
+            Some((_, known)) => *known = value,
+            None => self.properties.push((key.to_owned(), value)),
+        }
+        self
+    }
+
+    /// The properties of the state, as pairs of the key URI and the value.
+    pub fn properties(&self) -> impl Iterator<Item = (&str, &Term)> {
+        self.properties
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Check whether the state has no properties.
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    /// Write the state as the object of a `state:state` statement.
+    pub(crate) fn to_turtle(&self) -> String {
+        let properties: Vec<String> = self
+            .properties
+            .iter()
+            .map(|(key, value)| format!("        {} {}", write_iri(key), value))
+            .collect();
+        format!("[\n{}\n    ]", properties.join(" ;\n"))
+    }
+}
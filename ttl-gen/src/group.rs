@@ -0,0 +1,111 @@
+use crate::{write_iri, write_literal};
+use core::port::{PortDirection, PortGroupInfo};
+use urid::uri_str;
+
+/// The description of a port group of a plugin.
+///
+/// Port groups are declared with the `port_group` attribute of a port collection. The name of a group defaults to its symbol with the underscores replaced by spaces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortGroupDescription {
+    uri: String,
+    symbol: String,
+    name: String,
+    kind: String,
+    direction: Option<PortDirection>,
+    side_chain_of: Option<String>,
+}
+
+impl PortGroupDescription {
+    /// Create the description of a group of the given plugin, as declared by one of its ports.
+    pub(crate) fn new(
+        plugin_uri: &str,
+        info: &PortGroupInfo,
+        direction: Option<PortDirection>,
+    ) -> Self {
+        Self {
+            uri: group_uri(plugin_uri, info.symbol),
+            symbol: info.symbol.to_owned(),
+            name: symbol_name(info.symbol),
+            kind: info.kind.uri().to_str().unwrap().to_owned(),
+            direction,
+            side_chain_of: info
+                .side_chain_of
+                .map(|symbol| group_uri(plugin_uri, symbol)),
+        }
+    }
+
+    /// Update the direction of the group with the direction of another port.
+    ///
+    /// A group only has a direction if all of its ports have the same direction.
+    pub(crate) fn add_port_direction(&mut self, direction: Option<PortDirection>) {
+        if self.direction != direction {
+            self.direction = None;
+        }
+    }
+
+    /// The URI of the group.
+    ///
+    /// It's created by appending the symbol of the group as a fragment to the plugin's URI.
+    pub fn uri(&self) -> &str {
+        self.uri.as_ref()
+    }
+
+    /// The symbol of the group.
+    pub fn symbol(&self) -> &str {
+        self.symbol.as_ref()
+    }
+
+    /// The human-readable name of the group.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Set the human-readable name of the group.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_owned();
+    }
+
+    /// Create the description of the group as a resource of its own.
+    pub(crate) fn to_turtle(&self) -> String {
+        let mut classes: Vec<String> = Vec::new();
+        match self.direction {
+            Some(PortDirection::Input) => {
+                classes.push(write_iri(uri_str(sys::LV2_PORT_GROUPS__InputGroup)));
+            }
+            Some(PortDirection::Output) => {
+                classes.push(write_iri(uri_str(sys::LV2_PORT_GROUPS__OutputGroup)));
+            }
+            None => (),
+        }
+        classes.push(write_iri(&self.kind));
+
+        let mut statements = vec![
+            format!("a {}", classes.join(" , ")),
+            format!("lv2:symbol {}", write_literal(&self.symbol)),
+            format!("lv2:name {}", write_literal(&self.name)),
+        ];
+        if let Some(side_chain_of) = self.side_chain_of.as_ref() {
+            statements.push(format!("pg:sideChainOf {}", write_iri(side_chain_of)));
+        }
+
+        format!("<{}>\n    {} .\n", self.uri, statements.join(" ;\n    "))
+    }
+}
+
+/// Create the URI of a port group of a plugin.
+pub(crate) fn group_uri(plugin_uri: &str, symbol: &str) -> String {
+    if plugin_uri.contains('#') {
+        format!("{}_{}", plugin_uri, symbol)
+    } else {
+        format!("{}#{}", plugin_uri, symbol)
+    }
+}
+
+/// Create a human-readable name from a symbol, like "Audio input" from `audio_input`.
+pub(crate) fn symbol_name(symbol: &str) -> String {
+    let name = symbol.replace('_', " ").trim().to_owned();
+    match name.get(..1) {
+        Some(first) => first.to_uppercase() + &name[1..],
+        None => name,
+    }
+}
@@ -0,0 +1,249 @@
+//! Generation of the Turtle metadata of LV2 plugins.
+//!
+//! Every LV2 bundle contains a `manifest.ttl` file, which lists the plugins of the bundle, and a description of every plugin, which declares its ports, the host features it needs and the extensions it provides. Writing these files by hand is error-prone, since they have to match the plugin's code exactly: A port collection that doesn't match the declared ports leads to undefined behaviour.
+//!
+//! This crate creates these files from the Rust types of a plugin instead:
+//!
+//! * The plugin URI is taken from the plugin's [`UriBound`](../urid/trait.UriBound.html) implementation.
+//! * The ports are taken from the derived [`PortCollection`](../lv2_core/port/trait.PortCollection.html), in the order of their indices. The symbol of a port is the name of its field and the unit of a control port is taken from its value type, like `Control<Db>`. Port groups are taken from the `port_group` attributes of the fields.
+//! * The required and optional features are taken from the plugin's [`FeatureCollection`s](../lv2_core/feature/trait.FeatureCollection.html).
+//! * The classes of the plugin are taken from its [`CLASSES`](../lv2_core/plugin/trait.Plugin.html#associatedconstant.CLASSES) constant.
+//! * The version of the plugin and the plugins it replaces are taken from its [`VERSION`](../lv2_core/plugin/trait.Plugin.html#associatedconstant.VERSION) and [`REPLACES`](../lv2_core/plugin/trait.Plugin.html#associatedconstant.REPLACES) constants, which are declared with the [`plugin_version`](../lv2_core/plugin/attr.plugin_version.html) attribute.
+//! * The provided extensions are found by querying the plugin's [`extension_data`](../lv2_core/plugin/trait.Plugin.html#method.extension_data) method and reading its [`EXTENSIONS`](../lv2_core/plugin/trait.Plugin.html#associatedconstant.EXTENSIONS) table.
+//!
+//! UIs are described in the same way with a [`UIDescription`](struct.UIDescription.html), which takes the features of the UI from its [`PluginUI`](../lv2_ui/trait.PluginUI.html) implementation.
+//!
+//! Plugins with [parameters](../lv2_parameters/index.html) can declare a [`DefaultState`](struct.DefaultState.html) with the defaults of their parameters, which hosts restore when they instantiate the plugin.
+//!
+//! Information that can't be derived from the code, like the names and ranges of ports, is added to the [`PluginDescription`](struct.PluginDescription.html) before the bundle is written.
+//!
+//! Since the description is created from the compiled plugin types, the generator has to be run by code that is linked with the plugin, like an example or a test of the plugin crate. A build script of the plugin crate can't do this, because it's built before the plugin itself.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_ttl_gen::*;
+//! use urid::*;
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     gain: InputPort<Control>,
+//!     input: InputPort<Audio>,
+//!     output: OutputPort<Audio>,
+//! }
+//!
+//! #[uri("urn:lv2_ttl_gen:amp")]
+//! struct Amp;
+//!
+//! impl Plugin for Amp {
+//!     type Ports = Ports;
+//!     type InitFeatures = ();
+//!     type AudioFeatures = ();
+//!
+//!     fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+//!         Some(Self)
+//!     }
+//!
+//!     fn run(&mut self, ports: &mut Ports, _: &mut RunContext<()>) {
+//!         let coef = *ports.gain;
+//!         for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+//!             *output = input * coef;
+//!         }
+//!     }
+//! }
+//!
+//! // This would usually be the `main` function of an example, like `examples/ttl.rs`.
+//! let bundle_path = std::env::temp_dir().join("lv2_ttl_gen_example.lv2");
+//!
+//! let amp = PluginDescription::new::<Amp>()
+//!     .name("Simple Amplifier")
+//!     .class(plugin_class::AMPLIFIER)
+//!     .port_range("gain", -90.0, 0.0, 24.0);
+//!
+//! BundleDescription::new(&binary_name("amp"))
+//!     .plugin(amp, "amp.ttl")
+//!     .write(&bundle_path)
+//!     .unwrap();
+//!
+//! let description = std::fs::read_to_string(bundle_path.join("amp.ttl")).unwrap();
+//! assert!(description.contains("lv2:symbol \"gain\""));
+//! # std::fs::remove_dir_all(&bundle_path).unwrap();
+//! ```
+extern crate lv2_core as core;
+extern crate lv2_parameters as parameters;
+extern crate lv2_presets as presets;
+extern crate lv2_state as state;
+extern crate lv2_sys as sys;
+extern crate lv2_ui as ui;
+
+mod bundle;
+pub use bundle::*;
+
+mod default_state;
+pub use default_state::DefaultState;
+
+mod group;
+pub use group::PortGroupDescription;
+
+mod plugin;
+pub use plugin::*;
+
+mod port;
+pub use port::*;
+
+mod ui_description;
+pub use ui_description::*;
+
+use core::feature::FeatureInfo;
+
+/// URIs of common plugin classes.
+///
+/// A plugin is always an `lv2:Plugin`, but it may also declare one of the more specific classes of the LV2 core specification with [`PluginDescription::class`](../struct.PluginDescription.html#method.class). Classes that are known in the code should rather be declared with the [`CLASSES`](../../lv2_core/plugin/trait.Plugin.html#associatedconstant.CLASSES) constant of the plugin, where hosts and other tools can find them too.
+pub mod plugin_class {
+    pub const AMPLIFIER: &str = "http://lv2plug.in/ns/lv2core#AmplifierPlugin";
+    pub const ANALYSER: &str = "http://lv2plug.in/ns/lv2core#AnalyserPlugin";
+    pub const DELAY: &str = "http://lv2plug.in/ns/lv2core#DelayPlugin";
+    pub const DISTORTION: &str = "http://lv2plug.in/ns/lv2core#DistortionPlugin";
+    pub const DYNAMICS: &str = "http://lv2plug.in/ns/lv2core#DynamicsPlugin";
+    pub const EQ: &str = "http://lv2plug.in/ns/lv2core#EQPlugin";
+    pub const FILTER: &str = "http://lv2plug.in/ns/lv2core#FilterPlugin";
+    pub const GENERATOR: &str = "http://lv2plug.in/ns/lv2core#GeneratorPlugin";
+    pub const INSTRUMENT: &str = "http://lv2plug.in/ns/lv2core#InstrumentPlugin";
+    pub const MIXER: &str = "http://lv2plug.in/ns/lv2core#MixerPlugin";
+    pub const MODULATOR: &str = "http://lv2plug.in/ns/lv2core#ModulatorPlugin";
+    pub const OSCILLATOR: &str = "http://lv2plug.in/ns/lv2core#OscillatorPlugin";
+    pub const REVERB: &str = "http://lv2plug.in/ns/lv2core#ReverbPlugin";
+    pub const SIMULATOR: &str = "http://lv2plug.in/ns/lv2core#SimulatorPlugin";
+    pub const SPATIAL: &str = "http://lv2plug.in/ns/lv2core#SpatialPlugin";
+    pub const UTILITY: &str = "http://lv2plug.in/ns/lv2core#UtilityPlugin";
+}
+
+/// URIs of the UI types.
+///
+/// The type of a UI tells the host which kind of widget it creates and is set with [`UIDescription::new`](../struct.UIDescription.html#method.new).
+pub mod ui_class {
+    pub const COCOA: &str = "http://lv2plug.in/ns/extensions/ui#CocoaUI";
+    pub const GTK: &str = "http://lv2plug.in/ns/extensions/ui#GtkUI";
+    pub const GTK3: &str = "http://lv2plug.in/ns/extensions/ui#Gtk3UI";
+    pub const QT5: &str = "http://lv2plug.in/ns/extensions/ui#Qt5UI";
+    pub const WINDOWS: &str = "http://lv2plug.in/ns/extensions/ui#WindowsUI";
+    pub const X11: &str = "http://lv2plug.in/ns/extensions/ui#X11UI";
+}
+
+/// The prefixes that are used to abbreviate IRIs in the generated files.
+const PREFIXES: &[(&str, &str)] = &[
+    ("atom", "http://lv2plug.in/ns/ext/atom#"),
+    ("dc", "http://purl.org/dc/terms/"),
+    ("doap", "http://usefulinc.com/ns/doap#"),
+    ("lv2", "http://lv2plug.in/ns/lv2core#"),
+    ("morph", "http://lv2plug.in/ns/ext/morph#"),
+    ("param", "http://lv2plug.in/ns/ext/parameters#"),
+    ("pg", "http://lv2plug.in/ns/ext/port-groups#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("rsz", "http://lv2plug.in/ns/ext/resize-port#"),
+    ("ui", "http://lv2plug.in/ns/extensions/ui#"),
+    ("units", "http://lv2plug.in/ns/extensions/units#"),
+];
+
+/// Return the Turtle prefix declarations of all known prefixes.
+pub(crate) fn prefix_declarations() -> String {
+    let mut declarations = String::new();
+    for (prefix, namespace) in PREFIXES {
+        declarations.push_str(&format!("@prefix {}: <{}> .\n", prefix, namespace));
+    }
+    declarations
+}
+
+/// Write an IRI in Turtle syntax, abbreviated with a known prefix if possible.
+pub(crate) fn write_iri(iri: &str) -> String {
+    for (prefix, namespace) in PREFIXES {
+        if let Some(name) = iri.strip_prefix(namespace) {
+            if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return format!("{}:{}", prefix, name);
+            }
+        }
+    }
+    format!("<{}>", iri)
+}
+
+/// Write a string literal in Turtle syntax.
+pub(crate) fn write_literal(value: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Write a relative IRI reference to a file of the bundle.
+pub(crate) fn write_file_iri(file_name: &str) -> String {
+    let mut encoded = String::from("<");
+    for byte in file_name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/!$&'()*+,;=:@".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded.push('>');
+    encoded
+}
+
+/// Add a feature to the lists of required and optional features, unless it's already known.
+///
+/// A feature that is required by one collection is required, even if another collection declares it as optional.
+pub(crate) fn add_feature(
+    required: &mut Vec<String>,
+    optional: &mut Vec<String>,
+    info: &FeatureInfo,
+) {
+    let uri = info.uri.to_str().unwrap().to_owned();
+    if required.contains(&uri) {
+        return;
+    }
+    if info.optional {
+        if !optional.contains(&uri) {
+            optional.push(uri);
+        }
+    } else {
+        optional.retain(|feature| *feature != uri);
+        required.push(uri);
+    }
+}
+
+/// Prelude of `lv2_ttl_gen` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        binary_name, BundleDescription, PluginDescription, PortDescription, PortGroupDescription,
+        UIDescription,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_turtle_terms() {
+        assert_eq!(
+            write_iri("http://lv2plug.in/ns/lv2core#InputPort"),
+            "lv2:InputPort"
+        );
+        assert_eq!(
+            write_iri("http://lv2plug.in/ns/ext/urid#map"),
+            "<http://lv2plug.in/ns/ext/urid#map>"
+        );
+        assert_eq!(write_iri("urn:a:b"), "<urn:a:b>");
+        assert_eq!(write_literal("a \"b\"\n"), "\"a \\\"b\\\"\\n\"");
+        assert_eq!(write_file_iri("my amp.ttl"), "<my%20amp.ttl>");
+    }
+}
@@ -0,0 +1,408 @@
+use crate::{
+    add_feature, prefix_declarations, write_iri, write_literal, DefaultState, PortDescription,
+    PortGroupDescription,
+};
+use core::feature::{FeatureCollection, FeatureInfo};
+use core::plugin::{Plugin, PluginVersion};
+use core::port::PortCollection;
+use presets::turtle::Term;
+use urid::{uri_str, Uri};
+
+/// The extensions that are looked up in the plugin's `extension_data` method.
+const KNOWN_EXTENSIONS: &[&[u8]] = &[
+    sys::LV2_OPTIONS__interface,
+    sys::LV2_STATE__interface,
+    sys::LV2_WORKER__interface,
+];
+
+/// The description of a plugin, which is written to the plugin's Turtle file.
+///
+/// A description is created from the plugin type with [`new`](#method.new) and can then be extended with information that isn't part of the code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluginDescription {
+    uri: String,
+    name: String,
+    classes: Vec<String>,
+    license: Option<String>,
+    version: Option<PluginVersion>,
+    replaces: Vec<String>,
+    ports: Vec<PortDescription>,
+    groups: Vec<PortGroupDescription>,
+    main_input: Option<String>,
+    main_output: Option<String>,
+    required_features: Vec<String>,
+    optional_features: Vec<String>,
+    extensions: Vec<String>,
+    uis: Vec<String>,
+    default_state: DefaultState,
+}
+
+impl PluginDescription {
+    /// Create the description of a plugin type.
+    ///
+    /// The name of the plugin defaults to the name of its type.
+    pub fn new<P: Plugin>() -> Self {
+        let type_name = std::any::type_name::<P>();
+        let uri = P::uri().to_str().unwrap();
+        let port_info = P::Ports::port_info();
+
+        let mut groups: Vec<PortGroupDescription> = Vec::new();
+        for (info, group) in port_info
+            .iter()
+            .filter_map(|info| info.group.as_ref().map(|group| (info, group)))
+        {
+            match groups
+                .iter_mut()
+                .find(|known| known.symbol() == group.symbol)
+            {
+                Some(known) => known.add_port_direction(info.direction),
+                None => groups.push(PortGroupDescription::new(uri, group, info.direction)),
+            }
+        }
+
+        let mut description = Self {
+            uri: uri.to_owned(),
+            name: type_name
+                .rsplit("::")
+                .next()
+                .unwrap_or(type_name)
+                .to_owned(),
+            classes: P::CLASSES
+                .iter()
+                .map(|class| class.uri().to_str().unwrap().to_owned())
+                .collect(),
+            license: None,
+            version: P::VERSION,
+            replaces: P::REPLACES
+                .iter()
+                .map(|uri| uri_str(uri).to_owned())
+                .collect(),
+            ports: port_info.iter().map(PortDescription::from_info).collect(),
+            groups,
+            main_input: None,
+            main_output: None,
+            required_features: Vec::new(),
+            optional_features: Vec::new(),
+            extensions: Vec::new(),
+            uis: Vec::new(),
+            default_state: DefaultState::new(),
+        };
+
+        for info in P::InitFeatures::features()
+            .iter()
+            .chain(P::AudioFeatures::features().iter())
+        {
+            description.add_feature(info);
+        }
+
+        for extension in KNOWN_EXTENSIONS {
+            let uri = Uri::from_bytes_with_nul(extension).unwrap();
+            if P::extension_data(uri).is_some() {
+                description = description.extension(uri_str(extension));
+            }
+        }
+        for extension in P::EXTENSIONS {
+            description = description.extension(uri_str(extension));
+        }
+
+        description
+    }
+
+    fn add_feature(&mut self, info: &FeatureInfo) {
+        add_feature(
+            &mut self.required_features,
+            &mut self.optional_features,
+            info,
+        );
+    }
+
+    /// Set the human-readable name of the plugin.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_owned();
+        self
+    }
+
+    /// Add a plugin class, like [`plugin_class::AMPLIFIER`](plugin_class/constant.AMPLIFIER.html).
+    ///
+    /// The classes declared with the [`CLASSES`](../lv2_core/plugin/trait.Plugin.html#associatedconstant.CLASSES) constant of the plugin are added automatically.
+    pub fn class(mut self, class: &str) -> Self {
+        if !self.classes.iter().any(|known| known == class) {
+            self.classes.push(class.to_owned());
+        }
+        self
+    }
+
+    /// Set the URI of the plugin's license.
+    pub fn license(mut self, license: &str) -> Self {
+        self.license = Some(license.to_owned());
+        self
+    }
+
+    /// Add a feature that isn't part of the plugin's feature collections.
+    ///
+    /// This is needed for features that are used by extensions, like the features of a state implementation.
+    pub fn feature(mut self, uri: &'static Uri, optional: bool) -> Self {
+        self.add_feature(&FeatureInfo { uri, optional });
+        self
+    }
+
+    /// Add an extension that the plugin provides via its `extension_data` method.
+    ///
+    /// The options, state and worker interfaces as well as the extensions declared with the [`extensions`](../lv2_core/plugin/attr.extensions.html) attribute are detected automatically, other extensions have to be added manually.
+    pub fn extension(mut self, uri: &str) -> Self {
+        if !self.extensions.iter().any(|extension| extension == uri) {
+            self.extensions.push(uri.to_owned());
+        }
+        self
+    }
+
+    /// Link a UI to the plugin, which is described by a [`UIDescription`](struct.UIDescription.html) with the given URI.
+    pub fn ui(mut self, uri: &str) -> Self {
+        if !self.uis.iter().any(|ui| ui == uri) {
+            self.uis.push(uri.to_owned());
+        }
+        self
+    }
+
+    /// Set the default state of the plugin, which hosts restore when they instantiate it.
+    ///
+    /// The state is written as `state:state` and usually contains the [defaults of the plugin's parameters](struct.DefaultState.html#method.from_parameters). It replaces the previous default state, which allows to regenerate it from a live instance.
+    pub fn default_state(mut self, state: DefaultState) -> Self {
+        self.default_state = state;
+        self
+    }
+
+    /// Set the human-readable name of the port with the given symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin doesn't have a port with this symbol.
+    pub fn port_name(mut self, symbol: &str, name: &str) -> Self {
+        self.port_mut(symbol).set_name(name);
+        self
+    }
+
+    /// Set the minimum, default and maximum value of the port with the given symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin doesn't have a port with this symbol.
+    pub fn port_range(mut self, symbol: &str, minimum: f32, default: f32, maximum: f32) -> Self {
+        self.port_mut(symbol).set_range(minimum, default, maximum);
+        self
+    }
+
+    /// Declare the port with the given symbol as a morph port, which the host may switch to the given port classes, like `lv2:CVPort`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin doesn't have a port with this symbol.
+    pub fn port_morph(mut self, symbol: &str, types: &[&str]) -> Self {
+        self.port_mut(symbol).set_morph_types(types);
+        self
+    }
+
+    /// Declare the port with the given symbol as an auto-morph port, whose type is chosen by the plugin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin doesn't have a port with this symbol.
+    pub fn port_auto_morph(mut self, symbol: &str) -> Self {
+        self.port_mut(symbol).set_auto_morph();
+        self
+    }
+
+    /// Set the minimum size in bytes of the buffer of the port with the given symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin doesn't have a port with this symbol.
+    pub fn port_minimum_size(mut self, symbol: &str, size: usize) -> Self {
+        self.port_mut(symbol).set_minimum_size(size);
+        self
+    }
+
+    /// Set the human-readable name of the port group with the given symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin doesn't have a port group with this symbol.
+    pub fn group_name(mut self, symbol: &str, name: &str) -> Self {
+        let uri = &self.uri;
+        self.groups
+            .iter_mut()
+            .find(|group| group.symbol() == symbol)
+            .unwrap_or_else(|| panic!("Plugin {} doesn't have a port group \"{}\"", uri, symbol))
+            .set_name(name);
+        self
+    }
+
+    /// Declare the port group with the given symbol as the main input of the plugin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin doesn't have a port group with this symbol.
+    pub fn main_input(mut self, symbol: &str) -> Self {
+        self.main_input = Some(self.group_uri(symbol));
+        self
+    }
+
+    /// Declare the port group with the given symbol as the main output of the plugin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin doesn't have a port group with this symbol.
+    pub fn main_output(mut self, symbol: &str) -> Self {
+        self.main_output = Some(self.group_uri(symbol));
+        self
+    }
+
+    fn group_uri(&self, symbol: &str) -> String {
+        self.groups
+            .iter()
+            .find(|group| group.symbol() == symbol)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Plugin {} doesn't have a port group \"{}\"",
+                    self.uri, symbol
+                )
+            })
+            .uri()
+            .to_owned()
+    }
+
+    fn port_mut(&mut self, symbol: &str) -> &mut PortDescription {
+        let uri = &self.uri;
+        self.ports
+            .iter_mut()
+            .find(|port| port.symbol() == symbol)
+            .unwrap_or_else(|| panic!("Plugin {} doesn't have a port \"{}\"", uri, symbol))
+    }
+
+    /// The URI of the plugin.
+    pub fn uri(&self) -> &str {
+        self.uri.as_ref()
+    }
+
+    /// The URIs of the plugin's classes, besides `lv2:Plugin`.
+    pub fn classes(&self) -> &[String] {
+        self.classes.as_ref()
+    }
+
+    /// The version of the plugin, if it declares one.
+    pub fn version(&self) -> Option<PluginVersion> {
+        self.version
+    }
+
+    /// The URIs of the plugins this plugin replaces.
+    pub fn replaces(&self) -> &[String] {
+        self.replaces.as_ref()
+    }
+
+    /// The ports of the plugin, in the order of their indices.
+    pub fn ports(&self) -> &[PortDescription] {
+        self.ports.as_ref()
+    }
+
+    /// The port groups of the plugin, in the order of their first ports.
+    pub fn groups(&self) -> &[PortGroupDescription] {
+        self.groups.as_ref()
+    }
+
+    /// The URIs of the features the plugin can't be instantiated without.
+    pub fn required_features(&self) -> &[String] {
+        self.required_features.as_ref()
+    }
+
+    /// The URIs of the features the plugin uses if they're available.
+    pub fn optional_features(&self) -> &[String] {
+        self.optional_features.as_ref()
+    }
+
+    /// The URIs of the extensions the plugin provides.
+    pub fn extensions(&self) -> &[String] {
+        self.extensions.as_ref()
+    }
+
+    /// The URIs of the plugin's UIs.
+    pub fn uis(&self) -> &[String] {
+        self.uis.as_ref()
+    }
+
+    /// The properties of the plugin's default state, which is empty if none was set.
+    pub fn default_state_properties(&self) -> impl Iterator<Item = (&str, &Term)> {
+        self.default_state.properties()
+    }
+
+    /// Write the statements that belong in the bundle's manifest.
+    pub(crate) fn manifest_entry(&self, binary: &str, file: &str) -> String {
+        format!(
+            "<{}>\n    a lv2:Plugin ;\n    lv2:binary {} ;\n    rdfs:seeAlso {} .\n",
+            self.uri, binary, file
+        )
+    }
+
+    /// Create the content of the plugin's Turtle file.
+    pub fn to_turtle(&self) -> String {
+        let mut classes = vec![write_iri(uri_str(sys::LV2_CORE__Plugin))];
+        classes.extend(self.classes.iter().map(|class| write_iri(class)));
+
+        let mut statements = vec![
+            format!("a {}", classes.join(" , ")),
+            format!("doap:name {}", write_literal(&self.name)),
+        ];
+        if let Some(license) = self.license.as_ref() {
+            statements.push(format!("doap:license {}", write_iri(license)));
+        }
+        if let Some(version) = self.version {
+            statements.push(format!("lv2:minorVersion {}", version.minor));
+            statements.push(format!("lv2:microVersion {}", version.micro));
+        }
+        let lists = [
+            ("dc:replaces", &self.replaces),
+            ("lv2:requiredFeature", &self.required_features),
+            ("lv2:optionalFeature", &self.optional_features),
+            ("lv2:extensionData", &self.extensions),
+            ("ui:ui", &self.uis),
+        ];
+        for (predicate, uris) in lists.iter() {
+            if !uris.is_empty() {
+                let objects: Vec<String> = uris.iter().map(|uri| write_iri(uri)).collect();
+                statements.push(format!("{} {}", predicate, objects.join(" , ")));
+            }
+        }
+        if let Some(main_input) = self.main_input.as_ref() {
+            statements.push(format!("pg:mainInput {}", write_iri(main_input)));
+        }
+        if let Some(main_output) = self.main_output.as_ref() {
+            statements.push(format!("pg:mainOutput {}", write_iri(main_output)));
+        }
+        if !self.default_state.is_empty() {
+            statements.push(format!(
+                "{} {}",
+                write_iri(uri_str(sys::LV2_STATE__state)),
+                self.default_state.to_turtle()
+            ));
+        }
+        if !self.ports.is_empty() {
+            let ports: Vec<String> = self
+                .ports
+                .iter()
+                .map(|port| port.to_turtle(&self.uri))
+                .collect();
+            statements.push(format!("lv2:port {}", ports.join(" , ")));
+        }
+
+        let mut turtle = format!(
+            "{}\n<{}>\n    {} .\n",
+            prefix_declarations(),
+            self.uri,
+            statements.join(" ;\n    ")
+        );
+        for group in self.groups.iter() {
+            turtle.push('\n');
+            turtle.push_str(&group.to_turtle());
+        }
+        turtle
+    }
+}
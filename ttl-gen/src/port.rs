@@ -0,0 +1,227 @@
+use crate::group::{group_uri, symbol_name};
+use crate::{write_iri, write_literal};
+use core::port::{PortDirection, PortInfo};
+use urid::uri_str;
+
+/// The description of a port of a plugin.
+///
+/// The index, symbol, class and direction of a port are taken from the plugin's port collection. The name defaults to the symbol with the underscores replaced by spaces, for example "Audio input" for the symbol `audio_input`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortDescription {
+    index: u32,
+    symbol: String,
+    name: String,
+    class: Option<String>,
+    direction: Option<PortDirection>,
+    optional: bool,
+    unit: Option<String>,
+    group: Option<(String, String)>,
+    designation: Option<String>,
+    side_chain: bool,
+    range: Option<(f32, f32, f32)>,
+    morph_types: Vec<String>,
+    auto_morph: bool,
+    minimum_size: Option<usize>,
+}
+
+impl PortDescription {
+    /// Create the description of a port from the metadata of its port collection.
+    pub fn from_info(info: &PortInfo) -> Self {
+        Self {
+            index: info.index,
+            symbol: info.symbol.to_owned(),
+            name: symbol_name(info.symbol),
+            class: info
+                .class
+                .and_then(|class| class.to_str().ok())
+                .map(str::to_owned),
+            direction: info.direction,
+            optional: info.optional,
+            unit: info
+                .unit
+                .and_then(|unit| unit.to_str().ok())
+                .map(str::to_owned),
+            group: info.group.map(|group| {
+                (
+                    group.symbol.to_owned(),
+                    group.channel.uri().to_str().unwrap().to_owned(),
+                )
+            }),
+            designation: info
+                .designation
+                .map(|designation| designation.uri().to_str().unwrap().to_owned()),
+            side_chain: info.side_chain,
+            range: None,
+            morph_types: Vec::new(),
+            auto_morph: false,
+            minimum_size: None,
+        }
+    }
+
+    /// The index of the port.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The symbol of the port.
+    pub fn symbol(&self) -> &str {
+        self.symbol.as_ref()
+    }
+
+    /// The human-readable name of the port.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Set the human-readable name of the port.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_owned();
+    }
+
+    /// The URI of the port's class, like `lv2:AudioPort`, if it's known.
+    pub fn class(&self) -> Option<&str> {
+        self.class.as_deref()
+    }
+
+    /// The direction of the port, if it's known.
+    pub fn direction(&self) -> Option<PortDirection> {
+        self.direction
+    }
+
+    /// Whether the port doesn't have to be connected.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// The URI of the unit of the port's values, if it has one.
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    /// The symbol of the port's group, if it belongs to one.
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_ref().map(|(symbol, _)| symbol.as_ref())
+    }
+
+    /// The URI of the port's designation, if it has one.
+    ///
+    /// The designation of a port in a group is its channel.
+    pub fn designation(&self) -> Option<&str> {
+        match self.group.as_ref() {
+            Some((_, channel)) => Some(channel.as_ref()),
+            None => self.designation.as_deref(),
+        }
+    }
+
+    /// Whether the port is a side-chain input.
+    pub fn is_side_chain(&self) -> bool {
+        self.side_chain
+    }
+
+    /// The minimum, default and maximum value of the port, if they are set.
+    pub fn range(&self) -> Option<(f32, f32, f32)> {
+        self.range
+    }
+
+    /// Set the minimum, default and maximum value of the port.
+    pub fn set_range(&mut self, minimum: f32, default: f32, maximum: f32) {
+        self.range = Some((minimum, default, maximum));
+    }
+
+    /// The URIs of the port classes the port may be switched to by the host, if it's a morph port.
+    pub fn morph_types(&self) -> &[String] {
+        self.morph_types.as_ref()
+    }
+
+    /// Declare the port as a morph port, which the host may switch to the given port classes, like `lv2:CVPort`.
+    pub fn set_morph_types(&mut self, types: &[&str]) {
+        self.morph_types = types.iter().map(|class| (*class).to_owned()).collect();
+    }
+
+    /// Whether the port is an auto-morph port, whose type is chosen by the plugin.
+    pub fn is_auto_morph(&self) -> bool {
+        self.auto_morph
+    }
+
+    /// Declare the port as an auto-morph port, whose type is chosen by the plugin.
+    pub fn set_auto_morph(&mut self) {
+        self.auto_morph = true;
+    }
+
+    /// The minimum size of the port's buffer in bytes, if it's set.
+    pub fn minimum_size(&self) -> Option<usize> {
+        self.minimum_size
+    }
+
+    /// Set the minimum size of the port's buffer in bytes.
+    pub fn set_minimum_size(&mut self, size: usize) {
+        self.minimum_size = Some(size);
+    }
+
+    /// Write the description as the object of an `lv2:port` statement of the given plugin.
+    pub(crate) fn to_turtle(&self, plugin_uri: &str) -> String {
+        let mut classes: Vec<String> = Vec::new();
+        match self.direction {
+            Some(PortDirection::Input) => {
+                classes.push(write_iri(uri_str(sys::LV2_CORE__InputPort)));
+            }
+            Some(PortDirection::Output) => {
+                classes.push(write_iri(uri_str(sys::LV2_CORE__OutputPort)));
+            }
+            None => (),
+        }
+        if let Some(class) = self.class.as_ref() {
+            classes.push(write_iri(class));
+        }
+        if !self.morph_types.is_empty() {
+            classes.push(write_iri(uri_str(sys::LV2_MORPH__MorphPort)));
+        }
+        if self.auto_morph {
+            classes.push(write_iri(uri_str(sys::LV2_MORPH__AutoMorphPort)));
+        }
+
+        let mut statements: Vec<String> = Vec::new();
+        if !classes.is_empty() {
+            statements.push(format!("a {}", classes.join(" , ")));
+        }
+        statements.push(format!("lv2:index {}", self.index));
+        statements.push(format!("lv2:symbol {}", write_literal(&self.symbol)));
+        statements.push(format!("lv2:name {}", write_literal(&self.name)));
+        if self.class.as_deref() == Some(uri_str(sys::LV2_ATOM__AtomPort)) {
+            // Atom ports of rust-lv2 are almost always used with sequences.
+            statements.push("atom:bufferType atom:Sequence".to_owned());
+        }
+        if let Some((minimum, default, maximum)) = self.range {
+            statements.push(format!("lv2:default {:?}", default));
+            statements.push(format!("lv2:minimum {:?}", minimum));
+            statements.push(format!("lv2:maximum {:?}", maximum));
+        }
+        for class in self.morph_types.iter() {
+            statements.push(format!("morph:supportsType {}", write_iri(class)));
+        }
+        if let Some(size) = self.minimum_size {
+            statements.push(format!("rsz:minimumSize {}", size));
+        }
+        if let Some(unit) = self.unit.as_ref() {
+            statements.push(format!("units:unit {}", write_iri(unit)));
+        }
+        if let Some((group, channel)) = self.group.as_ref() {
+            statements.push(format!(
+                "pg:group {}",
+                write_iri(&group_uri(plugin_uri, group))
+            ));
+            statements.push(format!("lv2:designation {}", write_iri(channel)));
+        }
+        if let Some(designation) = self.designation.as_ref() {
+            statements.push(format!("lv2:designation {}", write_iri(designation)));
+        }
+        if self.optional {
+            statements.push("lv2:portProperty lv2:connectionOptional".to_owned());
+        }
+        if self.side_chain {
+            statements.push("lv2:portProperty lv2:isSideChain".to_owned());
+        }
+
+        format!("[\n        {}\n    ]", statements.join(" ;\n        "))
+    }
+}
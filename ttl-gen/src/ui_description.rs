@@ -0,0 +1,129 @@
+use crate::{add_feature, prefix_declarations, write_iri};
+use core::feature::{FeatureCollection, FeatureInfo};
+use ui::PluginUI;
+use urid::{uri_str, Uri};
+
+/// The extensions that are looked up in the UI's `extension_data` method.
+const KNOWN_EXTENSIONS: &[&[u8]] = &[sys::LV2_UI__idleInterface, sys::LV2_UI__showInterface];
+
+/// The description of a plugin UI, which is written to the UI's Turtle file.
+///
+/// Like a [`PluginDescription`](struct.PluginDescription.html), it's created from the UI type: The URI is taken from its [`UriBound`](../urid/trait.UriBound.html) implementation, the required and optional features from its [`Features`](../lv2_ui/trait.PluginUI.html#associatedtype.Features) and the idle and show interfaces are detected with its [`extension_data`](../lv2_ui/trait.PluginUI.html#method.extension_data) method. A UI that uses the [`Touch`](../lv2_ui/struct.Touch.html) feature to tell the host about gestures therefore declares `ui:touch` automatically.
+///
+/// The UI is linked to its plugin with [`PluginDescription::ui`](struct.PluginDescription.html#method.ui) and added to the bundle with [`BundleDescription::ui`](struct.BundleDescription.html#method.ui).
+#[derive(Clone, Debug, PartialEq)]
+pub struct UIDescription {
+    uri: String,
+    class: String,
+    required_features: Vec<String>,
+    optional_features: Vec<String>,
+    extensions: Vec<String>,
+}
+
+impl UIDescription {
+    /// Create the description of a UI type, with the type of widget it creates, like [`ui_class::X11`](ui_class/constant.X11.html).
+    pub fn new<U: PluginUI>(class: &str) -> Self {
+        let mut description = Self {
+            uri: U::uri().to_str().unwrap().to_owned(),
+            class: class.to_owned(),
+            required_features: Vec::new(),
+            optional_features: Vec::new(),
+            extensions: Vec::new(),
+        };
+
+        for info in U::Features::features().iter() {
+            description.add_feature(info);
+        }
+
+        for extension in KNOWN_EXTENSIONS {
+            let uri = Uri::from_bytes_with_nul(extension).unwrap();
+            if U::extension_data(uri).is_some() {
+                description = description.extension(uri_str(extension));
+            }
+        }
+
+        description
+    }
+
+    fn add_feature(&mut self, info: &FeatureInfo) {
+        add_feature(
+            &mut self.required_features,
+            &mut self.optional_features,
+            info,
+        );
+    }
+
+    /// Add a feature that isn't part of the UI's feature collection.
+    pub fn feature(mut self, uri: &'static Uri, optional: bool) -> Self {
+        self.add_feature(&FeatureInfo { uri, optional });
+        self
+    }
+
+    /// Add an extension that the UI provides via its `extension_data` method.
+    ///
+    /// The idle and show interfaces are detected automatically, other extensions have to be added manually.
+    pub fn extension(mut self, uri: &str) -> Self {
+        if !self.extensions.iter().any(|extension| extension == uri) {
+            self.extensions.push(uri.to_owned());
+        }
+        self
+    }
+
+    /// The URI of the UI.
+    pub fn uri(&self) -> &str {
+        self.uri.as_ref()
+    }
+
+    /// The type of widget the UI creates.
+    pub fn class(&self) -> &str {
+        self.class.as_ref()
+    }
+
+    /// The URIs of the features the UI can't be instantiated without.
+    pub fn required_features(&self) -> &[String] {
+        self.required_features.as_ref()
+    }
+
+    /// The URIs of the features the UI uses if they're available.
+    pub fn optional_features(&self) -> &[String] {
+        self.optional_features.as_ref()
+    }
+
+    /// The URIs of the extensions the UI provides.
+    pub fn extensions(&self) -> &[String] {
+        self.extensions.as_ref()
+    }
+
+    /// Write the statements that belong in the bundle's manifest.
+    pub(crate) fn manifest_entry(&self, binary: &str, file: &str) -> String {
+        format!(
+            "<{}>\n    a {} ;\n    ui:binary {} ;\n    rdfs:seeAlso {} .\n",
+            self.uri,
+            write_iri(&self.class),
+            binary,
+            file
+        )
+    }
+
+    /// Create the content of the UI's Turtle file.
+    pub fn to_turtle(&self) -> String {
+        let mut statements = vec![format!("a {}", write_iri(&self.class))];
+        let lists = [
+            ("lv2:requiredFeature", &self.required_features),
+            ("lv2:optionalFeature", &self.optional_features),
+            ("lv2:extensionData", &self.extensions),
+        ];
+        for (predicate, uris) in lists.iter() {
+            if !uris.is_empty() {
+                let objects: Vec<String> = uris.iter().map(|uri| write_iri(uri)).collect();
+                statements.push(format!("{} {}", predicate, objects.join(" , ")));
+            }
+        }
+        format!(
+            "{}\n<{}>\n    {} .\n",
+            prefix_declarations(),
+            self.uri,
+            statements.join(" ;\n    ")
+        )
+    }
+}
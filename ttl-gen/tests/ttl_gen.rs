@@ -0,0 +1,677 @@
+use lv2_atom::prelude::*;
+use lv2_core::feature::{HardRTCapable, IsLive};
+use lv2_core::prelude::*;
+use lv2_parameters::prelude::*;
+use lv2_presets::turtle::Term;
+use lv2_state::*;
+use lv2_ttl_gen::*;
+use lv2_ui::prelude::*;
+use lv2_units::prelude::*;
+use lv2_urid::*;
+use urid::*;
+
+#[allow(dead_code)]
+#[derive(PortCollection)]
+struct Ports {
+    gain: InputPort<Control<Db>>,
+    audio_input: InputPort<Audio>,
+    audio_output: OutputPort<Audio>,
+    control: InputPort<AtomPort>,
+    sidechain: Option<InputPort<CV>>,
+}
+
+#[derive(FeatureCollection)]
+struct InitFeatures<'a> {
+    _map: LV2Map<'a>,
+    _live: Option<IsLive>,
+}
+
+#[derive(FeatureCollection)]
+struct AudioFeatures {
+    _rt_capable: Option<HardRTCapable>,
+    _live: IsLive,
+}
+
+#[uri("urn:lv2_ttl_gen:test:compressor")]
+struct Compressor;
+
+#[extensions(StateDescriptor)]
+impl Plugin for Compressor {
+    type Ports = Ports;
+    type InitFeatures = InitFeatures<'static>;
+    type AudioFeatures = AudioFeatures;
+
+    fn new(_: &PluginInfo, _: &mut InitFeatures<'static>) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut Ports, _: &mut RunContext<AudioFeatures>) {}
+}
+
+impl State for Compressor {
+    type StateFeatures = ();
+
+    fn save(&self, _: StoreHandle, _: ()) -> Result<(), StateErr> {
+        Ok(())
+    }
+
+    fn restore(&mut self, _: RetrieveHandle, _: ()) -> Result<(), StateErr> {
+        Ok(())
+    }
+}
+
+#[uri("urn:lv2_ttl_gen:test:silence")]
+struct Silence;
+
+impl Plugin for Silence {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+#[test]
+fn test_plugin_description() {
+    let description = PluginDescription::new::<Compressor>()
+        .class(plugin_class::DYNAMICS)
+        .license("http://opensource.org/licenses/isc")
+        .port_name("gain", "Gain (dB)")
+        .port_range("gain", -90.0, 0.0, 24.0);
+
+    assert_eq!(description.uri(), "urn:lv2_ttl_gen:test:compressor");
+    assert_eq!(
+        description.required_features(),
+        &[
+            "http://lv2plug.in/ns/ext/urid#map".to_owned(),
+            "http://lv2plug.in/ns/lv2core#isLive".to_owned(),
+        ]
+    );
+    assert_eq!(
+        description.optional_features(),
+        &["http://lv2plug.in/ns/lv2core#hardRTCapable".to_owned()]
+    );
+    assert_eq!(
+        description.extensions(),
+        &["http://lv2plug.in/ns/ext/state#interface".to_owned()]
+    );
+    assert_eq!(description.ports().len(), 5);
+    assert_eq!(description.ports()[1].name(), "Audio input");
+    assert_eq!(description.ports()[0].range(), Some((-90.0, 0.0, 24.0)));
+    assert_eq!(
+        description.ports()[0].unit(),
+        Some("http://lv2plug.in/ns/extensions/units#db")
+    );
+    assert_eq!(description.ports()[1].unit(), None);
+
+    assert_eq!(
+        description.to_turtle(),
+        r#"@prefix atom: <http://lv2plug.in/ns/ext/atom#> .
+@prefix dc: <http://purl.org/dc/terms/> .
+@prefix doap: <http://usefulinc.com/ns/doap#> .
+@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix morph: <http://lv2plug.in/ns/ext/morph#> .
+@prefix param: <http://lv2plug.in/ns/ext/parameters#> .
+@prefix pg: <http://lv2plug.in/ns/ext/port-groups#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix rsz: <http://lv2plug.in/ns/ext/resize-port#> .
+@prefix ui: <http://lv2plug.in/ns/extensions/ui#> .
+@prefix units: <http://lv2plug.in/ns/extensions/units#> .
+
+<urn:lv2_ttl_gen:test:compressor>
+    a lv2:Plugin , lv2:DynamicsPlugin ;
+    doap:name "Compressor" ;
+    doap:license <http://opensource.org/licenses/isc> ;
+    lv2:requiredFeature <http://lv2plug.in/ns/ext/urid#map> , lv2:isLive ;
+    lv2:optionalFeature lv2:hardRTCapable ;
+    lv2:extensionData <http://lv2plug.in/ns/ext/state#interface> ;
+    lv2:port [
+        a lv2:InputPort , lv2:ControlPort ;
+        lv2:index 0 ;
+        lv2:symbol "gain" ;
+        lv2:name "Gain (dB)" ;
+        lv2:default 0.0 ;
+        lv2:minimum -90.0 ;
+        lv2:maximum 24.0 ;
+        units:unit units:db
+    ] , [
+        a lv2:InputPort , lv2:AudioPort ;
+        lv2:index 1 ;
+        lv2:symbol "audio_input" ;
+        lv2:name "Audio input"
+    ] , [
+        a lv2:OutputPort , lv2:AudioPort ;
+        lv2:index 2 ;
+        lv2:symbol "audio_output" ;
+        lv2:name "Audio output"
+    ] , [
+        a lv2:InputPort , atom:AtomPort ;
+        lv2:index 3 ;
+        lv2:symbol "control" ;
+        lv2:name "Control" ;
+        atom:bufferType atom:Sequence
+    ] , [
+        a lv2:InputPort , lv2:CVPort ;
+        lv2:index 4 ;
+        lv2:symbol "sidechain" ;
+        lv2:name "Sidechain" ;
+        lv2:portProperty lv2:connectionOptional
+    ] .
+"#
+    );
+}
+
+#[test]
+fn test_bundle() {
+    let path = std::env::temp_dir().join(format!("lv2_ttl_gen_bundle_{}", std::process::id()));
+    std::fs::remove_dir_all(&path).ok();
+
+    let bundle = BundleDescription::new("libtest plugins.so")
+        .plugin(PluginDescription::new::<Compressor>(), "compressor.ttl")
+        .plugin(
+            PluginDescription::new::<Silence>().feature(IsLive::uri(), true),
+            "silence.ttl",
+        );
+    assert_eq!(bundle.plugins().count(), 2);
+    bundle.write(&path).unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(path.join("manifest.ttl")).unwrap(),
+        r#"@prefix atom: <http://lv2plug.in/ns/ext/atom#> .
+@prefix dc: <http://purl.org/dc/terms/> .
+@prefix doap: <http://usefulinc.com/ns/doap#> .
+@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix morph: <http://lv2plug.in/ns/ext/morph#> .
+@prefix param: <http://lv2plug.in/ns/ext/parameters#> .
+@prefix pg: <http://lv2plug.in/ns/ext/port-groups#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix rsz: <http://lv2plug.in/ns/ext/resize-port#> .
+@prefix ui: <http://lv2plug.in/ns/extensions/ui#> .
+@prefix units: <http://lv2plug.in/ns/extensions/units#> .
+
+<urn:lv2_ttl_gen:test:compressor>
+    a lv2:Plugin ;
+    lv2:binary <libtest%20plugins.so> ;
+    rdfs:seeAlso <compressor.ttl> .
+
+<urn:lv2_ttl_gen:test:silence>
+    a lv2:Plugin ;
+    lv2:binary <libtest%20plugins.so> ;
+    rdfs:seeAlso <silence.ttl> .
+"#
+    );
+    assert_eq!(
+        std::fs::read_to_string(path.join("silence.ttl")).unwrap(),
+        r#"@prefix atom: <http://lv2plug.in/ns/ext/atom#> .
+@prefix dc: <http://purl.org/dc/terms/> .
+@prefix doap: <http://usefulinc.com/ns/doap#> .
+@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix morph: <http://lv2plug.in/ns/ext/morph#> .
+@prefix param: <http://lv2plug.in/ns/ext/parameters#> .
+@prefix pg: <http://lv2plug.in/ns/ext/port-groups#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix rsz: <http://lv2plug.in/ns/ext/resize-port#> .
+@prefix ui: <http://lv2plug.in/ns/extensions/ui#> .
+@prefix units: <http://lv2plug.in/ns/extensions/units#> .
+
+<urn:lv2_ttl_gen:test:silence>
+    a lv2:Plugin ;
+    doap:name "Silence" ;
+    lv2:optionalFeature lv2:isLive .
+"#
+    );
+    assert!(path.join("compressor.ttl").exists());
+
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[derive(PortCollection)]
+struct StereoPorts {
+    #[port_group(group = "main_in", kind = Stereo, channel = Left)]
+    input_left: InputPort<Audio>,
+    #[port_group(group = "main_in", channel = Right)]
+    input_right: InputPort<Audio>,
+    #[port_group(group = "sidechain", kind = Mono, channel = Center, side_chain_of = "main_in")]
+    sidechain: InputPort<Audio>,
+    #[port_group(group = "main_out", kind = Stereo, channel = Left)]
+    output_left: OutputPort<Audio>,
+    #[port_group(group = "main_out", channel = Right)]
+    output_right: OutputPort<Audio>,
+}
+
+#[uri("urn:lv2_ttl_gen:test:stereo")]
+struct Stereo;
+
+impl Plugin for Stereo {
+    type Ports = StereoPorts;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut StereoPorts, _: &mut RunContext<()>) {}
+}
+
+#[test]
+fn test_port_groups() {
+    let description = PluginDescription::new::<Stereo>()
+        .group_name("main_in", "Main input")
+        .main_input("main_in")
+        .main_output("main_out");
+
+    let groups: Vec<&str> = description
+        .groups()
+        .iter()
+        .map(PortGroupDescription::symbol)
+        .collect();
+    assert_eq!(groups, vec!["main_in", "sidechain", "main_out"]);
+    assert_eq!(
+        description.groups()[2].uri(),
+        "urn:lv2_ttl_gen:test:stereo#main_out"
+    );
+    assert_eq!(description.ports()[1].group(), Some("main_in"));
+
+    assert_eq!(
+        description.to_turtle(),
+        r#"@prefix atom: <http://lv2plug.in/ns/ext/atom#> .
+@prefix dc: <http://purl.org/dc/terms/> .
+@prefix doap: <http://usefulinc.com/ns/doap#> .
+@prefix lv2: <http://lv2plug.in/ns/lv2core#> .
+@prefix morph: <http://lv2plug.in/ns/ext/morph#> .
+@prefix param: <http://lv2plug.in/ns/ext/parameters#> .
+@prefix pg: <http://lv2plug.in/ns/ext/port-groups#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix rsz: <http://lv2plug.in/ns/ext/resize-port#> .
+@prefix ui: <http://lv2plug.in/ns/extensions/ui#> .
+@prefix units: <http://lv2plug.in/ns/extensions/units#> .
+
+<urn:lv2_ttl_gen:test:stereo>
+    a lv2:Plugin ;
+    doap:name "Stereo" ;
+    pg:mainInput <urn:lv2_ttl_gen:test:stereo#main_in> ;
+    pg:mainOutput <urn:lv2_ttl_gen:test:stereo#main_out> ;
+    lv2:port [
+        a lv2:InputPort , lv2:AudioPort ;
+        lv2:index 0 ;
+        lv2:symbol "input_left" ;
+        lv2:name "Input left" ;
+        pg:group <urn:lv2_ttl_gen:test:stereo#main_in> ;
+        lv2:designation pg:left
+    ] , [
+        a lv2:InputPort , lv2:AudioPort ;
+        lv2:index 1 ;
+        lv2:symbol "input_right" ;
+        lv2:name "Input right" ;
+        pg:group <urn:lv2_ttl_gen:test:stereo#main_in> ;
+        lv2:designation pg:right
+    ] , [
+        a lv2:InputPort , lv2:AudioPort ;
+        lv2:index 2 ;
+        lv2:symbol "sidechain" ;
+        lv2:name "Sidechain" ;
+        pg:group <urn:lv2_ttl_gen:test:stereo#sidechain> ;
+        lv2:designation pg:center
+    ] , [
+        a lv2:OutputPort , lv2:AudioPort ;
+        lv2:index 3 ;
+        lv2:symbol "output_left" ;
+        lv2:name "Output left" ;
+        pg:group <urn:lv2_ttl_gen:test:stereo#main_out> ;
+        lv2:designation pg:left
+    ] , [
+        a lv2:OutputPort , lv2:AudioPort ;
+        lv2:index 4 ;
+        lv2:symbol "output_right" ;
+        lv2:name "Output right" ;
+        pg:group <urn:lv2_ttl_gen:test:stereo#main_out> ;
+        lv2:designation pg:right
+    ] .
+
+<urn:lv2_ttl_gen:test:stereo#main_in>
+    a pg:InputGroup , pg:StereoGroup ;
+    lv2:symbol "main_in" ;
+    lv2:name "Main input" .
+
+<urn:lv2_ttl_gen:test:stereo#sidechain>
+    a pg:InputGroup , pg:MonoGroup ;
+    lv2:symbol "sidechain" ;
+    lv2:name "Sidechain" ;
+    pg:sideChainOf <urn:lv2_ttl_gen:test:stereo#main_in> .
+
+<urn:lv2_ttl_gen:test:stereo#main_out>
+    a pg:OutputGroup , pg:StereoGroup ;
+    lv2:symbol "main_out" ;
+    lv2:name "Main out" .
+"#
+    );
+}
+
+#[test]
+fn test_port_properties() {
+    let description = PluginDescription::new::<Compressor>()
+        .port_morph("gain", &["http://lv2plug.in/ns/lv2core#CVPort"])
+        .port_auto_morph("audio_output")
+        .port_minimum_size("control", 65536);
+
+    assert_eq!(
+        description.ports()[0].morph_types(),
+        &["http://lv2plug.in/ns/lv2core#CVPort".to_owned()]
+    );
+    assert!(description.ports()[2].is_auto_morph());
+    assert_eq!(description.ports()[3].minimum_size(), Some(65536));
+
+    let turtle = description.to_turtle();
+    assert!(turtle.contains(
+        r#"a lv2:InputPort , lv2:ControlPort , morph:MorphPort ;
+        lv2:index 0 ;
+        lv2:symbol "gain" ;
+        lv2:name "Gain" ;
+        morph:supportsType lv2:CVPort ;"#
+    ));
+    assert!(turtle.contains("a lv2:OutputPort , lv2:AudioPort , morph:AutoMorphPort ;"));
+    assert!(turtle.contains(
+        r#"atom:bufferType atom:Sequence ;
+        rsz:minimumSize 65536"#
+    ));
+}
+
+#[test]
+fn test_binary_name() {
+    let name = binary_name("my-plugin");
+    assert!(name.contains("my_plugin"));
+    if cfg!(target_os = "linux") {
+        assert_eq!(name, "libmy_plugin.so");
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_unknown_port() {
+    let _ = PluginDescription::new::<Compressor>().port_name("volume", "Volume");
+}
+
+#[allow(dead_code)]
+#[derive(PortCollection)]
+struct GatePorts {
+    input: InputPort<Audio>,
+    #[port(side_chain)]
+    key: InputPort<Audio>,
+    output: OutputPort<Audio>,
+    #[port(designation = Enabled)]
+    enabled: InputPort<Control>,
+    #[port(designation = Latency)]
+    latency: OutputPort<Control>,
+}
+
+#[uri("urn:lv2_ttl_gen:test:gate")]
+struct Gate;
+
+impl Plugin for Gate {
+    type Ports = GatePorts;
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut GatePorts, _: &mut RunContext<()>) {}
+}
+
+#[test]
+fn test_port_designations() {
+    let description = PluginDescription::new::<Gate>();
+    let ports = description.ports();
+    assert!(ports[1].is_side_chain());
+    assert_eq!(ports[0].designation(), None);
+    assert_eq!(
+        ports[3].designation(),
+        Some("http://lv2plug.in/ns/lv2core#enabled")
+    );
+
+    let turtle = description.to_turtle();
+    assert!(turtle.contains(
+        r#"lv2:symbol "key" ;
+        lv2:name "Key" ;
+        lv2:portProperty lv2:isSideChain
+    ]"#
+    ));
+    assert!(turtle.contains(
+        r#"lv2:symbol "enabled" ;
+        lv2:name "Enabled" ;
+        lv2:designation lv2:enabled
+    ]"#
+    ));
+    assert!(turtle.contains(
+        r#"lv2:symbol "latency" ;
+        lv2:name "Latency" ;
+        lv2:designation lv2:latency
+    ]"#
+    ));
+}
+
+#[derive(FeatureCollection)]
+struct UIFeatures<'a> {
+    _map: LV2Map<'a>,
+    _touch: Option<Touch<'a>>,
+}
+
+#[uri("urn:lv2_ttl_gen:test:silence-ui")]
+struct SilenceUI;
+
+impl PluginUI for SilenceUI {
+    type Features = UIFeatures<'static>;
+
+    fn new(_: &UIInfo, _: Controller, _: &mut UIFeatures<'static>) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn extension_data(uri: &Uri) -> Option<&'static dyn std::any::Any> {
+        match_extensions![uri, IdleDescriptor<Self>]
+    }
+}
+
+impl Idle for SilenceUI {
+    fn idle(&mut self, _: &mut UIFeatures<'static>) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_ui_description() {
+    let ui = UIDescription::new::<SilenceUI>(ui_class::X11);
+    assert_eq!(ui.uri(), "urn:lv2_ttl_gen:test:silence-ui");
+    assert_eq!(
+        ui.required_features(),
+        &["http://lv2plug.in/ns/ext/urid#map"]
+    );
+    // The touch feature is declared, so that hosts know the UI reports its gestures.
+    assert_eq!(
+        ui.optional_features(),
+        &["http://lv2plug.in/ns/extensions/ui#touch"]
+    );
+    assert_eq!(
+        ui.extensions(),
+        &["http://lv2plug.in/ns/extensions/ui#idleInterface"]
+    );
+
+    let bundle = BundleDescription::new("libsilence.so")
+        .plugin(
+            PluginDescription::new::<Silence>().ui(ui.uri()),
+            "silence.ttl",
+        )
+        .ui(ui, "libsilence_ui.so", "silence_ui.ttl");
+    assert_eq!(bundle.uis().count(), 1);
+
+    let (plugin, _) = bundle.plugins().next().unwrap();
+    assert!(plugin
+        .to_turtle()
+        .ends_with("    ui:ui <urn:lv2_ttl_gen:test:silence-ui> .\n"));
+    assert!(bundle.manifest().ends_with(
+        r#"
+<urn:lv2_ttl_gen:test:silence-ui>
+    a ui:X11UI ;
+    ui:binary <libsilence_ui.so> ;
+    rdfs:seeAlso <silence_ui.ttl> .
+"#
+    ));
+    let (ui, _) = bundle.uis().next().unwrap();
+    assert!(ui.to_turtle().ends_with(
+        r#"
+<urn:lv2_ttl_gen:test:silence-ui>
+    a ui:X11UI ;
+    lv2:requiredFeature <http://lv2plug.in/ns/ext/urid#map> ;
+    lv2:optionalFeature ui:touch ;
+    lv2:extensionData ui:idleInterface .
+"#
+    ));
+}
+
+#[uri("urn:lv2_ttl_gen:test:synth")]
+struct Synth;
+
+#[plugin_version(
+    minor = 2,
+    micro = 1,
+    replaces = "urn:ladspa:1049",
+    replaces = "urn:lv2_ttl_gen:test:old-synth"
+)]
+impl Plugin for Synth {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    const CLASSES: &'static [PluginClass] = &[PluginClass::Instrument, PluginClass::Oscillator];
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        Some(Self)
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+#[test]
+fn test_plugin_classes() {
+    let description = PluginDescription::new::<Synth>().class(plugin_class::INSTRUMENT);
+    assert_eq!(
+        description.classes(),
+        &[
+            "http://lv2plug.in/ns/lv2core#InstrumentPlugin".to_owned(),
+            "http://lv2plug.in/ns/lv2core#OscillatorPlugin".to_owned(),
+        ]
+    );
+    assert!(description
+        .to_turtle()
+        .contains("    a lv2:Plugin , lv2:InstrumentPlugin , lv2:OscillatorPlugin ;\n"));
+
+    // Plugins without declared classes are generic plugins.
+    assert!(PluginDescription::new::<Silence>().classes().is_empty());
+}
+
+#[test]
+fn test_plugin_version() {
+    let description = PluginDescription::new::<Synth>();
+    assert_eq!(description.version(), Some(PluginVersion::new(2, 1)));
+    assert_eq!(
+        description.replaces(),
+        &[
+            "urn:ladspa:1049".to_owned(),
+            "urn:lv2_ttl_gen:test:old-synth".to_owned(),
+        ]
+    );
+    let turtle = description.to_turtle();
+    assert!(turtle.contains("    lv2:minorVersion 2 ;\n    lv2:microVersion 1 ;\n"));
+    assert!(
+        turtle.contains("    dc:replaces <urn:ladspa:1049> , <urn:lv2_ttl_gen:test:old-synth> .\n")
+    );
+
+    let description = PluginDescription::new::<Silence>();
+    assert_eq!(description.version(), None);
+    assert!(!description.to_turtle().contains("Version"));
+}
+
+#[derive(ParameterCollection)]
+struct SynthParameters {
+    #[parameter(
+        uri = "urn:lv2_ttl_gen:test:synth#cutoff",
+        default = 1000.0,
+        min = 20.0,
+        max = 20000.0
+    )]
+    cutoff: f32,
+    #[parameter(uri = "urn:lv2_ttl_gen:test:synth#voices", default = 8)]
+    voices: i32,
+}
+
+#[uri("urn:lv2_ttl_gen:test:patch")]
+struct Patch {
+    parameters: Parameters<SynthParameters>,
+}
+
+impl Plugin for Patch {
+    type Ports = ();
+    type InitFeatures = ();
+    type AudioFeatures = ();
+
+    fn new(_: &PluginInfo, _: &mut ()) -> Option<Self> {
+        None
+    }
+
+    fn run(&mut self, _: &mut (), _: &mut RunContext<()>) {}
+}
+
+impl State for Patch {
+    type StateFeatures = ();
+
+    fn save(&self, mut store: StoreHandle, _: ()) -> Result<(), StateErr> {
+        self.parameters.save(&mut store)
+    }
+
+    fn restore(&mut self, store: RetrieveHandle, _: ()) -> Result<(), StateErr> {
+        self.parameters.restore(&store)
+    }
+}
+
+#[test]
+fn test_default_state() {
+    let description = PluginDescription::new::<Silence>();
+    assert_eq!(description.default_state_properties().count(), 0);
+    assert!(!description.to_turtle().contains("state#state"));
+
+    // The defaults of the parameters.
+    let description = description.default_state(DefaultState::from_parameters::<SynthParameters>());
+    assert!(description.to_turtle().ends_with(
+        r#"    <http://lv2plug.in/ns/ext/state#state> [
+        <urn:lv2_ttl_gen:test:synth#cutoff> "1000"^^<http://www.w3.org/2001/XMLSchema#float> ;
+        <urn:lv2_ttl_gen:test:synth#voices> "8"^^<http://www.w3.org/2001/XMLSchema#int>
+    ] .
+"#
+    ));
+
+    // The state of a live instance, which replaces the previous default state.
+    let map = HashURIDMapper::new();
+    let mut patch = Patch {
+        parameters: Parameters::new(&map).unwrap(),
+    };
+    patch.parameters.cutoff = 440.0;
+    let state = DefaultState::from_instance(&patch, (), &map, &map)
+        .unwrap()
+        .property(
+            "urn:lv2_ttl_gen:test:synth#voices",
+            Term::typed("4", "http://www.w3.org/2001/XMLSchema#int"),
+        )
+        .property("urn:lv2_ttl_gen:test:synth#name", Term::string("Lead"));
+    let description = description.default_state(state);
+    let properties: Vec<(&str, &Term)> = description.default_state_properties().collect();
+    assert_eq!(properties.len(), 3);
+    assert_eq!(properties[0].0, "urn:lv2_ttl_gen:test:synth#cutoff");
+    assert_eq!(properties[0].1.as_literal(), Some("440"));
+    assert_eq!(properties[1].1.as_literal(), Some("4"));
+    assert_eq!(properties[2].1, &Term::string("Lead"));
+}
@@ -0,0 +1,124 @@
+use proc_macro::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Result, Token, Type};
+
+/// A UI instance descriptor that should be exported.
+///
+/// The instance descriptor is defined by the UI type.
+struct Lv2UIInstanceDescriptor {
+    ui_type: Type,
+}
+
+impl Parse for Lv2UIInstanceDescriptor {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ui_type = input.parse()?;
+        Ok(Lv2UIInstanceDescriptor { ui_type })
+    }
+}
+
+impl Lv2UIInstanceDescriptor {
+    /// Implement the `UIInstanceDescriptor` for the UI.
+    ///
+    /// By implementing `UIInstanceDescriptor`, the descriptor of the UI is created: A struct with
+    /// the URI of the UI and pointers to the UI's basic functions; Like `instantiate` or
+    /// `port_event`.
+    pub fn make_instance_descriptor_impl(&self) -> impl ::quote::ToTokens {
+        let ui_type = &self.ui_type;
+        quote! {
+            unsafe impl UIInstanceDescriptor for #ui_type {
+                const DESCRIPTOR: LV2UI_Descriptor = LV2UI_Descriptor {
+                    URI: Self::URI.as_ptr() as *const u8 as *const ::std::os::raw::c_char,
+                    instantiate: Some(UIInstance::<Self>::instantiate),
+                    cleanup: Some(UIInstance::<Self>::cleanup),
+                    port_event: Some(UIInstance::<Self>::port_event),
+                    extension_data: Some(UIInstance::<Self>::extension_data)
+                };
+            }
+        }
+    }
+
+    /// Create a matching arm for the UI.
+    ///
+    /// The root function receives an index and has to return one UI descriptor per index, or
+    /// NULL. In this crate's implementation, this index is matched in a `match` statement and
+    /// this method creates a match arm for this UI.
+    fn make_index_match_arm(&self, index: u32) -> impl ::quote::ToTokens {
+        let ui_type = &self.ui_type;
+        quote! {
+            #index => &<#ui_type as UIInstanceDescriptor>::DESCRIPTOR,
+        }
+    }
+}
+
+/// A collection for UI instance descriptors.
+///
+/// The contained instance descriptors are used to create the export function `lv2ui_descriptor`
+/// that tells the host of a library's UIs.
+struct Lv2UIInstanceDescriptorList {
+    descriptors: Punctuated<Lv2UIInstanceDescriptor, Token![,]>,
+}
+
+impl Parse for Lv2UIInstanceDescriptorList {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            descriptors: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+impl Lv2UIInstanceDescriptorList {
+    /// Implement `UIInstanceDescriptor` for all UI instances.
+    fn make_instance_descriptor_impls<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = impl ::quote::ToTokens> + 'a {
+        self.descriptors
+            .iter()
+            .map(Lv2UIInstanceDescriptor::make_instance_descriptor_impl)
+    }
+
+    /// Create the `lv2ui_descriptor` function.
+    ///
+    /// This function tells the host of a library's UIs by returning one UI descriptor per index.
+    fn make_descriptor_function(&self) -> impl ::quote::ToTokens {
+        let index_matchers = self
+            .descriptors
+            .iter()
+            .enumerate()
+            .map(|(i, desc)| desc.make_index_match_arm(i as u32));
+
+        quote! {
+            /// Return a raw pointer to the UI descriptor with the given index.
+            ///
+            /// This function is used by the host to discover UIs in the library. The host calls it with an ascending index and stores every returned descriptor,
+            /// until a null pointer is returned.
+            ///
+            /// # Safety
+            ///
+            /// This function is primarily unsafe because it's a method that's directly called by the host. It doesn't actually do anything that unsafe.
+            ///
+            /// The returned pointer references a constant and there is valid as long as the library is loaded.
+            #[no_mangle]
+            pub unsafe extern "C" fn lv2ui_descriptor(index: u32) -> *const LV2UI_Descriptor {
+                match index {
+                    #(#index_matchers)*
+                    _ => ::std::ptr::null()
+                }
+            }
+        }
+    }
+}
+
+/// Generate external symbols for LV2 plugin UIs.
+#[inline]
+pub fn lv2ui_descriptors_impl(input: TokenStream) -> TokenStream {
+    let list: Lv2UIInstanceDescriptorList = parse_macro_input!(input);
+    let descriptors = list.make_instance_descriptor_impls();
+    let export_function = list.make_descriptor_function();
+
+    (quote! {
+        #(#descriptors)*
+        #export_function
+    })
+    .into()
+}
@@ -0,0 +1,126 @@
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use urid::*;
+
+/// The function the host provides to write data to the plugin's input ports.
+type WriteFunction = unsafe extern "C" fn(sys::LV2UI_Controller, u32, u32, u32, *const c_void);
+
+/// The host-side controller of a UI, which forwards data to the plugin's ports.
+///
+/// The host passes a write function and an opaque controller pointer to the UI when it's instantiated. This struct wraps both, so that data can be sent to the plugin without dealing with raw pointers.
+#[derive(Clone, Copy)]
+pub struct Controller {
+    write_function: WriteFunction,
+    controller: sys::LV2UI_Controller,
+}
+
+impl Controller {
+    /// Create a new controller from the raw information provided by the host.
+    ///
+    /// Returns `None` if the host didn't provide a write function.
+    ///
+    /// # Safety
+    ///
+    /// The write function has to be valid to be called with the controller pointer as long as the UI instance lives.
+    pub unsafe fn from_raw(
+        write_function: sys::LV2UI_Write_Function,
+        controller: sys::LV2UI_Controller,
+    ) -> Option<Self> {
+        Some(Self {
+            write_function: write_function?,
+            controller,
+        })
+    }
+
+    /// Write raw data to a port of the plugin.
+    ///
+    /// The protocol defines how the data is interpreted. If it's `None`, the port has to be a control port and the data has to be a single `f32`, which is better done with [`write_control`](#method.write_control). The host ignores protocols it doesn't understand.
+    pub fn write(&self, port_index: u32, protocol: Option<URID>, buffer: &[u8]) {
+        unsafe {
+            (self.write_function)(
+                self.controller,
+                port_index,
+                buffer.len() as u32,
+                protocol.map(URID::get).unwrap_or(0),
+                buffer.as_ptr() as *const c_void,
+            )
+        }
+    }
+
+    /// Set the value of a control port of the plugin.
+    pub fn write_control(&self, port_index: u32, value: f32) {
+        self.write(port_index, None, &value.to_ne_bytes())
+    }
+
+    /// Return the raw controller pointer.
+    pub fn as_raw(&self) -> sys::LV2UI_Controller {
+        self.controller
+    }
+}
+
+/// A notification that something interesting has happened at a port of the plugin.
+///
+/// By default, the host sends these events when the value of a control input port changes, but UIs can request updates for other ports too.
+#[derive(Clone, Copy)]
+pub struct PortEvent<'a> {
+    port_index: u32,
+    protocol: Option<URID>,
+    buffer: &'a [u8],
+}
+
+impl<'a> PortEvent<'a> {
+    /// Create a new port event.
+    pub fn new(port_index: u32, protocol: Option<URID>, buffer: &'a [u8]) -> Self {
+        Self {
+            port_index,
+            protocol,
+            buffer,
+        }
+    }
+
+    /// Create a new port event from the raw information provided by the host.
+    ///
+    /// # Safety
+    ///
+    /// The buffer has to be valid for `buffer_size` bytes, or null.
+    pub unsafe fn from_raw(
+        port_index: u32,
+        buffer_size: u32,
+        format: u32,
+        buffer: *const c_void,
+    ) -> Self {
+        let buffer = if buffer.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(buffer as *const u8, buffer_size as usize)
+        };
+        Self::new(port_index, URID::try_from(format).ok(), buffer)
+    }
+
+    /// The index of the port.
+    pub fn port_index(&self) -> u32 {
+        self.port_index
+    }
+
+    /// The protocol of the event, or `None` if it's a value of a control port.
+    pub fn protocol(&self) -> Option<URID> {
+        self.protocol
+    }
+
+    /// The raw data of the event.
+    pub fn buffer(&self) -> &'a [u8] {
+        self.buffer
+    }
+
+    /// Return the new value of a control port.
+    ///
+    /// Returns `None` if the event has a protocol or doesn't contain a single `f32`.
+    pub fn control(&self) -> Option<f32> {
+        if self.protocol.is_some() {
+            return None;
+        }
+        <[u8; 4]>::try_from(self.buffer)
+            .ok()
+            .map(f32::from_ne_bytes)
+    }
+}
@@ -0,0 +1,248 @@
+use crate::Widget;
+use core::feature::*;
+use std::ffi::c_void;
+use urid::*;
+
+/// Host feature to subscribe to updates of the plugin's ports.
+///
+/// The ports a UI is notified about are usually declared statically in the UI's description with `ui:portNotification`. This feature allows a UI to subscribe to, and unsubscribe from, port notifications at runtime, for example to only receive the output of a meter while it's visible.
+///
+/// This feature may only be used by UIs and therefore, it can't be requested in the audio threading class.
+#[repr(transparent)]
+pub struct PortSubscribe<'a> {
+    internal: &'a sys::LV2UI_Port_Subscribe,
+}
+
+unsafe impl<'a> UriBound for PortSubscribe<'a> {
+    const URI: &'static [u8] = sys::LV2_UI__portSubscribe;
+}
+
+unsafe impl<'a> Feature for PortSubscribe<'a> {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[
+        ThreadingClass::Discovery,
+        ThreadingClass::Instantiation,
+        ThreadingClass::Other,
+    ];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+        (feature as *const sys::LV2UI_Port_Subscribe)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+impl<'a> PortSubscribe<'a> {
+    /// Subscribe to updates of a port.
+    ///
+    /// After subscribing, the host notifies the UI about changes at the port with the given protocol, for example the [`FloatProtocol`](struct.FloatProtocol.html) for control ports or the [`EventTransfer`](struct.EventTransfer.html) for atom ports. Returns `true` if the subscription was successful.
+    pub fn subscribe<P: ?Sized>(&self, port_index: u32, protocol: URID<P>) -> bool {
+        let subscribe = if let Some(subscribe) = self.internal.subscribe {
+            subscribe
+        } else {
+            return false;
+        };
+        unsafe {
+            subscribe(
+                self.internal.handle,
+                port_index,
+                protocol.get(),
+                std::ptr::null(),
+            ) == 0
+        }
+    }
+
+    /// Unsubscribe from updates of a port.
+    ///
+    /// Returns `true` if the subscription was cancelled.
+    pub fn unsubscribe<P: ?Sized>(&self, port_index: u32, protocol: URID<P>) -> bool {
+        let unsubscribe = if let Some(unsubscribe) = self.internal.unsubscribe {
+            unsubscribe
+        } else {
+            return false;
+        };
+        unsafe {
+            unsubscribe(
+                self.internal.handle,
+                port_index,
+                protocol.get(),
+                std::ptr::null(),
+            ) == 0
+        }
+    }
+}
+
+/// Errors that may occur when requesting a parameter value from the host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestValueError {
+    /// The host is already requesting a value, for example because a dialog is still visible.
+    Busy,
+    /// The host doesn't know the requested parameter.
+    UnknownParameter,
+    /// The host knows the parameter, but can't request a value for it.
+    Unsupported,
+}
+
+/// Host feature to request a new value for a parameter from the user.
+///
+/// This is mainly used for parameters that are hard to edit in a UI, like file paths: Instead of implementing its own file chooser, the UI asks the host to show one. The request returns immediately and if the user selects a value, the host sends it to the plugin, which notifies the UI about the new value just like for any other parameter change.
+///
+/// This feature may only be used by UIs and therefore, it can't be requested in the audio threading class.
+#[repr(transparent)]
+pub struct RequestValue<'a> {
+    internal: &'a sys::LV2UI_Request_Value,
+}
+
+unsafe impl<'a> UriBound for RequestValue<'a> {
+    const URI: &'static [u8] = sys::LV2_UI__requestValue;
+}
+
+unsafe impl<'a> Feature for RequestValue<'a> {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[
+        ThreadingClass::Discovery,
+        ThreadingClass::Instantiation,
+        ThreadingClass::Other,
+    ];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+        (feature as *const sys::LV2UI_Request_Value)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+impl<'a> RequestValue<'a> {
+    /// Request a new value for a parameter.
+    ///
+    /// The key is the URID of the parameter, for example a property of the plugin. Some parameters support values of several types and in this case, the type of the requested value may be given too.
+    pub fn request<K: ?Sized>(
+        &self,
+        key: URID<K>,
+        value_type: Option<URID>,
+    ) -> Result<(), RequestValueError> {
+        let request = if let Some(request) = self.internal.request {
+            request
+        } else {
+            return Err(RequestValueError::Unsupported);
+        };
+        let status = unsafe {
+            request(
+                self.internal.handle,
+                key.get(),
+                value_type.map(URID::get).unwrap_or(0),
+                std::ptr::null(),
+            )
+        };
+        match status {
+            sys::LV2UI_Request_Value_Status_LV2UI_REQUEST_VALUE_SUCCESS => Ok(()),
+            sys::LV2UI_Request_Value_Status_LV2UI_REQUEST_VALUE_BUSY => {
+                Err(RequestValueError::Busy)
+            }
+            sys::LV2UI_Request_Value_Status_LV2UI_REQUEST_VALUE_ERR_UNKNOWN => {
+                Err(RequestValueError::UnknownParameter)
+            }
+            _ => Err(RequestValueError::Unsupported),
+        }
+    }
+}
+
+/// Host feature to notify the host that a control has been grabbed or released.
+///
+/// While the user drags a knob or a slider, the host should stop automating the port, and some hosts record the changes of a gesture as a single undo step. The UI tells the host where a gesture begins and ends by grabbing and releasing the port's control.
+///
+/// This feature may only be used by UIs and therefore, it can't be requested in the audio threading class.
+#[repr(transparent)]
+pub struct Touch<'a> {
+    internal: &'a sys::LV2UI_Touch,
+}
+
+unsafe impl<'a> UriBound for Touch<'a> {
+    const URI: &'static [u8] = sys::LV2_UI__touch;
+}
+
+unsafe impl<'a> Feature for Touch<'a> {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[
+        ThreadingClass::Discovery,
+        ThreadingClass::Instantiation,
+        ThreadingClass::Other,
+    ];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+        (feature as *const sys::LV2UI_Touch)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
+    }
+}
+
+impl<'a> Touch<'a> {
+    /// Tell the host that the control of a port has been grabbed or released.
+    pub fn touch(&self, port_index: u32, grabbed: bool) {
+        if let Some(touch) = self.internal.touch {
+            unsafe { touch(self.internal.handle, port_index, grabbed) }
+        }
+    }
+}
+
+/// Host feature providing the parent window of the UI.
+///
+/// Hosts that embed UIs pass the window the UI's widget should be embedded into with this feature. The kind of window depends on the type of the UI: For an X11 UI, it's the ID of an X11 window, for a Windows UI, it's a `HWND` and for a Cocoa UI, it's a pointer to an `NSView`. The UI then creates its own window as a child of the parent and returns it as its [`widget`](trait.PluginUI.html#method.widget).
+///
+/// This feature may only be used by UIs and therefore, it can't be requested in the audio threading class.
+pub struct Parent {
+    widget: Widget,
+}
+
+unsafe impl UriBound for Parent {
+    const URI: &'static [u8] = sys::LV2_UI__parent;
+}
+
+unsafe impl Feature for Parent {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[
+        ThreadingClass::Discovery,
+        ThreadingClass::Instantiation,
+        ThreadingClass::Other,
+    ];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+        if feature.is_null() {
+            Err(FeatureError::invalid_data::<Self>())
+        } else {
+            Ok(Self {
+                widget: Widget::from_raw(feature as sys::LV2UI_Widget),
+            })
+        }
+    }
+}
+
+impl Parent {
+    /// The parent window, as a widget.
+    pub fn widget(&self) -> Widget {
+        self.widget
+    }
+}
@@ -0,0 +1,67 @@
+use std::os::raw::c_char;
+use std::path::Path;
+use std::str::Utf8Error;
+use urid::Uri;
+
+#[derive(Debug)]
+pub enum UIInfoError {
+    InvalidBundlePathUtf8(Utf8Error),
+}
+
+/// Holds various data that is passed from the host at UI instantiation time.
+pub struct UIInfo<'a> {
+    ui_uri: &'a Uri,
+    plugin_uri: &'a Uri,
+    bundle_path: &'a Path,
+}
+
+impl<'a> UIInfo<'a> {
+    /// Create a new UI info instance from raw information.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it dereferences raw pointers. It panics when one of the pointers is null,
+    /// but does not check the pointers for other validity.
+    pub unsafe fn from_raw(
+        ui_descriptor: *const sys::LV2UI_Descriptor,
+        plugin_uri: *const c_char,
+        bundle_path: *const c_char,
+    ) -> Result<Self, UIInfoError> {
+        let bundle_path = Path::new(
+            Uri::from_ptr(bundle_path)
+                .to_str()
+                .map_err(UIInfoError::InvalidBundlePathUtf8)?,
+        );
+        Ok(Self::new(
+            Uri::from_ptr((*ui_descriptor).URI),
+            Uri::from_ptr(plugin_uri),
+            bundle_path,
+        ))
+    }
+
+    /// Create a new UI info instance.
+    pub fn new(ui_uri: &'a Uri, plugin_uri: &'a Uri, bundle_path: &'a Path) -> Self {
+        Self {
+            ui_uri,
+            plugin_uri,
+            bundle_path,
+        }
+    }
+
+    /// The URI of the UI that is being instantiated.
+    pub fn ui_uri(&self) -> &Uri {
+        self.ui_uri
+    }
+
+    /// The URI of the plugin this UI controls.
+    pub fn plugin_uri(&self) -> &Uri {
+        self.plugin_uri
+    }
+
+    /// The path to the LV2 bundle directory which contains this UI binary.
+    ///
+    /// This is useful to load extra resources of the UI, like images or fonts, from the bundle directory.
+    pub fn bundle_path(&self) -> &Path {
+        self.bundle_path
+    }
+}
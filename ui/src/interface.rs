@@ -0,0 +1,131 @@
+use crate::{PluginUI, UIInstance};
+use core::extension::ExtensionDescriptor;
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use urid::*;
+
+/// A UI extension that lets the host drive the UI's event loop.
+///
+/// Some hosts don't run a toolkit main loop the UI could hook into, for example because they use a different toolkit or none at all. If a UI implements this extension, the host calls [`idle`](#tymethod.idle) rapidly in the UI thread, at least 30 times per second, which gives the UI the opportunity to process its own events and redraw itself.
+///
+/// Hosts look for the `ui:idleInterface` in the UI's extension data, which is why the UI has to return the [`IdleDescriptor`](struct.IdleDescriptor.html) from its `extension_data` method, for example with the [`match_extensions`](../lv2_core/macro.match_extensions.html) macro.
+pub trait Idle: PluginUI {
+    /// Run a single iteration of the UI's event loop.
+    ///
+    /// Returns `true` as long as the UI is open. Once it returns `false`, the host stops calling this method and may either destroy the UI or show it again.
+    fn idle(&mut self, features: &mut Self::Features) -> bool;
+}
+
+/// A UI extension that lets the host show and hide the UI's own window.
+///
+/// This allows UIs to work in hosts that can't embed their widget: Instead, the UI opens its own window when it's shown and closes it when it's hidden. Since the UI's window has to be updated too, the host drives it with the [`Idle`](trait.Idle.html) extension, which is therefore required. When the user closes the window, [`idle`](trait.Idle.html#tymethod.idle) has to return `false`, after which the host calls [`hide`](#tymethod.hide).
+///
+/// Just like the idle extension, the UI has to return the [`ShowDescriptor`](struct.ShowDescriptor.html) from its `extension_data` method, in addition to the `IdleDescriptor`.
+pub trait Show: Idle {
+    /// Show the window of the UI.
+    ///
+    /// Returns `false` if the window couldn't be shown, in which case the host stops calling the UI.
+    fn show(&mut self, features: &mut Self::Features) -> bool;
+
+    /// Hide the window of the UI.
+    ///
+    /// Returns `false` if the window couldn't be hidden, in which case the host stops calling the UI.
+    fn hide(&mut self, features: &mut Self::Features) -> bool;
+}
+
+/// Raw wrapper of the [`Idle`](trait.Idle.html) extension.
+///
+/// This is a marker type that has the required external methods for the extension.
+pub struct IdleDescriptor<T: Idle> {
+    ui: PhantomData<T>,
+}
+
+unsafe impl<T: Idle> UriBound for IdleDescriptor<T> {
+    const URI: &'static [u8] = sys::LV2_UI__idleInterface;
+}
+
+impl<T: Idle> IdleDescriptor<T> {
+    /// Handle an idle call by the host.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it is an interface for hosts written in C and since it dereferences raw pointers.
+    pub unsafe extern "C" fn extern_idle(handle: sys::LV2UI_Handle) -> c_int {
+        let instance = if let Some(instance) = (handle as *mut UIInstance<T>).as_mut() {
+            instance
+        } else {
+            return 1;
+        };
+        let (ui, features) = instance.ui_class_handle();
+        if ui.idle(features) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl<T: Idle> ExtensionDescriptor for IdleDescriptor<T> {
+    type ExtensionInterface = sys::LV2UI_Idle_Interface;
+
+    const INTERFACE: &'static sys::LV2UI_Idle_Interface = &sys::LV2UI_Idle_Interface {
+        idle: Some(Self::extern_idle),
+    };
+}
+
+/// Raw wrapper of the [`Show`](trait.Show.html) extension.
+///
+/// This is a marker type that has the required external methods for the extension.
+pub struct ShowDescriptor<T: Show> {
+    ui: PhantomData<T>,
+}
+
+unsafe impl<T: Show> UriBound for ShowDescriptor<T> {
+    const URI: &'static [u8] = sys::LV2_UI__showInterface;
+}
+
+impl<T: Show> ShowDescriptor<T> {
+    /// Handle a show request by the host.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it is an interface for hosts written in C and since it dereferences raw pointers.
+    pub unsafe extern "C" fn extern_show(handle: sys::LV2UI_Handle) -> c_int {
+        Self::call(handle, T::show)
+    }
+
+    /// Handle a hide request by the host.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it is an interface for hosts written in C and since it dereferences raw pointers.
+    pub unsafe extern "C" fn extern_hide(handle: sys::LV2UI_Handle) -> c_int {
+        Self::call(handle, T::hide)
+    }
+
+    unsafe fn call(
+        handle: sys::LV2UI_Handle,
+        method: fn(&mut T, &mut T::Features) -> bool,
+    ) -> c_int {
+        let instance = if let Some(instance) = (handle as *mut UIInstance<T>).as_mut() {
+            instance
+        } else {
+            return 1;
+        };
+        let (ui, features) = instance.ui_class_handle();
+        if method(ui, features) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl<T: Show> ExtensionDescriptor for ShowDescriptor<T> {
+    type ExtensionInterface = sys::LV2UI_Show_Interface;
+
+    const INTERFACE: &'static sys::LV2UI_Show_Interface = &sys::LV2UI_Show_Interface {
+        show: Some(Self::extern_show),
+        hide: Some(Self::extern_hide),
+    };
+}
@@ -0,0 +1,187 @@
+//! User interfaces for LV2 plugins.
+//!
+//! The [LV2 UI specification](https://lv2plug.in/ns/extensions/ui/ui.html) allows plugins to have user interfaces that run separately from the plugin's audio processing, usually in a different thread and sometimes even in a different process. The UI can only communicate with the plugin via its ports: It sends data to the input ports of the plugin via the host and the host notifies the UI about changes at the plugin's ports.
+//!
+//! This crate lets you write these UIs in Rust: You implement the [`PluginUI`](trait.PluginUI.html) trait for your UI struct and export it with the [`lv2ui_descriptors`](macro.lv2ui_descriptors.html) macro, just like plugins are exported with `lv2_descriptors`.
+//!
+//! ## Example usage
+//!
+//! ```
+//! use lv2_ui::prelude::*;
+//! use urid::*;
+//!
+//! const GAIN_PORT: u32 = 0;
+//!
+//! #[uri("urn:lv2_ui:amp-ui")]
+//! struct AmpUI {
+//!     controller: Controller,
+//!     gain: f32,
+//! }
+//!
+//! impl PluginUI for AmpUI {
+//!     type Features = ();
+//!
+//!     fn new(_: &UIInfo, controller: Controller, _: &mut ()) -> Option<Self> {
+//!         Some(Self {
+//!             controller,
+//!             gain: 0.0,
+//!         })
+//!     }
+//!
+//!     fn port_event(&mut self, event: &PortEvent, _: &mut ()) {
+//!         // Keep track of the current gain.
+//!         if event.port_index() == GAIN_PORT {
+//!             if let Some(gain) = event.control() {
+//!                 self.gain = gain;
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! impl AmpUI {
+//!     /// Called by the toolkit when the user turns the knob.
+//!     fn knob_turned(&mut self, gain: f32) {
+//!         self.gain = gain;
+//!         self.controller.write_control(GAIN_PORT, gain);
+//!     }
+//! }
+//!
+//! lv2ui_descriptors!(AmpUI);
+//! ```
+//!
+//! UIs with many controls can leave the bookkeeping to a [`UiModel`](struct.UiModel.html), which tracks the values of the control ports, coalesces the changes of the user and notifies the host about gestures, while the widgets only deal with port indices and plain values.
+//!
+//! ## Communicating with atoms
+//!
+//! Plugins with more complex state, like parameters or samples, communicate with their UIs via atom ports: The UI writes atoms to an atom input port of the plugin with [`Controller::write_atom`](struct.Controller.html#method.write_atom) or [`Controller::write_event`](struct.Controller.html#method.write_event), and the plugin writes atoms to an atom output port, which the host forwards to the UI. The host only forwards them if the UI requests it, either statically by declaring a `ui:portNotification` with the `atom:eventTransfer` protocol in its description, or at runtime with the [`PortSubscribe`](struct.PortSubscribe.html) feature. The forwarded atoms are retrieved with [`PortEvent::atom`](struct.PortEvent.html#method.atom):
+//!
+//! ```
+//! use lv2_atom::prelude::*;
+//! use lv2_ui::prelude::*;
+//! use urid::*;
+//!
+//! const CONTROL_PORT: u32 = 0;
+//! const NOTIFY_PORT: u32 = 1;
+//!
+//! #[derive(URIDCollection)]
+//! struct URIDs {
+//!     atom: AtomURIDCollection,
+//!     protocol: PortProtocolURIDCollection,
+//! }
+//!
+//! fn port_event(event: &PortEvent, controller: &Controller, urids: &URIDs) {
+//!     if event.port_index() != NOTIFY_PORT {
+//!         return;
+//!     }
+//!     // Answer every integer from the plugin with its successor.
+//!     if let Some(number) = event
+//!         .atom(&urids.protocol)
+//!         .and_then(|atom| atom.read(urids.atom.int, ()))
+//!     {
+//!         controller.write_event(CONTROL_PORT, &urids.protocol, |space| {
+//!             space.init(urids.atom.int, number + 1).map(|_| ())
+//!         });
+//!     }
+//! }
+//! ```
+//!
+//! ## Meters
+//!
+//! UIs that only display the level of an audio port don't need its samples: If they subscribe to the port with the [`PeakProtocol`](struct.PeakProtocol.html), the host only sends the peak of every cycle, which is retrieved with [`PortEvent::peak`](struct.PortEvent.html#method.peak). The peaks are measured with a [`PeakMeter`](struct.PeakMeter.html), which plugins can also use to publish the level of a signal on a control output port.
+//!
+//! Plugins with more values for their UI, like several meters or the frames of a spectrum analyzer, publish them with a [`TelemetryPublisher`](struct.TelemetryPublisher.html): It collects the values of a plugin and writes them to an atom output port as one object every few cycles, which the UI reads with [`Telemetry::read`](struct.Telemetry.html#method.read).
+//!
+//! ## Showing your own window
+//!
+//! Not every host can embed every kind of widget. UIs that open their own window instead implement the [`Show`](trait.Show.html) and [`Idle`](trait.Idle.html) extensions: The host tells the UI when to show and hide its window and then calls [`idle`](trait.Idle.html#tymethod.idle) regularly, which is where the UI runs an iteration of its toolkit's event loop.
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_ui::prelude::*;
+//! use std::any::Any;
+//! use urid::*;
+//!
+//! #[uri("urn:lv2_ui:window-ui")]
+//! struct WindowUI {
+//!     visible: bool,
+//! }
+//!
+//! impl PluginUI for WindowUI {
+//!     type Features = ();
+//!
+//!     fn new(_: &UIInfo, _: Controller, _: &mut ()) -> Option<Self> {
+//!         Some(Self { visible: false })
+//!     }
+//!
+//!     fn extension_data(uri: &Uri) -> Option<&'static dyn Any> {
+//!         match_extensions![uri, IdleDescriptor<Self>, ShowDescriptor<Self>]
+//!     }
+//! }
+//!
+//! impl Idle for WindowUI {
+//!     fn idle(&mut self, _: &mut ()) -> bool {
+//!         // Process the events of the window here.
+//!         self.visible
+//!     }
+//! }
+//!
+//! impl Show for WindowUI {
+//!     fn show(&mut self, _: &mut ()) -> bool {
+//!         self.visible = true;
+//!         true
+//!     }
+//!
+//!     fn hide(&mut self, _: &mut ()) -> bool {
+//!         self.visible = false;
+//!         true
+//!     }
+//! }
+//!
+//! lv2ui_descriptors!(WindowUI);
+//! ```
+extern crate lv2_atom as atom;
+extern crate lv2_core as core;
+extern crate lv2_sys as sys;
+
+mod controller;
+pub use controller::*;
+
+mod feature;
+pub use feature::*;
+
+mod info;
+pub use info::*;
+
+mod interface;
+pub use interface::*;
+
+mod model;
+pub use model::*;
+
+mod peak;
+pub use peak::*;
+
+mod protocol;
+pub use protocol::*;
+
+mod telemetry;
+pub use telemetry::*;
+
+mod ui;
+pub use ui::*;
+
+#[cfg(feature = "raw-window-handle")]
+pub mod raw_window_handle_binding;
+
+pub use lv2_ui_derive::*;
+
+/// Prelude of `lv2_ui` for wildcard usage.
+pub mod prelude {
+    pub use crate::{
+        lv2ui_descriptors, Controller, Idle, IdleDescriptor, Parent, PeakData, PeakMeter,
+        PeakProtocol, PluginUI, PortEvent, PortProtocolURIDCollection, PortSubscribe, RequestValue,
+        RequestValueError, Show, ShowDescriptor, Telemetry, TelemetryPublisher,
+        TelemetryURIDCollection, Touch, UIInfo, UIInstance, UIInstanceDescriptor, UiModel, Widget,
+    };
+    pub use sys::LV2UI_Descriptor;
+}
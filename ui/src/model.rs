@@ -0,0 +1,208 @@
+use crate::{Controller, PortEvent, Touch};
+use std::collections::BTreeMap;
+
+/// The state of a single control port in the [`UiModel`](struct.UiModel.html).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Control {
+    value: f32,
+    /// The value has been changed by the host and the change hasn't been taken yet.
+    changed: bool,
+    /// The value has been changed by the UI and hasn't been sent to the plugin yet.
+    pending: bool,
+    /// A gesture is in progress.
+    touched: bool,
+    /// The beginning of a gesture hasn't been sent to the host yet.
+    grab: bool,
+    /// The end of a gesture hasn't been sent to the host yet.
+    release: bool,
+}
+
+/// A toolkit-agnostic model of the controls of a plugin.
+///
+/// Widgets of GUI toolkits usually have their own way to store and observe values, and binding them to the ports of a plugin involves the same steps for every toolkit: The values are updated when the host sends port events, the changes of the user are sent to the plugin, and the host is told when the user grabs and releases a control. The model does all of this and only exposes port indices and plain `f32`s, which makes the widgets independent of LV2.
+///
+/// Changes of the user are coalesced: [`set`](#method.set) only stores the new value, and the values are sent when the model is [flushed](#method.flush), which is usually done once per [idle](trait.Idle.html#tymethod.idle) call or per frame of the toolkit. If a widget emits many values between two flushes, only the last one is sent. While a gesture is in progress, port events of the host for that port are ignored, since they are only the echo of older values and would make the widget jump back.
+///
+/// Changes of the host are observed with [`take_changes`](#method.take_changes), which returns every port whose value was changed by the host since the last call.
+///
+/// # Example
+///
+/// ```
+/// use lv2_ui::prelude::*;
+///
+/// const GAIN_PORT: u32 = 0;
+///
+/// struct Knob {
+///     value: f32,
+/// }
+///
+/// struct AmpUI {
+///     model: UiModel,
+///     controller: Controller,
+///     knob: Knob,
+/// }
+///
+/// impl AmpUI {
+///     fn port_event(&mut self, event: &PortEvent) {
+///         self.model.port_event(event);
+///     }
+///
+///     /// Called by the toolkit while the user drags the knob.
+///     fn knob_dragged(&mut self, value: f32) {
+///         self.model.begin_gesture(GAIN_PORT);
+///         self.model.set(GAIN_PORT, value);
+///     }
+///
+///     /// Called by the toolkit when the user releases the knob.
+///     fn knob_released(&mut self) {
+///         self.model.end_gesture(GAIN_PORT);
+///     }
+///
+///     /// Called regularly, for example by the idle interface.
+///     fn idle(&mut self, touch: Option<&Touch>) {
+///         self.model.flush(&self.controller, touch);
+///         for (port_index, value) in self.model.take_changes() {
+///             if port_index == GAIN_PORT {
+///                 self.knob.value = value;
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UiModel {
+    controls: BTreeMap<u32, Control>,
+}
+
+impl UiModel {
+    /// Create an empty model.
+    ///
+    /// Ports are added when the host sends their first value or when they are [set](#method.set).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a control port with a default value, which is used until the host sends the actual value.
+    pub fn with_control(mut self, port_index: u32, default: f32) -> Self {
+        self.controls.insert(
+            port_index,
+            Control {
+                value: default,
+                ..Default::default()
+            },
+        );
+        self
+    }
+
+    /// The current value of a control port, or `None` if the port is unknown.
+    ///
+    /// The value includes changes of the user that haven't been sent yet.
+    pub fn value(&self, port_index: u32) -> Option<f32> {
+        self.controls.get(&port_index).map(|control| control.value)
+    }
+
+    /// Iterate over all known ports and their values, in the order of their indices.
+    pub fn values(&self) -> impl Iterator<Item = (u32, f32)> + '_ {
+        self.controls
+            .iter()
+            .map(|(port_index, control)| (*port_index, control.value))
+    }
+
+    /// Check if a gesture is in progress for a port.
+    pub fn is_touched(&self, port_index: u32) -> bool {
+        self.controls
+            .get(&port_index)
+            .is_some_and(|control| control.touched)
+    }
+
+    /// Update the model with a port event of the host.
+    ///
+    /// Events that don't contain control values are ignored, as well as events for ports with a gesture in progress or with a change of the user that hasn't been sent yet. Returns `true` if the value of the port has changed.
+    pub fn port_event(&mut self, event: &PortEvent) -> bool {
+        let value = match event.control() {
+            Some(value) => value,
+            None => return false,
+        };
+        let control = self.controls.entry(event.port_index()).or_default();
+        if control.touched || control.pending || control.value == value {
+            return false;
+        }
+        control.value = value;
+        control.changed = true;
+        true
+    }
+
+    /// Set the value of a control port, as the user did.
+    ///
+    /// The value is sent to the plugin with the next [`flush`](#method.flush). Changes of the user aren't returned by [`take_changes`](#method.take_changes).
+    pub fn set(&mut self, port_index: u32, value: f32) {
+        let control = self.controls.entry(port_index).or_default();
+        control.value = value;
+        control.pending = true;
+    }
+
+    /// Begin a gesture for a port, like grabbing a knob.
+    ///
+    /// Does nothing if a gesture is already in progress.
+    pub fn begin_gesture(&mut self, port_index: u32) {
+        let control = self.controls.entry(port_index).or_default();
+        if control.touched {
+            return;
+        }
+        control.touched = true;
+        if control.release {
+            // The gesture was released and grabbed again before flushing, which is sent as one longer gesture.
+            control.release = false;
+        } else {
+            control.grab = true;
+        }
+    }
+
+    /// End the gesture of a port, like releasing a knob.
+    ///
+    /// Does nothing if no gesture is in progress.
+    pub fn end_gesture(&mut self, port_index: u32) {
+        if let Some(control) = self.controls.get_mut(&port_index) {
+            if control.touched {
+                control.touched = false;
+                control.release = true;
+            }
+        }
+    }
+
+    /// Send all pending changes to the plugin and the host.
+    ///
+    /// For every port, the beginning of a gesture is sent first, then the latest value and then the end of the gesture. Gestures are only sent if the host provides the [`Touch`](struct.Touch.html) feature.
+    pub fn flush(&mut self, controller: &Controller, touch: Option<&Touch>) {
+        for (port_index, control) in self.controls.iter_mut() {
+            if control.grab {
+                if let Some(touch) = touch {
+                    touch.touch(*port_index, true);
+                }
+            }
+            if control.pending {
+                controller.write_control(*port_index, control.value);
+            }
+            if control.release {
+                if let Some(touch) = touch {
+                    touch.touch(*port_index, false);
+                }
+            }
+            control.grab = false;
+            control.pending = false;
+            control.release = false;
+        }
+    }
+
+    /// Return all ports whose values were changed by the host since the last call, with their new values.
+    pub fn take_changes(&mut self) -> Vec<(u32, f32)> {
+        self.controls
+            .iter_mut()
+            .filter(|(_, control)| control.changed)
+            .map(|(port_index, control)| {
+                control.changed = false;
+                (*port_index, control.value)
+            })
+            .collect()
+    }
+}
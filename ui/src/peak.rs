@@ -0,0 +1,127 @@
+use crate::{PortEvent, PortProtocolURIDCollection};
+use std::convert::TryFrom;
+use std::mem::size_of;
+use urid::*;
+
+/// The protocol to transfer peak measurements of audio ports.
+///
+/// Meters don't need every sample of an audio port, only the highest absolute value of every measurement period. When a UI subscribes to an audio port with this protocol, either statically with `ui:portNotification` or with the [`PortSubscribe`](struct.PortSubscribe.html) feature, the host measures the peaks and sends them to the UI as [`PeakData`](struct.PeakData.html).
+pub struct PeakProtocol;
+
+unsafe impl UriBound for PeakProtocol {
+    const URI: &'static [u8] = sys::LV2_UI__peakProtocol;
+}
+
+/// A peak measurement for a period of time, as sent with the [`PeakProtocol`](struct.PeakProtocol.html).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PeakData {
+    /// The start of the measurement period.
+    ///
+    /// This is a running counter that can only be compared to the starts of other periods, not an absolute time.
+    pub period_start: u32,
+    /// The length of the measurement period, in the same unit as the start.
+    pub period_size: u32,
+    /// The highest absolute value of all samples in the period.
+    pub peak: f32,
+}
+
+impl PeakData {
+    /// Read peak data from the buffer of a port event.
+    ///
+    /// Returns `None` if the buffer doesn't have the size of the peak data.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes = <[u8; size_of::<sys::LV2UI_Peak_Data>()]>::try_from(bytes).ok()?;
+        let field = |offset: usize| {
+            [
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ]
+        };
+        Some(Self {
+            period_start: u32::from_ne_bytes(field(0)),
+            period_size: u32::from_ne_bytes(field(4)),
+            peak: f32::from_ne_bytes(field(8)),
+        })
+    }
+
+    /// Write the peak data in the layout of the C struct, which is how it's sent to UIs.
+    pub fn to_bytes(&self) -> [u8; size_of::<sys::LV2UI_Peak_Data>()] {
+        let mut bytes = [0; size_of::<sys::LV2UI_Peak_Data>()];
+        bytes[0..4].copy_from_slice(&self.period_start.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&self.period_size.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&self.peak.to_ne_bytes());
+        bytes
+    }
+}
+
+impl<'a> PortEvent<'a> {
+    /// Return the peak measurement that was sent with this event.
+    ///
+    /// Returns `None` if the event doesn't use the [`PeakProtocol`](struct.PeakProtocol.html) or doesn't contain valid peak data.
+    pub fn peak(&self, urids: &PortProtocolURIDCollection) -> Option<PeakData> {
+        if self.protocol()? != urids.peak_protocol {
+            return None;
+        }
+        PeakData::from_bytes(self.buffer())
+    }
+}
+
+/// Measures the peaks of an audio signal once per cycle.
+///
+/// Hosts use this to measure the audio ports that UIs subscribed to with the [`PeakProtocol`](struct.PeakProtocol.html), and plugins can use it to publish the peaks of a signal themselves, for example on a control output port that is displayed as a meter. Every call to [`measure`](#method.measure) covers one cycle, and the periods are counted in frames, which makes consecutive periods line up.
+///
+/// Measuring doesn't allocate and can therefore be done in the `run()` context.
+///
+/// # Example
+///
+/// ```
+/// use lv2_core::prelude::*;
+/// use lv2_ui::PeakMeter;
+///
+/// #[derive(PortCollection)]
+/// struct Ports {
+///     input: InputPort<Audio>,
+///     output: OutputPort<Audio>,
+///     level: OutputPort<Control>,
+/// }
+///
+/// struct Meter {
+///     meter: PeakMeter,
+/// }
+///
+/// impl Meter {
+///     fn run(&mut self, ports: &mut Ports) {
+///         ports.output.copy_from_slice(&ports.input);
+///         **ports.level = self.meter.measure(&ports.input).peak;
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PeakMeter {
+    position: u32,
+}
+
+impl PeakMeter {
+    /// Create a new meter, whose first period starts at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Measure the peak of the samples of one cycle.
+    ///
+    /// The period of the returned data starts where the period of the previous cycle ended. The start wraps around once it exceeds the range of a `u32`.
+    pub fn measure(&mut self, samples: &[f32]) -> PeakData {
+        let peak = samples
+            .iter()
+            .fold(0.0, |peak: f32, sample| peak.max(sample.abs()));
+        let data = PeakData {
+            period_start: self.position,
+            period_size: samples.len() as u32,
+            peak,
+        };
+        self.position = self.position.wrapping_add(data.period_size);
+        data
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{Controller, PeakProtocol, PortEvent};
+use atom::prelude::*;
+use atom::space::*;
+use std::mem::size_of;
+use urid::*;
+
+/// The protocol to transfer the value of a control port.
+///
+/// This is the default protocol: When a UI writes to a port without a protocol, or is notified about a port without one, the data is a single `f32`.
+pub struct FloatProtocol;
+
+unsafe impl UriBound for FloatProtocol {
+    const URI: &'static [u8] = sys::LV2_UI__floatProtocol;
+}
+
+/// The protocol to transfer a single atom to or from a port.
+///
+/// When a UI writes an atom to an atom input port with this protocol, the plugin receives it like any other atom, for example as the only event of a sequence.
+pub struct AtomTransfer;
+
+unsafe impl UriBound for AtomTransfer {
+    const URI: &'static [u8] = sys::LV2_ATOM__atomTransfer;
+}
+
+/// The protocol to transfer events to or from a port.
+///
+/// When a UI writes an atom to an atom input port with this protocol, the host appends it as an event to the port's sequence. When the UI is notified about an output port with this protocol, every event the plugin wrote is sent to the UI individually.
+pub struct EventTransfer;
+
+unsafe impl UriBound for EventTransfer {
+    const URI: &'static [u8] = sys::LV2_ATOM__eventTransfer;
+}
+
+/// A URID cache containing the port protocols to transfer values, atoms and peaks.
+#[derive(URIDCollection)]
+pub struct PortProtocolURIDCollection {
+    pub float_protocol: URID<FloatProtocol>,
+    pub atom_transfer: URID<AtomTransfer>,
+    pub event_transfer: URID<EventTransfer>,
+    pub peak_protocol: URID<PeakProtocol>,
+}
+
+impl Controller {
+    /// Write an atom to an atom input port of the plugin, with the `atom:atomTransfer` protocol.
+    ///
+    /// The atom is written by the given function to a temporary buffer and then sent to the host. The function receives a space to initialize the atom in, just like an atom port, and returns `None` if writing failed, in which case nothing is sent.
+    ///
+    /// Returns `true` if the atom was sent.
+    pub fn write_atom<F>(
+        &self,
+        port_index: u32,
+        urids: &PortProtocolURIDCollection,
+        write: F,
+    ) -> bool
+    where
+        F: FnOnce(&mut dyn MutSpace) -> Option<()>,
+    {
+        self.write_with_protocol(port_index, urids.atom_transfer.into_general(), write)
+    }
+
+    /// Write an atom to an atom input port of the plugin, with the `atom:eventTransfer` protocol.
+    ///
+    /// This works like [`write_atom`](#method.write_atom), but the host appends the atom as an event to the port's sequence.
+    pub fn write_event<F>(
+        &self,
+        port_index: u32,
+        urids: &PortProtocolURIDCollection,
+        write: F,
+    ) -> bool
+    where
+        F: FnOnce(&mut dyn MutSpace) -> Option<()>,
+    {
+        self.write_with_protocol(port_index, urids.event_transfer.into_general(), write)
+    }
+
+    fn write_with_protocol<F>(&self, port_index: u32, protocol: URID, write: F) -> bool
+    where
+        F: FnOnce(&mut dyn MutSpace) -> Option<()>,
+    {
+        let mut element = SpaceElement::default();
+        {
+            let mut head = SpaceHead::new(&mut element);
+            if write(&mut head).is_none() {
+                return false;
+            }
+        }
+        let data = element.to_vec();
+        if data.len() < size_of::<sys::LV2_Atom>() {
+            return false;
+        }
+        self.write(port_index, Some(protocol), &data);
+        true
+    }
+}
+
+impl<'a> PortEvent<'a> {
+    /// Return the atom that was transferred with this event.
+    ///
+    /// This works for events with the `atom:atomTransfer` and `atom:eventTransfer` protocols, which the host uses to notify the UI about atoms written to the plugin's atom ports. The event has to contain a complete atom, or `None` is returned.
+    pub fn atom(&self, urids: &PortProtocolURIDCollection) -> Option<UnidentifiedAtom<'a>> {
+        let protocol = self.protocol()?;
+        if protocol != urids.atom_transfer && protocol != urids.event_transfer {
+            return None;
+        }
+        let (atom, _) = Space::from_slice(self.buffer()).split_atom()?;
+        Some(UnidentifiedAtom::new(atom))
+    }
+}
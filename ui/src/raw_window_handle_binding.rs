@@ -0,0 +1,50 @@
+//! Integration with the [`raw-window-handle`](https://crates.io/crates/raw-window-handle) crate.
+//!
+//! Windowing libraries like `baseview` or `winit` use `RawWindowHandle`s to describe native windows. This module converts between them and widgets, which allows UIs to open their window as a child of the host's [`Parent`](../struct.Parent.html) window without dealing with the platform-specific window types themselves.
+use crate::{Parent, Widget};
+use raw_window_handle::*;
+
+/// The widget is interpreted as a native window of the current platform: An X11 window on Linux and other Unix systems, a `HWND` on Windows and an `NSView` on macOS, which are the widget types of X11, Windows and Cocoa UIs, respectively.
+unsafe impl HasRawWindowHandle for Widget {
+    #[cfg(target_os = "windows")]
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        let mut handle = Win32WindowHandle::empty();
+        handle.hwnd = self.as_raw();
+        RawWindowHandle::Win32(handle)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        let mut handle = AppKitWindowHandle::empty();
+        handle.ns_view = self.as_raw();
+        RawWindowHandle::AppKit(handle)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        let mut handle = XlibWindowHandle::empty();
+        handle.window = self.as_raw() as _;
+        RawWindowHandle::Xlib(handle)
+    }
+}
+
+unsafe impl HasRawWindowHandle for Parent {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.widget().raw_window_handle()
+    }
+}
+
+impl Widget {
+    /// Create a widget from the handle of a native window.
+    ///
+    /// This is used to return a window created by a windowing library as the widget of the UI. Returns `None` if the window type can't be used as a widget, because it's not one of the X11, Windows or Cocoa windows.
+    pub fn from_raw_window_handle(handle: RawWindowHandle) -> Option<Self> {
+        match handle {
+            RawWindowHandle::Xlib(handle) => Some(Self::from_window_id(handle.window as usize)),
+            RawWindowHandle::Xcb(handle) => Some(Self::from_window_id(handle.window as usize)),
+            RawWindowHandle::Win32(handle) => Some(Self::from_raw(handle.hwnd)),
+            RawWindowHandle::AppKit(handle) => Some(Self::from_raw(handle.ns_view)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use atom::prelude::*;
+use atom::sequence::SequenceWriter;
+use urid::*;
+
+/// The type of the objects that are written by a [`TelemetryPublisher`](struct.TelemetryPublisher.html).
+///
+/// Every property of such an object is a telemetry value of the plugin: Its key is the URID the value was registered with and its value is either a `Float` or, for analysis frames, a `Vector` of `Float`s.
+pub struct Telemetry;
+
+unsafe impl UriBound for Telemetry {
+    const URI: &'static [u8] = b"urn:lv2_ui:Telemetry\0";
+}
+
+impl Telemetry {
+    /// Read a telemetry object, as it was received by the UI.
+    ///
+    /// Returns the properties of the object, or `None` if the atom isn't a telemetry object.
+    pub fn read<'a>(
+        atom: UnidentifiedAtom<'a>,
+        urids: &TelemetryURIDCollection,
+    ) -> Option<ObjectReader<'a>> {
+        let (header, reader) = atom.read(urids.atom.object, ())?;
+        if header.otype == urids.telemetry {
+            Some(reader)
+        } else {
+            None
+        }
+    }
+}
+
+/// A URID cache containing everything needed to publish and read telemetry.
+#[derive(URIDCollection)]
+pub struct TelemetryURIDCollection {
+    pub atom: AtomURIDCollection,
+    pub telemetry: URID<Telemetry>,
+}
+
+/// A telemetry value or frame that is registered with a publisher.
+struct Channel {
+    key: URID,
+    values: Box<[f32]>,
+    frame: bool,
+    marked: bool,
+}
+
+/// Publishes telemetry values, like meter levels or analysis frames, to the UI.
+///
+/// Plugins often have values that are only computed for their UIs. These are sent via an atom output port that the UI is notified about, but sending them every cycle floods the UI with more events than it can display. The publisher takes care of the throttling and framing: The plugin registers every value with the URID of a property when it's created and marks the values it has updated during a cycle. At the end of the cycle, [`publish`](#method.publish) writes all marked values as one [`Telemetry`](struct.Telemetry.html) object to the notify port, but only every `rate_divider`-th cycle. Values that were marked in the cycles between are published with the next object.
+///
+/// Marking and publishing doesn't allocate and can therefore be done in the `run()` context.
+///
+/// # Example
+///
+/// ```
+/// use lv2_atom::prelude::*;
+/// use lv2_atom::sequence::SequenceWriter;
+/// use lv2_ui::*;
+/// use urid::*;
+///
+/// #[uri("urn:my-plugin#level")]
+/// struct Level;
+///
+/// struct Meter {
+///     urids: TelemetryURIDCollection,
+///     level: URID<Level>,
+///     publisher: TelemetryPublisher,
+/// }
+///
+/// impl Meter {
+///     fn new(map: &impl Map) -> Option<Self> {
+///         let level = map.map_type::<Level>()?;
+///         Some(Self {
+///             urids: map.populate_collection()?,
+///             level,
+///             // Publish the level every eighth cycle.
+///             publisher: TelemetryPublisher::new(8).with_value(level),
+///         })
+///     }
+///
+///     /// Called in `run` with the input and the sequence of the notify port.
+///     fn run(&mut self, input: &[f32], notify: &mut SequenceWriter) {
+///         let peak = input.iter().fold(0.0, |peak: f32, x| peak.max(x.abs()));
+///         // Keep the highest level until it's published.
+///         self.publisher.mark_peak(self.level, peak);
+///         self.publisher.publish(notify, FrameTime(0), &self.urids);
+///     }
+/// }
+/// ```
+pub struct TelemetryPublisher {
+    rate_divider: u32,
+    cycle: u32,
+    channels: Vec<Channel>,
+}
+
+impl TelemetryPublisher {
+    /// Create a publisher without values, which publishes every `rate_divider`-th cycle.
+    ///
+    /// The first cycle is always published. A divider of zero is treated like a divider of one, which publishes every cycle.
+    pub fn new(rate_divider: u32) -> Self {
+        Self {
+            rate_divider: rate_divider.max(1),
+            cycle: 0,
+            channels: Vec::new(),
+        }
+    }
+
+    /// Register a single value, which is published as a `Float` property with the given key.
+    pub fn with_value<K: ?Sized>(self, key: URID<K>) -> Self {
+        self.with_channel(key.into_general(), 1, false)
+    }
+
+    /// Register a frame of `len` values, like the bins of a spectrum, which is published as a `Vector` property with the given key.
+    pub fn with_frame<K: ?Sized>(self, key: URID<K>, len: usize) -> Self {
+        self.with_channel(key.into_general(), len, true)
+    }
+
+    fn with_channel(mut self, key: URID, len: usize, frame: bool) -> Self {
+        self.channels.retain(|channel| channel.key != key);
+        self.channels.push(Channel {
+            key,
+            values: vec![0.0; len].into_boxed_slice(),
+            frame,
+            marked: false,
+        });
+        self
+    }
+
+    /// The number of cycles per publication.
+    pub fn rate_divider(&self) -> u32 {
+        self.rate_divider
+    }
+
+    /// Check whether the next call to [`publish`](#method.publish) writes the marked values.
+    ///
+    /// Plugins can use this to skip computing values that aren't published anyway.
+    pub fn is_due(&self) -> bool {
+        self.cycle == 0
+    }
+
+    fn channel(&mut self, key: URID, frame: bool) -> Option<&mut Channel> {
+        self.channels
+            .iter_mut()
+            .find(|channel| channel.key == key && channel.frame == frame)
+    }
+
+    /// Update a value and mark it for publication.
+    ///
+    /// Returns `false` if no value with this key is registered.
+    pub fn mark_value<K: ?Sized>(&mut self, key: URID<K>, value: f32) -> bool {
+        self.channel(key.into_general(), false)
+            .map(|channel| {
+                channel.values[0] = value;
+                channel.marked = true;
+            })
+            .is_some()
+    }
+
+    /// Update a value with a peak and mark it for publication.
+    ///
+    /// Unlike [`mark_value`](#method.mark_value), this keeps the highest value that was marked since the last publication, which is what meters display when not every cycle is published.
+    ///
+    /// Returns `false` if no value with this key is registered.
+    pub fn mark_peak<K: ?Sized>(&mut self, key: URID<K>, value: f32) -> bool {
+        self.channel(key.into_general(), false)
+            .map(|channel| {
+                if !channel.marked || value > channel.values[0] {
+                    channel.values[0] = value;
+                }
+                channel.marked = true;
+            })
+            .is_some()
+    }
+
+    /// Update a frame and mark it for publication.
+    ///
+    /// Returns `false` if no frame with this key is registered or if the frame doesn't have the registered length.
+    pub fn mark_frame<K: ?Sized>(&mut self, key: URID<K>, frame: &[f32]) -> bool {
+        match self.channel(key.into_general(), true) {
+            Some(channel) if channel.values.len() == frame.len() => {
+                channel.values.copy_from_slice(frame);
+                channel.marked = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// End a cycle and publish the marked values if the cycle is due.
+    ///
+    /// The values are written as one [`Telemetry`](struct.Telemetry.html) object with the given time stamp, which has to be measured in the unit of the sequence. Nothing is written if the cycle isn't due or no value was marked. If the object doesn't fit into the sequence, the values stay marked and are published in the next due cycle.
+    ///
+    /// Returns `true` if an object was written.
+    pub fn publish(
+        &mut self,
+        notify: &mut SequenceWriter,
+        stamp: impl Into<TimeStamp>,
+        urids: &TelemetryURIDCollection,
+    ) -> bool {
+        let due = self.is_due();
+        self.cycle = (self.cycle + 1) % self.rate_divider;
+        if !due || !self.channels.iter().any(|channel| channel.marked) {
+            return false;
+        }
+        // Check the space first, so that incomplete objects aren't written.
+        if notify
+            .remaining()
+            .is_some_and(|remaining| remaining < self.event_size())
+        {
+            return false;
+        }
+
+        let written = self.write(notify, stamp.into(), urids).is_some();
+        if written {
+            for channel in self.channels.iter_mut() {
+                channel.marked = false;
+            }
+        }
+        written
+    }
+
+    /// The size of the event with all marked values, including its time stamp and padding.
+    fn event_size(&self) -> usize {
+        let properties: usize = self
+            .channels
+            .iter()
+            .filter(|channel| channel.marked)
+            .map(|channel| {
+                let body = if channel.frame {
+                    8 + 4 * channel.values.len()
+                } else {
+                    4
+                };
+                16 + body.next_multiple_of(8)
+            })
+            .sum();
+        24 + properties
+    }
+
+    fn write(
+        &self,
+        notify: &mut SequenceWriter,
+        stamp: TimeStamp,
+        urids: &TelemetryURIDCollection,
+    ) -> Option<()> {
+        let mut object = notify.init(
+            stamp,
+            urids.atom.object,
+            ObjectHeader {
+                id: None,
+                otype: urids.telemetry.into_general(),
+            },
+        )?;
+        for channel in self.channels.iter().filter(|channel| channel.marked) {
+            if channel.frame {
+                object
+                    .init(channel.key, None, urids.atom.vector(), urids.atom.float)?
+                    .append(&channel.values)?;
+            } else {
+                object.init(channel.key, None, urids.atom.float, channel.values[0])?;
+            }
+        }
+        Some(())
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{Controller, PortEvent, UIInfo};
+use core::feature::*;
+use std::any::Any;
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use urid::{Uri, UriBound};
+
+/// A handle to the widget of a UI.
+///
+/// The actual type of the widget depends on the type of the UI: For an X11 UI, it's the ID of the UI's window, for a Gtk UI, it's a pointer to a `GtkWidget` and so on. UIs that don't embed a widget into the host, for example because they show their own window, may use a null widget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Widget(sys::LV2UI_Widget);
+
+impl Widget {
+    /// Create a null widget.
+    pub fn null() -> Self {
+        Self(std::ptr::null_mut())
+    }
+
+    /// Create a widget from a raw pointer.
+    pub fn from_raw(widget: sys::LV2UI_Widget) -> Self {
+        Self(widget)
+    }
+
+    /// Create a widget from a native window ID, like an X11 window.
+    pub fn from_window_id(id: usize) -> Self {
+        Self(id as sys::LV2UI_Widget)
+    }
+
+    /// Return the raw widget pointer.
+    pub fn as_raw(self) -> sys::LV2UI_Widget {
+        self.0
+    }
+}
+
+/// The central trait to describe LV2 plugin UIs.
+///
+/// A UI is a separate object that controls a plugin instance. It never talks to the plugin directly: Instead, it sends data to the plugin's input ports via a [`Controller`](struct.Controller.html) and is notified by the host about changes at the plugin's ports via [`port_event`](#method.port_event).
+///
+/// Just like plugins, the host does not directly talk to the UI. Instead, it creates and talks to the [`UIInstance`](struct.UIInstance.html), which dereferences raw pointers, does safety checks and then calls the corresponding UI methods. The UI is dropped when the host cleans it up.
+pub trait PluginUI: UriBound + Sized + 'static {
+    /// The host features used by this UI.
+    ///
+    /// This collection will be created by the framework when the UI is instantiated and every method of the UI has access to it via a mutable reference. All UI methods are called in the UI thread, which is why the features are created for the [`Other`](../lv2_core/feature/enum.ThreadingClass.html#variant.Other) threading class.
+    ///
+    /// If a host feature is missing, the UI creation simply fails.
+    type Features: FeatureCollection<'static>;
+
+    /// Create a new UI instance.
+    ///
+    /// The controller is used to send data to the plugin and can be stored by the UI.
+    fn new(ui_info: &UIInfo, controller: Controller, features: &mut Self::Features)
+        -> Option<Self>;
+
+    /// Return the widget of the UI, which is passed to the host after instantiation.
+    ///
+    /// UIs that don't embed a widget return a null widget, which is also the default.
+    fn widget(&self) -> Widget {
+        Widget::null()
+    }
+
+    /// Handle a change at a port of the plugin.
+    ///
+    /// By default, the host only calls this method for control input ports, but UIs can request updates for other ports too. The buffer of the event is only valid for the duration of the call.
+    fn port_event(&mut self, _event: &PortEvent, _features: &mut Self::Features) {}
+
+    /// Return additional, extension-specific data.
+    ///
+    /// This works just like the [`extension_data`](../lv2_core/plugin/trait.Plugin.html#method.extension_data) method of plugins and you can use the [`match_extensions`](../lv2_core/macro.match_extensions.html) macro to generate an appropiate method body.
+    fn extension_data(_uri: &Uri) -> Option<&'static dyn Any> {
+        None
+    }
+}
+
+/// UI wrapper which translates between the host and the UI.
+///
+/// The host interacts with the UI via a C API, but the UI is implemented with ideomatic, safe Rust. To bridge this gap, this wrapper is used to translate and abstract the communcation between the host and the UI.
+///
+/// This struct is `repr(C)` and has the UI as it's first field. Therefore, a valid `*mut UIInstance<T>` is also a valid `*mut T`.
+#[repr(C)]
+pub struct UIInstance<T: PluginUI> {
+    /// The UI instance.
+    instance: T,
+    /// All features that may be used by the UI.
+    features: T::Features,
+}
+
+impl<T: PluginUI> UIInstance<T> {
+    /// Instantiate the UI.
+    ///
+    /// This method provides a required method for the C interface of a UI and is used by the `lv2ui_descriptors` macro.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it derefences multiple raw pointers and is part of the C interface.
+    pub unsafe extern "C" fn instantiate(
+        descriptor: *const sys::LV2UI_Descriptor,
+        plugin_uri: *const c_char,
+        bundle_path: *const c_char,
+        write_function: sys::LV2UI_Write_Function,
+        controller: sys::LV2UI_Controller,
+        widget: *mut sys::LV2UI_Widget,
+        features: *const *const sys::LV2_Feature,
+    ) -> sys::LV2UI_Handle {
+        if descriptor.is_null() {
+            eprintln!("Failed to initialize UI: Descriptor points to null");
+            return std::ptr::null_mut();
+        }
+
+        // Dereference the UI info.
+        let ui_info = match UIInfo::from_raw(descriptor, plugin_uri, bundle_path) {
+            Ok(info) => info,
+            Err(e) => {
+                eprintln!("Failed to initialize UI: Illegal info from host: {:?}", e);
+                return std::ptr::null_mut();
+            }
+        };
+
+        let controller = match Controller::from_raw(write_function, controller) {
+            Some(controller) => controller,
+            None => {
+                eprintln!("Failed to initialize UI: No write function");
+                return std::ptr::null_mut();
+            }
+        };
+
+        // Collect the supported features.
+        let mut features_cache = FeatureCache::from_raw(features);
+        let mut features = match T::Features::from_cache(&mut features_cache, ThreadingClass::Other)
+        {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("{}", e);
+                return std::ptr::null_mut();
+            }
+        };
+
+        // Instantiate the UI.
+        match T::new(&ui_info, controller, &mut features) {
+            Some(instance) => {
+                if let Some(widget) = widget.as_mut() {
+                    *widget = instance.widget().as_raw();
+                }
+                let instance = Box::new(Self { instance, features });
+                Box::leak(instance) as *mut Self as sys::LV2UI_Handle
+            }
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    /// Clean the UI.
+    ///
+    /// This method provides a required method for the C interface of a UI and is used by the `lv2ui_descriptors` macro.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it derefences multiple raw pointers and is part of the C interface.
+    pub unsafe extern "C" fn cleanup(instance: sys::LV2UI_Handle) {
+        drop(Box::from_raw(instance as *mut Self));
+    }
+
+    /// Call `port_event`.
+    ///
+    /// This method provides a required method for the C interface of a UI and is used by the `lv2ui_descriptors` macro.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it derefences multiple raw pointers and is part of the C interface.
+    pub unsafe extern "C" fn port_event(
+        instance: sys::LV2UI_Handle,
+        port_index: u32,
+        buffer_size: u32,
+        format: u32,
+        buffer: *const c_void,
+    ) {
+        let instance = &mut *(instance as *mut Self);
+        let event = PortEvent::from_raw(port_index, buffer_size, format, buffer);
+        instance.instance.port_event(&event, &mut instance.features);
+    }
+
+    /// Dereference the URI, call the `extension_data` function and return the pointer.
+    ///
+    /// This method provides a required method for the C interface of a UI and is used by the `lv2ui_descriptors` macro.
+    ///
+    /// # Safety
+    ///
+    /// This method is unsafe since it derefences multiple raw pointers and is part of the C interface.
+    pub unsafe extern "C" fn extension_data(uri: *const c_char) -> *const c_void {
+        let uri = Uri::from_ptr(uri);
+        if let Some(data) = T::extension_data(uri) {
+            data as *const _ as *const c_void
+        } else {
+            std::ptr::null()
+        }
+    }
+
+    /// Retrieve the internal UI.
+    pub fn ui_handle(&mut self) -> &mut T {
+        &mut self.instance
+    }
+
+    /// Retrieve the required handles to execute a UI method.
+    ///
+    /// This method can be used by extensions to call an extension method and provide it the host features of the UI.
+    pub fn ui_class_handle(&mut self) -> (&mut T, &mut T::Features) {
+        (&mut self.instance, &mut self.features)
+    }
+}
+
+#[doc(hidden)]
+pub unsafe trait UIInstanceDescriptor: PluginUI {
+    const DESCRIPTOR: sys::LV2UI_Descriptor;
+}
@@ -0,0 +1,164 @@
+extern crate lv2_core as core;
+extern crate lv2_sys as sys;
+extern crate lv2_ui as ui;
+
+use core::feature::Feature;
+use core::prelude::*;
+use std::any::Any;
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use ui::prelude::*;
+use urid::*;
+
+#[uri("urn:rust-lv2:window-ui-test")]
+struct WindowUI {
+    visible: bool,
+    idle_calls: u32,
+}
+
+impl PluginUI for WindowUI {
+    type Features = ();
+
+    fn new(_: &UIInfo, _: Controller, _: &mut ()) -> Option<Self> {
+        Some(Self {
+            visible: false,
+            idle_calls: 0,
+        })
+    }
+
+    fn extension_data(uri: &Uri) -> Option<&'static dyn Any> {
+        match_extensions![uri, IdleDescriptor<Self>, ShowDescriptor<Self>]
+    }
+}
+
+impl Idle for WindowUI {
+    fn idle(&mut self, _: &mut ()) -> bool {
+        self.idle_calls += 1;
+        self.visible
+    }
+}
+
+impl Show for WindowUI {
+    fn show(&mut self, _: &mut ()) -> bool {
+        self.visible = true;
+        true
+    }
+
+    fn hide(&mut self, _: &mut ()) -> bool {
+        self.visible = false;
+        true
+    }
+}
+
+lv2ui_descriptors!(WindowUI);
+
+unsafe extern "C" fn write_function(
+    _: sys::LV2UI_Controller,
+    _: u32,
+    _: u32,
+    _: u32,
+    _: *const c_void,
+) {
+}
+
+#[test]
+fn test_idle_and_show() {
+    let features: &[*const sys::LV2_Feature] = &[std::ptr::null()];
+
+    unsafe {
+        let descriptor = lv2ui_descriptor(0).as_ref().unwrap();
+        let handle = (descriptor.instantiate.unwrap())(
+            descriptor,
+            b"urn:rust-lv2:plugin\0".as_ptr() as *const c_char,
+            b"/\0".as_ptr() as *const c_char,
+            Some(write_function),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            features.as_ptr(),
+        );
+        assert!(!handle.is_null());
+
+        let extension_data = descriptor.extension_data.unwrap();
+        let idle = (extension_data(sys::LV2_UI__idleInterface.as_ptr() as *const c_char)
+            as *const sys::LV2UI_Idle_Interface)
+            .as_ref()
+            .unwrap();
+        let show = (extension_data(sys::LV2_UI__showInterface.as_ptr() as *const c_char)
+            as *const sys::LV2UI_Show_Interface)
+            .as_ref()
+            .unwrap();
+
+        // The window is closed until it's shown.
+        assert_ne!((idle.idle.unwrap())(handle), 0);
+        assert_eq!((show.show.unwrap())(handle), 0);
+        assert_eq!((idle.idle.unwrap())(handle), 0);
+        assert_eq!((show.hide.unwrap())(handle), 0);
+        assert_ne!((idle.idle.unwrap())(handle), 0);
+        assert_eq!((*(handle as *mut WindowUI)).idle_calls, 3);
+
+        (descriptor.cleanup.unwrap())(handle);
+    }
+}
+
+#[uri("urn:rust-lv2:sample")]
+struct Sample;
+
+unsafe extern "C" fn request(
+    handle: sys::LV2UI_Feature_Handle,
+    key: sys::LV2_URID,
+    type_: sys::LV2_URID,
+    _: *const *const sys::LV2_Feature,
+) -> sys::LV2UI_Request_Value_Status {
+    let requests = &mut *(handle as *mut Vec<(u32, u32)>);
+    if !requests.is_empty() {
+        return sys::LV2UI_Request_Value_Status_LV2UI_REQUEST_VALUE_BUSY;
+    }
+    requests.push((key, type_));
+    sys::LV2UI_Request_Value_Status_LV2UI_REQUEST_VALUE_SUCCESS
+}
+
+#[test]
+fn test_request_value() {
+    let map = HashURIDMapper::new();
+    let sample: URID<Sample> = map.map_type().unwrap();
+
+    let mut requests: Vec<(u32, u32)> = Vec::new();
+    let raw_feature = sys::LV2UI_Request_Value {
+        handle: &mut requests as *mut Vec<(u32, u32)> as sys::LV2UI_Feature_Handle,
+        request: Some(request),
+    };
+    let request_value = unsafe {
+        RequestValue::from_feature_ptr(
+            &raw_feature as *const sys::LV2UI_Request_Value as *const c_void,
+            ThreadingClass::Other,
+        )
+    }
+    .unwrap();
+
+    assert_eq!(request_value.request(sample, None), Ok(()));
+    assert_eq!(
+        request_value.request(sample, None),
+        Err(RequestValueError::Busy)
+    );
+    assert_eq!(requests, vec![(sample.get(), 0)]);
+}
+
+#[cfg(feature = "raw-window-handle")]
+#[test]
+fn test_raw_window_handle() {
+    use raw_window_handle::*;
+
+    let parent =
+        unsafe { Parent::from_feature_ptr(42 as *const c_void, ThreadingClass::Other) }.unwrap();
+    assert_eq!(parent.widget().as_raw() as usize, 42);
+
+    let handle = parent.raw_window_handle();
+    assert_eq!(
+        Widget::from_raw_window_handle(handle),
+        Some(parent.widget())
+    );
+    assert_eq!(
+        Widget::from_raw_window_handle(RawWindowHandle::Web(WebWindowHandle::empty())),
+        None
+    );
+}
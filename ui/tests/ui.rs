@@ -0,0 +1,395 @@
+extern crate lv2_atom as atom;
+extern crate lv2_sys as sys;
+extern crate lv2_ui as ui;
+
+use atom::prelude::*;
+use lv2_core::feature::{Feature, ThreadingClass};
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use ui::prelude::*;
+use urid::*;
+
+#[uri("urn:rust-lv2:ui-test")]
+struct TestUI {
+    controller: Controller,
+    value: f32,
+}
+
+impl PluginUI for TestUI {
+    type Features = ();
+
+    fn new(info: &UIInfo, controller: Controller, _: &mut ()) -> Option<Self> {
+        assert_eq!(info.ui_uri(), Self::uri());
+        assert_eq!(info.plugin_uri().to_str().unwrap(), "urn:rust-lv2:plugin");
+        Some(Self {
+            controller,
+            value: 0.0,
+        })
+    }
+
+    fn widget(&self) -> Widget {
+        Widget::from_window_id(42)
+    }
+
+    fn port_event(&mut self, event: &PortEvent, _: &mut ()) {
+        if let Some(value) = event.control() {
+            self.value = value;
+            // Echo the value to the next port.
+            self.controller.write_control(event.port_index() + 1, value);
+        }
+    }
+}
+
+lv2ui_descriptors!(TestUI);
+
+/// Everything the UI has written, as (port index, protocol, data).
+type Writes = Vec<(u32, u32, Vec<u8>)>;
+
+unsafe extern "C" fn write_function(
+    controller: sys::LV2UI_Controller,
+    port_index: u32,
+    buffer_size: u32,
+    protocol: u32,
+    buffer: *const c_void,
+) {
+    let writes = &mut *(controller as *mut Writes);
+    let data = std::slice::from_raw_parts(buffer as *const u8, buffer_size as usize);
+    writes.push((port_index, protocol, data.to_owned()));
+}
+
+#[test]
+fn test_discovery() {
+    unsafe {
+        let descriptor = lv2ui_descriptor(0).as_ref().unwrap();
+        assert_eq!(Uri::from_ptr(descriptor.URI), TestUI::uri());
+        assert!(lv2ui_descriptor(1).is_null());
+    }
+}
+
+#[test]
+fn test_instantiate_and_port_event() {
+    let mut writes: Writes = Vec::new();
+    let features: &[*const sys::LV2_Feature] = &[std::ptr::null()];
+
+    unsafe {
+        let descriptor = lv2ui_descriptor(0).as_ref().unwrap();
+        let mut widget: sys::LV2UI_Widget = std::ptr::null_mut();
+        let handle = (descriptor.instantiate.unwrap())(
+            descriptor,
+            b"urn:rust-lv2:plugin\0".as_ptr() as *const c_char,
+            b"/\0".as_ptr() as *const c_char,
+            Some(write_function),
+            &mut writes as *mut Writes as sys::LV2UI_Controller,
+            &mut widget,
+            features.as_ptr(),
+        );
+        assert!(!handle.is_null());
+        assert_eq!(widget as usize, 42);
+
+        let value: f32 = 0.5;
+        (descriptor.port_event.unwrap())(
+            handle,
+            3,
+            std::mem::size_of::<f32>() as u32,
+            0,
+            &value as *const f32 as *const c_void,
+        );
+        assert_eq!((*(handle as *mut TestUI)).value, 0.5);
+
+        // Events with a protocol aren't control values.
+        (descriptor.port_event.unwrap())(
+            handle,
+            3,
+            std::mem::size_of::<f32>() as u32,
+            17,
+            &value as *const f32 as *const c_void,
+        );
+
+        let uri = b"urn:rust-lv2:unknown-extension\0";
+        assert!((descriptor.extension_data.unwrap())(uri.as_ptr() as *const c_char).is_null());
+
+        (descriptor.cleanup.unwrap())(handle);
+    }
+
+    assert_eq!(writes, vec![(4, 0, 0.5f32.to_ne_bytes().to_vec())]);
+}
+
+#[test]
+fn test_atom_transfer() {
+    let mut writes: Writes = Vec::new();
+    let map = HashURIDMapper::new();
+    let atom_urids: AtomURIDCollection = map.populate_collection().unwrap();
+    let urids: PortProtocolURIDCollection = map.populate_collection().unwrap();
+
+    let controller = unsafe {
+        Controller::from_raw(
+            Some(write_function),
+            &mut writes as *mut Writes as sys::LV2UI_Controller,
+        )
+    }
+    .unwrap();
+
+    assert!(controller.write_atom(1, &urids, |space| {
+        space.init(atom_urids.int, 42).map(|_| ())
+    }));
+    assert!(controller.write_event(2, &urids, |space| {
+        space.init(atom_urids.float, 1.5).map(|_| ())
+    }));
+    // Failed or empty writes aren't sent.
+    assert!(!controller.write_atom(3, &urids, |_| None));
+    assert!(!controller.write_atom(3, &urids, |_| Some(())));
+
+    assert_eq!(writes.len(), 2);
+    assert_eq!(writes[0].0, 1);
+    assert_eq!(writes[0].1, urids.atom_transfer.get());
+    assert_eq!(writes[1].0, 2);
+    assert_eq!(writes[1].1, urids.event_transfer.get());
+
+    // Read the written atoms back, like the host would send them to the UI.
+    let event = PortEvent::new(1, Some(urids.atom_transfer.into_general()), &writes[0].2);
+    let atom = event.atom(&urids).unwrap();
+    assert_eq!(atom.read(atom_urids.int, ()), Some(42));
+
+    let event = PortEvent::new(2, Some(urids.event_transfer.into_general()), &writes[1].2);
+    let atom = event.atom(&urids).unwrap();
+    assert_eq!(atom.read(atom_urids.float, ()), Some(1.5));
+
+    // Events with other protocols or incomplete atoms don't contain atoms.
+    let event = PortEvent::new(1, Some(urids.float_protocol.into_general()), &writes[0].2);
+    assert!(event.atom(&urids).is_none());
+    let event = PortEvent::new(1, None, &writes[0].2);
+    assert!(event.atom(&urids).is_none());
+    let event = PortEvent::new(
+        1,
+        Some(urids.atom_transfer.into_general()),
+        &writes[0].2[..4],
+    );
+    assert!(event.atom(&urids).is_none());
+}
+
+/// Every gesture notification of the UI, as (port index, grabbed).
+type Touches = Vec<(u32, bool)>;
+
+unsafe extern "C" fn touch_function(
+    handle: sys::LV2UI_Feature_Handle,
+    port_index: u32,
+    grabbed: bool,
+) {
+    let touches = &mut *(handle as *mut Touches);
+    touches.push((port_index, grabbed));
+}
+
+#[test]
+fn test_ui_model() {
+    let mut writes: Writes = Vec::new();
+    let mut touches: Touches = Vec::new();
+    let controller = unsafe {
+        Controller::from_raw(
+            Some(write_function),
+            &mut writes as *mut Writes as sys::LV2UI_Controller,
+        )
+    }
+    .unwrap();
+    let raw_touch = sys::LV2UI_Touch {
+        handle: &mut touches as *mut Touches as sys::LV2UI_Feature_Handle,
+        touch: Some(touch_function),
+    };
+    let touch = unsafe {
+        Touch::from_feature_ptr(
+            &raw_touch as *const sys::LV2UI_Touch as *const c_void,
+            ThreadingClass::Other,
+        )
+    }
+    .unwrap();
+
+    let mut model = UiModel::new().with_control(0, 1.0);
+    assert_eq!(model.value(0), Some(1.0));
+    assert_eq!(model.value(1), None);
+
+    // Values of the host are tracked and reported once.
+    let value = 0.5f32.to_ne_bytes();
+    assert!(model.port_event(&PortEvent::new(0, None, &value)));
+    assert!(!model.port_event(&PortEvent::new(0, None, &value)));
+    assert!(model.port_event(&PortEvent::new(1, None, &value)));
+    assert!(!model.port_event(&PortEvent::new(2, Some(URID::new(17).unwrap()), &value)));
+    assert_eq!(model.take_changes(), vec![(0, 0.5), (1, 0.5)]);
+    assert_eq!(model.take_changes(), vec![]);
+
+    // Changes of a gesture are coalesced and the echoes of the host are ignored.
+    model.begin_gesture(0);
+    model.set(0, 0.6);
+    model.set(0, 0.7);
+    assert!(model.is_touched(0));
+    assert!(!model.port_event(&PortEvent::new(0, None, &0.6f32.to_ne_bytes())));
+    assert_eq!(model.value(0), Some(0.7));
+    model.flush(&controller, Some(&touch));
+    model.set(0, 0.8);
+    model.end_gesture(0);
+    model.flush(&controller, Some(&touch));
+    assert!(!model.is_touched(0));
+
+    // Values without a gesture are sent too, and nothing is sent if nothing changed.
+    model.set(1, 0.25);
+    model.flush(&controller, Some(&touch));
+    model.flush(&controller, Some(&touch));
+
+    // Releasing and grabbing again before a flush continues the gesture.
+    model.begin_gesture(1);
+    model.flush(&controller, Some(&touch));
+    model.end_gesture(1);
+    model.begin_gesture(1);
+    model.flush(&controller, None);
+    model.end_gesture(1);
+    model.flush(&controller, Some(&touch));
+
+    assert_eq!(model.take_changes(), vec![]);
+    assert_eq!(
+        writes,
+        vec![
+            (0, 0, 0.7f32.to_ne_bytes().to_vec()),
+            (0, 0, 0.8f32.to_ne_bytes().to_vec()),
+            (1, 0, 0.25f32.to_ne_bytes().to_vec()),
+        ]
+    );
+    assert_eq!(touches, vec![(0, true), (0, false), (1, true), (1, false)]);
+}
+
+#[test]
+fn test_peak_protocol() {
+    let map = HashURIDMapper::new();
+    let urids: PortProtocolURIDCollection = map.populate_collection().unwrap();
+
+    let mut meter = PeakMeter::new();
+    let first = meter.measure(&[0.25, -0.75, 0.5]);
+    assert_eq!(
+        first,
+        PeakData {
+            period_start: 0,
+            period_size: 3,
+            peak: 0.75,
+        }
+    );
+    let second = meter.measure(&[0.0; 64]);
+    assert_eq!(second.period_start, 3);
+    assert_eq!(second.period_size, 64);
+    assert_eq!(second.peak, 0.0);
+
+    // Send the peak to the UI like the host does.
+    let bytes = first.to_bytes();
+    let event = PortEvent::new(2, Some(urids.peak_protocol.into_general()), &bytes);
+    assert_eq!(event.peak(&urids), Some(first));
+
+    // Events with other protocols or of the wrong size don't contain peaks.
+    let event = PortEvent::new(2, Some(urids.float_protocol.into_general()), &bytes);
+    assert_eq!(event.peak(&urids), None);
+    let event = PortEvent::new(2, None, &bytes);
+    assert_eq!(event.peak(&urids), None);
+    let event = PortEvent::new(2, Some(urids.peak_protocol.into_general()), &bytes[..8]);
+    assert_eq!(event.peak(&urids), None);
+}
+
+#[test]
+fn test_telemetry() {
+    use atom::space::RootMutSpace;
+
+    let map = HashURIDMapper::new();
+    let urids: TelemetryURIDCollection = map.populate_collection().unwrap();
+    let level = map
+        .map_uri(Uri::from_bytes_with_nul(b"urn:rust-lv2:level\0").unwrap())
+        .unwrap();
+    let spectrum = map
+        .map_uri(Uri::from_bytes_with_nul(b"urn:rust-lv2:spectrum\0").unwrap())
+        .unwrap();
+    let unknown = map
+        .map_uri(Uri::from_bytes_with_nul(b"urn:rust-lv2:unknown\0").unwrap())
+        .unwrap();
+
+    let mut publisher = TelemetryPublisher::new(2)
+        .with_value(level)
+        .with_frame(spectrum, 3);
+    assert_eq!(publisher.rate_divider(), 2);
+    assert_eq!(TelemetryPublisher::new(0).rate_divider(), 1);
+
+    let mut buffer = vec![0u64; 64];
+    let bytes =
+        unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8) };
+    {
+        let mut space = RootMutSpace::new(bytes);
+        let mut notify = (&mut space as &mut dyn MutSpace)
+            .init(
+                urids.atom.sequence,
+                TimeStampURID::Frames(map.map_type().unwrap()),
+            )
+            .unwrap();
+
+        // Nothing is published if nothing was marked, but the cycle still counts.
+        assert!(publisher.is_due());
+        assert!(!publisher.publish(&mut notify, FrameTime(0), &urids));
+        assert!(!publisher.is_due());
+        assert!(!publisher.publish(&mut notify, FrameTime(0), &urids));
+
+        // The first due cycle publishes the highest peak.
+        assert!(publisher.mark_peak(level, 0.5));
+        assert!(publisher.mark_peak(level, 0.25));
+        assert!(publisher.publish(&mut notify, FrameTime(0), &urids));
+
+        // Values that are marked in the cycles between are published with the next object.
+        assert!(publisher.mark_peak(level, 0.75));
+        assert!(!publisher.publish(&mut notify, FrameTime(1), &urids));
+        assert!(publisher.mark_frame(spectrum, &[1.0, 2.0, 3.0]));
+        assert!(!publisher.mark_frame(spectrum, &[1.0, 2.0]));
+        assert!(!publisher.mark_frame(level, &[1.0]));
+        assert!(!publisher.mark_value(spectrum, 1.0));
+        assert!(!publisher.mark_value(unknown, 1.0));
+        assert!(publisher.publish(&mut notify, FrameTime(2), &urids));
+    }
+
+    let mut sequence = UnidentifiedAtom::new(Space::from_slice(bytes))
+        .read(urids.atom.sequence, map.map_type().unwrap())
+        .unwrap();
+
+    let (stamp, atom) = sequence.next().unwrap();
+    assert_eq!(stamp.as_frames(), Some(0));
+    let mut properties = Telemetry::read(atom, &urids).unwrap();
+    let (header, value) = properties.next().unwrap();
+    assert_eq!(header.key, level);
+    assert_eq!(value.read(urids.atom.float, ()), Some(0.5));
+    assert!(properties.next().is_none());
+
+    let (stamp, atom) = sequence.next().unwrap();
+    assert_eq!(stamp.as_frames(), Some(2));
+    let mut properties = Telemetry::read(atom, &urids).unwrap();
+    let (header, value) = properties.next().unwrap();
+    assert_eq!(header.key, level);
+    assert_eq!(value.read(urids.atom.float, ()), Some(0.75));
+    let (header, value) = properties.next().unwrap();
+    assert_eq!(header.key, spectrum);
+    assert_eq!(
+        value.read(urids.atom.vector(), urids.atom.float),
+        Some(&[1.0, 2.0, 3.0][..])
+    );
+    assert!(properties.next().is_none());
+    assert!(sequence.next().is_none());
+
+    // Other atoms aren't telemetry.
+    assert!(Telemetry::read(UnidentifiedAtom::new(Space::from_slice(bytes)), &urids).is_none());
+
+    // If the object doesn't fit, nothing is written and the values stay marked.
+    let mut buffer = vec![0u64; 8];
+    let bytes =
+        unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8) };
+    let mut space = RootMutSpace::new(bytes);
+    let mut notify = (&mut space as &mut dyn MutSpace)
+        .init(
+            urids.atom.sequence,
+            TimeStampURID::Frames(map.map_type().unwrap()),
+        )
+        .unwrap();
+    let mut publisher = TelemetryPublisher::new(1)
+        .with_value(level)
+        .with_frame(spectrum, 3);
+    assert!(publisher.mark_value(level, 1.0));
+    assert!(publisher.mark_frame(spectrum, &[1.0, 2.0, 3.0]));
+    assert!(!publisher.publish(&mut notify, FrameTime(0), &urids));
+    assert_eq!(notify.statistics().written, 0);
+}
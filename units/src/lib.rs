@@ -1,8 +1,34 @@
 //! LV2 specification for measuring unit definitions.
 //!
 //! The original [specification](http://lv2plug.in/ns/extensions/units/units.html) contains means to describe units for LV2 values in RDF files. This implementation is focused on the stock units defined by the specification by binding them to marker types.
+//!
+//! The [`value`](value/index.html) module contains wrappers for values with units, which can be used as the value type of control ports:
+//!
+//! ```
+//! use lv2_core::prelude::*;
+//! use lv2_units::prelude::*;
+//!
+//! #[derive(PortCollection)]
+//! struct Ports {
+//!     gain: InputPort<Control<Db>>,
+//!     input: InputPort<Audio>,
+//!     output: OutputPort<Audio>,
+//! }
+//!
+//! fn run(ports: &mut Ports) {
+//!     let coef = *ports.gain.to_coef();
+//!     for (input, output) in ports.input.iter().zip(ports.output.iter_mut()) {
+//!         *output = input * coef;
+//!     }
+//! }
+//! ```
+//!
+//! The crate is `no_std` if the default `std` feature is disabled.
+#![cfg_attr(not(feature = "std"), no_std)]
 extern crate lv2_sys as sys;
 
+pub mod value;
+
 use urid::*;
 
 /// All unit URI bounds.
@@ -164,5 +190,6 @@ pub struct UnitURIDCollection {
 /// Prelude of `lv2_units` for wildcard usage.
 pub mod prelude {
     pub use crate::units::*;
+    pub use crate::value::*;
     pub use crate::UnitURIDCollection;
 }
@@ -0,0 +1,264 @@
+//! Values with units.
+//!
+//! The types in this module wrap a `f32` and carry the unit of the value. They can be used as the value type of control ports, like `InputPort<Control<Db>>`, which attaches the unit to the port's metadata. All of them dereference to `f32` and provide conversions to related units. The conversions that need floating-point math functions, like the one from decibels to coefficients, are only available with the `std` feature.
+use crate::units::*;
+use core::ops::{Deref, DerefMut};
+use lv2_core::port::ControlValue;
+use urid::{Uri, UriBound};
+
+macro_rules! make_unit_value {
+    ($(#[$meta:meta])* $name:ident, $unit:ty) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub f32);
+
+        impl Deref for $name {
+            type Target = f32;
+
+            fn deref(&self) -> &f32 {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut f32 {
+                &mut self.0
+            }
+        }
+
+        impl From<f32> for $name {
+            fn from(value: f32) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for f32 {
+            fn from(value: $name) -> f32 {
+                value.0
+            }
+        }
+
+        unsafe impl ControlValue for $name {
+            fn unit() -> Option<&'static Uri> {
+                Some(<$unit>::uri())
+            }
+        }
+    };
+}
+
+make_unit_value!(
+    /// A level in decibels.
+    Db,
+    Decibel
+);
+
+make_unit_value!(
+    /// A linear gain coefficient.
+    Coef,
+    Coefficient
+);
+
+make_unit_value!(
+    /// A frequency in hertz.
+    Hz,
+    Hertz
+);
+
+make_unit_value!(
+    /// A duration in seconds.
+    Seconds,
+    Second
+);
+
+make_unit_value!(
+    /// A duration in milliseconds.
+    Milliseconds,
+    Millisecond
+);
+
+make_unit_value!(
+    /// A tempo in beats per minute.
+    Bpm,
+    BeatPerMinute
+);
+
+make_unit_value!(
+    /// An interval in semitones of the twelve-tone equal temperament.
+    Semitones,
+    Semitone
+);
+
+make_unit_value!(
+    /// An interval in cents, which are hundredths of a semitone.
+    Cents,
+    Cent
+);
+
+make_unit_value!(
+    /// A MIDI note number, where 69 is the concert pitch A4.
+    MidiNote,
+    MIDINote
+);
+
+make_unit_value!(
+    /// A percentage.
+    Percentage,
+    Percent
+);
+
+#[cfg(feature = "std")]
+/// Convert a level in decibels to a linear gain coefficient.
+pub fn db_to_coefficient(db: f32) -> f32 {
+    10.0f32.powf(db * 0.05)
+}
+
+#[cfg(feature = "std")]
+/// Convert a linear gain coefficient to a level in decibels.
+///
+/// A coefficient of zero results in negative infinity.
+pub fn coefficient_to_db(coefficient: f32) -> f32 {
+    20.0 * coefficient.log10()
+}
+
+#[cfg(feature = "std")]
+impl Db {
+    /// Convert the level to a linear gain coefficient.
+    pub fn to_coef(self) -> Coef {
+        Coef(db_to_coefficient(self.0))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Coef {
+    /// Convert the coefficient to a level in decibels.
+    pub fn to_db(self) -> Db {
+        Db(coefficient_to_db(self.0))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Db> for Coef {
+    fn from(db: Db) -> Self {
+        db.to_coef()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Coef> for Db {
+    fn from(coef: Coef) -> Self {
+        coef.to_db()
+    }
+}
+
+impl Seconds {
+    /// Convert the duration to a number of frames at the given sample rate.
+    pub fn to_frames(self, sample_rate: f64) -> f64 {
+        f64::from(self.0) * sample_rate
+    }
+}
+
+impl From<Milliseconds> for Seconds {
+    fn from(milliseconds: Milliseconds) -> Self {
+        Self(milliseconds.0 / 1000.0)
+    }
+}
+
+impl From<Seconds> for Milliseconds {
+    fn from(seconds: Seconds) -> Self {
+        Self(seconds.0 * 1000.0)
+    }
+}
+
+impl Semitones {
+    /// The frequency ratio of the interval.
+    #[cfg(feature = "std")]
+    pub fn to_ratio(self) -> f32 {
+        2.0f32.powf(self.0 / 12.0)
+    }
+}
+
+impl From<Cents> for Semitones {
+    fn from(cents: Cents) -> Self {
+        Self(cents.0 / 100.0)
+    }
+}
+
+impl From<Semitones> for Cents {
+    fn from(semitones: Semitones) -> Self {
+        Self(semitones.0 * 100.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MidiNote {
+    /// The frequency of the note, with A4 tuned to 440 Hz.
+    pub fn to_hz(self) -> Hz {
+        Hz(440.0 * Semitones(self.0 - 69.0).to_ratio())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Hz {
+    /// The MIDI note of the frequency, with A4 tuned to 440 Hz.
+    ///
+    /// The result is not rounded, so it may lie between two notes.
+    pub fn to_midi_note(self) -> MidiNote {
+        MidiNote(69.0 + 12.0 * (self.0 / 440.0).log2())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<MidiNote> for Hz {
+    fn from(note: MidiNote) -> Self {
+        note.to_hz()
+    }
+}
+
+impl Percentage {
+    /// Convert the percentage to a fraction, where 100 % is 1.0.
+    pub fn to_fraction(self) -> f32 {
+        self.0 / 100.0
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::prelude::*;
+    use lv2_core::port::ControlValue;
+    use urid::UriBound;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_close(Db(0.0).to_coef().0, 1.0);
+        assert_close(Db(-6.0).to_coef().0, 0.501_187);
+        assert_close(Db::from(Coef(10.0)).0, 20.0);
+        assert_eq!(Coef(0.0).to_db().0, f32::NEG_INFINITY);
+
+        assert_eq!(Seconds::from(Milliseconds(250.0)), Seconds(0.25));
+        assert_eq!(Milliseconds::from(Seconds(2.0)), Milliseconds(2000.0));
+        assert_eq!(Seconds(0.5).to_frames(44100.0), 22050.0);
+
+        assert_close(Semitones(12.0).to_ratio(), 2.0);
+        assert_eq!(Semitones::from(Cents(150.0)), Semitones(1.5));
+        assert_close(MidiNote(69.0).to_hz().0, 440.0);
+        assert_close(MidiNote(81.0).to_hz().0, 880.0);
+        assert_close(Hz(220.0).to_midi_note().0, 57.0);
+        assert_eq!(Percentage(50.0).to_fraction(), 0.5);
+    }
+
+    #[test]
+    fn test_control_value() {
+        let mut gain = Db(-3.0);
+        *gain -= 3.0;
+        assert_eq!(*gain, -6.0);
+        assert_eq!(f32::from(gain), -6.0);
+        assert_eq!(<Db as ControlValue>::unit(), Some(Decibel::uri()));
+        assert_eq!(<f32 as ControlValue>::unit(), None);
+    }
+}
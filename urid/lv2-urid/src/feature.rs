@@ -15,14 +15,23 @@ unsafe impl<'a> UriBound for LV2Map<'a> {
 }
 
 unsafe impl<'a> Feature for LV2Map<'a> {
-    unsafe fn from_feature_ptr(feature: *const c_void, class: ThreadingClass) -> Option<Self> {
-        if class != ThreadingClass::Audio {
-            (feature as *const sys::LV2_URID_Map)
-                .as_ref()
-                .map(|internal| Self { internal })
-        } else {
-            panic!("The URID mapping feature isn't allowed in the audio threading class");
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[
+        ThreadingClass::Discovery,
+        ThreadingClass::Instantiation,
+        ThreadingClass::Other,
+    ];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
         }
+        (feature as *const sys::LV2_URID_Map)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
     }
 }
 
@@ -30,6 +39,13 @@ impl<'a> LV2Map<'a> {
     pub fn new(internal: &'a sys::LV2_URID_Map) -> Self {
         Self { internal }
     }
+
+    /// Return the raw map struct of the host.
+    ///
+    /// This can be passed to C libraries that need to map URIs themselves, like `sratom` or the atom forge. The struct is only valid for the lifetime of the feature, and the `map` function has to be called with the `handle` of the struct, in the threading classes this feature can be used in.
+    pub fn as_raw(&self) -> &'a sys::LV2_URID_Map {
+        self.internal
+    }
 }
 
 impl<'a> Map for LV2Map<'a> {
@@ -51,14 +67,23 @@ unsafe impl<'a> UriBound for LV2Unmap<'a> {
 }
 
 unsafe impl<'a> Feature for LV2Unmap<'a> {
-    unsafe fn from_feature_ptr(feature: *const c_void, class: ThreadingClass) -> Option<Self> {
-        if class != ThreadingClass::Audio {
-            (feature as *const sys::LV2_URID_Unmap)
-                .as_ref()
-                .map(|internal| Self { internal })
-        } else {
-            panic!("The URID unmapping feature isn't allowed in the audio threading class");
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[
+        ThreadingClass::Discovery,
+        ThreadingClass::Instantiation,
+        ThreadingClass::Other,
+    ];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
         }
+        (feature as *const sys::LV2_URID_Unmap)
+            .as_ref()
+            .map(|internal| Self { internal })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
     }
 }
 
@@ -66,6 +91,13 @@ impl<'a> LV2Unmap<'a> {
     pub fn new(internal: &'a sys::LV2_URID_Unmap) -> Self {
         Self { internal }
     }
+
+    /// Return the raw unmap struct of the host.
+    ///
+    /// Like the [raw map struct](struct.LV2Map.html#method.as_raw), it's only valid for the lifetime of the feature and its `unmap` function has to be called with its `handle`. The returned strings are owned by the host.
+    pub fn as_raw(&self) -> &'a sys::LV2_URID_Unmap {
+        self.internal
+    }
 }
 
 impl<'a> Unmap for LV2Unmap<'a> {
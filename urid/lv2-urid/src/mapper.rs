@@ -16,6 +16,13 @@ impl<M: Map + Unmap + Unpin> From<M> for HostMap<M> {
 }
 
 impl<M: Map + Unmap + Unpin> HostMap<M> {
+    /// Return a reference to the wrapped mapper.
+    ///
+    /// This is useful for hosts that need to map and unmap URIs themselves, for example to write atoms for a plugin.
+    pub fn mapper(&self) -> &M {
+        &self.internal_map
+    }
+
     /// Unsafe wrapper of the `map` method, used by the feature interface.
     ///
     /// If the `map` method returns `None`, this method will return `0`.
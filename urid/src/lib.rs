@@ -47,21 +47,45 @@
 //! test_unmapper(&map, urids.my_struct_b);
 //! test_unmapper(&map, urid_a);
 //! ```
-use std::cmp::{Ordering, PartialEq, PartialOrd};
+//!
+//! # Features
+//!
+//! The crate supports `no_std` environments. The `std` feature, which is enabled by default, provides the [`HashURIDMapper`](struct.HashURIDMapper.html) and the [`RecordingMap`](struct.RecordingMap.html), which helps debugging the use of URIDs. Without it, the `alloc` feature provides the owned [`UriBuf`](type.UriBuf.html) and [`Map::map_str`](trait.Map.html#method.map_str), which need to allocate memory.
+//!
+//! Code that needs URIDs can be unit-tested without a mapper of the host: A [`StaticMap`](struct.StaticMap.html) assigns stable URIDs to a fixed list of URIs without allocating, and the [`const_urid!`](macro.const_urid.html) macro retrieves them at compile time.
+#![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::{Ordering, PartialEq, PartialOrd};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::num::NonZeroU32;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
+#[cfg(feature = "std")]
 use std::convert::TryInto;
-use std::fmt;
-use std::hash::{Hash, Hasher};
-use std::marker::PhantomData;
-use std::num::NonZeroU32;
+#[cfg(feature = "std")]
 use std::sync::Mutex;
 
 pub use urid_derive::*;
 
+mod static_map;
+pub use static_map::StaticMap;
+
+#[cfg(feature = "std")]
+mod recording;
+#[cfg(feature = "std")]
+pub use recording::RecordingMap;
+
 /// Representation of a borrowed Uri.
-pub type Uri = ::std::ffi::CStr;
+pub type Uri = core::ffi::CStr;
 /// Representation of an owned Uri.
-pub type UriBuf = ::std::ffi::CString;
+#[cfg(feature = "alloc")]
+pub type UriBuf = alloc::ffi::CString;
 
 /// A trait for types that can be identified by a URI.
 ///
@@ -117,6 +141,27 @@ pub unsafe trait UriBound {
     fn uri() -> &'static Uri {
         unsafe { Uri::from_bytes_with_nul_unchecked(Self::URI) }
     }
+
+    /// Return the URI as a string slice, without the null terminator.
+    ///
+    /// See [`uri_str`](fn.uri_str.html) for details.
+    fn uri_str() -> &'static str {
+        uri_str(Self::URI)
+    }
+}
+
+/// Convert a null-terminated URI constant, like the constants of `lv2-sys`, to a string slice without the null terminator.
+///
+/// This is useful to compare URI constants with the URIs of Turtle documents or other Rust strings.
+///
+/// # Panics
+///
+/// Panics if the constant isn't null-terminated or isn't valid UTF-8, which means that the constant itself is wrong.
+pub fn uri_str(uri: &'static [u8]) -> &'static str {
+    let uri = uri
+        .strip_suffix(&[0])
+        .expect("URI constants have to be null-terminated");
+    core::str::from_utf8(uri).expect("URI constants have to be valid UTF-8")
 }
 
 /// Representation of a URI for fast comparisons.
@@ -182,12 +227,12 @@ impl<T: ?Sized> URID<T> {
     /// A URID may not be 0 since this value is reserved for the `None` value of `Option<URID<T>>`, which therefore has the same size as a `URID<T>`. If `T` is also a URI bound, the URID may only be the one that is mapped to the bounded URI.
     ///
     /// Since these constraints aren't checked by this method, it is unsafe. Using this method is technically sound as long as `raw_urid` is not zero, but might still result in bad behaviour if its the wrong URID for the bound `T`.
-    pub unsafe fn new_unchecked(raw_urid: u32) -> Self {
+    pub const unsafe fn new_unchecked(raw_urid: u32) -> Self {
         Self(NonZeroU32::new_unchecked(raw_urid), PhantomData)
     }
 
     /// Return the raw URID number.
-    pub fn get(self) -> u32 {
+    pub const fn get(self) -> u32 {
         self.0.get()
     }
 
@@ -267,7 +312,7 @@ impl<T: ?Sized> Hash for URID<T> {
     }
 }
 
-impl std::convert::TryFrom<u32> for URID {
+impl core::convert::TryFrom<u32> for URID {
     type Error = ();
 
     fn try_from(value: u32) -> Result<URID, ()> {
@@ -316,6 +361,7 @@ pub trait Map {
     ///
     /// # Additional Errors
     /// This method has the same error cases as `map_uri`, but also returns `None` if the string isn't an ASCII string or if the string can not be converted to a `Uri`.
+    #[cfg(feature = "alloc")]
     fn map_str(&self, uri: &str) -> Option<URID> {
         if !uri.is_ascii() {
             return None;
@@ -354,9 +400,13 @@ pub trait Unmap {
 
 /// A simple URI → URID mapper, backed by a standard `HashMap` and a `Mutex` for multi-thread
 /// access.
+///
+/// This mapper is only available with the `std` feature.
+#[cfg(feature = "std")]
 #[derive(Default)]
 pub struct HashURIDMapper(Mutex<HashMap<UriBuf, URID>>);
 
+#[cfg(feature = "std")]
 impl Map for HashURIDMapper {
     fn map_uri(&self, uri: &Uri) -> Option<URID<()>> {
         let mut map = self.0.lock().ok()?; // Fail if the Mutex got poisoned
@@ -375,6 +425,7 @@ impl Map for HashURIDMapper {
     }
 }
 
+#[cfg(feature = "std")]
 impl Unmap for HashURIDMapper {
     fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
         let map = self.0.lock().ok()?;
@@ -386,7 +437,7 @@ impl Unmap for HashURIDMapper {
                 // interface.
                 return Some(unsafe {
                     let bytes = uri.as_bytes_with_nul();
-                    Uri::from_bytes_with_nul_unchecked(std::slice::from_raw_parts(
+                    Uri::from_bytes_with_nul_unchecked(core::slice::from_raw_parts(
                         bytes.as_ptr(),
                         bytes.len(),
                     ))
@@ -398,9 +449,56 @@ impl Unmap for HashURIDMapper {
     }
 }
 
+#[cfg(feature = "std")]
 impl HashURIDMapper {
     /// Create a new URID map store.
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Create a URID map store that contains the given URIDs and URIs.
+    ///
+    /// This is the counterpart of [`table`](#method.table) and lets hosts restore the URIDs of a previous session. The URIDs have to be the numbers from one to the number of entries, in any order, just like the ones that are assigned by this mapper, and the URIs have to be unique. Otherwise, `None` is returned.
+    pub fn from_table<I: IntoIterator<Item = (URID, UriBuf)>>(table: I) -> Option<Self> {
+        let mut map: HashMap<UriBuf, URID> = HashMap::new();
+        for (urid, uri) in table {
+            if map.insert(uri, urid).is_some() {
+                return None;
+            }
+        }
+        // Every URID has to be used once, which means that none of them is greater than the number of entries.
+        let mut used = vec![false; map.len()];
+        for urid in map.values() {
+            let slot = used.get_mut(urid.get() as usize - 1)?;
+            if *slot {
+                return None;
+            }
+            *slot = true;
+        }
+        Some(Self(Mutex::new(map)))
+    }
+
+    /// Return all mapped URIDs and their URIs, ordered by URID.
+    pub fn table(&self) -> Vec<(URID, UriBuf)> {
+        let mut table: Vec<(URID, UriBuf)> = match self.0.lock() {
+            Ok(map) => map.iter().map(|(uri, urid)| (*urid, uri.clone())).collect(),
+            Err(_) => return Vec::new(),
+        };
+        table.sort_by_key(|(urid, _)| *urid);
+        table
+    }
+}
+
+#[cfg(feature = "std")]
+impl<M: Map> Map for std::sync::Arc<M> {
+    fn map_uri(&self, uri: &Uri) -> Option<URID> {
+        (**self).map_uri(uri)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<M: Unmap> Unmap for std::sync::Arc<M> {
+    fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
+        (**self).unmap(urid)
+    }
 }
@@ -0,0 +1,135 @@
+use crate::{Map, Unmap, Uri, UriBuf, URID};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::sync::Mutex;
+
+/// A mapper that records every URI it maps, to debug the use of URIDs.
+///
+/// A URID is just a number, which makes bugs like reading an atom with the URID of the wrong type hard to diagnose. A `RecordingMap` wraps another mapper, like the map feature of the host, and remembers every URID that passes through it. The recorded table can be [dumped](#method.dump) to a file or another writer, formatted as a string to send it to a log, or used to [describe](#method.describe) a single URID.
+///
+/// If the wrapped mapper is also an unmapper, so is the recording map. URIDs that were mapped by someone else, like the host, can be described with it too.
+///
+/// # Example
+///
+/// ```
+/// use urid::*;
+///
+/// let map = RecordingMap::new(HashURIDMapper::new());
+/// let int = map.map_str("http://lv2plug.in/ns/ext/atom#Int").unwrap();
+/// let float = map.map_str("http://lv2plug.in/ns/ext/atom#Float").unwrap();
+///
+/// assert_eq!(map.describe(int), "1 <http://lv2plug.in/ns/ext/atom#Int>");
+/// assert_eq!(map.describe(URID::new(42).unwrap()), "42 (unknown)");
+///
+/// let mut dump: Vec<u8> = Vec::new();
+/// map.dump(&mut dump).unwrap();
+/// assert_eq!(
+///     String::from_utf8(dump).unwrap(),
+///     "1 <http://lv2plug.in/ns/ext/atom#Int>\n2 <http://lv2plug.in/ns/ext/atom#Float>\n"
+/// );
+/// # assert_eq!(float, 2);
+/// ```
+pub struct RecordingMap<M> {
+    mapper: M,
+    records: Mutex<BTreeMap<u32, UriBuf>>,
+}
+
+impl<M> RecordingMap<M> {
+    /// Create a recording map that wraps the given mapper.
+    pub fn new(mapper: M) -> Self {
+        Self {
+            mapper,
+            records: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Return the wrapped mapper.
+    pub fn mapper(&self) -> &M {
+        &self.mapper
+    }
+
+    /// Return the recorded URIDs and their URIs, ordered by URID.
+    pub fn table(&self) -> Vec<(URID, UriBuf)> {
+        let records = match self.records.lock() {
+            Ok(records) => records,
+            Err(_) => return Vec::new(),
+        };
+        records
+            .iter()
+            .filter_map(|(urid, uri)| Some((URID::new(*urid)?, uri.clone())))
+            .collect()
+    }
+
+    /// Return the URI of a recorded URID.
+    pub fn recorded_uri<T: ?Sized>(&self, urid: URID<T>) -> Option<UriBuf> {
+        self.records.lock().ok()?.get(&urid.get()).cloned()
+    }
+
+    /// Describe a URID with its recorded URI, like `3 <http://lv2plug.in/ns/ext/atom#Int>`.
+    ///
+    /// URIDs that weren't recorded are described as unknown.
+    pub fn describe<T: ?Sized>(&self, urid: URID<T>) -> String {
+        describe(urid.get(), self.recorded_uri(urid).as_deref())
+    }
+
+    /// Write the recorded URIDs and their URIs, one per line and ordered by URID.
+    pub fn dump<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "{}", self)
+    }
+}
+
+impl<M: Unmap> RecordingMap<M> {
+    /// Describe a URID with its URI, like [`describe`](#method.describe), but ask the wrapped unmapper if the URID wasn't recorded.
+    pub fn describe_any<T: ?Sized>(&self, urid: URID<T>) -> String {
+        match self.recorded_uri(urid) {
+            Some(uri) => describe(urid.get(), Some(&uri)),
+            None => describe(urid.get(), self.mapper.unmap(urid)),
+        }
+    }
+}
+
+/// Format a URID and its URI, if it's known.
+fn describe(urid: u32, uri: Option<&Uri>) -> String {
+    match uri {
+        Some(uri) => format!("{} <{}>", urid, uri.to_string_lossy()),
+        None => format!("{} (unknown)", urid),
+    }
+}
+
+impl<M: Map> Map for RecordingMap<M> {
+    fn map_uri(&self, uri: &Uri) -> Option<URID> {
+        let urid = self.mapper.map_uri(uri)?;
+        if let Ok(mut records) = self.records.lock() {
+            records.entry(urid.get()).or_insert_with(|| uri.to_owned());
+        }
+        Some(urid)
+    }
+}
+
+impl<M: Unmap> Unmap for RecordingMap<M> {
+    fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
+        self.mapper.unmap(urid)
+    }
+}
+
+impl<M> fmt::Display for RecordingMap<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (urid, uri) in self.table() {
+            writeln!(f, "{}", describe(urid.get(), Some(&uri)))?;
+        }
+        Ok(())
+    }
+}
+
+impl<M> fmt::Debug for RecordingMap<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.table()
+                    .into_iter()
+                    .map(|(urid, uri)| (urid.get(), uri)),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{Map, Unmap, Uri, URID};
+
+/// A mapper with a fixed list of URIs, which are assigned stable URIDs.
+///
+/// The URID of a URI is its position in the list, plus one. The map never allocates, locks or calls the host, and it can be created in a constant, which makes it useful for unit tests of code that needs URIDs, like atom readers and writers: URID collections are populated from it with [`Map::populate_collection`](trait.Map.html#method.populate_collection), and single URIDs can be retrieved at compile time with the [`const_urid!`](macro.const_urid.html) macro.
+///
+/// Since the list is fixed, URIs that aren't part of it can't be mapped and `None` is returned for them. The URIs have to be null-terminated, like the [`URI`](trait.UriBound.html#associatedconstant.URI) constants of URI bounds, and they should be unique, since a duplicate URI is always mapped to its first occurrence.
+///
+/// With the `std` feature, a [`HashURIDMapper`](struct.HashURIDMapper.html) can be [seeded](#method.to_hash_mapper) with the same URIDs, which continues to map new URIs after the fixed ones.
+///
+/// # Example
+///
+/// ```
+/// use urid::*;
+///
+/// #[uri("urn:static-map-example:a")]
+/// struct TypeA;
+///
+/// #[uri("urn:static-map-example:b")]
+/// struct TypeB;
+///
+/// #[derive(URIDCollection)]
+/// struct Collection {
+///     type_a: URID<TypeA>,
+///     type_b: URID<TypeB>,
+/// }
+///
+/// const MAP: StaticMap = StaticMap::new(&[TypeA::URI, TypeB::URI, b"urn:static-map-example:c\0"]);
+/// const TYPE_B: URID<TypeB> = const_urid!(MAP, TypeB);
+///
+/// let collection: Collection = MAP.populate_collection().unwrap();
+/// assert_eq!(collection.type_a, 1);
+/// assert_eq!(collection.type_b, TYPE_B);
+/// assert_eq!(MAP.map_str("urn:static-map-example:c").unwrap(), 3);
+/// assert_eq!(MAP.map_str("urn:static-map-example:d"), None);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct StaticMap<'a> {
+    uris: &'a [&'a [u8]],
+}
+
+impl<'a> StaticMap<'a> {
+    /// Create a map with the given list of null-terminated URIs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the URIs isn't null-terminated, or contains a null byte before its end. In a constant, this is a compile-time error.
+    pub const fn new(uris: &'a [&'a [u8]]) -> Self {
+        let mut index = 0;
+        while index < uris.len() {
+            let uri = uris[index];
+            assert!(
+                !uri.is_empty() && uri[uri.len() - 1] == 0,
+                "URIs have to be null-terminated"
+            );
+            let mut byte = 0;
+            while byte < uri.len() - 1 {
+                assert!(uri[byte] != 0, "URIs may not contain null bytes");
+                byte += 1;
+            }
+            index += 1;
+        }
+        Self { uris }
+    }
+
+    /// Return the URID of a null-terminated URI, or `None` if it isn't part of the list.
+    ///
+    /// Unlike [`map_uri`](trait.Map.html#tymethod.map_uri), this method can be evaluated at compile time.
+    pub const fn find(&self, uri: &[u8]) -> Option<URID> {
+        let mut index = 0;
+        while index < self.uris.len() {
+            if bytes_eq(self.uris[index], uri) {
+                // The index is smaller than the length of a slice of references, so adding one can't overflow.
+                return Some(unsafe { URID::new_unchecked(index as u32 + 1) });
+            }
+            index += 1;
+        }
+        None
+    }
+
+    /// The number of URIs of the map.
+    pub const fn len(&self) -> usize {
+        self.uris.len()
+    }
+
+    /// Check whether the map contains no URIs.
+    pub const fn is_empty(&self) -> bool {
+        self.uris.is_empty()
+    }
+
+    /// Iterate over the URIDs and URIs of the map, ordered by URID.
+    pub fn iter(&self) -> impl Iterator<Item = (URID, &'a Uri)> + 'a {
+        self.uris.iter().enumerate().map(|(index, uri)| {
+            (unsafe { URID::new_unchecked(index as u32 + 1) }, unsafe {
+                Uri::from_bytes_with_nul_unchecked(uri)
+            })
+        })
+    }
+
+    /// Create a `HashURIDMapper` that maps the URIs of this map to the same URIDs.
+    ///
+    /// This lets a test use constant URIDs and still map URIs that aren't known in advance, for example the ones of a plugin under test. If the list contains a URI twice, `None` is returned.
+    #[cfg(feature = "std")]
+    pub fn to_hash_mapper(&self) -> Option<crate::HashURIDMapper> {
+        crate::HashURIDMapper::from_table(self.iter().map(|(urid, uri)| (urid, uri.into())))
+    }
+}
+
+/// Compare two byte slices in a constant context.
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < a.len() {
+        if a[index] != b[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+impl<'a> Map for StaticMap<'a> {
+    fn map_uri(&self, uri: &Uri) -> Option<URID> {
+        self.find(uri.to_bytes_with_nul())
+    }
+}
+
+impl<'a> Unmap for StaticMap<'a> {
+    fn unmap<T: ?Sized>(&self, urid: URID<T>) -> Option<&Uri> {
+        let uri = self.uris.get(urid.get() as usize - 1)?;
+        Some(unsafe { Uri::from_bytes_with_nul_unchecked(uri) })
+    }
+}
+
+/// Retrieve the URID of a URI bound from a [`StaticMap`](struct.StaticMap.html) at compile time.
+///
+/// The first argument is the map, which has to be a constant, and the second one is the type, which has to implement [`UriBound`](trait.UriBound.html). If the URI of the type isn't part of the map, compilation fails.
+///
+/// ```
+/// use urid::*;
+///
+/// #[uri("urn:const-urid-example:a")]
+/// struct TypeA;
+///
+/// const MAP: StaticMap = StaticMap::new(&[TypeA::URI]);
+/// const TYPE_A: URID<TypeA> = const_urid!(MAP, TypeA);
+///
+/// assert_eq!(TYPE_A, MAP.map_type::<TypeA>().unwrap());
+/// ```
+#[macro_export]
+macro_rules! const_urid {
+    ($map:expr, $type:ty) => {{
+        const URID: $crate::URID<$type> = match $map.find(<$type as $crate::UriBound>::URI) {
+            Some(urid) => unsafe { $crate::URID::new_unchecked(urid.get()) },
+            None => panic!("The URI of the type isn't part of the static map"),
+        };
+        URID
+    }};
+}
@@ -30,6 +30,7 @@ fn test_type<T: UriBound>(expected_uri: &str) {
 
     // Test for string equality.
     assert_eq!(expected_uri, T::uri().to_str().unwrap());
+    assert_eq!(expected_uri, T::uri_str());
 }
 
 #[test]
@@ -39,3 +40,9 @@ fn test_bounds() {
     test_type::<MyUnion>("urn:my-union");
     test_type::<MyType>("urn:my-type");
 }
+
+#[test]
+#[should_panic(expected = "null-terminated")]
+fn test_uri_str_without_terminator() {
+    uri_str(b"urn:my-struct");
+}
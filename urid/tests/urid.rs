@@ -49,3 +49,97 @@ fn test_collection() {
     assert_eq!(1, collection.type_a);
     assert_eq!(2, collection.type_b);
 }
+
+#[test]
+fn test_recording_map() {
+    let host_map = HashURIDMapper::new();
+    let host_urid = host_map.map_str("urn:host-type").unwrap();
+
+    let map = RecordingMap::new(host_map);
+    let type_a = map.map_type::<MyTypeA>().unwrap();
+    let type_b = map.map_type::<MyTypeB>().unwrap();
+    assert_eq!(type_a, map.map_type::<MyTypeA>().unwrap());
+
+    assert_eq!(
+        vec![
+            (type_a.into_general(), MyTypeA::uri().to_owned()),
+            (type_b.into_general(), MyTypeB::uri().to_owned())
+        ],
+        map.table()
+    );
+    assert_eq!("2 <urn:my-type-a>", map.describe(type_a));
+    assert_eq!("1 (unknown)", map.describe(host_urid));
+    assert_eq!("1 <urn:host-type>", map.describe_any(host_urid));
+    assert_eq!(MyTypeB::uri(), map.unmap(type_b).unwrap());
+    assert_eq!("2 <urn:my-type-a>\n3 <urn:my-type-b>\n", map.to_string());
+}
+
+#[test]
+fn test_table() {
+    let map = HashURIDMapper::new();
+    let type_a = map.map_type::<MyTypeA>().unwrap();
+    let type_b = map.map_type::<MyTypeB>().unwrap();
+    let table = map.table();
+    assert_eq!(
+        table,
+        vec![
+            (type_a.into_general(), MyTypeA::uri().to_owned()),
+            (type_b.into_general(), MyTypeB::uri().to_owned())
+        ]
+    );
+
+    // The restored mapper continues where the old one stopped.
+    let restored = HashURIDMapper::from_table(table.into_iter().rev()).unwrap();
+    assert_eq!(type_b, restored.map_type::<MyTypeB>().unwrap());
+    assert_eq!(3, restored.map_str("urn:my-type-c").unwrap());
+
+    // URIDs have to be unique and without gaps.
+    let urid = |urid| URID::new(urid).unwrap();
+    let uri = |uri: &str| UriBuf::new(uri).unwrap();
+    assert!(HashURIDMapper::from_table(vec![(urid(2), uri("urn:a"))]).is_none());
+    assert!(
+        HashURIDMapper::from_table(vec![(urid(1), uri("urn:a")), (urid(1), uri("urn:b"))])
+            .is_none()
+    );
+    assert!(
+        HashURIDMapper::from_table(vec![(urid(1), uri("urn:a")), (urid(2), uri("urn:a"))])
+            .is_none()
+    );
+
+    // Shared mappers are mappers too.
+    let shared = std::sync::Arc::new(restored);
+    assert_eq!(type_a, shared.map_type::<MyTypeA>().unwrap());
+    assert_eq!(MyTypeA::uri(), shared.unmap(type_a).unwrap());
+}
+
+const STATIC_MAP: StaticMap = StaticMap::new(&[MyTypeA::URI, b"urn:my-type-c\0", MyTypeB::URI]);
+const STATIC_TYPE_B: URID<MyTypeB> = const_urid!(STATIC_MAP, MyTypeB);
+
+#[test]
+fn test_static_map() {
+    assert_eq!(STATIC_MAP.len(), 3);
+    assert_eq!(STATIC_TYPE_B, 3);
+    assert_eq!(STATIC_MAP.map_type::<MyTypeA>().unwrap(), 1);
+    assert_eq!(STATIC_MAP.map_str("urn:my-type-c").unwrap(), 2);
+    assert_eq!(STATIC_MAP.map_str("urn:my-type-d"), None);
+    assert_eq!(STATIC_MAP.unmap(STATIC_TYPE_B).unwrap(), MyTypeB::uri());
+    assert_eq!(STATIC_MAP.unmap(URID::new(4).unwrap()), None);
+
+    let collection: MyURIDCollection = STATIC_MAP.populate_collection().unwrap();
+    assert_eq!(collection.type_a, 1);
+    assert_eq!(collection.type_b, STATIC_TYPE_B);
+
+    // A hash mapper that is seeded with the static map continues after its URIDs.
+    let mapper = STATIC_MAP.to_hash_mapper().unwrap();
+    assert_eq!(mapper.map_type::<MyTypeB>().unwrap(), STATIC_TYPE_B);
+    assert_eq!(mapper.map_str("urn:my-type-d").unwrap(), 4);
+    assert!(StaticMap::new(&[b"urn:a\0", b"urn:a\0"])
+        .to_hash_mapper()
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "null-terminated")]
+fn test_static_map_without_terminator() {
+    StaticMap::new(&[b"urn:my-type-c"]);
+}
@@ -11,6 +11,8 @@
 //!use std::any::Any;
 //!use lv2_core::feature::*;
 //!use lv2_core::prelude::*;
+//!use lv2_log::*;
+//!use lv2_urid::*;
 //!use urid::*;
 //!use lv2_worker::*;
 //!
@@ -19,9 +21,16 @@
 //!
 //!/// Requested features
 //!#[derive(FeatureCollection)]
+//!struct InitFeatures<'a> {
+//!    map: LV2Map<'a>,
+//!}
+//!
+//!#[derive(FeatureCollection)]
 //!struct AudioFeatures<'a> {
 //!    ///host feature allowing to schedule some work
 //!    schedule: Schedule<'a, EgWorker>,
+//!    ///host feature to log messages, since printing isn't real-time safe
+//!    log: Log<'a>,
 //!}
 //!
 //!//custom datatype
@@ -35,6 +44,7 @@
 //!    // The schedule handler needs to know the plugin type in order to access the `WorkData` type.
 //!    cycle: usize,
 //!    end_cycle: usize,
+//!    urids: LogURIDCollection,
 //!}
 //!
 //!/// URI identifier
@@ -44,25 +54,27 @@
 //!
 //!impl Plugin for EgWorker {
 //!    type Ports = Ports;
-//!    type InitFeatures = ();
+//!    type InitFeatures = InitFeatures<'static>;
 //!    type AudioFeatures = AudioFeatures<'static>;
 //!
-//!    fn new(_plugin_info: &PluginInfo, _features: &mut Self::InitFeatures) -> Option<Self> {
+//!    fn new(_plugin_info: &PluginInfo, features: &mut Self::InitFeatures) -> Option<Self> {
 //!        Some(Self {
 //!            cycle: 0,
 //!            end_cycle: 1,
+//!            urids: features.map.populate_collection()?,
 //!        })
 //!    }
 //!
-//!    fn run(&mut self, _ports: &mut Ports, features: &mut Self::AudioFeatures) {
+//!    fn run(&mut self, _ports: &mut Ports, context: &mut RunContext<Self::AudioFeatures>) {
 //!        self.cycle += 1;
 //!        let cycle = self.cycle;
-//!        println!("cycle {} started", cycle);
+//!        let features = context.features();
+//!        plugin_log!(features.log, &self.urids, Note, "cycle {} started", cycle);
 //!        for task in 0..10 {
 //!            let work = WorkMessage { cycle, task };
 //!            // schedule some work, passing some data and check for error
 //!            if let Err(e) = features.schedule.schedule_work(work) {
-//!                eprintln!("Can't schedule work: {}", e);
+//!                plugin_log!(features.log, &self.urids, Error, "Can't schedule work: {}", e);
 //!            }
 //!        }
 //!    }
@@ -98,31 +110,40 @@
 //!    fn work_response(
 //!        &mut self,
 //!        data: Self::ResponseData,
-//!        _features: &mut Self::AudioFeatures,
+//!        features: &mut Self::AudioFeatures,
 //!    ) -> Result<(), WorkerError> {
-//!        println!("work_response received: {}", data);
+//!        plugin_log!(features.log, &self.urids, Note, "work_response received: {}", data);
 //!        Ok(())
 //!    }
 //!
-//!    fn end_run(&mut self, _features: &mut Self::AudioFeatures) -> Result<(), WorkerError> {
-//!        println!("cycle {} ended", self.end_cycle);
+//!    fn end_run(&mut self, features: &mut Self::AudioFeatures) -> Result<(), WorkerError> {
+//!        plugin_log!(features.log, &self.urids, Note, "cycle {} ended", self.end_cycle);
 //!        self.end_cycle += 1;
 //!        Ok(())
 //!    }
 //!}
 //!```
 
-use lv2_core::extension::ExtensionDescriptor;
+use lv2_core::extension::{ExtensionCall, ExtensionDescriptor};
 use lv2_core::feature::*;
-use lv2_core::plugin::{Plugin, PluginInstance};
+use lv2_core::plugin::Plugin;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
 use std::mem::ManuallyDrop;
 use std::os::raw::*; //get all common c_type
 use std::ptr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::Instant;
 use urid::*;
 
+mod metrics;
+pub use metrics::*;
+
+mod swap;
+pub use swap::*;
+
 /// Errors potentially generated by the
 /// [`Schedule::schedule_work`](struct.Schedule.html#method.schedule_work) method
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -157,10 +178,190 @@ impl<T> fmt::Display for ScheduleError<T> {
     }
 }
 
-/// Host feature providing data to build a ScheduleHandler.
+/// The number of jobs that can be cancelled at the same time.
+const CANCELLATION_SLOTS: usize = 64;
+
+/// The cancelled jobs of a plugin, which are shared between the `run()` context and the worker.
+///
+/// Every job has a slot, which contains the id of the job if it was cancelled. Jobs share a slot if their ids are `CANCELLATION_SLOTS` apart, which is why a cancellation may be overwritten if too many jobs are in flight. Job ids start at one, since an empty slot contains zero.
+struct Cancellations {
+    next_id: AtomicU64,
+    slots: [AtomicU64; CANCELLATION_SLOTS],
+}
+
+impl Default for Cancellations {
+    fn default() -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const EMPTY: AtomicU64 = AtomicU64::new(0);
+        Self {
+            next_id: AtomicU64::new(0),
+            slots: [EMPTY; CANCELLATION_SLOTS],
+        }
+    }
+}
+
+impl Cancellations {
+    fn slot(&self, token: WorkToken) -> &AtomicU64 {
+        &self.slots[(token.id % CANCELLATION_SLOTS as u64) as usize]
+    }
+
+    fn next_token(&self) -> WorkToken {
+        WorkToken {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed) + 1,
+        }
+    }
+
+    // The flags don't guard any other data, which is why relaxed ordering is sufficient.
+    fn cancel(&self, token: WorkToken) {
+        self.slot(token).store(token.id, Ordering::Relaxed);
+    }
+
+    fn is_cancelled(&self, token: WorkToken) -> bool {
+        self.slot(token).load(Ordering::Relaxed) == token.id
+    }
+}
+
+/// The cancellations and metrics of the `Schedule`s that were created from a feature, keyed by the address of the feature.
+///
+/// A plugin may create several `Schedule`s from the same feature, for example when it's instantiated and when it lends its features to the audio threading class later. They have to share the cancelled jobs and the metrics, which is why the state is created by the first `Schedule` of a feature, usually when the plugin is instantiated, and found by all later ones. The `Schedule`s keep the state alive, while jobs only refer to it weakly since a host may discard them without calling the worker.
+static SHARED_STATES: Mutex<Vec<SharedState>> = Mutex::new(Vec::new());
+
+struct SharedState {
+    feature: usize,
+    cancellations: Weak<Cancellations>,
+    metrics: Weak<WorkerMetrics>,
+}
+
+/// Find the state of the `Schedule`s of the given feature, or create it if there's none.
+fn shared_state(
+    feature: *const lv2_sys::LV2_Worker_Schedule,
+) -> (Arc<Cancellations>, Arc<WorkerMetrics>) {
+    // The table can't be left in an inconsistent state, which is why a poisoned lock is ignored.
+    let mut states = SHARED_STATES.lock().unwrap_or_else(PoisonError::into_inner);
+    let feature = feature as usize;
+    if let Some(state) = states.iter().find(|state| state.feature == feature) {
+        if let (Some(cancellations), Some(metrics)) =
+            (state.cancellations.upgrade(), state.metrics.upgrade())
+        {
+            return (cancellations, metrics);
+        }
+    }
+    states.retain(|state| state.feature != feature && state.cancellations.strong_count() > 0);
+    let cancellations = Arc::<Cancellations>::default();
+    let metrics = Arc::<WorkerMetrics>::default();
+    states.push(SharedState {
+        feature,
+        cancellations: Arc::downgrade(&cancellations),
+        metrics: Arc::downgrade(&metrics),
+    });
+    (cancellations, metrics)
+}
+
+/// A token that identifies a scheduled job.
+///
+/// It's returned by [`Schedule::schedule_work`](struct.Schedule.html#method.schedule_work) and can be used to [cancel](struct.Schedule.html#method.cancel) the job, for example if a newer request supersedes it. Within `work()`, the [`ResponseHandler`](struct.ResponseHandler.html#method.is_cancelled) tells whether the current job was cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WorkToken {
+    id: u64,
+}
+
+/// The priority of a scheduled job.
+///
+/// Hosts usually handle work in the order it was scheduled, but a host that knows about priorities, like the test host of `lv2-test`, may handle more urgent jobs first; For example, loading the sample of a note that is currently sounding before preloading the other ones. The priority of a request can be read by the host with [`of_request`](#method.of_request), and within `work()`, the [`ResponseHandler`](struct.ResponseHandler.html#method.priority) tells the priority of the current job.
+///
+/// Higher priorities are more urgent. Jobs that are scheduled without a priority have the [`NORMAL`](#associatedconstant.NORMAL) one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
+pub struct Priority(pub u8);
+
+impl Priority {
+    /// The priority of work that can wait, like preloading data that may be needed later.
+    pub const BACKGROUND: Self = Self(0);
+    /// The priority of work that is scheduled without a priority.
+    pub const NORMAL: Self = Self(128);
+    /// The priority of work that is needed as soon as possible.
+    pub const URGENT: Self = Self(255);
+
+    /// Read the priority of a work request, as it's passed to the `schedule_work` function of the host.
+    ///
+    /// If the request wasn't scheduled by this crate, `None` is returned.
+    pub fn of_request(data: &[u8]) -> Option<Self> {
+        if data.len() < mem::size_of::<JobHeader>() {
+            return None;
+        }
+        let header = unsafe { ptr::read_unaligned(data.as_ptr() as *const JobHeader) };
+        if header.magic == JOB_MAGIC {
+            Some(header.priority)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// The value that marks the requests of this crate.
+const JOB_MAGIC: u32 = u32::from_ne_bytes(*b"lv2w");
+
+/// The start of every job, which can be read by hosts that don't know the type of the job.
+///
+/// The header is part of the [binary contract](../lv2_core/abi/index.html) of rust-lv2, since hosts read it from plugins that may be built with other releases.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct JobHeader {
+    magic: u32,
+    priority: Priority,
+}
+
+const _: () = {
+    assert!(mem::size_of::<Priority>() == 1);
+    assert!(mem::offset_of!(JobHeader, magic) == 0);
+    assert!(mem::offset_of!(JobHeader, priority) == 4);
+    assert!(mem::offset_of!(Job<()>, header) == 0);
+};
+
+/// The data of a job, as it's sent to the worker.
+#[repr(C)]
+struct Job<T> {
+    header: JobHeader,
+    token: WorkToken,
+    cancellations: Weak<Cancellations>,
+    metrics: Weak<WorkerMetrics>,
+    scheduled: Instant,
+    data: T,
+}
+
+/// Host feature providing data to build a ScheduleHandler.
+///
+/// All `Schedule`s that are created from the same feature share the cancelled jobs and the [metrics](#method.metrics), no matter whether they were created when the plugin was instantiated or lent later. The shared state is created along with the first `Schedule`, which is why creating further ones doesn't allocate memory.
+///
+/// Work may only be scheduled in the audio threading class: in `run()`, `work_response()` and `end_run()`. The feature therefore can only be part of the audio feature collection, which the plugin only borrows through the `RunContext` and the parameters of the worker methods. It's neither `Send` nor `Sync`, and since plugins have to be both, it can't be kept in the plugin or be passed to another thread:
+///
+/// ```compile_fail
+/// # use lv2_worker::*;
+/// # struct EgWorker;
+/// fn share(schedule: Schedule<'static, EgWorker>) {
+///     std::thread::spawn(move || drop(schedule));
+/// }
+/// ```
+///
+/// Likewise, the raw struct returned by [`as_raw`](#method.as_raw) only lives as long as the borrow of the feature:
+///
+/// ```compile_fail
+/// # use lv2_worker::*;
+/// # struct EgWorker;
+/// fn stash(schedule: &Schedule<'static, EgWorker>) -> &'static lv2_sys::LV2_Worker_Schedule {
+///     schedule.as_raw()
+/// }
+/// ```
 pub struct Schedule<'a, P> {
     internal: &'a lv2_sys::LV2_Worker_Schedule,
+    cancellations: Arc<Cancellations>,
+    metrics: Arc<WorkerMetrics>,
     phantom: PhantomData<*const P>,
 }
 
@@ -169,17 +370,27 @@ unsafe impl<'a, P> UriBound for Schedule<'a, P> {
 }
 
 unsafe impl<'a, P> Feature for Schedule<'a, P> {
-    unsafe fn from_feature_ptr(feature: *const c_void, class: ThreadingClass) -> Option<Self> {
-        if class == ThreadingClass::Audio {
-            (feature as *const lv2_sys::LV2_Worker_Schedule)
-                .as_ref()
-                .map(|internal| Self {
+    const THREADING_CLASSES: &'static [ThreadingClass] = &[ThreadingClass::Audio];
+
+    unsafe fn from_feature_ptr(
+        feature: *const c_void,
+        class: ThreadingClass,
+    ) -> Result<Self, FeatureError> {
+        if !class.is_in(Self::THREADING_CLASSES) {
+            return Err(FeatureError::wrong_threading_class::<Self>(class));
+        }
+        (feature as *const lv2_sys::LV2_Worker_Schedule)
+            .as_ref()
+            .map(|internal| {
+                let (cancellations, metrics) = shared_state(internal);
+                Self {
                     internal,
+                    cancellations,
+                    metrics,
                     phantom: PhantomData::<*const P>,
-                })
-        } else {
-            panic!("The Worker Schedule feature is only allowed in the audio threading class");
-        }
+                }
+            })
+            .ok_or_else(FeatureError::invalid_data::<Self>)
     }
 }
 
@@ -204,33 +415,94 @@ impl<'a, P: Worker> Schedule<'a, P> {
     /// **Notes about the passed data:** The buffer used to pass data is managed by the host. That
     /// mean the size is unknown and may be limited. So if you need to pass huge amount of data,
     /// it's preferable to use another way, for example a sync::mpsc channel.
-    pub fn schedule_work(&self, worker_data: P::WorkData) -> Result<(), ScheduleError<P::WorkData>>
+    ///
+    /// If the work was scheduled, a token for it is returned, which can be used to
+    /// [cancel](#method.cancel) it later.
+    ///
+    /// The work is scheduled with the [normal priority](struct.Priority.html#associatedconstant.NORMAL).
+    pub fn schedule_work(
+        &self,
+        worker_data: P::WorkData,
+    ) -> Result<WorkToken, ScheduleError<P::WorkData>>
     where
         P::WorkData: 'static + Send,
     {
-        let worker_data = ManuallyDrop::new(worker_data);
-        let size = mem::size_of_val(&worker_data) as u32;
-        let ptr = &worker_data as *const _ as *const c_void;
+        self.schedule_work_with_priority(worker_data, Priority::NORMAL)
+    }
+
+    /// Request the host to call the worker thread with the given priority.
+    ///
+    /// This works just like [`schedule_work`](#method.schedule_work), but hosts that know about priorities may handle more urgent work first. Other hosts ignore the priority, but it's always delivered to `work()`.
+    pub fn schedule_work_with_priority(
+        &self,
+        worker_data: P::WorkData,
+        priority: Priority,
+    ) -> Result<WorkToken, ScheduleError<P::WorkData>>
+    where
+        P::WorkData: 'static + Send,
+    {
+        let token = self.cancellations.next_token();
+        let job = ManuallyDrop::new(Job {
+            header: JobHeader {
+                magic: JOB_MAGIC,
+                priority,
+            },
+            token,
+            cancellations: Arc::downgrade(&self.cancellations),
+            metrics: Arc::downgrade(&self.metrics),
+            scheduled: Instant::now(),
+            data: worker_data,
+        });
+        let size = mem::size_of_val(&job) as u32;
+        let ptr = &job as *const _ as *const c_void;
         let schedule_work = if let Some(schedule_work) = self.internal.schedule_work {
             schedule_work
         } else {
-            return Err(ScheduleError::NoCallback(ManuallyDrop::into_inner(
-                worker_data,
-            )));
+            self.metrics.count_failed();
+            return Err(ScheduleError::NoCallback(
+                ManuallyDrop::into_inner(job).data,
+            ));
         };
         match unsafe { (schedule_work)(self.internal.handle, size, ptr) } {
-            lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS => Ok(()),
-            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN => Err(ScheduleError::Unknown(
-                ManuallyDrop::into_inner(worker_data),
-            )),
-            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE => Err(ScheduleError::NoSpace(
-                ManuallyDrop::into_inner(worker_data),
-            )),
-            _ => Err(ScheduleError::Unknown(ManuallyDrop::into_inner(
-                worker_data,
-            ))),
+            lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS => {
+                self.metrics.count_scheduled();
+                Ok(token)
+            }
+            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE => {
+                self.metrics.count_rejected();
+                Err(ScheduleError::NoSpace(ManuallyDrop::into_inner(job).data))
+            }
+            _ => {
+                self.metrics.count_failed();
+                Err(ScheduleError::Unknown(ManuallyDrop::into_inner(job).data))
+            }
         }
     }
+
+    /// Flag scheduled work as stale.
+    ///
+    /// This is a cheap, real-time safe operation that can be called from `run()`, for example when a newer request supersedes the scheduled one. The work isn't removed from the host's queue: Its `work()` call still happens, but it can check whether the job was cancelled with [`ResponseHandler::is_cancelled`](struct.ResponseHandler.html#method.is_cancelled) and skip the obsolete work.
+    ///
+    /// Cancelling is a best-effort mechanism: If more than 64 jobs are in flight, the cancellation of an older job may be missed.
+    pub fn cancel(&self, token: WorkToken) {
+        self.cancellations.cancel(token);
+    }
+
+    /// The metrics of the plugin's worker.
+    ///
+    /// They count the scheduled, rejected and completed jobs, as well as the responses and the latency of the work. The returned pointer can be cloned to read the metrics elsewhere, for example to report them to the UI.
+    pub fn metrics(&self) -> &Arc<WorkerMetrics> {
+        &self.metrics
+    }
+}
+
+impl<'a, P> Schedule<'a, P> {
+    /// Return the raw schedule struct of the host.
+    ///
+    /// This lets C libraries schedule work on their own. The struct is only borrowed for as long as the feature, which keeps it from being used outside of the audio threading class. However, the host passes all scheduled work to the `work` function of the plugin's worker interface, and the one of the [`WorkerDescriptor`](struct.WorkerDescriptor.html) only accepts jobs that were scheduled with [`schedule_work`](#method.schedule_work). Work that is scheduled with the raw struct therefore needs a worker interface of its own. Like the feature, the struct may only be used in the audio threading class.
+    pub fn as_raw(&self) -> &lv2_sys::LV2_Worker_Schedule {
+        self.internal
+    }
 }
 
 /// Errors potentially generated by the
@@ -277,10 +549,37 @@ pub struct ResponseHandler<P: Worker> {
     /// Response handler provided by the host, must be passed to the host provided
     /// response_function.
     respond_handle: lv2_sys::LV2_Worker_Respond_Handle,
+    /// The token and priority of the current job and the cancelled jobs of the plugin.
+    token: WorkToken,
+    priority: Priority,
+    cancellations: Arc<Cancellations>,
+    metrics: Arc<WorkerMetrics>,
     phantom: PhantomData<P>,
 }
 
 impl<P: Worker> ResponseHandler<P> {
+    /// The token of the job that is currently worked on.
+    pub fn token(&self) -> WorkToken {
+        self.token
+    }
+
+    /// The priority the current job was scheduled with.
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Check whether the current job was [cancelled](struct.Schedule.html#method.cancel) by the `run()` context.
+    ///
+    /// This is only an atomic load, which means that it can be checked repeatedly during long-running work, like loading a sample file chunk by chunk.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellations.is_cancelled(self.token)
+    }
+
+    /// The [metrics](struct.WorkerMetrics.html) of the plugin's worker, which are shared with the `Schedule` feature.
+    pub fn metrics(&self) -> &Arc<WorkerMetrics> {
+        &self.metrics
+    }
+
     /// Send a response to the `run` context.
     ///
     /// This method allows the worker to give a response to the `run` context. After calling this
@@ -305,13 +604,16 @@ impl<P: Worker> ResponseHandler<P> {
             )));
         };
         match unsafe { (response_function)(self.respond_handle, size, ptr) } {
-            lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS => Ok(()),
-            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN => Err(RespondError::Unknown(
-                ManuallyDrop::into_inner(response_data),
-            )),
-            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE => Err(RespondError::NoSpace(
-                ManuallyDrop::into_inner(response_data),
-            )),
+            lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS => {
+                self.metrics.count_response();
+                Ok(())
+            }
+            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE => {
+                self.metrics.count_dropped_response();
+                Err(RespondError::NoSpace(ManuallyDrop::into_inner(
+                    response_data,
+                )))
+            }
             _ => Err(RespondError::Unknown(ManuallyDrop::into_inner(
                 response_data,
             ))),
@@ -320,12 +622,69 @@ impl<P: Worker> ResponseHandler<P> {
 }
 
 /// Errors potentially generated by [`Worker`](trait.Worker.html) methods
+///
+/// The host only learns whether an error was caused by a lack of space, which is why the error is also handed to [`Worker::report_error`](trait.Worker.html#method.report_error) before it's reported. Plugins can use the `Code` and `Message` variants to tell their failures apart there. Errors never allocate, which is why they can be returned in the `run()` context.
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum WorkerError {
     /// Unknown or general error
     Unknown,
     /// Failure due to a lack of space
     NoSpace,
+    /// The host passed invalid data, like a null handle or data of the wrong size
+    BadData,
+    /// A plugin-specific error, identified by a code that is chosen by the plugin
+    Code(u32),
+    /// A plugin-specific error, described by a static message
+    Message(&'static str),
+}
+
+impl WorkerError {
+    /// The status that is reported to the host for this error.
+    ///
+    /// A lack of space is reported as `LV2_WORKER_ERR_NO_SPACE` and all other errors are reported as `LV2_WORKER_ERR_UNKNOWN`, since the specification doesn't know any other statuses.
+    pub fn status(self) -> lv2_sys::LV2_Worker_Status {
+        match self {
+            WorkerError::NoSpace => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+            _ => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+        }
+    }
+}
+
+impl fmt::Display for WorkerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            WorkerError::Unknown => "unknown error".fmt(f),
+            WorkerError::NoSpace => "not enough space".fmt(f),
+            WorkerError::BadData => "invalid data".fmt(f),
+            WorkerError::Code(code) => write!(f, "error code {}", code),
+            WorkerError::Message(message) => message.fmt(f),
+        }
+    }
+}
+
+/// The method of a [`Worker`](trait.Worker.html) in which an error occurred.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum WorkerStage {
+    /// The `work` method, which is called in the worker thread.
+    Work,
+    /// The `work_response` method, or `work_responses` of a [`BatchWorker`](trait.BatchWorker.html), which are called in the `run()` context.
+    WorkResponse,
+    /// The `end_run` method, which is called in the `run()` context.
+    EndRun,
+}
+
+/// Report the result of a worker method to the host, after handing errors to the plugin's hook.
+fn report_result<P: Worker>(
+    stage: WorkerStage,
+    result: Result<(), WorkerError>,
+) -> lv2_sys::LV2_Worker_Status {
+    match result {
+        Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
+        Err(error) => {
+            P::report_error(stage, error);
+            error.status()
+        }
+    }
 }
 
 /// The non-realtime working extension for plugins.
@@ -351,6 +710,9 @@ pub trait Worker: Plugin {
     /// only one call may be executed at a time. That is, the host MAY call this method from any
     /// non-real-time thread, but MUST NOT make concurrent calls to this method from several
     /// threads.
+    ///
+    /// If the job may be cancelled by the `run()` context, the response handler tells whether it's
+    /// obsolete, which allows skipping it.
     fn work(
         response_handler: &ResponseHandler<Self>,
         data: Self::WorkData,
@@ -370,10 +732,18 @@ pub trait Worker: Plugin {
     ///Called when all responses for this cycle have been delivered.
     ///
     ///Since work_response() may be called after `run()` finished, this method provides a hook for code that
-    ///must run after the cycle is completed.
+    ///must run after the cycle is completed. The host calls it after `run()` and after every response of the
+    ///cycle has been delivered, which means that no `work_response()` call of the cycle follows it.
     fn end_run(&mut self, _features: &mut Self::AudioFeatures) -> Result<(), WorkerError> {
         Ok(())
     }
+
+    /// Called with every error of the other methods, right before it's reported to the host.
+    ///
+    /// Since the host only learns whether there was a lack of space, this is the place to record what went wrong, for example by storing the error in an atomic or by sending it to a logging thread. This includes errors of the plugin itself and the `BadData` errors of faulty hosts.
+    ///
+    /// The hook is called in the thread of the failed method, i.e. in the worker thread for `work` and in the `run()` context otherwise, which is why it must not block or allocate. The default implementation does nothing.
+    fn report_error(_stage: WorkerStage, _error: WorkerError) {}
 }
 
 ///Raw wrapper of the [`Worker`](trait.Worker.html) extension.
@@ -396,24 +766,30 @@ impl<P: Worker> WorkerDescriptor<P> {
         size: u32,
         data: *const c_void,
     ) -> lv2_sys::LV2_Worker_Status {
+        // The job contains a reference-counted pointer, which is why it must not be read if the size doesn't match.
+        if size as usize != mem::size_of::<Job<<P as Worker>::WorkData>>() {
+            return report_result::<P>(WorkerStage::Work, Err(WorkerError::BadData));
+        }
+        //build ref to the job from raw pointer
+        let job =
+            ptr::read_unaligned(data as *const mem::ManuallyDrop<Job<<P as Worker>::WorkData>>);
+        let job = mem::ManuallyDrop::into_inner(job);
         //build response handler
         let response_handler = ResponseHandler {
             response_function,
             respond_handle,
+            token: job.token,
+            priority: job.header.priority,
+            // The state is gone if the plugin dropped all of its `Schedule`s, which means that nobody waits for the job anymore.
+            cancellations: job.cancellations.upgrade().unwrap_or_default(),
+            metrics: job.metrics.upgrade().unwrap_or_default(),
             phantom: PhantomData::<P>,
         };
-        //build ref to worker data from raw pointer
-        let worker_data =
-            ptr::read_unaligned(data as *const mem::ManuallyDrop<<P as Worker>::WorkData>);
-        let worker_data = mem::ManuallyDrop::into_inner(worker_data);
-        if size as usize != mem::size_of_val(&worker_data) {
-            return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
-        }
-        match P::work(&response_handler, worker_data) {
-            Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
-            Err(WorkerError::Unknown) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
-            Err(WorkerError::NoSpace) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
-        }
+        let result = P::work(&response_handler, job.data);
+        response_handler
+            .metrics
+            .count_completed(job.scheduled.elapsed());
+        report_result::<P>(WorkerStage::Work, result)
     }
 
     /// Extern unsafe version of `work_response` method actually called by the host
@@ -422,41 +798,31 @@ impl<P: Worker> WorkerDescriptor<P> {
         size: u32,
         body: *const c_void,
     ) -> lv2_sys::LV2_Worker_Status {
-        //deref plugin_instance and get the plugin
-        let plugin_instance =
-            if let Some(plugin_instance) = (handle as *mut PluginInstance<P>).as_mut() {
-                plugin_instance
-            } else {
-                return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
-            };
-        //build ref to response data from raw pointer
-        let response_data =
-            ptr::read_unaligned(body as *const mem::ManuallyDrop<<P as Worker>::ResponseData>);
-        let response_data = mem::ManuallyDrop::into_inner(response_data);
-        if size as usize != mem::size_of_val(&response_data) {
-            return lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN;
-        }
-
-        let (instance, features) = plugin_instance.audio_class_handle();
-        match instance.work_response(response_data, features) {
-            Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
-            Err(WorkerError::Unknown) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
-            Err(WorkerError::NoSpace) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+        // The response may own resources, which is why it must not be read if the size doesn't match.
+        if size as usize != mem::size_of::<<P as Worker>::ResponseData>() {
+            return report_result::<P>(WorkerStage::WorkResponse, Err(WorkerError::BadData));
         }
+        let result = ExtensionCall::<P>::from_handle(handle).audio_class(|instance, features| {
+            //build ref to response data from raw pointer
+            let response_data =
+                ptr::read_unaligned(body as *const mem::ManuallyDrop<<P as Worker>::ResponseData>);
+            let response_data = mem::ManuallyDrop::into_inner(response_data);
+            instance.work_response(response_data, features)
+        });
+        report_result::<P>(
+            WorkerStage::WorkResponse,
+            result.unwrap_or(Err(WorkerError::BadData)),
+        )
     }
 
     /// Extern unsafe version of `end_run` method actually called by the host
     unsafe extern "C" fn extern_end_run(handle: lv2_sys::LV2_Handle) -> lv2_sys::LV2_Worker_Status {
-        if let Some(plugin_instance) = (handle as *mut PluginInstance<P>).as_mut() {
-            let (instance, features) = plugin_instance.audio_class_handle();
-            match instance.end_run(features) {
-                Ok(()) => lv2_sys::LV2_Worker_Status_LV2_WORKER_SUCCESS,
-                Err(WorkerError::Unknown) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
-                Err(WorkerError::NoSpace) => lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
-            }
-        } else {
-            lv2_sys::LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN
-        }
+        let result = ExtensionCall::<P>::from_handle(handle)
+            .audio_class(|instance, features| instance.end_run(features));
+        report_result::<P>(
+            WorkerStage::EndRun,
+            result.unwrap_or(Err(WorkerError::BadData)),
+        )
     }
 }
 
@@ -471,6 +837,169 @@ impl<P: Worker> ExtensionDescriptor for WorkerDescriptor<P> {
     };
 }
 
+/// A bounded queue that collects the responses of a cycle for a [`BatchWorker`](trait.BatchWorker.html).
+///
+/// The queue is created with a fixed capacity when the plugin is instantiated and never allocates afterwards, which is why it can be used in the `run()` context. If the queue is full, further responses of the cycle are dropped and the host is told that there's not enough space.
+pub struct ResponseQueue<T> {
+    responses: Vec<T>,
+    capacity: usize,
+}
+
+impl<T> ResponseQueue<T> {
+    /// Create a new queue that holds up to `capacity` responses per cycle.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            responses: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// The maximal number of responses per cycle.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of responses that are currently queued.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Check whether no responses are queued.
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Add a response to the queue, or return it if the queue is full.
+    fn push(&mut self, response: T) -> Result<(), T> {
+        if self.responses.len() < self.capacity {
+            self.responses.push(response);
+            Ok(())
+        } else {
+            Err(response)
+        }
+    }
+}
+
+/// An iterator over the responses of a cycle, which are handed to [`BatchWorker::work_responses`](trait.BatchWorker.html#tymethod.work_responses).
+///
+/// The responses are removed from the queue while iterating. Responses that aren't consumed are dropped.
+pub struct Responses<'a, T> {
+    drain: std::vec::Drain<'a, T>,
+}
+
+impl<'a, T> Iterator for Responses<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Responses<'a, T> {}
+
+/// A worker that receives all responses of a cycle at once.
+///
+/// Usually, every response is handed to `work_response` as soon as it's delivered, which means that plugins that need all responses of a cycle at once, like a synthesizer that swaps a complete set of wavetables, have to keep track of partially arrived responses. A batch worker collects the responses in its [`ResponseQueue`](struct.ResponseQueue.html) instead and receives them in a single call to [`work_responses`](#tymethod.work_responses), right before `end_run` is called.
+///
+/// In order to be used by the host, you need to export the [`BatchWorkerDescriptor`](struct.BatchWorkerDescriptor.html) instead of the `WorkerDescriptor` in the `extension_data` method. The `work_response` method of the [`Worker`](trait.Worker.html) trait is never called then.
+pub trait BatchWorker: Worker {
+    /// The queue in which the responses of the current cycle are collected.
+    ///
+    /// The queue has to be created when the plugin is instantiated, for example with `ResponseQueue::with_capacity(16)`, and must always be the same.
+    fn response_queue(&mut self) -> &mut ResponseQueue<Self::ResponseData>;
+
+    /// Handle all responses of a cycle.
+    ///
+    /// This is called by the host in the `run()` context when all responses of the cycle have been delivered, but only if there is at least one. Afterwards, `end_run` is called, even if this method fails.
+    fn work_responses(
+        &mut self,
+        responses: Responses<Self::ResponseData>,
+        features: &mut Self::AudioFeatures,
+    ) -> Result<(), WorkerError>;
+}
+
+///Raw wrapper of the [`BatchWorker`](trait.BatchWorker.html) extension.
+///
+/// This is a marker type that has the required external methods for the extension. It exports the same interface as the [`WorkerDescriptor`](struct.WorkerDescriptor.html), which is why a plugin must only export one of them.
+pub struct BatchWorkerDescriptor<P: BatchWorker> {
+    plugin: PhantomData<P>,
+}
+
+unsafe impl<P: BatchWorker> UriBound for BatchWorkerDescriptor<P> {
+    const URI: &'static [u8] = lv2_sys::LV2_WORKER__interface;
+}
+
+impl<P: BatchWorker> BatchWorkerDescriptor<P> {
+    /// Extern unsafe version of `work_response` method actually called by the host, which queues the response
+    unsafe extern "C" fn extern_work_response(
+        handle: lv2_sys::LV2_Handle,
+        size: u32,
+        body: *const c_void,
+    ) -> lv2_sys::LV2_Worker_Status {
+        // The response may own resources, which is why it must not be read if the size doesn't match.
+        if size as usize != mem::size_of::<<P as Worker>::ResponseData>() {
+            return report_result::<P>(WorkerStage::WorkResponse, Err(WorkerError::BadData));
+        }
+        let result = ExtensionCall::<P>::from_handle(handle).audio_class(|instance, _| {
+            //build ref to response data from raw pointer
+            let response_data =
+                ptr::read_unaligned(body as *const mem::ManuallyDrop<<P as Worker>::ResponseData>);
+            let response_data = mem::ManuallyDrop::into_inner(response_data);
+            instance
+                .response_queue()
+                .push(response_data)
+                .map_err(|_| WorkerError::NoSpace)
+        });
+        report_result::<P>(
+            WorkerStage::WorkResponse,
+            result.unwrap_or(Err(WorkerError::BadData)),
+        )
+    }
+
+    /// Extern unsafe version of `end_run` method actually called by the host, which hands the queued responses to `work_responses` first
+    unsafe extern "C" fn extern_end_run(handle: lv2_sys::LV2_Handle) -> lv2_sys::LV2_Worker_Status {
+        let result = ExtensionCall::<P>::from_handle(handle).audio_class(|instance, features| {
+            // The queue is moved out of the plugin while the responses are handled, so that the plugin can be borrowed mutably. An empty vector doesn't allocate.
+            let mut queue = mem::replace(
+                instance.response_queue(),
+                ResponseQueue {
+                    responses: Vec::new(),
+                    capacity: 0,
+                },
+            );
+            let batch_result = if queue.is_empty() {
+                Ok(())
+            } else {
+                let responses = Responses {
+                    drain: queue.responses.drain(..),
+                };
+                instance.work_responses(responses, features)
+            };
+            *instance.response_queue() = queue;
+            let end_run_result = instance.end_run(features);
+            batch_result.and(end_run_result)
+        });
+        report_result::<P>(
+            WorkerStage::EndRun,
+            result.unwrap_or(Err(WorkerError::BadData)),
+        )
+    }
+}
+
+impl<P: BatchWorker> ExtensionDescriptor for BatchWorkerDescriptor<P> {
+    type ExtensionInterface = lv2_sys::LV2_Worker_Interface;
+
+    const INTERFACE: &'static lv2_sys::LV2_Worker_Interface = &lv2_sys::LV2_Worker_Interface {
+        work: Some(WorkerDescriptor::<P>::extern_work),
+        work_response: Some(Self::extern_work_response),
+        end_run: Some(Self::extern_end_run),
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -531,7 +1060,7 @@ mod tests {
             Some(Self {})
         }
 
-        fn run(&mut self, _ports: &mut Ports, _features: &mut Self::InitFeatures) {}
+        fn run(&mut self, _ports: &mut Ports, _context: &mut RunContext<()>) {}
     }
 
     impl Worker for TestDropWorker {
@@ -554,6 +1083,185 @@ mod tests {
         }
     }
 
+    struct TestCancelWorker;
+
+    // URI identifier
+    unsafe impl UriBound for TestCancelWorker {
+        const URI: &'static [u8] = b"not relevant\0";
+    }
+
+    impl Plugin for TestCancelWorker {
+        type Ports = Ports;
+        type InitFeatures = ();
+        type AudioFeatures = ();
+
+        fn new(_plugin_info: &PluginInfo, _features: &mut Self::InitFeatures) -> Option<Self> {
+            Some(Self {})
+        }
+
+        fn run(&mut self, _ports: &mut Ports, _context: &mut RunContext<()>) {}
+    }
+
+    impl Worker for TestCancelWorker {
+        type WorkData = u32;
+        type ResponseData = bool;
+
+        // respond whether the job was cancelled
+        fn work(response_handler: &ResponseHandler<Self>, _data: u32) -> Result<(), WorkerError> {
+            response_handler
+                .respond(response_handler.is_cancelled())
+                .map_err(|_| WorkerError::Unknown)
+        }
+    }
+
+    struct TestFailWorker;
+
+    // errors that were reported by `TestFailWorker`
+    static REPORTED_ERRORS: std::sync::Mutex<Vec<(WorkerStage, WorkerError)>> =
+        std::sync::Mutex::new(Vec::new());
+
+    // URI identifier
+    unsafe impl UriBound for TestFailWorker {
+        const URI: &'static [u8] = b"not relevant\0";
+    }
+
+    impl Plugin for TestFailWorker {
+        type Ports = Ports;
+        type InitFeatures = ();
+        type AudioFeatures = ();
+
+        fn new(_plugin_info: &PluginInfo, _features: &mut Self::InitFeatures) -> Option<Self> {
+            Some(Self {})
+        }
+
+        fn run(&mut self, _ports: &mut Ports, _context: &mut RunContext<()>) {}
+    }
+
+    impl Worker for TestFailWorker {
+        type WorkData = u32;
+        type ResponseData = bool;
+
+        // fail with the job as error code, or with a lack of space for job 0
+        fn work(_response_handler: &ResponseHandler<Self>, data: u32) -> Result<(), WorkerError> {
+            if data == 0 {
+                Err(WorkerError::NoSpace)
+            } else {
+                Err(WorkerError::Code(data))
+            }
+        }
+
+        fn report_error(stage: WorkerStage, error: WorkerError) {
+            REPORTED_ERRORS.lock().unwrap().push((stage, error));
+        }
+    }
+
+    struct TestBatchWorker {
+        responses: ResponseQueue<Box<u32>>,
+    }
+
+    // errors that were reported by `TestBatchWorker`
+    static BATCH_ERRORS: std::sync::Mutex<Vec<(WorkerStage, WorkerError)>> =
+        std::sync::Mutex::new(Vec::new());
+
+    // URI identifier
+    unsafe impl UriBound for TestBatchWorker {
+        const URI: &'static [u8] = b"not relevant\0";
+    }
+
+    impl Plugin for TestBatchWorker {
+        type Ports = Ports;
+        type InitFeatures = ();
+        type AudioFeatures = ();
+
+        fn new(_plugin_info: &PluginInfo, _features: &mut Self::InitFeatures) -> Option<Self> {
+            Some(Self {
+                responses: ResponseQueue::with_capacity(1),
+            })
+        }
+
+        fn run(&mut self, _ports: &mut Ports, _context: &mut RunContext<()>) {}
+    }
+
+    impl Worker for TestBatchWorker {
+        type WorkData = u32;
+        type ResponseData = Box<u32>;
+
+        fn work(response_handler: &ResponseHandler<Self>, data: u32) -> Result<(), WorkerError> {
+            response_handler
+                .respond(Box::new(data))
+                .map_err(|_| WorkerError::Unknown)
+        }
+
+        fn report_error(stage: WorkerStage, error: WorkerError) {
+            BATCH_ERRORS.lock().unwrap().push((stage, error));
+        }
+    }
+
+    impl BatchWorker for TestBatchWorker {
+        fn response_queue(&mut self) -> &mut ResponseQueue<Box<u32>> {
+            &mut self.responses
+        }
+
+        fn work_responses(
+            &mut self,
+            _responses: Responses<Box<u32>>,
+            _features: &mut Self::AudioFeatures,
+        ) -> Result<(), WorkerError> {
+            Ok(())
+        }
+    }
+
+    // copy the job into the `Vec<Vec<u8>>` behind the handle
+    extern "C" fn copy_schedule(
+        handle: LV2_Worker_Schedule_Handle,
+        size: u32,
+        data: *const c_void,
+    ) -> LV2_Worker_Status {
+        let jobs = unsafe { &mut *(handle as *mut Vec<Vec<u8>>) };
+        let job = unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) };
+        jobs.push(job.to_owned());
+        LV2_Worker_Status_LV2_WORKER_SUCCESS
+    }
+
+    // push the response into the `Vec<bool>` behind the handle
+    extern "C" fn record_respond(
+        handle: LV2_Worker_Respond_Handle,
+        _size: u32,
+        data: *const c_void,
+    ) -> LV2_Worker_Status {
+        let responses = unsafe { &mut *(handle as *mut Vec<bool>) };
+        responses.push(unsafe { *(data as *const bool) });
+        LV2_Worker_Status_LV2_WORKER_SUCCESS
+    }
+
+    // a host that copies the jobs into `jobs`, unless it rejects them with `status`
+    struct RejectingHost {
+        jobs: Vec<Vec<u8>>,
+        status: LV2_Worker_Status,
+    }
+
+    impl Default for RejectingHost {
+        fn default() -> Self {
+            Self {
+                jobs: Vec::new(),
+                status: LV2_Worker_Status_LV2_WORKER_SUCCESS,
+            }
+        }
+    }
+
+    extern "C" fn rejecting_schedule(
+        handle: LV2_Worker_Schedule_Handle,
+        size: u32,
+        data: *const c_void,
+    ) -> LV2_Worker_Status {
+        let host = unsafe { &mut *(handle as *mut RejectingHost) };
+        if host.status == LV2_Worker_Status_LV2_WORKER_SUCCESS {
+            copy_schedule(&mut host.jobs as *mut _ as *mut c_void, size, data)
+        } else {
+            host.status
+        }
+    }
+
     extern "C" fn extern_schedule(
         _handle: LV2_Worker_Schedule_Handle,
         _size: u32,
@@ -586,6 +1294,44 @@ mod tests {
         LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN
     }
 
+    // create a schedule for the feature like the framework does, sharing the state of the other schedules of the feature
+    fn new_schedule<P: Worker>(internal: &LV2_Worker_Schedule) -> Schedule<'_, P> {
+        unsafe {
+            Schedule::from_feature_ptr(internal as *const _ as *const c_void, ThreadingClass::Audio)
+        }
+        .unwrap()
+    }
+
+    // create a handler for the response to job 1, with a fresh state
+    fn new_response_handler<P: Worker>(
+        response_function: LV2_Worker_Respond_Function,
+    ) -> ResponseHandler<P> {
+        ResponseHandler {
+            response_function,
+            respond_handle: ptr::null_mut(),
+            token: WorkToken { id: 1 },
+            priority: Priority::NORMAL,
+            cancellations: Arc::default(),
+            metrics: Arc::default(),
+            phantom: PhantomData,
+        }
+    }
+
+    // create job 1 without a schedule
+    fn new_job<T>(data: T) -> Job<T> {
+        Job {
+            header: JobHeader {
+                magic: JOB_MAGIC,
+                priority: Priority::NORMAL,
+            },
+            token: WorkToken { id: 1 },
+            cancellations: Weak::new(),
+            metrics: Weak::new(),
+            scheduled: Instant::now(),
+            data,
+        }
+    }
+
     #[test]
     fn schedule_must_not_drop() {
         let hd = HasDrop::new(0);
@@ -593,10 +1339,7 @@ mod tests {
             handle: ptr::null_mut(),
             schedule_work: Some(extern_schedule),
         };
-        let schedule = Schedule {
-            internal: &internal,
-            phantom: PhantomData::<*const TestDropWorker>,
-        };
+        let schedule = new_schedule::<TestDropWorker>(&internal);
         let _ = schedule.schedule_work(hd);
     }
 
@@ -608,21 +1351,14 @@ mod tests {
             handle: ptr::null_mut(),
             schedule_work: Some(faulty_schedule),
         };
-        let schedule = Schedule {
-            internal: &internal,
-            phantom: PhantomData::<*const TestDropWorker>,
-        };
+        let schedule = new_schedule::<TestDropWorker>(&internal);
         let _ = schedule.schedule_work(hd);
     }
 
     #[test]
     fn respond_must_not_drop() {
         let hd = HasDrop::new(0);
-        let respond = ResponseHandler {
-            response_function: Some(extern_respond),
-            respond_handle: ptr::null_mut(),
-            phantom: PhantomData::<TestDropWorker>,
-        };
+        let respond = new_response_handler::<TestDropWorker>(Some(extern_respond));
         let _ = respond.respond(hd);
     }
 
@@ -630,18 +1366,14 @@ mod tests {
     #[should_panic(expected = "Dropped")]
     fn respond_must_enable_drop_on_error() {
         let hd = HasDrop::new(0);
-        let respond = ResponseHandler {
-            response_function: Some(faulty_respond),
-            respond_handle: ptr::null_mut(),
-            phantom: PhantomData::<TestDropWorker>,
-        };
+        let respond = new_response_handler::<TestDropWorker>(Some(faulty_respond));
         let _ = respond.respond(hd);
     }
 
     #[test]
     #[should_panic(expected = "Dropped")]
     fn extern_work_should_drop() {
-        let hd = mem::ManuallyDrop::new(HasDrop::new(0));
+        let hd = mem::ManuallyDrop::new(new_job(HasDrop::new(0)));
         let ptr_hd = &hd as *const _ as *const c_void;
         let size = mem::size_of_val(&hd) as u32;
         let mut tdw = TestDropWorker {};
@@ -661,7 +1393,7 @@ mod tests {
 
     #[test]
     fn extern_work_should_not_drop_twice() {
-        let hd = mem::ManuallyDrop::new(HasDrop::new(1));
+        let hd = mem::ManuallyDrop::new(new_job(HasDrop::new(1)));
         let ptr_hd = &hd as *const _ as *const c_void;
         let size = mem::size_of_val(&hd) as u32;
         let mut tdw = TestDropWorker {};
@@ -707,4 +1439,295 @@ mod tests {
             WorkerDescriptor::<TestDropWorker>::extern_work_response(ptr_tdw, size, ptr_hd);
         }
     }
+
+    #[test]
+    fn cancelled_work_is_flagged() {
+        let mut jobs: Vec<Vec<u8>> = Vec::new();
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: &mut jobs as *mut _ as *mut c_void,
+            schedule_work: Some(copy_schedule),
+        };
+        let schedule = new_schedule::<TestCancelWorker>(&internal);
+        let first = schedule.schedule_work(1).unwrap();
+        let second = schedule.schedule_work(2).unwrap();
+        assert_ne!(first, second);
+        schedule.cancel(first);
+
+        let mut responses: Vec<bool> = Vec::new();
+        for job in jobs.iter() {
+            unsafe {
+                WorkerDescriptor::<TestCancelWorker>::extern_work(
+                    ptr::null_mut(),
+                    Some(record_respond),
+                    &mut responses as *mut _ as *mut c_void,
+                    job.len() as u32,
+                    job.as_ptr() as *const c_void,
+                );
+            }
+        }
+        assert_eq!(responses, vec![true, false]);
+
+        // A job that shares the slot of a cancelled job isn't cancelled.
+        let cancellations = Cancellations::default();
+        let token = WorkToken { id: 3 };
+        cancellations.cancel(token);
+        assert!(cancellations.is_cancelled(token));
+        assert!(!cancellations.is_cancelled(WorkToken {
+            id: 3 + CANCELLATION_SLOTS as u64
+        }));
+    }
+
+    #[derive(FeatureCollection)]
+    struct LentFeatures<'a> {
+        schedule: Schedule<'a, TestCancelWorker>,
+    }
+
+    #[test]
+    fn state_is_shared_with_lent_schedules() {
+        let mut jobs: Vec<Vec<u8>> = Vec::new();
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: &mut jobs as *mut _ as *mut c_void,
+            schedule_work: Some(copy_schedule),
+        };
+        let feature = LV2_Feature {
+            URI: LV2_WORKER__schedule.as_ptr() as *const c_char,
+            data: &internal as *const _ as *mut c_void,
+        };
+        let features = [&feature as *const LV2_Feature, ptr::null()];
+
+        // The schedule of the instantiation and one that is lent later, like in `activate`.
+        let mut cache = unsafe { FeatureCache::from_raw(features.as_ptr()) };
+        let instantiated: Schedule<TestCancelWorker> = cache
+            .clone()
+            .retrieve_feature(ThreadingClass::Audio)
+            .unwrap();
+        let lent: LentFeatures = unsafe { cache.lend(ThreadingClass::Audio) }.unwrap();
+        let lent = lent.schedule;
+        assert!(Arc::ptr_eq(instantiated.metrics(), lent.metrics()));
+
+        let first = instantiated.schedule_work(1).unwrap();
+        let second = lent.schedule_work(2).unwrap();
+        assert_ne!(first, second);
+        instantiated.cancel(second);
+        assert_eq!(lent.metrics().statistics().scheduled, 2);
+
+        let mut responses: Vec<bool> = Vec::new();
+        for job in jobs.iter() {
+            unsafe {
+                WorkerDescriptor::<TestCancelWorker>::extern_work(
+                    ptr::null_mut(),
+                    Some(record_respond),
+                    &mut responses as *mut _ as *mut c_void,
+                    job.len() as u32,
+                    job.as_ptr() as *const c_void,
+                );
+            }
+        }
+        assert_eq!(responses, vec![false, true]);
+        assert_eq!(instantiated.metrics().statistics().completed, 2);
+
+        // Once all schedules of the feature are dropped, a new one starts with a new state.
+        let metrics = instantiated.metrics().clone();
+        drop(instantiated);
+        drop(lent);
+        let recreated = new_schedule::<TestCancelWorker>(&internal);
+        assert!(!Arc::ptr_eq(&metrics, recreated.metrics()));
+    }
+
+    #[test]
+    fn priority_is_readable_by_the_host() {
+        let mut jobs: Vec<Vec<u8>> = Vec::new();
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: &mut jobs as *mut _ as *mut c_void,
+            schedule_work: Some(copy_schedule),
+        };
+        let schedule = new_schedule::<TestCancelWorker>(&internal);
+        schedule.schedule_work(1).unwrap();
+        schedule
+            .schedule_work_with_priority(2, Priority::URGENT)
+            .unwrap();
+
+        assert_eq!(Priority::of_request(&jobs[0]), Some(Priority::NORMAL));
+        assert_eq!(Priority::of_request(&jobs[1]), Some(Priority::URGENT));
+        assert_eq!(Priority::of_request(&[0; 16]), None);
+        assert_eq!(Priority::of_request(&[]), None);
+        assert!(Priority::BACKGROUND < Priority::default());
+    }
+
+    #[test]
+    fn priority_and_metrics_share_the_schedule_state() {
+        let mut jobs: Vec<Vec<u8>> = Vec::new();
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: &mut jobs as *mut _ as *mut c_void,
+            schedule_work: Some(copy_schedule),
+        };
+        let schedule = new_schedule::<TestCancelWorker>(&internal);
+        let other = new_schedule::<TestCancelWorker>(&internal);
+
+        // A job that is scheduled with a priority can be cancelled and is counted through every schedule of the feature.
+        let token = other
+            .schedule_work_with_priority(1, Priority::URGENT)
+            .unwrap();
+        schedule.cancel(token);
+        assert!(Arc::ptr_eq(schedule.metrics(), other.metrics()));
+        assert_eq!(schedule.metrics().statistics().scheduled, 1);
+
+        let mut responses: Vec<bool> = Vec::new();
+        unsafe {
+            WorkerDescriptor::<TestCancelWorker>::extern_work(
+                ptr::null_mut(),
+                Some(record_respond),
+                &mut responses as *mut _ as *mut c_void,
+                jobs[0].len() as u32,
+                jobs[0].as_ptr() as *const c_void,
+            );
+        }
+        assert_eq!(Priority::of_request(&jobs[0]), Some(Priority::URGENT));
+        assert_eq!(responses, vec![true]);
+        assert_eq!(schedule.metrics().statistics().completed, 1);
+        assert_eq!(other.metrics().statistics().responses, 1);
+    }
+
+    #[test]
+    fn metrics_are_counted() {
+        let mut host = RejectingHost::default();
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: &mut host as *mut _ as *mut c_void,
+            schedule_work: Some(rejecting_schedule),
+        };
+        let schedule = new_schedule::<TestCancelWorker>(&internal);
+        schedule.schedule_work(1).unwrap();
+        schedule.schedule_work(2).unwrap();
+        schedule.schedule_work(3).unwrap();
+        assert_eq!(schedule.metrics().statistics().pending(), 3);
+        assert_eq!(schedule.metrics().statistics().average_latency(), None);
+
+        let mut responses: Vec<bool> = Vec::new();
+        for job in host.jobs.iter().take(2) {
+            unsafe {
+                WorkerDescriptor::<TestCancelWorker>::extern_work(
+                    ptr::null_mut(),
+                    Some(record_respond),
+                    &mut responses as *mut _ as *mut c_void,
+                    job.len() as u32,
+                    job.as_ptr() as *const c_void,
+                );
+            }
+        }
+
+        // The jobs that are rejected by the host are counted in the same metrics.
+        for status in [
+            LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+            LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE,
+            LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN,
+        ]
+        .iter()
+        {
+            host.status = *status;
+            assert!(schedule.schedule_work(4).is_err());
+        }
+        assert_eq!(host.jobs.len(), 3);
+
+        let statistics = schedule.metrics().statistics();
+        assert_eq!(statistics.scheduled, 3);
+        assert_eq!(statistics.completed, 2);
+        assert_eq!(statistics.pending(), 1);
+        assert_eq!(statistics.rejected, 2);
+        assert_eq!(statistics.failed, 1);
+        assert_eq!(statistics.responses, 2);
+        assert_eq!(statistics.dropped_responses, 0);
+        assert!(statistics.average_latency().unwrap() <= statistics.max_latency);
+        assert!(statistics
+            .to_string()
+            .starts_with("3 jobs scheduled, 1 pending, 2 rejected, 1 failed"));
+
+        schedule.metrics().reset();
+        assert_eq!(schedule.metrics().statistics(), WorkerStatistics::default());
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let mut jobs: Vec<Vec<u8>> = Vec::new();
+        let internal = lv2_sys::LV2_Worker_Schedule {
+            handle: &mut jobs as *mut _ as *mut c_void,
+            schedule_work: Some(copy_schedule),
+        };
+        let schedule = new_schedule::<TestFailWorker>(&internal);
+        schedule.schedule_work(7).unwrap();
+        schedule.schedule_work(0).unwrap();
+
+        let mut responses: Vec<bool> = Vec::new();
+        let mut work = |job: &[u8], size: usize| unsafe {
+            WorkerDescriptor::<TestFailWorker>::extern_work(
+                ptr::null_mut(),
+                Some(record_respond),
+                &mut responses as *mut _ as *mut c_void,
+                size as u32,
+                job.as_ptr() as *const c_void,
+            )
+        };
+        assert_eq!(
+            work(&jobs[0], jobs[0].len()),
+            LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN
+        );
+        assert_eq!(
+            work(&jobs[1], jobs[1].len()),
+            LV2_Worker_Status_LV2_WORKER_ERR_NO_SPACE
+        );
+        assert_eq!(
+            work(&jobs[0], jobs[0].len() - 1),
+            LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN
+        );
+
+        // A response of the wrong size is rejected before it's read.
+        let info = PluginInfo::new(TestFailWorker::uri(), std::path::Path::new("/"), 44100.0);
+        let mut plugin =
+            unsafe { EmbeddedPlugin::<TestFailWorker>::new(&info, ptr::null()) }.unwrap();
+        let response = [1u8; 4];
+        assert_eq!(
+            unsafe {
+                WorkerDescriptor::<TestFailWorker>::extern_work_response(
+                    plugin.handle(),
+                    response.len() as u32,
+                    response.as_ptr() as *const c_void,
+                )
+            },
+            LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN
+        );
+
+        assert_eq!(
+            *REPORTED_ERRORS.lock().unwrap(),
+            vec![
+                (WorkerStage::Work, WorkerError::Code(7)),
+                (WorkerStage::Work, WorkerError::NoSpace),
+                (WorkerStage::Work, WorkerError::BadData),
+                (WorkerStage::WorkResponse, WorkerError::BadData),
+            ]
+        );
+        assert_eq!(WorkerError::Code(7).to_string(), "error code 7");
+        assert_eq!(WorkerError::Message("no sample").to_string(), "no sample");
+    }
+
+    #[test]
+    fn batch_responses_of_the_wrong_size_are_not_read() {
+        let info = PluginInfo::new(TestBatchWorker::uri(), std::path::Path::new("/"), 44100.0);
+        let mut plugin =
+            unsafe { EmbeddedPlugin::<TestBatchWorker>::new(&info, ptr::null()) }.unwrap();
+
+        // Reading this as a `Box` would read past the buffer and free an invalid pointer.
+        let garbage = [0xa5u8; 3];
+        let status = unsafe {
+            BatchWorkerDescriptor::<TestBatchWorker>::extern_work_response(
+                plugin.handle(),
+                garbage.len() as u32,
+                garbage.as_ptr() as *const c_void,
+            )
+        };
+        assert_eq!(status, LV2_Worker_Status_LV2_WORKER_ERR_UNKNOWN);
+        assert_eq!(
+            *BATCH_ERRORS.lock().unwrap(),
+            vec![(WorkerStage::WorkResponse, WorkerError::BadData)]
+        );
+        assert!(plugin.plugin_mut().responses.is_empty());
+    }
 }
@@ -0,0 +1,151 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Counters that describe how the worker of a plugin is used.
+///
+/// The counters are shared between the [`Schedule`](struct.Schedule.html) feature, the scheduled jobs and their [`ResponseHandler`](struct.ResponseHandler.html)s, and they are updated automatically when work is scheduled, done and responded to. Every update is a single relaxed atomic operation, which is why the metrics are always collected, even in the `run()` context.
+///
+/// The metrics are retrieved with [`Schedule::metrics`](struct.Schedule.html#method.metrics), and since they're reference-counted, they can be handed to other parts of the plugin, like the code that sends status messages to the UI. A consistent [snapshot](#method.statistics) of the counters can be displayed or written to the log of the host.
+#[derive(Debug, Default)]
+pub struct WorkerMetrics {
+    scheduled: AtomicU64,
+    rejected: AtomicU64,
+    failed: AtomicU64,
+    completed: AtomicU64,
+    responses: AtomicU64,
+    dropped_responses: AtomicU64,
+    total_latency: AtomicU64,
+    max_latency: AtomicU64,
+}
+
+// The counters don't guard any other data, which is why relaxed ordering is sufficient.
+impl WorkerMetrics {
+    pub(crate) fn count_scheduled(&self) {
+        self.scheduled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_rejected(&self) {
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_failed(&self) {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_completed(&self, latency: Duration) {
+        let latency = latency.as_nanos().min(u64::MAX as u128) as u64;
+        self.total_latency.fetch_add(latency, Ordering::Relaxed);
+        self.max_latency.fetch_max(latency, Ordering::Relaxed);
+        self.completed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_response(&self) {
+        self.responses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count_dropped_response(&self) {
+        self.dropped_responses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Take a snapshot of the counters.
+    ///
+    /// The counters are read one after another, which means that work that is done while the snapshot is taken may only be partially included.
+    pub fn statistics(&self) -> WorkerStatistics {
+        WorkerStatistics {
+            scheduled: self.scheduled.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            completed: self.completed.load(Ordering::Relaxed),
+            responses: self.responses.load(Ordering::Relaxed),
+            dropped_responses: self.dropped_responses.load(Ordering::Relaxed),
+            total_latency: Duration::from_nanos(self.total_latency.load(Ordering::Relaxed)),
+            max_latency: Duration::from_nanos(self.max_latency.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Reset all counters to zero, for example to measure a single preset change.
+    pub fn reset(&self) {
+        for counter in [
+            &self.scheduled,
+            &self.rejected,
+            &self.failed,
+            &self.completed,
+            &self.responses,
+            &self.dropped_responses,
+            &self.total_latency,
+            &self.max_latency,
+        ]
+        .iter()
+        {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// A snapshot of the [`WorkerMetrics`](struct.WorkerMetrics.html) of a plugin.
+///
+/// The `Display` implementation writes a single line with the most important numbers, which can be written to the log of the host.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct WorkerStatistics {
+    /// The number of jobs that were accepted by the host.
+    pub scheduled: u64,
+    /// The number of jobs that were rejected because the host had no space left for them.
+    pub rejected: u64,
+    /// The number of jobs that couldn't be scheduled for other reasons, like a host without a `schedule_work` callback.
+    pub failed: u64,
+    /// The number of jobs whose `work()` call returned, successfully or not.
+    pub completed: u64,
+    /// The number of responses that were delivered to the host.
+    pub responses: u64,
+    /// The number of responses that were dropped because the host had no space left for them.
+    pub dropped_responses: u64,
+    /// The sum of the latencies of all completed jobs.
+    ///
+    /// The latency of a job is the time from scheduling it until its `work()` call returned.
+    pub total_latency: Duration,
+    /// The highest latency of a completed job.
+    pub max_latency: Duration,
+}
+
+impl WorkerStatistics {
+    /// The number of jobs that were scheduled, but aren't completed yet.
+    ///
+    /// This is the depth of the queue of the host, including the job that is currently worked on.
+    pub fn pending(&self) -> u64 {
+        self.scheduled.saturating_sub(self.completed)
+    }
+
+    /// The average latency of the completed jobs, or `None` if no job was completed yet.
+    pub fn average_latency(&self) -> Option<Duration> {
+        if self.completed == 0 {
+            None
+        } else {
+            Some(Duration::from_nanos(
+                (self.total_latency.as_nanos() / self.completed as u128) as u64,
+            ))
+        }
+    }
+}
+
+impl fmt::Display for WorkerStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} jobs scheduled, {} pending, {} rejected, {} failed, {} responses dropped",
+            self.scheduled,
+            self.pending(),
+            self.rejected,
+            self.failed,
+            self.dropped_responses
+        )?;
+        if let Some(average) = self.average_latency() {
+            write!(
+                f,
+                ", latency {:?} on average and {:?} at most",
+                average, self.max_latency
+            )?;
+        }
+        Ok(())
+    }
+}
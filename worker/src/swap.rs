@@ -0,0 +1,241 @@
+use std::fmt;
+use std::mem::ManuallyDrop;
+
+/// A cell for large objects that are created by the worker and swapped in by the `run()` context.
+///
+/// A common use of the worker is loading a sample: The worker allocates a buffer, fills it and sends it to the `run()` context in a response. Since a box is sent as a pointer, the buffer itself is never copied. The `run()` context then swaps the new buffer in, but it must not deallocate the old one, since deallocating isn't real-time safe. Instead, the old buffer has to be sent back to the worker, which drops it.
+///
+/// `RtSwapCell` enforces this with its types: [`swap`](#method.swap) returns the old object as [`Retired`](struct.Retired.html), which can't be dropped. It only releases its object with [`into_inner`](struct.Retired.html#method.into_inner), which should be called by the worker. If a retired object is dropped anyway, it's leaked instead of being deallocated.
+///
+/// # Example
+///
+/// ```
+/// use lv2_core::prelude::*;
+/// use lv2_worker::*;
+/// use urid::*;
+///
+/// #[derive(FeatureCollection)]
+/// struct AudioFeatures<'a> {
+///     schedule: Schedule<'a, Sampler>,
+/// }
+///
+/// enum Work {
+///     /// Load a sample with the given length.
+///     Load(usize),
+///     /// Deallocate a sample that is no longer used.
+///     Free(Retired<Vec<f32>>),
+/// }
+///
+/// struct Sampler {
+///     sample: RtSwapCell<Vec<f32>>,
+///     // A sample that couldn't be sent to the worker yet.
+///     pending: Option<Retired<Vec<f32>>>,
+/// }
+///
+/// unsafe impl UriBound for Sampler {
+///     const URI: &'static [u8] = b"urn:rust-lv2-example:sampler\0";
+/// }
+///
+/// impl Sampler {
+///     /// Send a retired sample to the worker, or keep it until the next cycle if that fails.
+///     fn free(&mut self, retired: Retired<Vec<f32>>, schedule: &Schedule<Self>) {
+///         match schedule.schedule_work(Work::Free(retired)) {
+///             Err(ScheduleError::Unknown(Work::Free(retired)))
+///             | Err(ScheduleError::NoSpace(Work::Free(retired)))
+///             | Err(ScheduleError::NoCallback(Work::Free(retired))) => {
+///                 self.pending = Some(retired)
+///             }
+///             _ => (),
+///         }
+///     }
+/// }
+///
+/// impl Plugin for Sampler {
+///     type Ports = ();
+///     type InitFeatures = ();
+///     type AudioFeatures = AudioFeatures<'static>;
+///
+///     fn new(_plugin_info: &PluginInfo, _features: &mut ()) -> Option<Self> {
+///         Some(Self {
+///             sample: RtSwapCell::empty(),
+///             pending: None,
+///         })
+///     }
+///
+///     fn run(&mut self, _ports: &mut (), context: &mut RunContext<AudioFeatures<'static>>) {
+///         if let Some(retired) = self.pending.take() {
+///             self.free(retired, &context.features().schedule);
+///         }
+///         if let Some(_sample) = self.sample.get() {
+///             // play the sample...
+///         }
+///     }
+/// }
+///
+/// impl Worker for Sampler {
+///     type WorkData = Work;
+///     type ResponseData = Box<Vec<f32>>;
+///
+///     fn work(response_handler: &ResponseHandler<Self>, data: Work) -> Result<(), WorkerError> {
+///         match data {
+///             Work::Load(length) => response_handler
+///                 .respond(Box::new(vec![0.0; length]))
+///                 .map_err(|_| WorkerError::Unknown),
+///             // The old sample is deallocated here, in the worker.
+///             Work::Free(retired) => {
+///                 drop(retired.into_inner());
+///                 Ok(())
+///             }
+///         }
+///     }
+///
+///     fn work_response(
+///         &mut self,
+///         data: Box<Vec<f32>>,
+///         features: &mut AudioFeatures<'static>,
+///     ) -> Result<(), WorkerError> {
+///         // Only the box is sent and swapped in, not the sample itself.
+///         if let Some(retired) = self.sample.swap(data) {
+///             self.free(retired, &features.schedule);
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub struct RtSwapCell<T> {
+    current: Option<Box<T>>,
+}
+
+impl<T> RtSwapCell<T> {
+    /// Create a cell that contains the given object.
+    pub fn new(value: Box<T>) -> Self {
+        Self {
+            current: Some(value),
+        }
+    }
+
+    /// Create an empty cell.
+    pub fn empty() -> Self {
+        Self { current: None }
+    }
+
+    /// Return a reference to the current object, if there is one.
+    pub fn get(&self) -> Option<&T> {
+        self.current.as_deref()
+    }
+
+    /// Return a mutable reference to the current object, if there is one.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.current.as_deref_mut()
+    }
+
+    /// Check whether the cell contains an object.
+    pub fn is_empty(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// Replace the current object with a new one.
+    ///
+    /// Only the boxes are moved, which makes this real-time safe. The old object is returned as `Retired`, if there was one, and should be sent to the worker to be deallocated.
+    pub fn swap(&mut self, value: Box<T>) -> Option<Retired<T>> {
+        self.current.replace(value).map(Retired::new)
+    }
+
+    /// Remove the current object from the cell.
+    ///
+    /// The object is returned as `Retired`, if there was one, and should be sent to the worker to be deallocated.
+    pub fn take(&mut self) -> Option<Retired<T>> {
+        self.current.take().map(Retired::new)
+    }
+}
+
+impl<T> Default for RtSwapCell<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RtSwapCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RtSwapCell").field(&self.current).finish()
+    }
+}
+
+/// An object that was removed from an [`RtSwapCell`](struct.RtSwapCell.html) and has to be deallocated by the worker.
+///
+/// A retired object is never deallocated when it's dropped. Instead, it has to be sent to the worker, for example as a part of the work data, and released with [`into_inner`](#method.into_inner) there. If it's dropped anyway, the object is leaked, which is better than deallocating it in the `run()` context.
+#[must_use = "Retired objects have to be sent to the worker, or they are leaked"]
+pub struct Retired<T> {
+    value: ManuallyDrop<Box<T>>,
+}
+
+impl<T> Retired<T> {
+    fn new(value: Box<T>) -> Self {
+        Self {
+            value: ManuallyDrop::new(value),
+        }
+    }
+
+    /// Return a reference to the retired object.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Release the retired object.
+    ///
+    /// This should only be called in the worker, since the returned box is deallocated normally once it's dropped.
+    pub fn into_inner(self) -> Box<T> {
+        ManuallyDrop::into_inner(self.value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Retired<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Retired").field(&self.value).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Counted(Rc<Cell<u32>>);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn retired_objects_are_not_dropped() {
+        let drops = Rc::new(Cell::new(0));
+        let mut cell = RtSwapCell::new(Box::new(Counted(drops.clone())));
+        assert!(!cell.is_empty());
+
+        // Dropping a retired object leaks it.
+        let retired = cell.swap(Box::new(Counted(drops.clone()))).unwrap();
+        drop(retired);
+        assert_eq!(drops.get(), 0);
+
+        // Releasing it allows dropping it.
+        let retired = cell.take().unwrap();
+        assert!(cell.is_empty());
+        assert!(cell.take().is_none());
+        drop(retired.into_inner());
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn swap_returns_the_old_object() {
+        let mut cell = RtSwapCell::empty();
+        assert!(cell.swap(Box::new(1)).is_none());
+        *cell.get_mut().unwrap() += 1;
+        let retired = cell.swap(Box::new(3)).unwrap();
+        assert_eq!(*retired.get(), 2);
+        assert_eq!(*retired.into_inner(), 2);
+        assert_eq!(cell.get(), Some(&3));
+    }
+}